@@ -224,7 +224,6 @@ impl NexusClient {
     /// Hit the HTTP surface for a command that has no RPC verb yet.
     /// Emits a visible warning so users know a fallback kicked in
     /// (required by the task's "no silent fallback" rule).
-    #[allow(dead_code)]
     fn warn_http_fallback(&self, command: &str) {
         if self.is_rpc() {
             eprintln!(
@@ -636,6 +635,223 @@ impl NexusClient {
             Err(anyhow!("Import failed: {}", text))
         }
     }
+
+    // ── Vector indexes (HTTP only: no RPC verbs yet) ───────────────────────
+
+    pub async fn create_vector_index(
+        &self,
+        name: &str,
+        label: &str,
+        property: &str,
+        dimensions: usize,
+        metric: &str,
+    ) -> Result<Value> {
+        let body = serde_json::json!({
+            "name": name,
+            "label": label,
+            "property": property,
+            "dimensions": dimensions,
+            "metric": metric,
+        });
+        self.vector_index_call(reqwest::Method::POST, &[], Some(body))
+            .await
+    }
+
+    pub async fn list_vector_indexes(&self) -> Result<Vec<Value>> {
+        let reply = self
+            .vector_index_call(reqwest::Method::GET, &[], None)
+            .await?;
+        Ok(reply
+            .get("indexes")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    pub async fn get_vector_index(&self, name: &str) -> Result<Value> {
+        self.vector_index_call(reqwest::Method::GET, &[name], None)
+            .await
+    }
+
+    pub async fn drop_vector_index(&self, name: &str) -> Result<Value> {
+        self.vector_index_call(reqwest::Method::DELETE, &[name], None)
+            .await
+    }
+
+    /// Upsert `(node_id, vector)` pairs; the reply lists rejected ones
+    /// under `failed`.
+    pub async fn upsert_vectors(&self, name: &str, vectors: &[(u64, Vec<f32>)]) -> Result<Value> {
+        self.vector_index_call(
+            reqwest::Method::POST,
+            &[name, "vectors"],
+            Some(node_vectors_body(vectors)),
+        )
+        .await
+    }
+
+    pub async fn delete_vector(&self, name: &str, node_id: u64) -> Result<Value> {
+        let node_id = node_id.to_string();
+        self.vector_index_call(reqwest::Method::DELETE, &[name, "vectors", &node_id], None)
+            .await
+    }
+
+    pub async fn search_vectors(
+        &self,
+        name: &str,
+        vector: &[f32],
+        k: usize,
+        mode: Option<&str>,
+        ef_search: Option<usize>,
+        measure_recall: bool,
+    ) -> Result<Value> {
+        let mut body = serde_json::json!({
+            "vector": vector,
+            "k": k,
+            "measure_recall": measure_recall,
+        });
+        if let Some(mode) = mode {
+            body["mode"] = Value::from(mode);
+        }
+        if let Some(ef_search) = ef_search {
+            body["ef_search"] = Value::from(ef_search);
+        }
+        self.vector_index_call(reqwest::Method::POST, &[name, "search"], Some(body))
+            .await
+    }
+
+    pub async fn start_vector_migration(
+        &self,
+        name: &str,
+        property: Option<&str>,
+        dimensions: Option<usize>,
+        metric: Option<&str>,
+    ) -> Result<Value> {
+        let body = serde_json::json!({
+            "property": property,
+            "dimensions": dimensions,
+            "metric": metric,
+        });
+        self.vector_index_call(reqwest::Method::POST, &[name, "migration"], Some(body))
+            .await
+    }
+
+    pub async fn get_vector_migration(&self, name: &str) -> Result<Value> {
+        self.vector_index_call(reqwest::Method::GET, &[name, "migration"], None)
+            .await
+    }
+
+    /// Upsert re-embedded `(node_id, vector)` pairs into a migration.
+    pub async fn upsert_migration_vectors(
+        &self,
+        name: &str,
+        vectors: &[(u64, Vec<f32>)],
+    ) -> Result<Value> {
+        self.vector_index_call(
+            reqwest::Method::POST,
+            &[name, "migration", "vectors"],
+            Some(node_vectors_body(vectors)),
+        )
+        .await
+    }
+
+    pub async fn complete_vector_migration(&self, name: &str, force: bool) -> Result<Value> {
+        let segments = [name, "migration", "complete"];
+        let query = if force { "?force=true" } else { "" };
+        self.vector_index_call_with_query(reqwest::Method::POST, &segments, query, None)
+            .await
+    }
+
+    pub async fn abort_vector_migration(&self, name: &str) -> Result<Value> {
+        self.vector_index_call(reqwest::Method::DELETE, &[name, "migration"], None)
+            .await
+    }
+
+    /// Call an `/admin/...` operator endpoint (cache, WAL, transactions)
+    /// and return the JSON reply, turning a non-2xx status or a populated
+    /// `error` field into an `Err`.
+    pub async fn admin_call(&self, method: reqwest::Method, path: &str) -> Result<Value> {
+        self.warn_http_fallback("admin");
+        let response = self.build_request(method, path).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP {} on {}: {}", status, path, text));
+        }
+        let reply: Value = response.json().await?;
+        if let Some(error) = reply.get("error").and_then(Value::as_str) {
+            return Err(anyhow!("{}", error));
+        }
+        Ok(reply)
+    }
+
+    /// Call `/schema/vector_indexes/<segments...>` and return the JSON
+    /// reply, turning a non-2xx status or a populated `error` field into
+    /// an `Err`.
+    async fn vector_index_call(
+        &self,
+        method: reqwest::Method,
+        segments: &[&str],
+        body: Option<Value>,
+    ) -> Result<Value> {
+        self.vector_index_call_with_query(method, segments, "", body)
+            .await
+    }
+
+    /// [`Self::vector_index_call`] with a query string (`?a=b`, or empty)
+    /// appended to the path.
+    async fn vector_index_call_with_query(
+        &self,
+        method: reqwest::Method,
+        segments: &[&str],
+        query: &str,
+        body: Option<Value>,
+    ) -> Result<Value> {
+        self.warn_http_fallback("schema vector-index");
+        let mut path = "/schema/vector_indexes".to_string();
+        for segment in segments {
+            path.push('/');
+            path.push_str(&encode_path_segment(segment));
+        }
+        path.push_str(query);
+        let mut request = self.build_request(method, &path);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP {} on {}: {}", status, path, text));
+        }
+        let reply: Value = response.json().await?;
+        if let Some(error) = reply.get("error").and_then(Value::as_str) {
+            return Err(anyhow!("{}", error));
+        }
+        Ok(reply)
+    }
+}
+
+/// `{"vectors": [{"node_id": .., "vector": [..]}, ..]}` request body.
+fn node_vectors_body(vectors: &[(u64, Vec<f32>)]) -> Value {
+    let vectors: Vec<Value> = vectors
+        .iter()
+        .map(|(node_id, vector)| serde_json::json!({"node_id": node_id, "vector": vector}))
+        .collect();
+    serde_json::json!({ "vectors": vectors })
+}
+
+/// Percent-encode one URL path segment (RFC 3986 unreserved characters
+/// pass through).
+fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
 }
 
 // ── Helpers ─────────────────────────────────────────────────────────────────
@@ -978,4 +1194,11 @@ mod tests {
         let err = nexus_to_query_result(NexusValue::Int(1)).unwrap_err();
         assert!(err.to_string().contains("must be a Map"));
     }
+
+    #[test]
+    fn encode_path_segment_escapes_reserved_bytes() {
+        assert_eq!(encode_path_segment("docs_v1.2"), "docs_v1.2");
+        assert_eq!(encode_path_segment("a b/c"), "a%20b%2Fc");
+        assert_eq!(encode_path_segment("é"), "%C3%A9");
+    }
 }
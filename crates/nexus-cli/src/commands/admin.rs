@@ -22,6 +22,82 @@ pub enum AdminCommands {
     /// Encryption-at-rest operator surface
     #[command(subcommand)]
     Encryption(EncryptionCommand),
+    /// Show the schema changelog (index, constraint and label changes)
+    SchemaLog {
+        /// Earliest change to include (RFC 3339 or epoch milliseconds)
+        #[arg(long)]
+        since: Option<String>,
+        /// Latest change to include (RFC 3339 or epoch milliseconds)
+        #[arg(long)]
+        until: Option<String>,
+        /// Print a Cypher script undoing the changes instead of listing them
+        #[arg(long)]
+        rollback: bool,
+    },
+    /// Engine cache statistics and flushing
+    #[command(subcommand)]
+    Cache(CacheCommand),
+    /// Write-ahead log state and checkpoints
+    #[command(subcommand)]
+    Wal(WalCommand),
+    /// Open explicit transactions
+    #[command(subcommand)]
+    Tx(TxCommand),
+    /// Plan regression detection across upgrades and statistics refreshes
+    #[command(subcommand)]
+    Plans(PlansCommand),
+}
+
+/// `nexus admin plans …` subcommands (`/admin/plans`).
+#[derive(Subcommand)]
+pub enum PlansCommand {
+    /// List the plan recorded for each query template
+    List,
+    /// Save the recorded plans as a new baseline
+    Baseline,
+    /// Compare a baseline with the plans chosen now; exits non-zero
+    /// when a changed plan is slower than the threshold allows
+    Check {
+        /// Baseline version (default: the newest)
+        #[arg(long)]
+        version: Option<u64>,
+        /// Relative slowdown that counts as a regression (0.25 = 25%)
+        #[arg(long, default_value_t = 0.25)]
+        threshold: f64,
+    },
+}
+
+/// `nexus admin cache …` subcommands (`/admin/cache`).
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Show page cache, cache layer and query cache statistics
+    Stats,
+    /// Drop every cache that can be rebuilt from storage
+    Flush,
+}
+
+/// `nexus admin wal …` subcommands (`/admin/wal`).
+#[derive(Subcommand)]
+pub enum WalCommand {
+    /// Show the log size and the background writer's backlog
+    Info,
+    /// Flush the record stores and write a checkpoint marker
+    Checkpoint,
+}
+
+/// `nexus admin tx …` subcommands (`/admin/transactions`).
+#[derive(Subcommand)]
+pub enum TxCommand {
+    /// List open explicit transactions, oldest first
+    List,
+    /// Roll back an open transaction; its session stays open
+    Kill {
+        /// Transaction id, as shown by `nexus admin tx list`
+        id: u64,
+        /// Skip confirmation
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 /// `nexus admin encryption …` subcommands. Today the only entry is
@@ -44,6 +120,30 @@ pub async fn execute(client: &NexusClient, args: AdminArgs, output: &OutputConte
         AdminCommands::Encryption(cmd) => match cmd {
             EncryptionCommand::Status => encryption_status(client, output).await,
         },
+        AdminCommands::SchemaLog {
+            since,
+            until,
+            rollback,
+        } => schema_log(client, since, until, rollback, output).await,
+        AdminCommands::Cache(cmd) => match cmd {
+            CacheCommand::Stats => cache_stats(client, output).await,
+            CacheCommand::Flush => cache_flush(client, output).await,
+        },
+        AdminCommands::Wal(cmd) => match cmd {
+            WalCommand::Info => wal_info(client, output).await,
+            WalCommand::Checkpoint => wal_checkpoint(client, output).await,
+        },
+        AdminCommands::Tx(cmd) => match cmd {
+            TxCommand::List => tx_list(client, output).await,
+            TxCommand::Kill { id, force } => tx_kill(client, id, force, output).await,
+        },
+        AdminCommands::Plans(cmd) => match cmd {
+            PlansCommand::List => plans_list(client, output).await,
+            PlansCommand::Baseline => plans_baseline(client, output).await,
+            PlansCommand::Check { version, threshold } => {
+                plans_check(client, version, threshold, output).await
+            }
+        },
     }
 }
 
@@ -176,6 +276,530 @@ async fn encryption_status(client: &NexusClient, output: &OutputContext) -> Resu
     Ok(())
 }
 
+/// Mirrors the server's `SchemaChange`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct SchemaChange {
+    seq: u64,
+    timestamp: i64,
+    kind: String,
+    statement: String,
+    #[serde(default)]
+    inverse: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaLogResponse {
+    #[serde(default)]
+    changes: Vec<SchemaChange>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaRollbackResponse {
+    #[serde(default)]
+    script: String,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Accept RFC 3339 or epoch milliseconds; the server is always sent
+/// milliseconds so the query string needs no escaping.
+fn parse_time_bound(value: &str) -> Result<i64> {
+    if let Ok(ms) = value.parse::<i64>() {
+        return Ok(ms);
+    }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|t| t.timestamp_millis())
+        .with_context(|| format!("invalid time '{value}': expected RFC 3339 or epoch milliseconds"))
+}
+
+async fn schema_log(
+    client: &NexusClient,
+    since: Option<String>,
+    until: Option<String>,
+    rollback: bool,
+    output: &OutputContext,
+) -> Result<()> {
+    let mut params = Vec::new();
+    if let Some(since) = since {
+        params.push(format!("since={}", parse_time_bound(&since)?));
+    }
+    if let Some(until) = until {
+        params.push(format!("until={}", parse_time_bound(&until)?));
+    }
+    let query = if params.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", params.join("&"))
+    };
+
+    if rollback {
+        let path = format!("/schema/changelog/rollback{query}");
+        let response: SchemaRollbackResponse = client
+            .get_json(&path)
+            .await
+            .with_context(|| format!("calling {path}"))?;
+        if let Some(error) = response.error {
+            anyhow::bail!(error);
+        }
+        print!("{}", response.script);
+        return Ok(());
+    }
+
+    let path = format!("/schema/changelog{query}");
+    let response: SchemaLogResponse = client
+        .get_json(&path)
+        .await
+        .with_context(|| format!("calling {path}"))?;
+    if let Some(error) = response.error {
+        anyhow::bail!(error);
+    }
+    if output.json {
+        output.print_json(&response.changes);
+        return Ok(());
+    }
+
+    let columns: Vec<String> = ["seq", "time", "kind", "statement", "inverse"]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    let rows: Vec<Vec<serde_json::Value>> = response
+        .changes
+        .into_iter()
+        .map(|change| {
+            let time = chrono::DateTime::from_timestamp_millis(change.timestamp)
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_else(|| change.timestamp.to_string());
+            vec![
+                change.seq.into(),
+                time.into(),
+                change.kind.into(),
+                change.statement.into(),
+                change
+                    .inverse
+                    .map(serde_json::Value::from)
+                    .unwrap_or(serde_json::Value::Null),
+            ]
+        })
+        .collect();
+    output.print_table(&columns, &rows);
+    Ok(())
+}
+
+/// Mirrors the server's `CacheLayerReport`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct CacheLayer {
+    layer: String,
+    entries: u64,
+    memory_bytes: u64,
+    hit_rate: f64,
+    evictions: u64,
+}
+
+/// Mirrors the server's `CacheReport`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct EngineCacheReport {
+    page_cache_pages: u64,
+    page_cache_capacity: u64,
+    page_cache_configured_capacity: u64,
+    page_cache_hits: u64,
+    page_cache_misses: u64,
+    page_cache_hit_rate: f64,
+    #[serde(default)]
+    layers: Vec<CacheLayer>,
+}
+
+/// Mirrors the server's `QueryCacheSummary`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct QueryCacheSummary {
+    lookups: u64,
+    hits: u64,
+    hit_rate: f64,
+    memory_usage_bytes: u64,
+}
+
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct CacheResponse {
+    engine: EngineCacheReport,
+    #[serde(default)]
+    query_cache: Option<QueryCacheSummary>,
+}
+
+async fn cache_stats(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::GET, "/admin/cache")
+        .await
+        .context("calling /admin/cache")?;
+    let response: CacheResponse = serde_json::from_value(reply)?;
+    if output.json {
+        output.print_json(&response);
+        return Ok(());
+    }
+
+    let engine = &response.engine;
+    println!("Page Cache");
+    println!("==========");
+    println!(
+        "Pages:     {} / {} (configured {})",
+        engine.page_cache_pages, engine.page_cache_capacity, engine.page_cache_configured_capacity
+    );
+    println!(
+        "Hit rate:  {:.1}% ({} hits, {} misses)",
+        engine.page_cache_hit_rate * 100.0,
+        engine.page_cache_hits,
+        engine.page_cache_misses
+    );
+    match &response.query_cache {
+        Some(q) => println!(
+            "Query cache: {:.1}% hit rate over {} lookups, {} bytes",
+            q.hit_rate * 100.0,
+            q.lookups,
+            q.memory_usage_bytes
+        ),
+        None => println!("Query cache: disabled"),
+    }
+    if !engine.layers.is_empty() {
+        println!();
+        let columns: Vec<String> = ["layer", "entries", "memory_bytes", "hit_rate", "evictions"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let rows: Vec<Vec<serde_json::Value>> = engine
+            .layers
+            .iter()
+            .map(|l| {
+                vec![
+                    l.layer.clone().into(),
+                    l.entries.into(),
+                    l.memory_bytes.into(),
+                    format!("{:.1}%", l.hit_rate * 100.0).into(),
+                    l.evictions.into(),
+                ]
+            })
+            .collect();
+        output.print_table(&columns, &rows);
+    }
+    Ok(())
+}
+
+async fn cache_flush(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::POST, "/admin/cache/flush")
+        .await
+        .context("calling /admin/cache/flush")?;
+    if output.json {
+        output.print_json(&reply);
+        return Ok(());
+    }
+    let pages = reply["flushed"]["pages_dropped"].as_u64().unwrap_or(0);
+    output.print_success(&format!("Caches flushed ({pages} pages dropped)"));
+    Ok(())
+}
+
+/// Mirrors the server's `WalInfo`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct WalInfo {
+    path: String,
+    file_bytes: u64,
+    entries_written: u64,
+    pending_entries: u64,
+    write_errors: u64,
+    current_epoch: u64,
+    #[serde(default)]
+    last_truncated_unix_ms: Option<u64>,
+}
+
+async fn wal_info(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::GET, "/admin/wal")
+        .await
+        .context("calling /admin/wal")?;
+    let wal: WalInfo = serde_json::from_value(reply["wal"].clone())?;
+    if output.json {
+        output.print_json(&wal);
+        return Ok(());
+    }
+
+    println!("Write-Ahead Log");
+    println!("===============");
+    println!("Path:            {}", wal.path);
+    println!("Size:            {} bytes", wal.file_bytes);
+    println!("Entries written: {}", wal.entries_written);
+    println!("Pending:         {}", wal.pending_entries);
+    println!("Write errors:    {}", wal.write_errors);
+    println!("Current epoch:   {}", wal.current_epoch);
+    if let Some(ms) = wal.last_truncated_unix_ms {
+        let time = chrono::DateTime::from_timestamp_millis(ms as i64)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_else(|| ms.to_string());
+        println!("Last truncated:  {time}");
+    }
+    Ok(())
+}
+
+async fn wal_checkpoint(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::POST, "/admin/wal/checkpoint")
+        .await
+        .context("calling /admin/wal/checkpoint")?;
+    if output.json {
+        output.print_json(&reply["checkpoint"]);
+        return Ok(());
+    }
+    let epoch = reply["checkpoint"]["epoch"].as_u64().unwrap_or(0);
+    output.print_success(&format!("Checkpoint written at epoch {epoch}"));
+    Ok(())
+}
+
+/// Mirrors the server's `OpenTransaction`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct OpenTransaction {
+    tx_id: u64,
+    session_id: String,
+    database: String,
+    epoch: u64,
+    idle_ms: u64,
+    created_nodes: u64,
+    created_relationships: u64,
+}
+
+async fn tx_list(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::GET, "/admin/transactions")
+        .await
+        .context("calling /admin/transactions")?;
+    let transactions: Vec<OpenTransaction> = serde_json::from_value(reply["transactions"].clone())?;
+    if output.json {
+        output.print_json(&transactions);
+        return Ok(());
+    }
+    if transactions.is_empty() {
+        output.print_info("No open transactions");
+        return Ok(());
+    }
+
+    let columns: Vec<String> = [
+        "tx_id",
+        "session",
+        "database",
+        "epoch",
+        "idle",
+        "created_nodes",
+        "created_rels",
+    ]
+    .iter()
+    .map(|c| c.to_string())
+    .collect();
+    let rows: Vec<Vec<serde_json::Value>> = transactions
+        .into_iter()
+        .map(|tx| {
+            vec![
+                tx.tx_id.into(),
+                tx.session_id.into(),
+                tx.database.into(),
+                tx.epoch.into(),
+                format!("{:.1}s", tx.idle_ms as f64 / 1000.0).into(),
+                tx.created_nodes.into(),
+                tx.created_relationships.into(),
+            ]
+        })
+        .collect();
+    output.print_table(&columns, &rows);
+    Ok(())
+}
+
+async fn tx_kill(client: &NexusClient, id: u64, force: bool, output: &OutputContext) -> Result<()> {
+    if !force {
+        use dialoguer::Confirm;
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Roll back transaction {id}? Its uncommitted writes are lost."
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            output.print_info("Operation cancelled");
+            return Ok(());
+        }
+    }
+
+    let path = format!("/admin/transactions/{id}");
+    let reply = client
+        .admin_call(reqwest::Method::DELETE, &path)
+        .await
+        .with_context(|| format!("calling {path}"))?;
+    if output.json {
+        output.print_json(&reply["killed"]);
+        return Ok(());
+    }
+    let session = reply["killed"]["session_id"].as_str().unwrap_or("?");
+    output.print_success(&format!(
+        "Transaction {id} rolled back (session '{session}')"
+    ));
+    Ok(())
+}
+
+/// Mirrors the server's `PlanRecord`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct PlanRecord {
+    template: String,
+    plan: Vec<String>,
+    executions: u64,
+    total_ms: f64,
+    plan_changes: u64,
+}
+
+async fn plans_list(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::GET, "/admin/plans")
+        .await
+        .context("calling /admin/plans")?;
+    let plans: Vec<PlanRecord> = serde_json::from_value(reply["plans"].clone())?;
+    if output.json {
+        output.print_json(&plans);
+        return Ok(());
+    }
+    if plans.is_empty() {
+        output.print_info("No plans recorded (is server.plan_history.enabled set?)");
+        return Ok(());
+    }
+
+    let columns: Vec<String> = [
+        "template",
+        "operators",
+        "executions",
+        "mean",
+        "plan_changes",
+    ]
+    .iter()
+    .map(|c| c.to_string())
+    .collect();
+    let rows: Vec<Vec<serde_json::Value>> = plans
+        .into_iter()
+        .map(|p| {
+            let mean = if p.executions == 0 {
+                0.0
+            } else {
+                p.total_ms / p.executions as f64
+            };
+            vec![
+                p.template.into(),
+                p.plan.len().into(),
+                p.executions.into(),
+                format!("{mean:.2}ms").into(),
+                p.plan_changes.into(),
+            ]
+        })
+        .collect();
+    output.print_table(&columns, &rows);
+    Ok(())
+}
+
+async fn plans_baseline(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let reply = client
+        .admin_call(reqwest::Method::POST, "/admin/plans/baseline")
+        .await
+        .context("calling /admin/plans/baseline")?;
+    if output.json {
+        output.print_json(&reply["baseline"]);
+        return Ok(());
+    }
+    let baseline = &reply["baseline"];
+    output.print_success(&format!(
+        "Plan baseline v{} saved ({} templates, nexus {})",
+        baseline["version"].as_u64().unwrap_or(0),
+        baseline["templates"].as_u64().unwrap_or(0),
+        baseline["nexus_version"].as_str().unwrap_or("?")
+    ));
+    Ok(())
+}
+
+/// Mirrors the server's `PlanChange`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct PlanChange {
+    template: String,
+    baseline_plan: Vec<String>,
+    current_plan: Vec<String>,
+    baseline_mean_ms: f64,
+    current_mean_ms: Option<f64>,
+    error: Option<String>,
+    regressed: bool,
+}
+
+/// Mirrors the server's `PlanComparison`.
+#[derive(Debug, Deserialize, serde::Serialize)]
+struct PlanComparison {
+    baseline_version: u64,
+    baseline_nexus_version: String,
+    nexus_version: String,
+    threshold: f64,
+    compared: usize,
+    changed: usize,
+    regressed: usize,
+    changes: Vec<PlanChange>,
+}
+
+async fn plans_check(
+    client: &NexusClient,
+    version: Option<u64>,
+    threshold: f64,
+    output: &OutputContext,
+) -> Result<()> {
+    let mut path = format!("/admin/plans/regressions?threshold={threshold}");
+    if let Some(version) = version {
+        path.push_str(&format!("&version={version}"));
+    }
+    let reply = client
+        .admin_call(reqwest::Method::GET, &path)
+        .await
+        .context("calling /admin/plans/regressions")?;
+    let comparison: PlanComparison = serde_json::from_value(reply["comparison"].clone())?;
+
+    if output.json {
+        output.print_json(&comparison);
+    } else {
+        println!(
+            "Baseline v{} (nexus {}) vs nexus {}: {} templates, {} changed, {} regressed",
+            comparison.baseline_version,
+            comparison.baseline_nexus_version,
+            comparison.nexus_version,
+            comparison.compared,
+            comparison.changed,
+            comparison.regressed
+        );
+        for change in &comparison.changes {
+            let status = if change.regressed {
+                "REGRESSED"
+            } else {
+                "changed"
+            };
+            let current = change
+                .current_mean_ms
+                .map(|ms| format!("{ms:.2}ms"))
+                .unwrap_or_else(|| "not run since".to_string());
+            println!();
+            println!("[{status}] {}", change.template);
+            println!("  latency: {:.2}ms -> {current}", change.baseline_mean_ms);
+            println!("  before:  {}", change.baseline_plan.join(" | "));
+            match &change.error {
+                Some(error) => println!("  after:   does not plan: {error}"),
+                None => println!("  after:   {}", change.current_plan.join(" | ")),
+            }
+        }
+    }
+
+    if comparison.regressed > 0 {
+        anyhow::bail!(
+            "{} plan regression(s) beyond a {:.0}% slowdown",
+            comparison.regressed,
+            comparison.threshold * 100.0
+        );
+    }
+    Ok(())
+}
+
 async fn show_stats(client: &NexusClient, output: &OutputContext) -> Result<()> {
     let stats = client.stats().await?;
 
@@ -20,9 +20,16 @@ pub enum DataCommands {
         /// Format (json, csv, cypher)
         #[arg(short, long, default_value = "json")]
         format: String,
-        /// Batch size
+        /// Rows sent to the server per request
         #[arg(short, long, default_value = "1000")]
         batch_size: usize,
+        /// Continue an interrupted import from its checkpoint, skipping
+        /// the rows already committed
+        #[arg(long)]
+        resume: bool,
+        /// Checkpoint file (default: `<file>.import-checkpoint.json`)
+        #[arg(long)]
+        checkpoint: Option<String>,
     },
     /// Export data to a file
     Export {
@@ -62,7 +69,20 @@ pub async fn execute(client: &NexusClient, args: DataArgs, output: &OutputContex
             file,
             format,
             batch_size,
-        } => import_data(client, &file, &format, batch_size, output).await,
+            resume,
+            checkpoint,
+        } => {
+            import_data(
+                client,
+                &file,
+                &format,
+                batch_size,
+                resume,
+                checkpoint.as_deref(),
+                output,
+            )
+            .await
+        }
         DataCommands::Export { file, format } => export_data(client, &file, &format, output).await,
         DataCommands::Backup {
             destination,
@@ -79,22 +99,95 @@ async fn import_data(
     client: &NexusClient,
     file: &str,
     format: &str,
-    _batch_size: usize,
+    batch_size: usize,
+    resume: bool,
+    checkpoint: Option<&str>,
     output: &OutputContext,
 ) -> Result<()> {
-    use super::create_spinner;
+    use super::create_progress_bar;
+    use crate::import::{ImportCheckpoint, ImportRows, fingerprint};
+    use chrono::Utc;
+    use std::path::PathBuf;
 
-    let spinner = create_spinner(&format!("Reading {}...", file));
+    if batch_size == 0 {
+        anyhow::bail!("--batch-size must be at least 1");
+    }
 
     let content = fs::read_to_string(file)?;
-    spinner.set_message("Importing data...".to_string());
+    let rows = ImportRows::parse(&content, format)?;
+    let fingerprint = fingerprint(&content);
+    let total_rows = rows.len() as u64;
+    let checkpoint_path = checkpoint
+        .map(PathBuf::from)
+        .unwrap_or_else(|| ImportCheckpoint::default_path(file));
+
+    // Never start over on top of a half-finished import by accident:
+    // that would ingest the committed rows a second time.
+    let mut state = if resume {
+        let state = ImportCheckpoint::load(&checkpoint_path)?;
+        state.check_matches(format, &fingerprint, total_rows)?;
+        output.print_info(&format!(
+            "Resuming import of {}: {} of {} rows already committed",
+            file, state.rows_committed, total_rows
+        ));
+        state
+    } else {
+        if checkpoint_path.exists() {
+            anyhow::bail!(
+                "Checkpoint {} exists from an interrupted import; pass --resume to continue \
+                 it or delete the file to start over",
+                checkpoint_path.display()
+            );
+        }
+        ImportCheckpoint {
+            file: file.to_string(),
+            format: format.to_ascii_lowercase(),
+            fingerprint,
+            total_rows,
+            rows_committed: 0,
+            batches_committed: 0,
+            updated_at: Utc::now().to_rfc3339(),
+        }
+    };
 
-    client.import_data(&content, format).await?;
+    if rows.is_empty() {
+        output.print_info(&format!("{} contains no rows to import", file));
+        return Ok(());
+    }
 
-    spinner.finish_and_clear();
+    let progress = create_progress_bar(total_rows, &format!("Importing {}", file));
+    progress.set_position(state.rows_committed);
+    // Rate and ETA should reflect this run, not the rows skipped on
+    // resume.
+    progress.reset_eta();
+
+    let mut start = state.rows_committed as usize;
+    while start < rows.len() {
+        let end = (start + batch_size).min(rows.len());
+        if let Err(e) = client.import_data(&rows.payload(start..end), format).await {
+            progress.abandon();
+            anyhow::bail!(
+                "Import failed after {} of {} rows: {}\nRerun with --resume to continue \
+                 from the last committed batch (checkpoint: {})",
+                state.rows_committed,
+                total_rows,
+                e,
+                checkpoint_path.display()
+            );
+        }
+        state.rows_committed = end as u64;
+        state.batches_committed += 1;
+        state.updated_at = Utc::now().to_rfc3339();
+        state.save(&checkpoint_path)?;
+        progress.set_position(state.rows_committed);
+        start = end;
+    }
+    progress.finish_and_clear();
+
+    fs::remove_file(&checkpoint_path)?;
     output.print_success(&format!(
-        "Data imported successfully from {} ({} format)",
-        file, format
+        "Imported {} rows from {} ({} format, {} batches)",
+        total_rows, file, format, state.batches_committed
     ));
     Ok(())
 }
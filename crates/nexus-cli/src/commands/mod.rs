@@ -42,15 +42,13 @@ pub fn create_spinner(message: &str) -> ProgressBar {
     spinner
 }
 
-/// Creates a progress bar with the given length. Exposed for future
-/// bulk-ingest wiring; callers will re-enable once the long-operation
-/// commands land.
-#[allow(dead_code)]
+/// Creates a progress bar with the given length, showing the rate and
+/// ETA alongside the position.
 pub fn create_progress_bar(len: u64, message: &str) -> ProgressBar {
     let pb = ProgressBar::new(len);
     pb.set_style(
         ProgressStyle::default_bar()
-            .template("{msg}\n{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
+            .template("{msg}\n{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({per_sec}, ETA {eta})")
             // See `create_spinner` — compile-time literal template.
             .expect("progress-bar template is a valid compile-time literal")
             .progress_chars("█▓▒░"),
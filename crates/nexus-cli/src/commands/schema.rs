@@ -28,6 +28,11 @@ pub enum SchemaCommands {
         #[command(subcommand)]
         command: IndexesCommands,
     },
+    /// Manage named vector (KNN) indexes
+    VectorIndex {
+        #[command(subcommand)]
+        command: VectorIndexCommands,
+    },
 }
 
 #[derive(Subcommand)]
@@ -94,6 +99,138 @@ pub enum IndexesCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum VectorIndexCommands {
+    /// List vector indexes with their stats
+    List,
+    /// Show one vector index
+    Show {
+        /// Index name
+        name: String,
+    },
+    /// Create a vector index over a label's embedding property
+    Create {
+        /// Index name
+        name: String,
+        /// Label whose nodes are indexed
+        #[arg(short, long)]
+        label: String,
+        /// Property holding the embeddings
+        #[arg(short, long)]
+        property: String,
+        /// Embedding length
+        #[arg(short, long)]
+        dimensions: usize,
+        /// Distance function (cosine, euclidean)
+        #[arg(short, long, default_value = "cosine")]
+        metric: String,
+    },
+    /// Drop a vector index (embeddings stay on the nodes)
+    Drop {
+        /// Index name
+        name: String,
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Insert or replace node vectors
+    Insert {
+        /// Index name
+        name: String,
+        /// Node ID (with --vector)
+        #[arg(short, long, requires = "vector", conflicts_with = "file")]
+        node: Option<u64>,
+        /// Comma-separated embedding (with --node)
+        #[arg(short, long, requires = "node")]
+        vector: Option<String>,
+        /// JSON file holding `[{"node_id": .., "vector": [..]}, ..]`
+        #[arg(short, long, required_unless_present = "node")]
+        file: Option<String>,
+    },
+    /// Remove a node's vector
+    Delete {
+        /// Index name
+        name: String,
+        /// Node ID
+        node: u64,
+    },
+    /// Find the nearest neighbours of a vector
+    Search {
+        /// Index name
+        name: String,
+        /// Comma-separated query embedding
+        #[arg(short, long)]
+        vector: String,
+        /// Neighbours to return
+        #[arg(short, long, default_value = "10")]
+        k: usize,
+        /// Search mode: auto, approximate or exact
+        #[arg(short, long)]
+        mode: Option<String>,
+        /// HNSW candidate list size for approximate searches
+        #[arg(long)]
+        ef: Option<usize>,
+        /// Run both searches and report the approximate search's recall
+        #[arg(long)]
+        recall: bool,
+    },
+    /// Migrate an index to new dimensions, metric or property
+    Migrate {
+        #[command(subcommand)]
+        command: VectorMigrationCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum VectorMigrationCommands {
+    /// Start a migration; node writes feed both indexes until it ends
+    Start {
+        /// Index name
+        name: String,
+        /// New embedding property
+        #[arg(short, long)]
+        property: Option<String>,
+        /// New embedding length
+        #[arg(short, long)]
+        dimensions: Option<usize>,
+        /// New distance function (cosine, euclidean)
+        #[arg(short, long)]
+        metric: Option<String>,
+    },
+    /// Show a migration and how many nodes are left to re-embed
+    Status {
+        /// Index name
+        name: String,
+    },
+    /// Write re-embedded node vectors
+    Insert {
+        /// Index name
+        name: String,
+        /// Node ID (with --vector)
+        #[arg(short, long, requires = "vector", conflicts_with = "file")]
+        node: Option<u64>,
+        /// Comma-separated embedding (with --node)
+        #[arg(short, long, requires = "node")]
+        vector: Option<String>,
+        /// JSON file holding `[{"node_id": .., "vector": [..]}, ..]`
+        #[arg(short, long, required_unless_present = "node")]
+        file: Option<String>,
+    },
+    /// Swap the migrated index in as the live one
+    Complete {
+        /// Index name
+        name: String,
+        /// Swap even if some nodes are not re-embedded yet
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Abort a migration, keeping the live index
+    Abort {
+        /// Index name
+        name: String,
+    },
+}
+
 pub async fn execute(client: &NexusClient, args: SchemaArgs, output: &OutputContext) -> Result<()> {
     match args.command {
         SchemaCommands::Labels { command } => match command {
@@ -121,6 +258,60 @@ pub async fn execute(client: &NexusClient, args: SchemaArgs, output: &OutputCont
                 delete_index(client, &name, force, output).await
             }
         },
+        SchemaCommands::VectorIndex { command } => match command {
+            VectorIndexCommands::List => list_vector_indexes(client, output).await,
+            VectorIndexCommands::Show { name } => show_vector_index(client, &name, output).await,
+            VectorIndexCommands::Create {
+                name,
+                label,
+                property,
+                dimensions,
+                metric,
+            } => {
+                let reply = client
+                    .create_vector_index(&name, &label, &property, dimensions, &metric)
+                    .await?;
+                print_reply(&reply, output);
+                Ok(())
+            }
+            VectorIndexCommands::Drop { name, force } => {
+                drop_vector_index(client, &name, force, output).await
+            }
+            VectorIndexCommands::Insert {
+                name,
+                node,
+                vector,
+                file,
+            } => {
+                let vectors = read_node_vectors(node, vector, file)?;
+                let reply = client.upsert_vectors(&name, &vectors).await?;
+                print_upsert_reply(&reply, output);
+                Ok(())
+            }
+            VectorIndexCommands::Delete { name, node } => {
+                let reply = client.delete_vector(&name, node).await?;
+                print_reply(&reply, output);
+                Ok(())
+            }
+            VectorIndexCommands::Search {
+                name,
+                vector,
+                k,
+                mode,
+                ef,
+                recall,
+            } => {
+                let query = parse_vector(&vector)?;
+                let reply = client
+                    .search_vectors(&name, &query, k, mode.as_deref(), ef, recall)
+                    .await?;
+                print_search_results(&reply, output);
+                Ok(())
+            }
+            VectorIndexCommands::Migrate { command } => {
+                migrate_vector_index(client, command, output).await
+            }
+        },
     }
 }
 
@@ -346,3 +537,224 @@ async fn delete_index(
     output.print_success(&format!("Index '{}' deleted", name));
     Ok(())
 }
+
+async fn list_vector_indexes(client: &NexusClient, output: &OutputContext) -> Result<()> {
+    let indexes = client.list_vector_indexes().await?;
+
+    if output.json {
+        output.print_json(&indexes);
+        return Ok(());
+    }
+
+    if indexes.is_empty() {
+        output.print_info("No vector indexes found");
+        return Ok(());
+    }
+
+    let columns: Vec<String> = VECTOR_INDEX_COLUMNS.iter().map(|c| c.to_string()).collect();
+    let rows: Vec<Vec<Value>> = indexes.iter().map(vector_index_row).collect();
+    output.print_table(&columns, &rows);
+    output.print_info(&format!("{} vector index(es) found", indexes.len()));
+
+    Ok(())
+}
+
+async fn show_vector_index(client: &NexusClient, name: &str, output: &OutputContext) -> Result<()> {
+    let reply = client.get_vector_index(name).await?;
+    let index = reply.get("index").cloned().unwrap_or(Value::Null);
+
+    if output.json {
+        output.print_json(&index);
+        return Ok(());
+    }
+
+    let columns: Vec<String> = VECTOR_INDEX_COLUMNS.iter().map(|c| c.to_string()).collect();
+    output.print_table(&columns, &[vector_index_row(&index)]);
+    Ok(())
+}
+
+async fn drop_vector_index(
+    client: &NexusClient,
+    name: &str,
+    force: bool,
+    output: &OutputContext,
+) -> Result<()> {
+    if !force {
+        use dialoguer::Confirm;
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Are you sure you want to drop vector index '{}'?",
+                name
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            output.print_info("Operation cancelled");
+            return Ok(());
+        }
+    }
+
+    let reply = client.drop_vector_index(name).await?;
+    print_reply(&reply, output);
+    Ok(())
+}
+
+/// The vectors to write, from `--node`/`--vector` or `--file`.
+fn read_node_vectors(
+    node: Option<u64>,
+    vector: Option<String>,
+    file: Option<String>,
+) -> Result<Vec<(u64, Vec<f32>)>> {
+    Ok(match (node, vector, file) {
+        (Some(node), Some(vector), _) => vec![(node, parse_vector(&vector)?)],
+        (_, _, Some(file)) => {
+            #[derive(serde::Deserialize)]
+            struct Entry {
+                node_id: u64,
+                vector: Vec<f32>,
+            }
+            let content = std::fs::read_to_string(&file)
+                .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", file, e))?;
+            let entries: Vec<Entry> = serde_json::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("{} is not a vector list: {}", file, e))?;
+            entries.into_iter().map(|e| (e.node_id, e.vector)).collect()
+        }
+        _ => anyhow::bail!("Pass --node with --vector, or --file"),
+    })
+}
+
+fn print_upsert_reply(reply: &Value, output: &OutputContext) {
+    if output.json {
+        output.print_json(reply);
+        return;
+    }
+    output.print_success(reply["message"].as_str().unwrap_or_default());
+    for failure in reply["failed"].as_array().into_iter().flatten() {
+        output.print_error(&format!(
+            "node {}: {}",
+            failure["node_id"],
+            failure["error"].as_str().unwrap_or_default()
+        ));
+    }
+}
+
+async fn migrate_vector_index(
+    client: &NexusClient,
+    command: VectorMigrationCommands,
+    output: &OutputContext,
+) -> Result<()> {
+    let reply = match command {
+        VectorMigrationCommands::Start {
+            name,
+            property,
+            dimensions,
+            metric,
+        } => {
+            client
+                .start_vector_migration(&name, property.as_deref(), dimensions, metric.as_deref())
+                .await?
+        }
+        VectorMigrationCommands::Status { name } => client.get_vector_migration(&name).await?,
+        VectorMigrationCommands::Insert {
+            name,
+            node,
+            vector,
+            file,
+        } => {
+            let vectors = read_node_vectors(node, vector, file)?;
+            let reply = client.upsert_migration_vectors(&name, &vectors).await?;
+            print_upsert_reply(&reply, output);
+            return Ok(());
+        }
+        VectorMigrationCommands::Complete { name, force } => {
+            client.complete_vector_migration(&name, force).await?
+        }
+        VectorMigrationCommands::Abort { name } => client.abort_vector_migration(&name).await?,
+    };
+    if output.json {
+        output.print_json(&reply);
+        return Ok(());
+    }
+    output.print_success(reply["message"].as_str().unwrap_or_default());
+    if let Some(migration) = reply.get("migration").filter(|m| !m.is_null()) {
+        let columns: Vec<String> = VECTOR_INDEX_COLUMNS.iter().map(|c| c.to_string()).collect();
+        output.print_table(
+            &columns,
+            &[
+                vector_index_row(&migration["source"]),
+                vector_index_row(&migration["target"]),
+            ],
+        );
+    }
+    Ok(())
+}
+
+fn print_search_results(reply: &Value, output: &OutputContext) {
+    let results = reply["results"].as_array().cloned().unwrap_or_default();
+
+    if output.json {
+        output.print_json(&results);
+        return;
+    }
+
+    let columns = vec!["Node".to_string(), "Score".to_string()];
+    let rows: Vec<Vec<Value>> = results
+        .iter()
+        .map(|hit| vec![hit["node_id"].clone(), hit["score"].clone()])
+        .collect();
+    output.print_table(&columns, &rows);
+    let mode = reply["mode"].as_str().unwrap_or("approximate");
+    match reply["recall"].as_f64() {
+        Some(recall) => output.print_info(&format!(
+            "{} neighbour(s) found ({mode} search, recall {recall:.3})",
+            rows.len()
+        )),
+        None => output.print_info(&format!(
+            "{} neighbour(s) found ({mode} search)",
+            rows.len()
+        )),
+    }
+}
+
+const VECTOR_INDEX_COLUMNS: [&str; 6] = [
+    "Name",
+    "Label",
+    "Property",
+    "Dimensions",
+    "Metric",
+    "Vectors",
+];
+
+fn vector_index_row(index: &Value) -> Vec<Value> {
+    [
+        "name",
+        "label",
+        "property",
+        "dimensions",
+        "metric",
+        "vectors",
+    ]
+    .iter()
+    .map(|field| index[*field].clone())
+    .collect()
+}
+
+fn print_reply(reply: &Value, output: &OutputContext) {
+    if output.json {
+        output.print_json(reply);
+    } else {
+        output.print_success(reply["message"].as_str().unwrap_or_default());
+    }
+}
+
+/// Parse a comma-separated embedding such as `0.1, 0.2,-0.3`.
+fn parse_vector(raw: &str) -> Result<Vec<f32>> {
+    raw.split(',')
+        .map(|x| {
+            x.trim()
+                .parse::<f32>()
+                .map_err(|_| anyhow::anyhow!("Invalid vector component {:?}", x.trim()))
+        })
+        .collect()
+}
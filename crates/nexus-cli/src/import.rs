@@ -0,0 +1,308 @@
+//! Batching and checkpointing for `nexus data import`.
+//!
+//! An import file is split into rows — array elements for JSON,
+//! records for CSV (quoted fields may span lines), `;`-terminated
+//! statements for Cypher — and sent to the server a batch at a time.
+//! After every committed batch the number of rows ingested so far is
+//! written to a checkpoint file next to the input. `--resume` reads it
+//! back and skips exactly that many rows, so an interrupted import
+//! continues where it stopped without ingesting anything twice. The
+//! checkpoint carries a fingerprint of the input file and is refused if
+//! the file has changed since; it is deleted once the import completes.
+//!
+//! A batch counts as committed once the server has acknowledged it. If
+//! the connection drops after the server applied a batch but before the
+//! reply arrived, that one batch is sent again on resume.
+
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Suffix appended to the input path to name the default checkpoint.
+pub const CHECKPOINT_SUFFIX: &str = ".import-checkpoint.json";
+
+/// An import file split into independently importable rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportRows {
+    format: String,
+    /// CSV header line, repeated at the top of every batch.
+    header: Option<String>,
+    rows: Vec<String>,
+}
+
+impl ImportRows {
+    /// Split `content` according to `format` (`json`, `csv` or
+    /// `cypher`).
+    pub fn parse(content: &str, format: &str) -> Result<Self> {
+        let format = format.to_ascii_lowercase();
+        let (header, rows) = match format.as_str() {
+            "json" => {
+                let value: serde_json::Value = serde_json::from_str(content)
+                    .map_err(|e| anyhow!("Import file is not valid JSON: {}", e))?;
+                let serde_json::Value::Array(items) = value else {
+                    bail!("JSON import expects an array of node objects");
+                };
+                let rows = items
+                    .iter()
+                    .map(serde_json::to_string)
+                    .collect::<serde_json::Result<_>>()?;
+                (None, rows)
+            }
+            "csv" => {
+                let mut records = split_csv_records(content).into_iter();
+                let header = records
+                    .next()
+                    .ok_or_else(|| anyhow!("CSV import file has no header line"))?;
+                (Some(header), records.collect())
+            }
+            "cypher" => (None, split_cypher_statements(content)),
+            other => bail!(
+                "Unsupported import format '{}' (expected json, csv or cypher)",
+                other
+            ),
+        };
+        Ok(Self {
+            format,
+            header,
+            rows,
+        })
+    }
+
+    /// Number of rows in the file.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the file holds no rows at all.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Import payload for `rows[range]`, in the file's own format.
+    pub fn payload(&self, range: std::ops::Range<usize>) -> String {
+        let rows = &self.rows[range];
+        match self.format.as_str() {
+            "json" => format!("[{}]", rows.join(",")),
+            "csv" => {
+                let mut out = self.header.clone().unwrap_or_default();
+                for row in rows {
+                    out.push('\n');
+                    out.push_str(row);
+                }
+                out.push('\n');
+                out
+            }
+            _ => rows.iter().map(|s| format!("{};\n", s)).collect(),
+        }
+    }
+}
+
+/// Progress of an interrupted import, persisted after every batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportCheckpoint {
+    /// Input file as given on the command line.
+    pub file: String,
+    /// Import format.
+    pub format: String,
+    /// Fingerprint of the input file content (see [`fingerprint`]).
+    pub fingerprint: String,
+    /// Rows in the input file.
+    pub total_rows: u64,
+    /// Rows committed so far; a resumed import starts after them.
+    pub rows_committed: u64,
+    /// Batches committed so far.
+    pub batches_committed: u64,
+    /// When the last batch was committed (RFC 3339).
+    pub updated_at: String,
+}
+
+impl ImportCheckpoint {
+    /// Checkpoint path used when `--checkpoint` is not given.
+    pub fn default_path(file: &str) -> PathBuf {
+        PathBuf::from(format!("{}{}", file, CHECKPOINT_SUFFIX))
+    }
+
+    /// Read a checkpoint written by [`Self::save`].
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read checkpoint {}: {}", path.display(), e))?;
+        serde_json::from_str(&raw)
+            .map_err(|e| anyhow!("Checkpoint {} is corrupt: {}", path.display(), e))
+    }
+
+    /// Persist the checkpoint. Written to a temporary file and renamed
+    /// into place, so a crash mid-write leaves the previous checkpoint
+    /// intact.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Refuse to resume against a different file or format than the one
+    /// the checkpoint was taken for.
+    pub fn check_matches(&self, format: &str, fingerprint: &str, total_rows: u64) -> Result<()> {
+        if !self.format.eq_ignore_ascii_case(format) {
+            bail!(
+                "Checkpoint was written for a {} import, not {}",
+                self.format,
+                format
+            );
+        }
+        if self.fingerprint != fingerprint || self.total_rows != total_rows {
+            bail!(
+                "Input file {} has changed since the checkpoint was written; \
+                 delete the checkpoint to start over",
+                self.file
+            );
+        }
+        if self.rows_committed > total_rows {
+            bail!(
+                "Checkpoint records {} committed rows but the file only has {}",
+                self.rows_committed,
+                total_rows
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Stable 64-bit FNV-1a fingerprint of `content`, as 16 hex digits.
+pub fn fingerprint(content: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Split CSV text into records. A newline inside a double-quoted field
+/// belongs to the field; blank lines are dropped.
+fn split_csv_records(content: &str) -> Vec<String> {
+    let mut records = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in content.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            '\n' if !in_quotes => {
+                let record = current.trim_end_matches('\r');
+                if !record.is_empty() {
+                    records.push(record.to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    let record = current.trim_end_matches('\r');
+    if !record.is_empty() {
+        records.push(record.to_string());
+    }
+    records
+}
+
+/// Split a Cypher script into statements on `;` outside string
+/// literals and backtick-quoted names. Empty statements are dropped.
+fn split_cypher_statements(content: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for c in content.chars() {
+        if let Some(q) = quote {
+            current.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' && q != '`' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            ';' => {
+                if !current.trim().is_empty() {
+                    statements.push(current.trim().to_string());
+                }
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        statements.push(current.trim().to_string());
+    }
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_rows_are_array_elements() {
+        let rows = ImportRows::parse(r#"[{"a":1},{"a":2},{"a":3}]"#, "json").unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.payload(1..3), r#"[{"a":2},{"a":3}]"#);
+        assert!(ImportRows::parse(r#"{"a":1}"#, "json").is_err());
+    }
+
+    #[test]
+    fn csv_batches_repeat_the_header() {
+        let rows = ImportRows::parse("name,note\nAnn,\"two\nlines\"\r\nBob,x\n", "csv").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows.payload(0..1), "name,note\nAnn,\"two\nlines\"\n");
+        assert_eq!(rows.payload(1..2), "name,note\nBob,x\n");
+    }
+
+    #[test]
+    fn cypher_statements_split_outside_literals() {
+        let rows =
+            ImportRows::parse("CREATE (:A {s: 'x;y'});\n\nCREATE (:`B;C`);", "cypher").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows.payload(1..2), "CREATE (:`B;C`);\n");
+    }
+
+    #[test]
+    fn checkpoint_round_trips_and_detects_changed_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.json.import-checkpoint.json");
+        let checkpoint = ImportCheckpoint {
+            file: "data.json".to_string(),
+            format: "json".to_string(),
+            fingerprint: fingerprint("[1,2,3]"),
+            total_rows: 3,
+            rows_committed: 2,
+            batches_committed: 1,
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+        };
+        checkpoint.save(&path).unwrap();
+        let loaded = ImportCheckpoint::load(&path).unwrap();
+        assert_eq!(loaded, checkpoint);
+        loaded
+            .check_matches("json", &fingerprint("[1,2,3]"), 3)
+            .unwrap();
+        assert!(
+            loaded
+                .check_matches("csv", &fingerprint("[1,2,3]"), 3)
+                .is_err()
+        );
+        assert!(
+            loaded
+                .check_matches("json", &fingerprint("[1,2,4]"), 3)
+                .is_err()
+        );
+    }
+}
@@ -6,6 +6,7 @@ mod commands;
 mod config;
 mod cypher_helper;
 mod endpoint;
+mod import;
 mod rpc_transport;
 
 use commands::{admin, completion, config as config_cmd, data, db, key, query, schema, user};
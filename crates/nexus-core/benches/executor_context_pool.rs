@@ -0,0 +1,113 @@
+//! Allocation + throughput bench for the executor's context pool.
+//!
+//! Runs a small read query (`MATCH (n:Person) WHERE n.age > 90 RETURN
+//! n.name`) from several threads at once, each thread on its own clone
+//! of one executor — the shape of the server's lock-free read path —
+//! twice: with the context pool disabled (`set_context_pool_capacity(0)`)
+//! and with the default capacity. A counting global allocator reports
+//! allocations per query for both lines before the timing groups run.
+//!
+//! ```text
+//! cargo +nightly bench -p nexus-core --bench executor_context_pool
+//! ```
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use nexus_core::executor::context_pool::DEFAULT_CONTEXT_POOL_CAPACITY;
+use nexus_core::executor::{Executor, Query};
+use nexus_core::testing::create_test_executor;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const THREADS: usize = 8;
+const QUERIES_PER_THREAD: usize = 50;
+
+fn read_query() -> Query {
+    Query {
+        cypher: "MATCH (n:Person) WHERE n.age > 90 RETURN n.name".to_string(),
+        params: HashMap::new(),
+    }
+}
+
+fn seed(executor: &Executor) {
+    for i in 0..100 {
+        executor
+            .execute(&Query {
+                cypher: format!("CREATE (:Person {{name: 'p{i}', age: {i}}})"),
+                params: HashMap::new(),
+            })
+            .expect("seed CREATE should succeed");
+    }
+}
+
+/// Run `QUERIES_PER_THREAD` reads on each of `THREADS` executor clones.
+fn run_concurrent(executor: &Executor) {
+    std::thread::scope(|scope| {
+        for _ in 0..THREADS {
+            let local = executor.clone();
+            scope.spawn(move || {
+                let query = read_query();
+                for _ in 0..QUERIES_PER_THREAD {
+                    black_box(local.execute(&query).expect("read should succeed"));
+                }
+            });
+        }
+    });
+}
+
+fn allocations_per_query(executor: &Executor) -> f64 {
+    // Warm the pool (and every lazily-built cache) first.
+    run_concurrent(executor);
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    run_concurrent(executor);
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    (after - before) as f64 / (THREADS * QUERIES_PER_THREAD) as f64
+}
+
+fn bench_context_pool(c: &mut Criterion) {
+    let (executor, _ctx) = create_test_executor();
+    seed(&executor);
+
+    let mut group = c.benchmark_group("executor_context_pool");
+    group.throughput(Throughput::Elements((THREADS * QUERIES_PER_THREAD) as u64));
+
+    for (name, capacity) in [("unpooled", 0), ("pooled", DEFAULT_CONTEXT_POOL_CAPACITY)] {
+        executor.set_context_pool_capacity(capacity);
+        println!(
+            "{name}: {:.1} allocations per query ({THREADS} threads)",
+            allocations_per_query(&executor)
+        );
+        group.bench_with_input(BenchmarkId::new(name, THREADS), &THREADS, |b, _| {
+            b.iter(|| run_concurrent(&executor))
+        });
+    }
+
+    let stats = executor.context_pool_stats();
+    println!(
+        "context pool: {} acquired, {} reused",
+        stats.acquired, stats.reused
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_context_pool);
+criterion_main!(benches);
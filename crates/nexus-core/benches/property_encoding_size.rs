@@ -0,0 +1,116 @@
+//! Size + speed comparison of property encodings: the JSON bytes the
+//! property store writes today, the fixed-width binary layout
+//! (`value::encode_fixed_width`), and the packed layout
+//! (`value::encode` — varint integers, bit-packed BOOLEAN lists).
+//!
+//! Two datasets:
+//!
+//! * `tiny`: the node properties of nexus-bench's `TinyDataset`
+//!   (`{id, name, score}` × 100) — string-heavy, little to pack.
+//! * `flags`: 1 000 nodes of small counters and booleans
+//!   (`{id, active, verified, age, visits, rank, perms: [16 × bool]}`)
+//!   — the shape varint / bit-packing targets.
+//!
+//! The byte totals are printed once before the timing groups run.
+//!
+//! ```text
+//! cargo +nightly bench -p nexus-core --bench property_encoding_size
+//! ```
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use nexus_core::value::{self, NexusValue};
+use serde_json::{Value, json};
+use std::hint::black_box;
+
+fn tiny_dataset() -> Vec<Value> {
+    (0..100)
+        .map(|i| json!({"id": i, "name": format!("n{i}"), "score": i as f64 / 100.0}))
+        .collect()
+}
+
+fn flags_dataset() -> Vec<Value> {
+    (0..1_000i64)
+        .map(|i| {
+            let perms: Vec<bool> = (0..16).map(|b| (i >> b) & 1 == 1).collect();
+            json!({
+                "id": i,
+                "active": i % 2 == 0,
+                "verified": i % 3 == 0,
+                "age": 18 + i % 60,
+                "visits": i * 7 % 1_000,
+                "rank": i % 10,
+                "perms": perms,
+            })
+        })
+        .collect()
+}
+
+fn total(values: &[NexusValue], enc: fn(&NexusValue) -> nexus_core::Result<Vec<u8>>) -> usize {
+    values.iter().map(|v| enc(v).unwrap().len()).sum()
+}
+
+fn report_sizes(name: &str, rows: &[Value]) {
+    let json_bytes: usize = rows
+        .iter()
+        .map(|r| serde_json::to_vec(r).unwrap().len())
+        .sum();
+    let values: Vec<NexusValue> = rows.iter().map(NexusValue::from_json).collect();
+    let fixed = total(&values, value::encode_fixed_width);
+    let packed = total(&values, value::encode);
+    eprintln!(
+        "property_encoding_size/{name}: json={json_bytes} fixed={fixed} packed={packed} \
+         (packed vs fixed {:+.1}%, vs json {:+.1}%)",
+        (packed as f64 / fixed as f64 - 1.0) * 100.0,
+        (packed as f64 / json_bytes as f64 - 1.0) * 100.0,
+    );
+}
+
+fn bench_encoding(c: &mut Criterion) {
+    let datasets = [("tiny", tiny_dataset()), ("flags", flags_dataset())];
+    for (name, rows) in &datasets {
+        report_sizes(name, rows);
+    }
+
+    let mut group = c.benchmark_group("property_encoding");
+    for (name, rows) in &datasets {
+        let values: Vec<NexusValue> = rows.iter().map(NexusValue::from_json).collect();
+        group.throughput(Throughput::Elements(values.len() as u64));
+        group.bench_with_input(BenchmarkId::new("json", name), rows, |b, rows| {
+            b.iter(|| {
+                for r in rows {
+                    black_box(serde_json::to_vec(black_box(r)).unwrap());
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("fixed", name), &values, |b, values| {
+            b.iter(|| {
+                for v in values {
+                    black_box(value::encode_fixed_width(black_box(v)).unwrap());
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("packed", name), &values, |b, values| {
+            b.iter(|| {
+                for v in values {
+                    black_box(value::encode(black_box(v)).unwrap());
+                }
+            })
+        });
+        let encoded: Vec<Vec<u8>> = values.iter().map(|v| value::encode(v).unwrap()).collect();
+        group.bench_with_input(
+            BenchmarkId::new("packed_decode", name),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    for buf in encoded {
+                        black_box(value::decode(black_box(buf)).unwrap());
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_encoding);
+criterion_main!(benches);
@@ -93,6 +93,14 @@ pub enum AuditOperation {
         entity_id: Option<String>,
         cypher_query: Option<String>,
     },
+    /// Idle transaction rolled back or abandoned session closed by the
+    /// session reaper
+    SessionReaped {
+        session_id: String,
+        reason: String, // "idle_transaction", "abandoned_session"
+        idle_secs: u64,
+        rolled_back_transaction: bool,
+    },
 }
 
 /// Result of an audit operation
@@ -448,6 +456,30 @@ impl AuditLogger {
         .await
     }
 
+    /// Log a session reaped for idleness
+    pub async fn log_session_reaped(&self, reaped: &crate::session::ReapedSession) -> Result<()> {
+        let reason = match reaped.reason {
+            crate::session::ReapReason::IdleTransaction => "idle_transaction",
+            crate::session::ReapReason::AbandonedSession => "abandoned_session",
+        };
+        self.log(AuditLogEntry {
+            timestamp: Utc::now(),
+            operation: AuditOperation::SessionReaped {
+                session_id: reaped.session_id.clone(),
+                reason: reason.to_string(),
+                idle_secs: reaped.idle.as_secs(),
+                rolled_back_transaction: reaped.rolled_back_transaction,
+            },
+            user_id: None,
+            username: None,
+            api_key_id: None,
+            result: AuditResult::Success,
+            metadata: serde_json::json!({}),
+            ip_address: None,
+        })
+        .await
+    }
+
     /// Rotate log file if needed (daily rotation)
     async fn rotate_if_needed(&self) -> Result<()> {
         let today = Utc::now().format("%Y-%m-%d").to_string();
@@ -1139,4 +1171,33 @@ mod tests {
         let content = std::fs::read_to_string(&log_file).unwrap();
         assert!(content.contains("192.168.1.100"));
     }
+
+    #[tokio::test]
+    async fn test_log_session_reaped() {
+        let ctx = TestContext::new();
+        let config = AuditConfig {
+            enabled: true,
+            log_dir: ctx.path().to_path_buf(),
+            retention_days: 30,
+            compress_logs: false,
+        };
+
+        let logger = AuditLogger::new(config).unwrap();
+        logger
+            .log_session_reaped(&crate::session::ReapedSession {
+                session_id: "default".to_string(),
+                reason: crate::session::ReapReason::IdleTransaction,
+                idle: std::time::Duration::from_secs(301),
+                rolled_back_transaction: true,
+            })
+            .await
+            .unwrap();
+
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let content =
+            std::fs::read_to_string(ctx.path().join(format!("audit-{}.log", today))).unwrap();
+        assert!(content.contains("\"type\":\"SessionReaped\""));
+        assert!(content.contains("\"reason\":\"idle_transaction\""));
+        assert!(content.contains("\"idle_secs\":301"));
+    }
 }
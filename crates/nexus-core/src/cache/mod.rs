@@ -27,6 +27,7 @@
 
 use crate::Result;
 use crate::executor::ResultSet;
+use crate::memory_management::MemoryPressureLevel;
 use crate::page_cache::{Page, PageCache};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -708,6 +709,24 @@ impl MultiLayerCache {
         self.relationship_cache.clear();
     }
 
+    /// Drop cached data to relieve memory pressure. Soft pressure drops
+    /// the layers that are cheapest to rebuild (query results and
+    /// deserialized objects); hard pressure also empties the page,
+    /// index-lookup and relationship-result caches. The relationship and
+    /// property indexes are left alone.
+    pub fn shed_for_memory_pressure(&mut self, level: MemoryPressureLevel) {
+        if level == MemoryPressureLevel::Normal {
+            return;
+        }
+        self.query_cache.clear();
+        self.object_cache.clear();
+        if level == MemoryPressureLevel::Hard {
+            let _ = self.page_cache.clear();
+            self.index_cache.clear();
+            self.relationship_cache.clear();
+        }
+    }
+
     /// Prefetch pages around the given page ID
     fn prefetch_pages(&mut self, page_id: u64) {
         let distance = self.config.page_cache.prefetch_distance as i64;
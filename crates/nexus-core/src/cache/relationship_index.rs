@@ -120,6 +120,17 @@ impl RelationshipIndex {
             .and_then(|ids| ids.first().copied())
     }
 
+    /// Every indexed relationship id for the exact `(src, type, dst)`
+    /// edge, oldest first. Same caveat as [`Self::find_edge`]: the ids are
+    /// hints to be verified against storage.
+    pub fn find_edges(&self, src_id: u64, type_id: u32, dst_id: u64) -> Vec<u64> {
+        let edge_index = self.edge_index.read().unwrap();
+        edge_index
+            .get(&(src_id, type_id, dst_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Index a new relationship
     pub fn add_relationship(
         &self,
@@ -1,5 +1,6 @@
-//! UDF, stored-procedure, property-index, and external-id extension methods
-//! for [`Catalog`].
+//! UDF, stored-procedure, property-index, enum-type, embedding-model, view,
+//! materialized-view result, schema-changelog, property-history,
+//! runtime-setting, and external-id extension methods for [`Catalog`].
 //!
 //! These methods persist supplementary catalog data that is not part of the
 //! core label/type/key mappings.
@@ -8,6 +9,9 @@ use crate::Result;
 use crate::catalog::external_id::ExternalId;
 use crate::catalog::external_id_index::ExternalIdIndex;
 use crate::catalog::store::Catalog;
+use crate::catalog::types::{
+    MaterializedViewResult, PropertyHistoryEntry, SchemaChange, SchemaChangeKind, ViewDefinition,
+};
 
 impl Catalog {
     // ── UDF storage ─────────────────────────────────────────────────────────
@@ -114,6 +118,328 @@ impl Catalog {
         Ok(iter.filter_map(|r| r.ok()).map(|(k, _)| k).collect())
     }
 
+    // ── Enum property types ──────────────────────────────────────────────────
+
+    /// Durably record the allowed values of an enum property on
+    /// `(label_id, key_id)`. Overwrites any previous definition.
+    pub fn persist_enum_property_type(
+        &self,
+        label_id: u32,
+        key_id: u32,
+        variants: &[String],
+    ) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.enum_property_db
+            .put(&mut wtxn, &(label_id, key_id), &variants.to_vec())?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Remove a durable enum property definition.
+    pub fn remove_enum_property_type(&self, label_id: u32, key_id: u32) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.enum_property_db
+            .delete(&mut wtxn, &(label_id, key_id))?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// List every persisted enum definition as `((label_id, key_id), variants)`.
+    pub fn list_enum_property_types(&self) -> Result<Vec<((u32, u32), Vec<String>)>> {
+        let rtxn = self.env.read_txn()?;
+        let iter = self.enum_property_db.iter(&rtxn)?;
+        Ok(iter.filter_map(|r| r.ok()).collect())
+    }
+
+    /// Durably record that relationships of `type_id` must run from a
+    /// node carrying `source_label` to a node carrying `target_label`.
+    /// Overwrites any previous definition for the type.
+    pub fn persist_rel_endpoint_constraint(
+        &self,
+        type_id: u32,
+        source_label: Option<u32>,
+        target_label: Option<u32>,
+    ) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.rel_endpoint_db
+            .put(&mut wtxn, &type_id, &(source_label, target_label))?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Remove a durable endpoint constraint. Returns `true` when one existed.
+    pub fn remove_rel_endpoint_constraint(&self, type_id: u32) -> Result<bool> {
+        let mut wtxn = self.env.write_txn()?;
+        let removed = self.rel_endpoint_db.delete(&mut wtxn, &type_id)?;
+        wtxn.commit()?;
+        Ok(removed)
+    }
+
+    /// Endpoint constraint for `type_id` as `(source_label, target_label)`.
+    pub fn get_rel_endpoint_constraint(
+        &self,
+        type_id: u32,
+    ) -> Result<Option<(Option<u32>, Option<u32>)>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.rel_endpoint_db.get(&rtxn, &type_id)?)
+    }
+
+    /// List every persisted endpoint constraint as
+    /// `(type_id, (source_label, target_label))`.
+    pub fn list_rel_endpoint_constraints(&self) -> Result<Vec<(u32, (Option<u32>, Option<u32>))>> {
+        let rtxn = self.env.read_txn()?;
+        let iter = self.rel_endpoint_db.iter(&rtxn)?;
+        Ok(iter.filter_map(|r| r.ok()).collect())
+    }
+
+    // ── Embedding model bindings ─────────────────────────────────────────────
+
+    /// Durably record that `(label_id, key_id)` holds embeddings produced
+    /// by `model` with `dimensions` components. Overwrites any previous
+    /// binding.
+    pub fn persist_embedding_model(
+        &self,
+        label_id: u32,
+        key_id: u32,
+        model: &str,
+        dimensions: u32,
+    ) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.embedding_model_db.put(
+            &mut wtxn,
+            &(label_id, key_id),
+            &(model.to_string(), dimensions),
+        )?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Remove an embedding model binding. Returns `true` when one existed.
+    pub fn remove_embedding_model(&self, label_id: u32, key_id: u32) -> Result<bool> {
+        let mut wtxn = self.env.write_txn()?;
+        let removed = self
+            .embedding_model_db
+            .delete(&mut wtxn, &(label_id, key_id))?;
+        wtxn.commit()?;
+        Ok(removed)
+    }
+
+    /// Embedding model binding of `(label_id, key_id)` as `(model, dimensions)`.
+    pub fn get_embedding_model(&self, label_id: u32, key_id: u32) -> Result<Option<(String, u32)>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self.embedding_model_db.get(&rtxn, &(label_id, key_id))?)
+    }
+
+    /// List every embedding model binding as
+    /// `((label_id, key_id), (model, dimensions))`.
+    pub fn list_embedding_models(&self) -> Result<Vec<((u32, u32), (String, u32))>> {
+        let rtxn = self.env.read_txn()?;
+        let iter = self.embedding_model_db.iter(&rtxn)?;
+        Ok(iter.filter_map(|r| r.ok()).collect())
+    }
+
+    // ── Saved views ──────────────────────────────────────────────────────────
+
+    /// Durably store a view, replacing any view of the same name.
+    pub fn persist_view(&self, view: &ViewDefinition) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.view_db.put(&mut wtxn, &view.name, view)?;
+        wtxn.commit()?;
+        self.view_cache.insert(view.name.clone(), view.clone());
+        Ok(())
+    }
+
+    /// Remove a view and its stored result. Returns `true` when the view
+    /// existed.
+    pub fn remove_view(&self, name: &str) -> Result<bool> {
+        let mut wtxn = self.env.write_txn()?;
+        let removed = self.view_db.delete(&mut wtxn, name)?;
+        self.view_result_db.delete(&mut wtxn, name)?;
+        wtxn.commit()?;
+        self.view_cache.remove(name);
+        self.view_result_cache.remove(name);
+        Ok(removed)
+    }
+
+    /// The view called `name`.
+    pub fn get_view(&self, name: &str) -> Option<ViewDefinition> {
+        self.view_cache.get(name).map(|v| v.clone())
+    }
+
+    /// Every view, by name.
+    pub fn list_views(&self) -> Vec<ViewDefinition> {
+        let mut views: Vec<ViewDefinition> =
+            self.view_cache.iter().map(|v| v.value().clone()).collect();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        views
+    }
+
+    /// Whether any view is defined. Cheap enough for the query hot path.
+    pub fn has_views(&self) -> bool {
+        !self.view_cache.is_empty()
+    }
+
+    /// Durably store the result of materialized view `name`, replacing
+    /// the previous one.
+    pub fn persist_view_result(&self, name: &str, result: &MaterializedViewResult) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.view_result_db.put(&mut wtxn, name, result)?;
+        wtxn.commit()?;
+        self.view_result_cache
+            .insert(name.to_string(), result.clone());
+        Ok(())
+    }
+
+    /// Remove the stored result of view `name`, if any.
+    pub fn remove_view_result(&self, name: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.view_result_db.delete(&mut wtxn, name)?;
+        wtxn.commit()?;
+        self.view_result_cache.remove(name);
+        Ok(())
+    }
+
+    /// The stored result of materialized view `name`.
+    pub fn get_view_result(&self, name: &str) -> Option<MaterializedViewResult> {
+        self.view_result_cache.get(name).map(|r| r.clone())
+    }
+
+    // ── Schema changelog ─────────────────────────────────────────────────────
+
+    /// Append a change to the schema changelog and return the stored entry.
+    pub fn log_schema_change(
+        &self,
+        kind: SchemaChangeKind,
+        statement: String,
+        inverse: Option<String>,
+    ) -> Result<SchemaChange> {
+        let mut wtxn = self.env.write_txn()?;
+        let change = self.log_schema_change_in(&mut wtxn, kind, statement, inverse)?;
+        wtxn.commit()?;
+        Ok(change)
+    }
+
+    /// [`log_schema_change`](Self::log_schema_change) inside a caller's
+    /// write txn, so the entry commits together with the change itself.
+    pub(crate) fn log_schema_change_in(
+        &self,
+        wtxn: &mut heed::RwTxn<'_>,
+        kind: SchemaChangeKind,
+        statement: String,
+        inverse: Option<String>,
+    ) -> Result<SchemaChange> {
+        let seq = self
+            .schema_log_db
+            .last(wtxn)?
+            .map(|(seq, _)| seq + 1)
+            .unwrap_or(1);
+        let change = SchemaChange {
+            seq,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            kind,
+            statement,
+            inverse,
+        };
+        self.schema_log_db.put(wtxn, &seq, &change)?;
+        Ok(change)
+    }
+
+    /// Changelog entries applied between `since` and `until` (epoch
+    /// milliseconds, both inclusive and optional), oldest first.
+    pub fn schema_changes(
+        &self,
+        since: Option<i64>,
+        until: Option<i64>,
+    ) -> Result<Vec<SchemaChange>> {
+        let rtxn = self.env.read_txn()?;
+        let mut changes = Vec::new();
+        for entry in self.schema_log_db.iter(&rtxn)? {
+            let (_, change) = entry?;
+            if since.is_some_and(|since| change.timestamp < since)
+                || until.is_some_and(|until| change.timestamp > until)
+            {
+                continue;
+            }
+            changes.push(change);
+        }
+        Ok(changes)
+    }
+
+    // ── Property change history ──────────────────────────────────────────────
+
+    /// Append `entry` to the history of `(node_id, key_id)`, dropping the
+    /// oldest entries beyond `max_entries`.
+    pub fn append_property_history(
+        &self,
+        node_id: u64,
+        key_id: u32,
+        entry: PropertyHistoryEntry,
+        max_entries: usize,
+    ) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        let mut entries = self
+            .property_history_db
+            .get(&wtxn, &(node_id, key_id))?
+            .unwrap_or_default();
+        entries.push(entry);
+        let excess = entries.len().saturating_sub(max_entries);
+        entries.drain(..excess);
+        self.property_history_db
+            .put(&mut wtxn, &(node_id, key_id), &entries)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Previous values of `(node_id, key_id)`, oldest first.
+    pub fn property_history(&self, node_id: u64, key_id: u32) -> Result<Vec<PropertyHistoryEntry>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self
+            .property_history_db
+            .get(&rtxn, &(node_id, key_id))?
+            .unwrap_or_default())
+    }
+
+    /// Forget the history of `node_id` for each of `key_ids`.
+    pub fn remove_property_history(&self, node_id: u64, key_ids: &[u32]) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        for &key_id in key_ids {
+            self.property_history_db
+                .delete(&mut wtxn, &(node_id, key_id))?;
+        }
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    // ── Runtime settings ─────────────────────────────────────────────────────
+
+    /// Durably store `value` as the runtime value of setting `name`.
+    pub fn persist_setting(&self, name: &str, value: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.settings_db.put(&mut wtxn, name, value)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Remove the stored value of setting `name`. Returns `true` when one
+    /// existed.
+    pub fn remove_setting(&self, name: &str) -> Result<bool> {
+        let mut wtxn = self.env.write_txn()?;
+        let removed = self.settings_db.delete(&mut wtxn, name)?;
+        wtxn.commit()?;
+        Ok(removed)
+    }
+
+    /// Every stored setting value, by name.
+    pub fn list_settings(&self) -> Result<Vec<(String, String)>> {
+        let rtxn = self.env.read_txn()?;
+        Ok(self
+            .settings_db
+            .iter(&rtxn)?
+            .flatten()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect())
+    }
+
     // ── External-id index ────────────────────────────────────────────────────
 
     /// Return a reference to the external-id index.
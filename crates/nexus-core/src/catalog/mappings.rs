@@ -5,7 +5,7 @@
 //! never hand out the same ID to different names.
 
 use crate::catalog::store::Catalog;
-use crate::catalog::types::{KeyId, LabelId, TypeId};
+use crate::catalog::types::{KeyId, LabelId, SchemaChangeKind, TypeId};
 use crate::{Error, Result};
 use parking_lot::RwLock;
 
@@ -33,6 +33,18 @@ impl Catalog {
         Ok(new_id)
     }
 
+    /// Record a newly allocated label in the schema changelog. Labels are
+    /// never dropped, so the entry has no inverse.
+    fn log_label_created(&self, wtxn: &mut heed::RwTxn<'_>, label: &str) -> Result<()> {
+        self.log_schema_change_in(
+            wtxn,
+            SchemaChangeKind::CreateLabel,
+            format!(":{label}"),
+            None,
+        )?;
+        Ok(())
+    }
+
     /// See [`alloc_label_id`]. Same atomic allocation for relationship types.
     fn alloc_type_id(&self, wtxn: &heed::RwTxn<'_>) -> Result<TypeId> {
         let new_id = self
@@ -114,6 +126,7 @@ impl Catalog {
         // Insert bidirectional mappings.
         self.label_name_to_id.put(&mut wtxn, label, &id)?;
         self.label_id_to_name.put(&mut wtxn, &id, label)?;
+        self.log_label_created(&mut wtxn, label)?;
 
         wtxn.commit()?;
 
@@ -170,6 +183,7 @@ impl Catalog {
                 // Insert bidirectional mappings.
                 self.label_name_to_id.put(&mut wtxn, *label, &id)?;
                 self.label_id_to_name.put(&mut wtxn, &id, *label)?;
+                self.log_label_created(&mut wtxn, label)?;
 
                 // Update cache.
                 self.label_name_cache.insert(label.to_string(), id);
@@ -31,7 +31,7 @@
 //! | [`store`] | `Catalog` struct, LMDB constructors, `Default` impl |
 //! | [`mappings`] | Label/type/key name ↔ ID allocation and lookup |
 //! | [`stats`] | Metadata and statistics read/write |
-//! | [`extensions`] | UDF, procedure, property-index, external-id index |
+//! | [`extensions`] | UDF, procedure, property-index, view, external-id index |
 //! | [`constraints`] | Uniqueness / existence constraint management |
 //! | [`external_id`] | `ExternalId` value type |
 //! | [`external_id_index`] | Forward+reverse LMDB external-id index |
@@ -51,7 +51,10 @@ pub(crate) mod types;
 // ── Public re-exports — every path that was previously reachable via
 //    `crate::catalog::*` is preserved here unchanged.
 // ── types ────────────────────────────────────────────────────────────────────
-pub use types::{CatalogMetadata, CatalogStats, KeyId, LabelId, TypeId};
+pub use types::{
+    CatalogMetadata, CatalogStats, KeyId, LabelId, MaterializedViewResult, PropertyHistoryEntry,
+    SchemaChange, SchemaChangeKind, TypeId, ViewDefinition, ViewRefresh,
+};
 
 // ── store ────────────────────────────────────────────────────────────────────
 pub use store::{CATALOG_MMAP_INITIAL_SIZE, Catalog};
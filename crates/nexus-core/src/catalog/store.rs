@@ -7,7 +7,10 @@
 //! `mod.rs`.
 
 use crate::catalog::external_id_index::ExternalIdIndex;
-use crate::catalog::types::{CatalogMetadata, CatalogStats, KeyId, LabelId, TypeId};
+use crate::catalog::types::{
+    CatalogMetadata, CatalogStats, KeyId, LabelId, MaterializedViewResult, PropertyHistoryEntry,
+    SchemaChange, TypeId, ViewDefinition,
+};
 use crate::{Error, Result};
 use dashmap::DashMap;
 use heed::types::*;
@@ -83,6 +86,43 @@ pub struct Catalog {
     /// the typed property index so indexes survive a restart (issue #11).
     pub(super) property_index_db: Database<SerdeBincode<(u32, u32)>, SerdeBincode<()>>,
 
+    /// Enum property definitions: `(label_id, key_id)` → allowed values in
+    /// ordinal order. Reloaded at startup so stored ordinals keep decoding.
+    pub(super) enum_property_db: Database<SerdeBincode<(u32, u32)>, SerdeBincode<Vec<String>>>,
+
+    /// Relationship endpoint constraints: `type_id` → required
+    /// `(source_label_id, target_label_id)`; `None` leaves that side free.
+    pub(super) rel_endpoint_db:
+        Database<SerdeBincode<u32>, SerdeBincode<(Option<u32>, Option<u32>)>>,
+
+    /// Embedding model bindings: `(label_id, key_id)` → `(model, dimensions)`
+    /// of the embeddings stored in that property.
+    pub(super) embedding_model_db: Database<SerdeBincode<(u32, u32)>, SerdeBincode<(String, u32)>>,
+
+    /// Saved views: name → definition.
+    pub(super) view_db: Database<Str, SerdeBincode<ViewDefinition>>,
+    /// In-memory copy of `view_db`, consulted on every query that may use
+    /// a view label (lock-free).
+    pub(super) view_cache: Arc<DashMap<String, ViewDefinition>>,
+    /// Stored results of materialized views: view name → result.
+    pub(super) view_result_db: Database<Str, SerdeBincode<MaterializedViewResult>>,
+    /// In-memory copy of `view_result_db`, read whenever a materialized
+    /// view is used (lock-free).
+    pub(super) view_result_cache: Arc<DashMap<String, MaterializedViewResult>>,
+
+    /// Schema changelog: sequence number → change. Big-endian keys so
+    /// iteration runs oldest first.
+    pub(super) schema_log_db: Database<U64<byteorder::BigEndian>, SerdeBincode<SchemaChange>>,
+
+    /// Property change history: `(node_id, key_id)` → previous values,
+    /// oldest first.
+    pub(super) property_history_db:
+        Database<SerdeBincode<(u64, u32)>, SerdeBincode<Vec<PropertyHistoryEntry>>>,
+
+    /// Runtime setting overrides made with `dbms.setConfigValue`:
+    /// setting name → value.
+    pub(super) settings_db: Database<Str, Str>,
+
     /// Next label ID counter (cached for performance).
     pub(super) next_label_id: Arc<RwLock<u32>>,
     /// Next type ID counter.
@@ -255,7 +295,7 @@ impl Catalog {
         let env = unsafe {
             EnvOpenOptions::new()
                 .map_size(actual_map_size)
-                .max_dbs(17) // Increased for constraints, UDFs, procedures, and external-id databases
+                .max_dbs(25) // Increased for constraints, UDFs, procedures, external-id, enum, endpoint, embedding-model, view, view-result, schema-log, property-history, and settings databases
                 .max_readers(2048)
                 .open(actual_path)?
         };
@@ -298,6 +338,39 @@ impl Catalog {
         let property_index_db: Database<SerdeBincode<(u32, u32)>, SerdeBincode<()>> =
             env.create_database(&mut wtxn, Some("property_indexes"))?;
 
+        // Create the enum property-type definition store.
+        let enum_property_db: Database<SerdeBincode<(u32, u32)>, SerdeBincode<Vec<String>>> =
+            env.create_database(&mut wtxn, Some("enum_property_types"))?;
+
+        // Create the relationship endpoint constraint store.
+        let rel_endpoint_db: Database<SerdeBincode<u32>, SerdeBincode<(Option<u32>, Option<u32>)>> =
+            env.create_database(&mut wtxn, Some("rel_endpoint_constraints"))?;
+
+        // Create the embedding model binding store.
+        let embedding_model_db: Database<SerdeBincode<(u32, u32)>, SerdeBincode<(String, u32)>> =
+            env.create_database(&mut wtxn, Some("embedding_models"))?;
+
+        // Create the saved-view store.
+        let view_db: Database<Str, SerdeBincode<ViewDefinition>> =
+            env.create_database(&mut wtxn, Some("views"))?;
+
+        // Create the materialized-view result store.
+        let view_result_db: Database<Str, SerdeBincode<MaterializedViewResult>> =
+            env.create_database(&mut wtxn, Some("view_results"))?;
+
+        // Create the schema changelog.
+        let schema_log_db: Database<U64<byteorder::BigEndian>, SerdeBincode<SchemaChange>> =
+            env.create_database(&mut wtxn, Some("schema_log"))?;
+
+        // Create the property change history store.
+        let property_history_db: Database<
+            SerdeBincode<(u64, u32)>,
+            SerdeBincode<Vec<PropertyHistoryEntry>>,
+        > = env.create_database(&mut wtxn, Some("property_history"))?;
+
+        // Create the runtime settings store.
+        let settings_db: Database<Str, Str> = env.create_database(&mut wtxn, Some("settings"))?;
+
         // Create external-id index sub-databases (forward + reverse).
         let external_id_index = ExternalIdIndex::open(&env, &mut wtxn)?;
 
@@ -355,6 +428,8 @@ impl Catalog {
         let type_id_cache = Arc::new(DashMap::new());
         let key_name_cache = Arc::new(DashMap::new());
         let key_id_cache = Arc::new(DashMap::new());
+        let view_cache = Arc::new(DashMap::new());
+        let view_result_cache = Arc::new(DashMap::new());
 
         // Warm up caches from existing data.
         // Populate caches immediately to ensure consistency.
@@ -381,6 +456,12 @@ impl Catalog {
                     key_id_cache.insert(id, name_str.to_string());
                 }
             }
+            for (name, view) in view_db.iter(&rtxn)?.flatten() {
+                view_cache.insert(name.to_string(), view);
+            }
+            for (name, result) in view_result_db.iter(&rtxn)?.flatten() {
+                view_result_cache.insert(name.to_string(), result);
+            }
         }
 
         // Initialize constraint manager with existing databases.
@@ -405,6 +486,16 @@ impl Catalog {
             udf_db,
             procedure_db,
             property_index_db,
+            enum_property_db,
+            rel_endpoint_db,
+            embedding_model_db,
+            view_db,
+            view_cache,
+            view_result_db,
+            view_result_cache,
+            schema_log_db,
+            property_history_db,
+            settings_db,
             next_label_id: Arc::new(RwLock::new(next_label_id)),
             next_type_id: Arc::new(RwLock::new(next_type_id)),
             next_key_id: Arc::new(RwLock::new(next_key_id)),
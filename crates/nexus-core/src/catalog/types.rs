@@ -27,6 +27,99 @@ pub struct CatalogStats {
     pub key_count: u32,
 }
 
+/// A saved Cypher view: `CREATE VIEW name AS <query>`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ViewDefinition {
+    /// View name, usable as a label in `MATCH`.
+    pub name: String,
+    /// Read-only query text the view expands to.
+    pub query: String,
+    /// Who created the view (API key user or id); `None` when created
+    /// without authentication.
+    pub owner: Option<String>,
+    /// Creation time in epoch milliseconds.
+    pub created_at: i64,
+    /// How the stored result is refreshed; `None` for a plain view,
+    /// which runs its query every time it is used.
+    pub materialized: Option<ViewRefresh>,
+}
+
+/// When a materialized view's stored result is recomputed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ViewRefresh {
+    /// Only on `REFRESH VIEW`.
+    Manual,
+    /// After any committed write since the last refresh.
+    OnChange,
+    /// At most once per `interval_ms`, whether or not anything changed.
+    Every {
+        /// Refresh period in milliseconds.
+        interval_ms: u64,
+    },
+}
+
+/// Stored result of a materialized view.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MaterializedViewResult {
+    /// Result columns.
+    pub columns: Vec<String>,
+    /// Rows as a JSON array of value arrays (bincode cannot encode
+    /// `serde_json::Value` directly).
+    pub rows_json: String,
+    /// Number of rows in `rows_json`.
+    pub row_count: u64,
+    /// When the result was computed, in epoch milliseconds.
+    pub refreshed_at: i64,
+    /// How long computing it took, in milliseconds.
+    pub refresh_ms: u64,
+}
+
+/// What a [`SchemaChange`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaChangeKind {
+    /// `CREATE INDEX`.
+    CreateIndex,
+    /// `DROP INDEX`.
+    DropIndex,
+    /// `CREATE CONSTRAINT`.
+    CreateConstraint,
+    /// `DROP CONSTRAINT`.
+    DropConstraint,
+    /// A label was used for the first time.
+    CreateLabel,
+}
+
+/// One entry of the schema changelog.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SchemaChange {
+    /// Position in the changelog, starting at 1.
+    pub seq: u64,
+    /// When the change was applied, in epoch milliseconds.
+    pub timestamp: i64,
+    /// What the change did.
+    pub kind: SchemaChangeKind,
+    /// Cypher statement equivalent to the change. Labels are created
+    /// implicitly, so a new label is recorded as just `:Label`.
+    pub statement: String,
+    /// Cypher statement that undoes the change; `None` when it cannot be
+    /// undone (labels are never dropped, and composite / spatial indexes
+    /// and the extended constraint kinds have no `DROP` form yet).
+    pub inverse: Option<String>,
+}
+
+/// A previous value of a node property with change history enabled.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PropertyHistoryEntry {
+    /// The replaced value as JSON (bincode cannot encode
+    /// `serde_json::Value` directly).
+    pub value_json: String,
+    /// When the value was replaced, in epoch milliseconds.
+    pub timestamp: i64,
+    /// User whose write replaced it, when known.
+    pub user: Option<String>,
+}
+
 /// Metadata stored in catalog.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CatalogMetadata {
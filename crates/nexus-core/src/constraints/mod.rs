@@ -36,6 +36,8 @@ pub enum ScalarType {
     String,
     Boolean,
     Bytes,
+    /// Exact decimal — the `{"_decimal": "<digits>"}` wire shape
+    Decimal,
     List,
     Map,
 }
@@ -48,6 +50,7 @@ impl ScalarType {
             ScalarType::String => "STRING",
             ScalarType::Boolean => "BOOLEAN",
             ScalarType::Bytes => "BYTES",
+            ScalarType::Decimal => "DECIMAL",
             ScalarType::List => "LIST",
             ScalarType::Map => "MAP",
         }
@@ -62,6 +65,7 @@ impl ScalarType {
             "STRING" | "TEXT" => Ok(ScalarType::String),
             "BOOLEAN" | "BOOL" => Ok(ScalarType::Boolean),
             "BYTES" => Ok(ScalarType::Bytes),
+            "DECIMAL" => Ok(ScalarType::Decimal),
             "LIST" => Ok(ScalarType::List),
             "MAP" => Ok(ScalarType::Map),
             other => Err(Error::CypherSyntax(format!(
@@ -85,11 +89,15 @@ impl ScalarType {
             ScalarType::String => v.is_string(),
             ScalarType::Boolean => v.is_boolean(),
             ScalarType::Bytes => crate::executor::eval::bytes::is_bytes_value(v),
+            ScalarType::Decimal => crate::executor::eval::decimal::is_decimal_value(v),
             ScalarType::List => v.is_array(),
             ScalarType::Map => {
-                // A BYTES wire-shape decodes as MAP in raw JSON — exclude
-                // it so `IS :: MAP` doesn't swallow byte arrays.
-                v.is_object() && !crate::executor::eval::bytes::is_bytes_value(v)
+                // BYTES and DECIMAL wire-shapes decode as MAP in raw
+                // JSON — exclude them so `IS :: MAP` doesn't swallow
+                // byte arrays or decimals.
+                v.is_object()
+                    && !crate::executor::eval::bytes::is_bytes_value(v)
+                    && !crate::executor::eval::decimal::is_decimal_value(v)
             }
         }
     }
@@ -128,27 +136,134 @@ pub struct PropertyTypeConstraint {
     pub ty: ScalarType,
 }
 
+/// Relationship endpoint constraint — every relationship of the type
+/// must start at a node carrying `source_label_id` and end at a node
+/// carrying `target_label_id`. `None` leaves that endpoint free. Unlike
+/// the kinds above this one is persisted in the catalog, because the
+/// planner consults it for expand selectivity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelEndpointConstraint {
+    pub rel_type_id: u32,
+    pub source_label_id: Option<u32>,
+    pub target_label_id: Option<u32>,
+}
+
+impl RelEndpointConstraint {
+    /// True iff a node with `label_bits` may sit at the source end.
+    pub fn accepts_source(&self, label_bits: u64) -> bool {
+        has_label(self.source_label_id, label_bits)
+    }
+
+    /// True iff a node with `label_bits` may sit at the target end.
+    pub fn accepts_target(&self, label_bits: u64) -> bool {
+        has_label(self.target_label_id, label_bits)
+    }
+}
+
+fn has_label(required: Option<u32>, label_bits: u64) -> bool {
+    match required {
+        None => true,
+        Some(id) => id < 64 && label_bits & (1u64 << id) != 0,
+    }
+}
+
 /// Structured payload attached to a constraint-violation error.
-/// Mirrors the JSON shape documented in `docs/guides/CONSTRAINTS.md`.
-#[derive(Debug, Clone)]
+/// Mirrors the JSON shape documented in `docs/guides/CONSTRAINTS.md`;
+/// the server returns it as `constraint_violation` next to the error
+/// message so clients can point at the offending value or fall back to
+/// an update of `conflicting_id`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct ConstraintViolation {
+    /// Human-readable `ERR_CONSTRAINT_VIOLATED: kind=...` message.
+    pub message: String,
     pub constraint_name: Option<String>,
     pub kind: &'static str,
     pub entity_type: &'static str,
     pub labels_or_types: Vec<String>,
     pub properties: Vec<String>,
+    /// Node or relationship being written, when it already has an id.
     pub offending_id: Option<u64>,
-    pub offending_values: Vec<(String, Value)>,
+    /// Existing entity the write collides with (UNIQUENESS / NODE_KEY).
+    pub conflicting_id: Option<u64>,
+    pub offending_values: serde_json::Map<String, Value>,
 }
 
 impl ConstraintViolation {
-    pub fn into_error(self) -> Error {
-        let msg = format!(
-            "ERR_CONSTRAINT_VIOLATED: kind={} entity={} labelsOrTypes={:?} properties={:?} \
-             offending_id={:?}",
-            self.kind, self.entity_type, self.labels_or_types, self.properties, self.offending_id,
-        );
-        Error::ConstraintViolation(msg)
+    /// Empty violation of `kind` on `entity_type` (`NODE` or
+    /// `RELATIONSHIP`); fill in the details with the builder methods.
+    pub fn new(kind: &'static str, entity_type: &'static str) -> Self {
+        Self {
+            message: String::new(),
+            constraint_name: None,
+            kind,
+            entity_type,
+            labels_or_types: Vec::new(),
+            properties: Vec::new(),
+            offending_id: None,
+            conflicting_id: None,
+            offending_values: serde_json::Map::new(),
+        }
+    }
+
+    pub fn named(mut self, name: Option<&str>) -> Self {
+        self.constraint_name = name.map(str::to_string);
+        self
+    }
+
+    pub fn label_or_type(mut self, name: impl Into<String>) -> Self {
+        self.labels_or_types.push(name.into());
+        self
+    }
+
+    pub fn property(mut self, key: impl Into<String>) -> Self {
+        self.properties.push(key.into());
+        self
+    }
+
+    /// Record the value written to `key` (also listing `key` as a property).
+    pub fn value(mut self, key: impl Into<String>, value: Value) -> Self {
+        let key = key.into();
+        if !self.properties.contains(&key) {
+            self.properties.push(key.clone());
+        }
+        self.offending_values.insert(key, value);
+        self
+    }
+
+    pub fn offending(mut self, id: Option<u64>) -> Self {
+        self.offending_id = id;
+        self
+    }
+
+    pub fn conflicting(mut self, id: u64) -> Self {
+        self.conflicting_id = Some(id);
+        self
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    pub fn into_error(mut self) -> Error {
+        if self.message.is_empty() {
+            self.message = format!(
+                "ERR_CONSTRAINT_VIOLATED: kind={} entity={} labelsOrTypes={:?} properties={:?} \
+                 offending_id={:?}",
+                self.kind,
+                self.entity_type,
+                self.labels_or_types,
+                self.properties,
+                self.offending_id,
+            );
+        }
+        Error::ConstraintViolation(Box::new(self))
+    }
+}
+
+impl std::fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
     }
 }
 
@@ -182,12 +297,14 @@ impl BackfillReport {
             .take(5)
             .map(|(id, r)| format!("{id}:{r}"))
             .collect();
-        Error::ConstraintViolation(format!(
-            "ERR_CONSTRAINT_VIOLATED: backfill found {n} {kind} violation(s) (showing up to 5): \
-             [{}]; total_scanned={}",
-            preview.join(", "),
-            self.total_scanned,
-        ))
+        ConstraintViolation::new(kind, "NODE")
+            .with_message(format!(
+                "ERR_CONSTRAINT_VIOLATED: backfill found {n} {kind} violation(s) (showing up to \
+                 5): [{}]; total_scanned={}",
+                preview.join(", "),
+                self.total_scanned,
+            ))
+            .into_error()
     }
 }
 
@@ -196,6 +313,18 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn rel_endpoint_checks_required_labels_only() {
+        let c = RelEndpointConstraint {
+            rel_type_id: 0,
+            source_label_id: Some(1),
+            target_label_id: None,
+        };
+        assert!(c.accepts_source(0b10));
+        assert!(!c.accepts_source(0b01));
+        assert!(c.accepts_target(0));
+    }
+
     #[test]
     fn scalar_integer_strict() {
         assert!(ScalarType::Integer.accepts(&json!(42)));
@@ -219,6 +348,15 @@ mod tests {
         assert!(ScalarType::Map.accepts(&plain_map));
     }
 
+    #[test]
+    fn scalar_decimal_distinguished_from_map_and_float() {
+        let decimal_value = json!({"_decimal": "19.99"});
+        assert!(ScalarType::Decimal.accepts(&decimal_value));
+        assert!(!ScalarType::Decimal.accepts(&json!(19.99)));
+        assert!(!ScalarType::Map.accepts(&decimal_value));
+        assert_eq!(ScalarType::parse("decimal").unwrap(), ScalarType::Decimal);
+    }
+
     #[test]
     fn parse_canonical_and_aliases() {
         assert_eq!(ScalarType::parse("INTEGER").unwrap(), ScalarType::Integer);
@@ -239,18 +377,22 @@ mod tests {
 
     #[test]
     fn violation_into_error_carries_shape() {
-        let v = ConstraintViolation {
-            constraint_name: Some("person_email_unique".to_string()),
-            kind: "UNIQUENESS",
-            entity_type: "NODE",
-            labels_or_types: vec!["Person".to_string()],
-            properties: vec!["email".to_string()],
-            offending_id: Some(42),
-            offending_values: vec![("email".to_string(), json!("a@b.c"))],
-        };
-        let msg = v.into_error().to_string();
+        let v = ConstraintViolation::new("UNIQUENESS", "NODE")
+            .named(Some("person_email_unique"))
+            .label_or_type("Person")
+            .value("email", json!("a@b.c"))
+            .offending(Some(42))
+            .conflicting(7);
+        let err = v.into_error();
+        let msg = err.to_string();
         assert!(msg.contains("ERR_CONSTRAINT_VIOLATED"));
         assert!(msg.contains("UNIQUENESS"));
         assert!(msg.contains("Person"));
+        let details = err.constraint_violation().unwrap();
+        assert_eq!(details.properties, vec!["email".to_string()]);
+        assert_eq!(
+            serde_json::to_value(details).unwrap()["offending_values"],
+            json!({"email": "a@b.c"})
+        );
     }
 }
@@ -6,12 +6,25 @@
 //! - Catalog (labels, types, property keys)
 //! - Indexes (label, property, KNN)
 //! - Transaction log (WAL)
-
+//!
+//! and its own storage backend, chosen by name when the database is
+//! created (see [`crate::storage::backend`]).
+//!
+//! A backup directory can also be attached as a read-only database with
+//! [`DatabaseManager::attach_database`]: its store files are mapped in
+//! place, its WAL is neither copied nor replayed, and every write is
+//! rejected. Attached databases are queried like any other, including
+//! from cross-database queries. A server confines attachable backups to
+//! its backups root with [`DatabaseManager::set_backups_root`].
+
+use crate::engine::EngineConfig;
+use crate::storage::backend;
+use crate::storage::{ArchiveBackend, StorageBackend};
 use crate::{Engine, Error, Result};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing;
 
@@ -47,8 +60,23 @@ pub struct DatabaseInfo {
     pub storage_size: u64,
     /// Current database state
     pub state: DatabaseState,
+    /// Name of the storage backend holding the store files
+    #[serde(default)]
+    pub storage_backend: String,
+    /// Whether every write is rejected (attached archives, or a store
+    /// opened read-only after a failed integrity check)
+    #[serde(default)]
+    pub read_only: bool,
 }
 
+/// Files a backup directory must contain to be attached.
+const ARCHIVE_FILES: [&str; 4] = [
+    "catalog.mdb",
+    "nodes.store",
+    "rels.store",
+    "properties.store",
+];
+
 /// Database manager for multiple isolated databases
 pub struct DatabaseManager {
     /// Map of database name to Engine instance
@@ -59,6 +87,10 @@ pub struct DatabaseManager {
     base_dir: PathBuf,
     /// Default database name
     default_db: String,
+    /// Storage backends databases can be created on, by name
+    storage_backends: Arc<RwLock<HashMap<String, Arc<dyn StorageBackend>>>>,
+    /// Directory attached backups must lie under; `None` accepts any path
+    backups_root: Option<PathBuf>,
 }
 
 impl DatabaseManager {
@@ -67,12 +99,19 @@ impl DatabaseManager {
         let default_db = "neo4j".to_string();
         let databases = Arc::new(RwLock::new(HashMap::new()));
         let states = Arc::new(RwLock::new(HashMap::new()));
+        let storage_backends: HashMap<String, Arc<dyn StorageBackend>> = ["mmap", "memory"]
+            .into_iter()
+            .filter_map(backend::builtin_backend)
+            .map(|backend| (backend.name().to_string(), backend))
+            .collect();
 
         let manager = Self {
             databases,
             states,
             base_dir: base_dir.clone(),
             default_db: default_db.clone(),
+            storage_backends: Arc::new(RwLock::new(storage_backends)),
+            backups_root: None,
         };
 
         // Create default database
@@ -81,18 +120,49 @@ impl DatabaseManager {
         Ok(manager)
     }
 
-    /// Create a new database
+    /// Make `backend` available to [`Self::create_database_with_backend`]
+    /// under its name, replacing any backend registered with that name.
+    pub fn register_storage_backend(&self, backend: Arc<dyn StorageBackend>) {
+        self.storage_backends
+            .write()
+            .insert(backend.name().to_string(), backend);
+    }
+
+    /// Only attach backups that lie under `root`. Relative attach paths
+    /// are resolved against it.
+    pub fn set_backups_root(&mut self, root: PathBuf) {
+        self.backups_root = Some(root);
+    }
+
+    /// Names of the registered storage backends, sorted.
+    pub fn storage_backend_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.storage_backends.read().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Create a new database on the default (memory-mapped) storage backend
     pub fn create_database(&self, name: &str) -> Result<Arc<RwLock<Engine>>> {
-        // Validate database name
-        if name.is_empty()
-            || !name
-                .chars()
-                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
-        {
-            return Err(Error::InvalidInput(
-                "Database name must be alphanumeric with _ or -".to_string(),
-            ));
-        }
+        self.create_database_with_backend(name, backend::default_backend().name())
+    }
+
+    /// Create a new database whose store files live on the registered
+    /// storage backend called `backend`
+    pub fn create_database_with_backend(
+        &self,
+        name: &str,
+        backend: &str,
+    ) -> Result<Arc<RwLock<Engine>>> {
+        Self::validate_name(name)?;
+
+        let registered = self.storage_backends.read().get(backend).cloned();
+        let Some(storage_backend) = registered else {
+            return Err(Error::InvalidInput(format!(
+                "Unknown storage backend '{}' (available: {})",
+                backend,
+                self.storage_backend_names().join(", ")
+            )));
+        };
 
         let mut dbs = self.databases.write();
 
@@ -109,7 +179,13 @@ impl DatabaseManager {
         std::fs::create_dir_all(&db_path)?;
 
         // Create engine for this database
-        let engine = Engine::with_data_dir(&db_path)?;
+        let engine = Engine::with_data_dir_and_config(
+            &db_path,
+            EngineConfig {
+                storage_backend,
+                ..Default::default()
+            },
+        )?;
         let engine_arc = Arc::new(RwLock::new(engine));
 
         // Store database
@@ -123,6 +199,157 @@ impl DatabaseManager {
         Ok(engine_arc)
     }
 
+    /// Attach the backup directory `archive` (a copy of a data directory,
+    /// as written by the server's backup operation) as the read-only
+    /// database `name`.
+    ///
+    /// With a backups root set (see [`Self::set_backups_root`]), a
+    /// relative `archive` is resolved against it and the canonical path
+    /// must lie under it. The database directory must not exist yet.
+    ///
+    /// The store files are mapped from `archive` through an
+    /// [`ArchiveBackend`] and never modified. The catalog and indexes
+    /// are copied into the database directory, because opening them
+    /// needs write access; the archive's WAL is ignored. If attaching
+    /// fails, the database directory is removed again. Every write to
+    /// the database fails with [`Error::ReadOnly`]. Dropping the
+    /// database removes the copies and leaves the archive alone.
+    pub fn attach_database(&self, name: &str, archive: &Path) -> Result<Arc<RwLock<Engine>>> {
+        Self::validate_name(name)?;
+
+        let archive = self.resolve_archive(archive)?;
+        if let Some(missing) = ARCHIVE_FILES
+            .iter()
+            .find(|file| !archive.join(file).exists())
+        {
+            return Err(Error::InvalidInput(format!(
+                "'{}' is not a backup directory (missing {})",
+                archive.display(),
+                missing
+            )));
+        }
+
+        let mut dbs = self.databases.write();
+
+        if dbs.contains_key(name) {
+            return Err(Error::InvalidInput(format!(
+                "Database '{}' already exists",
+                name
+            )));
+        }
+
+        let db_path = self.base_dir.join(name);
+        if db_path.exists() {
+            return Err(Error::InvalidInput(format!(
+                "Cannot attach '{}': directory {} already exists",
+                name,
+                db_path.display()
+            )));
+        }
+        std::fs::create_dir_all(&db_path)?;
+        let engine = match Self::open_archive(name, &archive, &db_path) {
+            Ok(engine) => engine,
+            Err(e) => {
+                if let Err(cleanup) = std::fs::remove_dir_all(&db_path) {
+                    tracing::warn!(
+                        "Failed to remove {} after a failed attach: {}",
+                        db_path.display(),
+                        cleanup
+                    );
+                }
+                return Err(e);
+            }
+        };
+        let engine_arc = Arc::new(RwLock::new(engine));
+
+        dbs.insert(name.to_string(), engine_arc.clone());
+        self.states
+            .write()
+            .insert(name.to_string(), DatabaseState::Online);
+        tracing::info!(
+            "Attached '{}' read-only as database '{}'",
+            archive.display(),
+            name
+        );
+
+        Ok(engine_arc)
+    }
+
+    /// Canonical path of the backup `archive`, checked against the
+    /// backups root.
+    fn resolve_archive(&self, archive: &Path) -> Result<PathBuf> {
+        let not_a_backup = |e: std::io::Error| {
+            Error::InvalidInput(format!(
+                "'{}' is not a backup directory ({})",
+                archive.display(),
+                e
+            ))
+        };
+        let Some(root) = &self.backups_root else {
+            return archive.canonicalize().map_err(not_a_backup);
+        };
+        let root = root.canonicalize().map_err(|e| {
+            Error::InvalidInput(format!(
+                "backups root {} is not usable: {}",
+                root.display(),
+                e
+            ))
+        })?;
+        let resolved = root.join(archive).canonicalize().map_err(not_a_backup)?;
+        if !resolved.starts_with(&root) {
+            return Err(Error::InvalidInput(format!(
+                "'{}' is outside the backups root {}",
+                archive.display(),
+                root.display()
+            )));
+        }
+        Ok(resolved)
+    }
+
+    /// Copy the catalog and indexes of `archive` into `db_path` and open
+    /// the read-only engine over its store files.
+    fn open_archive(name: &str, archive: &Path, db_path: &Path) -> Result<Engine> {
+        copy_dir(
+            &archive.join("catalog.mdb"),
+            &db_path.join("catalog.mdb"),
+            |_, _| true,
+        )?;
+        if archive.join("indexes").is_dir() {
+            copy_dir(
+                &archive.join("indexes"),
+                &db_path.join("indexes"),
+                |_, _| true,
+            )?;
+        }
+
+        let mut engine = Engine::with_data_dir_and_config(
+            db_path,
+            EngineConfig {
+                storage_backend: Arc::new(ArchiveBackend::new(archive)),
+                ..Default::default()
+            },
+        )?;
+        engine.read_only_reason = Some(format!(
+            "database '{}' is attached read-only from {}",
+            name,
+            archive.display()
+        ));
+        Ok(engine)
+    }
+
+    fn validate_name(name: &str) -> Result<()> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(Error::InvalidInput(
+                "Database name must be alphanumeric with _ or -".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Drop a database (delete all data)
     pub fn drop_database(&self, name: &str, if_exists: bool) -> Result<()> {
         // Cannot drop default database
@@ -225,6 +452,8 @@ impl DatabaseManager {
                     Ok(stats) => (stats.nodes, stats.relationships),
                     Err(_) => (0, 0),
                 };
+                let storage_backend = engine_guard.storage_backend_name().to_string();
+                let read_only = engine_guard.read_only_reason().is_some();
 
                 let db_path = self.base_dir.join(name);
 
@@ -257,6 +486,8 @@ impl DatabaseManager {
                     relationship_count,
                     storage_size,
                     state,
+                    storage_backend,
+                    read_only,
                 }
             })
             .collect();
@@ -393,12 +624,102 @@ impl DatabaseManager {
     }
 }
 
+/// Bytes copied between two [`copy_dir`] progress checkpoints
+const COPY_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Copy every file under `source` into `destination`, recursively.
+/// `progress` is called with the bytes copied so far and the total, at
+/// the start and after every chunk; returning `false` stops the copy.
+/// Returns the file and byte counts, or `None` when stopped.
+pub fn copy_dir(
+    source: &Path,
+    destination: &Path,
+    mut progress: impl FnMut(u64, u64) -> bool,
+) -> std::io::Result<Option<(u64, u64)>> {
+    use std::io::{Read, Write};
+
+    let mut files = Vec::new();
+    collect_files(source, &mut files)?;
+    let total: u64 = files.iter().map(|(_, len)| len).sum();
+    if !progress(0, total) {
+        return Ok(None);
+    }
+
+    std::fs::create_dir_all(destination)?;
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+    let mut copied = 0u64;
+    for (path, _) in &files {
+        let relative = path.strip_prefix(source).map_err(std::io::Error::other)?;
+        let target = destination.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut reader = std::fs::File::open(path)?;
+        let mut writer = std::fs::File::create(&target)?;
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..read])?;
+            copied += read as u64;
+            // Files may grow while the copy runs.
+            if !progress(copied, total.max(copied)) {
+                return Ok(None);
+            }
+        }
+        writer.sync_all()?;
+    }
+    Ok(Some((files.len() as u64, copied)))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<(PathBuf, u64)>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push((entry.path(), entry.metadata()?.len()));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::testing::TestContext;
     use serial_test::serial;
 
+    #[test]
+    fn copy_dir_reports_progress_and_stops_when_asked() {
+        let ctx = TestContext::new();
+        let source = ctx.path().join("source");
+        std::fs::create_dir_all(source.join("nested")).unwrap();
+        std::fs::write(source.join("a.bin"), [1u8; 10]).unwrap();
+        std::fs::write(source.join("nested").join("b.bin"), [2u8; 5]).unwrap();
+
+        let mut reports = Vec::new();
+        let copied = copy_dir(&source, &ctx.path().join("copy"), |copied, total| {
+            reports.push((copied, total));
+            true
+        })
+        .unwrap();
+        assert_eq!(copied, Some((2, 15)));
+        assert_eq!(reports.first(), Some(&(0, 15)));
+        assert_eq!(reports.last(), Some(&(15, 15)));
+        assert_eq!(
+            std::fs::read(ctx.path().join("copy").join("nested").join("b.bin")).unwrap(),
+            vec![2u8; 5]
+        );
+
+        let stopped = copy_dir(&source, &ctx.path().join("stopped"), |copied, _| {
+            copied == 0
+        });
+        assert_eq!(stopped.unwrap(), None);
+    }
+
     #[test]
     fn test_database_manager_creation() {
         let ctx = TestContext::new();
@@ -771,4 +1092,137 @@ mod tests {
         let result = manager.start_database("nonexistent");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_create_database_on_memory_backend() {
+        let ctx = TestContext::new();
+        let manager = DatabaseManager::new(ctx.path().to_path_buf()).unwrap();
+
+        let db = manager
+            .create_database_with_backend("scratch", "memory")
+            .unwrap();
+        db.write()
+            .execute_cypher("CREATE (:Tmp {n: 1}), (:Tmp {n: 2})")
+            .unwrap();
+        assert_eq!(db.write().stats().unwrap().nodes, 2);
+        assert!(!ctx.path().join("scratch").join("nodes.store").exists());
+
+        let info = manager
+            .list_databases()
+            .into_iter()
+            .find(|d| d.name == "scratch")
+            .unwrap();
+        assert_eq!(info.storage_backend, "memory");
+
+        let err = manager
+            .create_database_with_backend("remote", "s3")
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown storage backend 's3'"));
+        assert!(!manager.exists("remote"));
+    }
+
+    #[test]
+    fn test_attach_backup_read_only() {
+        let ctx = TestContext::new();
+        let backup = ctx.path().join("backup");
+        {
+            let mut engine = Engine::with_data_dir(&backup).unwrap();
+            engine
+                .execute_cypher("CREATE (:Person {name: 'Ada'}), (:Person {name: 'Alan'})")
+                .unwrap();
+            engine.flush().unwrap();
+        }
+        let manager = DatabaseManager::new(ctx.path().join("dbs")).unwrap();
+
+        let db = manager.attach_database("history", &backup).unwrap();
+        let result = db
+            .write()
+            .execute_cypher("MATCH (p:Person) RETURN p.name ORDER BY p.name")
+            .unwrap();
+        assert_eq!(result.rows.len(), 2);
+
+        let err = db
+            .write()
+            .execute_cypher("CREATE (:Person {name: 'Grace'})")
+            .unwrap_err();
+        assert!(matches!(err, Error::ReadOnly(_)), "{err}");
+        assert!(!ctx.path().join("dbs/history/nodes.store").exists());
+
+        let info = manager
+            .list_databases()
+            .into_iter()
+            .find(|d| d.name == "history")
+            .unwrap();
+        assert!(info.read_only);
+        assert_eq!(info.storage_backend, "archive");
+
+        manager.drop_database("history", false).unwrap();
+        assert!(backup.join("nodes.store").exists());
+
+        let err = manager
+            .attach_database("bogus", &ctx.path().join("missing"))
+            .unwrap_err();
+        assert!(err.to_string().contains("not a backup directory"));
+        assert!(!manager.exists("bogus"));
+    }
+
+    #[test]
+    fn test_attach_is_confined_to_the_backups_root() {
+        let ctx = TestContext::new();
+        let backups = ctx.path().join("backups");
+        for dir in [backups.join("nightly"), ctx.path().join("elsewhere")] {
+            let mut engine = Engine::with_data_dir(&dir).unwrap();
+            engine
+                .execute_cypher("CREATE (:Person {name: 'Ada'})")
+                .unwrap();
+            engine.flush().unwrap();
+        }
+        let mut manager = DatabaseManager::new(ctx.path().join("dbs")).unwrap();
+        manager.set_backups_root(backups.clone());
+
+        let err = manager
+            .attach_database("outside", &ctx.path().join("elsewhere"))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("outside the backups root"),
+            "{err}"
+        );
+        let err = manager
+            .attach_database("escape", Path::new("../elsewhere"))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("outside the backups root"),
+            "{err}"
+        );
+
+        // A leftover directory is never reused.
+        std::fs::create_dir_all(ctx.path().join("dbs/stale")).unwrap();
+        let err = manager
+            .attach_database("stale", Path::new("nightly"))
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+
+        // A failed attach leaves no directory behind.
+        let broken = backups.join("broken");
+        std::fs::create_dir_all(&broken).unwrap();
+        for file in ARCHIVE_FILES {
+            std::fs::write(broken.join(file), b"").unwrap();
+        }
+        assert!(
+            manager
+                .attach_database("broken", Path::new("broken"))
+                .is_err()
+        );
+        assert!(!manager.exists("broken"));
+        assert!(!ctx.path().join("dbs/broken").exists());
+
+        let db = manager
+            .attach_database("nightly", Path::new("nightly"))
+            .unwrap();
+        let result = db
+            .write()
+            .execute_cypher("MATCH (p:Person) RETURN p.name")
+            .unwrap();
+        assert_eq!(result.rows.len(), 1);
+    }
 }
@@ -0,0 +1,213 @@
+//! Operator introspection for the engine caches, the WAL and open
+//! explicit transactions.
+//!
+//! These back the server's `/admin/cache`, `/admin/wal` and
+//! `/admin/transactions` endpoints (and `nexus admin cache|wal|tx`):
+//!
+//! - [`Engine::cache_report`] / [`Engine::flush_caches`] — page cache
+//!   occupancy and hit rate per layer; flushing drops everything that
+//!   can be rebuilt from storage, exactly like hard memory pressure.
+//! - [`Engine::wal_info`] / [`Engine::checkpoint_wal`] — log size and
+//!   writer backlog; a checkpoint makes the record stores durable and
+//!   then appends a checkpoint marker. The log is not truncated, since
+//!   startup still replays external-id assignments from it.
+//! - [`Engine::open_transactions`] / [`Engine::kill_transaction`] —
+//!   explicit transactions held by sessions; killing one rolls it back
+//!   the same way the idle-transaction reaper does, keeping the session.
+
+use super::Engine;
+use crate::cache::CacheLayer;
+use crate::memory_management::MemoryPressureLevel;
+use crate::session::OpenTransaction;
+use crate::{Result, wal};
+
+/// One layer of the multi-layer cache in a [`CacheReport`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct CacheLayerReport {
+    /// Layer name
+    pub layer: CacheLayer,
+    /// Entries currently cached
+    pub entries: usize,
+    /// Approximate memory held, in bytes
+    pub memory_bytes: usize,
+    /// Hits over lookups since startup
+    pub hit_rate: f64,
+    /// Entries evicted since startup
+    pub evictions: u64,
+}
+
+/// Outcome of [`Engine::cache_report`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct CacheReport {
+    /// Pages in the page cache
+    pub page_cache_pages: usize,
+    /// Current page cache capacity, in pages
+    pub page_cache_capacity: usize,
+    /// Capacity the page cache was configured with; larger than
+    /// `page_cache_capacity` while memory pressure has it shrunk
+    pub page_cache_configured_capacity: usize,
+    /// Page cache hits since startup
+    pub page_cache_hits: u64,
+    /// Page cache misses since startup
+    pub page_cache_misses: u64,
+    /// `hits / (hits + misses)`
+    pub page_cache_hit_rate: f64,
+    /// Multi-layer cache, one entry per layer, sorted by name
+    pub layers: Vec<CacheLayerReport>,
+}
+
+/// Outcome of [`Engine::flush_caches`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct CacheFlush {
+    /// Pages dropped from the page cache
+    pub pages_dropped: usize,
+}
+
+/// Outcome of [`Engine::wal_info`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct WalInfo {
+    /// Log file path
+    pub path: String,
+    /// Log file size on disk, in bytes
+    pub file_bytes: u64,
+    /// Entries written since startup
+    pub entries_written: u64,
+    /// Entries submitted but not yet written by the background writer
+    pub pending_entries: u64,
+    /// Write failures reported by the background writer
+    pub write_errors: u64,
+    /// Current transaction epoch
+    pub current_epoch: u64,
+    /// When the log was last truncated (Unix milliseconds), if it has
+    /// been since startup
+    pub last_truncated_unix_ms: Option<u64>,
+}
+
+/// Outcome of [`Engine::checkpoint_wal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct WalCheckpoint {
+    /// Epoch recorded in the checkpoint marker
+    pub epoch: u64,
+}
+
+impl Engine {
+    /// Page cache occupancy and per-layer cache statistics.
+    pub fn cache_report(&mut self) -> CacheReport {
+        let page_stats = self.page_cache.stats();
+        let hits = self.page_cache.hit_count();
+        let misses = self.page_cache.miss_count();
+        let stats = self.cache.stats();
+
+        let mut layers: Vec<CacheLayerReport> = stats
+            .sizes
+            .keys()
+            .chain(stats.hit_rates.keys())
+            .copied()
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .map(|layer| CacheLayerReport {
+                layer,
+                entries: stats.sizes.get(&layer).copied().unwrap_or(0),
+                memory_bytes: stats.memory_usage.get(&layer).copied().unwrap_or(0),
+                hit_rate: stats.hit_rates.get(&layer).copied().unwrap_or(0.0),
+                evictions: stats.evictions.get(&layer).copied().unwrap_or(0),
+            })
+            .collect();
+        layers.sort_by_key(|l| format!("{:?}", l.layer));
+
+        CacheReport {
+            page_cache_pages: page_stats.cache_size,
+            page_cache_capacity: self.page_cache.capacity(),
+            page_cache_configured_capacity: self.page_cache.configured_capacity(),
+            page_cache_hits: hits,
+            page_cache_misses: misses,
+            page_cache_hit_rate: if hits + misses == 0 {
+                0.0
+            } else {
+                hits as f64 / (hits + misses) as f64
+            },
+            layers,
+        }
+    }
+
+    /// Drop every cached page, query result and object. Dirty pages are
+    /// written back first; the relationship and property indexes stay.
+    pub fn flush_caches(&mut self) -> Result<CacheFlush> {
+        let before = self.page_cache.len();
+        self.page_cache.clear()?;
+        self.cache
+            .shed_for_memory_pressure(MemoryPressureLevel::Hard);
+        Ok(CacheFlush {
+            pages_dropped: before.saturating_sub(self.page_cache.len()),
+        })
+    }
+
+    /// Size and writer backlog of the write-ahead log.
+    pub fn wal_info(&self) -> Result<WalInfo> {
+        let path = self.wal.path();
+        let file_bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        // The background writer appends through its own handle, so its
+        // counters are the live ones when it runs.
+        let (entries_written, pending_entries, write_errors) = match self.async_wal_stats() {
+            Some(stats) => (
+                stats.entries_written,
+                stats.current_queue_depth,
+                stats.wal_errors,
+            ),
+            None => (self.wal.entry_count(), 0, 0),
+        };
+        Ok(WalInfo {
+            path: path.display().to_string(),
+            file_bytes,
+            entries_written,
+            pending_entries,
+            write_errors,
+            current_epoch: self.transaction_manager.read().current_epoch(),
+            last_truncated_unix_ms: self.wal.stats().last_truncated_unix_ms,
+        })
+    }
+
+    /// Flush the record stores to disk and append a checkpoint marker
+    /// at the current epoch. Rejected while the engine is read-only.
+    pub fn checkpoint_wal(&mut self) -> Result<WalCheckpoint> {
+        self.ensure_writable()?;
+        self.storage.flush()?;
+        let epoch = self.transaction_manager.read().current_epoch();
+        self.write_wal_async(wal::WalEntry::Checkpoint { epoch })?;
+        self.flush_async_wal()?;
+        Ok(WalCheckpoint { epoch })
+    }
+
+    /// Explicit transactions currently open, oldest first.
+    pub fn open_transactions(&self) -> Vec<OpenTransaction> {
+        self.session_manager.open_transactions()
+    }
+
+    /// Roll back the open transaction `tx_id`. The owning session stays
+    /// open, so its client gets a "no active transaction" error on its
+    /// next COMMIT. Returns the transaction as it was before the
+    /// rollback, or `None` if no session holds it.
+    pub fn kill_transaction(&mut self, tx_id: u64) -> Result<Option<OpenTransaction>> {
+        let Some(open) = self
+            .open_transactions()
+            .into_iter()
+            .find(|tx| tx.tx_id == tx_id)
+        else {
+            return Ok(None);
+        };
+        let Some(mut session) = self.session_manager.take_session(&open.session_id) else {
+            return Ok(None);
+        };
+        let last_activity = session.last_activity;
+        self.rollback_session_transaction(&mut session)?;
+        session.last_activity = last_activity;
+        self.session_manager.update_session(session);
+        self.refresh_executor()?;
+        tracing::warn!(
+            tx_id,
+            session_id = %open.session_id,
+            "transaction killed by operator"
+        );
+        Ok(Some(open))
+    }
+}
@@ -0,0 +1,248 @@
+//! Versioned analysis artifacts.
+//!
+//! Clustering results, layouts and other analysis outputs are computed
+//! on demand and thrown away. [`ArtifactStore`] keeps named results on
+//! disk next to the database they were computed from, so a nightly
+//! community-detection run can be compared with last week's.
+//!
+//! Each save appends a new version; earlier versions stay readable
+//! until deleted. Layout on disk:
+//!
+//! ```text
+//! <data_dir>/artifacts/<name>/v<version>.json
+//! ```
+//!
+//! Every file holds one [`Artifact`] written via tmp-then-rename, so a
+//! crash mid-save never leaves a truncated version behind. Payloads
+//! are opaque JSON; `kind` (`"clustering"`, `"layout"`, ...) is a free
+//! tag for clients.
+
+use crate::{Error, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Longest accepted artifact name.
+const MAX_NAME_LEN: usize = 128;
+
+/// One stored version of a named artifact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Artifact name.
+    pub name: String,
+    /// Version, starting at 1 and increasing with every save.
+    pub version: u64,
+    /// Free-form tag describing the payload (`"clustering"`, `"layout"`, ...).
+    pub kind: String,
+    /// When this version was saved.
+    pub created_at: DateTime<Utc>,
+    /// The saved result.
+    pub payload: serde_json::Value,
+}
+
+/// Metadata of one version, without the payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArtifactVersion {
+    /// Version number.
+    pub version: u64,
+    /// Payload tag.
+    pub kind: String,
+    /// When this version was saved.
+    pub created_at: DateTime<Utc>,
+    /// Size of the stored file in bytes.
+    pub size_bytes: u64,
+}
+
+/// One named artifact and its newest version.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ArtifactSummary {
+    /// Artifact name.
+    pub name: String,
+    /// Number of stored versions.
+    pub versions: usize,
+    /// Newest version's metadata.
+    pub latest: ArtifactVersion,
+}
+
+/// On-disk store of versioned artifacts for one database.
+///
+/// Cheap to clone; clones share the save lock so concurrent saves of
+/// the same name get distinct versions.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    dir: PathBuf,
+    save_lock: Arc<Mutex<()>>,
+}
+
+impl ArtifactStore {
+    /// Open the store rooted at `dir`. The directory is created on the
+    /// first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            save_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Save `payload` as the next version of `name`.
+    pub fn save(&self, name: &str, kind: &str, payload: serde_json::Value) -> Result<Artifact> {
+        validate_name(name)?;
+        let _guard = self.save_lock.lock();
+        let dir = self.dir.join(name);
+        std::fs::create_dir_all(&dir)?;
+        let version = version_numbers(&dir)?.last().copied().unwrap_or(0) + 1;
+        let artifact = Artifact {
+            name: name.to_string(),
+            version,
+            kind: kind.to_string(),
+            created_at: Utc::now(),
+            payload,
+        };
+        let bytes = serde_json::to_vec(&artifact)?;
+        let tmp = dir.join(format!("v{version}.json.tmp"));
+        std::fs::write(&tmp, &bytes)?;
+        std::fs::rename(&tmp, version_path(&dir, version))?;
+        Ok(artifact)
+    }
+
+    /// Load `version` of `name`, or the newest version when `None`.
+    pub fn get(&self, name: &str, version: Option<u64>) -> Result<Option<Artifact>> {
+        validate_name(name)?;
+        let dir = self.dir.join(name);
+        let version = match version {
+            Some(v) => v,
+            None => match version_numbers(&dir)?.last() {
+                Some(&v) => v,
+                None => return Ok(None),
+            },
+        };
+        read_artifact(&version_path(&dir, version))
+    }
+
+    /// Metadata of every stored version of `name`, oldest first.
+    pub fn versions(&self, name: &str) -> Result<Vec<ArtifactVersion>> {
+        validate_name(name)?;
+        let dir = self.dir.join(name);
+        let mut out = Vec::new();
+        for version in version_numbers(&dir)? {
+            let path = version_path(&dir, version);
+            if let Some(artifact) = read_artifact(&path)? {
+                out.push(ArtifactVersion {
+                    version,
+                    kind: artifact.kind,
+                    created_at: artifact.created_at,
+                    size_bytes: std::fs::metadata(&path)?.len(),
+                });
+            }
+        }
+        Ok(out)
+    }
+
+    /// Every artifact with at least one version, sorted by name.
+    pub fn list(&self) -> Result<Vec<ArtifactSummary>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                if let Some(name) = entry.file_name().to_str() {
+                    if validate_name(name).is_ok() {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        names.sort();
+
+        let mut out = Vec::new();
+        for name in names {
+            let mut versions = self.versions(&name)?;
+            if let Some(latest) = versions.pop() {
+                out.push(ArtifactSummary {
+                    name,
+                    versions: versions.len() + 1,
+                    latest,
+                });
+            }
+        }
+        Ok(out)
+    }
+
+    /// Delete `version` of `name`, or every version when `None`.
+    /// Returns how many versions were removed.
+    pub fn delete(&self, name: &str, version: Option<u64>) -> Result<usize> {
+        validate_name(name)?;
+        let _guard = self.save_lock.lock();
+        let dir = self.dir.join(name);
+        let targets = match version {
+            Some(v) => version_numbers(&dir)?
+                .into_iter()
+                .filter(|&n| n == v)
+                .collect(),
+            None => version_numbers(&dir)?,
+        };
+        for v in &targets {
+            std::fs::remove_file(version_path(&dir, *v))?;
+        }
+        if version_numbers(&dir)?.is_empty() && dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        Ok(targets.len())
+    }
+}
+
+/// Names become directory names: ASCII letters, digits, `-`, `_` and
+/// `.`, not starting with `.`.
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && !name.starts_with('.')
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::CypherExecution(format!(
+            "ERR_INVALID_ARG_VALUE: invalid artifact name {name:?} \
+             (use 1-{MAX_NAME_LEN} of [A-Za-z0-9_.-], not starting with '.')"
+        )))
+    }
+}
+
+fn version_path(dir: &Path, version: u64) -> PathBuf {
+    dir.join(format!("v{version}.json"))
+}
+
+/// Stored version numbers under `dir`, ascending.
+fn version_numbers(dir: &Path) -> Result<Vec<u64>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut versions = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let file_name = entry?.file_name();
+        let parsed = file_name
+            .to_str()
+            .and_then(|n| n.strip_prefix('v'))
+            .and_then(|n| n.strip_suffix(".json"))
+            .and_then(|n| n.parse::<u64>().ok());
+        if let Some(v) = parsed {
+            versions.push(v);
+        }
+    }
+    versions.sort_unstable();
+    Ok(versions)
+}
+
+fn read_artifact(path: &Path) -> Result<Option<Artifact>> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -34,12 +34,57 @@ pub struct EngineConfig {
     /// (8 MB), which is tiny for any real workload but safe on cold
     /// start.
     pub page_cache_capacity: usize,
+    /// Startup integrity scan depth and corruption policy. Defaults to a
+    /// quick scan that refuses to open on failure.
+    pub integrity_check: super::integrity::IntegrityCheckConfig,
+    /// Idle limits after which explicit transactions are rolled back and
+    /// sessions closed by `Engine::reap_idle_sessions`.
+    pub session_timeouts: crate::session::SessionTimeoutConfig,
+    /// Property bloom filters built (from existing data) at startup.
+    /// Filters live in memory only, so this is how they survive restarts.
+    pub property_bloom_filters: Vec<super::property_bloom::PropertyBloomFilterSpec>,
+    /// Node TTL policies registered (and backfilled) at startup. Like the
+    /// bloom filters, the TTL index lives in memory only.
+    pub ttl_policies: Vec<super::ttl::TtlPolicySpec>,
+    /// Count aggregates registered (and backfilled) at startup. Their
+    /// running counts live in memory only.
+    pub count_aggregates: Vec<super::count_aggregates::CountAggregateSpec>,
+    /// Property history policies registered at startup. Policies live
+    /// in memory only; the recorded history is persistent.
+    pub property_history: Vec<super::property_history::PropertyHistorySpec>,
+    /// Unicode normal form applied to query text, parameters and CRUD
+    /// property values before they reach storage. Off by default;
+    /// existing data is not rewritten when this changes.
+    pub string_normalization: crate::text::StringNormalization,
+    /// Allowed hosts, timeout and size limit for the `nexus.load.*`
+    /// remote data functions. The default allows no hosts.
+    pub external_data: crate::external_data::ExternalDataConfig,
+    /// Backend that holds the node, relationship and property store
+    /// files. Defaults to memory-mapped files in the data directory.
+    pub storage_backend: std::sync::Arc<dyn crate::storage::StorageBackend>,
+    /// Per-template plan recording for plan regression detection. Off
+    /// by default.
+    pub plan_history: crate::performance::plan_history::PlanHistoryConfig,
+    /// What an explicit `COMMIT` waits for: a store sync (default) or
+    /// only the WAL fsync, with the stores synced in the background.
+    pub write_pipeline: super::write_pipeline::WritePipelineConfig,
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
         Self {
             page_cache_capacity: 1024,
+            integrity_check: Default::default(),
+            session_timeouts: Default::default(),
+            property_bloom_filters: Vec::new(),
+            ttl_policies: Vec::new(),
+            count_aggregates: Vec::new(),
+            property_history: Vec::new(),
+            string_normalization: Default::default(),
+            external_data: Default::default(),
+            storage_backend: crate::storage::backend::default_backend(),
+            plan_history: Default::default(),
+            write_pipeline: Default::default(),
         }
     }
 }
@@ -3,6 +3,7 @@
 
 use super::Engine;
 use super::typed_collections;
+use crate::constraints::ConstraintViolation;
 use crate::{Error, Result, catalog};
 use std::collections::HashMap;
 
@@ -162,6 +163,268 @@ impl Engine {
         Ok(())
     }
 
+    /// Define an enumeration property type on `(label, property)`.
+    ///
+    /// Writes to the property on nodes carrying `label` must use one of
+    /// `variants` (or NULL); the value is stored as its ordinal and read
+    /// back as the string. Existing nodes are validated first (offending
+    /// rows abort the definition) and then re-encoded, so redefining an
+    /// enum with a different ordering is safe.
+    pub fn add_enum_property_type(
+        &mut self,
+        label: &str,
+        property: &str,
+        variants: &[&str],
+    ) -> Result<()> {
+        if variants.is_empty() {
+            return Err(Error::CypherSyntax(
+                "enum property type requires at least one value".to_string(),
+            ));
+        }
+        let variants: Vec<String> = variants.iter().map(|s| s.to_string()).collect();
+        if let Some(dup) = variants
+            .iter()
+            .enumerate()
+            .find(|(i, v)| variants[..*i].contains(v))
+        {
+            return Err(Error::CypherSyntax(format!(
+                "enum property type lists {:?} more than once",
+                dup.1
+            )));
+        }
+        let label_id = self.catalog.get_or_create_label(label)?;
+        if label_id >= 64 {
+            return Err(Error::CypherExecution(format!(
+                "enum property types are not supported on label {label:?} (id {label_id} \
+                 exceeds the 64-label record bitmap)"
+            )));
+        }
+        let key_id = self.catalog.get_or_create_key(property)?;
+
+        // Backfill scan — values are read through any previous definition,
+        // so `existing` holds plain strings ready to be re-encoded.
+        let bitmap = self
+            .indexes
+            .label_index
+            .get_nodes_with_labels(&[label_id])?;
+        let mut report = crate::constraints::BackfillReport::default();
+        let mut existing = Vec::new();
+        for nid in bitmap.iter() {
+            let nid = nid as u64;
+            report.total_scanned += 1;
+            let props = match self.storage.load_node_properties(nid)? {
+                Some(serde_json::Value::Object(m)) => m,
+                _ => continue,
+            };
+            match props.get(property) {
+                None | Some(serde_json::Value::Null) => continue,
+                Some(serde_json::Value::String(s)) if variants.contains(s) => {}
+                Some(v) => report.record(
+                    nid,
+                    format!("node :{label}.{property} is {v}, expected one of {variants:?}"),
+                ),
+            }
+            existing.push((nid, props));
+        }
+        if report.has_violations() {
+            return Err(report.into_error("PROPERTY_ENUM"));
+        }
+
+        self.catalog
+            .persist_enum_property_type(label_id, key_id, &variants)?;
+        self.storage
+            .enum_types()
+            .write()
+            .unwrap()
+            .define(label_id, property, variants);
+        for (nid, props) in existing {
+            self.storage
+                .update_node_properties(nid, serde_json::Value::Object(props))?;
+        }
+        Ok(())
+    }
+
+    /// Remove an enumeration property type. Stored ordinals are rewritten
+    /// back to plain strings. No-op when nothing is registered.
+    pub fn drop_enum_property_type(&mut self, label: &str, property: &str) -> Result<()> {
+        let Ok(label_id) = self.catalog.get_label_id(label) else {
+            return Ok(());
+        };
+        let Ok(key_id) = self.catalog.get_key_id(property) else {
+            return Ok(());
+        };
+        if self
+            .storage
+            .enum_types()
+            .read()
+            .unwrap()
+            .variants(label_id, property)
+            .is_none()
+        {
+            return Ok(());
+        }
+        // Decode while the definition is still registered.
+        let bitmap = self
+            .indexes
+            .label_index
+            .get_nodes_with_labels(&[label_id])?;
+        let mut existing = Vec::new();
+        for nid in bitmap.iter() {
+            let nid = nid as u64;
+            if let Some(serde_json::Value::Object(m)) = self.storage.load_node_properties(nid)? {
+                if m.contains_key(property) {
+                    existing.push((nid, m));
+                }
+            }
+        }
+        self.catalog.remove_enum_property_type(label_id, key_id)?;
+        self.storage
+            .enum_types()
+            .write()
+            .unwrap()
+            .remove(label_id, property);
+        for (nid, props) in existing {
+            self.storage
+                .update_node_properties(nid, serde_json::Value::Object(props))?;
+        }
+        Ok(())
+    }
+
+    /// Every registered enumeration property type as
+    /// `(label, property, allowed values)`.
+    pub fn enum_property_types(&self) -> Result<Vec<(String, String, Vec<String>)>> {
+        let registry = self.storage.enum_types().read().unwrap();
+        let mut out = Vec::new();
+        for (label_id, key, variants) in registry.iter() {
+            let label = self
+                .catalog
+                .get_label_name(label_id)?
+                .unwrap_or_else(|| format!("ID{label_id}"));
+            out.push((label, key.to_string(), variants.to_vec()));
+        }
+        Ok(out)
+    }
+
+    /// Load the durable enum definitions into the record store's codec.
+    /// Runs at startup before anything reads node properties.
+    pub(super) fn load_enum_property_types(&self) -> Result<()> {
+        let mut registry = self.storage.enum_types().write().unwrap();
+        for ((label_id, key_id), variants) in self.catalog.list_enum_property_types()? {
+            match self.catalog.get_key_name(key_id)? {
+                Some(key) => registry.define(label_id, &key, variants),
+                None => tracing::warn!(
+                    "enum property type ({label_id},{key_id}) references an unknown key; skipped"
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Register a relationship endpoint constraint:
+    /// `FOR (:Source)-[r:TYPE]->(:Target) REQUIRE ENDPOINTS`. Either
+    /// label may be omitted to leave that side unconstrained, but not
+    /// both. Replaces any previous endpoint constraint on the type.
+    /// Backfill rejects existing relationships whose endpoints lack the
+    /// required labels.
+    pub fn add_rel_endpoint_constraint(
+        &mut self,
+        rel_type: &str,
+        source_label: Option<&str>,
+        target_label: Option<&str>,
+    ) -> Result<()> {
+        if source_label.is_none() && target_label.is_none() {
+            return Err(Error::CypherSyntax(
+                "endpoint constraint requires a label on at least one endpoint".to_string(),
+            ));
+        }
+        let rel_type_id = self.catalog.get_or_create_type(rel_type)?;
+        let label_id = |label: Option<&str>| -> Result<Option<u32>> {
+            let Some(label) = label else {
+                return Ok(None);
+            };
+            let id = self.catalog.get_or_create_label(label)?;
+            if id >= 64 {
+                return Err(Error::CypherExecution(format!(
+                    "endpoint constraint label :{label} has id {id}; only the first 64 labels \
+                     can be constrained"
+                )));
+            }
+            Ok(Some(id))
+        };
+        let constraint = crate::constraints::RelEndpointConstraint {
+            rel_type_id,
+            source_label_id: label_id(source_label)?,
+            target_label_id: label_id(target_label)?,
+        };
+        self.backfill_rel_endpoints(&constraint, rel_type)?;
+        self.catalog.persist_rel_endpoint_constraint(
+            rel_type_id,
+            constraint.source_label_id,
+            constraint.target_label_id,
+        )?;
+        self.rel_endpoint_constraints
+            .insert(rel_type_id, constraint);
+        Ok(())
+    }
+
+    /// Remove the endpoint constraint on `rel_type`. Returns `true`
+    /// when one was registered.
+    pub fn drop_rel_endpoint_constraint(&mut self, rel_type: &str) -> Result<bool> {
+        let Some(rel_type_id) = self.catalog.get_type_id(rel_type)? else {
+            return Ok(false);
+        };
+        self.rel_endpoint_constraints.remove(&rel_type_id);
+        self.catalog.remove_rel_endpoint_constraint(rel_type_id)
+    }
+
+    /// Every endpoint constraint as `(type, source label, target label)`.
+    pub fn rel_endpoint_constraints(
+        &self,
+    ) -> Result<Vec<(String, Option<String>, Option<String>)>> {
+        let mut constraints: Vec<_> = self.rel_endpoint_constraints.values().copied().collect();
+        constraints.sort_by_key(|c| c.rel_type_id);
+        let label_name = |id: Option<u32>| -> Result<Option<String>> {
+            id.map(|id| {
+                Ok(self
+                    .catalog
+                    .get_label_name(id)?
+                    .unwrap_or_else(|| format!("ID{id}")))
+            })
+            .transpose()
+        };
+        let mut out = Vec::with_capacity(constraints.len());
+        for c in constraints {
+            let rel_type = self
+                .catalog
+                .get_type_name(c.rel_type_id)?
+                .unwrap_or_else(|| format!("ID{}", c.rel_type_id));
+            out.push((
+                rel_type,
+                label_name(c.source_label_id)?,
+                label_name(c.target_label_id)?,
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Load the durable endpoint constraints. Runs at startup.
+    pub(super) fn load_rel_endpoint_constraints(&mut self) -> Result<()> {
+        self.rel_endpoint_constraints.clear();
+        for (rel_type_id, (source_label_id, target_label_id)) in
+            self.catalog.list_rel_endpoint_constraints()?
+        {
+            self.rel_endpoint_constraints.insert(
+                rel_type_id,
+                crate::constraints::RelEndpointConstraint {
+                    rel_type_id,
+                    source_label_id,
+                    target_label_id,
+                },
+            );
+        }
+        Ok(())
+    }
+
     // ────────── Backfill validators (§8) ──────────
 
     /// Verify every existing node with `label_id` has non-NULL values
@@ -252,6 +515,32 @@ impl Engine {
         Ok(())
     }
 
+    fn backfill_rel_endpoints(
+        &self,
+        constraint: &crate::constraints::RelEndpointConstraint,
+        rel_type: &str,
+    ) -> Result<()> {
+        let mut report = crate::constraints::BackfillReport::default();
+        for rid in 0..self.storage.relationship_count() {
+            let rec = match self.storage.read_rel(rid) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+            if rec.is_deleted() || rec.type_id != constraint.rel_type_id {
+                continue;
+            }
+            report.total_scanned += 1;
+            let (src, dst) = (rec.src_id, rec.dst_id);
+            if let Some(reason) = self.rel_endpoint_mismatch(constraint, src, dst) {
+                report.record(rid, format!("rel :{rel_type} {reason}"));
+            }
+        }
+        if report.has_violations() {
+            return Err(report.into_error("RELATIONSHIP_ENDPOINT"));
+        }
+        Ok(())
+    }
+
     fn backfill_property_type(
         &self,
         label_id: u32,
@@ -300,12 +589,13 @@ impl Engine {
     /// Extra constraint checks that run alongside the legacy
     /// `check_constraints` path. Called from every site that writes
     /// node properties. Applies property-type constraints and NODE
-    /// KEY uniqueness/NOT-NULL.
+    /// KEY uniqueness/NOT-NULL; `exclude_node_ids` as in
+    /// `check_constraints`.
     pub(crate) fn enforce_extended_node_constraints(
         &self,
         label_ids: &[u32],
         properties: &serde_json::Value,
-        exclude_node_id: Option<u64>,
+        exclude_node_ids: &[u64],
     ) -> Result<()> {
         // Property-type checks (node-scoped).
         if let Some(props) = properties.as_object() {
@@ -321,13 +611,20 @@ impl Engine {
                         continue;
                     }
                     if !c.ty.accepts(v) {
-                        return self.maybe_violation(format!(
-                            "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE property={:?} \
-                             expected={} got={}",
-                            c.property_key,
-                            c.ty.name(),
-                            super::json_type_label(v),
-                        ));
+                        return self.maybe_violation(
+                            ConstraintViolation::new("PROPERTY_TYPE", "NODE")
+                                .named(c.name.as_deref())
+                                .label_or_type(self.label_name_or_id(label_id))
+                                .value(c.property_key.clone(), v.clone())
+                                .offending(exclude_node_ids.first().copied())
+                                .with_message(format!(
+                                    "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE property={:?} \
+                                     expected={} got={}",
+                                    c.property_key,
+                                    c.ty.name(),
+                                    super::json_type_label(v),
+                                )),
+                        );
                     }
                 }
             }
@@ -346,9 +643,16 @@ impl Engine {
             for p in &nk.property_keys {
                 match obj.get(p) {
                     None | Some(serde_json::Value::Null) => {
-                        return self.maybe_violation(format!(
-                            "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY property={p:?} is NULL"
-                        ));
+                        return self.maybe_violation(
+                            ConstraintViolation::new("NODE_KEY", "NODE")
+                                .named(nk.name.as_deref())
+                                .label_or_type(self.label_name_or_id(nk.label_id))
+                                .value(p.clone(), serde_json::Value::Null)
+                                .offending(exclude_node_ids.first().copied())
+                                .with_message(format!(
+                                    "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY property={p:?} is NULL"
+                                )),
+                        );
                     }
                     Some(v) => tuple_vals.push(super::json_to_property_value(v)),
                 }
@@ -360,11 +664,19 @@ impl Engine {
                 .find(nk.label_id, &nk.property_keys)
             {
                 let hits = idx.read().seek_exact(&tuple_vals);
-                if hits.iter().any(|id| Some(*id) != exclude_node_id) {
-                    return self.maybe_violation(format!(
+                if let Some(&conflict) = hits.iter().find(|&&id| !exclude_node_ids.contains(&id)) {
+                    let mut violation = ConstraintViolation::new("NODE_KEY", "NODE")
+                        .named(nk.name.as_deref())
+                        .label_or_type(self.label_name_or_id(nk.label_id))
+                        .offending(exclude_node_ids.first().copied())
+                        .conflicting(conflict);
+                    for p in &nk.property_keys {
+                        violation = violation.value(p.clone(), obj[p].clone());
+                    }
+                    return self.maybe_violation(violation.with_message(format!(
                         "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY tuple={:?} not unique",
                         nk.property_keys,
-                    ));
+                    )));
                 }
             }
         }
@@ -385,11 +697,17 @@ impl Engine {
             }
             let v = obj.and_then(|m| m.get(&c.property_key));
             if !matches!(v, Some(v) if !matches!(v, serde_json::Value::Null)) {
-                return self.maybe_violation(format!(
-                    "ERR_CONSTRAINT_VIOLATED: kind=RELATIONSHIP_PROPERTY_EXISTENCE \
-                     property={:?} must be non-null",
-                    c.property_key,
-                ));
+                return self.maybe_violation(
+                    ConstraintViolation::new("RELATIONSHIP_PROPERTY_EXISTENCE", "RELATIONSHIP")
+                        .named(c.name.as_deref())
+                        .label_or_type(self.type_name_or_id(rel_type_id))
+                        .value(c.property_key.clone(), serde_json::Value::Null)
+                        .with_message(format!(
+                            "ERR_CONSTRAINT_VIOLATED: kind=RELATIONSHIP_PROPERTY_EXISTENCE \
+                             property={:?} must be non-null",
+                            c.property_key,
+                        )),
+                );
             }
         }
         if let Some(obj) = obj {
@@ -405,13 +723,19 @@ impl Engine {
                         continue;
                     }
                     if !c.ty.accepts(v) {
-                        return self.maybe_violation(format!(
-                            "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE (rel) \
-                             property={:?} expected={} got={}",
-                            c.property_key,
-                            c.ty.name(),
-                            super::json_type_label(v),
-                        ));
+                        return self.maybe_violation(
+                            ConstraintViolation::new("PROPERTY_TYPE", "RELATIONSHIP")
+                                .named(c.name.as_deref())
+                                .label_or_type(self.type_name_or_id(rel_type_id))
+                                .value(c.property_key.clone(), v.clone())
+                                .with_message(format!(
+                                    "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE (rel) \
+                                     property={:?} expected={} got={}",
+                                    c.property_key,
+                                    c.ty.name(),
+                                    super::json_type_label(v),
+                                )),
+                        );
                     }
                 }
             }
@@ -419,6 +743,73 @@ impl Engine {
         Ok(())
     }
 
+    /// Reject a relationship whose endpoints lack the labels required by
+    /// the endpoint constraint on `rel_type_id`.
+    pub(crate) fn enforce_rel_endpoint_constraint(
+        &self,
+        rel_type_id: u32,
+        from: u64,
+        to: u64,
+    ) -> Result<()> {
+        let Some(constraint) = self.rel_endpoint_constraints.get(&rel_type_id) else {
+            return Ok(());
+        };
+        let reason = self.rel_endpoint_mismatch(constraint, from, to);
+        self.rel_endpoint_violation(rel_type_id, reason)
+    }
+
+    /// [`Self::enforce_rel_endpoint_constraint`] for endpoints known only
+    /// by their label bits, such as nodes a statement has yet to create.
+    /// `source` and `target` name the endpoints in the violation message.
+    pub(crate) fn enforce_rel_endpoint_labels(
+        &self,
+        rel_type_id: u32,
+        (source, source_bits): (&str, u64),
+        (target, target_bits): (&str, u64),
+    ) -> Result<()> {
+        let Some(constraint) = self.rel_endpoint_constraints.get(&rel_type_id) else {
+            return Ok(());
+        };
+        let reason =
+            endpoint_label_mismatch(constraint, (source, source_bits), (target, target_bits));
+        self.rel_endpoint_violation(rel_type_id, reason)
+    }
+
+    fn rel_endpoint_violation(&self, rel_type_id: u32, reason: Option<String>) -> Result<()> {
+        match reason {
+            Some(reason) => self.maybe_violation(
+                ConstraintViolation::new("RELATIONSHIP_ENDPOINT", "RELATIONSHIP")
+                    .label_or_type(self.type_name_or_id(rel_type_id))
+                    .with_message(format!(
+                        "ERR_CONSTRAINT_VIOLATED: kind=RELATIONSHIP_ENDPOINT \
+                         type_id={rel_type_id} {reason}"
+                    )),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Describe why `(from)-[]->(to)` breaks `constraint`, or `None` when
+    /// both endpoints carry the required labels.
+    fn rel_endpoint_mismatch(
+        &self,
+        constraint: &crate::constraints::RelEndpointConstraint,
+        from: u64,
+        to: u64,
+    ) -> Option<String> {
+        let bits = |id: u64| {
+            self.storage
+                .read_node(id)
+                .map(|r| r.label_bits)
+                .unwrap_or(0)
+        };
+        endpoint_label_mismatch(
+            constraint,
+            (&format!("source node {from}"), bits(from)),
+            (&format!("target node {to}"), bits(to)),
+        )
+    }
+
     /// Reject writes that would remove a required property / set it to
     /// NULL. Called from `apply_set_clause` / `apply_remove_clause`.
     pub(crate) fn enforce_not_null_on_prop_change(
@@ -443,10 +834,15 @@ impl Engine {
                     if name == property_key
                         && matches!(new_value, None | Some(serde_json::Value::Null))
                     {
-                        return self.maybe_violation(format!(
-                            "ERR_CONSTRAINT_VIOLATED: kind=NODE_PROPERTY_EXISTENCE \
-                             property={property_key:?} must be non-null",
-                        ));
+                        return self.maybe_violation(
+                            ConstraintViolation::new("NODE_PROPERTY_EXISTENCE", "NODE")
+                                .label_or_type(self.label_name_or_id(*label_id))
+                                .value(property_key, serde_json::Value::Null)
+                                .with_message(format!(
+                                    "ERR_CONSTRAINT_VIOLATED: kind=NODE_PROPERTY_EXISTENCE \
+                                     property={property_key:?} must be non-null",
+                                )),
+                        );
                     }
                 }
             }
@@ -459,10 +855,16 @@ impl Engine {
             if nk.property_keys.iter().any(|p| p == property_key)
                 && matches!(new_value, None | Some(serde_json::Value::Null))
             {
-                return self.maybe_violation(format!(
-                    "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY component={property_key:?} \
-                     cannot be NULL",
-                ));
+                return self.maybe_violation(
+                    ConstraintViolation::new("NODE_KEY", "NODE")
+                        .named(nk.name.as_deref())
+                        .label_or_type(self.label_name_or_id(nk.label_id))
+                        .value(property_key, serde_json::Value::Null)
+                        .with_message(format!(
+                            "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY component={property_key:?} \
+                             cannot be NULL",
+                        )),
+                );
             }
         }
         Ok(())
@@ -493,10 +895,15 @@ impl Engine {
                     .unwrap_or_default();
                 if !matches!(properties.get(&prop), Some(v) if !matches!(v, serde_json::Value::Null))
                 {
-                    return self.maybe_violation(format!(
-                        "ERR_CONSTRAINT_VIOLATED: kind=NODE_PROPERTY_EXISTENCE label={label:?} \
-                         property={prop:?} missing while adding label",
-                    ));
+                    return self.maybe_violation(
+                        ConstraintViolation::new("NODE_PROPERTY_EXISTENCE", "NODE")
+                            .label_or_type(label)
+                            .value(prop.clone(), serde_json::Value::Null)
+                            .with_message(format!(
+                                "ERR_CONSTRAINT_VIOLATED: kind=NODE_PROPERTY_EXISTENCE \
+                                 label={label:?} property={prop:?} missing while adding label",
+                            )),
+                    );
                 }
             }
         }
@@ -508,10 +915,16 @@ impl Engine {
             }
             for p in &nk.property_keys {
                 if !matches!(properties.get(p), Some(v) if !matches!(v, serde_json::Value::Null)) {
-                    return self.maybe_violation(format!(
-                        "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY label={label:?} component={p:?} \
-                         missing while adding label",
-                    ));
+                    return self.maybe_violation(
+                        ConstraintViolation::new("NODE_KEY", "NODE")
+                            .named(nk.name.as_deref())
+                            .label_or_type(label)
+                            .value(p.clone(), serde_json::Value::Null)
+                            .with_message(format!(
+                                "ERR_CONSTRAINT_VIOLATED: kind=NODE_KEY label={label:?} \
+                                 component={p:?} missing while adding label",
+                            )),
+                    );
                 }
             }
         }
@@ -525,13 +938,19 @@ impl Engine {
                     continue;
                 }
                 if !c.ty.accepts(v) {
-                    return self.maybe_violation(format!(
-                        "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE label={label:?} \
-                         property={:?} expected={} got={}",
-                        c.property_key,
-                        c.ty.name(),
-                        super::json_type_label(v),
-                    ));
+                    return self.maybe_violation(
+                        ConstraintViolation::new("PROPERTY_TYPE", "NODE")
+                            .named(c.name.as_deref())
+                            .label_or_type(label)
+                            .value(c.property_key.clone(), v.clone())
+                            .with_message(format!(
+                                "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE label={label:?} \
+                                 property={:?} expected={} got={}",
+                                c.property_key,
+                                c.ty.name(),
+                                super::json_type_label(v),
+                            )),
+                    );
                 }
             }
         }
@@ -554,21 +973,41 @@ impl Engine {
         Ok(out)
     }
 
-    pub(super) fn maybe_violation(&self, message: String) -> Result<()> {
+    /// Label name for violation payloads; `ID<n>` if it was dropped.
+    pub(crate) fn label_name_or_id(&self, label_id: u32) -> String {
+        self.catalog
+            .get_label_name(label_id)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| format!("ID{label_id}"))
+    }
+
+    /// Relationship type name for violation payloads; `ID<n>` if unknown.
+    pub(crate) fn type_name_or_id(&self, type_id: u32) -> String {
+        self.catalog
+            .get_type_name(type_id)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| format!("ID{type_id}"))
+    }
+
+    pub(super) fn maybe_violation(&self, violation: ConstraintViolation) -> Result<()> {
         if self.relaxed_constraint_enforcement {
-            tracing::warn!("relaxed_constraint_enforcement: {message}");
+            tracing::warn!("relaxed_constraint_enforcement: {}", violation.message);
             Ok(())
         } else {
-            Err(Error::ConstraintViolation(message))
+            Err(violation.into_error())
         }
     }
 
-    /// Check constraints before creating or updating a node
+    /// Check constraints before creating or updating a node. Nodes in
+    /// `exclude_node_ids` (the node being updated, or every node being
+    /// merged into one) are skipped by the uniqueness checks.
     pub(super) fn check_constraints(
         &self,
         label_ids: &[u32],
         properties: &serde_json::Value,
-        exclude_node_id: Option<u64>,
+        exclude_node_ids: &[u64],
     ) -> Result<()> {
         // phase6_opencypher-advanced-types §4.3 — typed-list
         // constraint enforcement. Run first so a clearly-typed
@@ -618,10 +1057,15 @@ impl Engine {
                                 .catalog
                                 .get_label_name(label_id)?
                                 .unwrap_or_else(|| format!("ID{}", label_id));
-                            return Err(Error::ConstraintViolation(format!(
-                                "EXISTS constraint violated: property '{}' must exist on nodes with label '{}'",
-                                property_name, label_name
-                            )));
+                            return Err(ConstraintViolation::new("NODE_PROPERTY_EXISTENCE", "NODE")
+                                .label_or_type(label_name.clone())
+                                .value(property_name.clone(), serde_json::Value::Null)
+                                .offending(exclude_node_ids.first().copied())
+                                .with_message(format!(
+                                    "EXISTS constraint violated: property '{}' must exist on nodes with label '{}'",
+                                    property_name, label_name
+                                ))
+                                .into_error());
                         }
                     }
                     catalog::constraints::ConstraintType::Unique => {
@@ -642,8 +1086,8 @@ impl Engine {
                             for node_id in bitmap.iter() {
                                 let node_id_u64 = node_id as u64;
 
-                                // Skip the node being updated
-                                if Some(node_id_u64) == exclude_node_id {
+                                // Skip the node(s) being updated
+                                if exclude_node_ids.contains(&node_id_u64) {
                                     continue;
                                 }
 
@@ -651,10 +1095,16 @@ impl Engine {
                                 if let Some(serde_json::Value::Object(props_map)) = node_props {
                                     if let Some(existing_value) = props_map.get(&property_name) {
                                         if existing_value == value {
-                                            return Err(Error::ConstraintViolation(format!(
-                                                "UNIQUE constraint violated: property '{}' value already exists on another node with label '{}'",
-                                                property_name, label_name
-                                            )));
+                                            return Err(ConstraintViolation::new("UNIQUENESS", "NODE")
+                                                .label_or_type(label_name.clone())
+                                                .value(property_name.clone(), value.clone())
+                                                .offending(exclude_node_ids.first().copied())
+                                                .conflicting(node_id_u64)
+                                                .with_message(format!(
+                                                    "UNIQUE constraint violated: property '{}' value already exists on another node with label '{}'",
+                                                    property_name, label_name
+                                                ))
+                                                .into_error());
                                         }
                                     }
                                 }
@@ -668,3 +1118,25 @@ impl Engine {
         Ok(())
     }
 }
+
+/// Describe which named endpoint lacks the label `constraint` requires,
+/// given each endpoint's label bits.
+fn endpoint_label_mismatch(
+    constraint: &crate::constraints::RelEndpointConstraint,
+    (source, source_bits): (&str, u64),
+    (target, target_bits): (&str, u64),
+) -> Option<String> {
+    if !constraint.accepts_source(source_bits) {
+        return Some(format!(
+            "{source} lacks required label_id={}",
+            constraint.source_label_id.unwrap_or_default()
+        ));
+    }
+    if !constraint.accepts_target(target_bits) {
+        return Some(format!(
+            "{target} lacks required label_id={}",
+            constraint.target_label_id.unwrap_or_default()
+        ));
+    }
+    None
+}
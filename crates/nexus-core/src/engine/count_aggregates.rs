@@ -0,0 +1,113 @@
+//! Count aggregates: registered per-label (optionally per-group) node
+//! counts kept current as writes land.
+//!
+//! See [`crate::index::count_aggregates`] for the registry itself. An
+//! aggregate is backfilled from the nodes already carrying its label
+//! and then fed by the same write hooks that maintain the TTL and typed
+//! property indexes: node creation, SET / REMOVE (properties and
+//! labels) and node deletion. A dashboard reading
+//! `CALL nexus.aggregate.counts(name)` gets the precomputed groups
+//! instead of re-running the label scan and aggregation.
+//!
+//! Aggregates are in-memory only; list them in
+//! [`super::EngineConfig::count_aggregates`] to rebuild them at startup.
+
+use super::Engine;
+use crate::index::CountAggregate;
+use crate::{Error, Result};
+use serde_json::Value;
+
+/// One count aggregate to register at startup.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CountAggregateSpec {
+    /// Name the aggregate is read back by.
+    pub name: String,
+    /// Label whose nodes are counted.
+    pub label: String,
+    /// Property the counts are grouped by; omit for a single total.
+    #[serde(default)]
+    pub group_by: Option<String>,
+}
+
+impl Engine {
+    /// Count nodes labelled `label`, grouped by `group_by` when given,
+    /// and keep the counts current on every write. Counts the nodes that
+    /// already exist before returning. Returns `false` when an aggregate
+    /// called `name` already exists.
+    ///
+    /// Fails with [`Error::InvalidInput`] for an empty name, label or
+    /// group property.
+    pub fn create_count_aggregate(
+        &mut self,
+        name: &str,
+        label: &str,
+        group_by: Option<&str>,
+    ) -> Result<bool> {
+        if name.is_empty() || label.is_empty() || group_by.is_some_and(str::is_empty) {
+            return Err(Error::invalid_input(
+                "count aggregate name, label and group property must not be empty",
+            ));
+        }
+        let label_id = self.catalog.get_or_create_label(label)?;
+        let registered = self.indexes.count_aggregates.register(CountAggregate {
+            name: name.to_string(),
+            label_id,
+            group_by: group_by.map(str::to_string),
+        });
+        if !registered {
+            return Ok(false);
+        }
+
+        let nodes = self
+            .indexes
+            .label_index
+            .get_nodes_with_labels(&[label_id])?;
+        for node_id in nodes.iter() {
+            let node_id = node_id as u64;
+            match self.storage.read_node(node_id) {
+                Ok(record) if !record.is_deleted() => {}
+                _ => continue,
+            }
+            let label_ids = self
+                .effective_label_ids_from_record(node_id)
+                .unwrap_or_default();
+            let properties = self
+                .storage
+                .load_node_properties(node_id)?
+                .unwrap_or(Value::Null);
+            self.indexes
+                .count_aggregates
+                .sync_node_into(name, node_id, &label_ids, &properties);
+        }
+        Ok(true)
+    }
+
+    /// Remove the count aggregate `name`. Returns whether it existed.
+    pub fn drop_count_aggregate(&mut self, name: &str) -> bool {
+        self.indexes.count_aggregates.drop_aggregate(name)
+    }
+
+    /// Registered count aggregates, by name.
+    pub fn count_aggregates(&self) -> Vec<CountAggregateSpec> {
+        self.indexes
+            .count_aggregates
+            .definitions()
+            .into_iter()
+            .filter_map(|aggregate| {
+                let label = self.catalog.get_label_name(aggregate.label_id).ok()??;
+                Some(CountAggregateSpec {
+                    name: aggregate.name,
+                    label,
+                    group_by: aggregate.group_by,
+                })
+            })
+            .collect()
+    }
+
+    /// Current `(group, count)` pairs of the aggregate `name`. The group
+    /// is NULL for an ungrouped aggregate and for nodes missing the group
+    /// property. `None` when no such aggregate is registered.
+    pub fn count_aggregate(&self, name: &str) -> Option<Vec<(Value, u64)>> {
+        self.indexes.count_aggregates.counts(name)
+    }
+}
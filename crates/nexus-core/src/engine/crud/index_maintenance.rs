@@ -426,15 +426,61 @@ impl Engine {
     /// ONLY for `(label, key)` pairs that already have a registered index.
     /// `add_property` would otherwise auto-create a tree (turning every
     /// property into a phantom index), so the `has_index` guard is required.
+    /// Also records the values in any registered property bloom filters,
+    /// syncs the node's embeddings into the named vector indexes, files
+    /// its expiry in the TTL index and counts it in the count aggregates.
     pub(in crate::engine) fn maintain_indexed_properties(
         &self,
         node_id: u64,
         label_ids: &[u32],
         properties: &serde_json::Value,
+    ) -> Result<()> {
+        self.vector_index_sync_node(node_id, label_ids, properties);
+        self.ttl_index_sync_node(node_id, label_ids, properties);
+        self.indexes
+            .count_aggregates
+            .sync_node(node_id, label_ids, properties);
+        self.maintain_property_indexes(node_id, label_ids, properties)
+    }
+
+    /// Re-file node `node_id` in every node index after its record was
+    /// put back to an earlier state: it is evicted everywhere, then, if
+    /// `restored` holds its label ids and properties, added back as a
+    /// live node. Used by the paths that undo a write without a storage
+    /// undo log.
+    pub(in crate::engine) fn reindex_restored_node(
+        &mut self,
+        node_id: u64,
+        restored: Option<(&[u32], &serde_json::Value)>,
+    ) -> Result<()> {
+        self.indexes.label_index.remove_node(node_id)?;
+        self.fts_evict_node(node_id);
+        self.spatial_evict_node(node_id);
+        self.vector_index_evict_node(node_id);
+        self.indexes.ttl.remove_node(node_id);
+        self.indexes.count_aggregates.remove_node(node_id);
+        let Some((label_ids, properties)) = restored else {
+            return Ok(());
+        };
+        self.indexes.label_index.add_node(node_id, label_ids)?;
+        self.maintain_indexed_properties(node_id, label_ids, properties)?;
+        self.index_composite_tuples(node_id, label_ids, properties)?;
+        self.fts_autopopulate_node(node_id, label_ids, properties)?;
+        self.spatial_autopopulate_node(node_id, label_ids, properties)
+    }
+
+    /// [`Self::maintain_indexed_properties`] minus the vector indexes:
+    /// typed property B-tree and bloom filters only.
+    fn maintain_property_indexes(
+        &self,
+        node_id: u64,
+        label_ids: &[u32],
+        properties: &serde_json::Value,
     ) -> Result<()> {
         let serde_json::Value::Object(props) = properties else {
             return Ok(());
         };
+        self.record_property_blooms(label_ids, props);
         // #21: fast-path — when no property index is registered at all (the
         // common case for un-indexed graphs), skip the per-property ×
         // per-label `get_key_id` / `has_index` loop entirely on every write.
@@ -472,6 +518,28 @@ impl Engine {
         Ok(())
     }
 
+    /// Record a written node's property values in the bloom filters
+    /// registered for its labels. Filters only ever gain values, so there
+    /// is no removal counterpart.
+    fn record_property_blooms(
+        &self,
+        label_ids: &[u32],
+        props: &serde_json::Map<String, serde_json::Value>,
+    ) {
+        let blooms = &self.indexes.property_bloom;
+        if !blooms.has_any_filter() || !label_ids.iter().any(|&l| blooms.has_filter_for_label(l)) {
+            return;
+        }
+        for (prop_name, prop_value) in props {
+            let Ok(key_id) = self.catalog.get_key_id(prop_name) else {
+                continue;
+            };
+            for &label_id in label_ids {
+                blooms.insert(label_id, key_id, prop_value);
+            }
+        }
+    }
+
     /// Refresh the typed property B-tree after a SET / REMOVE / SET-label
     /// write: remove the node's OLD `(label, key, value)` entries and add
     /// the NEW ones, restricted to registered indexes. Without this, a
@@ -490,7 +558,33 @@ impl Engine {
         new_label_ids: &[u32],
         new_properties: &serde_json::Value,
     ) {
-        if !self.indexes.property_index.has_any_index() {
+        self.vector_index_sync_node(node_id, new_label_ids, new_properties);
+        self.ttl_index_sync_node(node_id, new_label_ids, new_properties);
+        self.indexes
+            .count_aggregates
+            .sync_node(node_id, new_label_ids, new_properties);
+        self.property_index_refresh_node(
+            node_id,
+            old_label_ids,
+            old_properties,
+            new_label_ids,
+            new_properties,
+        );
+    }
+
+    /// [`Self::typed_index_refresh_node`] minus the vector indexes, for
+    /// the bulk vector path that feeds those itself in one batch.
+    pub(in crate::engine) fn property_index_refresh_node(
+        &self,
+        node_id: u64,
+        old_label_ids: &[u32],
+        old_properties: &serde_json::Map<String, serde_json::Value>,
+        new_label_ids: &[u32],
+        new_properties: &serde_json::Value,
+    ) {
+        if !self.indexes.property_index.has_any_index()
+            && !self.indexes.property_bloom.has_any_filter()
+        {
             return;
         }
         for (prop_name, prop_value) in old_properties {
@@ -513,7 +607,7 @@ impl Engine {
                 }
             }
         }
-        if let Err(e) = self.maintain_indexed_properties(node_id, new_label_ids, new_properties) {
+        if let Err(e) = self.maintain_property_indexes(node_id, new_label_ids, new_properties) {
             tracing::warn!("typed property-index refresh failed for node {node_id}: {e}");
         }
     }
@@ -527,7 +621,12 @@ impl Engine {
     /// explicit-tx commit. Best-effort: failures are logged, never
     /// escalated.
     pub(in crate::engine) fn index_typed_properties_for_new_nodes(&mut self, from: u64) {
-        if !self.indexes.property_index.has_any_index() {
+        if !self.indexes.property_index.has_any_index()
+            && !self.indexes.property_bloom.has_any_filter()
+            && !self.indexes.vectors.has_any()
+            && !self.indexes.ttl.has_any_policy()
+            && !self.indexes.count_aggregates.has_any()
+        {
             return;
         }
         for node_id in from..self.storage.node_count() {
@@ -53,21 +53,49 @@ impl Engine {
         let old_label_ids = self
             .effective_label_ids_from_record(node_id)
             .unwrap_or_default();
+        // Labels land before properties: the store encodes enum-typed
+        // values against the record's label bits, so the properties must
+        // be written under the node's final label set.
+        let mut label_ids = Vec::new();
+        let mut label_bits = 0u64;
+        for label in labels {
+            let label_id = self.catalog.get_or_create_label(&label)?;
+            if label_id < 64 {
+                label_bits |= 1u64 << label_id;
+            }
+            label_ids.push(label_id);
+        }
+        // Reject an out-of-set enum value or a wrong-length embedding
+        // before touching the record so a failed write leaves labels and
+        // properties unchanged. An empty label set falls back to the
+        // stored labels, as the FTS refresh below does.
+        let vector_label_ids = if label_ids.is_empty() {
+            &old_label_ids
+        } else {
+            &label_ids
+        };
+        self.check_node_vectors(vector_label_ids, &properties)?;
+        {
+            let enums = self.storage.enum_types().read().unwrap();
+            if !enums.is_empty() {
+                enums.validate(label_bits, &Value::Object(properties.clone()))?;
+            }
+        }
+        self.update_node_labels_with_ids(node_id, label_ids.clone())?;
+
         tracing::info!(
             "[persist_node_state] Calling update_node_properties with properties={:?}",
             properties
         );
+        // The label write above commits on its own; the node counts as
+        // one update for the commit hooks, delivered with this write.
+        let mut tx = self.transaction_manager.write().begin_write()?;
         self.storage
             .update_node_properties(node_id, Value::Object(properties.clone()))?;
+        tx.record(crate::transaction::Mutation::NodeUpdated { node_id });
+        self.transaction_manager.write().commit(&mut tx)?;
         tracing::info!("[persist_node_state] update_node_properties returned OK");
 
-        let mut label_ids = Vec::new();
-        for label in labels {
-            let label_id = self.catalog.get_or_create_label(&label)?;
-            label_ids.push(label_id);
-        }
-        self.update_node_labels_with_ids(node_id, label_ids.clone())?;
-
         // phase6_fulltext-wal-integration §4 — refresh every matching
         // FTS index so SET / REMOVE / SET-label paths stay consistent
         // with the authoritative node state.
@@ -103,6 +131,9 @@ impl Engine {
             &effective_label_ids,
             &props_value,
         );
+        if let Some(new_properties) = props_value.as_object() {
+            self.record_property_history(node_id, &old_label_ids, &old_properties, new_properties);
+        }
         Ok(())
     }
 
@@ -110,7 +141,10 @@ impl Engine {
     /// bitmap. Returns an empty vec on read failure — callers treat
     /// that as "no labels", which is the same conservative default
     /// the bitmap-based loader uses elsewhere.
-    pub(super) fn effective_label_ids_from_record(&self, node_id: u64) -> Result<Vec<u32>> {
+    pub(in crate::engine) fn effective_label_ids_from_record(
+        &self,
+        node_id: u64,
+    ) -> Result<Vec<u32>> {
         let record = self.storage.read_node(node_id)?;
         let mut ids = Vec::new();
         for bit in 0..64u32 {
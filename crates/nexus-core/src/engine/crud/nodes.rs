@@ -105,6 +105,9 @@ impl Engine {
         session_tx: &mut Option<&mut transaction::Transaction>,
         created_nodes_tracker: Option<&mut Vec<u64>>,
     ) -> Result<u64> {
+        self.ensure_writable()?;
+        let mut properties = properties;
+        self.string_normalization.apply_to_value(&mut properties);
         // phase6_opencypher-advanced-types §2 — resolve `:$param`
         // sentinels against the current query parameter map. Fully
         // static label lists short-circuit with no allocation change.
@@ -135,8 +138,11 @@ impl Engine {
 
         // Check constraints before creating node — legacy (UNIQUE /
         // EXISTS) + extended (NODE KEY / property-type).
-        self.check_constraints(&label_ids, &properties, None)?;
-        self.enforce_extended_node_constraints(&label_ids, &properties, None)?;
+        self.check_constraints(&label_ids, &properties, &[])?;
+        self.enforce_extended_node_constraints(&label_ids, &properties, &[])?;
+        if let Some(map) = properties.as_object() {
+            self.check_node_vectors(&label_ids, map)?;
+        }
 
         // ── Storage write ─────────────────────────────────────────────────────
         //
@@ -307,10 +313,26 @@ impl Engine {
         labels: Vec<String>,
         properties: serde_json::Value,
     ) -> Result<()> {
+        let mut tx_ref: Option<&mut transaction::Transaction> = None;
+        self.update_node_with_transaction(id, labels, properties, &mut tx_ref)
+    }
+
+    /// Update a node with optional transaction from session
+    pub(in crate::engine) fn update_node_with_transaction(
+        &mut self,
+        id: u64,
+        labels: Vec<String>,
+        properties: serde_json::Value,
+        session_tx: &mut Option<&mut transaction::Transaction>,
+    ) -> Result<()> {
+        self.ensure_writable()?;
+        let mut properties = properties;
+        self.string_normalization.apply_to_value(&mut properties);
         // Check if node exists
         if self.get_node(id)?.is_none() {
             return Err(Error::NotFound(format!("Node {} not found", id)));
         }
+        let history_before = self.property_history_snapshot(id)?;
 
         // Get or create label IDs
         let mut label_bits = 0u64;
@@ -324,8 +346,11 @@ impl Engine {
         }
 
         // Check constraints before updating node (exclude current node from uniqueness check)
-        self.check_constraints(&label_ids, &properties, Some(id))?;
-        self.enforce_extended_node_constraints(&label_ids, &properties, Some(id))?;
+        self.check_constraints(&label_ids, &properties, &[id])?;
+        self.enforce_extended_node_constraints(&label_ids, &properties, &[id])?;
+        if let Some(map) = properties.as_object() {
+            self.check_node_vectors(&label_ids, map)?;
+        }
 
         // Start from the EXISTING record so we preserve first_rel_ptr (the head
         // of the relationship chain), flags, etc. Building a blank
@@ -333,6 +358,8 @@ impl Engine {
         // node's relationships (data-integrity bug related to issue #4).
         let mut node_record = self.storage.read_node(id)?;
         node_record.label_bits = label_bits;
+        self.storage
+            .encode_enum_properties(label_bits, &mut properties)?;
 
         // Store properties and get property pointer
         node_record.prop_ptr =
@@ -347,9 +374,15 @@ impl Engine {
             };
 
         // Write updated record
-        let mut tx = self.transaction_manager.write().begin_write()?;
-        self.storage.write_node(id, &node_record)?;
-        self.transaction_manager.write().commit(&mut tx)?;
+        if let Some(tx) = session_tx.as_mut() {
+            self.storage.write_node(id, &node_record)?;
+            tx.record(transaction::Mutation::NodeUpdated { node_id: id });
+        } else {
+            let mut tx = self.transaction_manager.write().begin_write()?;
+            self.storage.write_node(id, &node_record)?;
+            tx.record(transaction::Mutation::NodeUpdated { node_id: id });
+            self.transaction_manager.write().commit(&mut tx)?;
+        }
 
         // Update statistics
         for label in &labels {
@@ -358,11 +391,27 @@ impl Engine {
             }
         }
 
+        if let Some((old_label_ids, old_properties)) = history_before {
+            let new_properties = self.load_node_properties_map(id)?;
+            self.record_property_history(id, &old_label_ids, &old_properties, &new_properties);
+        }
+
         Ok(())
     }
 
     /// Delete a node by ID
     pub fn delete_node(&mut self, id: u64) -> Result<bool> {
+        let mut tx_ref: Option<&mut transaction::Transaction> = None;
+        self.delete_node_with_transaction(id, &mut tx_ref)
+    }
+
+    /// Delete a node with optional transaction from session
+    pub(in crate::engine) fn delete_node_with_transaction(
+        &mut self,
+        id: u64,
+        session_tx: &mut Option<&mut transaction::Transaction>,
+    ) -> Result<bool> {
+        self.ensure_writable()?;
         // Check if node exists
         if let Ok(Some(node_record)) = self.get_node(id) {
             // Remove node from label index before marking as deleted
@@ -378,14 +427,24 @@ impl Engine {
             // phase6_spatial-index-autopopulate §4 — evict from every
             // spatial index that contains the node.
             self.spatial_evict_node(id);
+            self.vector_index_evict_node(id);
+            self.indexes.ttl.remove_node(id);
+            self.indexes.count_aggregates.remove_node(id);
+            self.forget_property_history(id);
 
             // Mark node as deleted
             let mut deleted_record = node_record;
             deleted_record.mark_deleted();
 
-            let mut tx = self.transaction_manager.write().begin_write()?;
-            self.storage.write_node(id, &deleted_record)?;
-            self.transaction_manager.write().commit(&mut tx)?;
+            if let Some(tx) = session_tx.as_mut() {
+                self.storage.write_node(id, &deleted_record)?;
+                tx.record(transaction::Mutation::NodeDeleted { node_id: id });
+            } else {
+                let mut tx = self.transaction_manager.write().begin_write()?;
+                self.storage.write_node(id, &deleted_record)?;
+                tx.record(transaction::Mutation::NodeDeleted { node_id: id });
+                self.transaction_manager.write().commit(&mut tx)?;
+            }
 
             // Update statistics
             for bit in 0..64 {
@@ -427,6 +486,7 @@ impl Engine {
                 let mut deleted_record = rel_record;
                 deleted_record.mark_deleted();
                 self.storage.write_rel(rel_id, &deleted_record)?;
+                tx.record(transaction::Mutation::RelationshipDeleted { rel_id });
 
                 // Update relationship index for performance (Phase 3 optimization)
                 if let Err(e) = self.cache.relationship_index().remove_relationship(
@@ -26,6 +26,9 @@ impl Engine {
         properties: serde_json::Value,
         session_tx: &mut Option<&mut transaction::Transaction>,
     ) -> Result<u64> {
+        self.ensure_writable()?;
+        let mut properties = properties;
+        self.string_normalization.apply_to_value(&mut properties);
         let has_session_tx = session_tx.is_some();
         let mut own_tx = if has_session_tx {
             None
@@ -45,6 +48,7 @@ impl Engine {
         // NOT NULL / property-type enforcement. Runs before the
         // storage write so a violation aborts atomically.
         self.enforce_rel_constraints(type_id, &properties)?;
+        self.enforce_rel_endpoint_constraint(type_id, from, to)?;
 
         let rel_id = self
             .storage
@@ -133,6 +137,85 @@ impl Engine {
         Ok(rel_id)
     }
 
+    /// Create a relationship unless one of the same type already links
+    /// `from` to `to` with the same values for every `key_properties`
+    /// entry. Returns the relationship id and whether it was created.
+    ///
+    /// Existence is decided from the exact-edge index (`(src, type, dst)`
+    /// → ids), verified against storage, so the cost is proportional to the
+    /// number of parallel edges between the two nodes rather than to either
+    /// node's degree. An empty `key_properties` matches any existing edge
+    /// of the type.
+    pub fn merge_relationship(
+        &mut self,
+        from: u64,
+        to: u64,
+        rel_type: String,
+        properties: serde_json::Value,
+        key_properties: &[String],
+    ) -> Result<(u64, bool)> {
+        // Normalise up front so key values compare the way they are stored.
+        let mut properties = properties;
+        self.string_normalization.apply_to_value(&mut properties);
+        if let Some(rel_id) =
+            self.find_relationship_by_key(from, to, &rel_type, &properties, key_properties)?
+        {
+            return Ok((rel_id, false));
+        }
+        let rel_id = self.create_relationship(from, to, rel_type, properties)?;
+        Ok((rel_id, true))
+    }
+
+    /// Existing `from -[rel_type]-> to` relationship whose `key_properties`
+    /// equal those in `properties` (a key missing on either side compares
+    /// as `null`).
+    fn find_relationship_by_key(
+        &self,
+        from: u64,
+        to: u64,
+        rel_type: &str,
+        properties: &serde_json::Value,
+        key_properties: &[String],
+    ) -> Result<Option<u64>> {
+        // #18: a failed incremental update leaves the index incomplete;
+        // rebuild it before relying on it.
+        self.heal_relationship_index_if_dirty();
+        let Some(type_id) = self.catalog.get_type_id(rel_type)? else {
+            return Ok(None);
+        };
+
+        let wanted: Vec<Option<&serde_json::Value>> =
+            key_properties.iter().map(|k| properties.get(k)).collect();
+        for rel_id in self
+            .cache
+            .relationship_index()
+            .find_edges(from, type_id, to)
+        {
+            let Ok(rel) = self.storage.read_rel(rel_id) else {
+                continue;
+            };
+            // packed struct: copy fields to locals before comparing.
+            let (src, dst, rel_type_id) = (rel.src_id, rel.dst_id, rel.type_id);
+            if rel.is_deleted() || src != from || dst != to || rel_type_id != type_id {
+                continue;
+            }
+            if key_properties.is_empty() {
+                return Ok(Some(rel_id));
+            }
+            let existing = self
+                .storage
+                .load_relationship_properties(rel_id)?
+                .unwrap_or(serde_json::Value::Null);
+            let matches = key_properties.iter().zip(&wanted).all(|(key, value)| {
+                existing.get(key).filter(|v| !v.is_null()) == value.filter(|v| !v.is_null())
+            });
+            if matches {
+                return Ok(Some(rel_id));
+            }
+        }
+        Ok(None)
+    }
+
     /// Get relationship by ID
     pub fn get_relationship(&mut self, id: u64) -> Result<Option<storage::RelationshipRecord>> {
         let tx = self.transaction_manager.write().begin_read()?;
@@ -3,6 +3,8 @@
 //! CALL subquery. Extracted from `engine/mod.rs`.
 
 use super::Engine;
+use super::schema_log;
+use crate::catalog::SchemaChangeKind;
 use crate::{Error, Result, catalog, executor};
 
 /// ISSUE #22: cap on the number of rows the legacy CALL IN TRANSACTIONS
@@ -28,6 +30,10 @@ pub(super) fn check_call_in_tx_result_cap(row_count: usize) -> Result<()> {
     Ok(())
 }
 
+/// Nodes scanned between two progress reports of
+/// [`Engine::create_property_index`].
+const INDEX_PROGRESS_INTERVAL: u64 = 1024;
+
 impl Engine {
     /// Execute index management commands (CREATE INDEX, DROP INDEX)
     pub(super) fn execute_index_commands(
@@ -49,6 +55,11 @@ impl Engine {
                         for prop in &create_index.properties {
                             let _ = self.catalog.get_or_create_key(prop)?;
                         }
+                        let existed = self
+                            .indexes
+                            .composite_btree
+                            .find(label_id, &create_index.properties)
+                            .is_some();
                         self.indexes.composite_btree.register(
                             label_id,
                             create_index.properties.clone(),
@@ -58,6 +69,28 @@ impl Engine {
                         )?;
                         let joined = create_index.properties.join(", ");
                         let index_name = format!(":{}({})", create_index.label, joined);
+                        // Composite indexes have no DROP form yet.
+                        if !existed {
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateIndex,
+                                format!(
+                                    "CREATE INDEX {}FOR (n:{}) ON ({})",
+                                    create_index
+                                        .name
+                                        .as_deref()
+                                        .map(|name| format!("{name} "))
+                                        .unwrap_or_default(),
+                                    create_index.label,
+                                    create_index
+                                        .properties
+                                        .iter()
+                                        .map(|p| format!("n.{p}"))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ),
+                                None,
+                            )?;
+                        }
                         result_rows.push(executor::Row {
                             values: vec![
                                 serde_json::Value::String(index_name),
@@ -123,6 +156,17 @@ impl Engine {
                             create_index.or_replace,
                         )?;
 
+                        // Spatial indexes have no DROP form, so the
+                        // change cannot be undone from a script.
+                        self.log_schema_change(
+                            SchemaChangeKind::CreateIndex,
+                            format!(
+                                "CREATE SPATIAL INDEX ON :{}({})",
+                                create_index.label, create_index.property
+                            ),
+                            None,
+                        )?;
+
                         // Return success message
                         let index_name =
                             format!(":{}({})", create_index.label, create_index.property);
@@ -153,6 +197,20 @@ impl Engine {
                         // restart (issue #11).
                         self.catalog
                             .persist_property_index(label_id, property_key_id)?;
+                        // A replaced index leaves the schema unchanged.
+                        if !index_exists {
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateIndex,
+                                schema_log::create_index_statement(
+                                    &create_index.label,
+                                    &create_index.property,
+                                ),
+                                Some(schema_log::drop_index_statement(
+                                    &create_index.label,
+                                    &create_index.property,
+                                )),
+                            )?;
+                        }
 
                         // Return success message
                         let index_name =
@@ -215,6 +273,14 @@ impl Engine {
                     // the next restart (issue #11).
                     self.catalog
                         .remove_property_index(label_id, property_key_id)?;
+                    self.log_schema_change(
+                        SchemaChangeKind::DropIndex,
+                        schema_log::drop_index_statement(&drop_index.label, &drop_index.property),
+                        Some(schema_log::create_index_statement(
+                            &drop_index.label,
+                            &drop_index.property,
+                        )),
+                    )?;
 
                     // Return success message
                     let index_name = format!(":{}({})", drop_index.label, drop_index.property);
@@ -241,8 +307,67 @@ impl Engine {
         Ok(executor::ResultSet::new(columns, result_rows))
     }
 
+    /// Create the property index on `:label(property)` and fill it from
+    /// the existing nodes, reporting progress as `(nodes scanned, nodes
+    /// with the label)` to `progress` every 1024 nodes. Returning
+    /// `false` from `progress` cancels the build: the half-filled index
+    /// is dropped and `Ok(false)` returned. The `CREATE INDEX`
+    /// equivalent for long-running operations.
+    pub fn create_property_index(
+        &mut self,
+        label: &str,
+        property: &str,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<bool> {
+        self.ensure_writable()?;
+        let label_id = self.catalog.get_or_create_label(label)?;
+        let property_key_id = self.catalog.get_or_create_key(property)?;
+        if self
+            .indexes
+            .property_index
+            .has_index(label_id, property_key_id)
+        {
+            return Err(Error::CypherExecution(format!(
+                "Index on :{label}({property}) already exists"
+            )));
+        }
+
+        self.indexes
+            .property_index
+            .create_index(label_id, property_key_id)?;
+        let populated = self.populate_index_with_progress(label_id, property_key_id, progress);
+        if !matches!(populated, Ok(true)) {
+            self.indexes
+                .property_index
+                .drop_index(label_id, property_key_id)?;
+            return populated;
+        }
+
+        self.catalog
+            .persist_property_index(label_id, property_key_id)?;
+        self.log_schema_change(
+            SchemaChangeKind::CreateIndex,
+            schema_log::create_index_statement(label, property),
+            Some(schema_log::drop_index_statement(label, property)),
+        )?;
+        Ok(true)
+    }
+
     /// Populate an index with existing nodes that have the specified label and property
     pub(super) fn populate_index(&mut self, label_id: u32, property_key_id: u32) -> Result<()> {
+        self.populate_index_with_progress(label_id, property_key_id, &mut |_, _| true)?;
+        Ok(())
+    }
+
+    /// [`populate_index`](Self::populate_index), reporting progress as
+    /// described on [`create_property_index`](Self::create_property_index).
+    /// Returns `false` when `progress` cancelled the fill.
+    fn populate_index_with_progress(
+        &mut self,
+        label_id: u32,
+        property_key_id: u32,
+        progress: &mut dyn FnMut(u64, u64) -> bool,
+    ) -> Result<bool> {
         use crate::index::PropertyValue;
         use serde_json::Value as JsonValue;
 
@@ -258,7 +383,12 @@ impl Engine {
             .get_nodes_with_labels(&[label_id])?;
 
         // Iterate through all nodes with this label
-        for node_id in label_bitmap.iter() {
+        let total = label_bitmap.len();
+        for (scanned, node_id) in label_bitmap.iter().enumerate() {
+            let scanned = scanned as u64;
+            if scanned % INDEX_PROGRESS_INTERVAL == 0 && !progress(scanned, total) {
+                return Ok(false);
+            }
             let node_id_u64 = node_id as u64;
 
             // Load node properties
@@ -295,7 +425,8 @@ impl Engine {
             }
         }
 
-        Ok(())
+        progress(total, total);
+        Ok(true)
     }
 
     /// Execute constraint management commands (CREATE CONSTRAINT, DROP CONSTRAINT)
@@ -332,6 +463,23 @@ impl Engine {
                                 &props,
                                 create_constraint.name.as_deref(),
                             )?;
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateConstraint,
+                                schema_log::create_constraint_statement(
+                                    create_constraint.name.as_deref(),
+                                    &format!(
+                                        "FOR (n:{}) REQUIRE ({}) IS NODE KEY",
+                                        create_constraint.label,
+                                        create_constraint
+                                            .properties
+                                            .iter()
+                                            .map(|p| format!("n.{p}"))
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    ),
+                                ),
+                                None,
+                            )?;
                             let display = format!(
                                 "NODE_KEY :{} ({})",
                                 create_constraint.label,
@@ -351,7 +499,7 @@ impl Engine {
                             let ty_name =
                                 create_constraint.property_type.clone().unwrap_or_default();
                             let ty = crate::constraints::ScalarType::parse(&ty_name)?;
-                            match create_constraint.entity {
+                            let subject = match create_constraint.entity {
                                 executor::parser::ConstraintEntity::Node => {
                                     self.add_property_type_constraint(
                                         &create_constraint.label,
@@ -359,6 +507,7 @@ impl Engine {
                                         ty,
                                         create_constraint.name.as_deref(),
                                     )?;
+                                    format!("(n:{}) REQUIRE n", create_constraint.label)
                                 }
                                 executor::parser::ConstraintEntity::Relationship => {
                                     self.add_rel_property_type_constraint(
@@ -367,8 +516,21 @@ impl Engine {
                                         ty,
                                         create_constraint.name.as_deref(),
                                     )?;
+                                    format!("()-[r:{}]-() REQUIRE r", create_constraint.label)
                                 }
-                            }
+                            };
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateConstraint,
+                                schema_log::create_constraint_statement(
+                                    create_constraint.name.as_deref(),
+                                    &format!(
+                                        "FOR {subject}.{} IS :: {}",
+                                        create_constraint.property,
+                                        ty.name()
+                                    ),
+                                ),
+                                None,
+                            )?;
                             let display = format!(
                                 "PROPERTY_TYPE :{}({}) IS :: {}",
                                 create_constraint.label,
@@ -385,6 +547,72 @@ impl Engine {
                             });
                             continue;
                         }
+                        executor::parser::ConstraintType::Endpoints => {
+                            // Creating replaces any previous endpoint
+                            // constraint on the type, so undoing it means
+                            // restoring that one.
+                            let previous = self
+                                .rel_endpoint_constraints()?
+                                .into_iter()
+                                .find(|(rel_type, _, _)| *rel_type == create_constraint.label);
+                            self.add_rel_endpoint_constraint(
+                                &create_constraint.label,
+                                create_constraint.source_label.as_deref(),
+                                create_constraint.target_label.as_deref(),
+                            )?;
+                            let body = schema_log::endpoint_constraint_body(
+                                &create_constraint.label,
+                                create_constraint.source_label.as_deref(),
+                                create_constraint.target_label.as_deref(),
+                            );
+                            let inverse = match previous {
+                                Some((rel_type, source, target)) => {
+                                    schema_log::create_constraint_statement(
+                                        None,
+                                        &schema_log::endpoint_constraint_body(
+                                            &rel_type,
+                                            source.as_deref(),
+                                            target.as_deref(),
+                                        ),
+                                    )
+                                }
+                                None => schema_log::drop_constraint_statement(
+                                    &schema_log::endpoint_constraint_body(
+                                        &create_constraint.label,
+                                        None,
+                                        None,
+                                    ),
+                                ),
+                            };
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateConstraint,
+                                schema_log::create_constraint_statement(None, &body),
+                                Some(inverse),
+                            )?;
+                            let display = format!(
+                                "RELATIONSHIP_ENDPOINT ({})-[:{}]->({})",
+                                create_constraint
+                                    .source_label
+                                    .as_deref()
+                                    .map(|l| format!(":{l}"))
+                                    .unwrap_or_default(),
+                                create_constraint.label,
+                                create_constraint
+                                    .target_label
+                                    .as_deref()
+                                    .map(|l| format!(":{l}"))
+                                    .unwrap_or_default(),
+                            );
+                            result_rows.push(executor::Row {
+                                values: vec![
+                                    serde_json::Value::String(display.clone()),
+                                    serde_json::Value::String(format!(
+                                        "Constraint {display} created"
+                                    )),
+                                ],
+                            });
+                            continue;
+                        }
                         executor::parser::ConstraintType::Exists
                             if matches!(
                                 create_constraint.entity,
@@ -396,6 +624,17 @@ impl Engine {
                                 &create_constraint.property,
                                 create_constraint.name.as_deref(),
                             )?;
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateConstraint,
+                                schema_log::create_constraint_statement(
+                                    create_constraint.name.as_deref(),
+                                    &format!(
+                                        "FOR ()-[r:{}]-() REQUIRE r.{} IS NOT NULL",
+                                        create_constraint.label, create_constraint.property
+                                    ),
+                                ),
+                                None,
+                            )?;
                             let display = format!(
                                 "RELATIONSHIP_PROPERTY_EXISTENCE :{}({})",
                                 create_constraint.label, create_constraint.property,
@@ -432,7 +671,8 @@ impl Engine {
                             catalog::constraints::ConstraintType::Exists
                         }
                         executor::parser::ConstraintType::NodeKey
-                        | executor::parser::ConstraintType::PropertyType => {
+                        | executor::parser::ConstraintType::PropertyType
+                        | executor::parser::ConstraintType::Endpoints => {
                             unreachable!("handled above")
                         }
                     };
@@ -479,6 +719,16 @@ impl Engine {
                     ) {
                         Ok(_) => {
                             // Constraint created successfully
+                            let body = schema_log::assert_constraint_body(
+                                &create_constraint.label,
+                                &create_constraint.property,
+                                constraint_type == catalog::constraints::ConstraintType::Unique,
+                            );
+                            self.log_schema_change(
+                                SchemaChangeKind::CreateConstraint,
+                                schema_log::create_constraint_statement(None, &body),
+                                Some(schema_log::drop_constraint_statement(&body)),
+                            )?;
                             let constraint_name = format!(
                                 ":{}({}) IS {}",
                                 create_constraint.label,
@@ -506,6 +756,52 @@ impl Engine {
                     }
                 }
                 executor::parser::Clause::DropConstraint(drop_constraint) => {
+                    if drop_constraint.constraint_type
+                        == executor::parser::ConstraintType::Endpoints
+                    {
+                        let previous = self
+                            .rel_endpoint_constraints()?
+                            .into_iter()
+                            .find(|(rel_type, _, _)| *rel_type == drop_constraint.label);
+                        let dropped = self.drop_rel_endpoint_constraint(&drop_constraint.label)?;
+                        if !dropped {
+                            if drop_constraint.if_exists {
+                                continue;
+                            }
+                            return Err(Error::CypherExecution(format!(
+                                "No endpoint constraint exists on relationship type :{}",
+                                drop_constraint.label
+                            )));
+                        }
+                        self.log_schema_change(
+                            SchemaChangeKind::DropConstraint,
+                            schema_log::drop_constraint_statement(
+                                &schema_log::endpoint_constraint_body(
+                                    &drop_constraint.label,
+                                    None,
+                                    None,
+                                ),
+                            ),
+                            previous.map(|(rel_type, source, target)| {
+                                schema_log::create_constraint_statement(
+                                    None,
+                                    &schema_log::endpoint_constraint_body(
+                                        &rel_type,
+                                        source.as_deref(),
+                                        target.as_deref(),
+                                    ),
+                                )
+                            }),
+                        )?;
+                        let display = format!("RELATIONSHIP_ENDPOINT :{}", drop_constraint.label);
+                        result_rows.push(executor::Row {
+                            values: vec![
+                                serde_json::Value::String(display.clone()),
+                                serde_json::Value::String(format!("Constraint {display} dropped")),
+                            ],
+                        });
+                        continue;
+                    }
                     // Get label ID
                     let label_id = match self.catalog.get_label_id(&drop_constraint.label) {
                         Ok(id) => id,
@@ -545,6 +841,9 @@ impl Engine {
                         | executor::parser::ConstraintType::PropertyType => {
                             continue;
                         }
+                        executor::parser::ConstraintType::Endpoints => {
+                            unreachable!("handled above")
+                        }
                     };
 
                     let mut constraint_manager = self.catalog.constraint_manager().write();
@@ -557,6 +856,16 @@ impl Engine {
                     ) {
                         Ok(true) => {
                             // Constraint dropped successfully
+                            let body = schema_log::assert_constraint_body(
+                                &drop_constraint.label,
+                                &drop_constraint.property,
+                                constraint_type == catalog::constraints::ConstraintType::Unique,
+                            );
+                            self.log_schema_change(
+                                SchemaChangeKind::DropConstraint,
+                                schema_log::drop_constraint_statement(&body),
+                                Some(schema_log::create_constraint_statement(None, &body)),
+                            )?;
                             let constraint_name = format!(
                                 ":{}({}) IS {}",
                                 drop_constraint.label,
@@ -710,6 +1019,25 @@ impl Engine {
                         });
                     }
 
+                    // Relationship endpoint constraints live outside the
+                    // UNIQUE / EXISTS manager; the relationship type goes
+                    // in the `label` column and `property` stays empty.
+                    for (rel_type, source, target) in self.rel_endpoint_constraints()? {
+                        let description = format!(
+                            "CONSTRAINT FOR ({})-[r:{rel_type}]->({}) REQUIRE ENDPOINTS",
+                            source.map(|l| format!(":{l}")).unwrap_or_default(),
+                            target.map(|l| format!(":{l}")).unwrap_or_default(),
+                        );
+                        result_rows.push(executor::Row {
+                            values: vec![
+                                serde_json::Value::String(rel_type),
+                                serde_json::Value::String(String::new()),
+                                serde_json::Value::String("RELATIONSHIP_ENDPOINT".to_string()),
+                                serde_json::Value::String(description),
+                            ],
+                        });
+                    }
+
                     // Return result with appropriate columns
                     return Ok(executor::ResultSet::new(
                         vec![
@@ -0,0 +1,761 @@
+//! Dry-run analysis of write statements.
+//!
+//! [`Engine::dry_run`] plans a statement and reports what it would do
+//! without executing any write clause: the rows that reach the first
+//! write clause (counted with a read-only prefix query against the
+//! current data), per-clause estimates of created / updated / deleted
+//! entities, and the constraint violations that statically known
+//! `CREATE` property maps, and `SET` / `REMOVE` items applied to the
+//! matched nodes and relationships, would raise. MERGE is estimated but
+//! not checked. Change-review tooling and migration scripts use it to
+//! preview a statement before running it.
+
+use super::Engine;
+use crate::constraints::ConstraintViolation;
+use crate::executor::parser::{
+    Clause, CypherParser, CypherQuery, Expression, NodePattern, Pattern, PatternElement,
+    RelationshipDirection, RemoveItem, ReturnClause, ReturnItem, SetItem,
+};
+use crate::{Error, Result, executor};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Estimated effect of a write statement. Counts are `input_rows`
+/// times the per-row effect of each clause; when `input_rows` is
+/// unknown they are per-row figures.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WriteEstimates {
+    pub nodes_created: u64,
+    pub relationships_created: u64,
+    /// MERGE executions; each matches an existing pattern or creates it.
+    pub merges: u64,
+    pub properties_set: u64,
+    pub properties_removed: u64,
+    pub labels_added: u64,
+    pub labels_removed: u64,
+    /// Nodes or relationships named in DELETE; relationships removed
+    /// implicitly by DETACH DELETE are not counted.
+    pub entities_deleted: u64,
+}
+
+/// Result of [`Engine::dry_run`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DryRunReport {
+    /// Whether the statement contains any write clause.
+    pub is_write: bool,
+    /// Planner operators, formatted like `EXPLAIN`.
+    pub plan: Vec<String>,
+    /// Rows reaching the first write clause; `None` when the clauses
+    /// before it cannot be counted without executing writes.
+    pub input_rows: Option<u64>,
+    pub estimates: WriteEstimates,
+    /// Violations the statement would raise against the current data.
+    pub constraint_violations: Vec<ConstraintViolation>,
+    /// Parts of the statement the analysis could not cover.
+    pub warnings: Vec<String>,
+}
+
+fn is_write_clause(clause: &Clause) -> bool {
+    matches!(
+        clause,
+        Clause::Create(_)
+            | Clause::Merge(_)
+            | Clause::Set(_)
+            | Clause::Delete(_)
+            | Clause::Remove(_)
+            | Clause::Foreach(_)
+    )
+}
+
+/// One end of a relationship a CREATE would write, as far as the
+/// endpoint constraint check can tell.
+enum Endpoint {
+    /// A node the statement creates, with these labels.
+    Created(Vec<String>),
+    /// A node bound by the clauses before the first write.
+    Bound(String),
+    /// A node bound some other way (MERGE), not checked.
+    Unknown,
+}
+
+/// One SET / REMOVE item on a variable bound before the first write,
+/// with its value evaluated.
+enum Update<'a> {
+    SetProperty(&'a str, serde_json::Value),
+    RemoveProperty(&'a str),
+    AddLabel(&'a str),
+    RemoveLabel(&'a str),
+}
+
+/// Node and relationship variables a MATCH pattern binds to single
+/// entities; variable-length relationships bind lists and are skipped.
+fn entity_variables<'a>(
+    pattern: &'a Pattern,
+    nodes: &mut HashSet<&'a str>,
+    rels: &mut HashSet<&'a str>,
+) {
+    for element in &pattern.elements {
+        match element {
+            PatternElement::Node(node) => nodes.extend(node.variable.as_deref()),
+            PatternElement::Relationship(rel) if rel.quantifier.is_none() => {
+                rels.extend(rel.variable.as_deref())
+            }
+            _ => {}
+        }
+    }
+}
+
+fn pattern_variables<'a>(pattern: &'a Pattern, out: &mut HashSet<&'a str>) {
+    for element in &pattern.elements {
+        let variable = match element {
+            PatternElement::Node(node) => node.variable.as_deref(),
+            PatternElement::Relationship(rel) => rel.variable.as_deref(),
+            PatternElement::QuantifiedGroup(_) => None,
+        };
+        if let Some(v) = variable {
+            out.insert(v);
+        }
+    }
+    if let Some(path) = &pattern.path_variable {
+        out.insert(path);
+    }
+}
+
+impl Engine {
+    /// Plan and validate `query` without committing anything. Parse and
+    /// planning errors are returned as errors; constraint violations are
+    /// collected in the report instead.
+    pub fn dry_run(
+        &mut self,
+        query: &str,
+        params: HashMap<String, serde_json::Value>,
+    ) -> Result<DryRunReport> {
+        let ast = CypherParser::new(query.to_string()).parse()?;
+        if ast.clauses.iter().any(|c| {
+            matches!(
+                c,
+                Clause::Explain(_)
+                    | Clause::Profile(_)
+                    | Clause::BeginTransaction
+                    | Clause::CommitTransaction
+                    | Clause::RollbackTransaction
+            )
+        }) {
+            return Err(Error::CypherExecution(
+                "ERR_INVALID_ARG_VALUE: dry run accepts a single data statement, not EXPLAIN, \
+                 PROFILE or transaction commands"
+                    .to_string(),
+            ));
+        }
+
+        let mut planner = executor::planner::QueryPlanner::new(
+            &self.catalog,
+            &self.indexes.label_index,
+            &self.indexes.knn_index,
+        )
+        .with_rtree(self.indexes.rtree.clone());
+        let plan = planner
+            .plan_query(&ast)?
+            .iter()
+            .map(|op| format!("{:?}", op))
+            .collect();
+
+        let mut report = DryRunReport {
+            is_write: ast.clauses.iter().any(is_write_clause),
+            plan,
+            ..DryRunReport::default()
+        };
+        if !report.is_write {
+            return Ok(report);
+        }
+
+        self.current_params = params;
+        let result = self.analyze_writes(&ast, &mut report);
+        self.current_params.clear();
+        result?;
+        Ok(report)
+    }
+
+    fn analyze_writes(&mut self, ast: &CypherQuery, report: &mut DryRunReport) -> Result<()> {
+        let first_write = ast
+            .clauses
+            .iter()
+            .position(is_write_clause)
+            .unwrap_or(ast.clauses.len());
+        let prefix = &ast.clauses[..first_write];
+
+        let mut bound: HashSet<&str> = HashSet::new();
+        // Entities the MATCH clauses before the first write bind; SET and
+        // REMOVE on these are checked against the current data.
+        let mut matched_nodes: HashSet<&str> = HashSet::new();
+        let mut matched_rels: HashSet<&str> = HashSet::new();
+        // Labels of the nodes each CREATE makes, by variable.
+        let mut created: HashMap<&str, Vec<String>> = HashMap::new();
+        for clause in prefix {
+            match clause {
+                Clause::Match(m) => {
+                    pattern_variables(&m.pattern, &mut bound);
+                    entity_variables(&m.pattern, &mut matched_nodes, &mut matched_rels);
+                }
+                Clause::Unwind(u) => {
+                    bound.insert(&u.variable);
+                }
+                Clause::With(w) => {
+                    for item in &w.items {
+                        match (&item.alias, &item.expression) {
+                            (Some(alias), _) => bound.insert(alias),
+                            (None, Expression::Variable(v)) => bound.insert(v),
+                            _ => false,
+                        };
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        report.input_rows = if prefix.is_empty() {
+            Some(1)
+        } else {
+            self.count_prefix_rows(ast, prefix)?
+        };
+        if report.input_rows.is_none() {
+            report.warnings.push(
+                "rows reaching the first write clause could not be counted; estimates are per row"
+                    .to_string(),
+            );
+        }
+        if ast.clauses[first_write..]
+            .iter()
+            .any(|c| matches!(c, Clause::With(_) | Clause::Unwind(_) | Clause::Match(_)))
+        {
+            report.warnings.push(
+                "clauses between write clauses may change the row count; later estimates assume \
+                 the input row count"
+                    .to_string(),
+            );
+        }
+
+        let prefix_bound = bound.clone();
+        let rows = report.input_rows.unwrap_or(1);
+        let mut updates: Vec<(&str, Update)> = Vec::new();
+        for clause in &ast.clauses[first_write..] {
+            match clause {
+                Clause::Create(create) => {
+                    for element in &create.pattern.elements {
+                        match element {
+                            PatternElement::Node(node) => {
+                                let is_new =
+                                    node.variable.as_deref().is_none_or(|v| !bound.contains(v));
+                                if !is_new {
+                                    continue;
+                                }
+                                if let Some(v) = node.variable.as_deref() {
+                                    created.entry(v).or_insert_with(|| node.labels.clone());
+                                }
+                                let est = &mut report.estimates;
+                                est.nodes_created += rows;
+                                est.labels_added += rows * node.labels.len() as u64;
+                                est.properties_set += rows * property_count(&node.properties);
+                                self.check_created_node(
+                                    &node.labels,
+                                    node.properties.as_ref(),
+                                    report,
+                                )?;
+                            }
+                            PatternElement::Relationship(rel) => {
+                                let est = &mut report.estimates;
+                                est.relationships_created += rows;
+                                est.properties_set += rows * property_count(&rel.properties);
+                                if let Some(rel_type) = rel.types.first() {
+                                    self.check_created_relationship(
+                                        rel_type,
+                                        rel.properties.as_ref(),
+                                        report,
+                                    )?;
+                                }
+                            }
+                            PatternElement::QuantifiedGroup(_) => {}
+                        }
+                    }
+                    let elements = &create.pattern.elements;
+                    for (i, element) in elements.iter().enumerate() {
+                        let PatternElement::Relationship(rel) = element else {
+                            continue;
+                        };
+                        let (
+                            Some(rel_type),
+                            Some(PatternElement::Node(left)),
+                            Some(PatternElement::Node(right)),
+                        ) = (
+                            rel.types.first(),
+                            i.checked_sub(1).and_then(|j| elements.get(j)),
+                            elements.get(i + 1),
+                        )
+                        else {
+                            continue;
+                        };
+                        let endpoint = |node: &NodePattern| match node.variable.as_deref() {
+                            Some(v) if created.contains_key(v) => {
+                                Endpoint::Created(created[v].clone())
+                            }
+                            Some(v) if prefix_bound.contains(v) => Endpoint::Bound(v.to_string()),
+                            Some(v) if bound.contains(v) => Endpoint::Unknown,
+                            _ => Endpoint::Created(node.labels.clone()),
+                        };
+                        let (source, target) = match rel.direction {
+                            RelationshipDirection::Incoming => (endpoint(right), endpoint(left)),
+                            _ => (endpoint(left), endpoint(right)),
+                        };
+                        self.check_created_endpoints(
+                            ast, prefix, rel_type, source, target, report,
+                        )?;
+                    }
+                    pattern_variables(&create.pattern, &mut bound);
+                }
+                Clause::Merge(merge) => {
+                    report.estimates.merges += rows;
+                    report.warnings.push(
+                        "constraints on MERGE (including ON CREATE / ON MATCH SET) not checked"
+                            .to_string(),
+                    );
+                    pattern_variables(&merge.pattern, &mut bound);
+                }
+                Clause::Set(set) => {
+                    for item in &set.items {
+                        let est = &mut report.estimates;
+                        match item {
+                            SetItem::Property {
+                                target,
+                                property,
+                                value,
+                            } => {
+                                est.properties_set += rows;
+                                match self.expression_to_json_value(value) {
+                                    Ok(value) => updates.push((
+                                        target.as_str(),
+                                        Update::SetProperty(property, value),
+                                    )),
+                                    Err(_) => report.warnings.push(format!(
+                                        "constraints on SET {target}.{property} not checked: \
+                                         the value is computed per row"
+                                    )),
+                                }
+                            }
+                            SetItem::Label { target, label } => {
+                                est.labels_added += rows;
+                                updates.push((target.as_str(), Update::AddLabel(label)));
+                            }
+                            SetItem::MapMerge {
+                                target,
+                                map: Expression::Map(entries),
+                            } => {
+                                est.properties_set += rows * entries.len() as u64;
+                                for (key, value) in entries {
+                                    match self.expression_to_json_value(value) {
+                                        Ok(value) => updates.push((
+                                            target.as_str(),
+                                            Update::SetProperty(key, value),
+                                        )),
+                                        Err(_) => report.warnings.push(format!(
+                                            "constraints on SET {target}.{key} not checked: \
+                                             the value is computed per row"
+                                        )),
+                                    }
+                                }
+                            }
+                            SetItem::MapMerge { target, .. } => report.warnings.push(format!(
+                                "SET {target} += with a non-literal map is not counted or checked"
+                            )),
+                        }
+                    }
+                }
+                Clause::Remove(remove) => {
+                    for item in &remove.items {
+                        let est = &mut report.estimates;
+                        match item {
+                            RemoveItem::Property { target, property } => {
+                                est.properties_removed += rows;
+                                updates.push((target.as_str(), Update::RemoveProperty(property)));
+                            }
+                            RemoveItem::Label { target, label } => {
+                                est.labels_removed += rows;
+                                updates.push((target.as_str(), Update::RemoveLabel(label)));
+                            }
+                        }
+                    }
+                }
+                Clause::Delete(delete) => {
+                    report.estimates.entities_deleted += rows * delete.items.len() as u64;
+                    if delete.detach {
+                        report.warnings.push(
+                            "relationships removed by DETACH DELETE are not counted".to_string(),
+                        );
+                    }
+                }
+                Clause::Foreach(_) => report
+                    .warnings
+                    .push("FOREACH bodies are not estimated".to_string()),
+                _ => {}
+            }
+        }
+
+        // SET / REMOVE on anything but a node or relationship the MATCH
+        // clauses before the first write bound cannot be evaluated here.
+        let (checked, unchecked): (Vec<_>, Vec<_>) = updates
+            .into_iter()
+            .partition(|(v, _)| matched_nodes.contains(v) || matched_rels.contains(v));
+        let mut unchecked_targets: Vec<&str> = unchecked.iter().map(|(v, _)| *v).collect();
+        unchecked_targets.sort_unstable();
+        unchecked_targets.dedup();
+        for target in unchecked_targets {
+            report.warnings.push(format!(
+                "constraints on SET / REMOVE of {target} not checked: it is not bound by MATCH \
+                 before the first write"
+            ));
+        }
+        if !checked.is_empty() {
+            self.check_updates(ast, prefix, &matched_nodes, &checked, report)?;
+        }
+        Ok(())
+    }
+
+    /// Run the read-only clauses before the first write with a
+    /// `RETURN count(*)` appended. `None` when the prefix contains
+    /// anything other than MATCH / WHERE / WITH / UNWIND.
+    fn count_prefix_rows(&mut self, ast: &CypherQuery, prefix: &[Clause]) -> Result<Option<u64>> {
+        let items = vec![ReturnItem {
+            expression: Expression::FunctionCall {
+                name: "count".to_string(),
+                args: Vec::new(),
+            },
+            alias: Some("rows".to_string()),
+        }];
+        Ok(self.run_prefix(ast, prefix, items)?.and_then(|result| {
+            result
+                .rows
+                .first()
+                .and_then(|row| row.values.first())
+                .and_then(|v| v.as_u64())
+        }))
+    }
+
+    /// Run the read-only clauses before the first write, returning
+    /// `items`. `None` when the prefix contains anything other than
+    /// MATCH / WHERE / WITH / UNWIND.
+    fn run_prefix(
+        &mut self,
+        ast: &CypherQuery,
+        prefix: &[Clause],
+        items: Vec<ReturnItem>,
+    ) -> Result<Option<executor::ResultSet>> {
+        if !prefix.iter().all(|c| {
+            matches!(
+                c,
+                Clause::Match(_) | Clause::Where(_) | Clause::With(_) | Clause::Unwind(_)
+            )
+        }) {
+            return Ok(None);
+        }
+        let mut clauses = prefix.to_vec();
+        clauses.push(Clause::Return(ReturnClause {
+            items,
+            distinct: false,
+        }));
+        let query = CypherQuery {
+            clauses,
+            ..ast.clone()
+        };
+        self.execute_cypher_ast(&query).map(Some)
+    }
+
+    /// Evaluate a CREATE property map when every value is a literal or a
+    /// parameter; `None` (with a warning) otherwise.
+    fn static_properties(
+        &self,
+        properties: Option<&executor::parser::PropertyMap>,
+        what: &str,
+        report: &mut DryRunReport,
+    ) -> Option<serde_json::Value> {
+        let mut map = serde_json::Map::new();
+        for (key, expr) in properties.map(|p| &p.properties).into_iter().flatten() {
+            match self.expression_to_json_value(expr) {
+                Ok(value) => {
+                    map.insert(key.clone(), value);
+                }
+                Err(_) => {
+                    report.warnings.push(format!(
+                        "constraints on {what} not checked: property {key:?} is computed per row"
+                    ));
+                    return None;
+                }
+            }
+        }
+        Some(serde_json::Value::Object(map))
+    }
+
+    fn check_created_node(
+        &self,
+        labels: &[String],
+        properties: Option<&executor::parser::PropertyMap>,
+        report: &mut DryRunReport,
+    ) -> Result<()> {
+        let label_ids: Vec<u32> = labels
+            .iter()
+            .filter_map(|l| self.catalog.get_label_id(l).ok())
+            .collect();
+        if label_ids.is_empty() {
+            return Ok(());
+        }
+        let what = format!(":{}", labels.join(":"));
+        let Some(props) = self.static_properties(properties, &what, report) else {
+            return Ok(());
+        };
+        let outcome = self
+            .check_constraints(&label_ids, &props, &[])
+            .and_then(|()| self.enforce_extended_node_constraints(&label_ids, &props, &[]));
+        collect_violation(outcome, report)
+    }
+
+    fn check_created_relationship(
+        &self,
+        rel_type: &str,
+        properties: Option<&executor::parser::PropertyMap>,
+        report: &mut DryRunReport,
+    ) -> Result<()> {
+        let Some(type_id) = self.catalog.get_type_id(rel_type)? else {
+            return Ok(());
+        };
+        let what = format!("[:{rel_type}]");
+        let Some(props) = self.static_properties(properties, &what, report) else {
+            return Ok(());
+        };
+        collect_violation(self.enforce_rel_constraints(type_id, &props), report)
+    }
+
+    /// Run the endpoint constraint check the write path runs for a
+    /// created `[:rel_type]`. Endpoints bound before the first write are
+    /// checked on every row reaching it; the first violation is
+    /// reported, as the write would stop there.
+    fn check_created_endpoints(
+        &mut self,
+        ast: &CypherQuery,
+        prefix: &[Clause],
+        rel_type: &str,
+        source: Endpoint,
+        target: Endpoint,
+        report: &mut DryRunReport,
+    ) -> Result<()> {
+        let Some(type_id) = self.catalog.get_type_id(rel_type)? else {
+            return Ok(());
+        };
+        if !self.rel_endpoint_constraints.contains_key(&type_id) {
+            return Ok(());
+        }
+        let not_checked = || format!("endpoint constraint on [:{rel_type}] not checked");
+        if matches!(source, Endpoint::Unknown) || matches!(target, Endpoint::Unknown) {
+            report
+                .warnings
+                .push(format!("{}: an endpoint is bound by MERGE", not_checked()));
+            return Ok(());
+        }
+
+        let bound: Vec<&str> = [&source, &target]
+            .into_iter()
+            .filter_map(|endpoint| match endpoint {
+                Endpoint::Bound(v) => Some(v.as_str()),
+                _ => None,
+            })
+            .collect();
+        let rows = if bound.is_empty() {
+            vec![Vec::new()]
+        } else {
+            let items = bound
+                .iter()
+                .enumerate()
+                .map(|(i, v)| ReturnItem {
+                    expression: Expression::FunctionCall {
+                        name: "id".to_string(),
+                        args: vec![Expression::Variable(v.to_string())],
+                    },
+                    alias: Some(format!("endpoint{i}")),
+                })
+                .collect();
+            match self.run_prefix(ast, prefix, items)? {
+                Some(result) => result.rows.into_iter().map(|row| row.values).collect(),
+                None => {
+                    report.warnings.push(format!(
+                        "{}: the clauses before it cannot be run without writes",
+                        not_checked()
+                    ));
+                    return Ok(());
+                }
+            }
+        };
+
+        for row in rows {
+            let mut ids = row.iter().filter_map(serde_json::Value::as_u64);
+            let mut describe = |side: &str, endpoint: &Endpoint| match endpoint {
+                Endpoint::Created(labels) => (
+                    format!("new {side} node :{}", labels.join(":")),
+                    self.label_bits_of(labels),
+                ),
+                _ => {
+                    let id = ids.next().unwrap_or_default();
+                    let bits = self
+                        .storage
+                        .read_node(id)
+                        .map(|r| r.label_bits)
+                        .unwrap_or(0);
+                    (format!("{side} node {id}"), bits)
+                }
+            };
+            let (source_name, source_bits) = describe("source", &source);
+            let (target_name, target_bits) = describe("target", &target);
+            let before = report.constraint_violations.len();
+            collect_violation(
+                self.enforce_rel_endpoint_labels(
+                    type_id,
+                    (&source_name, source_bits),
+                    (&target_name, target_bits),
+                ),
+                report,
+            )?;
+            if report.constraint_violations.len() > before {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the SET / REMOVE `updates` to the entities each row reaching
+    /// the first write binds, and run the constraint checks the write
+    /// path runs on the result. The first violation is reported, as the
+    /// write would stop there.
+    fn check_updates(
+        &mut self,
+        ast: &CypherQuery,
+        prefix: &[Clause],
+        nodes: &HashSet<&str>,
+        updates: &[(&str, Update<'_>)],
+        report: &mut DryRunReport,
+    ) -> Result<()> {
+        let mut targets: Vec<&str> = Vec::new();
+        for (target, _) in updates {
+            if !targets.contains(target) {
+                targets.push(*target);
+            }
+        }
+        let items = targets
+            .iter()
+            .enumerate()
+            .map(|(i, v)| ReturnItem {
+                expression: Expression::FunctionCall {
+                    name: "id".to_string(),
+                    args: vec![Expression::Variable(v.to_string())],
+                },
+                alias: Some(format!("target{i}")),
+            })
+            .collect();
+        let Some(result) = self.run_prefix(ast, prefix, items)? else {
+            report.warnings.push(
+                "constraints on SET / REMOVE not checked: the clauses before them cannot be run \
+                 without writes"
+                    .to_string(),
+            );
+            return Ok(());
+        };
+
+        for row in result.rows {
+            let before = report.constraint_violations.len();
+            for (target, id) in targets.iter().zip(&row.values) {
+                // OPTIONAL MATCH leaves the variable NULL; the write skips it.
+                let Some(id) = id.as_u64() else {
+                    continue;
+                };
+                let changes = updates.iter().filter(|(v, _)| v == target).map(|(_, u)| u);
+                let outcome = if nodes.contains(target) {
+                    let mut label_ids = self.effective_label_ids_from_record(id)?;
+                    let mut props = self.load_node_properties_map(id)?;
+                    for change in changes {
+                        match change {
+                            Update::SetProperty(key, serde_json::Value::Null)
+                            | Update::RemoveProperty(key) => {
+                                props.remove(*key);
+                            }
+                            Update::SetProperty(key, value) => {
+                                props.insert(key.to_string(), value.clone());
+                            }
+                            // A label the catalog does not know has no constraints.
+                            Update::AddLabel(label) => {
+                                if let Ok(label_id) = self.catalog.get_label_id(label)
+                                    && !label_ids.contains(&label_id)
+                                {
+                                    label_ids.push(label_id);
+                                }
+                            }
+                            Update::RemoveLabel(label) => {
+                                if let Ok(label_id) = self.catalog.get_label_id(label) {
+                                    label_ids.retain(|l| *l != label_id);
+                                }
+                            }
+                        }
+                    }
+                    let props = serde_json::Value::Object(props);
+                    self.check_constraints(&label_ids, &props, &[id])
+                        .and_then(|()| {
+                            self.enforce_extended_node_constraints(&label_ids, &props, &[id])
+                        })
+                } else {
+                    let type_id = self.storage.read_rel(id)?.type_id;
+                    let mut props = match self.storage.load_relationship_properties(id)? {
+                        Some(serde_json::Value::Object(map)) => map,
+                        _ => serde_json::Map::new(),
+                    };
+                    for change in changes {
+                        match change {
+                            Update::SetProperty(key, serde_json::Value::Null)
+                            | Update::RemoveProperty(key) => {
+                                props.remove(*key);
+                            }
+                            Update::SetProperty(key, value) => {
+                                props.insert(key.to_string(), value.clone());
+                            }
+                            Update::AddLabel(_) | Update::RemoveLabel(_) => {}
+                        }
+                    }
+                    self.enforce_rel_constraints(type_id, &serde_json::Value::Object(props))
+                };
+                collect_violation(outcome, report)?;
+            }
+            if report.constraint_violations.len() > before {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Label bits of a node carrying `labels`; labels the catalog does
+    /// not know yet set no bit.
+    fn label_bits_of(&self, labels: &[String]) -> u64 {
+        labels
+            .iter()
+            .filter_map(|l| self.catalog.get_label_id(l).ok())
+            .filter(|id| *id < 64)
+            .fold(0, |bits, id| bits | (1u64 << id))
+    }
+}
+
+fn property_count(properties: &Option<executor::parser::PropertyMap>) -> u64 {
+    properties.as_ref().map_or(0, |p| p.properties.len() as u64)
+}
+
+/// Record a constraint violation in the report; other errors propagate.
+fn collect_violation(outcome: Result<()>, report: &mut DryRunReport) -> Result<()> {
+    match outcome {
+        Err(Error::ConstraintViolation(violation)) => {
+            report.constraint_violations.push(*violation);
+            Ok(())
+        }
+        other => other,
+    }
+}
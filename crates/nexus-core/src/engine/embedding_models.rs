@@ -0,0 +1,157 @@
+//! Per-property embedding model bindings.
+//!
+//! A binding records, in the catalog, that a `(label, property)` pair
+//! holds embeddings produced by a named model (`text-embedding-3-small`,
+//! `bge-m3`, …) with a fixed number of dimensions. Nexus does not run
+//! the model itself; the binding is what an embedding pipeline reads to
+//! know which model to call and how long the vectors must be, and what
+//! schema introspection (`db.schema`, the REST schema endpoints) reports
+//! so MCP/LLM agents can tell which fields are semantically searchable.
+//!
+//! Vector indexes consume the binding: creating one over a bound
+//! property with `dimensions = 0` takes the bound dimension count, and
+//! an explicit count that disagrees with the binding is rejected, as is
+//! binding a property to a dimension count its vector index does not
+//! use.
+
+use super::Engine;
+use super::vector_indexes::MAX_VECTOR_DIMENSIONS;
+use crate::{Error, Result};
+
+/// An embedding model binding as reported to clients.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct EmbeddingModelInfo {
+    /// Label whose nodes carry the embeddings.
+    pub label: String,
+    /// Property holding the embeddings.
+    pub property: String,
+    /// Model identifier the embeddings come from.
+    pub model: String,
+    /// Embedding length.
+    pub dimensions: usize,
+    /// Vector index serving searches over the property, if any.
+    pub vector_index: Option<String>,
+}
+
+impl Engine {
+    /// Bind `(label, property)` to embedding model `model` producing
+    /// vectors of `dimensions` components, replacing any previous
+    /// binding.
+    ///
+    /// Fails with [`Error::InvalidInput`] for an empty label, property
+    /// or model, a dimension outside `1..=MAX_VECTOR_DIMENSIONS`, or a
+    /// vector index over the pair with a different dimension count.
+    pub fn set_embedding_model(
+        &mut self,
+        label: &str,
+        property: &str,
+        model: &str,
+        dimensions: usize,
+    ) -> Result<EmbeddingModelInfo> {
+        self.ensure_writable()?;
+        if label.is_empty() || property.is_empty() || model.trim().is_empty() {
+            return Err(Error::invalid_input(
+                "embedding model label, property and model must not be empty",
+            ));
+        }
+        if dimensions == 0 || dimensions > MAX_VECTOR_DIMENSIONS {
+            return Err(Error::invalid_input(format!(
+                "dimensions must be between 1 and {MAX_VECTOR_DIMENSIONS}, got {dimensions}"
+            )));
+        }
+        if let Some(index) = self.vector_index_over(label, property)
+            && index.dimensions != dimensions
+        {
+            return Err(Error::invalid_input(format!(
+                "vector index {:?} over :{label}({property}) has {} dimensions, not {dimensions}",
+                index.name, index.dimensions
+            )));
+        }
+
+        let label_id = self.catalog.get_or_create_label(label)?;
+        let key_id = self.catalog.get_or_create_key(property)?;
+        self.catalog
+            .persist_embedding_model(label_id, key_id, model, dimensions as u32)?;
+        Ok(EmbeddingModelInfo {
+            label: label.to_string(),
+            property: property.to_string(),
+            model: model.to_string(),
+            dimensions,
+            vector_index: self.vector_index_over(label, property).map(|i| i.name),
+        })
+    }
+
+    /// Remove the binding of `(label, property)`. Embeddings and vector
+    /// indexes are left alone. Returns whether a binding existed.
+    pub fn drop_embedding_model(&mut self, label: &str, property: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        let (Ok(label_id), Ok(key_id)) = (
+            self.catalog.get_label_id(label),
+            self.catalog.get_key_id(property),
+        ) else {
+            return Ok(false);
+        };
+        self.catalog.remove_embedding_model(label_id, key_id)
+    }
+
+    /// Binding of `(label, property)`, if any.
+    pub fn embedding_model(
+        &self,
+        label: &str,
+        property: &str,
+    ) -> Result<Option<EmbeddingModelInfo>> {
+        let (Ok(label_id), Ok(key_id)) = (
+            self.catalog.get_label_id(label),
+            self.catalog.get_key_id(property),
+        ) else {
+            return Ok(None);
+        };
+        Ok(self
+            .catalog
+            .get_embedding_model(label_id, key_id)?
+            .map(|(model, dimensions)| EmbeddingModelInfo {
+                label: label.to_string(),
+                property: property.to_string(),
+                model,
+                dimensions: dimensions as usize,
+                vector_index: self.vector_index_over(label, property).map(|i| i.name),
+            }))
+    }
+
+    /// Every embedding model binding, by label and property.
+    pub fn embedding_models(&self) -> Result<Vec<EmbeddingModelInfo>> {
+        let mut models = Vec::new();
+        for ((label_id, key_id), (model, dimensions)) in self.catalog.list_embedding_models()? {
+            let (Some(label), Some(property)) = (
+                self.catalog.get_label_name(label_id)?,
+                self.catalog.get_key_name(key_id)?,
+            ) else {
+                continue;
+            };
+            let vector_index = self.vector_index_over(&label, &property).map(|i| i.name);
+            models.push(EmbeddingModelInfo {
+                label,
+                property,
+                model,
+                dimensions: dimensions as usize,
+                vector_index,
+            });
+        }
+        models.sort_by(|a, b| (&a.label, &a.property).cmp(&(&b.label, &b.property)));
+        Ok(models)
+    }
+
+    /// Vector index over `(label, property)`, if one exists.
+    fn vector_index_over(
+        &self,
+        label: &str,
+        property: &str,
+    ) -> Option<crate::index::VectorIndexDefinition> {
+        self.indexes
+            .vectors
+            .list()
+            .into_iter()
+            .map(|entry| entry.definition)
+            .find(|d| d.label == label && d.property == property)
+    }
+}
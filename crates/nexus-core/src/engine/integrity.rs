@@ -0,0 +1,355 @@
+//! Startup integrity verification.
+//!
+//! Every engine constructor runs [`Engine::verify_integrity`] at the
+//! level configured in [`IntegrityCheckConfig`] before the first query
+//! is served:
+//!
+//! * [`IntegrityScanLevel::Quick`] (default) — the record / property
+//!   stores and the catalog open and read, every WAL frame checksums
+//!   and decodes, and the record changes journalled after the last
+//!   background store flush are in the stores (see
+//!   [`super::write_pipeline`]). Cost is proportional to the WAL, not
+//!   the graph. The record stores carry no file header of their own, so
+//!   "headers" here means the catalog databases and the store files
+//!   themselves.
+//! * [`IntegrityScanLevel::Full`] — quick, plus a scan of every record:
+//!   node labels and relationship types resolve in the catalog, every
+//!   relationship chain reachable from a node stays in range, only
+//!   threads relationships incident to that node and terminates, every
+//!   live relationship points at live endpoints, and the external-id
+//!   index's forward and reverse maps agree.
+//!
+//! When issues are found, [`CorruptionPolicy`] decides between refusing
+//! to open (`FailFast`, default) and opening read-only (`ReadOnly`):
+//! reads are served, every write is rejected with
+//! [`Error::ReadOnly`](crate::Error::ReadOnly) and the report stays
+//! available through [`Engine::startup_integrity_report`] so an operator
+//! can inspect it before restoring from backup.
+
+use super::Engine;
+use crate::{Error, Result, wal};
+
+/// Issues kept verbatim in an [`IntegrityReport`]; further ones are only
+/// counted.
+const MAX_REPORTED_ISSUES: usize = 100;
+
+/// How much of the store [`Engine::verify_integrity`] inspects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityScanLevel {
+    /// Skip verification entirely.
+    Off,
+    /// Store / catalog accessibility and WAL frame checksums.
+    #[default]
+    Quick,
+    /// Quick, plus record-chain verification and index cross-checks.
+    Full,
+}
+
+impl std::str::FromStr for IntegrityScanLevel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Ok(Self::Off),
+            "quick" => Ok(Self::Quick),
+            "full" => Ok(Self::Full),
+            other => Err(Error::invalid_input(format!(
+                "unknown integrity scan level {other:?} (expected off, quick or full)"
+            ))),
+        }
+    }
+}
+
+/// What the engine does when the startup scan reports issues.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorruptionPolicy {
+    /// Refuse to open: the constructor returns the report summary as an
+    /// error.
+    #[default]
+    FailFast,
+    /// Open, serve reads, and reject every write.
+    ReadOnly,
+}
+
+impl std::str::FromStr for CorruptionPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "fail_fast" => Ok(Self::FailFast),
+            "read_only" => Ok(Self::ReadOnly),
+            other => Err(Error::invalid_input(format!(
+                "unknown corruption policy {other:?} (expected fail_fast or read_only)"
+            ))),
+        }
+    }
+}
+
+/// Startup verification settings, part of [`super::EngineConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct IntegrityCheckConfig {
+    /// Scan depth.
+    pub level: IntegrityScanLevel,
+    /// Reaction to a non-clean report.
+    pub on_corruption: CorruptionPolicy,
+}
+
+/// A single inconsistency found by the scan.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct IntegrityIssue {
+    /// Subsystem the issue was found in (`storage`, `catalog`, `wal`,
+    /// `store_flush`, `relationship_chain`, `external_id_index`).
+    pub component: &'static str,
+    /// Human-readable description, naming the record involved.
+    pub detail: String,
+}
+
+/// Outcome of [`Engine::verify_integrity`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IntegrityReport {
+    /// Level the scan ran at.
+    pub level: IntegrityScanLevel,
+    /// WAL frames that checksummed and decoded.
+    pub wal_entries: u64,
+    /// Node records inspected (full scans only).
+    pub nodes_checked: u64,
+    /// Relationship records inspected (full scans only).
+    pub relationships_checked: u64,
+    /// Total issues found, including ones not kept in `issues`.
+    pub issue_count: u64,
+    /// The first [`MAX_REPORTED_ISSUES`] issues.
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Whether the scan found nothing wrong.
+    pub fn is_clean(&self) -> bool {
+        self.issue_count == 0
+    }
+
+    /// One-line summary used in logs and errors.
+    pub fn summary(&self) -> String {
+        match self.issues.first() {
+            None => format!("{:?} integrity scan clean", self.level),
+            Some(first) => format!(
+                "{:?} integrity scan found {} issue(s); first: [{}] {}",
+                self.level, self.issue_count, first.component, first.detail
+            ),
+        }
+    }
+
+    pub(super) fn push(&mut self, component: &'static str, detail: String) {
+        self.issue_count += 1;
+        if self.issues.len() < MAX_REPORTED_ISSUES {
+            self.issues.push(IntegrityIssue { component, detail });
+        }
+    }
+}
+
+impl Engine {
+    /// Verify on-disk state at `level`. Never repairs anything; torn
+    /// trailing WAL frames are truncated exactly as crash recovery does.
+    pub fn verify_integrity(&mut self, level: IntegrityScanLevel) -> Result<IntegrityReport> {
+        let mut report = IntegrityReport {
+            level,
+            ..Default::default()
+        };
+        if level == IntegrityScanLevel::Off {
+            return Ok(report);
+        }
+
+        self.check_stores_and_wal(&mut report)?;
+        if level == IntegrityScanLevel::Full {
+            self.check_node_records(&mut report)?;
+            self.check_relationship_records(&mut report)?;
+            if let Err(e) = self.catalog.external_id_index().verify_consistency() {
+                report.push("external_id_index", e.to_string());
+            }
+        }
+        Ok(report)
+    }
+
+    /// Report of the scan run by the constructor; `None` when the
+    /// configured level was `Off`.
+    pub fn startup_integrity_report(&self) -> Option<&IntegrityReport> {
+        self.integrity_report.as_ref()
+    }
+
+    /// Why the engine is refusing writes, if it is.
+    pub fn read_only_reason(&self) -> Option<&str> {
+        self.read_only_reason.as_deref()
+    }
+
+    /// Reject a write while the engine is read-only.
+    pub(crate) fn ensure_writable(&self) -> Result<()> {
+        match &self.read_only_reason {
+            None => Ok(()),
+            Some(reason) => Err(Error::ReadOnly(format!("ERR_READ_ONLY: {reason}"))),
+        }
+    }
+
+    /// Run the configured startup scan and apply the corruption policy.
+    pub(super) fn run_startup_integrity_check(
+        &mut self,
+        config: &IntegrityCheckConfig,
+    ) -> Result<()> {
+        if config.level == IntegrityScanLevel::Off {
+            return Ok(());
+        }
+        let report = self.verify_integrity(config.level)?;
+        if !report.is_clean() {
+            let summary = report.summary();
+            match config.on_corruption {
+                CorruptionPolicy::FailFast => {
+                    return Err(Error::storage(format!(
+                        "ERR_INTEGRITY_CHECK_FAILED: {summary}"
+                    )));
+                }
+                CorruptionPolicy::ReadOnly => {
+                    tracing::error!("{summary}; opening read-only");
+                    self.read_only_reason = Some(format!(
+                        "engine opened read-only after a failed integrity check: {summary}"
+                    ));
+                }
+            }
+        }
+        self.integrity_report = Some(report);
+        Ok(())
+    }
+
+    fn check_stores_and_wal(&mut self, report: &mut IntegrityReport) -> Result<()> {
+        if let Err(e) = self.storage.health_check() {
+            report.push("storage", e.to_string());
+        }
+        if let Err(e) = self.storage.property_store.read().unwrap().health_check() {
+            report.push("storage", e.to_string());
+        }
+        if let Err(e) = self.catalog.health_check() {
+            report.push("catalog", e.to_string());
+        }
+
+        // Everything buffered must be on disk before the frames are read
+        // back, otherwise an in-flight tail would look torn.
+        self.flush_async_wal()?;
+        let mut wal = wal::Wal::new(self.wal.path())?;
+        match wal.recover() {
+            Ok(entries) => {
+                report.wal_entries = entries.len() as u64;
+                self.check_unflushed_store_tail(&entries, report);
+            }
+            Err(e) => report.push("wal", e.to_string()),
+        }
+        Ok(())
+    }
+
+    fn check_node_records(&self, report: &mut IntegrityReport) -> Result<()> {
+        let node_count = self.storage.node_count();
+        let rel_count = self.storage.relationship_count();
+        for node_id in 0..node_count {
+            let node = match self.storage.read_node(node_id) {
+                Ok(node) => node,
+                Err(e) => {
+                    report.push("storage", format!("node {node_id} unreadable: {e}"));
+                    continue;
+                }
+            };
+            if node.is_deleted() {
+                continue;
+            }
+            report.nodes_checked += 1;
+
+            let label_bits = node.label_bits;
+            for label_id in (0..64u32).filter(|b| label_bits & (1u64 << b) != 0) {
+                if self.catalog.get_label_name(label_id)?.is_none() {
+                    report.push(
+                        "catalog",
+                        format!("node {node_id} carries unknown label_id={label_id}"),
+                    );
+                }
+            }
+
+            // Chain pointers are `rel_id + 1`, 0 terminates. A chain can
+            // never be longer than the relationship store, so a longer
+            // walk means a cycle.
+            let mut rel_ptr = node.first_rel_ptr;
+            let mut hops = 0u64;
+            while rel_ptr != 0 {
+                let rel_id = rel_ptr - 1;
+                if rel_id >= rel_count {
+                    report.push(
+                        "relationship_chain",
+                        format!("node {node_id} chain points past the store at rel {rel_id}"),
+                    );
+                    break;
+                }
+                hops += 1;
+                if hops > rel_count {
+                    report.push(
+                        "relationship_chain",
+                        format!("node {node_id} chain does not terminate (cycle at rel {rel_id})"),
+                    );
+                    break;
+                }
+                let rel = self.storage.read_rel(rel_id)?;
+                let (src, dst) = (rel.src_id, rel.dst_id);
+                rel_ptr = if src == node_id {
+                    rel.next_src_ptr
+                } else if dst == node_id {
+                    rel.next_dst_ptr
+                } else {
+                    report.push(
+                        "relationship_chain",
+                        format!(
+                            "node {node_id} chain threads rel {rel_id} ({src}->{dst}) \
+                             which is not incident to it"
+                        ),
+                    );
+                    break;
+                };
+            }
+        }
+        Ok(())
+    }
+
+    fn check_relationship_records(&self, report: &mut IntegrityReport) -> Result<()> {
+        let node_count = self.storage.node_count();
+        for rel_id in 0..self.storage.relationship_count() {
+            let rel = match self.storage.read_rel(rel_id) {
+                Ok(rel) => rel,
+                Err(e) => {
+                    report.push("storage", format!("relationship {rel_id} unreadable: {e}"));
+                    continue;
+                }
+            };
+            if rel.is_deleted() {
+                continue;
+            }
+            report.relationships_checked += 1;
+
+            let (src, dst, type_id) = (rel.src_id, rel.dst_id, rel.type_id);
+            if self.catalog.get_type_name(type_id)?.is_none() {
+                report.push(
+                    "catalog",
+                    format!("relationship {rel_id} has unknown type_id={type_id}"),
+                );
+            }
+            for (end, node_id) in [("source", src), ("target", dst)] {
+                let live = node_id < node_count
+                    && self
+                        .storage
+                        .read_node(node_id)
+                        .is_ok_and(|n| !n.is_deleted());
+                if !live {
+                    report.push(
+                        "storage",
+                        format!("relationship {rel_id} {end} node {node_id} is missing or deleted"),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
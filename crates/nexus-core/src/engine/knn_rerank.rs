@@ -0,0 +1,285 @@
+//! Hybrid KNN re-ranking: vector similarity combined with graph signals.
+//!
+//! [`Engine::knn_rerank`] over-fetches a candidate pool from the vector
+//! index, keeps the candidates carrying the requested label, scores each
+//! with a Cypher arithmetic expression over the signals below, and
+//! returns the best `k`. Scoring runs next to the data, so clients get
+//! the final ranking instead of a candidate list to post-process.
+//!
+//! | variable     | meaning                                                   |
+//! |--------------|-----------------------------------------------------------|
+//! | `similarity` | cosine similarity reported by the vector index            |
+//! | `degree`     | live relationships touching the node (either direction)   |
+//! | `pagerank`   | PageRank over the whole graph (damping 0.85)              |
+//! | `proximity`  | `1 / (1 + hops)` to `context_node` ignoring direction, `0` when unreachable |
+//!
+//! The expression supports numeric literals, the variables, `+ - * / % ^`,
+//! unary minus, and `abs`, `sqrt`, `exp`, `log`, `log10`. Graph signals
+//! are computed only when the expression names them; each needs one
+//! pass over the stored graph, so they cost O(nodes + relationships) per
+//! call.
+
+use super::Engine;
+use crate::executor::parser::{self, BinaryOperator, Expression, Literal, UnaryOperator};
+use crate::{Error, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Re-ranking settings for [`Engine::knn_rerank`].
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct KnnRerankOptions {
+    /// Scoring expression; higher scores rank first.
+    #[serde(default = "default_expression")]
+    pub expression: String,
+    /// Node `proximity` is measured from.
+    #[serde(default)]
+    pub context_node: Option<u64>,
+    /// Vector-index candidates to fetch before re-ranking (default `4 * k`,
+    /// never below `k`).
+    #[serde(default)]
+    pub candidates: Option<usize>,
+}
+
+fn default_expression() -> String {
+    "similarity".to_string()
+}
+
+impl Default for KnnRerankOptions {
+    fn default() -> Self {
+        Self {
+            expression: default_expression(),
+            context_node: None,
+            candidates: None,
+        }
+    }
+}
+
+/// One re-ranked result. Graph signals are `None` unless the scoring
+/// expression used them.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RankedKnnCandidate {
+    /// Node ID.
+    pub node_id: u64,
+    /// Final score from the expression.
+    pub score: f64,
+    /// Cosine similarity from the vector index.
+    pub similarity: f32,
+    /// Relationship count, when used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degree: Option<u64>,
+    /// PageRank, when used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagerank: Option<f64>,
+    /// Hops to the context node, when `proximity` was used and it is reachable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<u64>,
+}
+
+const SIGNALS: [&str; 4] = ["similarity", "degree", "pagerank", "proximity"];
+
+impl Engine {
+    /// Return the `k` nodes labelled `label` whose embeddings are closest
+    /// to `vector`, ordered by `options.expression` (see the module docs).
+    pub fn knn_rerank(
+        &self,
+        label: &str,
+        vector: &[f32],
+        k: usize,
+        options: &KnnRerankOptions,
+    ) -> Result<Vec<RankedKnnCandidate>> {
+        let mut p = parser::CypherParser::new(options.expression.clone());
+        let expr = p
+            .parse_expression()
+            .map_err(|e| Error::CypherSyntax(format!("invalid KNN scoring expression: {e}")))?;
+        let mut used = HashSet::new();
+        collect_signals(&expr, &mut used)?;
+        if used.contains("proximity") && options.context_node.is_none() {
+            return Err(Error::CypherExecution(
+                "KNN scoring expression uses `proximity` but no context_node was given".to_string(),
+            ));
+        }
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        let Ok(label_id) = self.catalog.get_label_id(label) else {
+            return Ok(Vec::new());
+        };
+
+        let pool = options.candidates.unwrap_or(k.saturating_mul(4)).max(k);
+        let mut candidates = Vec::new();
+        for (node_id, similarity) in self.knn_search(label, vector, pool)? {
+            let Ok(record) = self.storage.read_node(node_id) else {
+                continue;
+            };
+            if !record.is_deleted() && label_id < 64 && record.label_bits & (1 << label_id) != 0 {
+                candidates.push((node_id, similarity));
+            }
+        }
+
+        let needs_graph = used.iter().any(|s| *s != "similarity");
+        let graph = if needs_graph {
+            Some(crate::graph::algorithms::Graph::from_engine(self, None)?)
+        } else {
+            None
+        };
+        let mut degrees: HashMap<u64, u64> = HashMap::new();
+        let mut undirected: HashMap<u64, Vec<u64>> = HashMap::new();
+        if let Some(graph) = &graph {
+            for node in graph.get_nodes() {
+                for &(neighbour, _) in graph.get_neighbors(node) {
+                    *degrees.entry(node).or_default() += 1;
+                    *degrees.entry(neighbour).or_default() += 1;
+                    if used.contains("proximity") {
+                        undirected.entry(node).or_default().push(neighbour);
+                        undirected.entry(neighbour).or_default().push(node);
+                    }
+                }
+            }
+        }
+        let ranks = match &graph {
+            Some(graph) if used.contains("pagerank") => graph.pagerank(0.85, 100, 1e-4),
+            _ => HashMap::new(),
+        };
+        let hops = match options.context_node {
+            Some(context) if used.contains("proximity") => bfs_hops(&undirected, context),
+            _ => HashMap::new(),
+        };
+
+        let mut ranked = Vec::with_capacity(candidates.len());
+        for (node_id, similarity) in candidates {
+            let mut candidate = RankedKnnCandidate {
+                node_id,
+                score: 0.0,
+                similarity,
+                degree: used
+                    .contains("degree")
+                    .then(|| degrees.get(&node_id).copied().unwrap_or(0)),
+                pagerank: used
+                    .contains("pagerank")
+                    .then(|| ranks.get(&node_id).copied().unwrap_or(0.0)),
+                distance: hops.get(&node_id).copied(),
+            };
+            let proximity = candidate.distance.map_or(0.0, |d| 1.0 / (1.0 + d as f64));
+            let score = evaluate(&expr, &|name| match name {
+                "similarity" => f64::from(candidate.similarity),
+                "degree" => candidate.degree.unwrap_or(0) as f64,
+                "pagerank" => candidate.pagerank.unwrap_or(0.0),
+                _ => proximity,
+            })?;
+            candidate.score = score;
+            ranked.push(candidate);
+        }
+        ranked.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.similarity.total_cmp(&a.similarity))
+                .then_with(|| a.node_id.cmp(&b.node_id))
+        });
+        ranked.truncate(k);
+        Ok(ranked)
+    }
+}
+
+/// Record the signals `expr` references, rejecting unsupported forms.
+fn collect_signals(expr: &Expression, used: &mut HashSet<&'static str>) -> Result<()> {
+    match expr {
+        Expression::Literal(Literal::Integer(_) | Literal::Float(_)) => Ok(()),
+        Expression::Variable(name) => match SIGNALS.iter().find(|s| **s == name.as_str()) {
+            Some(signal) => {
+                used.insert(*signal);
+                Ok(())
+            }
+            None => Err(Error::CypherSyntax(format!(
+                "unknown KNN scoring variable `{name}` (expected one of {})",
+                SIGNALS.join(", ")
+            ))),
+        },
+        Expression::BinaryOp { left, op, right } => match op {
+            BinaryOperator::Add
+            | BinaryOperator::Subtract
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Modulo
+            | BinaryOperator::Power => {
+                collect_signals(left, used)?;
+                collect_signals(right, used)
+            }
+            other => Err(Error::CypherSyntax(format!(
+                "operator {other:?} is not supported in KNN scoring expressions"
+            ))),
+        },
+        Expression::UnaryOp {
+            op: UnaryOperator::Minus | UnaryOperator::Plus,
+            operand,
+        } => collect_signals(operand, used),
+        Expression::FunctionCall { name, args } if args.len() == 1 && scalar_fn(name).is_some() => {
+            collect_signals(&args[0], used)
+        }
+        other => Err(Error::CypherSyntax(format!(
+            "unsupported KNN scoring expression: {other:?}"
+        ))),
+    }
+}
+
+fn scalar_fn(name: &str) -> Option<fn(f64) -> f64> {
+    match name.to_ascii_lowercase().as_str() {
+        "abs" => Some(f64::abs),
+        "sqrt" => Some(f64::sqrt),
+        "exp" => Some(f64::exp),
+        "log" => Some(f64::ln),
+        "log10" => Some(f64::log10),
+        _ => None,
+    }
+}
+
+/// Evaluate an expression already checked by [`collect_signals`].
+fn evaluate(expr: &Expression, signal: &dyn Fn(&str) -> f64) -> Result<f64> {
+    Ok(match expr {
+        Expression::Literal(Literal::Integer(i)) => *i as f64,
+        Expression::Literal(Literal::Float(f)) => *f,
+        Expression::Variable(name) => signal(name),
+        Expression::BinaryOp { left, op, right } => {
+            let (l, r) = (evaluate(left, signal)?, evaluate(right, signal)?);
+            match op {
+                BinaryOperator::Add => l + r,
+                BinaryOperator::Subtract => l - r,
+                BinaryOperator::Multiply => l * r,
+                BinaryOperator::Divide => l / r,
+                BinaryOperator::Modulo => l % r,
+                _ => l.powf(r),
+            }
+        }
+        Expression::UnaryOp { op, operand } => {
+            let v = evaluate(operand, signal)?;
+            if matches!(op, UnaryOperator::Minus) {
+                -v
+            } else {
+                v
+            }
+        }
+        Expression::FunctionCall { name, args } => match scalar_fn(name) {
+            Some(f) => f(evaluate(&args[0], signal)?),
+            None => unreachable!("validated by collect_signals"),
+        },
+        other => {
+            return Err(Error::Internal(format!(
+                "unvalidated KNN scoring expression: {other:?}"
+            )));
+        }
+    })
+}
+
+/// Hop counts from `source` over an undirected adjacency map.
+fn bfs_hops(adjacency: &HashMap<u64, Vec<u64>>, source: u64) -> HashMap<u64, u64> {
+    let mut hops = HashMap::from([(source, 0)]);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        let next = hops[&node] + 1;
+        for &neighbour in adjacency.get(&node).map(Vec::as_slice).unwrap_or(&[]) {
+            if let std::collections::hash_map::Entry::Vacant(slot) = hops.entry(neighbour) {
+                slot.insert(next);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    hops
+}
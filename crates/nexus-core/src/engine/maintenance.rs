@@ -133,7 +133,8 @@ impl Engine {
         stats.rel_counts.clear();
         self.catalog.update_statistics(&stats)?;
 
-        Ok(())
+        // Journalled records before this point no longer exist.
+        self.mark_stores_flushed()
     }
 
     /// Validate the entire graph for integrity and consistency.
@@ -0,0 +1,68 @@
+//! Cache shrinking under memory pressure.
+//!
+//! The server samples memory usage with a
+//! [`MemoryPressureMonitor`](crate::memory_management::MemoryPressureMonitor)
+//! and calls [`Engine::relieve_memory_pressure`] with the level it
+//! reports. The page cache shrinks step by step while pressure lasts
+//! and grows back the same way once it is gone:
+//!
+//! - **soft** — page cache capacity halves on each call, down to an
+//!   eighth of its configured size; cached query results and objects
+//!   are dropped.
+//! - **hard** — page cache capacity goes straight to the floor and every
+//!   cache layer that can be rebuilt from storage is emptied.
+//! - **normal** — page cache capacity doubles on each call until it is
+//!   back at its configured size.
+
+use super::Engine;
+use crate::Result;
+use crate::memory_management::MemoryPressureLevel;
+
+/// The page cache never shrinks below its configured capacity divided
+/// by this.
+const PAGE_CACHE_FLOOR_DIVISOR: usize = 8;
+
+/// What one [`Engine::relieve_memory_pressure`] call did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct MemoryRelief {
+    /// Level the call reacted to
+    pub level: MemoryPressureLevel,
+    /// Page cache capacity after the call, in pages
+    pub page_cache_capacity: usize,
+    /// Pages evicted to reach that capacity
+    pub pages_evicted: usize,
+}
+
+impl Engine {
+    /// Shrink (or, at [`MemoryPressureLevel::Normal`], regrow) the
+    /// engine caches for `level`.
+    pub fn relieve_memory_pressure(&mut self, level: MemoryPressureLevel) -> Result<MemoryRelief> {
+        let configured = self.page_cache.configured_capacity();
+        let floor = (configured / PAGE_CACHE_FLOOR_DIVISOR).max(1);
+        let current = self.page_cache.capacity();
+        let target = match level {
+            MemoryPressureLevel::Normal => current.saturating_mul(2).min(configured),
+            MemoryPressureLevel::Soft => (current / 2).max(floor),
+            MemoryPressureLevel::Hard => floor,
+        };
+
+        let pages_evicted = if target == current {
+            0
+        } else {
+            self.page_cache.resize(target)?
+        };
+        self.cache.shed_for_memory_pressure(level);
+
+        Ok(MemoryRelief {
+            level,
+            page_cache_capacity: self.page_cache.capacity(),
+            pages_evicted,
+        })
+    }
+
+    /// Whether the page cache is below its configured capacity, i.e.
+    /// an earlier pressure episode has not been fully undone yet.
+    pub fn page_cache_shrunk(&self) -> bool {
+        self.page_cache.capacity() < self.page_cache.configured_capacity()
+    }
+}
@@ -0,0 +1,549 @@
+//! Entity resolution: collapsing duplicate nodes into one.
+//!
+//! `CALL nexus.merge.nodes([id1, id2, ...] [, {properties: 'combine',
+//! relationships: 'merge'}])` keeps the first node and folds the rest
+//! into it:
+//!
+//! * labels are unioned;
+//! * properties are merged by [`PropertyMergePolicy`] — `discard` keeps
+//!   the first value seen (the surviving node wins), `overwrite` keeps
+//!   the last, `combine` (default) keeps equal values once and gathers
+//!   conflicting ones into a list;
+//! * every relationship of a duplicate is moved onto the surviving node,
+//!   including relationships between the merged nodes, which become
+//!   self-loops. With `relationships: 'merge'` relationships that end up
+//!   with the same type, direction and other endpoint are collapsed into
+//!   one, their properties merged by the same policy; `keep` (default)
+//!   leaves them parallel;
+//! * the duplicates are deleted.
+//!
+//! All inputs and the merged node's constraints are validated before
+//! anything is written. The rewrite then runs in one write transaction:
+//! relationships are moved, the duplicates deleted and the surviving
+//! node rewritten. A failure part-way (a relationship constraint on a
+//! moved relationship, an I/O error) aborts the transaction and undoes
+//! the steps already applied — moved relationships are put back under
+//! their own ids and the merged nodes restored with their labels,
+//! properties and index entries — so a failed merge leaves the graph as
+//! it was. Each step and its undo are journalled as they are applied,
+//! so the log matches the store. The incident relationships are found
+//! by walking each merged node's adjacency chain. As with the
+//! relationship refactor procedures, moved relationships are recreated
+//! and get new ids.
+
+use super::Engine;
+use super::refactor::RelationshipRewriteLog;
+use crate::storage::NodeRecord;
+use crate::{Error, Result, executor, transaction, wal};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// How conflicting property values are resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PropertyMergePolicy {
+    /// First value seen wins.
+    Discard,
+    /// Last value seen wins.
+    Overwrite,
+    /// Distinct values are gathered into a list.
+    #[default]
+    Combine,
+}
+
+/// What happens to relationships that become parallel after merging.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RelationshipMergePolicy {
+    /// Keep every relationship.
+    #[default]
+    Keep,
+    /// Collapse relationships sharing type, direction and endpoints.
+    Merge,
+}
+
+/// Outcome of [`Engine::merge_nodes`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeMergeReport {
+    /// The surviving node.
+    pub node_id: u64,
+    /// Duplicates deleted.
+    pub merged: u64,
+    /// Relationships moved from a duplicate onto the surviving node.
+    pub relationships_rewired: u64,
+    /// Relationships removed by collapsing parallel ones.
+    pub relationships_collapsed: u64,
+}
+
+/// A live relationship incident to one of the merged nodes.
+struct IncidentRel {
+    id: u64,
+    src: u64,
+    dst: u64,
+    type_id: u32,
+}
+
+/// What a merge has written so far, so a failure can be undone.
+#[derive(Default)]
+struct MergeUndo {
+    relationships: RelationshipRewriteLog,
+    /// Each merged node's record and properties before the merge.
+    nodes: Vec<(u64, NodeRecord, Option<Value>)>,
+    /// Duplicates already deleted.
+    deleted: Vec<u64>,
+    /// Whether the surviving node was rewritten.
+    rewritten: bool,
+}
+
+impl Engine {
+    /// Merge the nodes in `node_ids` into the first one. Repeated ids
+    /// are ignored; a single distinct id is a no-op.
+    pub fn merge_nodes(
+        &mut self,
+        node_ids: &[u64],
+        properties: PropertyMergePolicy,
+        relationships: RelationshipMergePolicy,
+    ) -> Result<NodeMergeReport> {
+        self.ensure_writable()?;
+        let mut ids: Vec<u64> = Vec::with_capacity(node_ids.len());
+        for &id in node_ids {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        let Some(&target) = ids.first() else {
+            return Err(Error::CypherExecution(
+                "ERR_INVALID_ARG_VALUE: nexus.merge.nodes requires at least one node id"
+                    .to_string(),
+            ));
+        };
+
+        let mut labels: Vec<String> = Vec::new();
+        let mut merged_props: Map<String, Value> = Map::new();
+        let mut seen: HashMap<String, Vec<Value>> = HashMap::new();
+        for &id in &ids {
+            let (node_labels, node_props) = self.node_labels_and_properties(id)?;
+            for label in node_labels {
+                if !labels.contains(&label) {
+                    labels.push(label);
+                }
+            }
+            merge_property_map(&mut merged_props, &mut seen, node_props, properties);
+        }
+        let mut report = NodeMergeReport {
+            node_id: target,
+            ..Default::default()
+        };
+        if ids.len() == 1 {
+            return Ok(report);
+        }
+
+        // Every label comes from an existing node, so look the ids up
+        // rather than creating catalog entries before validation.
+        let mut label_ids = Vec::with_capacity(labels.len());
+        for label in &labels {
+            label_ids.push(self.catalog.get_label_id(label)?);
+        }
+        let merged_props = Value::Object(merged_props);
+        self.check_constraints(&label_ids, &merged_props, &ids)?;
+        self.enforce_extended_node_constraints(&label_ids, &merged_props, &ids)?;
+        if let Some(map) = merged_props.as_object() {
+            self.check_node_vectors(&label_ids, map)?;
+        }
+
+        // Snapshot every relationship touching a merged node, then work
+        // out the rewritten set before writing anything.
+        let incident = self.incident_relationships(&ids)?;
+        let remap = |node: u64| if ids.contains(&node) { target } else { node };
+
+        // Groups of relationships that become one; under `Keep` every
+        // relationship is its own group.
+        let mut groups: Vec<Vec<IncidentRel>> = Vec::new();
+        let mut group_of: HashMap<(u64, u64, u32), usize> = HashMap::new();
+        for rel in incident {
+            let key = (remap(rel.src), remap(rel.dst), rel.type_id);
+            match (relationships, group_of.get(&key)) {
+                (RelationshipMergePolicy::Merge, Some(&g)) => groups[g].push(rel),
+                _ => {
+                    group_of.insert(key, groups.len());
+                    groups.push(vec![rel]);
+                }
+            }
+        }
+
+        let outcome = self.apply_node_merge(
+            &ids,
+            groups,
+            labels,
+            &label_ids,
+            merged_props,
+            properties,
+            &mut report,
+        );
+        // The merge, or its undo, is journalled; make it durable and
+        // visible either way.
+        self.storage.flush()?;
+        self.flush_async_wal()?;
+        self.refresh_executor()?;
+        outcome.map(|()| report)
+    }
+
+    /// Live relationships threaded on the adjacency chains of `ids`, in
+    /// id order. A relationship between two of the nodes is listed once.
+    fn incident_relationships(&self, ids: &[u64]) -> Result<Vec<IncidentRel>> {
+        let rel_count = self.storage.relationship_count();
+        let mut seen = HashSet::new();
+        let mut incident = Vec::new();
+        for &node_id in ids {
+            // Chain pointers are `rel_id + 1`, 0 terminates; the hop
+            // bound keeps a corrupted (cyclic) chain from spinning.
+            let mut rel_ptr = self.storage.read_node(node_id)?.first_rel_ptr;
+            let mut hops = 0u64;
+            while rel_ptr != 0 && hops < rel_count {
+                hops += 1;
+                let rel_id = rel_ptr - 1;
+                let record = self.storage.read_rel(rel_id)?;
+                // Copy out of the #[repr(packed)] record.
+                let (src, dst, type_id) = (record.src_id, record.dst_id, record.type_id);
+                rel_ptr = if src == node_id {
+                    record.next_src_ptr
+                } else if dst == node_id {
+                    record.next_dst_ptr
+                } else {
+                    return Err(Error::storage(format!(
+                        "relationship chain of node {node_id} threads rel {rel_id}, \
+                         which is not incident to it"
+                    )));
+                };
+                if !record.is_deleted() && seen.insert(rel_id) {
+                    incident.push(IncidentRel {
+                        id: rel_id,
+                        src,
+                        dst,
+                        type_id,
+                    });
+                }
+            }
+        }
+        incident.sort_unstable_by_key(|rel| rel.id);
+        Ok(incident)
+    }
+
+    /// Write a validated merge in one write transaction: move the
+    /// relationship `groups` onto `ids[0]`, delete the other ids and
+    /// rewrite the surviving node. On error the transaction is aborted
+    /// and every step already applied is undone.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_node_merge(
+        &mut self,
+        ids: &[u64],
+        groups: Vec<Vec<IncidentRel>>,
+        labels: Vec<String>,
+        label_ids: &[u32],
+        merged_props: Value,
+        properties: PropertyMergePolicy,
+        report: &mut NodeMergeReport,
+    ) -> Result<()> {
+        let mut undo = MergeUndo::default();
+        for &id in ids {
+            let record = self.storage.read_node(id)?;
+            let props = self.storage.load_node_properties(id)?;
+            undo.nodes.push((id, record, props));
+        }
+        let mut tx = self.transaction_manager.write().begin_write()?;
+        let outcome = self
+            .write_node_merge(
+                &mut tx,
+                &mut undo,
+                ids,
+                groups,
+                labels,
+                label_ids,
+                merged_props,
+                properties,
+                report,
+            )
+            .and_then(|()| self.transaction_manager.write().commit(&mut tx));
+        if let Err(e) = outcome {
+            if tx.state == transaction::TxState::Active
+                && let Err(abort_err) = self.transaction_manager.write().abort(&mut tx)
+            {
+                tracing::warn!("Failed to abort node merge: {}", abort_err);
+            }
+            self.undo_node_merge(undo, label_ids)?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// The writes of [`Self::apply_node_merge`] inside `tx`, each noted
+    /// in `undo` as it is applied.
+    #[allow(clippy::too_many_arguments)]
+    fn write_node_merge(
+        &mut self,
+        tx: &mut transaction::Transaction,
+        undo: &mut MergeUndo,
+        ids: &[u64],
+        groups: Vec<Vec<IncidentRel>>,
+        labels: Vec<String>,
+        label_ids: &[u32],
+        merged_props: Value,
+        properties: PropertyMergePolicy,
+        report: &mut NodeMergeReport,
+    ) -> Result<()> {
+        let target = ids[0];
+        self.rewire_relationships(tx, &mut undo.relationships, ids, groups, properties, report)?;
+        for &duplicate in &ids[1..] {
+            let mut session_tx: Option<&mut transaction::Transaction> = Some(&mut *tx);
+            self.delete_node_with_transaction(duplicate, &mut session_tx)?;
+            undo.deleted.push(duplicate);
+            self.write_wal_async(wal::WalEntry::DeleteNode { node_id: duplicate })?;
+            report.merged += 1;
+        }
+        {
+            let mut session_tx: Option<&mut transaction::Transaction> = Some(&mut *tx);
+            self.update_node_with_transaction(
+                target,
+                labels,
+                merged_props.clone(),
+                &mut session_tx,
+            )?;
+        }
+        undo.rewritten = true;
+        self.indexes.label_index.add_node(target, label_ids)?;
+        self.maintain_indexed_properties(target, label_ids, &merged_props)
+    }
+
+    /// Put the graph back as it was before a failed merge: the moved
+    /// relationships, then each merged node's record, properties, index
+    /// entries and label counts, journalling every restored node.
+    fn undo_node_merge(&mut self, undo: MergeUndo, label_ids: &[u32]) -> Result<()> {
+        self.undo_relationship_rewrite(undo.relationships)?;
+        let target = undo.nodes.first().map(|(id, ..)| *id);
+        for (node_id, record, props) in undo.nodes {
+            let image = wal::WalEntry::NodeImage {
+                node_id,
+                record: bytemuck::bytes_of(&record).to_vec(),
+                properties: match &props {
+                    Some(props) => serde_json::to_vec(props)?,
+                    None => Vec::new(),
+                },
+            };
+            self.storage.restore_node(node_id, record, props.clone())?;
+            let restored_labels: Vec<u32> = (0..64u32)
+                .filter(|bit| record.label_bits & (1u64 << bit) != 0)
+                .collect();
+            let props = props.unwrap_or_else(|| Value::Object(Map::new()));
+            self.reindex_restored_node(node_id, Some((&restored_labels, &props)))?;
+            // Deleting a duplicate took its labels off the counts;
+            // rewriting the surviving node added the merged labels.
+            if undo.deleted.contains(&node_id) {
+                for &label_id in &restored_labels {
+                    self.catalog.increment_node_count(label_id)?;
+                }
+            } else if undo.rewritten && Some(node_id) == target {
+                for &label_id in label_ids {
+                    self.catalog.decrement_node_count(label_id)?;
+                }
+            }
+            self.write_wal_async(image)?;
+        }
+        Ok(())
+    }
+
+    /// Recreate each group of relationships as one relationship between
+    /// the remapped endpoints inside `tx`, journalling as it goes and
+    /// noting every change in `log`.
+    fn rewire_relationships(
+        &mut self,
+        tx: &mut transaction::Transaction,
+        log: &mut RelationshipRewriteLog,
+        ids: &[u64],
+        groups: Vec<Vec<IncidentRel>>,
+        properties: PropertyMergePolicy,
+        report: &mut NodeMergeReport,
+    ) -> Result<()> {
+        let target = ids[0];
+        let remap = |node: u64| if ids.contains(&node) { target } else { node };
+        let touches_duplicate = |rel: &IncidentRel| {
+            (rel.src != target && ids.contains(&rel.src))
+                || (rel.dst != target && ids.contains(&rel.dst))
+        };
+        for group in groups {
+            if group.len() == 1 && !touches_duplicate(&group[0]) {
+                continue;
+            }
+            report.relationships_rewired +=
+                group.iter().filter(|rel| touches_duplicate(rel)).count() as u64;
+            report.relationships_collapsed += group.len() as u64 - 1;
+
+            let mut rel_props = Map::new();
+            let mut rel_seen = HashMap::new();
+            for rel in &group {
+                if let Some(Value::Object(props)) =
+                    self.storage.load_relationship_properties(rel.id)?
+                {
+                    merge_property_map(&mut rel_props, &mut rel_seen, props, properties);
+                }
+            }
+            let first = &group[0];
+            let (src, dst, type_id) = (remap(first.src), remap(first.dst), first.type_id);
+            let rel_type = self.catalog.get_type_name(type_id)?.ok_or_else(|| {
+                Error::Internal(format!("relationship type id {type_id} not in catalog"))
+            })?;
+            self.recreate_relationship(tx, log, src, dst, &rel_type, Value::Object(rel_props))?;
+            for rel in &group {
+                self.replace_relationship(tx, log, rel.id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Engine-side dispatch for `CALL nexus.merge.nodes(...)`.
+    pub(super) fn execute_merge_nodes(
+        &mut self,
+        call: &executor::parser::CallProcedureClause,
+    ) -> Result<executor::ResultSet> {
+        const NAME: &str = "nexus.merge.nodes";
+        let mut args = Vec::with_capacity(call.arguments.len());
+        for expr in &call.arguments {
+            args.push(self.evaluate_set_expression(expr, "", &Map::new())?);
+        }
+
+        let node_ids = match args.first() {
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|v| {
+                    v.as_u64().ok_or_else(|| {
+                        Error::CypherExecution(format!(
+                            "ERR_INVALID_ARG_TYPE: {NAME} `nodes` must be LIST<INTEGER> \
+                             of node ids (got element {v})"
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<u64>>>()?,
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {NAME} `nodes` must be LIST<INTEGER> (got {other})"
+                )));
+            }
+            None => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_MISSING_ARG: {NAME} requires `nodes`"
+                )));
+            }
+        };
+
+        let mut property_policy = PropertyMergePolicy::default();
+        let mut relationship_policy = RelationshipMergePolicy::default();
+        match args.get(1) {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(config)) => {
+                for (key, value) in config {
+                    let choice = value.as_str().unwrap_or_default();
+                    match (key.as_str(), choice) {
+                        ("properties", "discard") => property_policy = PropertyMergePolicy::Discard,
+                        ("properties", "overwrite") => {
+                            property_policy = PropertyMergePolicy::Overwrite
+                        }
+                        ("properties", "combine") => property_policy = PropertyMergePolicy::Combine,
+                        ("relationships", "keep") => {
+                            relationship_policy = RelationshipMergePolicy::Keep
+                        }
+                        ("relationships", "merge") => {
+                            relationship_policy = RelationshipMergePolicy::Merge
+                        }
+                        ("properties", _) | ("relationships", _) => {
+                            return Err(Error::CypherExecution(format!(
+                                "ERR_INVALID_ARG_VALUE: {NAME} config `{key}` must be one of \
+                                 {} (got {value})",
+                                if key == "properties" {
+                                    "'discard', 'overwrite', 'combine'"
+                                } else {
+                                    "'keep', 'merge'"
+                                }
+                            )));
+                        }
+                        _ => {
+                            return Err(Error::CypherExecution(format!(
+                                "ERR_INVALID_ARG_VALUE: {NAME} unknown config key `{key}` \
+                                 (expected `properties` or `relationships`)"
+                            )));
+                        }
+                    }
+                }
+            }
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {NAME} `config` must be MAP (got {other})"
+                )));
+            }
+        }
+
+        let report = self.merge_nodes(&node_ids, property_policy, relationship_policy)?;
+
+        let all_columns = [
+            "nodeId",
+            "merged",
+            "relationshipsRewired",
+            "relationshipsCollapsed",
+        ];
+        let columns: Vec<String> = match &call.yield_columns {
+            Some(cols) => cols.clone(),
+            None => all_columns.iter().map(|c| c.to_string()).collect(),
+        };
+        let mut values = Vec::with_capacity(columns.len());
+        for col in &columns {
+            values.push(match col.as_str() {
+                "nodeId" => Value::from(report.node_id),
+                "merged" => Value::from(report.merged),
+                "relationshipsRewired" => Value::from(report.relationships_rewired),
+                "relationshipsCollapsed" => Value::from(report.relationships_collapsed),
+                other => {
+                    return Err(Error::CypherExecution(format!(
+                        "Unknown procedure output: `{other}` ({NAME} yields {})",
+                        all_columns
+                            .iter()
+                            .map(|c| format!("`{c}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )));
+                }
+            });
+        }
+        Ok(executor::ResultSet::new(
+            columns,
+            vec![executor::Row { values }],
+        ))
+    }
+}
+
+/// Fold `props` into `merged` under `policy`. `seen` tracks the distinct
+/// values per key for `Combine`.
+fn merge_property_map(
+    merged: &mut Map<String, Value>,
+    seen: &mut HashMap<String, Vec<Value>>,
+    props: Map<String, Value>,
+    policy: PropertyMergePolicy,
+) {
+    for (key, value) in props {
+        match policy {
+            PropertyMergePolicy::Discard => {
+                merged.entry(key).or_insert(value);
+            }
+            PropertyMergePolicy::Overwrite => {
+                merged.insert(key, value);
+            }
+            PropertyMergePolicy::Combine => {
+                let values = seen.entry(key.clone()).or_default();
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+                let combined = if values.len() == 1 {
+                    values[0].clone()
+                } else {
+                    Value::Array(values.clone())
+                };
+                merged.insert(key, combined);
+            }
+        }
+    }
+}
@@ -20,14 +20,39 @@ use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+pub mod admin;
+pub mod artifacts;
 pub mod clustering;
 pub mod config;
+pub mod count_aggregates;
 pub mod crud;
+pub mod csr_export;
+pub mod dry_run;
 pub mod dynamic_labels;
+pub mod embedding_models;
 pub mod graph_scope;
+pub mod integrity;
+pub mod knn_rerank;
 pub mod maintenance;
+pub mod memory_pressure;
+pub mod merge_nodes;
+pub mod neighborhood;
+pub mod node_diff;
+pub mod plan_baseline;
+pub mod property_bloom;
+pub mod property_history;
+pub mod property_search;
+pub mod refactor;
+pub mod result_diff;
+pub mod schema_log;
+pub mod settings;
 pub mod stats;
+pub mod storage_metrics;
+pub mod ttl;
 pub mod typed_collections;
+pub mod vector_indexes;
+pub mod views;
+pub mod write_pipeline;
 
 // Extracted impl-block modules (engine/mod.rs split).
 mod constraints;
@@ -40,8 +65,45 @@ mod write_exec;
 #[cfg(test)]
 mod tests;
 
+pub use admin::{CacheFlush, CacheLayerReport, CacheReport, WalCheckpoint, WalInfo};
+pub use artifacts::{Artifact, ArtifactStore, ArtifactSummary, ArtifactVersion};
 pub use config::{EngineConfig, GraphStatistics};
+pub use count_aggregates::CountAggregateSpec;
+pub use csr_export::{CsrDirection, CsrExportOptions, CsrGraph};
+pub use dry_run::{DryRunReport, WriteEstimates};
+pub use embedding_models::EmbeddingModelInfo;
+pub use integrity::{
+    CorruptionPolicy, IntegrityCheckConfig, IntegrityIssue, IntegrityReport, IntegrityScanLevel,
+};
+pub use knn_rerank::{KnnRerankOptions, RankedKnnCandidate};
+pub use memory_pressure::MemoryRelief;
+pub use merge_nodes::{NodeMergeReport, PropertyMergePolicy, RelationshipMergePolicy};
+pub use neighborhood::{
+    DEFAULT_RELATIONSHIP_PAGE_SIZE, MAX_RELATIONSHIP_PAGE_SIZE, NodeRelationship, RelationshipPage,
+};
+pub use node_diff::NodeDiff;
+pub use plan_baseline::{
+    DEFAULT_PLAN_REGRESSION_THRESHOLD, PLAN_BASELINE_ARTIFACT, PlanBaseline, PlanBaselineSummary,
+    PlanChange, PlanComparison,
+};
+pub use property_bloom::PropertyBloomFilterSpec;
+pub use property_history::PropertyHistorySpec;
+pub use property_search::{
+    DEFAULT_PROPERTY_SEARCH_LIMIT, DEFAULT_PROPERTY_SEARCH_SCAN_LIMIT, MAX_PROPERTY_SEARCH_LIMIT,
+    PropertyMatch, PropertySearchResult,
+};
+pub use refactor::RelationshipRefactorReport;
+pub use result_diff::{ChangedRow, ResultDiff, ResultDiffOptions, UnmatchedRow, diff_results};
+pub use settings::{SETTINGS, SettingDefinition, SettingInfo, SettingSource, SettingType};
 pub use stats::{EngineStats, HealthState, HealthStatus};
+pub use storage_metrics::{StorageMetrics, StoreFileMetrics};
+pub use ttl::{TtlPolicySpec, TtlSweepReport, TtlSweeperMetrics, ttl_sweeper_metrics};
+pub use vector_indexes::{
+    VectorIndexInfo, VectorIndexMigrationInfo, VectorUpsertFailure, VectorUpsertReport,
+};
+pub use write_pipeline::{
+    CommitMode, DEFAULT_STORE_FLUSH_INTERVAL_MS, WritePipelineConfig, WritePipelineStats,
+};
 
 // `NodeWriteState` lives in `crud.rs` alongside the CRUD methods
 // that build and consume it; re-import under the short name so the
@@ -65,6 +127,8 @@ fn json_type_label(v: &serde_json::Value) -> &'static str {
         serde_json::Value::Object(_) => {
             if crate::executor::eval::bytes::is_bytes_value(v) {
                 "BYTES"
+            } else if crate::executor::eval::decimal::is_decimal_value(v) {
+                "DECIMAL"
             } else {
                 "MAP"
             }
@@ -178,6 +242,9 @@ pub struct Engine {
     pub executor: executor::Executor,
     /// Multi-layer cache system for performance optimization
     pub cache: cache::MultiLayerCache,
+    /// Versioned analysis results (clustering, layouts) saved under
+    /// `<data_dir>/artifacts`.
+    pub artifacts: artifacts::ArtifactStore,
     /// Optional cluster-mode quota provider. When set AND a
     /// `UserContext` is supplied to
     /// [`Self::execute_cypher_with_context`], the engine gates
@@ -229,6 +296,9 @@ pub struct Engine {
     pub(crate) rel_not_null_constraints: Vec<crate::constraints::RelNotNullConstraint>,
     /// Property-type constraints — `REQUIRE n.p IS :: <TYPE>` (§7).
     pub(crate) property_type_constraints: Vec<crate::constraints::PropertyTypeConstraint>,
+    /// Relationship endpoint constraints keyed by type id. Mirrors the
+    /// catalog's `rel_endpoint_constraints` store; loaded at startup.
+    pub(crate) rel_endpoint_constraints: HashMap<u32, crate::constraints::RelEndpointConstraint>,
     /// Compatibility flag — when `true`, violations downgrade to a
     /// `warn` log instead of rejecting the write (§10). Default
     /// `false`; scheduled for removal at v1.5.
@@ -244,6 +314,50 @@ pub struct Engine {
     /// no dangling forward/reverse entries are left behind.  The field is
     /// cleared (drained) by both the commit and abort paths.
     pub(crate) pending_external_ids: Vec<(u64, crate::storage::external_id::ExternalId)>,
+    /// Monotonic generation of `executor`'s storage snapshot. Bumped by
+    /// every [`Self::refresh_executor`]; surfaced to clients as the
+    /// read-your-writes consistency token (see
+    /// [`Self::consistency_token`]).
+    pub(crate) executor_generation: u64,
+    /// `executor_generation` at the last refresh of each materialized
+    /// view. A view without an entry (never refreshed by this process)
+    /// counts as stale.
+    pub(crate) view_refresh_generations: HashMap<String, u64>,
+    /// `executor_generation` at which writes last touched each label
+    /// and relationship type, compared against
+    /// `view_refresh_generations` to find the views a write affected.
+    pub(crate) view_write_generations: views::WriteGenerations,
+    /// Result of the startup integrity scan (`None` when disabled).
+    pub(crate) integrity_report: Option<integrity::IntegrityReport>,
+    /// Set when the startup scan found corruption under
+    /// [`integrity::CorruptionPolicy::ReadOnly`], or when the engine
+    /// serves an attached backup; every write is then rejected with
+    /// this reason.
+    pub(crate) read_only_reason: Option<String>,
+    /// Normal form applied to incoming strings; see
+    /// [`EngineConfig::string_normalization`].
+    pub(crate) string_normalization: crate::text::StringNormalization,
+    /// Limits for the `nexus.load.*` remote data functions; see
+    /// [`EngineConfig::external_data`].
+    pub(crate) external_data: Arc<crate::external_data::ExternalDataConfig>,
+    /// Property history policies: `(label_id, key_id)` → previous values
+    /// kept per node. See [`property_history`].
+    pub(crate) property_history: HashMap<(u32, u32), usize>,
+    /// User the current writes are attributed to in property history.
+    pub(crate) acting_user: Option<String>,
+    /// Plans and latencies recorded per query template, `None` while
+    /// recording is off. See [`plan_baseline`].
+    pub(crate) plan_history: Option<crate::performance::plan_history::PlanHistory>,
+    /// Commit path settings; see [`write_pipeline`].
+    pub(crate) write_pipeline: write_pipeline::WritePipelineConfig,
+    /// Background store flusher, running in the pipelined commit mode.
+    pub(crate) store_flusher: Option<write_pipeline::StoreFlusher>,
+    /// Configuration `executor` is rebuilt with; the executor knobs of
+    /// the settings registry live here. See [`settings`].
+    pub(crate) executor_config: executor::ExecutorConfig,
+    /// Setting values before the stored runtime overrides were
+    /// applied, restored when an override is dropped.
+    pub(crate) startup_settings: settings::SettingValues,
 }
 
 impl Engine {
@@ -284,7 +398,8 @@ impl Engine {
         let catalog = catalog::Catalog::new(data_dir.join("catalog.mdb"))?;
 
         // Initialize record stores
-        let storage = storage::RecordStore::new(data_dir)?;
+        let storage =
+            storage::RecordStore::with_backend(data_dir, Arc::clone(&config.storage_backend))?;
 
         // Initialize page cache
         let page_cache = page_cache::PageCache::new(config.page_cache_capacity)?;
@@ -303,7 +418,8 @@ impl Engine {
         let transaction_manager_arc = Arc::new(RwLock::new(transaction_manager));
 
         // Initialize session manager (shares TransactionManager Arc)
-        let session_manager = session::SessionManager::new(transaction_manager_arc.clone());
+        let session_manager = session::SessionManager::new(transaction_manager_arc.clone())
+            .with_timeouts(config.session_timeouts);
 
         // Initialize index manager
         let indexes = index::IndexManager::new(data_dir.join("indexes"))?;
@@ -332,6 +448,7 @@ impl Engine {
             indexes,
             executor,
             cache,
+            artifacts: artifacts::ArtifactStore::new(data_dir.join("artifacts")),
             quota_provider: None,
             current_params: HashMap::new(),
             unwind_bindings: HashMap::new(),
@@ -340,9 +457,28 @@ impl Engine {
             node_key_constraints: Vec::new(),
             rel_not_null_constraints: Vec::new(),
             property_type_constraints: Vec::new(),
+            rel_endpoint_constraints: HashMap::new(),
             relaxed_constraint_enforcement: false,
             _temp_dir: None,
             pending_external_ids: Vec::new(),
+            executor_generation: 0,
+            view_refresh_generations: HashMap::new(),
+            view_write_generations: Default::default(),
+            integrity_report: None,
+            read_only_reason: None,
+            string_normalization: config.string_normalization,
+            external_data: Arc::new(config.external_data.clone()),
+            property_history: HashMap::new(),
+            acting_user: None,
+            plan_history: config.plan_history.enabled.then(|| {
+                crate::performance::plan_history::PlanHistory::new(
+                    config.plan_history.max_templates,
+                )
+            }),
+            write_pipeline: Default::default(),
+            store_flusher: None,
+            executor_config: Default::default(),
+            startup_settings: settings::SettingValues::defaults(),
         };
 
         // Configure cache in executor for relationship index access
@@ -351,6 +487,8 @@ impl Engine {
 
         engine.rebuild_indexes_from_storage()?;
         engine.recover_external_ids_from_wal()?;
+        engine.replay_unflushed_store_tail()?;
+        engine.run_startup_integrity_check(&config.integrity_check)?;
 
         // phase6_opencypher-advanced-types §3.5 — install the
         // composite-B-tree registry on the executor so `db.indexes()`
@@ -374,6 +512,42 @@ impl Engine {
         engine
             .executor
             .install_property_index(engine.indexes.property_index.clone());
+        engine
+            .executor
+            .install_property_bloom(engine.indexes.property_bloom.clone());
+        engine
+            .executor
+            .install_vector_indexes(engine.indexes.vectors.clone());
+        engine
+            .executor
+            .install_count_aggregates(engine.indexes.count_aggregates.clone());
+        engine
+            .executor
+            .install_commit_hooks(engine.transaction_manager.read().commit_hooks().clone());
+        engine
+            .executor
+            .install_external_data(engine.external_data.clone());
+        if let Some(history) = &engine.plan_history {
+            engine.executor.install_plan_history(history.clone());
+        }
+        for spec in &config.property_bloom_filters {
+            engine.create_property_bloom_filter(
+                &spec.label,
+                &spec.property,
+                spec.expected_items,
+            )?;
+        }
+        for spec in &config.ttl_policies {
+            engine.create_ttl_policy(&spec.label, &spec.property)?;
+        }
+        for spec in &config.count_aggregates {
+            engine.create_count_aggregate(&spec.name, &spec.label, spec.group_by.as_deref())?;
+        }
+        for spec in &config.property_history {
+            engine.track_property_history(&spec.label, &spec.property, spec.max_entries)?;
+        }
+        engine.set_write_pipeline_config(config.write_pipeline)?;
+        engine.load_persisted_settings()?;
 
         Ok(engine)
     }
@@ -416,6 +590,40 @@ impl Engine {
         self.quota_provider.is_some()
     }
 
+    /// Commit hooks run for this engine's writes, shared by its
+    /// transaction managers. See [`transaction::hooks`].
+    pub fn commit_hooks(&self) -> transaction::CommitHooks {
+        self.transaction_manager.read().commit_hooks().clone()
+    }
+
+    /// Change the Unicode normal form applied to incoming strings.
+    /// Only affects later writes and queries; stored data is not
+    /// rewritten.
+    pub fn set_string_normalization(&mut self, form: crate::text::StringNormalization) {
+        self.string_normalization = form;
+    }
+
+    /// Unicode normal form currently applied to incoming strings.
+    pub fn string_normalization(&self) -> crate::text::StringNormalization {
+        self.string_normalization
+    }
+
+    /// Replace the limits for the `nexus.load.*` remote data functions.
+    /// Takes effect for the next query.
+    pub fn set_external_data_config(
+        &mut self,
+        config: crate::external_data::ExternalDataConfig,
+    ) -> Result<()> {
+        self.external_data = Arc::new(config);
+        self.refresh_executor()
+    }
+
+    /// Limits currently applied to the `nexus.load.*` remote data
+    /// functions.
+    pub fn external_data_config(&self) -> &crate::external_data::ExternalDataConfig {
+        &self.external_data
+    }
+
     /// Approximate storage bytes owned by a specific tenant
     /// namespace. Sums `node_count * NODE_RECORD_SIZE` across every
     /// label whose catalog name carries the `ns` prefix, plus
@@ -528,6 +736,7 @@ impl Engine {
             indexes,
             executor,
             cache,
+            artifacts: artifacts::ArtifactStore::new(data_dir.join("artifacts")),
             quota_provider: None,
             current_params: HashMap::new(),
             unwind_bindings: HashMap::new(),
@@ -536,13 +745,30 @@ impl Engine {
             node_key_constraints: Vec::new(),
             rel_not_null_constraints: Vec::new(),
             property_type_constraints: Vec::new(),
+            rel_endpoint_constraints: HashMap::new(),
             relaxed_constraint_enforcement: false,
             _temp_dir: None,
             pending_external_ids: Vec::new(),
+            executor_generation: 0,
+            view_refresh_generations: HashMap::new(),
+            view_write_generations: Default::default(),
+            integrity_report: None,
+            read_only_reason: None,
+            string_normalization: Default::default(),
+            external_data: Default::default(),
+            property_history: HashMap::new(),
+            acting_user: None,
+            plan_history: None,
+            write_pipeline: Default::default(),
+            store_flusher: None,
+            executor_config: Default::default(),
+            startup_settings: settings::SettingValues::defaults(),
         };
 
         engine.rebuild_indexes_from_storage()?;
         engine.recover_external_ids_from_wal()?;
+        engine.replay_unflushed_store_tail()?;
+        engine.run_startup_integrity_check(&integrity::IntegrityCheckConfig::default())?;
 
         // phase6_opencypher-advanced-types §3.5 — install the
         // composite-B-tree registry on the executor so `db.indexes()`
@@ -561,6 +787,22 @@ impl Engine {
         engine
             .executor
             .install_property_index(engine.indexes.property_index.clone());
+        engine
+            .executor
+            .install_property_bloom(engine.indexes.property_bloom.clone());
+        engine
+            .executor
+            .install_vector_indexes(engine.indexes.vectors.clone());
+        engine
+            .executor
+            .install_count_aggregates(engine.indexes.count_aggregates.clone());
+        engine
+            .executor
+            .install_commit_hooks(engine.transaction_manager.read().commit_hooks().clone());
+        engine
+            .executor
+            .install_external_data(engine.external_data.clone());
+        engine.load_persisted_settings()?;
 
         Ok(engine)
     }
@@ -574,6 +816,11 @@ impl Engine {
     }
 
     fn rebuild_indexes_from_storage(&mut self) -> Result<()> {
+        // Enum definitions first: the property-index rebuild below reads
+        // node properties and must see decoded enum values.
+        self.load_enum_property_types()?;
+        self.load_rel_endpoint_constraints()?;
+
         // Clear the index first to ensure we start fresh
         self.indexes.label_index.clear()?;
 
@@ -626,6 +873,12 @@ impl Engine {
             }
         }
 
+        // Named vector indexes persist only their definitions; refill the
+        // HNSW graphs from the embeddings stored on the nodes.
+        if let Err(e) = self.rebuild_vector_indexes() {
+            tracing::warn!("vector-index rebuild failed: {e}");
+        }
+
         Ok(())
     }
 
@@ -751,11 +1004,12 @@ impl Engine {
     /// which has its own PropertyStore instance
     pub fn refresh_executor(&mut self) -> Result<()> {
         // Recreate executor with current storage state
-        self.executor = executor::Executor::new(
+        self.executor = executor::Executor::new_with_config(
             &self.catalog,
             &self.storage,
             &self.indexes.label_index,
             &self.indexes.knn_index,
+            self.executor_config.clone(),
         )?;
         // phase6_opencypher-advanced-types §3.5 — share the composite
         // B-tree registry so `db.indexes()` sees it and the planner can
@@ -772,6 +1026,54 @@ impl Engine {
         // so the planner can consult it for USING INDEX seeks.
         self.executor
             .install_property_index(self.indexes.property_index.clone());
+        self.executor
+            .install_property_bloom(self.indexes.property_bloom.clone());
+        self.executor
+            .install_vector_indexes(self.indexes.vectors.clone());
+        self.executor
+            .install_count_aggregates(self.indexes.count_aggregates.clone());
+        self.executor
+            .install_commit_hooks(self.transaction_manager.read().commit_hooks().clone());
+        self.executor
+            .install_external_data(self.external_data.clone());
+        if let Some(history) = &self.plan_history {
+            self.executor.install_plan_history(history.clone());
+        }
+        self.executor_generation += 1;
+        self.note_view_writes();
+        Ok(())
+    }
+
+    /// Read-your-writes consistency token for the current executor
+    /// snapshot.
+    ///
+    /// Every committed autocommit write (and every explicit `COMMIT`)
+    /// leaves the engine with a refreshed executor before returning, so
+    /// the token read right after a write is guaranteed to cover it.
+    /// Clients echo the token back on subsequent reads; a read whose
+    /// token is covered by [`Self::satisfies_consistency_token`] can run
+    /// on a cloned executor without taking the engine write lock.
+    ///
+    /// Tokens are scoped to this engine instance — the counter restarts
+    /// at zero when the process restarts.
+    pub fn consistency_token(&self) -> u64 {
+        self.executor_generation
+    }
+
+    /// True when the executor snapshot already includes every write that
+    /// was acknowledged with `token`.
+    pub fn satisfies_consistency_token(&self, token: u64) -> bool {
+        self.executor_generation >= token
+    }
+
+    /// Make sure the executor snapshot covers `token`, refreshing it when
+    /// the token is ahead of this engine (e.g. minted before a restart,
+    /// where the counter started over). After a refresh the snapshot
+    /// reflects every committed write, so the guarantee holds either way.
+    pub fn ensure_consistency_token(&mut self, token: u64) -> Result<()> {
+        if !self.satisfies_consistency_token(token) {
+            self.refresh_executor()?;
+        }
         Ok(())
     }
 
@@ -795,6 +1097,11 @@ impl Engine {
         })
     }
 
+    /// Name of the backend holding this engine's store files.
+    pub fn storage_backend_name(&self) -> &str {
+        self.storage.backend().name()
+    }
+
     /// Write a WAL entry asynchronously (if async writer is enabled)
     /// Falls back to synchronous WAL if async writer is not available
     pub fn write_wal_async(&mut self, entry: wal::WalEntry) -> Result<()> {
@@ -809,10 +1116,11 @@ impl Engine {
         }
     }
 
-    /// Force flush all pending async WAL entries
+    /// Force flush all pending async WAL entries and wait until they
+    /// are on disk
     pub fn flush_async_wal(&mut self) -> Result<()> {
         if let Some(ref writer) = self.async_wal_writer {
-            writer.flush()?;
+            writer.sync()?;
         }
         Ok(())
     }
@@ -851,6 +1159,9 @@ impl Default for Engine {
 
 impl Drop for Engine {
     fn drop(&mut self) {
+        // The store flusher's last marker goes through the WAL writer,
+        // so it stops first.
+        self.store_flusher = None;
         // Ensure async WAL writer is properly shut down
         if let Some(ref mut writer) = self.async_wal_writer {
             if let Err(e) = writer.shutdown() {
@@ -0,0 +1,156 @@
+//! Paginated access to a node's relationships.
+//!
+//! [`Engine::node_relationships`] walks the node's adjacency chain in
+//! storage directly — no Cypher parse or plan — and stops as soon as a
+//! page is full, so a client can page through a supernode's edges
+//! without materialising the whole neighbourhood.
+//!
+//! The cursor is the id of the first relationship of the next page.
+//! Chains are threaded newest-first, so relationships created after a
+//! walk began are not visited by it; relationships deleted mid-walk are
+//! skipped. A cursor is only meaningful for the node it was issued for.
+
+use super::Engine;
+use crate::executor::Direction;
+use crate::{Error, Result};
+
+/// Page size used when the caller does not ask for one.
+pub const DEFAULT_RELATIONSHIP_PAGE_SIZE: usize = 100;
+/// Upper bound on a single page.
+pub const MAX_RELATIONSHIP_PAGE_SIZE: usize = 1_000;
+
+/// One relationship incident to the requested node.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct NodeRelationship {
+    /// Relationship id.
+    pub id: u64,
+    /// Relationship type name.
+    #[serde(rename = "type")]
+    pub rel_type: String,
+    /// Source node id.
+    pub start_node: u64,
+    /// Destination node id.
+    pub end_node: u64,
+    /// Stored properties (an empty object when there are none).
+    pub properties: serde_json::Value,
+}
+
+/// One page of [`Engine::node_relationships`].
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct RelationshipPage {
+    /// Matching relationships in chain order.
+    pub relationships: Vec<NodeRelationship>,
+    /// Cursor for the next page; `None` once the chain is exhausted.
+    pub next_cursor: Option<u64>,
+}
+
+impl Engine {
+    /// Up to `limit` relationships of `node_id` in `direction`, optionally
+    /// restricted to `types` (an empty slice matches every type), starting
+    /// at `cursor` (a previous page's `next_cursor`) or at the head of the
+    /// chain.
+    ///
+    /// Fails with [`Error::NotFound`] when the node does not exist and
+    /// with [`Error::InvalidInput`] for a zero or oversized `limit` or a
+    /// cursor that is not on this node's chain.
+    pub fn node_relationships(
+        &mut self,
+        node_id: u64,
+        direction: Direction,
+        types: &[String],
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> Result<RelationshipPage> {
+        if limit == 0 || limit > MAX_RELATIONSHIP_PAGE_SIZE {
+            return Err(Error::invalid_input(format!(
+                "limit must be between 1 and {MAX_RELATIONSHIP_PAGE_SIZE}, got {limit}"
+            )));
+        }
+        let node = self
+            .get_node(node_id)?
+            .filter(|record| !record.is_deleted())
+            .ok_or_else(|| Error::NotFound(format!("Node {node_id} not found")))?;
+
+        // Unknown type names can never match; if none of the requested
+        // types exist the page is empty without touching the chain.
+        let mut type_ids = Vec::with_capacity(types.len());
+        for name in types {
+            if let Some(id) = self.catalog.get_type_id(name)? {
+                type_ids.push(id);
+            }
+        }
+        if !types.is_empty() && type_ids.is_empty() {
+            return Ok(RelationshipPage::default());
+        }
+
+        let rel_count = self.storage.relationship_count();
+        // Chain pointers are `rel_id + 1`, 0 terminates.
+        let mut rel_ptr = match cursor {
+            Some(rel_id) => {
+                let on_chain = rel_id < rel_count && {
+                    let rel = self.storage.read_rel(rel_id)?;
+                    rel.src_id == node_id || rel.dst_id == node_id
+                };
+                if !on_chain {
+                    return Err(Error::invalid_input(format!(
+                        "cursor {rel_id} is not a relationship of node {node_id}"
+                    )));
+                }
+                rel_id + 1
+            }
+            None => node.first_rel_ptr,
+        };
+
+        let mut page = RelationshipPage::default();
+        // A chain can never be longer than the relationship store; the
+        // hop bound keeps a corrupted (cyclic) chain from spinning.
+        let mut hops = 0u64;
+        while rel_ptr != 0 && hops < rel_count {
+            hops += 1;
+            let rel_id = rel_ptr - 1;
+            let rel = self.storage.read_rel(rel_id)?;
+            let outgoing = rel.src_id == node_id;
+            let incoming = rel.dst_id == node_id;
+            rel_ptr = if outgoing {
+                rel.next_src_ptr
+            } else if incoming {
+                rel.next_dst_ptr
+            } else {
+                return Err(Error::storage(format!(
+                    "relationship chain of node {node_id} threads rel {rel_id}, \
+                     which is not incident to it"
+                )));
+            };
+
+            let wanted = match direction {
+                Direction::Outgoing => outgoing,
+                Direction::Incoming => incoming,
+                Direction::Both => true,
+            };
+            if rel.is_deleted()
+                || !wanted
+                || (!type_ids.is_empty() && !type_ids.contains(&rel.type_id))
+            {
+                continue;
+            }
+            if page.relationships.len() == limit {
+                page.next_cursor = Some(rel_id);
+                break;
+            }
+
+            let rel_type = self.catalog.get_type_name(rel.type_id)?.unwrap_or_default();
+            let properties = self
+                .storage
+                .load_relationship_properties(rel_id)?
+                .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+            page.relationships.push(NodeRelationship {
+                id: rel_id,
+                rel_type,
+                start_node: rel.src_id,
+                end_node: rel.dst_id,
+                properties,
+            });
+        }
+        Ok(page)
+    }
+}
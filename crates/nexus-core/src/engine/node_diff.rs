@@ -0,0 +1,166 @@
+//! Property-level comparison of two stored nodes.
+//!
+//! [`Engine::diff_nodes`] reports label and property differences in the
+//! shape the graph comparison module already uses for node
+//! modifications ([`NodeChanges`] / [`PropertyValueChange`]), read as
+//! the edits that turn node `a` into node `b`. Dedup and merge tooling
+//! uses it to decide which properties conflict before collapsing two
+//! nodes into one.
+//!
+//! Stored properties are JSON; they are converted to the comparison
+//! module's [`PropertyValue`] for the report, lists and maps included,
+//! so a list never reads the same as a string holding its JSON text.
+//! Equality is decided structurally on the stored JSON, so `1` and
+//! `1.0` differ.
+
+use super::Engine;
+use crate::graph::comparison::{NodeChanges, PropertyValueChange};
+use crate::graph::simple::PropertyValue;
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+/// Outcome of [`Engine::diff_nodes`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NodeDiff {
+    /// First node compared.
+    pub node_a: u64,
+    /// Second node compared.
+    pub node_b: u64,
+    /// Labels carried by both nodes, sorted.
+    pub common_labels: Vec<String>,
+    /// Properties present on both nodes with equal values, sorted.
+    pub common_properties: Vec<String>,
+    /// Differences from `node_a` to `node_b`: `added_*` exist only on
+    /// `node_b`, `removed_*` only on `node_a`, and `modified_properties`
+    /// hold `a`'s value as `original` and `b`'s as `new`.
+    pub changes: NodeChanges,
+}
+
+impl NodeDiff {
+    /// Whether the nodes carry the same labels and properties.
+    pub fn is_identical(&self) -> bool {
+        self.changes.added_labels.is_empty()
+            && self.changes.removed_labels.is_empty()
+            && self.changes.added_properties.is_empty()
+            && self.changes.removed_properties.is_empty()
+            && self.changes.modified_properties.is_empty()
+    }
+}
+
+impl Engine {
+    /// Compare the labels and properties of nodes `a` and `b`.
+    ///
+    /// Fails with [`Error::NotFound`] when either node does not exist or
+    /// has been deleted.
+    pub fn diff_nodes(&mut self, a: u64, b: u64) -> Result<NodeDiff> {
+        let (labels_a, props_a) = self.node_labels_and_properties(a)?;
+        let (labels_b, props_b) = self.node_labels_and_properties(b)?;
+
+        let mut diff = NodeDiff {
+            node_a: a,
+            node_b: b,
+            common_labels: Vec::new(),
+            common_properties: Vec::new(),
+            changes: NodeChanges {
+                added_labels: Vec::new(),
+                removed_labels: Vec::new(),
+                added_properties: HashMap::new(),
+                removed_properties: HashMap::new(),
+                modified_properties: HashMap::new(),
+            },
+        };
+
+        for label in &labels_a {
+            if labels_b.contains(label) {
+                diff.common_labels.push(label.clone());
+            } else {
+                diff.changes.removed_labels.push(label.clone());
+            }
+        }
+        diff.changes.added_labels = labels_b
+            .into_iter()
+            .filter(|label| !labels_a.contains(label))
+            .collect();
+
+        for (key, value_a) in &props_a {
+            match props_b.get(key) {
+                Some(value_b) if value_b == value_a => diff.common_properties.push(key.clone()),
+                Some(value_b) => {
+                    diff.changes.modified_properties.insert(
+                        key.clone(),
+                        PropertyValueChange {
+                            original: to_property_value(value_a),
+                            new: to_property_value(value_b),
+                        },
+                    );
+                }
+                None => {
+                    diff.changes
+                        .removed_properties
+                        .insert(key.clone(), to_property_value(value_a));
+                }
+            }
+        }
+        for (key, value_b) in &props_b {
+            if !props_a.contains_key(key) {
+                diff.changes
+                    .added_properties
+                    .insert(key.clone(), to_property_value(value_b));
+            }
+        }
+        diff.common_properties.sort();
+
+        Ok(diff)
+    }
+
+    /// Sorted label names and the property map of a live node.
+    pub(super) fn node_labels_and_properties(
+        &mut self,
+        node_id: u64,
+    ) -> Result<(Vec<String>, serde_json::Map<String, serde_json::Value>)> {
+        let record = self
+            .get_node(node_id)?
+            .filter(|record| !record.is_deleted())
+            .ok_or_else(|| Error::NotFound(format!("Node {node_id} not found")))?;
+
+        let mut labels = Vec::new();
+        for label_id in record.get_labels() {
+            if let Some(name) = self.catalog.get_label_name(label_id)? {
+                labels.push(name);
+            }
+        }
+        labels.sort();
+
+        let properties = match self.storage.load_node_properties(node_id)? {
+            Some(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        Ok((labels, properties))
+    }
+}
+
+fn to_property_value(value: &serde_json::Value) -> PropertyValue {
+    match value {
+        serde_json::Value::Null => PropertyValue::Null,
+        serde_json::Value::Bool(b) => PropertyValue::Bool(*b),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                PropertyValue::Int64(i)
+            } else if let Some(f) = n.as_f64() {
+                PropertyValue::Float64(f)
+            } else {
+                PropertyValue::Null
+            }
+        }
+        serde_json::Value::String(s) => PropertyValue::String(s.clone()),
+        serde_json::Value::Array(items) => {
+            PropertyValue::List(items.iter().map(to_property_value).collect())
+        }
+        serde_json::Value::Object(entries) => PropertyValue::Map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), to_property_value(value)))
+                .collect(),
+        ),
+    }
+}
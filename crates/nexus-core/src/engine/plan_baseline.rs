@@ -0,0 +1,230 @@
+//! Plan regression detection between releases.
+//!
+//! With [`super::EngineConfig::plan_history`] enabled, the executor
+//! records the plan and latency of every query template (see
+//! [`crate::performance::plan_history`]). The workflow around an
+//! upgrade or a statistics refresh is:
+//!
+//! 1. [`Engine::capture_plan_baseline`] saves the recorded plans as a
+//!    new version of the [`PLAN_BASELINE_ARTIFACT`] artifact, tagged
+//!    with the running release, and starts a fresh recording.
+//! 2. After the change, [`Engine::compare_plan_baseline`] compares
+//!    every baseline template with its current plan. A template that
+//!    has run since the capture is compared with the plan it ran with;
+//!    one that has not is re-planned from its sample query, without
+//!    executing it, so plan changes show up before any traffic does.
+//!
+//! A template whose plan changed is *regressed* when it has run since
+//! and its mean latency exceeds the baseline's by more than the
+//! threshold. Changed plans that are not slower are reported too, since
+//! they have not necessarily seen representative traffic yet.
+
+use super::Engine;
+use crate::executor::planner;
+use crate::performance::plan_history::{PlanHistory, PlanHistoryConfig, PlanRecord, plan_shape};
+use crate::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Artifact name baselines are saved under; each capture is a version.
+pub const PLAN_BASELINE_ARTIFACT: &str = "plan-baseline";
+
+/// Relative slowdown [`Engine::compare_plan_baseline`] flags by default.
+pub const DEFAULT_PLAN_REGRESSION_THRESHOLD: f64 = 0.25;
+
+/// Payload of a [`PLAN_BASELINE_ARTIFACT`] version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanBaseline {
+    /// Release of `nexus-core` that chose the plans
+    pub nexus_version: String,
+    /// Recorded plans, sorted by template
+    pub plans: Vec<PlanRecord>,
+}
+
+/// Outcome of [`Engine::capture_plan_baseline`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanBaselineSummary {
+    /// Artifact version the baseline was saved as
+    pub version: u64,
+    /// Release of `nexus-core` that chose the plans
+    pub nexus_version: String,
+    /// When the baseline was captured
+    pub captured_at: DateTime<Utc>,
+    /// Templates in the baseline
+    pub templates: usize,
+}
+
+/// One template whose plan differs from the baseline.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanChange {
+    /// Query template
+    pub template: String,
+    /// Query text the current plan was taken from
+    pub sample_query: String,
+    /// Plan shape in the baseline
+    pub baseline_plan: Vec<String>,
+    /// Plan shape now; empty when the sample query no longer plans
+    pub current_plan: Vec<String>,
+    /// Mean latency in the baseline, in milliseconds
+    pub baseline_mean_ms: f64,
+    /// Mean latency since the capture, in milliseconds; `None` when the
+    /// template has not run since
+    pub current_mean_ms: Option<f64>,
+    /// Planning error, when the sample query no longer plans
+    pub error: Option<String>,
+    /// Whether the template is slower than the baseline by more than
+    /// the threshold
+    pub regressed: bool,
+}
+
+/// Outcome of [`Engine::compare_plan_baseline`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanComparison {
+    /// Baseline artifact version compared against
+    pub baseline_version: u64,
+    /// Release that chose the baseline plans
+    pub baseline_nexus_version: String,
+    /// Release running now
+    pub nexus_version: String,
+    /// Relative slowdown that counts as a regression
+    pub threshold: f64,
+    /// Templates compared
+    pub compared: usize,
+    /// Templates whose plan changed
+    pub changed: usize,
+    /// Changed templates that are also slower than the threshold allows
+    pub regressed: usize,
+    /// Every changed template, regressions first
+    pub changes: Vec<PlanChange>,
+}
+
+impl Engine {
+    /// Turn plan recording on or off. Enabling starts an empty history;
+    /// disabling drops the recorded plans (saved baselines are kept).
+    pub fn set_plan_history_config(&mut self, config: &PlanHistoryConfig) -> Result<()> {
+        self.plan_history = config
+            .enabled
+            .then(|| PlanHistory::new(config.max_templates));
+        self.refresh_executor()
+    }
+
+    /// Plans recorded since startup or the last capture, sorted by
+    /// template. Empty when recording is off.
+    pub fn plan_records(&self) -> Vec<PlanRecord> {
+        self.plan_history
+            .as_ref()
+            .map(PlanHistory::records)
+            .unwrap_or_default()
+    }
+
+    /// Save the recorded plans as a new baseline version and start a
+    /// fresh recording, so later comparisons only see plans chosen after
+    /// the capture. Fails when plan recording is off.
+    pub fn capture_plan_baseline(&mut self) -> Result<PlanBaselineSummary> {
+        let Some(history) = self.plan_history.as_ref() else {
+            return Err(Error::CypherExecution(
+                "ERR_PLAN_HISTORY_DISABLED: plan recording is off; enable plan_history first"
+                    .to_string(),
+            ));
+        };
+        let baseline = PlanBaseline {
+            nexus_version: env!("CARGO_PKG_VERSION").to_string(),
+            plans: history.records(),
+        };
+        let templates = baseline.plans.len();
+        let artifact = self.artifacts.save(
+            PLAN_BASELINE_ARTIFACT,
+            "plan_baseline",
+            serde_json::to_value(&baseline)?,
+        )?;
+        history.clear();
+        Ok(PlanBaselineSummary {
+            version: artifact.version,
+            nexus_version: baseline.nexus_version,
+            captured_at: artifact.created_at,
+            templates,
+        })
+    }
+
+    /// Compare baseline `version` (the newest when `None`) with the
+    /// plans chosen now. Templates count as regressed when their mean
+    /// latency exceeds the baseline's by more than `threshold` (0.25 is
+    /// 25% slower). Returns `None` when no such baseline was saved.
+    pub fn compare_plan_baseline(
+        &self,
+        version: Option<u64>,
+        threshold: f64,
+    ) -> Result<Option<PlanComparison>> {
+        if !threshold.is_finite() || threshold < 0.0 {
+            return Err(Error::invalid_input(format!(
+                "plan regression threshold must be a non-negative number, got {threshold}"
+            )));
+        }
+        let Some(artifact) = self.artifacts.get(PLAN_BASELINE_ARTIFACT, version)? else {
+            return Ok(None);
+        };
+        let baseline: PlanBaseline = serde_json::from_value(artifact.payload)?;
+
+        let mut changes = Vec::new();
+        for old in &baseline.plans {
+            let live = self
+                .plan_history
+                .as_ref()
+                .and_then(|history| history.record_for(&old.sample_query));
+            let (current, error) = match live {
+                Some(record) => (record, None),
+                None => {
+                    let (plan, error) = match self.replan(&old.sample_query) {
+                        Ok(plan) => (plan, None),
+                        Err(e) => (Vec::new(), Some(e.to_string())),
+                    };
+                    let replanned = PlanRecord {
+                        plan,
+                        executions: 0,
+                        total_ms: 0.0,
+                        ..old.clone()
+                    };
+                    (replanned, error)
+                }
+            };
+            if current.plan == old.plan {
+                continue;
+            }
+            let current_mean_ms = (current.executions > 0).then(|| current.mean_ms());
+            let regressed = current_mean_ms
+                .is_some_and(|mean| old.executions > 0 && mean > old.mean_ms() * (1.0 + threshold));
+            changes.push(PlanChange {
+                template: old.template.clone(),
+                sample_query: current.sample_query,
+                baseline_plan: old.plan.clone(),
+                current_plan: current.plan,
+                baseline_mean_ms: old.mean_ms(),
+                current_mean_ms,
+                error,
+                regressed,
+            });
+        }
+        changes.sort_by(|a, b| {
+            b.regressed
+                .cmp(&a.regressed)
+                .then_with(|| a.template.cmp(&b.template))
+        });
+
+        Ok(Some(PlanComparison {
+            baseline_version: artifact.version,
+            baseline_nexus_version: baseline.nexus_version,
+            nexus_version: env!("CARGO_PKG_VERSION").to_string(),
+            threshold,
+            compared: baseline.plans.len(),
+            changed: changes.len(),
+            regressed: changes.iter().filter(|c| c.regressed).count(),
+            changes,
+        }))
+    }
+
+    /// Plan `query` the way the executor would, without running it.
+    fn replan(&self, query: &str) -> Result<Vec<String>> {
+        let (cleaned, _hints) = planner::extract_plan_hints(query);
+        Ok(plan_shape(&self.executor.parse_and_plan(&cleaned)?))
+    }
+}
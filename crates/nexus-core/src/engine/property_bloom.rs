@@ -0,0 +1,82 @@
+//! Registering per-(label, property) bloom filters.
+//!
+//! See [`crate::index::property_bloom`] for the filters themselves. A
+//! new filter is backfilled from the nodes already carrying the label,
+//! then kept current by the same write hooks that maintain the typed
+//! property index. Filters are in-memory only; list them in
+//! [`super::EngineConfig::property_bloom_filters`] to rebuild them at
+//! startup.
+
+use super::Engine;
+use crate::index::property_bloom::DEFAULT_BLOOM_EXPECTED_ITEMS;
+use crate::{Error, Result};
+
+/// One bloom filter to build at startup.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct PropertyBloomFilterSpec {
+    /// Label whose nodes are covered.
+    pub label: String,
+    /// Property whose values are recorded.
+    pub property: String,
+    /// Distinct values to size the filter for.
+    #[serde(default = "default_expected_items")]
+    pub expected_items: usize,
+}
+
+fn default_expected_items() -> usize {
+    DEFAULT_BLOOM_EXPECTED_ITEMS
+}
+
+impl Engine {
+    /// Register a bloom filter for `(label, property)` sized for
+    /// `expected_items` distinct values and fill it from existing nodes.
+    /// Returns `false` when the filter already exists.
+    pub fn create_property_bloom_filter(
+        &mut self,
+        label: &str,
+        property: &str,
+        expected_items: usize,
+    ) -> Result<bool> {
+        if expected_items == 0 {
+            return Err(Error::Index(
+                "property bloom filter expected_items must be positive".to_string(),
+            ));
+        }
+        let label_id = self.catalog.get_or_create_label(label)?;
+        let key_id = self.catalog.get_or_create_key(property)?;
+        if !self
+            .indexes
+            .property_bloom
+            .create(label_id, key_id, expected_items)
+        {
+            return Ok(false);
+        }
+
+        let nodes = self
+            .indexes
+            .label_index
+            .get_nodes_with_labels(&[label_id])?;
+        for node_id in nodes.iter() {
+            if let Some(serde_json::Value::Object(props)) =
+                self.storage.load_node_properties(node_id as u64)?
+            {
+                if let Some(value) = props.get(property) {
+                    self.indexes.property_bloom.insert(label_id, key_id, value);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Remove the bloom filter for `(label, property)`. Returns whether
+    /// one existed.
+    pub fn drop_property_bloom_filter(&mut self, label: &str, property: &str) -> Result<bool> {
+        let (Ok(label_id), Ok(key_id)) = (
+            self.catalog.get_label_id(label),
+            self.catalog.get_key_id(property),
+        ) else {
+            return Ok(false);
+        };
+        Ok(self.indexes.property_bloom.drop_filter(label_id, key_id))
+    }
+}
@@ -0,0 +1,196 @@
+//! Change history for selected node properties.
+//!
+//! A history policy names a `(label, property)` pair and how many
+//! previous values to keep. Whenever a write replaces or removes the
+//! value of a tracked property on a node carrying the label, the old
+//! value is appended to that node's history together with the time of
+//! the change and the user whose write made it (see
+//! [`Engine::set_acting_user`]). Entries beyond the policy's limit are
+//! dropped oldest first, so the history is a bounded audit trail for a
+//! few critical fields rather than a versioned store.
+//!
+//! Policies are in-memory only; list them in
+//! [`super::EngineConfig::property_history`] to register them at
+//! startup. The history itself lives in the catalog and survives
+//! restarts. Cypher reads it back with `history(n.property)`.
+
+use super::Engine;
+use crate::catalog::PropertyHistoryEntry;
+use crate::{Error, Result};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Previous values kept per node and property when a policy does not say.
+pub const DEFAULT_PROPERTY_HISTORY_ENTRIES: usize = 10;
+
+/// Upper bound on [`PropertyHistorySpec::max_entries`].
+pub const MAX_PROPERTY_HISTORY_ENTRIES: usize = 1_000;
+
+/// One property history policy to register at startup.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PropertyHistorySpec {
+    /// Label whose nodes are tracked.
+    pub label: String,
+    /// Property whose previous values are kept.
+    pub property: String,
+    /// Previous values kept per node.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_max_entries() -> usize {
+    DEFAULT_PROPERTY_HISTORY_ENTRIES
+}
+
+impl Engine {
+    /// Keep the last `max_entries` previous values of `property` on
+    /// nodes labelled `label`, replacing any earlier limit for the pair.
+    /// History starts with the next write; existing values are not
+    /// backfilled.
+    ///
+    /// Fails with [`Error::InvalidInput`] for an empty label or property
+    /// or a limit outside `1..=MAX_PROPERTY_HISTORY_ENTRIES`.
+    pub fn track_property_history(
+        &mut self,
+        label: &str,
+        property: &str,
+        max_entries: usize,
+    ) -> Result<()> {
+        if label.is_empty() || property.is_empty() {
+            return Err(Error::invalid_input(
+                "property history label and property must not be empty",
+            ));
+        }
+        if max_entries == 0 || max_entries > MAX_PROPERTY_HISTORY_ENTRIES {
+            return Err(Error::invalid_input(format!(
+                "max_entries must be between 1 and {MAX_PROPERTY_HISTORY_ENTRIES}, got {max_entries}"
+            )));
+        }
+        let label_id = self.catalog.get_or_create_label(label)?;
+        let key_id = self.catalog.get_or_create_key(property)?;
+        self.property_history
+            .insert((label_id, key_id), max_entries);
+        Ok(())
+    }
+
+    /// Stop recording history for `(label, property)`. History already
+    /// recorded is kept. Returns whether a policy existed.
+    pub fn untrack_property_history(&mut self, label: &str, property: &str) -> bool {
+        let (Ok(label_id), Ok(key_id)) = (
+            self.catalog.get_label_id(label),
+            self.catalog.get_key_id(property),
+        ) else {
+            return false;
+        };
+        self.property_history.remove(&(label_id, key_id)).is_some()
+    }
+
+    /// Every registered history policy, by label and property.
+    pub fn property_history_policies(&self) -> Result<Vec<PropertyHistorySpec>> {
+        let mut policies = Vec::new();
+        for (&(label_id, key_id), &max_entries) in &self.property_history {
+            let (Some(label), Some(property)) = (
+                self.catalog.get_label_name(label_id)?,
+                self.catalog.get_key_name(key_id)?,
+            ) else {
+                continue;
+            };
+            policies.push(PropertyHistorySpec {
+                label,
+                property,
+                max_entries,
+            });
+        }
+        policies.sort_by(|a, b| (&a.label, &a.property).cmp(&(&b.label, &b.property)));
+        Ok(policies)
+    }
+
+    /// Attribute the writes that follow to `user` in property history.
+    /// Servers set this around each request and clear it with `None`
+    /// afterwards; writes made while it is `None` are recorded without a
+    /// user.
+    pub fn set_acting_user(&mut self, user: Option<String>) {
+        self.acting_user = user;
+    }
+
+    /// Labels and properties of `node_id` before a write, for
+    /// [`Self::record_property_history`]. `None` when no policy is
+    /// registered, so untracked writes skip the extra read.
+    pub(in crate::engine) fn property_history_snapshot(
+        &self,
+        node_id: u64,
+    ) -> Result<Option<(Vec<u32>, Map<String, Value>)>> {
+        if self.property_history.is_empty() {
+            return Ok(None);
+        }
+        let label_ids = self
+            .effective_label_ids_from_record(node_id)
+            .unwrap_or_default();
+        Ok(Some((label_ids, self.load_node_properties_map(node_id)?)))
+    }
+
+    /// Append the tracked values a write on `node_id` replaced or
+    /// removed to their history. `old_label_ids` and `old_properties`
+    /// describe the node before the write, `new_properties` after it.
+    ///
+    /// Best-effort like the index refresh hooks: the write has already
+    /// landed, so a failure is logged rather than returned.
+    pub(in crate::engine) fn record_property_history(
+        &self,
+        node_id: u64,
+        old_label_ids: &[u32],
+        old_properties: &Map<String, Value>,
+        new_properties: &Map<String, Value>,
+    ) {
+        // A key tracked under several of the node's labels is recorded
+        // once, with the largest limit.
+        let mut limits: HashMap<u32, usize> = HashMap::new();
+        for (&(label_id, key_id), &max_entries) in &self.property_history {
+            if old_label_ids.contains(&label_id) {
+                let limit = limits.entry(key_id).or_default();
+                *limit = (*limit).max(max_entries);
+            }
+        }
+        if limits.is_empty() {
+            return;
+        }
+
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        for (key_id, max_entries) in limits {
+            let Ok(Some(key)) = self.catalog.get_key_name(key_id) else {
+                continue;
+            };
+            let Some(previous) = old_properties.get(&key).filter(|v| !v.is_null()) else {
+                continue;
+            };
+            if new_properties.get(&key) == Some(previous) {
+                continue;
+            }
+            let entry = PropertyHistoryEntry {
+                value_json: previous.to_string(),
+                timestamp,
+                user: self.acting_user.clone(),
+            };
+            if let Err(e) =
+                self.catalog
+                    .append_property_history(node_id, key_id, entry, max_entries)
+            {
+                tracing::warn!("property history append failed for node {node_id} ({key}): {e}");
+            }
+        }
+    }
+
+    /// Forget the recorded history of a deleted node so a later node
+    /// reusing its id starts clean.
+    pub(in crate::engine) fn forget_property_history(&self, node_id: u64) {
+        if self.property_history.is_empty() {
+            return;
+        }
+        let mut key_ids: Vec<u32> = self.property_history.keys().map(|&(_, k)| k).collect();
+        key_ids.sort_unstable();
+        key_ids.dedup();
+        if let Err(e) = self.catalog.remove_property_history(node_id, &key_ids) {
+            tracing::warn!("property history cleanup failed for node {node_id}: {e}");
+        }
+    }
+}
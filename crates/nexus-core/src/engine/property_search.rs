@@ -0,0 +1,182 @@
+//! Reverse lookup: which nodes carry a given property value, whatever
+//! their label.
+//!
+//! [`Engine::find_nodes_by_property`] answers "where is this entity"
+//! without the caller knowing the label. Every property index on the
+//! key is probed first; nodes with none of those labels can only be
+//! found by reading their properties, so a scan over the node store
+//! covers them, capped at a caller-supplied number of records. The
+//! result says which labels were answered from an index and whether
+//! the scan stopped early, so a partial answer is never mistaken for a
+//! complete one.
+
+use super::Engine;
+use crate::{Error, Result};
+use std::collections::HashSet;
+
+/// Result count used when the caller does not ask for one.
+pub const DEFAULT_PROPERTY_SEARCH_LIMIT: usize = 100;
+/// Upper bound on the result count.
+pub const MAX_PROPERTY_SEARCH_LIMIT: usize = 1_000;
+/// Node records the fallback scan reads when the caller does not say.
+pub const DEFAULT_PROPERTY_SEARCH_SCAN_LIMIT: u64 = 100_000;
+
+/// A node whose property matched.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PropertyMatch {
+    /// Node id.
+    pub id: u64,
+    /// The node's labels, sorted.
+    pub labels: Vec<String>,
+    /// All stored properties of the node.
+    pub properties: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Outcome of [`Engine::find_nodes_by_property`].
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct PropertySearchResult {
+    /// Matching nodes, index hits first, then scan hits in id order.
+    pub matches: Vec<PropertyMatch>,
+    /// Labels whose property index on the key was consulted, sorted.
+    pub indexed_labels: Vec<String>,
+    /// Node records read by the fallback scan.
+    pub nodes_scanned: u64,
+    /// `true` when the result may be incomplete: `limit` matches were
+    /// reached or the scan hit its record cap before the end of the
+    /// store.
+    pub truncated: bool,
+}
+
+impl Engine {
+    /// Up to `limit` live nodes whose property `key` equals `value`.
+    ///
+    /// Indexed `(label, key)` pairs are answered from the property index;
+    /// nodes carrying none of those labels are found by scanning at most
+    /// `scan_limit` node records. Lists and maps can be searched for but
+    /// are never indexed, so they always go through the scan. Equality is
+    /// on the stored JSON (`1` and `1.0` differ).
+    ///
+    /// Fails with [`Error::InvalidInput`] for an empty key or a zero or
+    /// oversized `limit`.
+    pub fn find_nodes_by_property(
+        &mut self,
+        key: &str,
+        value: &serde_json::Value,
+        limit: usize,
+        scan_limit: u64,
+    ) -> Result<PropertySearchResult> {
+        if key.is_empty() {
+            return Err(Error::invalid_input("property key must not be empty"));
+        }
+        if limit == 0 || limit > MAX_PROPERTY_SEARCH_LIMIT {
+            return Err(Error::invalid_input(format!(
+                "limit must be between 1 and {MAX_PROPERTY_SEARCH_LIMIT}, got {limit}"
+            )));
+        }
+
+        // Stored strings went through the configured normalization, so
+        // the needle has to as well.
+        let mut value = value.clone();
+        self.string_normalization.apply_to_value(&mut value);
+        let value = &value;
+
+        let mut result = PropertySearchResult::default();
+        let mut seen = HashSet::new();
+
+        // Indexes hold scalars only (lists and maps index as NULL), so a
+        // structured value has to be found by the scan.
+        let indexable = matches!(
+            value,
+            serde_json::Value::String(_)
+                | serde_json::Value::Number(_)
+                | serde_json::Value::Bool(_)
+        );
+        let indexed_label_ids = match self.catalog.get_key_id(key) {
+            Ok(key_id) if indexable => {
+                let label_ids = self.indexes.property_index.labels_indexing_key(key_id);
+                let needle = super::json_to_property_value(value);
+                'labels: for &label_id in &label_ids {
+                    let hits =
+                        self.indexes
+                            .property_index
+                            .find_exact(label_id, key_id, needle.clone())?;
+                    for node_id in hits.iter().map(u64::from) {
+                        if !seen.insert(node_id) {
+                            continue;
+                        }
+                        if result.matches.len() == limit {
+                            result.truncated = true;
+                            break 'labels;
+                        }
+                        if let Some(hit) = self.property_match(node_id, key, value)? {
+                            result.matches.push(hit);
+                        }
+                    }
+                }
+                label_ids
+            }
+            _ => Vec::new(),
+        };
+        for &label_id in &indexed_label_ids {
+            if let Some(name) = self.catalog.get_label_name(label_id)? {
+                result.indexed_labels.push(name);
+            }
+        }
+        result.indexed_labels.sort();
+        if result.truncated {
+            return Ok(result);
+        }
+
+        // Fallback scan over nodes no consulted index covers (index hits
+        // all carry an indexed label, so they are skipped here too).
+        let node_count = self.storage.node_count();
+        for node_id in 0..node_count {
+            if result.nodes_scanned == scan_limit {
+                result.truncated = true;
+                break;
+            }
+            result.nodes_scanned += 1;
+            let record = self.storage.read_node(node_id)?;
+            if record.is_deleted()
+                || record
+                    .get_labels()
+                    .iter()
+                    .any(|label_id| indexed_label_ids.contains(label_id))
+            {
+                continue;
+            }
+            if let Some(hit) = self.property_match(node_id, key, value)? {
+                if result.matches.len() == limit {
+                    result.truncated = true;
+                    break;
+                }
+                result.matches.push(hit);
+            }
+        }
+        Ok(result)
+    }
+
+    /// `node_id` as a [`PropertyMatch`] when it is live and its `key`
+    /// property equals `value`. Re-checking index hits keeps a stale
+    /// index entry from surfacing a node that no longer matches.
+    fn property_match(
+        &mut self,
+        node_id: u64,
+        key: &str,
+        value: &serde_json::Value,
+    ) -> Result<Option<PropertyMatch>> {
+        let (labels, properties) = match self.node_labels_and_properties(node_id) {
+            Ok(found) => found,
+            Err(Error::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if properties.get(key) != Some(value) {
+            return Ok(None);
+        }
+        Ok(Some(PropertyMatch {
+            id: node_id,
+            labels,
+            properties,
+        }))
+    }
+}
@@ -163,6 +163,22 @@ impl Engine {
         ctx: Option<&crate::cluster::UserContext>,
         mode: crate::cluster::TenantIsolationMode,
     ) -> Result<executor::ResultSet> {
+        // Unicode normalization (`EngineConfig::string_normalization`).
+        // String literals live in the query text and the executor
+        // re-parses that text downstream, so a query that is not
+        // already in the configured form is normalized and re-parsed
+        // here rather than by patching literals in `ast`.
+        let form = self.string_normalization;
+        if form != crate::text::StringNormalization::Off {
+            for value in self.current_params.values_mut() {
+                form.apply_to_value(value);
+            }
+            if let std::borrow::Cow::Owned(normalized) = form.apply(query) {
+                let ast = executor::parser::CypherParser::new(normalized.clone()).parse()?;
+                return self.execute_cypher_ast_with_context(&ast, &normalized, ctx, mode);
+            }
+        }
+
         let mut ast = ast.clone();
 
         // phase6_opencypher-advanced-types §6 — honour a leading
@@ -190,6 +206,17 @@ impl Engine {
             }
         }
 
+        // Saved views used as labels in a write query. The executor's
+        // planner expands them for whatever it plans, but the write
+        // paths below also walk `ast` themselves. Runs before the
+        // cluster scope rewrite, whose one-shot override the view
+        // queries would otherwise consume.
+        if crate::cluster::scope::is_write_query(&ast)
+            && let std::borrow::Cow::Owned(expanded) = self.executor.expand_views(&ast)?
+        {
+            ast = expanded;
+        }
+
         // Cluster-mode scope rewrite. When a UserContext is present
         // AND the isolation mode asks for catalog-level prefixing,
         // rewrite every label / relationship-type in place, then
@@ -256,6 +283,7 @@ impl Engine {
         // fits, always reject one that definitely does not.
         let is_write = crate::cluster::scope::is_write_query(&ast);
         if is_write {
+            self.ensure_writable()?;
             if let (Some(user_ctx), Some(provider)) = (ctx, self.quota_provider.as_ref()) {
                 let decision = provider.check_storage(user_ctx.namespace(), 0);
                 if let crate::cluster::QuotaDecision::Deny { reason, .. } = decision {
@@ -269,7 +297,89 @@ impl Engine {
         // success path feeds through a single bookkeeping point —
         // there are ~8 `return Ok(...)` sites inside the dispatcher
         // and instrumenting each individually is brittle.
-        let dispatch_result = self.execute_cypher_dispatch(&ast, query);
+        //
+        // Every executor call the dispatch makes runs inside this
+        // scope, so `db.query.timeout_ms` covers the whole query and
+        // `db.query.max_rows` counts only the rows it returns. Writes
+        // are not row-checked: failing them after they applied would
+        // misreport the outcome.
+        //
+        // A row limit armed by the caller (`executor::limit_rows`) is
+        // left for the executor to plan into a read. A write takes it
+        // first, so the reads the write runs internally aren't cut,
+        // and only the rows it returns are.
+        //
+        // Commits the dispatch makes belong to the "default" session
+        // every statement here runs in, so the commit hooks hold them
+        // back while it has an explicit transaction open.
+        //
+        // With commit hooks registered, a write outside an explicit
+        // transaction runs in an implicit one: its mutations reach the
+        // hooks once, when it ends, and a refusing before-commit hook
+        // rolls it back like an explicit ROLLBACK instead of finding it
+        // already applied.
+        let implicit_tx = is_write
+            && !self.in_explicit_transaction()
+            && !self.transaction_manager.read().commit_hooks().is_empty();
+        if implicit_tx {
+            self.begin_session_transaction("default")?;
+        }
+        let generation_before = self.executor_generation;
+        let session = self
+            .transaction_manager
+            .read()
+            .commit_hooks()
+            .enter_session("default");
+        let limits =
+            executor::query_limits::QueryScope::enter(self.executor_config.query_timeout_ms);
+        let row_limit = if is_write {
+            executor::query_limits::take_row_limit()
+        } else {
+            executor::query_limits::row_limit()
+        };
+        let mut dispatch_result = self.execute_cypher_dispatch(&ast, query);
+        if let (Some(limit), Ok(result)) = (row_limit, &mut dispatch_result) {
+            result.rows.truncate(limit);
+        }
+        if !is_write
+            && limits.is_outermost()
+            && let Ok(result) = &dispatch_result
+            && let Err(e) = executor::query_limits::check_result_rows(
+                result.rows.len(),
+                self.executor_config.max_result_rows,
+            )
+        {
+            dispatch_result = Err(e);
+        }
+        drop(limits);
+        drop(session);
+        if implicit_tx {
+            dispatch_result = match dispatch_result {
+                Ok(result) => self.commit_session_transaction("default").map(|()| result),
+                Err(e) => {
+                    self.abort_session_transaction("default")?;
+                    Err(e)
+                }
+            };
+        }
+
+        // Read-your-writes: a few dispatch branches (top-level
+        // `MATCH ... CREATE`) sync storage back from the executor but
+        // defer `refresh_executor` to the caller. Refresh here so every
+        // acknowledged autocommit write is visible to the next reader
+        // that clones `self.executor`, and so `consistency_token` is
+        // bumped past it. Writes staged inside an explicit transaction
+        // become visible on COMMIT instead.
+        if is_write
+            && dispatch_result.is_ok()
+            && self.executor_generation == generation_before
+            && !self.in_explicit_transaction()
+        {
+            self.refresh_executor()?;
+        }
+        if is_write && dispatch_result.is_ok() {
+            self.note_store_write();
+        }
 
         // Post-write usage charge (Phase 4 §13 / §14.1). Runs once,
         // after a successful write, once the RAII override guard
@@ -375,7 +485,11 @@ impl Engine {
                     .query_string
                     .clone()
                     .unwrap_or_else(|| self.query_to_string(&explain_clause.query));
-                return self.execute_explain_with_string(&explain_clause.query, &query_str);
+                return self.execute_explain_with_string(
+                    &explain_clause.query,
+                    &query_str,
+                    explain_clause.format,
+                );
             }
 
             // Check for PROFILE command
@@ -385,7 +499,11 @@ impl Engine {
                     .query_string
                     .clone()
                     .unwrap_or_else(|| self.query_to_string(&profile_clause.query));
-                return self.execute_profile_with_string(&profile_clause.query, &query_str);
+                return self.execute_profile_with_string(
+                    &profile_clause.query,
+                    &query_str,
+                    profile_clause.format,
+                );
             }
         }
 
@@ -488,6 +606,43 @@ impl Engine {
             return self.execute_function_commands(ast);
         }
 
+        // View commands; the HTTP API passes the caller as owner through
+        // `execute_view_commands` directly.
+        let has_view_cmd = ast.clauses.iter().any(|c| {
+            matches!(
+                c,
+                executor::parser::Clause::CreateView(_)
+                    | executor::parser::Clause::DropView(_)
+                    | executor::parser::Clause::RefreshView(_)
+                    | executor::parser::Clause::ShowViews
+            )
+        });
+
+        if has_view_cmd {
+            return self.execute_view_commands(ast, None);
+        }
+
+        if let [executor::parser::Clause::ShowSettings] = ast.clauses.as_slice() {
+            return self.execute_show_settings();
+        }
+
+        // Relationship data-repair procedures (`db.relationships.invert` /
+        // `.retype`) and node merging (`nexus.merge.nodes`) rewrite
+        // storage, so they run on the engine instead of the executor's
+        // read-only snapshot. `dbms.setConfigValue` changes engine
+        // settings (see `engine/settings.rs`).
+        if let [executor::parser::Clause::CallProcedure(call)] = ast.clauses.as_slice() {
+            if call.procedure_name.starts_with("db.relationships.") {
+                return self.execute_relationship_refactor(call);
+            }
+            if call.procedure_name == "nexus.merge.nodes" {
+                return self.execute_merge_nodes(call);
+            }
+            if call.procedure_name == "dbms.setConfigValue" {
+                return self.execute_set_config_value(call);
+            }
+        }
+
         // Check for user management commands (should be handled at server level)
         let has_user_cmd = ast.clauses.iter().any(|c| {
             matches!(
@@ -831,56 +986,82 @@ impl Engine {
         &mut self,
         query: &executor::parser::CypherQuery,
         query_str: &str,
+        format: executor::parser::PlanFormat,
     ) -> Result<executor::ResultSet> {
-        // Use the query AST directly if it has clauses, otherwise parse the string
-        let operators = if !query.clauses.is_empty() {
-            // Use the planner directly with the AST
-            let mut planner = executor::planner::QueryPlanner::new(
-                &self.catalog,
-                &self.indexes.label_index,
-                &self.indexes.knn_index,
-            )
-            .with_rtree(self.indexes.rtree.clone());
-            planner.plan_query(query)?
-        } else {
-            // Fallback: parse and plan from string
-            self.executor.parse_and_plan(query_str)?
+        let operators = self.plan_for_explain(query, query_str)?;
+        let plan = render_plan(&operators, &[], format, None);
+        let plan_value = match format {
+            executor::parser::PlanFormat::Json => serde_json::json!({
+                "plan": plan,
+                "estimated_cost": "N/A", // Would need cost estimation
+                "estimated_rows": "N/A"  // Would need row estimation
+            }),
+            _ => plan,
         };
 
-        // Format plan as JSON for return
-        let plan_json = serde_json::json!({
-            "plan": {
-                "operators": operators.iter().map(|op| {
-                    serde_json::json!({
-                        "type": format!("{:?}", op),
-                        "description": format!("{:?}", op)
-                    })
-                }).collect::<Vec<_>>()
-            },
-            "estimated_cost": "N/A", // Would need cost estimation
-            "estimated_rows": "N/A"  // Would need row estimation
-        });
-
         Ok(executor::ResultSet::new(
             vec!["plan".to_string()],
             vec![executor::Row {
-                values: vec![plan_json],
+                values: vec![plan_value],
             }],
         ))
     }
 
-    /// Execute PROFILE command - executes query and returns execution statistics
+    /// Execute PROFILE (or EXPLAIN ANALYZE) command - executes query and
+    /// returns the plan with rows and time per operator
     pub(super) fn execute_profile_with_string(
         &mut self,
         query: &executor::parser::CypherQuery,
         query_str: &str,
+        format: executor::parser::PlanFormat,
     ) -> Result<executor::ResultSet> {
+        use executor::plan_output;
         use std::time::Instant;
 
         let start_time = Instant::now();
+        let planned = self.plan_for_explain(query, query_str)?;
+
+        // Execute the query, recording the operators the executor runs
+        plan_output::start_recording();
+        let result = self.execute_cypher_internal(query_str);
+        let recording = plan_output::finish_recording();
+        let result = result?;
 
+        let execution_time = start_time.elapsed();
+        let operators = recording.operators.unwrap_or(planned);
+        let summary = format!(
+            "execution time: {} us, rows returned: {}",
+            execution_time.as_micros(),
+            result.rows.len()
+        );
+        let plan = render_plan(&operators, &recording.stats, format, Some(&summary));
+        let profile_value = match format {
+            executor::parser::PlanFormat::Json => serde_json::json!({
+                "plan": plan,
+                "execution_time_ms": execution_time.as_millis(),
+                "execution_time_us": execution_time.as_micros(),
+                "rows_returned": result.rows.len(),
+                "columns_returned": result.columns.len()
+            }),
+            _ => plan,
+        };
+
+        Ok(executor::ResultSet::new(
+            vec!["profile".to_string()],
+            vec![executor::Row {
+                values: vec![profile_value],
+            }],
+        ))
+    }
+
+    /// Plan the query wrapped by EXPLAIN / PROFILE without running it.
+    fn plan_for_explain(
+        &self,
+        query: &executor::parser::CypherQuery,
+        query_str: &str,
+    ) -> Result<Vec<executor::Operator>> {
         // Use the query AST directly if it has clauses, otherwise parse the string
-        let operators = if !query.clauses.is_empty() {
+        if !query.clauses.is_empty() {
             // Use the planner directly with the AST
             let mut planner = executor::planner::QueryPlanner::new(
                 &self.catalog,
@@ -888,39 +1069,11 @@ impl Engine {
                 &self.indexes.knn_index,
             )
             .with_rtree(self.indexes.rtree.clone());
-            planner.plan_query(query)?
+            planner.plan_query(query)
         } else {
             // Fallback: parse and plan from string
-            self.executor.parse_and_plan(query_str)?
-        };
-
-        // Execute the query
-        let result = self.execute_cypher_internal(query_str)?;
-
-        let execution_time = start_time.elapsed();
-
-        // Format profile as JSON
-        let profile_json = serde_json::json!({
-            "plan": {
-                "operators": operators.iter().map(|op| {
-                    serde_json::json!({
-                        "type": format!("{:?}", op),
-                        "description": format!("{:?}", op)
-                    })
-                }).collect::<Vec<_>>()
-            },
-            "execution_time_ms": execution_time.as_millis(),
-            "execution_time_us": execution_time.as_micros(),
-            "rows_returned": result.rows.len(),
-            "columns_returned": result.columns.len()
-        });
-
-        Ok(executor::ResultSet::new(
-            vec!["profile".to_string()],
-            vec![executor::Row {
-                values: vec![profile_json],
-            }],
-        ))
+            self.executor.parse_and_plan(query_str)
+        }
     }
 
     /// Convert CypherQuery AST to string representation
@@ -956,3 +1109,55 @@ impl Engine {
         self.dispatch(ast, DispatchSource::Internal)
     }
 }
+
+/// Render an EXPLAIN / PROFILE plan. JSON yields `{operators, tree}`,
+/// the operators in execution order; text and DOT yield a string, with
+/// `summary` as a trailing line or the graph title.
+fn render_plan(
+    operators: &[executor::Operator],
+    stats: &[Option<executor::plan_output::OperatorStats>],
+    format: executor::parser::PlanFormat,
+    summary: Option<&str>,
+) -> Value {
+    use executor::parser::PlanFormat;
+    use executor::plan_output;
+
+    let tree = plan_output::plan_tree(operators, stats);
+    match format {
+        PlanFormat::Json => {
+            let operators: Vec<Value> = operators
+                .iter()
+                .enumerate()
+                .map(|(i, op)| {
+                    let (name, details) = plan_output::describe_operator(op);
+                    let mut entry = serde_json::json!({
+                        "type": name,
+                        "description": details,
+                    });
+                    if let Some(Some(s)) = stats.get(i) {
+                        entry["rows"] = s.rows.into();
+                        entry["time_us"] = s.time_us.into();
+                    }
+                    entry
+                })
+                .collect();
+            serde_json::json!({ "operators": operators, "tree": tree })
+        }
+        PlanFormat::Text => {
+            let mut text = tree
+                .as_ref()
+                .map(plan_output::render_text)
+                .unwrap_or_default();
+            if let Some(summary) = summary {
+                text.push_str(summary);
+                text.push('\n');
+            }
+            Value::String(text)
+        }
+        PlanFormat::Dot => Value::String(
+            tree.as_ref()
+                .map(|root| plan_output::render_dot(root, summary))
+                .unwrap_or_default(),
+        ),
+    }
+}
@@ -0,0 +1,436 @@
+//! Relationship data-repair procedures.
+//!
+//! `CALL db.relationships.invert(type [, batchSize])` flips the
+//! direction of every relationship of `type`, and
+//! `CALL db.relationships.retype(fromType, toType [, filter [, batchSize]])`
+//! moves the relationships of `fromType` whose properties match
+//! `filter` to `toType`. Both work in batches: the candidate set is
+//! snapshotted up front, then every `batchSize` relationships run in
+//! their own write transaction so a large repair never holds one huge
+//! transaction open. Candidates come from the relationship-type index,
+//! so the cost follows the number of relationships of the type rather
+//! than the size of the store.
+//!
+//! Relationship records thread source / destination adjacency chains
+//! through their own pointers, so neither the endpoints nor the type
+//! can be rewritten in place. Each relationship is tombstoned and
+//! recreated with the same properties; the rewritten relationship
+//! therefore gets a new id. Relationship constraints on the target type
+//! are enforced on the recreated relationship, so a retype that would
+//! violate them aborts at the offending batch: earlier batches stay
+//! committed, and the failing batch is undone — the relationships it
+//! created are tombstoned again and the ones it retired are restored
+//! with their ids and properties ([`RelationshipRewriteLog`]). Every
+//! change, including the undo, is journalled as it is applied, so the
+//! log matches the store either way.
+
+use super::Engine;
+use crate::storage::RelationshipRecord;
+use crate::{Error, Result, executor, transaction, wal};
+
+/// Default number of relationships rewritten per transaction.
+pub const DEFAULT_REFACTOR_BATCH_SIZE: usize = 10_000;
+
+/// Outcome of a bulk relationship rewrite.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelationshipRefactorReport {
+    /// Relationships that matched the type (and filter) and were rewritten.
+    pub updated: u64,
+    /// Write transactions committed.
+    pub batches: u64,
+}
+
+/// The relationships a rewrite has created and retired so far, so a
+/// failed rewrite can be put back with
+/// [`Engine::undo_relationship_rewrite`].
+#[derive(Debug, Default)]
+pub(super) struct RelationshipRewriteLog {
+    /// `(rel_id, src, dst, type_id)` of each relationship created.
+    created: Vec<(u64, u64, u64, u32)>,
+    /// Each retired relationship with its record and properties as they
+    /// were before it was retired.
+    retired: Vec<(u64, RelationshipRecord, Option<serde_json::Value>)>,
+}
+
+impl Engine {
+    /// Reverse the direction of every relationship of `rel_type`.
+    ///
+    /// Self-loops are counted but left untouched. Returns an empty
+    /// report when the type has never been used.
+    pub fn invert_relationships(
+        &mut self,
+        rel_type: &str,
+        batch_size: usize,
+    ) -> Result<RelationshipRefactorReport> {
+        let Some(type_id) = self.catalog.get_type_id(rel_type)? else {
+            return Ok(RelationshipRefactorReport::default());
+        };
+        let candidates = self.matching_relationships(type_id, &serde_json::Map::new())?;
+        self.rewrite_relationships(&candidates, batch_size, |src, dst| (dst, src), rel_type)
+    }
+
+    /// Change the type of every `from_type` relationship whose properties
+    /// contain all entries of `filter` (an empty filter matches every
+    /// relationship) to `to_type`.
+    pub fn retype_relationships(
+        &mut self,
+        from_type: &str,
+        to_type: &str,
+        filter: &serde_json::Map<String, serde_json::Value>,
+        batch_size: usize,
+    ) -> Result<RelationshipRefactorReport> {
+        if to_type.is_empty() {
+            return Err(Error::CypherExecution(
+                "ERR_INVALID_ARG_VALUE: db.relationships.retype `toType` must not be empty"
+                    .to_string(),
+            ));
+        }
+        let Some(type_id) = self.catalog.get_type_id(from_type)? else {
+            return Ok(RelationshipRefactorReport::default());
+        };
+        if from_type == to_type {
+            return Ok(RelationshipRefactorReport::default());
+        }
+        let candidates = self.matching_relationships(type_id, filter)?;
+        self.rewrite_relationships(&candidates, batch_size, |src, dst| (src, dst), to_type)
+    }
+
+    /// Live relationships of `type_id` whose properties match `filter`,
+    /// in id order. Candidates come from the relationship-type index and
+    /// are checked against storage.
+    fn matching_relationships(
+        &self,
+        type_id: u32,
+        filter: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Vec<u64>> {
+        self.heal_relationship_index_if_dirty();
+        let mut candidates = self
+            .cache
+            .relationship_index()
+            .get_relationships_by_types(&[type_id])?;
+        candidates.sort_unstable();
+        candidates.dedup();
+        let mut out = Vec::new();
+        for rel_id in candidates {
+            let Ok(record) = self.storage.read_rel(rel_id) else {
+                continue;
+            };
+            if record.is_deleted() || record.type_id != type_id {
+                continue;
+            }
+            if !filter.is_empty() {
+                let props = self
+                    .storage
+                    .load_relationship_properties(rel_id)?
+                    .unwrap_or_else(|| serde_json::json!({}));
+                if !filter.iter().all(|(k, v)| props.get(k) == Some(v)) {
+                    continue;
+                }
+            }
+            out.push(rel_id);
+        }
+        Ok(out)
+    }
+
+    /// Tombstone each relationship in `rel_ids` and recreate it with the
+    /// endpoints produced by `endpoints(src, dst)` and type `new_type`,
+    /// committing every `batch_size` relationships.
+    fn rewrite_relationships(
+        &mut self,
+        rel_ids: &[u64],
+        batch_size: usize,
+        endpoints: impl Fn(u64, u64) -> (u64, u64),
+        new_type: &str,
+    ) -> Result<RelationshipRefactorReport> {
+        if batch_size == 0 {
+            return Err(Error::CypherExecution(
+                "ERR_INVALID_ARG_VALUE: `batchSize` must be a positive INTEGER".to_string(),
+            ));
+        }
+        let mut report = RelationshipRefactorReport::default();
+        if rel_ids.is_empty() {
+            return Ok(report);
+        }
+        let new_type_id = self.catalog.get_or_create_type(new_type)?;
+        for batch in rel_ids.chunks(batch_size) {
+            let mut tx = self.transaction_manager.write().begin_write()?;
+            let mut log = RelationshipRewriteLog::default();
+            let outcome = self
+                .rewrite_batch(&mut tx, &mut log, batch, &endpoints, new_type, new_type_id)
+                .and_then(|updated| {
+                    self.transaction_manager.write().commit(&mut tx)?;
+                    Ok(updated)
+                });
+            match outcome {
+                Ok(updated) => {
+                    self.storage.flush()?;
+                    report.updated += updated;
+                    report.batches += 1;
+                }
+                Err(e) => {
+                    // Put back what the batch wrote before the failure,
+                    // then close the transaction instead of leaving it
+                    // open.
+                    if tx.state == transaction::TxState::Active
+                        && let Err(abort_err) = self.transaction_manager.write().abort(&mut tx)
+                    {
+                        tracing::warn!("Failed to abort relationship rewrite: {}", abort_err);
+                    }
+                    self.undo_relationship_rewrite(log)?;
+                    self.storage.flush()?;
+                    self.flush_async_wal()?;
+                    self.refresh_executor()?;
+                    return Err(e);
+                }
+            }
+        }
+        self.flush_async_wal()?;
+        self.refresh_executor()?;
+        Ok(report)
+    }
+
+    /// Rewrite one batch inside `tx`, journalling each change as it is
+    /// applied and noting it in `log`. Returns how many relationships the
+    /// batch covered.
+    fn rewrite_batch(
+        &mut self,
+        tx: &mut transaction::Transaction,
+        log: &mut RelationshipRewriteLog,
+        batch: &[u64],
+        endpoints: &impl Fn(u64, u64) -> (u64, u64),
+        new_type: &str,
+        new_type_id: u32,
+    ) -> Result<u64> {
+        let mut updated = 0;
+        for &rel_id in batch {
+            let record = self.storage.read_rel(rel_id)?;
+            // Copy out of the #[repr(packed)] record.
+            let (src, dst, type_id) = (record.src_id, record.dst_id, record.type_id);
+            let (new_src, new_dst) = endpoints(src, dst);
+            if new_type_id == type_id && (new_src, new_dst) == (src, dst) {
+                updated += 1;
+                continue;
+            }
+            let properties = self
+                .storage
+                .load_relationship_properties(rel_id)?
+                .unwrap_or_else(|| serde_json::json!({}));
+
+            self.recreate_relationship(tx, log, new_src, new_dst, new_type, properties)?;
+            self.replace_relationship(tx, log, rel_id)?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    /// Create the rewritten copy of a relationship inside `tx`, journal
+    /// it and note it in `log`.
+    pub(super) fn recreate_relationship(
+        &mut self,
+        tx: &mut transaction::Transaction,
+        log: &mut RelationshipRewriteLog,
+        src: u64,
+        dst: u64,
+        rel_type: &str,
+        properties: serde_json::Value,
+    ) -> Result<u64> {
+        let rel_id = {
+            let mut session_tx: Option<&mut transaction::Transaction> = Some(&mut *tx);
+            self.create_relationship_with_transaction(
+                src,
+                dst,
+                rel_type.to_string(),
+                properties,
+                &mut session_tx,
+            )?
+        };
+        let type_id = self.storage.read_rel(rel_id)?.type_id;
+        log.created.push((rel_id, src, dst, type_id));
+        self.write_wal_async(wal::WalEntry::CreateRel {
+            rel_id,
+            src,
+            dst,
+            type_id,
+        })?;
+        Ok(rel_id)
+    }
+
+    /// Retire a relationship that a rewritten copy replaces inside `tx`,
+    /// journal it and note its record and properties in `log`.
+    pub(super) fn replace_relationship(
+        &mut self,
+        tx: &mut transaction::Transaction,
+        log: &mut RelationshipRewriteLog,
+        rel_id: u64,
+    ) -> Result<()> {
+        let record = self.storage.read_rel(rel_id)?;
+        let properties = self.storage.load_relationship_properties(rel_id)?;
+        // Copy out of the #[repr(packed)] record.
+        let (src, dst, type_id) = (record.src_id, record.dst_id, record.type_id);
+        self.retire_relationship(rel_id, src, dst, type_id)?;
+        log.retired.push((rel_id, record, properties));
+        tx.record(transaction::Mutation::RelationshipDeleted { rel_id });
+        self.write_wal_async(wal::WalEntry::DeleteRel { rel_id })
+    }
+
+    /// Undo a failed rewrite: tombstone the relationships it created and
+    /// restore the ones it retired under their own ids, journalling both.
+    pub(super) fn undo_relationship_rewrite(&mut self, log: RelationshipRewriteLog) -> Result<()> {
+        for (rel_id, src, dst, type_id) in log.created.into_iter().rev() {
+            self.retire_relationship(rel_id, src, dst, type_id)?;
+            self.write_wal_async(wal::WalEntry::DeleteRel { rel_id })?;
+        }
+        for (rel_id, record, properties) in log.retired.into_iter().rev() {
+            let (src, dst, type_id) = (record.src_id, record.dst_id, record.type_id);
+            let image = wal::WalEntry::RelImage {
+                rel_id,
+                record: bytemuck::bytes_of(&record).to_vec(),
+                properties: match &properties {
+                    Some(props) => serde_json::to_vec(props)?,
+                    None => Vec::new(),
+                },
+            };
+            self.storage
+                .restore_relationship(rel_id, record, properties.clone())?;
+            if let Err(e) = self
+                .cache
+                .relationship_index()
+                .add_relationship(rel_id, src, dst, type_id)
+            {
+                tracing::warn!("Failed to restore relationship index entry: {}", e);
+                self.relationship_index_dirty
+                    .store(true, std::sync::atomic::Ordering::Release);
+            }
+            if let (Some(prop_index), Some(serde_json::Value::Object(props))) =
+                (self.executor.relationship_property_index(), properties)
+                && !props.is_empty()
+            {
+                let props: std::collections::HashMap<_, _> = props.into_iter().collect();
+                let _ = prop_index.write().index_properties(rel_id, type_id, &props);
+            }
+            self.catalog.increment_rel_count(type_id)?;
+            self.write_wal_async(image)?;
+        }
+        Ok(())
+    }
+
+    /// Tombstone a relationship being replaced by a rewritten copy and
+    /// drop it from the relationship indexes and type counts. The caller
+    /// logs the WAL entries.
+    pub(super) fn retire_relationship(
+        &mut self,
+        rel_id: u64,
+        src: u64,
+        dst: u64,
+        type_id: u32,
+    ) -> Result<()> {
+        self.storage.delete_rel(rel_id)?;
+        self.storage.delete_relationship_properties(rel_id)?;
+        if let Err(e) = self
+            .cache
+            .relationship_index()
+            .remove_relationship(rel_id, src, dst, type_id)
+        {
+            tracing::warn!("Failed to update relationship index on rewrite: {}", e);
+        }
+        if let Some(prop_index) = self.executor.relationship_property_index() {
+            let _ = prop_index.write().remove_relationship(rel_id, type_id);
+        }
+        self.catalog.decrement_rel_count(type_id)?;
+        Ok(())
+    }
+
+    /// Engine-side dispatch for `CALL db.relationships.*`. These
+    /// procedures mutate storage and the relationship index, so they
+    /// cannot run on the executor's snapshot like the read-only `db.*`
+    /// procedures.
+    pub(super) fn execute_relationship_refactor(
+        &mut self,
+        call: &executor::parser::CallProcedureClause,
+    ) -> Result<executor::ResultSet> {
+        let name = call.procedure_name.as_str();
+        let mut args = Vec::with_capacity(call.arguments.len());
+        for expr in &call.arguments {
+            args.push(self.evaluate_set_expression(expr, "", &serde_json::Map::new())?);
+        }
+        let report = match name {
+            "db.relationships.invert" => {
+                let rel_type = string_arg(name, "type", args.first())?;
+                let batch_size = batch_size_arg(name, args.get(1))?;
+                self.invert_relationships(&rel_type, batch_size)?
+            }
+            "db.relationships.retype" => {
+                let from_type = string_arg(name, "fromType", args.first())?;
+                let to_type = string_arg(name, "toType", args.get(1))?;
+                let filter = match args.get(2) {
+                    None | Some(serde_json::Value::Null) => serde_json::Map::new(),
+                    Some(serde_json::Value::Object(m)) => m.clone(),
+                    Some(other) => {
+                        return Err(Error::CypherExecution(format!(
+                            "ERR_INVALID_ARG_TYPE: {name} `filter` must be MAP (got {other})"
+                        )));
+                    }
+                };
+                let batch_size = batch_size_arg(name, args.get(3))?;
+                self.retype_relationships(&from_type, &to_type, &filter, batch_size)?
+            }
+            _ => {
+                return Err(Error::CypherExecution(format!(
+                    "Procedure '{name}' not found"
+                )));
+            }
+        };
+
+        let all_columns = ["updated", "batches"];
+        let columns: Vec<String> = match &call.yield_columns {
+            Some(cols) => cols.clone(),
+            None => all_columns.iter().map(|c| c.to_string()).collect(),
+        };
+        let mut values = Vec::with_capacity(columns.len());
+        for col in &columns {
+            values.push(match col.as_str() {
+                "updated" => serde_json::Value::from(report.updated),
+                "batches" => serde_json::Value::from(report.batches),
+                other => {
+                    return Err(Error::CypherExecution(format!(
+                        "Unknown YIELD column '{other}' for {name}"
+                    )));
+                }
+            });
+        }
+        Ok(executor::ResultSet::new(
+            columns,
+            vec![executor::Row { values }],
+        ))
+    }
+}
+
+fn string_arg(procedure: &str, param: &str, value: Option<&serde_json::Value>) -> Result<String> {
+    match value {
+        Some(serde_json::Value::String(s)) => Ok(s.clone()),
+        Some(other) => Err(Error::CypherExecution(format!(
+            "ERR_INVALID_ARG_TYPE: {procedure} `{param}` must be STRING (got {other})"
+        ))),
+        None => Err(Error::CypherExecution(format!(
+            "ERR_MISSING_ARG: {procedure} requires `{param}`"
+        ))),
+    }
+}
+
+fn batch_size_arg(procedure: &str, value: Option<&serde_json::Value>) -> Result<usize> {
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(DEFAULT_REFACTOR_BATCH_SIZE),
+        Some(serde_json::Value::Number(n)) => n
+            .as_u64()
+            .filter(|n| *n > 0)
+            .map(|n| n as usize)
+            .ok_or_else(|| {
+                Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_VALUE: {procedure} `batchSize` must be a positive INTEGER"
+                ))
+            }),
+        Some(other) => Err(Error::CypherExecution(format!(
+            "ERR_INVALID_ARG_TYPE: {procedure} `batchSize` must be INTEGER (got {other})"
+        ))),
+    }
+}
@@ -0,0 +1,357 @@
+//! Row-level comparison of two query results.
+//!
+//! [`diff_results`] compares the [`ResultSet`]s one query produced on two
+//! databases — before and after a migration, or on two engine versions —
+//! and reports the rows found on only one side. It backs
+//! `POST /cypher/diff`.
+//!
+//! Columns are matched by name, so a reordered `RETURN` still compares
+//! equal; results whose column names differ are compared by position
+//! and never reported identical. Rows are compared as multisets unless
+//! [`ResultDiffOptions::ordered`] is set, in which case row `i` is
+//! compared with row `i` and mismatches are reported as changed rows.
+//!
+//! Numbers compare by value (`1` equals `1.0`), within the configured
+//! absolute / relative float tolerance. Node and relationship ids
+//! (`_nexus_id`) are ignored by default because they rarely survive a
+//! migration.
+
+use crate::executor::ResultSet;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Internal id key carried by node and relationship values.
+const ID_KEY: &str = "_nexus_id";
+
+/// Tolerances for [`diff_results`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ResultDiffOptions {
+    /// Compare rows position by position instead of as multisets.
+    pub ordered: bool,
+    /// Largest absolute difference at which two numbers still match.
+    pub float_tolerance: f64,
+    /// Largest difference, relative to the larger magnitude, at which two
+    /// numbers still match. Applied alongside `float_tolerance`.
+    pub relative_tolerance: f64,
+    /// Ignore `_nexus_id` inside node and relationship values.
+    pub ignore_ids: bool,
+    /// Most rows listed in the report; counts always cover every row.
+    pub max_differences: usize,
+}
+
+impl Default for ResultDiffOptions {
+    fn default() -> Self {
+        Self {
+            ordered: false,
+            float_tolerance: 0.0,
+            relative_tolerance: 0.0,
+            ignore_ids: true,
+            max_differences: 100,
+        }
+    }
+}
+
+/// A row present on one side only.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct UnmatchedRow {
+    /// Position of the row in its result.
+    pub index: usize,
+    /// Row values, in that result's column order.
+    pub values: Vec<Value>,
+}
+
+/// Rows at the same position that differ (ordered comparison only).
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ChangedRow {
+    /// Position of the row in both results.
+    pub index: usize,
+    /// Left row values.
+    pub left: Vec<Value>,
+    /// Right row values, in the left result's column order when the
+    /// column names match.
+    pub right: Vec<Value>,
+}
+
+/// Outcome of [`diff_results`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ResultDiff {
+    /// Same column names (in any order) and same rows.
+    pub identical: bool,
+    /// Whether both results have the same column names.
+    pub columns_match: bool,
+    /// Columns of the left result.
+    pub left_columns: Vec<String>,
+    /// Columns of the right result.
+    pub right_columns: Vec<String>,
+    /// Rows in the left result.
+    pub left_row_count: usize,
+    /// Rows in the right result.
+    pub right_row_count: usize,
+    /// Rows matched between the two sides.
+    pub matched_rows: usize,
+    /// Rows without a counterpart, counting changed rows once per side.
+    pub only_in_left_count: usize,
+    /// See `only_in_left_count`.
+    pub only_in_right_count: usize,
+    /// Left rows without a counterpart (up to `max_differences`).
+    pub only_in_left: Vec<UnmatchedRow>,
+    /// Right rows without a counterpart (up to `max_differences`).
+    pub only_in_right: Vec<UnmatchedRow>,
+    /// Rows that differ at the same position (up to `max_differences`).
+    pub changed_rows: Vec<ChangedRow>,
+    /// Whether differences were left out of the lists above.
+    pub truncated: bool,
+}
+
+/// Compare `left` and `right` as described in the module docs.
+pub fn diff_results(
+    left: &ResultSet,
+    right: &ResultSet,
+    options: &ResultDiffOptions,
+) -> ResultDiff {
+    // Map each left column to the right column of the same name.
+    let mut sorted_left = left.columns.clone();
+    let mut sorted_right = right.columns.clone();
+    sorted_left.sort();
+    sorted_right.sort();
+    let columns_match = sorted_left == sorted_right;
+    let permutation: Option<Vec<usize>> = columns_match.then(|| {
+        let mut used = vec![false; right.columns.len()];
+        left.columns
+            .iter()
+            .map(|name| {
+                let position = (0..right.columns.len())
+                    .find(|&i| !used[i] && &right.columns[i] == name)
+                    .unwrap_or_default();
+                used[position] = true;
+                position
+            })
+            .collect()
+    });
+    let right_rows: Vec<Vec<Value>> = right
+        .rows
+        .iter()
+        .map(|row| match &permutation {
+            Some(order) => order
+                .iter()
+                .map(|&i| row.values.get(i).cloned().unwrap_or(Value::Null))
+                .collect(),
+            None => row.values.clone(),
+        })
+        .collect();
+    let left_rows: Vec<&Vec<Value>> = left.rows.iter().map(|row| &row.values).collect();
+
+    let mut diff = ResultDiff {
+        identical: false,
+        columns_match,
+        left_columns: left.columns.clone(),
+        right_columns: right.columns.clone(),
+        left_row_count: left_rows.len(),
+        right_row_count: right_rows.len(),
+        matched_rows: 0,
+        only_in_left_count: 0,
+        only_in_right_count: 0,
+        only_in_left: Vec::new(),
+        only_in_right: Vec::new(),
+        changed_rows: Vec::new(),
+        truncated: false,
+    };
+    let mut listed = 0usize;
+    let mut may_list = |diff: &mut ResultDiff| {
+        listed += 1;
+        if listed > options.max_differences {
+            diff.truncated = true;
+            false
+        } else {
+            true
+        }
+    };
+
+    if options.ordered {
+        for index in 0..left_rows.len().max(right_rows.len()) {
+            match (left_rows.get(index), right_rows.get(index)) {
+                (Some(l), Some(r)) if rows_equal(l, r, options) => diff.matched_rows += 1,
+                (Some(l), Some(r)) => {
+                    diff.only_in_left_count += 1;
+                    diff.only_in_right_count += 1;
+                    if may_list(&mut diff) {
+                        diff.changed_rows.push(ChangedRow {
+                            index,
+                            left: (*l).clone(),
+                            right: r.clone(),
+                        });
+                    }
+                }
+                (Some(l), None) => {
+                    diff.only_in_left_count += 1;
+                    if may_list(&mut diff) {
+                        diff.only_in_left.push(UnmatchedRow {
+                            index,
+                            values: (*l).clone(),
+                        });
+                    }
+                }
+                (None, Some(_)) => {
+                    diff.only_in_right_count += 1;
+                    if may_list(&mut diff) {
+                        diff.only_in_right.push(UnmatchedRow {
+                            index,
+                            values: right.rows[index].values.clone(),
+                        });
+                    }
+                }
+                (None, None) => {}
+            }
+        }
+    } else {
+        // Exact matches first, through a canonical key per row.
+        let mut unmatched_right: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, row) in right_rows.iter().enumerate().rev() {
+            unmatched_right
+                .entry(canonical_row(row, options))
+                .or_default()
+                .push(index);
+        }
+        let mut left_leftover = Vec::new();
+        for (index, row) in left_rows.iter().enumerate() {
+            match unmatched_right
+                .get_mut(&canonical_row(row, options))
+                .and_then(Vec::pop)
+            {
+                Some(_) => diff.matched_rows += 1,
+                None => left_leftover.push(index),
+            }
+        }
+        let mut right_leftover: Vec<usize> = unmatched_right.into_values().flatten().collect();
+        right_leftover.sort_unstable();
+
+        // Then pair what is left within tolerance. Quadratic in the
+        // unmatched rows, which is fine for the near-identical results
+        // a tolerance is meant for.
+        if options.float_tolerance > 0.0 || options.relative_tolerance > 0.0 {
+            left_leftover.retain(|&l| {
+                match right_leftover
+                    .iter()
+                    .position(|&r| rows_equal(left_rows[l], &right_rows[r], options))
+                {
+                    Some(position) => {
+                        right_leftover.remove(position);
+                        diff.matched_rows += 1;
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+
+        diff.only_in_left_count = left_leftover.len();
+        diff.only_in_right_count = right_leftover.len();
+        for index in left_leftover {
+            if may_list(&mut diff) {
+                diff.only_in_left.push(UnmatchedRow {
+                    index,
+                    values: left_rows[index].clone(),
+                });
+            }
+        }
+        for index in right_leftover {
+            if may_list(&mut diff) {
+                diff.only_in_right.push(UnmatchedRow {
+                    index,
+                    values: right.rows[index].values.clone(),
+                });
+            }
+        }
+    }
+
+    diff.identical = columns_match && diff.only_in_left_count == 0 && diff.only_in_right_count == 0;
+    diff
+}
+
+fn rows_equal(left: &[Value], right: &[Value], options: &ResultDiffOptions) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .all(|(l, r)| values_equal(l, r, options))
+}
+
+fn values_equal(left: &Value, right: &Value, options: &ResultDiffOptions) -> bool {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => {
+            if l == r {
+                return true;
+            }
+            let (Some(l), Some(r)) = (l.as_f64(), r.as_f64()) else {
+                return false;
+            };
+            let tolerance = options
+                .float_tolerance
+                .max(options.relative_tolerance * l.abs().max(r.abs()));
+            l == r || (l - r).abs() <= tolerance
+        }
+        (Value::Array(l), Value::Array(r)) => rows_equal(l, r, options),
+        (Value::Object(l), Value::Object(r)) => {
+            let keys = |map: &serde_json::Map<String, Value>| {
+                map.keys()
+                    .filter(|key| !(options.ignore_ids && key.as_str() == ID_KEY))
+                    .count()
+            };
+            keys(l) == keys(r)
+                && l.iter().all(|(key, value)| {
+                    (options.ignore_ids && key == ID_KEY)
+                        || r.get(key)
+                            .is_some_and(|other| values_equal(value, other, options))
+                })
+        }
+        _ => left == right,
+    }
+}
+
+/// Key under which rows that are equal without tolerance collide:
+/// object keys sorted, ids dropped when ignored, and integral floats
+/// written as integers.
+fn canonical_row(row: &[Value], options: &ResultDiffOptions) -> String {
+    let mut key = String::new();
+    for value in row {
+        write_canonical(value, options, &mut key);
+        key.push('\u{1f}');
+    }
+    key
+}
+
+fn write_canonical(value: &Value, options: &ResultDiffOptions, out: &mut String) {
+    match value {
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() < 9.0e15 => {
+                out.push_str(&(f as i64).to_string())
+            }
+            _ => out.push_str(&n.to_string()),
+        },
+        Value::Array(items) => {
+            out.push('[');
+            for item in items {
+                write_canonical(item, options, out);
+                out.push(',');
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map
+                .keys()
+                .filter(|key| !(options.ignore_ids && key.as_str() == ID_KEY))
+                .collect();
+            keys.sort();
+            out.push('{');
+            for key in keys {
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], options, out);
+                out.push(',');
+            }
+            out.push('}');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
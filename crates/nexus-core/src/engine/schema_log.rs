@@ -0,0 +1,116 @@
+//! Schema changelog and rollback scripts.
+//!
+//! Every successful `CREATE` / `DROP INDEX`, `CREATE` / `DROP CONSTRAINT`
+//! and every first use of a label is appended to a changelog in the
+//! catalog, together with the Cypher statement that undoes it.
+//! [`Engine::schema_rollback_script`] turns the entries of a time range
+//! into a script that, run newest first, returns the schema to its state
+//! before the range. Changes with no inverse (new labels, composite and
+//! spatial indexes, the constraint kinds without a `DROP` form) appear in
+//! the script as comments so an operator can see what is left behind.
+//!
+//! Backs `GET /schema/changelog`, `GET /schema/changelog/rollback` and
+//! `nexus admin schema-log`.
+
+use super::Engine;
+use crate::Result;
+use crate::catalog::{SchemaChange, SchemaChangeKind};
+
+impl Engine {
+    /// Changelog entries applied between `since` and `until` (epoch
+    /// milliseconds, both inclusive and optional), oldest first.
+    pub fn schema_log(&self, since: Option<i64>, until: Option<i64>) -> Result<Vec<SchemaChange>> {
+        self.catalog.schema_changes(since, until)
+    }
+
+    /// Cypher script undoing every change applied between `since` and
+    /// `until`, newest first. One statement per line, each terminated by
+    /// `;`.
+    pub fn schema_rollback_script(&self, since: Option<i64>, until: Option<i64>) -> Result<String> {
+        Ok(rollback_script(&self.schema_log(since, until)?))
+    }
+
+    /// Append a DDL change to the schema changelog.
+    pub(super) fn log_schema_change(
+        &self,
+        kind: SchemaChangeKind,
+        statement: String,
+        inverse: Option<String>,
+    ) -> Result<()> {
+        self.catalog.log_schema_change(kind, statement, inverse)?;
+        Ok(())
+    }
+}
+
+/// Render the inverses of `changes` (oldest first) as a rollback script.
+pub fn rollback_script(changes: &[SchemaChange]) -> String {
+    let mut script = match (changes.first(), changes.last()) {
+        (Some(first), Some(last)) => format!(
+            "// Rollback of {} schema change(s), seq {}..{}, newest first\n",
+            changes.len(),
+            first.seq,
+            last.seq
+        ),
+        _ => "// No schema changes in range\n".to_string(),
+    };
+    for change in changes.iter().rev() {
+        match &change.inverse {
+            Some(inverse) => {
+                script.push_str(inverse);
+                script.push_str(";\n");
+            }
+            None => script.push_str(&format!(
+                "// seq {}: `{}` cannot be undone\n",
+                change.seq, change.statement
+            )),
+        }
+    }
+    script
+}
+
+/// `CREATE INDEX ON :L(p)`.
+pub(super) fn create_index_statement(label: &str, property: &str) -> String {
+    format!("CREATE INDEX ON :{label}({property})")
+}
+
+/// `DROP INDEX ON :L(p)`.
+pub(super) fn drop_index_statement(label: &str, property: &str) -> String {
+    format!("DROP INDEX ON :{label}({property})")
+}
+
+/// `CREATE CONSTRAINT [name] <body>`.
+pub(super) fn create_constraint_statement(name: Option<&str>, body: &str) -> String {
+    match name {
+        Some(name) => format!("CREATE CONSTRAINT {name} {body}"),
+        None => format!("CREATE CONSTRAINT {body}"),
+    }
+}
+
+/// `DROP CONSTRAINT <body>`.
+pub(super) fn drop_constraint_statement(body: &str) -> String {
+    format!("DROP CONSTRAINT {body}")
+}
+
+/// The `ON (n:L) ASSERT ...` body of a UNIQUE or node EXISTS constraint.
+pub(super) fn assert_constraint_body(label: &str, property: &str, unique: bool) -> String {
+    if unique {
+        format!("ON (n:{label}) ASSERT n.{property} IS UNIQUE")
+    } else {
+        format!("ON (n:{label}) ASSERT EXISTS(n.{property})")
+    }
+}
+
+/// The `FOR (:A)-[r:T]->(:B) REQUIRE ENDPOINTS` body of an endpoint
+/// constraint.
+pub(super) fn endpoint_constraint_body(
+    rel_type: &str,
+    source_label: Option<&str>,
+    target_label: Option<&str>,
+) -> String {
+    let endpoint = |label: Option<&str>| label.map(|l| format!(":{l}")).unwrap_or_default();
+    format!(
+        "FOR ({})-[r:{rel_type}]->({}) REQUIRE ENDPOINTS",
+        endpoint(source_label),
+        endpoint(target_label)
+    )
+}
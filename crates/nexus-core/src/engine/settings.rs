@@ -0,0 +1,456 @@
+//! Runtime settings: the registry behind `SHOW SETTINGS` and
+//! `CALL dbms.setConfigValue(name, value)`.
+//!
+//! [`SETTINGS`] lists the engine knobs an operator can inspect by name.
+//! A dynamic setting can be changed on a running engine; the new value
+//! takes effect for the next query and is stored in the catalog, so it
+//! is applied again — over the value from [`super::EngineConfig`] —
+//! when the engine reopens the data directory. Setting a value to `''`
+//! drops the stored override and restores the value the engine started
+//! with.
+//!
+//! Only knobs that are safe to change under load are dynamic.
+//! `db.string_normalization` is listed but read-only: strings already
+//! stored in one normal form would stop matching queries normalized
+//! into another.
+
+use super::Engine;
+use super::write_pipeline::{CommitMode, WritePipelineConfig};
+use crate::text::StringNormalization;
+use crate::{Error, Result, executor};
+
+/// Kind of value a setting accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingType {
+    /// A non-negative integer no smaller than `min`.
+    Integer {
+        /// Smallest accepted value
+        min: u64,
+    },
+    /// One of a fixed set of lowercase words.
+    Choice(&'static [&'static str]),
+}
+
+/// One entry of the settings registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettingDefinition {
+    /// Setting name, as used by `dbms.setConfigValue`
+    pub name: &'static str,
+    /// One-line description shown by `SHOW SETTINGS`
+    pub description: &'static str,
+    /// Values the setting accepts
+    pub value_type: SettingType,
+    /// Whether the setting can be changed at runtime
+    pub dynamic: bool,
+}
+
+/// Every setting, in `SHOW SETTINGS` order.
+pub const SETTINGS: &[SettingDefinition] = &[
+    SettingDefinition {
+        name: "db.page_cache.capacity",
+        description: "Page cache capacity in 8 KB pages.",
+        value_type: SettingType::Integer { min: 1 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.query.timeout_ms",
+        description: "Wall-clock limit of one query in milliseconds, checked between \
+                      operators; 0 for none.",
+        value_type: SettingType::Integer { min: 0 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.query.max_rows",
+        description: "Rows a query may return before it fails with ERR_RESULT_TOO_LARGE; \
+                      0 for no limit.",
+        value_type: SettingType::Integer { min: 0 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.executor.vectorized_threshold",
+        description: "Input rows on both sides at which joins switch to the vectorized path.",
+        value_type: SettingType::Integer { min: 0 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.executor.columnar_threshold",
+        description: "Rows at which filters and aggregates switch to the columnar SIMD path.",
+        value_type: SettingType::Integer { min: 0 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.cypher.concurrency",
+        description: "Worker count of CALL { ... } IN CONCURRENT TRANSACTIONS.",
+        value_type: SettingType::Integer { min: 1 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.commit_mode",
+        description: "What COMMIT waits for: a store sync (inline) or the WAL fsync with \
+                      background store syncs (pipelined).",
+        value_type: SettingType::Choice(&["inline", "pipelined"]),
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.store_flush_interval_ms",
+        description: "Background store flush interval of the pipelined commit mode.",
+        value_type: SettingType::Integer { min: 1 },
+        dynamic: true,
+    },
+    SettingDefinition {
+        name: "db.string_normalization",
+        description: "Unicode normal form applied to incoming strings.",
+        value_type: SettingType::Choice(&["off", "nfc", "nfd", "nfkc", "nfkd"]),
+        dynamic: false,
+    },
+];
+
+/// The registry entry called `name`.
+pub fn setting_definition(name: &str) -> Option<&'static SettingDefinition> {
+    SETTINGS.iter().find(|def| def.name == name)
+}
+
+impl SettingDefinition {
+    /// Validate `raw` and return it in canonical form.
+    pub fn parse(&self, raw: &str) -> Result<String> {
+        let raw = raw.trim();
+        match self.value_type {
+            SettingType::Integer { min } => match raw.parse::<u64>() {
+                Ok(value) if value >= min => Ok(value.to_string()),
+                _ => Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_VALUE: {} must be an INTEGER >= {min} (got '{raw}')",
+                    self.name
+                ))),
+            },
+            SettingType::Choice(choices) => {
+                let value = raw.to_ascii_lowercase();
+                if choices.contains(&value.as_str()) {
+                    Ok(value)
+                } else {
+                    Err(Error::CypherExecution(format!(
+                        "ERR_INVALID_ARG_VALUE: {} must be one of {} (got '{raw}')",
+                        self.name,
+                        choices.join(", ")
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Where the value in effect comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingSource {
+    /// Built-in default
+    Default,
+    /// [`super::EngineConfig`] supplied at construction
+    Config,
+    /// Set with `dbms.setConfigValue` and stored in the catalog
+    Runtime,
+}
+
+impl SettingSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Config => "config",
+            Self::Runtime => "runtime",
+        }
+    }
+}
+
+/// A setting and its value, as listed by [`Engine::settings`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SettingInfo {
+    /// Setting name
+    pub name: String,
+    /// Value in effect
+    pub value: String,
+    /// Built-in default
+    pub default_value: String,
+    /// One-line description
+    pub description: String,
+    /// Whether the setting can be changed at runtime
+    pub dynamic: bool,
+    /// Where `value` comes from
+    pub source: SettingSource,
+}
+
+/// Snapshot of the knobs the registry covers.
+#[derive(Debug, Clone)]
+pub(crate) struct SettingValues {
+    page_cache_capacity: usize,
+    executor: executor::ExecutorConfig,
+    write_pipeline: WritePipelineConfig,
+    string_normalization: StringNormalization,
+}
+
+impl SettingValues {
+    /// Values of an engine built from the default [`super::EngineConfig`].
+    pub(crate) fn defaults() -> Self {
+        let config = super::EngineConfig::default();
+        Self {
+            page_cache_capacity: config.page_cache_capacity,
+            executor: executor::ExecutorConfig::default(),
+            write_pipeline: config.write_pipeline,
+            string_normalization: config.string_normalization,
+        }
+    }
+
+    /// Value of setting `name` in canonical form.
+    fn get(&self, name: &str) -> String {
+        match name {
+            "db.page_cache.capacity" => self.page_cache_capacity.to_string(),
+            "db.query.timeout_ms" => self.executor.query_timeout_ms.to_string(),
+            "db.query.max_rows" => self.executor.max_result_rows.to_string(),
+            "db.executor.vectorized_threshold" => self.executor.vectorized_threshold.to_string(),
+            "db.executor.columnar_threshold" => self.executor.columnar_threshold.to_string(),
+            "db.cypher.concurrency" => self.executor.cypher_concurrency.to_string(),
+            "db.commit_mode" => match self.write_pipeline.commit_mode {
+                CommitMode::Inline => "inline".to_string(),
+                CommitMode::Pipelined => "pipelined".to_string(),
+            },
+            "db.store_flush_interval_ms" => self.write_pipeline.store_flush_interval_ms.to_string(),
+            "db.string_normalization" => match self.string_normalization {
+                StringNormalization::Off => "off".to_string(),
+                StringNormalization::Nfc => "nfc".to_string(),
+                StringNormalization::Nfd => "nfd".to_string(),
+                StringNormalization::Nfkc => "nfkc".to_string(),
+                StringNormalization::Nfkd => "nfkd".to_string(),
+            },
+            _ => String::new(),
+        }
+    }
+}
+
+impl Engine {
+    /// Every setting with its current value, in registry order.
+    pub fn settings(&self) -> Result<Vec<SettingInfo>> {
+        let stored = self.catalog.list_settings()?;
+        let current = self.setting_values();
+        let defaults = SettingValues::defaults();
+        Ok(SETTINGS
+            .iter()
+            .map(|def| {
+                let value = current.get(def.name);
+                let source = if stored.iter().any(|(name, _)| name == def.name) {
+                    SettingSource::Runtime
+                } else if self.startup_settings.get(def.name) != defaults.get(def.name) {
+                    SettingSource::Config
+                } else {
+                    SettingSource::Default
+                };
+                SettingInfo {
+                    name: def.name.to_string(),
+                    value,
+                    default_value: defaults.get(def.name),
+                    description: def.description.to_string(),
+                    dynamic: def.dynamic,
+                    source,
+                }
+            })
+            .collect())
+    }
+
+    /// Change dynamic setting `name` to `value` and store the new value
+    /// in the catalog. An empty `value` drops the stored value and
+    /// restores the one the engine started with. Returns the previous
+    /// value.
+    pub fn set_setting(&mut self, name: &str, value: &str) -> Result<String> {
+        let def = setting_definition(name).ok_or_else(|| {
+            Error::CypherExecution(format!("ERR_UNKNOWN_SETTING: no setting named '{name}'"))
+        })?;
+        if !def.dynamic {
+            return Err(Error::CypherExecution(format!(
+                "ERR_SETTING_NOT_DYNAMIC: {name} cannot be changed at runtime"
+            )));
+        }
+        let previous = self.setting_values().get(name);
+        if value.trim().is_empty() {
+            let restored = self.startup_settings.get(name);
+            self.apply_setting(name, &restored)?;
+            self.catalog.remove_setting(name)?;
+        } else {
+            let value = def.parse(value)?;
+            self.apply_setting(name, &value)?;
+            self.catalog.persist_setting(name, &value)?;
+        }
+        Ok(previous)
+    }
+
+    /// Apply the setting values stored in the catalog. A stored value
+    /// that no longer parses, or names a setting that is gone or no
+    /// longer dynamic, is logged and skipped so it cannot keep the
+    /// engine from opening.
+    pub(super) fn load_persisted_settings(&mut self) -> Result<()> {
+        self.startup_settings = self.setting_values();
+        for (name, value) in self.catalog.list_settings()? {
+            let applied = match setting_definition(&name) {
+                Some(def) if def.dynamic => def
+                    .parse(&value)
+                    .and_then(|value| self.apply_setting(&name, &value)),
+                _ => Err(Error::CypherExecution(format!(
+                    "'{name}' is not a dynamic setting"
+                ))),
+            };
+            if let Err(e) = applied {
+                tracing::warn!("ignoring stored setting {name} = {value:?}: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Engine-side dispatch for `CALL dbms.setConfigValue(name, value)`.
+    pub(super) fn execute_set_config_value(
+        &mut self,
+        call: &executor::parser::CallProcedureClause,
+    ) -> Result<executor::ResultSet> {
+        const PROCEDURE: &str = "dbms.setConfigValue";
+        let mut args = Vec::with_capacity(call.arguments.len());
+        for expr in &call.arguments {
+            args.push(self.evaluate_set_expression(expr, "", &serde_json::Map::new())?);
+        }
+        let name = match args.first() {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {PROCEDURE} `setting` must be STRING (got {other})"
+                )));
+            }
+            None => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_MISSING_ARG: {PROCEDURE} requires `setting`"
+                )));
+            }
+        };
+        let value = match args.get(1) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Number(n)) => n.to_string(),
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {PROCEDURE} `value` must be STRING (got {other})"
+                )));
+            }
+            None => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_MISSING_ARG: {PROCEDURE} requires `value`"
+                )));
+            }
+        };
+        let previous = self.set_setting(&name, &value)?;
+        let current = self.setting_values().get(&name);
+
+        let all_columns = ["setting", "value", "previousValue"];
+        let columns: Vec<String> = match &call.yield_columns {
+            Some(cols) => cols.clone(),
+            None => all_columns.iter().map(|c| c.to_string()).collect(),
+        };
+        let mut values = Vec::with_capacity(columns.len());
+        for col in &columns {
+            values.push(match col.as_str() {
+                "setting" => serde_json::Value::String(name.clone()),
+                "value" => serde_json::Value::String(current.clone()),
+                "previousValue" => serde_json::Value::String(previous.clone()),
+                other => {
+                    return Err(Error::CypherExecution(format!(
+                        "Unknown YIELD column '{other}' for {PROCEDURE}"
+                    )));
+                }
+            });
+        }
+        Ok(executor::ResultSet::new(
+            columns,
+            vec![executor::Row { values }],
+        ))
+    }
+
+    /// `SHOW SETTINGS`: one row per registered setting.
+    pub(super) fn execute_show_settings(&self) -> Result<executor::ResultSet> {
+        use serde_json::Value;
+
+        let columns = [
+            "name",
+            "value",
+            "isDynamic",
+            "defaultValue",
+            "description",
+            "source",
+        ]
+        .map(str::to_string)
+        .to_vec();
+        let rows = self
+            .settings()?
+            .into_iter()
+            .map(|setting| executor::Row {
+                values: vec![
+                    Value::String(setting.name),
+                    Value::String(setting.value),
+                    Value::Bool(setting.dynamic),
+                    Value::String(setting.default_value),
+                    Value::String(setting.description),
+                    Value::String(setting.source.as_str().to_string()),
+                ],
+            })
+            .collect();
+        Ok(executor::ResultSet::new(columns, rows))
+    }
+
+    fn setting_values(&self) -> SettingValues {
+        SettingValues {
+            page_cache_capacity: self.page_cache.configured_capacity(),
+            executor: self.executor_config.clone(),
+            write_pipeline: self.write_pipeline,
+            string_normalization: self.string_normalization,
+        }
+    }
+
+    /// Put canonical `value` of dynamic setting `name` into effect.
+    fn apply_setting(&mut self, name: &str, value: &str) -> Result<()> {
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| Error::invalid_input(format!("{name}: '{value}' is not an integer")))
+        };
+        match name {
+            "db.page_cache.capacity" => {
+                self.page_cache.reconfigure(number()? as usize)?;
+            }
+            "db.commit_mode" => {
+                let config = WritePipelineConfig {
+                    commit_mode: value.parse()?,
+                    ..self.write_pipeline
+                };
+                self.set_write_pipeline_config(config)?;
+            }
+            "db.store_flush_interval_ms" => {
+                let config = WritePipelineConfig {
+                    store_flush_interval_ms: number()?,
+                    ..self.write_pipeline
+                };
+                self.set_write_pipeline_config(config)?;
+            }
+            _ => {
+                let executor = &mut self.executor_config;
+                match name {
+                    "db.query.timeout_ms" => executor.query_timeout_ms = number()?,
+                    "db.query.max_rows" => executor.max_result_rows = number()? as usize,
+                    "db.executor.vectorized_threshold" => {
+                        executor.vectorized_threshold = number()? as usize
+                    }
+                    "db.executor.columnar_threshold" => {
+                        executor.columnar_threshold = number()? as usize
+                    }
+                    "db.cypher.concurrency" => executor.cypher_concurrency = number()? as usize,
+                    _ => {
+                        return Err(Error::CypherExecution(format!(
+                            "ERR_SETTING_NOT_DYNAMIC: {name} cannot be changed at runtime"
+                        )));
+                    }
+                }
+                self.refresh_executor()?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+//! Per-store-file space accounting for operators.
+//!
+//! [`Engine::storage_metrics`] reports, for every file family under the
+//! data directory, how much disk it takes, how much of that holds live
+//! data, and how much is dead weight that only a rewrite of the file
+//! would reclaim:
+//!
+//! - **nodes / relationships** — fixed-size records; deleted records
+//!   keep their slot (ids are never reused), so tombstones are the
+//!   fragmentation.
+//! - **properties** — append-only; updates that outgrow their slot and
+//!   deletes leave the old entry behind.
+//! - **indexes / catalog** — directory sizes only; their internal slack
+//!   is not visible from here.
+//! - **wal** — the log; "compaction" is a truncation after checkpoint.
+//!
+//! Counting tombstones reads every node and relationship record header,
+//! so the call is linear in the store size.
+
+use super::Engine;
+use crate::Result;
+use crate::storage::{NODE_RECORD_SIZE, REL_RECORD_SIZE};
+use std::path::Path;
+
+/// Space accounting for one store.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct StoreFileMetrics {
+    /// Store name: `nodes`, `relationships`, `properties`, `indexes`,
+    /// `catalog` or `wal`.
+    pub store: &'static str,
+    /// File or directory backing the store.
+    pub path: String,
+    /// Bytes on disk (preallocated space included).
+    pub file_bytes: u64,
+    /// Bytes written so far.
+    pub used_bytes: u64,
+    /// Bytes of `used_bytes` still holding live data.
+    pub live_bytes: u64,
+    /// Records or entries written, when the store counts them.
+    pub records: Option<u64>,
+    /// Records or entries still live.
+    pub live_records: Option<u64>,
+    /// Deleted records still occupying a slot.
+    pub tombstones: Option<u64>,
+    /// `tombstones / records`.
+    pub tombstone_ratio: Option<f64>,
+    /// Reclaimable share of `used_bytes`: `1 - live_bytes / used_bytes`.
+    /// `None` where the store's internal layout is opaque.
+    pub fragmentation: Option<f64>,
+    /// Last compaction (Unix milliseconds). Only the WAL is compacted
+    /// today (truncated after a checkpoint); the other stores report
+    /// `None` until an offline rewrite exists.
+    pub last_compaction_unix_ms: Option<u64>,
+}
+
+/// Outcome of [`Engine::storage_metrics`].
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct StorageMetrics {
+    /// One entry per store, in the order listed in the module docs.
+    pub stores: Vec<StoreFileMetrics>,
+    /// Sum of `file_bytes` across stores.
+    pub total_file_bytes: u64,
+}
+
+impl Engine {
+    /// Collect per-store file sizes, record counts, tombstones and
+    /// fragmentation estimates.
+    pub fn storage_metrics(&self) -> Result<StorageMetrics> {
+        let data_dir = self.storage.path().to_path_buf();
+        let files = self.storage.stats();
+        let mut stores = Vec::with_capacity(6);
+
+        let node_count = self.storage.node_count();
+        let mut deleted_nodes = 0;
+        for node_id in 0..node_count {
+            if self.storage.read_node(node_id)?.is_deleted() {
+                deleted_nodes += 1;
+            }
+        }
+        stores.push(record_store_metrics(
+            "nodes",
+            &data_dir.join("nodes.store"),
+            files.nodes_file_size as u64,
+            NODE_RECORD_SIZE as u64,
+            node_count,
+            deleted_nodes,
+        ));
+
+        let rel_count = self.storage.relationship_count();
+        let mut deleted_rels = 0;
+        for rel_id in 0..rel_count {
+            if self.storage.read_rel(rel_id)?.is_deleted() {
+                deleted_rels += 1;
+            }
+        }
+        stores.push(record_store_metrics(
+            "relationships",
+            &data_dir.join("rels.store"),
+            files.rels_file_size as u64,
+            REL_RECORD_SIZE as u64,
+            rel_count,
+            deleted_rels,
+        ));
+
+        let usage = self
+            .storage
+            .property_store
+            .read()
+            .map_err(|_| crate::Error::storage("property store lock poisoned"))?
+            .usage();
+        stores.push(StoreFileMetrics {
+            store: "properties",
+            path: data_dir.join("properties.store").display().to_string(),
+            file_bytes: usage.file_bytes,
+            used_bytes: usage.used_bytes,
+            live_bytes: usage.live_bytes,
+            live_records: Some(usage.live_entries),
+            fragmentation: Some(reclaimable_share(usage.used_bytes, usage.live_bytes)),
+            ..Default::default()
+        });
+
+        for (store, dir) in [("indexes", "indexes"), ("catalog", "catalog.mdb")] {
+            let path = data_dir.join(dir);
+            let bytes = dir_size(&path);
+            stores.push(StoreFileMetrics {
+                store,
+                path: path.display().to_string(),
+                file_bytes: bytes,
+                used_bytes: bytes,
+                live_bytes: bytes,
+                ..Default::default()
+            });
+        }
+
+        let wal_stats = self.wal.stats();
+        let wal_bytes = self.wal.file_size();
+        stores.push(StoreFileMetrics {
+            store: "wal",
+            path: self.wal.path().display().to_string(),
+            file_bytes: wal_bytes,
+            used_bytes: wal_bytes,
+            live_bytes: wal_bytes,
+            last_compaction_unix_ms: wal_stats.last_truncated_unix_ms,
+            ..Default::default()
+        });
+
+        Ok(StorageMetrics {
+            total_file_bytes: stores.iter().map(|s| s.file_bytes).sum(),
+            stores,
+        })
+    }
+}
+
+fn record_store_metrics(
+    store: &'static str,
+    path: &Path,
+    file_bytes: u64,
+    record_size: u64,
+    records: u64,
+    tombstones: u64,
+) -> StoreFileMetrics {
+    let used_bytes = records * record_size;
+    let live_bytes = (records - tombstones) * record_size;
+    StoreFileMetrics {
+        store,
+        path: path.display().to_string(),
+        file_bytes,
+        used_bytes,
+        live_bytes,
+        records: Some(records),
+        live_records: Some(records - tombstones),
+        tombstones: Some(tombstones),
+        tombstone_ratio: Some(if records == 0 {
+            0.0
+        } else {
+            tombstones as f64 / records as f64
+        }),
+        fragmentation: Some(reclaimable_share(used_bytes, live_bytes)),
+        last_compaction_unix_ms: None,
+    }
+}
+
+fn reclaimable_share(used_bytes: u64, live_bytes: u64) -> f64 {
+    if used_bytes == 0 {
+        0.0
+    } else {
+        used_bytes.saturating_sub(live_bytes) as f64 / used_bytes as f64
+    }
+}
+
+/// Total size of the files under `path` (or of `path` itself when it is
+/// a file). Unreadable entries count as zero.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| dir_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
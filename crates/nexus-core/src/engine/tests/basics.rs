@@ -130,6 +130,146 @@ fn test_engine_knn_search() {
     drop(result);
 }
 
+#[test]
+fn test_engine_knn_rerank_uses_graph_signals() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let dim = engine.indexes.knn_index.dimension();
+    let embedding = |lead: f32| {
+        let mut v = vec![0.0f32; dim];
+        v[0] = lead;
+        v[1] = 1.0 - lead;
+        v
+    };
+
+    let close = engine
+        .create_node(vec!["Doc".into()], serde_json::json!({}))
+        .unwrap();
+    let hub = engine
+        .create_node(vec!["Doc".into()], serde_json::json!({}))
+        .unwrap();
+    let other = engine
+        .create_node(vec!["Tag".into()], serde_json::json!({}))
+        .unwrap();
+    for _ in 0..3 {
+        engine
+            .create_relationship(hub, other, "TAGGED".into(), serde_json::json!({}))
+            .unwrap();
+    }
+    engine
+        .indexes
+        .knn_index
+        .add_vector(close, embedding(1.0))
+        .unwrap();
+    engine
+        .indexes
+        .knn_index
+        .add_vector(hub, embedding(0.8))
+        .unwrap();
+    engine
+        .indexes
+        .knn_index
+        .add_vector(other, embedding(0.9))
+        .unwrap();
+
+    let query = embedding(1.0);
+    let plain = engine
+        .knn_rerank("Doc", &query, 2, &KnnRerankOptions::default())
+        .unwrap();
+    assert_eq!(
+        plain.iter().map(|c| c.node_id).collect::<Vec<_>>(),
+        vec![close, hub]
+    );
+    assert!(plain[0].degree.is_none());
+
+    let options = KnnRerankOptions {
+        expression: "similarity + degree".to_string(),
+        ..Default::default()
+    };
+    let ranked = engine.knn_rerank("Doc", &query, 2, &options).unwrap();
+    assert_eq!(ranked[0].node_id, hub);
+    assert_eq!(ranked[0].degree, Some(3));
+
+    let options = KnnRerankOptions {
+        expression: "proximity".to_string(),
+        context_node: Some(other),
+        ..Default::default()
+    };
+    let ranked = engine.knn_rerank("Doc", &query, 2, &options).unwrap();
+    assert_eq!(ranked[0].node_id, hub);
+    assert_eq!(ranked[0].distance, Some(1));
+    assert_eq!(ranked[1].distance, None);
+
+    let bad = KnnRerankOptions {
+        expression: "similarity * popularity".to_string(),
+        ..Default::default()
+    };
+    assert!(engine.knn_rerank("Doc", &query, 2, &bad).is_err());
+}
+
+#[test]
+fn test_engine_artifacts_are_versioned_and_persisted() {
+    let ctx = crate::testing::TestContext::new();
+    let engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    assert!(engine.artifacts.list().unwrap().is_empty());
+
+    let first = engine
+        .artifacts
+        .save(
+            "nightly-communities",
+            "clustering",
+            serde_json::json!({"clusters": 5}),
+        )
+        .unwrap();
+    assert_eq!(first.version, 1);
+    let second = engine
+        .artifacts
+        .save(
+            "nightly-communities",
+            "clustering",
+            serde_json::json!({"clusters": 7}),
+        )
+        .unwrap();
+    assert_eq!(second.version, 2);
+    assert!(
+        engine
+            .artifacts
+            .save("../escape", "layout", serde_json::json!({}))
+            .is_err()
+    );
+    drop(engine);
+
+    // A fresh store over the same directory sees the saved versions.
+    let artifacts = ArtifactStore::new(ctx.path().join("artifacts"));
+    let latest = artifacts.get("nightly-communities", None).unwrap().unwrap();
+    assert_eq!(latest.payload, serde_json::json!({"clusters": 7}));
+    assert_eq!(
+        artifacts
+            .get("nightly-communities", Some(1))
+            .unwrap()
+            .unwrap()
+            .created_at,
+        first.created_at
+    );
+    let summary = artifacts.list().unwrap();
+    assert_eq!(summary.len(), 1);
+    assert_eq!((summary[0].versions, summary[0].latest.version), (2, 2));
+
+    assert_eq!(artifacts.delete("nightly-communities", Some(1)).unwrap(), 1);
+    let versions = artifacts.versions("nightly-communities").unwrap();
+    assert_eq!(
+        versions.iter().map(|v| v.version).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert_eq!(artifacts.delete("nightly-communities", None).unwrap(), 1);
+    assert!(
+        artifacts
+            .get("nightly-communities", None)
+            .unwrap()
+            .is_none()
+    );
+}
+
 #[test]
 fn test_engine_health_check() {
     let mut engine = Engine::new().unwrap();
@@ -333,3 +473,305 @@ fn test_engine_mut_operations() {
 
     // Test passes if all mutable operations compile
 }
+
+fn integrity_config(level: IntegrityScanLevel, on_corruption: CorruptionPolicy) -> EngineConfig {
+    EngineConfig {
+        integrity_check: IntegrityCheckConfig {
+            level,
+            on_corruption,
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn full_integrity_scan_fails_fast_or_opens_read_only() {
+    let ctx = crate::testing::TestContext::new();
+    {
+        let mut engine = Engine::with_data_dir(ctx.path()).unwrap();
+        assert!(engine.startup_integrity_report().unwrap().is_clean());
+        engine
+            .execute_cypher("CREATE (:IntegrityA {id: 1})-[:INTEGRITY_R]->(:IntegrityA {id: 2})")
+            .unwrap();
+        let report = engine.verify_integrity(IntegrityScanLevel::Full).unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.nodes_checked, 2);
+        assert_eq!(report.relationships_checked, 1);
+
+        // Point the relationship at a node past the end of the store.
+        let mut rel = engine.storage.read_rel(0).unwrap();
+        rel.dst_id = 1_000;
+        engine.storage.write_rel(0, &rel).unwrap();
+        engine.flush().unwrap();
+    }
+
+    // The quick scan does not look at records, so the default opens.
+    drop(Engine::with_data_dir(ctx.path()).unwrap());
+
+    let err = Engine::with_data_dir_and_config(
+        ctx.path(),
+        integrity_config(IntegrityScanLevel::Full, CorruptionPolicy::FailFast),
+    )
+    .err()
+    .expect("full scan must refuse to open a corrupt store");
+    assert!(
+        err.to_string().contains("ERR_INTEGRITY_CHECK_FAILED"),
+        "{err}"
+    );
+
+    let mut engine = Engine::with_data_dir_and_config(
+        ctx.path(),
+        integrity_config(IntegrityScanLevel::Full, CorruptionPolicy::ReadOnly),
+    )
+    .unwrap();
+    assert!(engine.read_only_reason().is_some());
+    let report = engine.startup_integrity_report().unwrap();
+    assert!(
+        report
+            .issues
+            .iter()
+            .any(|i| i.detail.contains("target node 1000")),
+        "{:?}",
+        report.issues
+    );
+
+    let count = engine
+        .execute_cypher("MATCH (n:IntegrityA) RETURN count(n) AS c")
+        .unwrap();
+    assert_eq!(count.rows[0].values[0].as_u64(), Some(2));
+    let err = engine
+        .execute_cypher("CREATE (:IntegrityA {id: 3})")
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)), "{err:?}");
+    assert!(matches!(
+        engine.create_node(
+            vec!["IntegrityA".to_string()],
+            serde_json::serde_json::json!({})
+        ),
+        Err(Error::ReadOnly(_))
+    ));
+}
+
+#[test]
+fn quick_integrity_scan_detects_corrupt_wal_frame() {
+    let ctx = crate::testing::TestContext::new();
+    {
+        let mut engine = Engine::with_data_dir(ctx.path()).unwrap();
+        engine
+            .execute_cypher("CREATE (:IntegrityWal {id: 1})")
+            .unwrap();
+        engine.flush_async_wal().unwrap();
+    }
+
+    // A v1 frame (type, length, payload, crc) whose checksum is wrong.
+    use std::io::Write;
+    let mut wal = std::fs::OpenOptions::new()
+        .append(true)
+        .open(ctx.path().join("wal.log"))
+        .unwrap();
+    wal.write_all(&[1, 1, 0, 0, 0, 0xAB, 0, 0, 0, 0]).unwrap();
+    drop(wal);
+
+    let err = Engine::with_data_dir(ctx.path())
+        .err()
+        .expect("quick scan must catch the bad frame");
+    assert!(err.to_string().contains("[wal]"), "{err}");
+
+    let engine = Engine::with_data_dir_and_config(
+        ctx.path(),
+        integrity_config(IntegrityScanLevel::Off, CorruptionPolicy::FailFast),
+    )
+    .unwrap();
+    assert!(engine.startup_integrity_report().is_none());
+}
+
+#[test]
+fn storage_metrics_report_tombstones_per_store() {
+    let (mut engine, _ctx) = setup_isolated_test_engine().unwrap();
+    let mut ids = Vec::new();
+    for i in 0..4 {
+        ids.push(
+            engine
+                .create_node(vec!["Item".to_string()], serde_json::json!({"i": i}))
+                .unwrap(),
+        );
+    }
+    engine.delete_node(ids[0]).unwrap();
+
+    let metrics = engine.storage_metrics().unwrap();
+    let names: Vec<_> = metrics.stores.iter().map(|s| s.store).collect();
+    assert_eq!(
+        names,
+        vec![
+            "nodes",
+            "relationships",
+            "properties",
+            "indexes",
+            "catalog",
+            "wal"
+        ]
+    );
+    let nodes = &metrics.stores[0];
+    assert_eq!(nodes.records, Some(4));
+    assert_eq!(nodes.tombstones, Some(1));
+    assert_eq!(nodes.tombstone_ratio, Some(0.25));
+    assert_eq!(nodes.fragmentation, Some(0.25));
+    assert!(nodes.file_bytes >= nodes.used_bytes);
+
+    let rels = &metrics.stores[1];
+    assert_eq!(rels.records, Some(0));
+    assert_eq!(rels.fragmentation, Some(0.0));
+
+    let props = &metrics.stores[2];
+    assert!(props.live_bytes > 0);
+    assert!(props.used_bytes >= props.live_bytes);
+    assert_eq!(
+        metrics.total_file_bytes,
+        metrics.stores.iter().map(|s| s.file_bytes).sum::<u64>()
+    );
+}
+
+#[test]
+fn csr_export_lists_live_adjacency_in_row_order() {
+    let (mut engine, _ctx) = setup_isolated_test_engine().unwrap();
+    let mut ids = Vec::new();
+    for i in 0..4 {
+        ids.push(
+            engine
+                .create_node(vec!["V".to_string()], serde_json::json!({"i": i}))
+                .unwrap(),
+        );
+    }
+    let rel = |engine: &mut Engine, a: u64, b: u64, t: &str| {
+        engine
+            .create_relationship(a, b, t.to_string(), serde_json::json!({}))
+            .unwrap()
+    };
+    rel(&mut engine, ids[0], ids[2], "KNOWS");
+    rel(&mut engine, ids[0], ids[3], "LIKES");
+    rel(&mut engine, ids[2], ids[0], "KNOWS");
+    rel(&mut engine, ids[1], ids[3], "KNOWS");
+    engine.delete_node(ids[1]).unwrap();
+
+    let csr = engine.export_csr(&CsrExportOptions::default()).unwrap();
+    assert_eq!(csr.node_ids, vec![ids[0], ids[2], ids[3]]);
+    assert_eq!(csr.offsets, vec![0, 2, 3, 3]);
+    assert_eq!(csr.targets, vec![1, 2, 0]);
+    let names: Vec<&str> = csr.rel_types.iter().map(|(_, n)| n.as_str()).collect();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"KNOWS") && names.contains(&"LIKES"));
+
+    let knows_in = engine
+        .export_csr(&CsrExportOptions {
+            rel_types: vec!["KNOWS".to_string()],
+            direction: CsrDirection::Incoming,
+        })
+        .unwrap();
+    assert_eq!(knows_in.offsets, vec![0, 1, 2, 2]);
+    assert_eq!(knows_in.targets, vec![1, 0]);
+
+    let bytes = csr.to_bytes();
+    assert_eq!(&bytes[..8], crate::engine::csr_export::CSR_MAGIC);
+    assert_eq!(u64::from_le_bytes(bytes[8..16].try_into().unwrap()), 3);
+    assert_eq!(u64::from_le_bytes(bytes[16..24].try_into().unwrap()), 3);
+}
+
+#[test]
+fn memory_pressure_shrinks_and_regrows_the_page_cache() {
+    use crate::memory_management::MemoryPressureLevel;
+
+    let (mut engine, _ctx) = setup_isolated_test_engine().unwrap();
+    let configured = engine.page_cache.configured_capacity();
+    for page_id in 0..configured as u64 {
+        engine.page_cache.get_page(page_id).unwrap();
+    }
+
+    let soft = engine
+        .relieve_memory_pressure(MemoryPressureLevel::Soft)
+        .unwrap();
+    assert_eq!(soft.page_cache_capacity, configured / 2);
+    assert_eq!(soft.pages_evicted, configured - configured / 2);
+
+    let hard = engine
+        .relieve_memory_pressure(MemoryPressureLevel::Hard)
+        .unwrap();
+    assert_eq!(hard.page_cache_capacity, configured / 8);
+    assert!(engine.page_cache.len() <= configured / 8);
+    assert!(engine.page_cache_shrunk());
+
+    let mut capacity = hard.page_cache_capacity;
+    while engine.page_cache_shrunk() {
+        let relief = engine
+            .relieve_memory_pressure(MemoryPressureLevel::Normal)
+            .unwrap();
+        assert!(relief.page_cache_capacity > capacity);
+        capacity = relief.page_cache_capacity;
+    }
+    assert_eq!(capacity, configured);
+}
+
+#[test]
+fn runtime_settings_apply_and_survive_a_restart() {
+    let ctx = crate::testing::TestContext::new();
+    {
+        let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+        let show = engine.execute_cypher("SHOW SETTINGS").unwrap();
+        assert_eq!(show.columns[..2], ["name", "value"]);
+        assert_eq!(show.rows.len(), SETTINGS.len());
+
+        let set = engine
+            .execute_cypher("CALL dbms.setConfigValue('db.page_cache.capacity', '256')")
+            .unwrap();
+        assert_eq!(set.rows[0].values[1], serde_json::json!("256"));
+        assert_eq!(set.rows[0].values[2], serde_json::json!("1024"));
+        assert_eq!(engine.page_cache.configured_capacity(), 256);
+        engine.set_setting("db.query.max_rows", "2").unwrap();
+
+        for i in 0..3 {
+            engine
+                .execute_cypher(&format!("CREATE (:Item {{n: {i}}})"))
+                .unwrap();
+        }
+        let err = engine
+            .execute_cypher("MATCH (n:Item) RETURN n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ERR_RESULT_TOO_LARGE"), "{err}");
+
+        let err = engine
+            .set_setting("db.string_normalization", "nfc")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ERR_SETTING_NOT_DYNAMIC"), "{err}");
+        let err = engine
+            .set_setting("db.cypher.concurrency", "0")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ERR_INVALID_ARG_VALUE"), "{err}");
+        assert!(engine.set_setting("db.no_such_setting", "1").is_err());
+    }
+
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    assert_eq!(engine.page_cache.configured_capacity(), 256);
+    let settings = engine.settings().unwrap();
+    let max_rows = settings
+        .iter()
+        .find(|s| s.name == "db.query.max_rows")
+        .unwrap();
+    assert_eq!(max_rows.value, "2");
+    assert_eq!(max_rows.source, SettingSource::Runtime);
+    assert!(engine.execute_cypher("MATCH (n:Item) RETURN n").is_err());
+
+    // An empty value drops the override.
+    engine.set_setting("db.query.max_rows", "").unwrap();
+    let result = engine.execute_cypher("MATCH (n:Item) RETURN n").unwrap();
+    assert_eq!(result.rows.len(), 3);
+    let max_rows = engine
+        .settings()
+        .unwrap()
+        .into_iter()
+        .find(|s| s.name == "db.query.max_rows")
+        .unwrap();
+    assert_eq!(max_rows.value, "0");
+    assert_eq!(max_rows.source, SettingSource::Default);
+}
@@ -21,13 +21,13 @@ fn constraint_enforcement_all_kinds() {
     engine
         .add_node_key_constraint("Person", &["tenantId", "id"], Some("person_key"))
         .expect("register NODE KEY");
-    engine
+    let alice = engine
         .create_node(
             vec!["Person".to_string()],
             serde_json::json!({ "tenantId": "t1", "id": 1, "name": "Alice" }),
         )
         .expect("first tuple accepted");
-    // Duplicate tuple → NODE_KEY violation.
+    // Duplicate tuple → NODE_KEY violation naming the existing node.
     let err = engine
         .create_node(
             vec!["Person".to_string()],
@@ -35,6 +35,20 @@ fn constraint_enforcement_all_kinds() {
         )
         .expect_err("duplicate tuple must be rejected");
     assert!(err.to_string().contains("NODE_KEY"));
+    let details = err.constraint_violation().expect("structured details");
+    assert_eq!(details.kind, "NODE_KEY");
+    assert_eq!(details.constraint_name.as_deref(), Some("person_key"));
+    assert_eq!(details.labels_or_types, vec!["Person".to_string()]);
+    assert_eq!(
+        details.properties,
+        vec!["tenantId".to_string(), "id".to_string()]
+    );
+    assert_eq!(
+        details.offending_values["tenantId"],
+        serde_json::json!("t1")
+    );
+    assert_eq!(details.offending_values["id"], serde_json::json!(1));
+    assert_eq!(details.conflicting_id, Some(alice));
     // Missing component → NODE_KEY violation (implicit NOT NULL).
     let err = engine
         .create_node(
@@ -62,6 +76,9 @@ fn constraint_enforcement_all_kinds() {
         )
         .expect_err("STRING age rejected under IS :: INTEGER");
     assert!(err.to_string().contains("PROPERTY_TYPE"));
+    let details = err.constraint_violation().expect("structured details");
+    assert_eq!(details.constraint_name.as_deref(), Some("person_age_int"));
+    assert_eq!(details.offending_values["age"], serde_json::json!("thirty"));
 
     // ─── Relationship NOT NULL ───
     engine
@@ -171,3 +188,134 @@ fn cypher25_ddl_routes_through_extended_constraint_apis() {
         .expect_err("rel missing weight rejected via DDL-registered NOT NULL");
     assert!(err.to_string().contains("RELATIONSHIP_PROPERTY_EXISTENCE"));
 }
+
+#[test]
+fn enum_property_type_validates_and_stores_ordinals() {
+    let (mut engine, _ctx) = crate::testing::setup_test_engine().unwrap();
+    let raw = |engine: &Engine, id: u64| {
+        engine
+            .storage
+            .property_store
+            .read()
+            .unwrap()
+            .load_properties(id, crate::storage::property_store::EntityType::Node)
+            .unwrap()
+            .unwrap()
+    };
+
+    // Pre-existing node is validated and re-encoded by the definition.
+    let old = engine
+        .create_node(
+            vec!["EnumTicket".to_string()],
+            serde_json::json!({"status": "CLOSED"}),
+        )
+        .unwrap();
+    engine
+        .add_enum_property_type("EnumTicket", "status", &["OPEN", "CLOSED"])
+        .expect("define enum");
+    assert_eq!(raw(&engine, old)["status"], serde_json::json!(1));
+    assert_eq!(
+        engine.storage.load_node_properties(old).unwrap().unwrap()["status"],
+        serde_json::json!("CLOSED")
+    );
+
+    // Out-of-set values are rejected on CREATE and SET.
+    let err = engine
+        .create_node(
+            vec!["EnumTicket".to_string()],
+            serde_json::json!({"status": "PENDING"}),
+        )
+        .expect_err("value outside the enum rejected");
+    assert!(err.to_string().contains("PROPERTY_ENUM"), "{err}");
+    let err = engine
+        .execute_cypher("MATCH (t:EnumTicket) SET t.status = 'LOST'")
+        .expect_err("SET outside the enum rejected");
+    assert!(err.to_string().contains("PROPERTY_ENUM"), "{err}");
+
+    // Results surface the string form.
+    engine
+        .execute_cypher("CREATE (:EnumTicket {status: 'OPEN'})")
+        .unwrap();
+    let r = engine
+        .execute_cypher("MATCH (t:EnumTicket) RETURN t.status ORDER BY t.status")
+        .unwrap();
+    let statuses: Vec<_> = r.rows.iter().map(|row| row.values[0].clone()).collect();
+    assert_eq!(
+        statuses,
+        vec![serde_json::json!("CLOSED"), serde_json::json!("OPEN")]
+    );
+
+    // Dropping the definition rewrites ordinals back to strings.
+    engine
+        .drop_enum_property_type("EnumTicket", "status")
+        .unwrap();
+    assert_eq!(raw(&engine, old)["status"], serde_json::json!("CLOSED"));
+    assert!(engine.enum_property_types().unwrap().is_empty());
+}
+
+#[test]
+fn rel_endpoint_constraint_enforced_and_listed() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (:EpPerson {id: 1})-[:EP_WORKS_AT]->(:EpCompany {id: 2}), \
+             (:EpPerson {id: 3})-[:EP_LIKES]->(:EpPerson {id: 4})",
+        )
+        .unwrap();
+
+    // Backfill: an existing EP_LIKES edge between two people blocks a
+    // Person -> Company constraint on that type.
+    let err = engine
+        .execute_cypher(
+            "CREATE CONSTRAINT FOR (:EpPerson)-[r:EP_LIKES]->(:EpCompany) REQUIRE ENDPOINTS",
+        )
+        .expect_err("backfill must reject EP_LIKES");
+    assert!(err.to_string().contains("RELATIONSHIP_ENDPOINT"), "{err}");
+
+    engine
+        .execute_cypher(
+            "CREATE CONSTRAINT FOR (:EpPerson)-[r:EP_WORKS_AT]->(:EpCompany) REQUIRE ENDPOINTS",
+        )
+        .expect("existing data satisfies the constraint");
+
+    engine
+        .execute_cypher(
+            "MATCH (p:EpPerson {id: 3}), (c:EpCompany {id: 2}) CREATE (p)-[:EP_WORKS_AT]->(c)",
+        )
+        .expect("Person -> Company accepted");
+    let err = engine
+        .execute_cypher(
+            "MATCH (c:EpCompany {id: 2}), (p:EpPerson {id: 4}) CREATE (c)-[:EP_WORKS_AT]->(p)",
+        )
+        .expect_err("Company -> Person rejected");
+    assert!(err.to_string().contains("RELATIONSHIP_ENDPOINT"), "{err}");
+
+    let shown = engine.execute_cypher("SHOW CONSTRAINTS").unwrap();
+    assert!(
+        shown.rows.iter().any(|r| {
+            r.values[0] == serde_json::json!("EP_WORKS_AT")
+                && r.values[2] == serde_json::json!("RELATIONSHIP_ENDPOINT")
+        }),
+        "SHOW CONSTRAINTS must list the endpoint constraint: {:?}",
+        shown.rows
+    );
+    assert_eq!(
+        engine.rel_endpoint_constraints().unwrap(),
+        vec![(
+            "EP_WORKS_AT".to_string(),
+            Some("EpPerson".to_string()),
+            Some("EpCompany".to_string())
+        )]
+    );
+
+    engine
+        .execute_cypher("DROP CONSTRAINT FOR ()-[r:EP_WORKS_AT]->() REQUIRE ENDPOINTS")
+        .unwrap();
+    assert!(engine.rel_endpoint_constraints().unwrap().is_empty());
+    engine
+        .execute_cypher(
+            "MATCH (c:EpCompany {id: 2}), (p:EpPerson {id: 4}) CREATE (c)-[:EP_WORKS_AT]->(p)",
+        )
+        .expect("unconstrained after DROP");
+}
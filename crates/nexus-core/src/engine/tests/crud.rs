@@ -375,3 +375,221 @@ fn test_clear_all_data() {
     assert_eq!(stats_after.node_count, 0);
     assert_eq!(stats_after.relationship_count, 0);
 }
+
+#[test]
+fn diff_nodes_reports_label_and_property_differences() {
+    use crate::graph::simple::PropertyValue;
+
+    let (mut engine, _ctx) = setup_isolated_test_engine().unwrap();
+    let a = engine
+        .create_node(
+            vec!["Person".to_string(), "Customer".to_string()],
+            serde_json::json!({
+                "name": "Ada", "age": 36, "email": "ada@example.com", "tags": ["x"],
+                "codes": [1, 2], "address": {"city": "London"}
+            }),
+        )
+        .unwrap();
+    let b = engine
+        .create_node(
+            vec!["Person".to_string(), "Employee".to_string()],
+            serde_json::json!({
+                "name": "Ada", "age": 37, "phone": "555", "tags": ["x"],
+                "codes": "[1,2]", "address": {"city": "Paris"}
+            }),
+        )
+        .unwrap();
+
+    let diff = engine.diff_nodes(a, b).unwrap();
+    assert!(!diff.is_identical());
+    assert_eq!(diff.common_labels, vec!["Person".to_string()]);
+    assert_eq!(diff.changes.removed_labels, vec!["Customer".to_string()]);
+    assert_eq!(diff.changes.added_labels, vec!["Employee".to_string()]);
+    assert_eq!(
+        diff.common_properties,
+        vec!["name".to_string(), "tags".to_string()]
+    );
+    let age = &diff.changes.modified_properties["age"];
+    assert_eq!(age.original, PropertyValue::Int64(36));
+    assert_eq!(age.new, PropertyValue::Int64(37));
+    assert_eq!(
+        diff.changes.removed_properties.get("email"),
+        Some(&PropertyValue::String("ada@example.com".to_string()))
+    );
+    assert_eq!(
+        diff.changes.added_properties.get("phone"),
+        Some(&PropertyValue::String("555".to_string()))
+    );
+    // A list and a string holding its JSON text are different values.
+    let codes = &diff.changes.modified_properties["codes"];
+    assert_eq!(
+        codes.original,
+        PropertyValue::List(vec![PropertyValue::Int64(1), PropertyValue::Int64(2)])
+    );
+    assert_eq!(codes.new, PropertyValue::String("[1,2]".to_string()));
+    let address = &diff.changes.modified_properties["address"];
+    assert_eq!(
+        address.new,
+        PropertyValue::Map(std::collections::HashMap::from([(
+            "city".to_string(),
+            PropertyValue::String("Paris".to_string())
+        )]))
+    );
+
+    assert!(engine.diff_nodes(a, a).unwrap().is_identical());
+    assert!(matches!(
+        engine.diff_nodes(a, 9_999),
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[test]
+fn node_relationships_pages_through_adjacency_chain() {
+    use crate::executor::Direction;
+
+    let (mut engine, _ctx) = setup_isolated_test_engine().unwrap();
+    let hub = engine
+        .create_node(vec!["Hub".to_string()], serde_json::json!({}))
+        .unwrap();
+    let mut created = Vec::new();
+    for i in 0..5 {
+        let leaf = engine
+            .create_node(vec!["Leaf".to_string()], serde_json::json!({}))
+            .unwrap();
+        created.push(
+            engine
+                .create_relationship(hub, leaf, "KNOWS".to_string(), serde_json::json!({"i": i}))
+                .unwrap(),
+        );
+    }
+    let fan = engine
+        .create_node(vec!["Fan".to_string()], serde_json::json!({}))
+        .unwrap();
+    let like = engine
+        .create_relationship(fan, hub, "LIKES".to_string(), serde_json::json!({}))
+        .unwrap();
+
+    // Page through everything two at a time; every edge shows up once.
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    let mut pages = 0;
+    loop {
+        let page = engine
+            .node_relationships(hub, Direction::Both, &[], cursor, 2)
+            .unwrap();
+        assert!(page.relationships.len() <= 2);
+        seen.extend(page.relationships.iter().map(|r| r.id));
+        pages += 1;
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(pages, 3);
+    seen.sort_unstable();
+    let mut expected = created.clone();
+    expected.push(like);
+    expected.sort_unstable();
+    assert_eq!(seen, expected);
+
+    let incoming = engine
+        .node_relationships(hub, Direction::Incoming, &[], None, 10)
+        .unwrap();
+    assert_eq!(incoming.relationships.len(), 1);
+    assert_eq!(incoming.relationships[0].rel_type, "LIKES");
+    assert_eq!(incoming.relationships[0].start_node, fan);
+    assert_eq!(incoming.next_cursor, None);
+
+    let knows = engine
+        .node_relationships(hub, Direction::Both, &["KNOWS".to_string()], None, 10)
+        .unwrap();
+    assert_eq!(knows.relationships.len(), 5);
+    assert!(
+        knows
+            .relationships
+            .iter()
+            .all(|r| r.properties["i"].is_number())
+    );
+    let unknown = engine
+        .node_relationships(hub, Direction::Both, &["NOPE".to_string()], None, 10)
+        .unwrap();
+    assert!(unknown.relationships.is_empty());
+
+    // A cursor from another node's chain and an oversized page are rejected.
+    assert!(
+        engine
+            .node_relationships(fan, Direction::Both, &[], Some(created[0]), 10)
+            .is_err()
+    );
+    assert!(
+        engine
+            .node_relationships(hub, Direction::Both, &[], None, 0)
+            .is_err()
+    );
+    assert!(matches!(
+        engine.node_relationships(9_999, Direction::Both, &[], None, 10),
+        Err(crate::Error::NotFound(_))
+    ));
+}
+
+#[test]
+fn test_merge_relationship_reuses_matching_edges() {
+    let (mut engine, _ctx) = setup_isolated_test_engine().unwrap();
+    let a = engine
+        .create_node(vec!["Person".to_string()], serde_json::json!({}))
+        .unwrap();
+    let b = engine
+        .create_node(vec!["Person".to_string()], serde_json::json!({}))
+        .unwrap();
+
+    let (first, created) = engine
+        .merge_relationship(a, b, "KNOWS".to_string(), serde_json::json!({}), &[])
+        .unwrap();
+    assert!(created);
+    let (again, created) = engine
+        .merge_relationship(a, b, "KNOWS".to_string(), serde_json::json!({}), &[])
+        .unwrap();
+    assert!(!created);
+    assert_eq!(again, first);
+
+    // The reverse direction is a different edge.
+    let (_, created) = engine
+        .merge_relationship(b, a, "KNOWS".to_string(), serde_json::json!({}), &[])
+        .unwrap();
+    assert!(created);
+
+    // With key properties, edges differing on a key stay distinct.
+    let keys = ["since".to_string()];
+    let (y2020, created) = engine
+        .merge_relationship(
+            a,
+            b,
+            "WORKED_WITH".to_string(),
+            serde_json::json!({"since": 2020, "note": "x"}),
+            &keys,
+        )
+        .unwrap();
+    assert!(created);
+    let (y2021, created) = engine
+        .merge_relationship(
+            a,
+            b,
+            "WORKED_WITH".to_string(),
+            serde_json::json!({"since": 2021}),
+            &keys,
+        )
+        .unwrap();
+    assert!(created);
+    assert_ne!(y2020, y2021);
+    let (same, created) = engine
+        .merge_relationship(
+            a,
+            b,
+            "WORKED_WITH".to_string(),
+            serde_json::json!({"since": 2020, "note": "y"}),
+            &keys,
+        )
+        .unwrap();
+    assert!(!created);
+    assert_eq!(same, y2020);
+}
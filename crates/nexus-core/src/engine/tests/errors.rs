@@ -116,8 +116,14 @@ fn test_error_invalid_id() {
 
 #[test]
 fn test_error_constraint_violation() {
-    let err = Error::ConstraintViolation("unique constraint violated".to_string());
+    let err = crate::constraints::ConstraintViolation::new("UNIQUENESS", "NODE")
+        .with_message("unique constraint violated")
+        .into_error();
     assert!(matches!(err, Error::ConstraintViolation(_)));
+    assert_eq!(
+        err.constraint_violation().map(|v| v.kind),
+        Some("UNIQUENESS")
+    );
     assert!(err.to_string().contains("unique constraint violated"));
 }
 
@@ -1,6 +1,7 @@
 //! Tests for typed property indexes: correctness, seek vs scan planning,
 //! comma-join planning, API CREATE INDEX (register + backfill + IF NOT EXISTS
-//! + OR REPLACE).
+//! + OR REPLACE), property bloom filters, the node TTL index, embedding
+//! model bindings, the schema changelog and plan baselines.
 
 use super::*;
 
@@ -542,3 +543,809 @@ fn cypher_create_maintains_typed_index_immediately() {
         res.notifications
     );
 }
+
+// ─── property bloom filters ─────────────────────────────────────────────────
+
+/// An un-indexed selector on a bloom-filtered `(label, property)` plans a
+/// `NodeBloomProbe`; values never stored return nothing without a scan,
+/// and values written after the filter was created (CREATE and SET) are
+/// still found.
+#[test]
+#[serial_test::serial]
+fn property_bloom_filter_skips_absent_values() {
+    use crate::executor::types::Operator;
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+
+    engine
+        .execute_cypher("CREATE (:Person {email: 'a@x.io'}), (:Person {email: 'b@x.io'})")
+        .expect("seed CREATE must succeed");
+    assert!(
+        engine
+            .create_property_bloom_filter("Person", "email", 1_000)
+            .unwrap()
+    );
+    assert!(
+        !engine
+            .create_property_bloom_filter("Person", "email", 1_000)
+            .unwrap(),
+        "re-creating an existing filter is a no-op"
+    );
+
+    let plan = engine
+        .executor
+        .parse_and_plan("MATCH (n:Person {email: 'a@x.io'}) RETURN n")
+        .expect("plan must succeed");
+    assert!(
+        plan.iter()
+            .any(|op| matches!(op, Operator::NodeBloomProbe { .. })),
+        "bloom-filtered selector must plan a NodeBloomProbe; plan = {plan:?}"
+    );
+
+    let label_id = engine.catalog.get_label_id("Person").unwrap();
+    let key_id = engine.catalog.get_key_id("email").unwrap();
+    let blooms = &engine.indexes.property_bloom;
+    assert_eq!(
+        blooms.might_contain(label_id, key_id, &serde_json::json!("a@x.io")),
+        Some(true),
+        "backfill must record existing values"
+    );
+    assert_eq!(
+        blooms.might_contain(label_id, key_id, &serde_json::json!("nobody@x.io")),
+        Some(false)
+    );
+
+    let count = |engine: &mut Engine, email: &str| {
+        engine
+            .execute_cypher(&format!(
+                "MATCH (n:Person {{email: '{email}'}}) RETURN count(n) AS c"
+            ))
+            .unwrap()
+            .rows[0]
+            .values[0]
+            .as_i64()
+    };
+    assert_eq!(count(&mut engine, "a@x.io"), Some(1));
+    assert_eq!(count(&mut engine, "nobody@x.io"), Some(0));
+
+    engine
+        .execute_cypher("CREATE (:Person {email: 'c@x.io'})")
+        .unwrap();
+    assert_eq!(count(&mut engine, "c@x.io"), Some(1));
+    engine
+        .execute_cypher("MATCH (n:Person {email: 'b@x.io'}) SET n.email = 'd@x.io'")
+        .unwrap();
+    assert_eq!(count(&mut engine, "d@x.io"), Some(1));
+
+    assert!(
+        engine
+            .drop_property_bloom_filter("Person", "email")
+            .unwrap()
+    );
+    let plan = engine
+        .executor
+        .parse_and_plan("MATCH (n:Person {email: 'a@x.io'}) RETURN n")
+        .unwrap();
+    assert!(
+        !plan
+            .iter()
+            .any(|op| matches!(op, Operator::NodeBloomProbe { .. })),
+        "dropped filter must not be planned; plan = {plan:?}"
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn find_nodes_by_property_combines_indexes_and_scan() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (:Person {email: 'ada@x.io'}), (:Person {email: 'bob@x.io'}), \
+             (:Customer:Vip {email: 'ada@x.io', tags: ['a', 'b']}), \
+             (:Company {domain: 'x.io'})",
+        )
+        .unwrap();
+    engine
+        .execute_cypher("CREATE INDEX FOR (n:Person) ON (n.email)")
+        .unwrap();
+
+    let value = serde_json::json!("ada@x.io");
+    let found = engine
+        .find_nodes_by_property("email", &value, 10, 1_000)
+        .unwrap();
+    assert_eq!(found.indexed_labels, vec!["Person".to_string()]);
+    assert!(!found.truncated);
+    let labels: Vec<_> = found.matches.iter().map(|m| m.labels.clone()).collect();
+    assert_eq!(
+        labels,
+        vec![
+            vec!["Person".to_string()],
+            vec!["Customer".to_string(), "Vip".to_string()],
+        ]
+    );
+    assert_eq!(
+        found.matches[1].properties["tags"],
+        serde_json::json!(["a", "b"])
+    );
+
+    // Structured values bypass the indexes.
+    let tags = engine
+        .find_nodes_by_property("tags", &serde_json::json!(["a", "b"]), 10, 1_000)
+        .unwrap();
+    assert_eq!(tags.matches.len(), 1);
+
+    // Hitting either cap reports a possibly partial answer.
+    let limited = engine
+        .find_nodes_by_property("email", &value, 1, 1_000)
+        .unwrap();
+    assert_eq!(limited.matches.len(), 1);
+    assert!(limited.truncated);
+    let capped = engine
+        .find_nodes_by_property("domain", &serde_json::json!("x.io"), 10, 2)
+        .unwrap();
+    assert!(capped.matches.is_empty());
+    assert_eq!(capped.nodes_scanned, 2);
+    assert!(capped.truncated);
+
+    assert!(engine.find_nodes_by_property("", &value, 10, 10).is_err());
+}
+
+#[test]
+#[serial_test::serial]
+fn named_vector_index_tracks_node_writes_and_survives_restart() {
+    use crate::index::VectorMetric;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let a = engine
+        .create_node(
+            vec!["Doc".to_string()],
+            serde_json::json!({"embedding": [1.0, 0.0, 0.0]}),
+        )
+        .unwrap();
+    let b = engine
+        .create_node(
+            vec!["Doc".to_string()],
+            serde_json::json!({"embedding": [0.0, 1.0]}),
+        )
+        .unwrap();
+    let other = engine
+        .create_node(
+            vec!["Other".to_string()],
+            serde_json::json!({"embedding": [1.0, 0.0, 0.0]}),
+        )
+        .unwrap();
+
+    // Backfill skips the wrong-length vector and the other label.
+    let info = engine
+        .create_vector_index("docs", "Doc", "embedding", 3, VectorMetric::Cosine)
+        .unwrap();
+    assert_eq!(info.vectors, 1);
+    assert!(
+        engine
+            .create_vector_index("dup", "Doc", "embedding", 3, VectorMetric::Cosine)
+            .is_err()
+    );
+
+    engine
+        .upsert_node_vector("docs", b, &[0.0, 1.0, 0.0])
+        .unwrap();
+    assert!(engine.upsert_node_vector("docs", b, &[1.0]).is_err());
+    assert!(
+        engine
+            .upsert_node_vector("docs", other, &[1.0, 0.0, 0.0])
+            .is_err()
+    );
+    let hits = engine.vector_search("docs", &[0.9, 0.1, 0.0], 2).unwrap();
+    assert_eq!(hits.iter().map(|h| h.0).collect::<Vec<_>>(), vec![a, b]);
+
+    // Cypher writes are indexed too.
+    engine
+        .execute_cypher("CREATE (:Doc {embedding: [0.0, 0.0, 1.0]})")
+        .unwrap();
+    assert_eq!(engine.vector_index("docs").unwrap().vectors, 3);
+
+    assert!(engine.remove_node_vector("docs", b).unwrap());
+    engine.delete_node(a).unwrap();
+    assert_eq!(engine.vector_index("docs").unwrap().vectors, 1);
+
+    drop(engine);
+    let engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let info = engine.vector_index("docs").unwrap();
+    assert_eq!((info.dimensions, info.vectors), (3, 1));
+    assert!(matches!(
+        engine.vector_index("missing"),
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[test]
+#[serial_test::serial]
+fn bulk_vector_upsert_reports_rejects_and_feeds_every_covering_index() {
+    use crate::index::VectorMetric;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let doc = engine
+        .create_node(vec!["Doc".to_string()], serde_json::json!({}))
+        .unwrap();
+    let both = engine
+        .create_node(
+            vec!["Doc".to_string(), "Image".to_string()],
+            serde_json::json!({}),
+        )
+        .unwrap();
+    let other = engine
+        .create_node(vec!["Other".to_string()], serde_json::json!({}))
+        .unwrap();
+    engine
+        .create_vector_index("docs", "Doc", "embedding", 2, VectorMetric::Cosine)
+        .unwrap();
+    engine
+        .create_vector_index("images", "Image", "embedding", 2, VectorMetric::Cosine)
+        .unwrap();
+
+    let report = engine
+        .upsert_node_vectors(
+            "docs",
+            vec![
+                (doc, vec![1.0, 0.0]),
+                (both, vec![0.0, 1.0]),
+                (doc, vec![0.6, 0.8]),
+                (other, vec![1.0, 0.0]),
+                (doc, vec![1.0]),
+                (999, vec![1.0, 0.0]),
+            ],
+        )
+        .unwrap();
+    assert_eq!(report.upserted, 3);
+    assert_eq!(
+        report
+            .failed
+            .iter()
+            .map(|f| (f.position, f.node_id))
+            .collect::<Vec<_>>(),
+        vec![(3, other), (4, doc), (5, 999)]
+    );
+
+    assert_eq!(engine.vector_index("docs").unwrap().vectors, 2);
+    assert_eq!(engine.vector_index("images").unwrap().vectors, 1);
+    let hits = engine.vector_search("docs", &[0.6, 0.8], 1).unwrap();
+    assert_eq!(hits[0].0, doc);
+    let properties = engine.storage.load_node_properties(doc).unwrap().unwrap();
+    assert_eq!(properties["embedding"].as_array().unwrap().len(), 2);
+
+    assert!(engine.upsert_node_vectors("missing", vec![]).is_err());
+}
+
+#[test]
+#[serial_test::serial]
+fn vector_search_reports_mode_and_samples_recall() {
+    use crate::index::{KnnSearchMode, KnnSearchOptions, VectorMetric};
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .create_vector_index("docs", "Doc", "embedding", 2, VectorMetric::Euclidean)
+        .unwrap();
+    let vectors = (0..20u64)
+        .map(|i| {
+            let node = engine
+                .create_node(vec!["Doc".to_string()], serde_json::json!({}))
+                .unwrap();
+            (node, vec![i as f32, 1.0])
+        })
+        .collect::<Vec<_>>();
+    let nearest = vectors[3].0;
+    engine.upsert_node_vectors("docs", vectors).unwrap();
+
+    let auto = engine
+        .vector_search_with("docs", &[3.1, 1.0], 1, KnnSearchOptions::default())
+        .unwrap();
+    assert_eq!(auto.mode, KnnSearchMode::Exact);
+    assert_eq!(auto.results[0].0, nearest);
+    assert_eq!(auto.recall, None);
+
+    let sampled = engine
+        .vector_search_with(
+            "docs",
+            &[3.1, 1.0],
+            3,
+            KnnSearchOptions {
+                mode: KnnSearchMode::Approximate,
+                ef_search: Some(64),
+                measure_recall: true,
+            },
+        )
+        .unwrap();
+    assert_eq!(sampled.mode, KnnSearchMode::Approximate);
+    assert!(sampled.recall.is_some());
+
+    let info = engine.vector_index("docs").unwrap();
+    assert_eq!(info.recall_samples, 1);
+    assert_eq!(info.avg_recall, sampled.recall);
+    assert!(info.exact_searches >= 2);
+    assert!(info.approximate_searches >= 1);
+
+    let zero_ef = KnnSearchOptions {
+        ef_search: Some(0),
+        ..KnnSearchOptions::default()
+    };
+    assert!(
+        engine
+            .vector_search_with("docs", &[3.1, 1.0], 1, zero_ef)
+            .is_err()
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn vector_index_rejects_wrong_dimensions_and_migrates_with_atomic_swap() {
+    use crate::index::VectorMetric;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .create_vector_index("docs", "Doc", "embedding", 2, VectorMetric::Cosine)
+        .unwrap();
+    let doc = |engine: &mut Engine, embedding: serde_json::Value| {
+        engine.create_node(
+            vec!["Doc".to_string()],
+            serde_json::json!({ "embedding": embedding }),
+        )
+    };
+    let a = doc(&mut engine, serde_json::json!([1.0, 0.0])).unwrap();
+    let b = doc(&mut engine, serde_json::json!([0.0, 1.0])).unwrap();
+
+    // Wrong-length embeddings are rejected on every write path; other
+    // labels and non-numeric values are not checked.
+    let err = doc(&mut engine, serde_json::json!([1.0, 0.0, 0.0])).unwrap_err();
+    assert!(
+        err.to_string().contains("expects 2 dimensions, got 3"),
+        "{err}"
+    );
+    assert!(
+        engine
+            .execute_cypher("CREATE (:Doc {embedding: [1.0, 2.0, 3.0]})")
+            .is_err()
+    );
+    assert!(
+        engine
+            .update_node(
+                a,
+                vec!["Doc".to_string()],
+                serde_json::json!({"embedding": [1.0]})
+            )
+            .is_err()
+    );
+    engine
+        .create_node(
+            vec!["Other".to_string()],
+            serde_json::json!({"embedding": [1.0, 0.0, 0.0]}),
+        )
+        .unwrap();
+    doc(&mut engine, serde_json::json!("pending")).unwrap();
+
+    // Migrate to three dimensions: both lengths are accepted meanwhile.
+    let migration = engine
+        .start_vector_index_migration("docs", None, Some(3), None)
+        .unwrap();
+    assert_eq!((migration.pending, migration.target.vectors), (2, 0));
+    let c = doc(&mut engine, serde_json::json!([0.0, 0.0, 1.0])).unwrap();
+    assert!(doc(&mut engine, serde_json::json!([1.0, 0.0, 0.0, 0.0])).is_err());
+    assert!(
+        engine
+            .start_vector_index_migration("docs", None, Some(4), None)
+            .is_err()
+    );
+
+    let report = engine
+        .upsert_migration_vectors("docs", vec![(a, vec![1.0, 0.0, 0.0]), (b, vec![1.0])])
+        .unwrap();
+    assert_eq!((report.upserted, report.failed.len()), (1, 1));
+    assert_eq!(engine.vector_index_migration("docs").unwrap().pending, 1);
+    assert!(
+        engine
+            .complete_vector_index_migration("docs", false)
+            .is_err()
+    );
+    // Searches are still served by the live, two-dimensional index.
+    assert_eq!(
+        engine.vector_search("docs", &[0.0, 1.0], 1).unwrap()[0].0,
+        b
+    );
+
+    engine
+        .upsert_migration_vectors("docs", vec![(b, vec![0.0, 1.0, 0.0])])
+        .unwrap();
+    let live = engine
+        .complete_vector_index_migration("docs", false)
+        .unwrap();
+    assert_eq!((live.dimensions, live.vectors), (3, 3));
+    assert!(matches!(
+        engine.vector_index_migration("docs"),
+        Err(Error::NotFound(_))
+    ));
+    assert_eq!(
+        engine.vector_search("docs", &[0.0, 0.0, 1.0], 1).unwrap()[0].0,
+        c
+    );
+    assert!(doc(&mut engine, serde_json::json!([1.0, 0.0])).is_err());
+
+    // An in-progress migration survives a restart and can be aborted.
+    engine
+        .start_vector_index_migration("docs", None, None, Some(VectorMetric::Euclidean))
+        .unwrap();
+    drop(engine);
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let migration = engine.vector_index_migration("docs").unwrap();
+    assert_eq!(migration.target.metric, VectorMetric::Euclidean);
+    assert_eq!((migration.pending, migration.target.vectors), (0, 3));
+    assert!(engine.abort_vector_index_migration("docs").unwrap());
+    assert_eq!(
+        engine.vector_index("docs").unwrap().metric,
+        VectorMetric::Cosine
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn ttl_sweep_deletes_only_expired_nodes_and_their_relationships() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+
+    engine
+        .execute_cypher(
+            "CREATE (:Session {id: 'old', expiresAt: 1000})-[:OWNED_BY]->(:User {id: 'u'})",
+        )
+        .unwrap();
+    assert!(engine.create_ttl_policy("Session", "expiresAt").unwrap());
+    assert!(!engine.create_ttl_policy("Session", "other").unwrap());
+    engine
+        .execute_cypher("CREATE (:Session {id: 'new', expiresAt: 5000})")
+        .unwrap();
+    engine
+        .execute_cypher("CREATE (:Session {id: 'moved', expiresAt: 1500})")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (s:Session {id: 'moved'}) SET s.expiresAt = 9000")
+        .unwrap();
+    assert_eq!(
+        engine.ttl_policies(),
+        vec![("Session".to_string(), "expiresAt".to_string())]
+    );
+
+    let report = engine.sweep_expired_nodes(2_000, 100).unwrap();
+    assert_eq!((report.deleted, report.remaining), (1, 0));
+    assert_eq!(report.lag_ms, 1_000);
+
+    let ids = |engine: &mut Engine| {
+        let mut ids: Vec<String> = engine
+            .execute_cypher("MATCH (s:Session) RETURN s.id AS id")
+            .unwrap()
+            .rows
+            .iter()
+            .filter_map(|row| row.values[0].as_str().map(str::to_string))
+            .collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(ids(&mut engine), vec!["moved", "new"]);
+    let rels = engine
+        .execute_cypher("MATCH ()-[r:OWNED_BY]->() RETURN count(r) AS c")
+        .unwrap();
+    assert_eq!(rels.rows[0].values[0].as_i64(), Some(0));
+
+    let report = engine.sweep_expired_nodes(6_000, 100).unwrap();
+    assert_eq!(report.deleted, 1);
+    assert_eq!(ids(&mut engine), vec!["moved"]);
+    assert!(crate::engine::ttl_sweeper_metrics().sweeps >= 2);
+}
+
+#[test]
+#[serial_test::serial]
+fn count_aggregate_tracks_creates_updates_and_deletes() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+
+    engine
+        .execute_cypher("CREATE (:Order {id: 1, status: 'open'}), (:Order {id: 2, status: 'open'})")
+        .unwrap();
+    assert!(
+        engine
+            .create_count_aggregate("orders_by_status", "Order", Some("status"))
+            .unwrap()
+    );
+    assert!(
+        !engine
+            .create_count_aggregate("orders_by_status", "Order", None)
+            .unwrap()
+    );
+    assert!(engine.create_count_aggregate("", "Order", None).is_err());
+
+    engine
+        .execute_cypher("CREATE (:Order {id: 3, status: 'shipped'}), (:Order {id: 4})")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (o:Order {id: 1}) SET o.status = 'shipped'")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (o:Order {id: 2}) DELETE o")
+        .unwrap();
+
+    let counts = |engine: &mut Engine| {
+        engine
+            .execute_cypher("CALL nexus.aggregate.counts('orders_by_status') YIELD group, count")
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|row| (row.values[0].clone(), row.values[1].as_i64().unwrap()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        counts(&mut engine),
+        vec![
+            (serde_json::json!("shipped"), 2),
+            (serde_json::Value::Null, 1)
+        ]
+    );
+    // Matches a full aggregation over the label.
+    let scanned = engine
+        .execute_cypher("MATCH (o:Order) WHERE o.status = 'shipped' RETURN count(o) AS c")
+        .unwrap();
+    assert_eq!(scanned.rows[0].values[0].as_i64(), Some(2));
+
+    assert_eq!(
+        engine.count_aggregates(),
+        vec![crate::engine::CountAggregateSpec {
+            name: "orders_by_status".to_string(),
+            label: "Order".to_string(),
+            group_by: Some("status".to_string()),
+        }]
+    );
+    assert!(
+        engine
+            .execute_cypher("CALL nexus.aggregate.counts('missing')")
+            .is_err()
+    );
+    assert!(engine.drop_count_aggregate("orders_by_status"));
+    assert!(engine.count_aggregate("orders_by_status").is_none());
+}
+
+#[test]
+#[serial_test::serial]
+fn embedding_model_binding_sizes_vector_indexes_and_shows_in_schema() {
+    use crate::index::VectorMetric;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .set_embedding_model("Doc", "embedding", "bge-small", 3)
+        .unwrap();
+    assert!(
+        engine
+            .set_embedding_model("Doc", "embedding", "bge-small", 0)
+            .is_err()
+    );
+
+    // The bound dimension count fills in for 0 and rejects a mismatch.
+    assert!(
+        engine
+            .create_vector_index("docs", "Doc", "embedding", 4, VectorMetric::Cosine)
+            .is_err()
+    );
+    let index = engine
+        .create_vector_index("docs", "Doc", "embedding", 0, VectorMetric::Cosine)
+        .unwrap();
+    assert_eq!(index.dimensions, 3);
+    assert!(
+        engine
+            .set_embedding_model("Doc", "embedding", "bge-large", 4)
+            .is_err()
+    );
+
+    let result = engine.execute_cypher("CALL db.schema()").unwrap();
+    let doc = result.rows[0].values[0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["name"] == "Doc")
+        .unwrap()
+        .clone();
+    assert_eq!(
+        doc["embeddings"],
+        serde_json::json!([{"property": "embedding", "model": "bge-small", "dimensions": 3}])
+    );
+
+    // Bindings live in the catalog and survive a restart.
+    drop(engine);
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let models = engine.embedding_models().unwrap();
+    assert_eq!(models.len(), 1);
+    assert_eq!(
+        (models[0].model.as_str(), models[0].dimensions),
+        ("bge-small", 3)
+    );
+    assert_eq!(models[0].vector_index.as_deref(), Some("docs"));
+
+    assert!(engine.drop_embedding_model("Doc", "embedding").unwrap());
+    assert!(!engine.drop_embedding_model("Doc", "embedding").unwrap());
+    assert!(
+        engine
+            .embedding_model("Doc", "embedding")
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+#[serial_test::serial]
+fn schema_changelog_records_ddl_and_rolls_it_back() {
+    use crate::catalog::SchemaChangeKind;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let start = engine.schema_log(None, None).unwrap().len();
+
+    engine
+        .execute_cypher("CREATE INDEX ON :Person(email)")
+        .unwrap();
+    engine
+        .execute_cypher("CREATE CONSTRAINT ON (n:Person) ASSERT n.email IS UNIQUE")
+        .unwrap();
+    engine
+        .execute_cypher("DROP INDEX ON :Person(email)")
+        .unwrap();
+    // Skipped commands change nothing and are not recorded.
+    engine
+        .execute_cypher("DROP INDEX IF EXISTS ON :Person(email)")
+        .unwrap();
+
+    let log = engine.schema_log(None, None).unwrap();
+    let changes: Vec<_> = log[start..]
+        .iter()
+        .map(|c| (c.kind, c.statement.as_str(), c.inverse.as_deref()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (SchemaChangeKind::CreateLabel, ":Person", None),
+            (
+                SchemaChangeKind::CreateIndex,
+                "CREATE INDEX ON :Person(email)",
+                Some("DROP INDEX ON :Person(email)")
+            ),
+            (
+                SchemaChangeKind::CreateConstraint,
+                "CREATE CONSTRAINT ON (n:Person) ASSERT n.email IS UNIQUE",
+                Some("DROP CONSTRAINT ON (n:Person) ASSERT n.email IS UNIQUE")
+            ),
+            (
+                SchemaChangeKind::DropIndex,
+                "DROP INDEX ON :Person(email)",
+                Some("CREATE INDEX ON :Person(email)")
+            ),
+        ]
+    );
+    assert!(log.windows(2).all(|w| w[0].seq < w[1].seq));
+
+    // The script undoes the range newest first; running it restores the
+    // index and removes the constraint.
+    let since = log[start].timestamp;
+    let script = engine.schema_rollback_script(Some(since), None).unwrap();
+    let statements: Vec<&str> = script
+        .lines()
+        .filter(|line| !line.starts_with("//"))
+        .map(|line| line.trim_end_matches(';'))
+        .collect();
+    assert_eq!(
+        statements,
+        vec![
+            "CREATE INDEX ON :Person(email)",
+            "DROP CONSTRAINT ON (n:Person) ASSERT n.email IS UNIQUE",
+            "DROP INDEX ON :Person(email)",
+        ]
+    );
+    assert!(script.contains("`:Person` cannot be undone"));
+    engine.execute_cypher(statements[1]).unwrap();
+    engine
+        .create_node(
+            vec!["Person".to_string()],
+            serde_json::json!({"email": "a@example.com"}),
+        )
+        .unwrap();
+    engine
+        .create_node(
+            vec!["Person".to_string()],
+            serde_json::json!({"email": "a@example.com"}),
+        )
+        .expect("constraint dropped by the rollback");
+
+    // The changelog is durable.
+    drop(engine);
+    let engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    assert_eq!(engine.schema_log(None, None).unwrap().len(), log.len() + 1);
+    assert!(engine.schema_log(Some(i64::MAX), None).unwrap().is_empty());
+}
+
+/// Creating an index changes the plan of a recorded template; the
+/// comparison re-plans it before it runs again, then uses the live plan.
+#[test]
+#[serial_test::serial]
+fn plan_baseline_flags_templates_whose_plan_changed() {
+    use crate::performance::plan_history::PlanHistoryConfig;
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    assert!(engine.capture_plan_baseline().is_err());
+    engine
+        .set_plan_history_config(&PlanHistoryConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+    engine
+        .execute_cypher("CREATE (:Person {id: 'a'}), (:Person {id: 'b'})")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (n:Person {id: 'a'}) RETURN n")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (n:Person {id: 'b'}) RETURN n")
+        .unwrap();
+    let records = engine.plan_records();
+    let lookup = records
+        .iter()
+        .find(|r| r.template == "MATCH (n:Person {id: ?}) RETURN n")
+        .expect("lookup template recorded");
+    assert_eq!(lookup.executions, 2);
+
+    let baseline = engine.capture_plan_baseline().unwrap();
+    assert_eq!(baseline.version, 1);
+    assert!(engine.plan_records().is_empty());
+    let unchanged = engine
+        .compare_plan_baseline(None, DEFAULT_PLAN_REGRESSION_THRESHOLD)
+        .unwrap()
+        .unwrap();
+    assert_eq!(unchanged.changed, 0);
+
+    engine
+        .execute_cypher("CREATE INDEX FOR (n:Person) ON (n.id)")
+        .unwrap();
+    let replanned = engine
+        .compare_plan_baseline(None, DEFAULT_PLAN_REGRESSION_THRESHOLD)
+        .unwrap()
+        .unwrap();
+    let change = replanned
+        .changes
+        .iter()
+        .find(|c| c.template == "MATCH (n:Person {id: ?}) RETURN n")
+        .expect("index seek replaces the label scan");
+    assert!(
+        change
+            .current_plan
+            .iter()
+            .any(|op| op.starts_with("NodeIndexSeek"))
+    );
+    assert_eq!(change.current_mean_ms, None);
+    assert!(!change.regressed);
+
+    engine
+        .execute_cypher("MATCH (n:Person {id: 'b'}) RETURN n")
+        .unwrap();
+    let live = engine.compare_plan_baseline(Some(1), 0.0).unwrap().unwrap();
+    let change = live
+        .changes
+        .iter()
+        .find(|c| c.template == "MATCH (n:Person {id: ?}) RETURN n")
+        .unwrap();
+    assert!(change.current_mean_ms.is_some());
+    assert!(
+        engine
+            .compare_plan_baseline(Some(2), 0.0)
+            .unwrap()
+            .is_none()
+    );
+    assert!(engine.compare_plan_baseline(None, -1.0).is_err());
+}
@@ -1,7 +1,9 @@
 //! Regression tests for Cypher query execution: pattern matching, arithmetic,
 //! ORDER BY null positioning, aggregations, procedure calls, WITH pipelines,
 //! type-check predicates, list converters, dynamic property access, SET +=,
-//! WHERE label predicates, system procedures, and cartesian-product counting.
+//! WHERE label predicates, system procedures, cartesian-product counting,
+//! count-only expansions, saved and materialized views, remote data
+//! loading, result diffs, numeric precision and caller row limits.
 
 use super::*;
 
@@ -513,6 +515,45 @@ fn db_labels_procedure_emits_a_row_per_label() {
     }
 }
 
+/// The catalog metadata procedures keep Neo4j's single-column
+/// signatures: the declared column can be yielded and projected, and
+/// yielding any other name is an error rather than a silent rename.
+#[test]
+#[serial_test::serial]
+fn metadata_procedures_yield_declared_column_only() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE (:MetaPerson {name: 'a'})-[:META_KNOWS]->(:MetaPerson)")
+        .unwrap();
+
+    for (procedure, column, expected) in [
+        ("db.labels", "label", "MetaPerson"),
+        ("db.relationshipTypes", "relationshipType", "META_KNOWS"),
+        ("db.propertyKeys", "propertyKey", "name"),
+    ] {
+        let r = engine
+            .execute_cypher(&format!(
+                "CALL {procedure}() YIELD {column} RETURN {column}"
+            ))
+            .unwrap();
+        assert_eq!(r.columns, vec![column.to_string()], "{procedure}");
+        assert!(
+            r.rows.iter().any(|row| row.values[0] == expected),
+            "{procedure} should include {expected}; got {:?}",
+            r.rows
+        );
+
+        let err = engine
+            .execute_cypher(&format!("CALL {procedure}() YIELD name"))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown procedure output"),
+            "{procedure}: {err}"
+        );
+    }
+}
+
 /// Regression for phase6_nexus-bench-correctness-gaps §5 —
 /// when WITH carries the aggregation and RETURN only references its
 /// aliases (wrapping them in a non-aggregate expression), the planner
@@ -1272,3 +1313,696 @@ fn point_property_accessors_match_neo4j() {
         .expect("query must succeed");
     assert_eq!(rs.rows.len(), 0, "empty MATCH stays empty through WITH");
 }
+
+#[test]
+fn sample_procedures_return_bounded_subgraphs() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (a:Hub {name: 'a'}), (b:Leaf {name: 'b'}), (c:Leaf {name: 'c'}), \
+             (d:Leaf {name: 'd'}), (a)-[:LINK]->(b), (a)-[:LINK]->(c), (c)-[:NEXT]->(d)",
+        )
+        .unwrap();
+    let hub = engine
+        .execute_cypher("MATCH (n:Hub) RETURN id(n) AS id")
+        .unwrap()
+        .rows[0]
+        .values[0]
+        .as_u64()
+        .unwrap();
+    let sizes = |engine: &mut Engine, query: &str| {
+        let result = engine.execute_cypher(query).unwrap();
+        assert_eq!(result.columns, vec!["nodes", "relationships"]);
+        let len = |v: &serde_json::Value| v.as_array().unwrap().len();
+        (
+            len(&result.rows[0].values[0]),
+            len(&result.rows[0].values[1]),
+        )
+    };
+
+    // Four walks of three hops from the hub cannot leave its component.
+    let (nodes, rels) = sizes(
+        &mut engine,
+        &format!("CALL nexus.sample.randomWalk({hub}, 3, 3, {{seed: 7}})"),
+    );
+    assert!((2..=4).contains(&nodes), "{nodes} nodes");
+    assert!((1..=3).contains(&rels), "{rels} relationships");
+    let (nodes, rels) = sizes(
+        &mut engine,
+        &format!("CALL nexus.sample.randomWalk({hub}, 5, 0, {{relationshipTypes: ['MISSING']}})"),
+    );
+    assert_eq!((nodes, rels), (1, 0));
+
+    assert_eq!(
+        sizes(&mut engine, "CALL nexus.sample.nodes(2, {seed: 1})").0,
+        2
+    );
+    assert_eq!(
+        sizes(&mut engine, "CALL nexus.sample.nodes(10, {label: 'Hub'})"),
+        (1, 0)
+    );
+    assert_eq!(
+        sizes(&mut engine, "CALL nexus.sample.nodes(10)"),
+        (4, 3),
+        "sampling every node keeps every induced relationship"
+    );
+    assert_eq!(
+        sizes(
+            &mut engine,
+            "CALL nexus.sample.relationships(5, {type: 'NEXT'})"
+        ),
+        (2, 1)
+    );
+
+    let err = engine
+        .execute_cypher("CALL nexus.sample.nodes(1000000)")
+        .unwrap_err();
+    assert!(err.to_string().contains("ERR_INVALID_ARG_VALUE"), "{err}");
+}
+
+/// Labels, relationship types, and property keys that are not plain
+/// identifiers round-trip through backtick escaping, including the
+/// planner's re-rendered WHERE predicates.
+#[test]
+fn backtick_escaped_identifiers_round_trip() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+
+    engine
+        .execute_cypher(
+            "CREATE (a:`Imported Person` {`first name`: 'Ann', `e-mail`: 'ann@x.io'}), \
+             (b:`🚀` {`first name`: 'Bob'}), (a)-[:`WORKS WITH`]->(b)",
+        )
+        .unwrap();
+
+    let result = engine
+        .execute_cypher(
+            "MATCH (n:`Imported Person`)-[:`WORKS WITH`]->(m:`🚀`) \
+             WHERE n.`first name` = 'Ann' \
+             RETURN n.`e-mail` AS `e-mail`, m.`first name` AS name",
+        )
+        .unwrap();
+    assert_eq!(result.columns, vec!["e-mail", "name"]);
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0].values[0], serde_json::json!("ann@x.io"));
+    assert_eq!(result.rows[0].values[1], serde_json::json!("Bob"));
+
+    let labels = engine
+        .execute_cypher("MATCH (n {`first name`: 'Ann'}) RETURN labels(n) AS l")
+        .unwrap();
+    assert_eq!(
+        labels.rows[0].values[0],
+        serde_json::json!(["Imported Person"])
+    );
+}
+
+/// With NFC normalization on, decomposed input (literal or parameter)
+/// is stored composed and matches a composed literal; `collationKey`
+/// sorts Swedish names with å/ä/ö after z.
+#[test]
+fn string_normalization_and_collation() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine.set_string_normalization(crate::text::StringNormalization::Nfc);
+
+    engine
+        .execute_cypher("CREATE (:City {name: 'Malmo\u{308}'})")
+        .unwrap();
+    let mut params = HashMap::new();
+    params.insert("name".to_string(), serde_json::json!("A\u{30a}rhus"));
+    engine
+        .execute_cypher_with_params("CREATE (:City {name: $name})", params)
+        .unwrap();
+    engine
+        .execute_cypher("CREATE (:City {name: 'Zurich'})")
+        .unwrap();
+
+    let found = engine
+        .execute_cypher("MATCH (c:City {name: 'Malm\u{f6}'}) RETURN c.name AS name")
+        .unwrap();
+    assert_eq!(found.rows.len(), 1);
+    assert_eq!(found.rows[0].values[0], serde_json::json!("Malm\u{f6}"));
+
+    let sorted = engine
+        .execute_cypher(
+            "MATCH (c:City) RETURN c.name AS name, collationKey(c.name, 'sv') AS k \
+             ORDER BY k",
+        )
+        .unwrap();
+    let names: Vec<_> = sorted.rows.iter().map(|r| r.values[0].clone()).collect();
+    assert_eq!(
+        names,
+        vec![
+            serde_json::json!("Malm\u{f6}"),
+            serde_json::json!("Zurich"),
+            serde_json::json!("\u{c5}rhus"),
+        ]
+    );
+
+    let cmp = engine
+        .execute_cypher("RETURN collate('résumé', 'RESUME', 'fr', 'primary') AS c")
+        .unwrap();
+    assert_eq!(cmp.rows[0].values[0], serde_json::json!(0));
+}
+
+/// `MATCH (n)-[:T]->(m) RETURN n, count(m)` plans as `ExpandCount` and
+/// returns one row per node with at least one matching relationship,
+/// counting every relationship (parallel ones included).
+#[test]
+fn neighbor_count_plans_expand_count_and_counts_per_node() {
+    use crate::executor::types::Operator;
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_data_dir(ctx.path()).unwrap();
+
+    engine
+        .execute_cypher(
+            "CREATE (a:Person {name: 'a'}), (b:Person {name: 'b'}), (c:Person {name: 'c'}), \
+             (a)-[:FOLLOWS]->(b), (a)-[:FOLLOWS]->(c), (a)-[:FOLLOWS]->(c), \
+             (b)-[:FOLLOWS]->(c), (c)-[:LIKES]->(a)",
+        )
+        .unwrap();
+
+    let cypher = "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n.name AS name, count(m) AS c \
+                  ORDER BY name";
+    let plan = engine.executor.parse_and_plan(cypher).unwrap();
+    assert!(
+        plan.iter()
+            .any(|op| matches!(op, Operator::ExpandCount { .. })),
+        "count-only expansion must plan ExpandCount; plan = {plan:?}"
+    );
+
+    let result = engine.execute_cypher(cypher).unwrap();
+    let rows: Vec<_> = result
+        .rows
+        .iter()
+        .map(|r| (r.values[0].clone(), r.values[1].as_i64()))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (serde_json::json!("a"), Some(3)),
+            (serde_json::json!("b"), Some(1)),
+        ]
+    );
+    assert_eq!(result.columns, vec!["name".to_string(), "c".to_string()]);
+
+    let incoming = engine
+        .execute_cypher(
+            "MATCH (n:Person)<-[:FOLLOWS]-(m) RETURN n.name AS name, count(*) AS c ORDER BY name",
+        )
+        .unwrap();
+    let rows: Vec<_> = incoming
+        .rows
+        .iter()
+        .map(|r| (r.values[0].clone(), r.values[1].as_i64()))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            (serde_json::json!("b"), Some(1)),
+            (serde_json::json!("c"), Some(3)),
+        ]
+    );
+}
+
+/// A saved view works as a label in MATCH, through `nexus.view`, in
+/// SHOW VIEWS, and survives a restart.
+#[test]
+fn saved_view_is_usable_as_label_and_procedure() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (:User {name: 'Ann', active: true}), (:User {name: 'Bob', active: false}), \
+             (:User {name: 'Cy', active: true}), (:Team {name: 'core'})",
+        )
+        .unwrap();
+    engine
+        .execute_cypher("CREATE VIEW active_users AS MATCH (u:User) WHERE u.active = true RETURN u")
+        .unwrap();
+
+    let names = |engine: &mut Engine, query: &str| -> Vec<String> {
+        engine
+            .execute_cypher(query)
+            .unwrap()
+            .rows
+            .iter()
+            .map(|r| r.values[0].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        names(
+            &mut engine,
+            "MATCH (u:active_users) RETURN u.name AS name ORDER BY name"
+        ),
+        vec!["Ann", "Cy"]
+    );
+    assert_eq!(
+        names(
+            &mut engine,
+            "MATCH (u:active_users) WHERE u.name = 'Cy' OR u.name = 'Bob' RETURN u.name"
+        ),
+        vec!["Cy"]
+    );
+
+    let called = engine
+        .execute_cypher("CALL nexus.view('active_users')")
+        .unwrap();
+    assert_eq!(called.columns, vec!["u".to_string()]);
+    assert_eq!(called.rows.len(), 2);
+
+    // Duplicates, write queries and self-references are rejected.
+    assert!(
+        engine
+            .execute_cypher("CREATE VIEW active_users AS MATCH (u:User) RETURN u")
+            .is_err()
+    );
+    engine
+        .execute_cypher("CREATE VIEW IF NOT EXISTS active_users AS MATCH (u:User) RETURN u")
+        .unwrap();
+    assert!(
+        engine
+            .execute_cypher("CREATE VIEW bad AS MATCH (u:User) SET u.x = 1 RETURN u")
+            .is_err()
+    );
+    assert!(
+        engine
+            .execute_cypher("CREATE VIEW loop AS MATCH (u:loop) RETURN u")
+            .is_err()
+    );
+    assert!(
+        engine
+            .execute_cypher("CREATE VIEW Team AS MATCH (u:User) RETURN u")
+            .is_err(),
+        "a label with nodes cannot be shadowed by a view"
+    );
+
+    drop(engine);
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let shown = engine.execute_cypher("SHOW VIEWS").unwrap();
+    assert_eq!(
+        shown.columns,
+        vec![
+            "name",
+            "query",
+            "owner",
+            "createdAt",
+            "materialized",
+            "refresh",
+            "refreshedAt",
+            "stale",
+            "rowCount"
+        ]
+    );
+    assert_eq!(shown.rows.len(), 1);
+    assert_eq!(shown.rows[0].values[0], serde_json::json!("active_users"));
+    assert_eq!(
+        shown.rows[0].values[1],
+        serde_json::json!("MATCH (u:User) WHERE u.active = true RETURN u")
+    );
+
+    engine.execute_cypher("DROP VIEW active_users").unwrap();
+    assert!(engine.views().is_empty());
+    assert!(engine.execute_cypher("DROP VIEW active_users").is_err());
+    engine
+        .execute_cypher("DROP VIEW IF EXISTS active_users")
+        .unwrap();
+}
+
+#[test]
+fn materialized_view_serves_stored_result_until_refreshed() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE (:Order {total: 10}), (:Order {total: 5})")
+        .unwrap();
+    engine
+        .execute_cypher(
+            "CREATE MATERIALIZED VIEW order_totals AS \
+             MATCH (o:Order) RETURN count(o) AS orders, sum(o.total) AS revenue",
+        )
+        .unwrap();
+    let totals = |engine: &mut Engine| {
+        engine
+            .execute_cypher("CALL nexus.view('order_totals')")
+            .unwrap()
+            .rows[0]
+            .values
+            .iter()
+            .map(|v| v.as_f64().unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(totals(&mut engine), vec![2.0, 15.0]);
+    assert!(!engine.is_view_stale("order_totals"));
+
+    // Writes to labels the view does not read leave it fresh.
+    engine
+        .execute_cypher("CREATE (:Customer {name: 'Ann'})")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (c:Customer) SET c.name = 'Bo'")
+        .unwrap();
+    assert!(!engine.is_view_stale("order_totals"));
+    assert!(!engine.has_due_views());
+
+    // Writes leave the stored result alone but mark the view stale.
+    engine.execute_cypher("CREATE (:Order {total: 7})").unwrap();
+    assert!(engine.is_view_stale("order_totals"));
+    assert_eq!(totals(&mut engine), vec![2.0, 15.0]);
+    let shown = engine.execute_cypher("SHOW VIEWS").unwrap();
+    assert_eq!(shown.rows[0].values[4], serde_json::json!(true));
+    assert_eq!(shown.rows[0].values[5], serde_json::json!("ON CHANGE"));
+    assert_eq!(shown.rows[0].values[7], serde_json::json!(true));
+    assert_eq!(shown.rows[0].values[8], serde_json::json!(1));
+
+    // ON CHANGE views are due once something changed; MANUAL ones never.
+    engine
+        .execute_cypher(
+            "CREATE MATERIALIZED VIEW big_orders REFRESH MANUAL AS \
+             MATCH (o:Order) WHERE o.total > 6 RETURN o",
+        )
+        .unwrap();
+    engine.execute_cypher("CREATE (:Order {total: 9})").unwrap();
+    assert!(engine.has_due_views());
+    assert_eq!(engine.refresh_due_views(), vec!["order_totals".to_string()]);
+    assert!(!engine.has_due_views());
+    assert_eq!(totals(&mut engine), vec![4.0, 31.0]);
+
+    // A materialized view used as a label keeps its node set until
+    // refreshed.
+    let big = |engine: &mut Engine| {
+        engine
+            .execute_cypher("MATCH (o:big_orders) RETURN o.total AS t ORDER BY t")
+            .unwrap()
+            .rows
+            .iter()
+            .map(|r| r.values[0].clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        big(&mut engine),
+        vec![serde_json::json!(7), serde_json::json!(10)]
+    );
+    engine.execute_cypher("REFRESH VIEW big_orders").unwrap();
+    assert_eq!(
+        big(&mut engine),
+        vec![
+            serde_json::json!(7),
+            serde_json::json!(9),
+            serde_json::json!(10)
+        ]
+    );
+    engine
+        .execute_cypher("CREATE VIEW plain AS MATCH (o:Order) RETURN o")
+        .unwrap();
+    assert!(engine.execute_cypher("REFRESH VIEW plain").is_err());
+
+    // Stored results survive a restart; change tracking does not.
+    drop(engine);
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    assert_eq!(totals(&mut engine), vec![4.0, 31.0]);
+    assert!(engine.is_view_stale("order_totals"));
+    engine
+        .execute_cypher("DROP MATERIALIZED VIEW order_totals")
+        .unwrap();
+    assert!(!engine.is_view_stale("order_totals"));
+    assert!(
+        engine
+            .execute_cypher("CALL nexus.view('order_totals')")
+            .is_err()
+    );
+}
+
+/// Serve each path in `routes` once, in any order, from a local listener.
+/// Returns the base URL.
+fn serve_once(routes: Vec<(&'static str, &'static str)>) -> String {
+    use std::io::{Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for _ in 0..routes.len() {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let request = String::from_utf8_lossy(&request);
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let body = routes
+                .iter()
+                .find(|(route, _)| *route == path)
+                .map_or("", |(_, body)| body);
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+    });
+    base
+}
+
+#[test]
+fn load_functions_yield_remote_rows_from_allowed_hosts() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let base = serve_once(vec![
+        (
+            "/plans.csv",
+            "email,plan\nann@x.io,pro\n\"cy@x.io\",\"free, trial\"\n",
+        ),
+        (
+            "/scores.json",
+            r#"[{"email": "bob@x.io", "score": 3}, {"email": "ann@x.io"}]"#,
+        ),
+    ]);
+
+    // Remote loading is off until hosts are allowed.
+    let err = engine
+        .execute_cypher(&format!("CALL nexus.load.csvFromUrl('{base}/plans.csv')"))
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("ERR_EXTERNAL_DATA_DENIED"),
+        "{err}"
+    );
+    engine
+        .set_external_data_config(crate::external_data::ExternalDataConfig {
+            allowed_hosts: vec!["127.0.0.1".to_string()],
+            allow_http: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+    let plans = engine
+        .execute_cypher(&format!(
+            "CALL nexus.load.csvFromUrl('{base}/plans.csv') YIELD lineNo, map"
+        ))
+        .unwrap();
+    assert_eq!(plans.columns, vec!["lineNo", "map"]);
+    assert_eq!(
+        plans
+            .rows
+            .iter()
+            .map(|r| r.values.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            vec![
+                serde_json::json!(0),
+                serde_json::json!({"email": "ann@x.io", "plan": "pro"})
+            ],
+            vec![
+                serde_json::json!(1),
+                serde_json::json!({"email": "cy@x.io", "plan": "free, trial"})
+            ],
+        ]
+    );
+
+    let scores = engine
+        .execute_cypher(&format!(
+            "CALL nexus.load.jsonFromUrl('{base}/scores.json') YIELD value"
+        ))
+        .unwrap();
+    assert_eq!(scores.rows.len(), 2);
+    assert_eq!(
+        scores.rows[0].values[0],
+        serde_json::json!({"email": "bob@x.io", "score": 3})
+    );
+
+    // Hosts off the allow-list are refused before any request is made.
+    let err = engine
+        .execute_cypher("CALL nexus.load.jsonFromUrl('http://example.com/x.json')")
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("not in the allowed hosts"),
+        "{err}"
+    );
+}
+
+#[test]
+fn diff_results_reports_rows_missing_from_either_side() {
+    use crate::engine::{ResultDiffOptions, diff_results};
+
+    let ctx_a = crate::testing::TestContext::new();
+    let ctx_b = crate::testing::TestContext::new();
+    let mut before = Engine::with_isolated_catalog(ctx_a.path()).unwrap();
+    let mut after = Engine::with_isolated_catalog(ctx_b.path()).unwrap();
+    before
+        .execute_cypher(
+            "CREATE (:City {name: 'Oslo', score: 1.0}), (:City {name: 'Rome', score: 2.0}), \
+             (:City {name: 'Kyiv', score: 3.0})",
+        )
+        .unwrap();
+    // Same data loaded in another order, one score drifted and one city
+    // replaced.
+    after
+        .execute_cypher(
+            "CREATE (:City {name: 'Lima', score: 3.0}), (:City {name: 'Rome', score: 2.0000001}), \
+             (:City {name: 'Oslo', score: 1})",
+        )
+        .unwrap();
+    let query = "MATCH (c:City) RETURN c.name AS name, c.score AS score";
+    let left = before.execute_cypher(query).unwrap();
+    let right = after
+        .execute_cypher("MATCH (c:City) RETURN c.score AS score, c.name AS name")
+        .unwrap();
+
+    let strict = diff_results(&left, &right, &ResultDiffOptions::default());
+    assert!(strict.columns_match);
+    assert!(!strict.identical);
+    assert_eq!(strict.matched_rows, 1, "Oslo matches; 1 equals 1.0");
+    assert_eq!(strict.only_in_left_count, 2);
+    assert_eq!(strict.only_in_right_count, 2);
+
+    let tolerant = diff_results(
+        &left,
+        &right,
+        &ResultDiffOptions {
+            float_tolerance: 1e-3,
+            ..Default::default()
+        },
+    );
+    assert_eq!(tolerant.matched_rows, 2);
+    assert_eq!(tolerant.only_in_left.len(), 1);
+    assert_eq!(
+        tolerant.only_in_left[0].values,
+        vec![serde_json::json!("Kyiv"), serde_json::json!(3.0)]
+    );
+    assert_eq!(
+        tolerant.only_in_right[0].values,
+        vec![serde_json::json!(3.0), serde_json::json!("Lima")]
+    );
+
+    // Node values match across databases although their ids differ.
+    let nodes = "MATCH (c:City) WHERE c.name = 'Oslo' RETURN c";
+    let left = before.execute_cypher(nodes).unwrap();
+    after.execute_cypher("CREATE (:Filler)").unwrap();
+    let right = after.execute_cypher(nodes).unwrap();
+    assert!(diff_results(&left, &right, &ResultDiffOptions::default()).identical);
+
+    // Ordered comparison reports positional changes and truncates.
+    let left = before
+        .execute_cypher("MATCH (c:City) RETURN c.name AS name ORDER BY name")
+        .unwrap();
+    let right = after
+        .execute_cypher("MATCH (c:City) RETURN c.name AS name ORDER BY name DESC")
+        .unwrap();
+    let ordered = diff_results(
+        &left,
+        &right,
+        &ResultDiffOptions {
+            ordered: true,
+            max_differences: 1,
+            ..Default::default()
+        },
+    );
+    assert_eq!(ordered.changed_rows.len(), 1);
+    assert_eq!(ordered.changed_rows[0].index, 0);
+    assert!(ordered.truncated);
+}
+
+/// INTEGER stays exact end to end, overflow is an error rather than a
+/// silent FLOAT, and DECIMAL arithmetic is exact.
+#[test]
+fn integer_fidelity_overflow_and_decimal_arithmetic() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_data_dir(ctx.path()).unwrap();
+
+    // 2^53 + 1 survives a property round trip and compares exactly.
+    engine
+        .execute_cypher("CREATE (:Ledger {id: 9007199254740993, total: 9223372036854775807})")
+        .unwrap();
+    let r = engine
+        .execute_cypher(
+            "MATCH (l:Ledger) RETURN l.id AS id, l.id > 9007199254740992 AS bigger, l.total AS total",
+        )
+        .unwrap();
+    assert_eq!(r.rows[0].values[0], serde_json::json!(9007199254740993i64));
+    assert_eq!(r.rows[0].values[1], serde_json::json!(true));
+    assert_eq!(r.rows[0].values[2], serde_json::json!(i64::MAX));
+
+    let err = engine
+        .execute_cypher("MATCH (l:Ledger) RETURN l.total + 1 AS v")
+        .unwrap_err();
+    assert!(err.to_string().contains("ERR_INTEGER_OVERFLOW"), "{err}");
+
+    // DECIMAL: exact sums, INTEGER mixes in, FLOAT does not.
+    let r = engine
+        .execute_cypher("RETURN toDecimal('0.1') + toDecimal('0.2') AS v")
+        .unwrap();
+    assert_eq!(r.rows[0].values[0], serde_json::json!({"_decimal": "0.3"}));
+    let r = engine
+        .execute_cypher("RETURN toDecimal('19.99') * 3 AS v, toString(toDecimal('2.50')) AS s")
+        .unwrap();
+    assert_eq!(
+        r.rows[0].values[0],
+        serde_json::json!({"_decimal": "59.97"})
+    );
+    assert_eq!(r.rows[0].values[1], serde_json::json!("2.5"));
+    let err = engine
+        .execute_cypher("RETURN toDecimal('1.5') + 0.5 AS v")
+        .unwrap_err();
+    assert!(err.to_string().contains("ERR_DECIMAL_FLOAT_MIX"), "{err}");
+    let r = engine
+        .execute_cypher(
+            "RETURN toDecimal('1.10') = toDecimal('1.1') AS eq, toDecimal('2') = 2 AS eq_int",
+        )
+        .unwrap();
+    assert_eq!(r.rows[0].values[0], serde_json::json!(true));
+    assert_eq!(r.rows[0].values[1], serde_json::json!(true));
+}
+
+#[test]
+fn caller_row_limit_cuts_reads_in_the_plan_and_writes_after_they_apply() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_data_dir(ctx.path()).unwrap();
+
+    let written = {
+        let _rows = crate::executor::limit_rows(2);
+        engine
+            .execute_cypher("UNWIND range(1, 5) AS i CREATE (:Batch {i: i}) RETURN i")
+            .unwrap()
+    };
+    assert_eq!(written.rows.len(), 2);
+    let r = engine
+        .execute_cypher("MATCH (b:Batch) RETURN count(b) AS c")
+        .unwrap();
+    assert_eq!(r.rows[0].values[0], serde_json::json!(5));
+
+    let _rows = crate::executor::limit_rows(3);
+    let r = engine
+        .execute_cypher("MATCH (b:Batch) RETURN b.i AS i ORDER BY i DESC")
+        .unwrap();
+    let values: Vec<_> = r.rows.iter().map(|row| row.values[0].clone()).collect();
+    assert_eq!(values, [5, 4, 3].map(|i| serde_json::json!(i)));
+    let r = engine
+        .execute_cypher("MATCH (b:Batch) RETURN b.i AS i ORDER BY i LIMIT 1")
+        .unwrap();
+    assert_eq!(r.rows.len(), 1);
+}
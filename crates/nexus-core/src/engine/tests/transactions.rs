@@ -1,7 +1,8 @@
 //! Tests for transaction correctness and restart durability: UNWIND writes,
 //! property index persistence across restart, CALL IN TRANSACTIONS termination,
-//! explicit BEGIN/COMMIT index maintenance, relationship index self-heal, and
-//! UNWIND+MATCH+MERGE edge upsert.
+//! explicit BEGIN/COMMIT index maintenance, relationship index self-heal,
+//! UNWIND+MATCH+MERGE edge upsert, idle transaction / session reaping,
+//! operator transaction kills, the pipelined commit mode and commit hooks.
 
 use super::*;
 
@@ -455,6 +456,41 @@ fn rollback_undoes_executor_created_nodes() {
         .expect("write after rollback");
 }
 
+/// Nodes a rolled-back transaction created must leave no count-aggregate
+/// or TTL entry behind; those indexes are fed as the writes land.
+#[test]
+#[serial_test::serial]
+fn rollback_reverses_count_aggregate_and_ttl_entries() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE (:RBOrder {status: 'open', expires: 1000})")
+        .unwrap();
+    engine
+        .create_count_aggregate("rb_orders", "RBOrder", Some("status"))
+        .unwrap();
+    engine.create_ttl_policy("RBOrder", "expires").unwrap();
+    let tracked = |engine: &Engine| engine.indexes.ttl.stats(0).tracked;
+    assert_eq!(tracked(&engine), 1);
+
+    engine.execute_cypher("BEGIN TRANSACTION").expect("BEGIN");
+    engine
+        .execute_cypher(
+            "CREATE (:RBOrder {status: 'open', expires: 2000}), \
+             (:RBOrder {status: 'shipped', expires: 3000})",
+        )
+        .expect("CREATE in tx");
+    engine
+        .execute_cypher("ROLLBACK TRANSACTION")
+        .expect("ROLLBACK");
+
+    assert_eq!(
+        engine.count_aggregate("rb_orders"),
+        Some(vec![(serde_json::json!("open"), 1)])
+    );
+    assert_eq!(tracked(&engine), 1);
+}
+
 /// ISSUE #20: the O(degree) chain-walk warning must fire DURING the walk,
 /// the moment it crosses the 1000-hop threshold — including when the edge is
 /// eventually FOUND (the early return skipped the old post-loop warning
@@ -842,3 +878,480 @@ fn profile_over_call_subquery_parses_and_executes() {
         "profiled execution must return the 3 seed rows, got {profile:?}"
     );
 }
+
+/// Read-your-writes: every acknowledged autocommit write leaves a fresh
+/// executor snapshot behind, so a cloned executor (the HTTP lock-free read
+/// path) sees it and the consistency token advances past it. `MATCH ...
+/// CREATE` used to defer the refresh to the caller and leave the clone
+/// stale. Writes inside an explicit transaction only advance it on COMMIT.
+#[test]
+#[serial_test::serial]
+fn consistency_token_advances_with_each_visible_write() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+
+    engine.execute_cypher("CREATE (:ZZRyw {id: 1})").unwrap();
+    let after_create = engine.consistency_token();
+
+    engine
+        .execute_cypher("MATCH (a:ZZRyw {id: 1}) CREATE (a)-[:NEXT]->(:ZZRyw {id: 2})")
+        .unwrap();
+    let after_match_create = engine.consistency_token();
+    assert!(after_match_create > after_create);
+
+    let snapshot = engine.executor.clone();
+    let read = snapshot
+        .execute(&executor::Query {
+            cypher: "MATCH (n:ZZRyw) RETURN count(n) AS c".to_string(),
+            params: HashMap::new(),
+        })
+        .unwrap();
+    assert_eq!(read.rows[0].values[0].as_i64(), Some(2));
+
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    let in_tx = engine.consistency_token();
+    engine.execute_cypher("CREATE (:ZZRyw {id: 3})").unwrap();
+    assert_eq!(engine.consistency_token(), in_tx);
+    engine.execute_cypher("COMMIT TRANSACTION").unwrap();
+    assert!(engine.satisfies_consistency_token(in_tx + 1));
+
+    // A token ahead of the counter (minted before a restart) refreshes.
+    let ahead = engine.consistency_token() + 10;
+    engine.ensure_consistency_token(ahead).unwrap();
+    assert!(engine.consistency_token() > in_tx + 1);
+}
+
+fn session_timeouts(idle_transaction_secs: u64, session_secs: u64) -> EngineConfig {
+    EngineConfig {
+        session_timeouts: crate::session::SessionTimeoutConfig {
+            idle_transaction_timeout_secs: idle_transaction_secs,
+            session_timeout_secs: session_secs,
+        },
+        ..Default::default()
+    }
+}
+
+fn count_label(engine: &mut Engine, label: &str) -> Option<i64> {
+    let r = engine
+        .execute_cypher(&format!("MATCH (n:{label}) RETURN count(n) AS c"))
+        .expect("count");
+    r.rows[0].values[0].as_i64()
+}
+
+/// An explicit transaction with no statement past the idle limit is rolled
+/// back like a ROLLBACK would, the session survives, and a late COMMIT
+/// fails instead of committing.
+#[test]
+#[serial_test::serial]
+fn idle_transaction_is_rolled_back() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine =
+        Engine::with_data_dir_and_config(ctx.path(), session_timeouts(1, 3600)).unwrap();
+    let before = crate::session::reaper_metrics();
+
+    engine.execute_cypher("BEGIN TRANSACTION").expect("BEGIN");
+    engine
+        .execute_cypher("CREATE (:ReapIdle {id: 1})")
+        .expect("CREATE in tx");
+    assert!(engine.reap_idle_sessions().unwrap().is_empty());
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let reaped = engine.reap_idle_sessions().unwrap();
+    assert_eq!(reaped.len(), 1, "{reaped:?}");
+    assert_eq!(reaped[0].session_id, "default");
+    assert_eq!(
+        reaped[0].reason,
+        crate::session::ReapReason::IdleTransaction
+    );
+    assert!(reaped[0].rolled_back_transaction);
+    assert!(
+        crate::session::reaper_metrics().idle_transactions_rolled_back
+            > before.idle_transactions_rolled_back
+    );
+
+    assert!(!engine.in_explicit_transaction());
+    assert_eq!(count_label(&mut engine, "ReapIdle"), Some(0));
+    assert!(engine.execute_cypher("COMMIT TRANSACTION").is_err());
+    assert!(engine.reap_idle_sessions().unwrap().is_empty());
+}
+
+/// A session idle past the session limit is closed, rolling back its open
+/// transaction first even when the transaction limit is disabled.
+#[test]
+#[serial_test::serial]
+fn abandoned_session_is_closed_and_rolled_back() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_data_dir_and_config(ctx.path(), session_timeouts(0, 1)).unwrap();
+    let before = crate::session::reaper_metrics();
+
+    engine.execute_cypher("BEGIN TRANSACTION").expect("BEGIN");
+    engine
+        .execute_cypher("CREATE (:ReapAbandoned {id: 1})")
+        .expect("CREATE in tx");
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    // Expiry alone must not drop the open transaction unreaped.
+    engine.session_manager.cleanup_expired_sessions();
+    let reaped = engine.reap_idle_sessions().unwrap();
+    assert_eq!(reaped.len(), 1, "{reaped:?}");
+    assert_eq!(
+        reaped[0].reason,
+        crate::session::ReapReason::AbandonedSession
+    );
+    assert!(reaped[0].rolled_back_transaction);
+    let after = crate::session::reaper_metrics();
+    assert!(after.abandoned_sessions_closed > before.abandoned_sessions_closed);
+    assert!(after.idle_transactions_rolled_back > before.idle_transactions_rolled_back);
+
+    assert!(engine.session_manager.get_active_session_ids().is_empty());
+    assert_eq!(count_label(&mut engine, "ReapAbandoned"), Some(0));
+}
+
+/// An operator kill rolls the transaction back like ROLLBACK, keeps the
+/// session, and leaves nothing for a late COMMIT to commit.
+#[test]
+#[serial_test::serial]
+fn killed_transaction_is_rolled_back() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    assert!(engine.open_transactions().is_empty());
+
+    engine.execute_cypher("BEGIN TRANSACTION").expect("BEGIN");
+    engine
+        .execute_cypher("CREATE (:KillMe {id: 1})")
+        .expect("CREATE in tx");
+    let open = engine.open_transactions();
+    assert_eq!(open.len(), 1, "{open:?}");
+    assert_eq!(open[0].session_id, "default");
+
+    assert!(
+        engine
+            .kill_transaction(open[0].tx_id + 1)
+            .unwrap()
+            .is_none()
+    );
+    let killed = engine.kill_transaction(open[0].tx_id).unwrap();
+    assert_eq!(killed.as_ref().map(|tx| tx.tx_id), Some(open[0].tx_id));
+
+    assert!(engine.open_transactions().is_empty());
+    assert!(!engine.in_explicit_transaction());
+    assert_eq!(count_label(&mut engine, "KillMe"), Some(0));
+    assert!(engine.execute_cypher("COMMIT TRANSACTION").is_err());
+}
+
+/// A checkpoint lands in the log and the WAL report sees it.
+#[test]
+fn checkpoint_is_written_to_the_wal() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE (:Checkpointed {id: 1})")
+        .expect("CREATE");
+
+    let checkpoint = engine.checkpoint_wal().unwrap();
+    let info = engine.wal_info().unwrap();
+    assert_eq!(info.current_epoch, checkpoint.epoch);
+    assert!(info.path.ends_with("wal.log"), "{}", info.path);
+
+    let report = engine.cache_report();
+    assert!(report.page_cache_capacity <= report.page_cache_configured_capacity);
+    engine.flush_caches().unwrap();
+    assert_eq!(engine.cache_report().page_cache_pages, 0);
+}
+
+fn pipelined_commits() -> WritePipelineConfig {
+    WritePipelineConfig {
+        commit_mode: CommitMode::Pipelined,
+        store_flush_interval_ms: 20,
+    }
+}
+
+/// Wait until the background flusher has synced the stores past `lsn`.
+fn wait_for_store_flush(engine: &Engine, lsn: u64) {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while engine.write_pipeline_stats().store_flushed_lsn < lsn {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "store flusher never caught up: {:?}",
+            engine.write_pipeline_stats()
+        );
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+}
+
+/// In the pipelined mode COMMIT returns once the WAL is fsynced; the
+/// stores are synced afterwards and a marker records it.
+#[test]
+fn pipelined_commit_waits_for_the_wal_and_flushes_stores_behind_it() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .set_write_pipeline_config(pipelined_commits())
+        .unwrap();
+
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine
+        .create_node(vec!["Piped".to_string()], serde_json::json!({"id": 1}))
+        .unwrap();
+    let journalled = engine.write_pipeline_stats().wal_submitted_lsn;
+    assert!(journalled > 0);
+    engine.execute_cypher("COMMIT TRANSACTION").unwrap();
+
+    let stats = engine.write_pipeline_stats();
+    assert_eq!(stats.commit_mode, CommitMode::Pipelined);
+    assert!(stats.wal_durable_lsn >= journalled, "{stats:?}");
+
+    wait_for_store_flush(&engine, journalled);
+    let entries = wal::Wal::new(ctx.path().join("wal.log"))
+        .unwrap()
+        .recover()
+        .unwrap();
+    assert!(
+        entries
+            .iter()
+            .any(|entry| matches!(entry, wal::WalEntry::StoreFlush { .. }))
+    );
+    assert!(
+        engine
+            .verify_integrity(IntegrityScanLevel::Quick)
+            .unwrap()
+            .is_clean()
+    );
+}
+
+/// Entries journalled after the last store flush are what a crash can
+/// have lost: deletes are re-applied, missing creates are reported.
+#[test]
+fn changes_after_the_last_store_flush_are_replayed_or_reported() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .set_write_pipeline_config(pipelined_commits())
+        .unwrap();
+    let kept = engine
+        .create_node(vec!["Tail".to_string()], Value::Null)
+        .unwrap();
+    let deleted = engine
+        .create_node(vec!["Tail".to_string()], Value::Null)
+        .unwrap();
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine.execute_cypher("COMMIT TRANSACTION").unwrap();
+    wait_for_store_flush(&engine, engine.write_pipeline_stats().wal_submitted_lsn);
+    // Stop the flusher so no marker covers what follows.
+    engine
+        .set_write_pipeline_config(WritePipelineConfig::default())
+        .unwrap();
+
+    // A delete that reached the log but not the store, and a create
+    // whose record the crash lost.
+    engine
+        .write_wal_async(wal::WalEntry::DeleteNode { node_id: deleted })
+        .unwrap();
+    engine
+        .write_wal_async(wal::WalEntry::CreateNode {
+            node_id: 10_000,
+            label_bits: 1,
+        })
+        .unwrap();
+    assert!(!engine.storage.read_node(deleted).unwrap().is_deleted());
+
+    engine.replay_unflushed_store_tail().unwrap();
+    assert!(engine.storage.read_node(deleted).unwrap().is_deleted());
+    assert!(!engine.storage.read_node(kept).unwrap().is_deleted());
+
+    let report = engine.verify_integrity(IntegrityScanLevel::Quick).unwrap();
+    assert_eq!(report.issue_count, 1, "{report:?}");
+    assert_eq!(report.issues[0].component, "store_flush");
+    assert!(report.issues[0].detail.contains("node 10000"), "{report:?}");
+}
+
+/// A pipelined COMMIT journals images of the records it wrote, and
+/// recovery puts a Cypher create back even if its record and
+/// properties never reached the store.
+#[test]
+fn pipelined_commit_images_restore_cypher_writes() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .set_write_pipeline_config(pipelined_commits())
+        .unwrap();
+
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine
+        .execute_cypher("CREATE (:Imaged {name: 'kept'})")
+        .unwrap();
+    engine.execute_cypher("COMMIT TRANSACTION").unwrap();
+    wait_for_store_flush(&engine, engine.write_pipeline_stats().wal_submitted_lsn);
+    engine
+        .set_write_pipeline_config(WritePipelineConfig::default())
+        .unwrap();
+
+    let entries = wal::Wal::new(ctx.path().join("wal.log"))
+        .unwrap()
+        .recover()
+        .unwrap();
+    let image = entries
+        .iter()
+        .rev()
+        .find(|entry| matches!(entry, wal::WalEntry::NodeImage { .. }))
+        .cloned()
+        .expect("COMMIT journalled no node image");
+    let wal::WalEntry::NodeImage { node_id, .. } = image else {
+        unreachable!()
+    };
+
+    // The crash lost the record and its properties after the image
+    // reached the log.
+    engine.write_wal_async(image).unwrap();
+    let mut lost = engine.storage.read_node(node_id).unwrap();
+    lost.mark_deleted();
+    engine.storage.write_node(node_id, &lost).unwrap();
+    engine
+        .storage
+        .update_node_properties(node_id, Value::Null)
+        .unwrap();
+
+    engine.replay_unflushed_store_tail().unwrap();
+    assert!(!engine.storage.read_node(node_id).unwrap().is_deleted());
+    assert_eq!(
+        engine.storage.load_node_properties(node_id).unwrap(),
+        Some(serde_json::json!({"name": "kept"}))
+    );
+    let result = engine
+        .execute_cypher("MATCH (n:Imaged) RETURN n.name")
+        .unwrap();
+    assert_eq!(result.rows.len(), 1);
+}
+
+/// Commit hooks see each auto-commit write as it lands, get an explicit
+/// transaction's statements once at COMMIT (nothing on ROLLBACK), and a
+/// refusing before-commit hook rolls the explicit transaction, or the
+/// auto-commit statement, back.
+#[test]
+#[serial_test::serial]
+fn commit_hooks_receive_mutation_sets() {
+    use crate::transaction::Mutation;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    let seen: Arc<parking_lot::Mutex<Vec<Vec<Mutation>>>> = Arc::default();
+    let sink = Arc::clone(&seen);
+    engine.commit_hooks().on_after_commit(move |tx, _| {
+        sink.lock().push(tx.mutations.iter().cloned().collect());
+    });
+    let take = || std::mem::take(&mut *seen.lock());
+
+    engine.execute_cypher("CREATE (:Hooked {id: 1})").unwrap();
+    let batches = take();
+    assert!(
+        batches
+            .iter()
+            .flatten()
+            .any(|m| matches!(m, Mutation::NodeCreated { .. })),
+        "{batches:?}"
+    );
+
+    engine
+        .execute_cypher("MATCH (n:Hooked {id: 1}) SET n.name = 'a'")
+        .unwrap();
+    assert!(
+        take()
+            .iter()
+            .flatten()
+            .any(|m| matches!(m, Mutation::NodeUpdated { .. }))
+    );
+
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine.execute_cypher("CREATE (:Hooked {id: 2})").unwrap();
+    engine.execute_cypher("CREATE (:Hooked {id: 3})").unwrap();
+    assert!(take().is_empty(), "held back until COMMIT");
+    engine.execute_cypher("COMMIT TRANSACTION").unwrap();
+    let batches = take();
+    assert_eq!(batches.len(), 1, "{batches:?}");
+    let created = batches[0]
+        .iter()
+        .filter(|m| matches!(m, Mutation::NodeCreated { .. }))
+        .count();
+    assert_eq!(created, 2, "{batches:?}");
+
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine.execute_cypher("CREATE (:Hooked {id: 4})").unwrap();
+    engine.execute_cypher("ROLLBACK TRANSACTION").unwrap();
+    assert!(take().is_empty());
+
+    let veto = engine
+        .commit_hooks()
+        .on_before_commit(|_| Err(crate::Error::transaction("rejected by validation hook")));
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine.execute_cypher("CREATE (:Hooked {id: 5})").unwrap();
+    let err = engine.execute_cypher("COMMIT TRANSACTION").unwrap_err();
+    assert!(
+        err.to_string().contains("rejected by validation hook"),
+        "{err}"
+    );
+    assert!(take().is_empty());
+    assert!(!engine.in_explicit_transaction());
+
+    // An auto-commit write is vetoed before it is left in place.
+    let err = engine
+        .execute_cypher("CREATE (:Hooked {id: 6})")
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("rejected by validation hook"),
+        "{err}"
+    );
+    assert!(take().is_empty());
+    assert!(!engine.in_explicit_transaction());
+    assert!(engine.commit_hooks().remove(veto));
+
+    let ids = engine
+        .execute_cypher("MATCH (n:Hooked) RETURN n.id AS id ORDER BY id")
+        .unwrap()
+        .rows
+        .iter()
+        .filter_map(|row| row.values[0].as_i64())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![1, 2, 3]);
+}
+
+/// An open explicit transaction only holds back its own statements:
+/// the TTL sweeper committing meanwhile is delivered at once and
+/// survives the transaction's ROLLBACK.
+#[test]
+#[serial_test::serial]
+fn commit_hooks_defer_only_the_transaction_session() {
+    use crate::transaction::Mutation;
+
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE (:HookTtl {expiresAt: 1000})")
+        .unwrap();
+    engine.create_ttl_policy("HookTtl", "expiresAt").unwrap();
+    let seen: Arc<parking_lot::Mutex<Vec<Mutation>>> = Arc::default();
+    let sink = Arc::clone(&seen);
+    engine.commit_hooks().on_after_commit(move |tx, _| {
+        sink.lock().extend(tx.mutations.iter().cloned());
+    });
+
+    engine.execute_cypher("BEGIN TRANSACTION").unwrap();
+    engine.execute_cypher("CREATE (:Hooked {id: 6})").unwrap();
+    let report = engine.sweep_expired_nodes(2_000, 100).unwrap();
+    assert_eq!(report.deleted, 1);
+    let delivered = std::mem::take(&mut *seen.lock());
+    assert!(
+        delivered
+            .iter()
+            .any(|m| matches!(m, Mutation::NodeDeleted { .. })),
+        "{delivered:?}"
+    );
+    assert!(
+        !delivered
+            .iter()
+            .any(|m| matches!(m, Mutation::NodeCreated { .. })),
+        "{delivered:?}"
+    );
+
+    engine.execute_cypher("ROLLBACK TRANSACTION").unwrap();
+    assert!(seen.lock().is_empty());
+}
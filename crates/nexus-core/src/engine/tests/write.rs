@@ -1,5 +1,6 @@
 //! Regression tests for write-path correctness: multi-hop bound-variable
-//! CREATE chains, DETACH DELETE, and the typed-list constraint API roundtrip.
+//! CREATE chains, DETACH DELETE, the typed-list constraint API roundtrip,
+//! and dry-run analysis of write statements.
 
 use super::*;
 
@@ -585,3 +586,489 @@ fn merge_relationship_standalone_creates_edge_idempotently() {
         "edge must terminate at the GB node with id 2"
     );
 }
+
+#[test]
+fn relationship_invert_and_retype_procedures_rewrite_in_batches() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (a:P {id: 1}), (b:P {id: 2}), (c:P {id: 3}), \
+             (a)-[:OWNS {since: 2020}]->(b), (a)-[:OWNS {since: 2021}]->(c), \
+             (b)-[:OWNS {since: 2020}]->(c)",
+        )
+        .unwrap();
+
+    let inverted = engine
+        .execute_cypher("CALL db.relationships.invert('OWNS', 2)")
+        .expect("invert must succeed");
+    assert_eq!(inverted.columns, vec!["updated", "batches"]);
+    assert_eq!(inverted.rows[0].values[0].as_u64(), Some(3));
+    assert_eq!(inverted.rows[0].values[1].as_u64(), Some(2));
+
+    let from_c = engine
+        .execute_cypher("MATCH (x:P {id: 3})-[r:OWNS]->(y:P) RETURN count(r) AS c")
+        .unwrap();
+    assert_eq!(from_c.rows[0].values[0].as_i64(), Some(2));
+    let props = engine
+        .execute_cypher("MATCH (:P {id: 2})-[r:OWNS]->(:P {id: 1}) RETURN r.since AS s")
+        .unwrap();
+    assert_eq!(props.rows[0].values[0].as_i64(), Some(2020));
+
+    let retyped = engine
+        .execute_cypher("CALL db.relationships.retype('OWNS', 'OWNED_SINCE_2020', {since: 2020})")
+        .expect("retype must succeed");
+    assert_eq!(retyped.rows[0].values[0].as_u64(), Some(2));
+    let remaining = engine
+        .execute_cypher("MATCH ()-[r:OWNS]->() RETURN count(r) AS c")
+        .unwrap();
+    assert_eq!(remaining.rows[0].values[0].as_i64(), Some(1));
+    let moved = engine
+        .execute_cypher("MATCH ()-[r:OWNED_SINCE_2020]->() RETURN count(r) AS c")
+        .unwrap();
+    assert_eq!(moved.rows[0].values[0].as_i64(), Some(2));
+
+    let err = engine
+        .execute_cypher("CALL db.relationships.invert('OWNS', 0)")
+        .unwrap_err();
+    assert!(err.to_string().contains("ERR_INVALID_ARG_VALUE"), "{err}");
+}
+
+/// A batch that fails part-way is put back: the relationships it
+/// already rewrote keep their ids, types and properties.
+#[test]
+fn relationship_retype_undoes_a_failed_batch() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (a:P {id: 1}), (b:P {id: 2}), \
+             (a)-[:LINK {weight: 1}]->(b), (b)-[:LINK]->(a)",
+        )
+        .unwrap();
+    engine
+        .execute_cypher("CREATE CONSTRAINT FOR ()-[r:WEIGHTED]-() REQUIRE r.weight IS NOT NULL")
+        .unwrap();
+    let before = engine
+        .execute_cypher("MATCH ()-[r:LINK]->() RETURN id(r) AS id, r.weight AS w ORDER BY id")
+        .unwrap();
+    assert_eq!(before.rows.len(), 2);
+
+    let err = engine
+        .execute_cypher("CALL db.relationships.retype('LINK', 'WEIGHTED')")
+        .unwrap_err();
+    assert!(err.to_string().contains("weight"), "{err}");
+
+    let after = engine
+        .execute_cypher("MATCH ()-[r:LINK]->() RETURN id(r) AS id, r.weight AS w ORDER BY id")
+        .unwrap();
+    let values = |rows: &[crate::executor::Row]| -> Vec<Vec<serde_json::Value>> {
+        rows.iter().map(|row| row.values.clone()).collect()
+    };
+    assert_eq!(values(&after.rows), values(&before.rows));
+    let moved = engine
+        .execute_cypher("MATCH ()-[r:WEIGHTED]->() RETURN count(r) AS c")
+        .unwrap();
+    assert_eq!(moved.rows[0].values[0].as_i64(), Some(0));
+}
+
+#[test]
+fn merge_nodes_procedure_folds_duplicates_into_first_node() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (a:Person {name: 'Ann', email: 'ann@a.com'}), \
+             (b:Customer {name: 'Ann', email: 'ann@b.com'}), (c:City {name: 'Oslo'}), \
+             (a)-[:LIVES_IN]->(c), (b)-[:LIVES_IN {since: 2020}]->(c), (b)-[:KNOWS]->(a)",
+        )
+        .unwrap();
+    let id_of = |engine: &mut Engine, label: &str| {
+        engine
+            .execute_cypher(&format!("MATCH (n:{label}) RETURN id(n) AS id"))
+            .unwrap()
+            .rows[0]
+            .values[0]
+            .as_u64()
+            .unwrap()
+    };
+    let a = id_of(&mut engine, "Person");
+    let b = id_of(&mut engine, "Customer");
+
+    let bad = engine
+        .execute_cypher(&format!(
+            "CALL nexus.merge.nodes([{a}, {b}], {{properties: 'newest'}})"
+        ))
+        .unwrap_err();
+    assert!(bad.to_string().contains("ERR_INVALID_ARG_VALUE"), "{bad}");
+
+    let merged = engine
+        .execute_cypher(&format!(
+            "CALL nexus.merge.nodes([{a}, {b}, {a}], {{relationships: 'merge'}})"
+        ))
+        .expect("merge must succeed");
+    assert_eq!(
+        merged.columns,
+        vec![
+            "nodeId",
+            "merged",
+            "relationshipsRewired",
+            "relationshipsCollapsed"
+        ]
+    );
+    let row = &merged.rows[0].values;
+    assert_eq!(row[0].as_u64(), Some(a));
+    assert_eq!(row[1].as_u64(), Some(1));
+    assert_eq!(row[2].as_u64(), Some(2), "LIVES_IN and KNOWS moved off b");
+    assert_eq!(row[3].as_u64(), Some(1), "parallel LIVES_IN collapsed");
+
+    let (labels, props) = engine.node_labels_and_properties(a).unwrap();
+    assert_eq!(labels, vec!["Customer", "Person"]);
+    assert_eq!(props["name"], serde_json::json!("Ann"));
+    assert_eq!(
+        props["email"],
+        serde_json::json!(["ann@a.com", "ann@b.com"])
+    );
+    assert!(
+        engine.diff_nodes(a, b).is_err(),
+        "duplicate must be deleted"
+    );
+
+    let lives_in = engine
+        .execute_cypher("MATCH (:Person)-[r:LIVES_IN]->(:City) RETURN r.since AS s")
+        .unwrap();
+    assert_eq!(lives_in.rows.len(), 1);
+    assert_eq!(lives_in.rows[0].values[0].as_i64(), Some(2020));
+    let loops = engine
+        .execute_cypher("MATCH (p:Person)-[r:KNOWS]->(p) RETURN count(r) AS c")
+        .unwrap();
+    assert_eq!(loops.rows[0].values[0].as_i64(), Some(1));
+}
+
+/// A merge that fails after moving some relationships is undone: the
+/// duplicate, the surviving node and every relationship are back as
+/// they were, under their own ids.
+#[test]
+fn merge_nodes_failure_leaves_the_graph_untouched() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher(
+            "CREATE (a:Dup {k: 1}), (b:Dup {k: 2}), (c:Hub), (b)-[:TAGGED]->(c), \
+             (a)-[:RATED {score: 1}]->(c), (b)-[:RATED {score: 2}]->(c)",
+        )
+        .unwrap();
+    // Collapsing the two RATED relationships combines their scores into
+    // a list, which this constraint rejects after TAGGED was moved.
+    engine
+        .add_rel_property_type_constraint(
+            "RATED",
+            "score",
+            crate::constraints::ScalarType::Integer,
+            None,
+        )
+        .unwrap();
+    let snapshot = |engine: &mut Engine| -> Vec<Vec<serde_json::Value>> {
+        engine
+            .execute_cypher(
+                "MATCH (x)-[r]->(y) RETURN id(r) AS id, type(r) AS t, x.k AS src, \
+                 r.score AS score ORDER BY id",
+            )
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|row| row.values)
+            .collect()
+    };
+    let before = snapshot(&mut engine);
+    let ids = engine
+        .execute_cypher("MATCH (n:Dup) RETURN id(n) AS id ORDER BY n.k")
+        .unwrap();
+    let (a, b) = (
+        ids.rows[0].values[0].as_u64().unwrap(),
+        ids.rows[1].values[0].as_u64().unwrap(),
+    );
+
+    let err = engine
+        .execute_cypher(&format!(
+            "CALL nexus.merge.nodes([{a}, {b}], {{relationships: 'merge'}})"
+        ))
+        .unwrap_err();
+    assert!(err.to_string().contains("PROPERTY_TYPE"), "{err}");
+
+    assert_eq!(snapshot(&mut engine), before);
+    let (labels, props) = engine.node_labels_and_properties(a).unwrap();
+    assert_eq!(labels, vec!["Dup"]);
+    assert_eq!(
+        props,
+        serde_json::Map::from_iter([("k".to_string(), serde_json::json!(1))])
+    );
+    let (_, props) = engine.node_labels_and_properties(b).unwrap();
+    assert_eq!(props["k"], serde_json::json!(2));
+    let dups = engine
+        .execute_cypher("MATCH (n:Dup) RETURN count(n) AS c")
+        .unwrap();
+    assert_eq!(dups.rows[0].values[0].as_i64(), Some(2));
+
+    let unknown = engine
+        .execute_cypher(&format!("CALL nexus.merge.nodes([{a}]) YIELD merges"))
+        .unwrap_err();
+    assert!(
+        unknown.to_string().contains("Unknown procedure output"),
+        "{unknown}"
+    );
+}
+
+#[test]
+fn dry_run_estimates_and_checks_constraints_without_writing() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE CONSTRAINT ON (n:Acct) ASSERT n.email IS UNIQUE")
+        .unwrap();
+    let first = engine
+        .create_node(
+            vec!["Acct".to_string()],
+            serde_json::json!({"email": "a@x"}),
+        )
+        .unwrap();
+    for email in ["b@x", "c@x"] {
+        engine
+            .create_node(
+                vec!["Acct".to_string()],
+                serde_json::json!({"email": email}),
+            )
+            .unwrap();
+    }
+
+    let mut params = std::collections::HashMap::new();
+    params.insert("e".to_string(), serde_json::json!("a@x"));
+    let report = engine
+        .dry_run("CREATE (:Acct {email: $e})", params)
+        .unwrap();
+    assert!(report.is_write);
+    assert_eq!(report.input_rows, Some(1));
+    assert_eq!(report.estimates.nodes_created, 1);
+    assert_eq!(report.estimates.properties_set, 1);
+    assert_eq!(report.constraint_violations.len(), 1);
+    let violation = &report.constraint_violations[0];
+    assert_eq!(violation.kind, "UNIQUENESS");
+    assert_eq!(violation.conflicting_id, Some(first));
+
+    let report = engine
+        .dry_run("MATCH (n:Acct) SET n.tier = 'gold'", Default::default())
+        .unwrap();
+    assert_eq!(report.input_rows, Some(3));
+    assert_eq!(report.estimates.properties_set, 3);
+    assert!(report.constraint_violations.is_empty());
+
+    let report = engine
+        .dry_run("MATCH (n:Acct) RETURN n", Default::default())
+        .unwrap();
+    assert!(!report.is_write);
+
+    // Nothing was written.
+    let read = engine
+        .execute_cypher("MATCH (n:Acct) WHERE n.tier IS NOT NULL RETURN count(n) AS c")
+        .unwrap();
+    assert_eq!(read.rows[0].values[0].as_u64(), Some(0));
+    let read = engine
+        .execute_cypher("MATCH (n:Acct) RETURN count(n) AS c")
+        .unwrap();
+    assert_eq!(read.rows[0].values[0].as_u64(), Some(3));
+}
+
+#[test]
+fn dry_run_checks_relationship_endpoint_constraints() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE (:DrPerson {id: 1}), (:DrCompany {id: 2})")
+        .unwrap();
+    engine
+        .add_rel_endpoint_constraint("DR_WORKS_AT", Some("DrPerson"), Some("DrCompany"))
+        .unwrap();
+
+    // Both endpoints created by the statement.
+    let report = engine
+        .dry_run(
+            "CREATE (:DrPerson)-[:DR_WORKS_AT]->(:DrPerson)",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(report.constraint_violations.len(), 1);
+    assert_eq!(
+        report.constraint_violations[0].kind,
+        "RELATIONSHIP_ENDPOINT"
+    );
+
+    // Endpoints bound by MATCH are checked per row.
+    let report = engine
+        .dry_run(
+            "MATCH (c:DrCompany {id: 2}), (p:DrPerson {id: 1}) CREATE (c)-[:DR_WORKS_AT]->(p)",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(report.constraint_violations.len(), 1);
+    let report = engine
+        .dry_run(
+            "MATCH (c:DrCompany {id: 2}), (p:DrPerson {id: 1}) CREATE (c)<-[:DR_WORKS_AT]-(p)",
+            Default::default(),
+        )
+        .unwrap();
+    assert!(report.constraint_violations.is_empty());
+    engine
+        .execute_cypher(
+            "MATCH (c:DrCompany {id: 2}), (p:DrPerson {id: 1}) CREATE (c)<-[:DR_WORKS_AT]-(p)",
+        )
+        .expect("the write the dry run accepted succeeds");
+}
+
+#[test]
+fn dry_run_checks_set_and_remove_against_matched_rows() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .execute_cypher("CREATE CONSTRAINT ON (n:Acct) ASSERT n.email IS UNIQUE")
+        .unwrap();
+    engine
+        .execute_cypher("CREATE CONSTRAINT FOR ()-[r:PAYS]-() REQUIRE r.amount IS NOT NULL")
+        .unwrap();
+    let first = engine
+        .create_node(
+            vec!["Acct".to_string()],
+            serde_json::json!({"email": "a@x"}),
+        )
+        .unwrap();
+    engine
+        .execute_cypher("CREATE (:Acct {email: 'b@x'})-[:PAYS {amount: 5}]->(:Shop)")
+        .unwrap();
+
+    // Setting another node's value is a uniqueness violation; setting a
+    // node's own value is not.
+    let report = engine
+        .dry_run(
+            "MATCH (n:Acct {email: 'b@x'}) SET n.email = 'a@x'",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(report.constraint_violations.len(), 1);
+    assert_eq!(report.constraint_violations[0].kind, "UNIQUENESS");
+    assert_eq!(report.constraint_violations[0].conflicting_id, Some(first));
+    let report = engine
+        .dry_run(
+            "MATCH (n:Acct {email: 'a@x'}) SET n += {email: 'a@x', tier: 1}",
+            Default::default(),
+        )
+        .unwrap();
+    assert!(report.constraint_violations.is_empty());
+
+    // Adding the constrained label to a node that clashes.
+    let report = engine
+        .dry_run(
+            "MATCH (s:Shop) SET s.email = 'a@x' SET s:Acct",
+            Default::default(),
+        )
+        .unwrap();
+    assert_eq!(report.constraint_violations.len(), 1);
+
+    let report = engine
+        .dry_run("MATCH ()-[r:PAYS]->() REMOVE r.amount", Default::default())
+        .unwrap();
+    assert_eq!(report.constraint_violations.len(), 1);
+    assert_eq!(report.estimates.properties_removed, 1);
+
+    // Values computed per row and MERGE are reported as not checked.
+    let report = engine
+        .dry_run(
+            "MATCH (n:Acct) SET n.email = n.email + '.old'",
+            Default::default(),
+        )
+        .unwrap();
+    assert!(
+        report
+            .warnings
+            .iter()
+            .any(|w| w.contains("SET n.email not checked")),
+        "{:?}",
+        report.warnings
+    );
+    let report = engine
+        .dry_run(
+            "MERGE (n:Acct {email: 'z@x'}) ON MATCH SET n.email = 'a@x'",
+            Default::default(),
+        )
+        .unwrap();
+    assert!(
+        report.warnings.iter().any(|w| w.contains("MERGE")),
+        "{:?}",
+        report.warnings
+    );
+
+    // Nothing was written.
+    let read = engine
+        .execute_cypher("MATCH (n:Acct) RETURN n.email AS e ORDER BY e")
+        .unwrap();
+    let emails: Vec<_> = read.rows.iter().map(|r| r.values[0].clone()).collect();
+    assert_eq!(
+        emails,
+        vec![serde_json::json!("a@x"), serde_json::json!("b@x")]
+    );
+    let read = engine
+        .execute_cypher("MATCH ()-[r:PAYS]->() RETURN r.amount AS a")
+        .unwrap();
+    assert_eq!(read.rows[0].values[0], serde_json::json!(5));
+}
+
+#[test]
+fn property_history_keeps_bounded_previous_values() {
+    let ctx = crate::testing::TestContext::new();
+    let mut engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+    engine
+        .track_property_history("Invoice", "status", 2)
+        .unwrap();
+    engine
+        .execute_cypher("CREATE (o:Invoice {id: 1, status: 'new', note: 'x'})")
+        .unwrap();
+
+    engine.set_acting_user(Some("alice".to_string()));
+    for status in ["paid", "shipped", "delivered"] {
+        engine
+            .execute_cypher(&format!(
+                "MATCH (o:Invoice {{id: 1}}) SET o.status = '{status}'"
+            ))
+            .unwrap();
+    }
+    engine.set_acting_user(None);
+    // Untracked properties and unchanged values leave no history.
+    engine
+        .execute_cypher("MATCH (o:Invoice {id: 1}) SET o.note = 'y', o.status = 'delivered'")
+        .unwrap();
+    engine
+        .execute_cypher("MATCH (o:Invoice {id: 1}) REMOVE o.status")
+        .unwrap();
+
+    let result = engine
+        .execute_cypher(
+            "MATCH (o:Invoice {id: 1}) RETURN history(o.status) AS status, history(o.note) AS note",
+        )
+        .unwrap();
+    let status = result.rows[0].values[0].as_array().unwrap();
+    let values: Vec<&Value> = status.iter().map(|c| &c["value"]).collect();
+    assert_eq!(
+        values,
+        [
+            &serde_json::json!("shipped"),
+            &serde_json::json!("delivered")
+        ]
+    );
+    assert_eq!(status[0]["user"], serde_json::json!("alice"));
+    assert_eq!(status[1]["user"], Value::Null);
+    assert!(status[1]["timestamp"].as_i64().unwrap() >= status[0]["timestamp"].as_i64().unwrap());
+    assert_eq!(result.rows[0].values[1], serde_json::json!([]));
+
+    assert!(matches!(
+        engine.execute_cypher("MATCH (o:Invoice) RETURN history(o.status + 'x')"),
+        Err(Error::CypherExecution(msg)) if msg.starts_with("ERR_INVALID_ARG_TYPE")
+    ));
+}
@@ -2,9 +2,18 @@
 //! Extracted from `engine/mod.rs`.
 
 use super::Engine;
-use crate::{Error, Result, executor, transaction};
+use crate::{Error, Result, executor, session, transaction};
 
 impl Engine {
+    /// Whether the autocommit `"default"` session — the only session the
+    /// HTTP and RPC transports use — has an open explicit transaction.
+    pub fn in_explicit_transaction(&self) -> bool {
+        self.session_manager
+            .get_session(&"default".to_string())
+            .map(|session| session.has_active_transaction())
+            .unwrap_or(false)
+    }
+
     /// Execute transaction commands (BEGIN, COMMIT, ROLLBACK)
     /// Requires a session_id to track transaction context across queries
     pub(super) fn execute_transaction_commands(
@@ -16,177 +25,20 @@ impl Engine {
         // In a full implementation, session_id would come from HTTP headers or connection context
         let session_id = session_id.unwrap_or("default");
 
+        // Settle idle limits first so a COMMIT arriving after its
+        // transaction timed out fails instead of committing.
+        self.reap_idle_sessions()?;
+
         for clause in &ast.clauses {
             match clause {
                 executor::parser::Clause::BeginTransaction => {
-                    // Get or create session
-                    let mut session = self
-                        .session_manager
-                        .get_or_create_session(session_id.to_string());
-
-                    // Begin transaction for this session
-                    session.begin_transaction()?;
-
-                    // ISSUE #15: capture storage watermarks so COMMIT can
-                    // index exactly the entities this transaction creates
-                    // (single-writer model — no concurrent id allocation).
-                    session.tx_begin_node_watermark = self.storage.node_count();
-                    session.tx_begin_rel_watermark = self.storage.relationship_count();
-
-                    // Update session in manager
-                    self.session_manager.update_session(session);
+                    self.begin_session_transaction(session_id)?;
                 }
                 executor::parser::Clause::CommitTransaction => {
-                    // Get session
-                    let mut session = self
-                        .session_manager
-                        .get_session(&session_id.to_string())
-                        .ok_or_else(|| {
-                            Error::transaction(format!(
-                                "Session {} not found or expired",
-                                session_id
-                            ))
-                        })?;
-
-                    // Apply pending index updates in batch before commit (Phase 1 optimization)
-                    self.apply_pending_index_updates(&mut session)?;
-
-                    // 3.4: External-id reservations are now permanent — clear
-                    // the pending list so no stale entries carry over.
-                    self.pending_external_ids.clear();
-
-                    // ISSUE #15: scoped index maintenance over the session's
-                    // own write set (created nodes + relationships) replaces
-                    // the previous per-COMMIT `rebuild_indexes_from_storage()`
-                    // full O(N) scan, so commit cost no longer scales with
-                    // total graph size. The typed property index — the part
-                    // the rebuild was load-bearing for — is maintained per
-                    // created node via `maintain_indexed_properties`.
-                    self.apply_committed_entity_index_updates(&session)?;
-
-                    // Commit transaction
-                    session.commit_transaction()?;
-
-                    // Flush storage to ensure durability
-                    self.storage.flush()?;
-
-                    // Refresh executor to see the updated indexes
-                    self.refresh_executor()?;
-
-                    // Update session in manager
-                    self.session_manager.update_session(session);
+                    self.commit_session_transaction(session_id)?;
                 }
                 executor::parser::Clause::RollbackTransaction => {
-                    // Get session
-                    let mut session = self
-                        .session_manager
-                        .get_session(&session_id.to_string())
-                        .ok_or_else(|| {
-                            Error::transaction(format!(
-                                "Session {} not found or expired",
-                                session_id
-                            ))
-                        })?;
-
-                    // CRITICAL: Clone created_nodes list before marking as deleted
-                    // because get_session may return a cloned session.
-                    //
-                    // Union with the session's storage watermark range: a
-                    // standalone CREATE inside an explicit tx routes through
-                    // the EXECUTOR write path, which does not report into
-                    // `created_nodes` — the watermark range (captured at
-                    // BEGIN, exact under the single-writer model) covers
-                    // those, same source as the #15 scoped-commit fix.
-                    // Without it, ROLLBACK silently kept executor-created
-                    // entities. Gated on an active transaction so a stray
-                    // ROLLBACK can never sweep ids from a stale watermark.
-                    let mut nodes_to_delete = session.created_nodes.clone();
-                    let mut rels_to_delete = session.created_relationships.clone();
-                    if session.has_active_transaction() {
-                        nodes_to_delete
-                            .extend(session.tx_begin_node_watermark..self.storage.node_count());
-                        rels_to_delete.extend(
-                            session.tx_begin_rel_watermark..self.storage.relationship_count(),
-                        );
-                        nodes_to_delete.sort_unstable();
-                        nodes_to_delete.dedup();
-                        rels_to_delete.sort_unstable();
-                        rels_to_delete.dedup();
-                    }
-
-                    // Remove nodes from index and mark as deleted in storage BEFORE rollback
-                    // This ensures we clean up nodes that were written to storage (mmap writes immediately)
-                    for node_id in &nodes_to_delete {
-                        // First, mark as deleted in storage (this prevents reads from returning the node)
-                        if let Err(e) = self.storage.delete_node(*node_id) {
-                            tracing::warn!("Failed to delete node {} from storage: {}", node_id, e);
-                        }
-
-                        // Read node properties before deletion to remove from property index
-                        if let Ok(Some(properties)) = self.storage.load_node_properties(*node_id) {
-                            if let serde_json::Value::Object(props) = properties {
-                                let property_index = self.cache.property_index_manager();
-                                for prop_name in props.keys() {
-                                    if let Err(e) =
-                                        property_index.remove_property(prop_name, *node_id)
-                                    {
-                                        // Property index may not exist for this property, ignore error
-                                        let _ = e;
-                                    }
-                                }
-                            }
-                        }
-
-                        // Remove from label index AFTER marking as deleted
-                        // remove_node removes the node from all label bitmaps
-                        if let Err(e) = self.indexes.label_index.remove_node(*node_id) {
-                            tracing::warn!(
-                                "Failed to remove node {} from label index: {}",
-                                node_id,
-                                e
-                            );
-                        }
-                    }
-
-                    // Mark all relationships created during this transaction as deleted
-                    for rel_id in &rels_to_delete {
-                        if let Err(e) = self.storage.delete_rel(*rel_id) {
-                            tracing::warn!(
-                                "Failed to delete relationship {} from storage: {}",
-                                rel_id,
-                                e
-                            );
-                        }
-                    }
-
-                    // Flush storage to ensure consistency (must be done before rollback)
-                    if let Err(e) = self.storage.flush() {
-                        tracing::warn!("Failed to flush storage: {}", e);
-                    }
-
-                    // 3.4: Undo any external-id reservations made during
-                    // this transaction before the storage records are
-                    // deleted, so the catalog index stays consistent.
-                    self.rollback_external_id_reservations();
-
-                    // Rollback transaction (abort the transaction)
-                    session.rollback_transaction()?;
-
-                    // Clear tracking lists after rollback
-                    session.created_nodes.clear();
-                    session.created_relationships.clear();
-                    // Clear pending index updates (they should not be applied on rollback)
-                    session.pending_index_updates.clear();
-
-                    // Update session in manager BEFORE refreshing executor
-                    // This ensures the session state is saved before executor refresh
-                    self.session_manager.update_session(session);
-
-                    // Refresh executor to see the updated indexes
-                    // Note: We don't rebuild indexes here because we've already removed
-                    // nodes from indexes manually above. Rebuilding would be redundant and
-                    // could potentially reintroduce deleted nodes if there's a timing issue.
-                    self.refresh_executor()?;
+                    self.abort_session_transaction(session_id)?;
                 }
                 // phase6_opencypher-advanced-types §5 — savepoint
                 // lifecycle statements. All three require an active
@@ -284,4 +136,267 @@ impl Engine {
             }],
         ))
     }
+
+    /// Open an explicit transaction on `session_id` (`BEGIN`).
+    pub(super) fn begin_session_transaction(&mut self, session_id: &str) -> Result<()> {
+        // Get or create session
+        let mut session = self
+            .session_manager
+            .get_or_create_session(session_id.to_string());
+
+        // Begin transaction for this session
+        session.begin_transaction()?;
+        // Statements commit storage transactions of their own;
+        // hold this session's mutations back for its COMMIT
+        // (see `transaction::hooks`).
+        self.transaction_manager
+            .read()
+            .commit_hooks()
+            .begin_deferral(session_id);
+
+        // ISSUE #15: capture storage watermarks so COMMIT can
+        // index exactly the entities this transaction creates
+        // (single-writer model — no concurrent id allocation).
+        session.tx_begin_node_watermark = self.storage.node_count();
+        session.tx_begin_rel_watermark = self.storage.relationship_count();
+
+        // Update session in manager
+        self.session_manager.update_session(session);
+        Ok(())
+    }
+
+    /// Commit `session_id`'s explicit transaction (`COMMIT`). A commit
+    /// refused by a hook rolls the transaction back and returns the
+    /// hook's error.
+    pub(super) fn commit_session_transaction(&mut self, session_id: &str) -> Result<()> {
+        // Get session
+        let mut session = self
+            .session_manager
+            .get_session(&session_id.to_string())
+            .ok_or_else(|| {
+                Error::transaction(format!("Session {} not found or expired", session_id))
+            })?;
+
+        // Apply pending index updates in batch before commit (Phase 1 optimization)
+        self.apply_pending_index_updates(&mut session)?;
+
+        // 3.4: External-id reservations are now permanent — clear
+        // the pending list so no stale entries carry over.
+        self.pending_external_ids.clear();
+
+        // ISSUE #15: scoped index maintenance over the session's
+        // own write set (created nodes + relationships) replaces
+        // the previous per-COMMIT `rebuild_indexes_from_storage()`
+        // full O(N) scan, so commit cost no longer scales with
+        // total graph size. The typed property index — the part
+        // the rebuild was load-bearing for — is maintained per
+        // created node via `maintain_indexed_properties`.
+        self.apply_committed_entity_index_updates(&session)?;
+
+        // Commit transaction, handing the commit hooks every
+        // mutation its statements made. A refused commit is
+        // rolled back like an explicit ROLLBACK.
+        let deferred = self
+            .transaction_manager
+            .read()
+            .commit_hooks()
+            .end_deferral(session_id);
+        let mut mutations = transaction::MutationSet::default();
+        if let Some(tx) = session.active_transaction.as_mut() {
+            tx.mutations.extend(deferred);
+            mutations = tx.mutations.clone();
+        }
+        if let Err(e) = session.commit_transaction() {
+            self.rollback_session_transaction(&mut session)?;
+            self.session_manager.update_session(session);
+            self.refresh_executor()?;
+            return Err(e);
+        }
+
+        // Make the commit durable: a store sync, or in the
+        // pipelined mode journalled record images, a WAL fsync
+        // and a background flush
+        self.commit_durably(&mutations)?;
+
+        // Refresh executor to see the updated indexes
+        self.refresh_executor()?;
+
+        // Update session in manager
+        self.session_manager.update_session(session);
+        Ok(())
+    }
+
+    /// Roll back `session_id`'s explicit transaction (`ROLLBACK`).
+    pub(super) fn abort_session_transaction(&mut self, session_id: &str) -> Result<()> {
+        // Get session
+        let mut session = self
+            .session_manager
+            .get_session(&session_id.to_string())
+            .ok_or_else(|| {
+                Error::transaction(format!("Session {} not found or expired", session_id))
+            })?;
+
+        self.rollback_session_transaction(&mut session)?;
+
+        // Update session in manager BEFORE refreshing executor
+        // This ensures the session state is saved before executor refresh
+        self.session_manager.update_session(session);
+
+        // Refresh executor to see the updated indexes
+        // Note: We don't rebuild indexes here because we've already removed
+        // nodes from indexes manually above. Rebuilding would be redundant and
+        // could potentially reintroduce deleted nodes if there's a timing issue.
+        self.refresh_executor()?;
+        Ok(())
+    }
+
+    /// Undo the writes of `session`'s explicit transaction and abort it.
+    /// Shared by ROLLBACK and the idle-session reaper; the caller stores
+    /// the session back (or drops it) and refreshes the executor.
+    pub(super) fn rollback_session_transaction(
+        &mut self,
+        session: &mut session::Session,
+    ) -> Result<()> {
+        // CRITICAL: Clone created_nodes list before marking as deleted
+        // because get_session may return a cloned session.
+        //
+        // Union with the session's storage watermark range: a
+        // standalone CREATE inside an explicit tx routes through
+        // the EXECUTOR write path, which does not report into
+        // `created_nodes` — the watermark range (captured at
+        // BEGIN, exact under the single-writer model) covers
+        // those, same source as the #15 scoped-commit fix.
+        // Without it, ROLLBACK silently kept executor-created
+        // entities. Gated on an active transaction so a stray
+        // ROLLBACK can never sweep ids from a stale watermark.
+        let mut nodes_to_delete = session.created_nodes.clone();
+        let mut rels_to_delete = session.created_relationships.clone();
+        if session.has_active_transaction() {
+            nodes_to_delete.extend(session.tx_begin_node_watermark..self.storage.node_count());
+            rels_to_delete
+                .extend(session.tx_begin_rel_watermark..self.storage.relationship_count());
+            nodes_to_delete.sort_unstable();
+            nodes_to_delete.dedup();
+            rels_to_delete.sort_unstable();
+            rels_to_delete.dedup();
+        }
+
+        // Remove nodes from index and mark as deleted in storage BEFORE rollback
+        // This ensures we clean up nodes that were written to storage (mmap writes immediately)
+        for node_id in &nodes_to_delete {
+            // First, mark as deleted in storage (this prevents reads from returning the node)
+            if let Err(e) = self.storage.delete_node(*node_id) {
+                tracing::warn!("Failed to delete node {} from storage: {}", node_id, e);
+            }
+
+            // Read node properties before deletion to remove from property index
+            if let Ok(Some(properties)) = self.storage.load_node_properties(*node_id) {
+                if let serde_json::Value::Object(props) = properties {
+                    let property_index = self.cache.property_index_manager();
+                    for prop_name in props.keys() {
+                        if let Err(e) = property_index.remove_property(prop_name, *node_id) {
+                            // Property index may not exist for this property, ignore error
+                            let _ = e;
+                        }
+                    }
+                }
+            }
+
+            // Evict AFTER marking as deleted: label bitmaps, and the TTL,
+            // vector, full-text, spatial and count-aggregate entries the
+            // create fed, so nothing still counts or expires the node.
+            if let Err(e) = self.reindex_restored_node(*node_id, None) {
+                tracing::warn!("Failed to evict node {} from the indexes: {}", node_id, e);
+            }
+        }
+
+        // Mark all relationships created during this transaction as deleted
+        for rel_id in &rels_to_delete {
+            if let Err(e) = self.storage.delete_rel(*rel_id) {
+                tracing::warn!(
+                    "Failed to delete relationship {} from storage: {}",
+                    rel_id,
+                    e
+                );
+            }
+        }
+
+        // Flush storage to ensure consistency (must be done before rollback)
+        if let Err(e) = self.storage.flush() {
+            tracing::warn!("Failed to flush storage: {}", e);
+        }
+
+        // 3.4: Undo any external-id reservations made during
+        // this transaction before the storage records are
+        // deleted, so the catalog index stays consistent.
+        self.rollback_external_id_reservations();
+
+        // Mutations held back for COMMIT are dropped with the transaction.
+        self.transaction_manager
+            .read()
+            .commit_hooks()
+            .end_deferral(&session.id);
+
+        // Rollback transaction (abort the transaction)
+        session.rollback_transaction()?;
+
+        // Clear tracking lists after rollback
+        session.created_nodes.clear();
+        session.created_relationships.clear();
+        // Clear pending index updates (they should not be applied on rollback)
+        session.pending_index_updates.clear();
+        Ok(())
+    }
+
+    /// Roll back explicit transactions idle beyond
+    /// `idle_transaction_timeout_secs` and close sessions idle beyond
+    /// `session_timeout_secs` (see [`session::SessionTimeoutConfig`]), so
+    /// an abandoned client cannot pin its transaction forever. Returns
+    /// what was reaped, for audit logging; each entry is also counted in
+    /// [`session::reaper_metrics`].
+    pub fn reap_idle_sessions(&mut self) -> Result<Vec<session::ReapedSession>> {
+        let due = self.session_manager.sessions_due_for_reaping();
+        if due.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut reaped = Vec::with_capacity(due.len());
+        for (session_id, reason) in due {
+            let Some(mut session) = self.session_manager.take_session(&session_id) else {
+                continue;
+            };
+            let last_activity = session.last_activity;
+            let idle = last_activity.elapsed();
+            let rolled_back_transaction = session.has_active_transaction();
+            if rolled_back_transaction {
+                self.rollback_session_transaction(&mut session)?;
+            }
+            if reason == session::ReapReason::IdleTransaction {
+                // The session itself is still live; only its
+                // transaction is gone. Keep the idle clock running.
+                session.last_activity = last_activity;
+                self.session_manager.update_session(session);
+            }
+            tracing::warn!(
+                session_id = %session_id,
+                ?reason,
+                idle_secs = idle.as_secs(),
+                rolled_back_transaction,
+                "reaped idle session"
+            );
+            let entry = session::ReapedSession {
+                session_id,
+                reason,
+                idle,
+                rolled_back_transaction,
+            };
+            session::record_reaped(&entry);
+            reaped.push(entry);
+        }
+
+        if reaped.iter().any(|r| r.rolled_back_transaction) {
+            self.refresh_executor()?;
+        }
+        Ok(reaped)
+    }
 }
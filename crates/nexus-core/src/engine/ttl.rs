@@ -0,0 +1,223 @@
+//! Node TTL: policies, index maintenance and the expiry sweep.
+//!
+//! See [`crate::index::ttl_index`] for the index itself. A policy is
+//! backfilled from the nodes already carrying its label and then kept
+//! current by the same write hooks that maintain the typed property
+//! index. Policies are in-memory only; list them in
+//! [`super::EngineConfig::ttl_policies`] to rebuild them at startup.
+//!
+//! [`Engine::sweep_expired_nodes`] reads the overdue prefix of the index
+//! and detach-deletes those nodes, finding their relationships through
+//! the relationship index, so a sweep costs O(expired) rather than a
+//! label or relationship-store scan. Sweeper progress is published as
+//! process-wide counters ([`ttl_sweeper_metrics`]) for Prometheus.
+
+use super::Engine;
+use crate::{Error, Result};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// One TTL policy to register at startup.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct TtlPolicySpec {
+    /// Label whose nodes expire.
+    pub label: String,
+    /// Property holding the expiry time (epoch milliseconds or an
+    /// RFC 3339 timestamp).
+    pub property: String,
+}
+
+/// Outcome of one [`Engine::sweep_expired_nodes`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TtlSweepReport {
+    /// Nodes deleted.
+    pub deleted: usize,
+    /// Candidates that turned out not to be expired any more (the expiry
+    /// was moved or removed) or were already gone.
+    pub skipped: usize,
+    /// Expired nodes left for the next sweep (the batch limit was hit).
+    pub remaining: usize,
+    /// How long the oldest candidate had been expired when the sweep
+    /// started, in milliseconds.
+    pub lag_ms: u64,
+}
+
+static TTL_SWEEPS: AtomicU64 = AtomicU64::new(0);
+static TTL_NODES_DELETED: AtomicU64 = AtomicU64::new(0);
+static TTL_LAG_MS: AtomicU64 = AtomicU64::new(0);
+static TTL_PENDING: AtomicU64 = AtomicU64::new(0);
+
+/// Process-wide sweeper counters, exported by `nexus-server` as
+/// Prometheus metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TtlSweeperMetrics {
+    /// Sweeps run.
+    pub sweeps: u64,
+    /// Nodes deleted for expiry.
+    pub nodes_deleted: u64,
+    /// Lag reported by the latest sweep, in milliseconds.
+    pub lag_ms: u64,
+    /// Expired nodes the latest sweep left behind.
+    pub pending: u64,
+}
+
+/// Snapshot of the sweeper counters.
+pub fn ttl_sweeper_metrics() -> TtlSweeperMetrics {
+    TtlSweeperMetrics {
+        sweeps: TTL_SWEEPS.load(Ordering::Relaxed),
+        nodes_deleted: TTL_NODES_DELETED.load(Ordering::Relaxed),
+        lag_ms: TTL_LAG_MS.load(Ordering::Relaxed),
+        pending: TTL_PENDING.load(Ordering::Relaxed),
+    }
+}
+
+/// Current wall-clock time in epoch milliseconds.
+pub fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+impl Engine {
+    /// Make nodes labelled `label` expire at the time stored in
+    /// `property`, and index the nodes that already have one. Returns
+    /// `false` when the label already has a TTL policy.
+    pub fn create_ttl_policy(&mut self, label: &str, property: &str) -> Result<bool> {
+        if property.is_empty() {
+            return Err(Error::Index("TTL property must not be empty".to_string()));
+        }
+        let label_id = self.catalog.get_or_create_label(label)?;
+        if !self.indexes.ttl.create_policy(label_id, property) {
+            return Ok(false);
+        }
+
+        let nodes = self
+            .indexes
+            .label_index
+            .get_nodes_with_labels(&[label_id])?;
+        for node_id in nodes.iter() {
+            self.ttl_index_refresh_from_storage(node_id as u64)?;
+        }
+        Ok(true)
+    }
+
+    /// Remove the TTL policy of `label`. Returns whether one existed.
+    /// Nodes it tracked stop expiring (unless another of their labels
+    /// has a policy) once the sweeper next looks at them.
+    pub fn drop_ttl_policy(&mut self, label: &str) -> Result<bool> {
+        let Ok(label_id) = self.catalog.get_label_id(label) else {
+            return Ok(false);
+        };
+        Ok(self.indexes.ttl.drop_policy(label_id))
+    }
+
+    /// Registered TTL policies as `(label, property)` pairs.
+    pub fn ttl_policies(&self) -> Vec<(String, String)> {
+        self.indexes
+            .ttl
+            .policies()
+            .into_iter()
+            .filter_map(|policy| {
+                let label = self.catalog.get_label_name(policy.label_id).ok()??;
+                Some((label, policy.property))
+            })
+            .collect()
+    }
+
+    /// Detach-delete up to `limit` nodes whose expiry is at or before
+    /// `now_ms`. Each candidate is re-read first; one whose expiry moved
+    /// is re-filed instead of deleted.
+    pub fn sweep_expired_nodes(&mut self, now_ms: i64, limit: usize) -> Result<TtlSweepReport> {
+        self.ensure_writable()?;
+        let lag_ms = self.indexes.ttl.stats(now_ms).lag_ms;
+        let mut report = TtlSweepReport {
+            lag_ms,
+            ..Default::default()
+        };
+
+        for (node_id, _) in self.indexes.ttl.expired(now_ms, limit) {
+            match self.ttl_index_refresh_from_storage(node_id)? {
+                Some(expires_at) if expires_at <= now_ms => {
+                    self.detach_delete_expired_node(node_id)?;
+                    report.deleted += 1;
+                }
+                _ => report.skipped += 1,
+            }
+        }
+
+        report.remaining = self.indexes.ttl.stats(now_ms).expired;
+        TTL_SWEEPS.fetch_add(1, Ordering::Relaxed);
+        TTL_NODES_DELETED.fetch_add(report.deleted as u64, Ordering::Relaxed);
+        TTL_LAG_MS.store(report.lag_ms, Ordering::Relaxed);
+        TTL_PENDING.store(report.remaining as u64, Ordering::Relaxed);
+        Ok(report)
+    }
+
+    /// Re-file a node in the TTL index after a write.
+    pub(in crate::engine) fn ttl_index_sync_node(
+        &self,
+        node_id: u64,
+        label_ids: &[u32],
+        properties: &serde_json::Value,
+    ) {
+        self.indexes.ttl.sync_node(node_id, label_ids, properties);
+    }
+
+    /// Re-file a node from its stored labels and properties, returning
+    /// its current expiry. Missing and deleted nodes are dropped.
+    fn ttl_index_refresh_from_storage(&self, node_id: u64) -> Result<Option<i64>> {
+        let record = match self.storage.read_node(node_id) {
+            Ok(record) if !record.is_deleted() => record,
+            _ => {
+                self.indexes.ttl.remove_node(node_id);
+                return Ok(None);
+            }
+        };
+        let label_bits = record.label_bits;
+        let label_ids: Vec<u32> = (0..64u32)
+            .filter(|bit| label_bits & (1u64 << bit) != 0)
+            .collect();
+        let properties = self
+            .storage
+            .load_node_properties(node_id)?
+            .unwrap_or(serde_json::Value::Null);
+        self.indexes.ttl.sync_node(node_id, &label_ids, &properties);
+        Ok(self.indexes.ttl.expiry_for(&label_ids, &properties))
+    }
+
+    /// `DETACH DELETE` for one node, finding its relationships through
+    /// the relationship index instead of scanning the relationship store
+    /// like [`Engine::delete_node_relationships`].
+    fn detach_delete_expired_node(&mut self, node_id: u64) -> Result<()> {
+        self.heal_relationship_index_if_dirty();
+        let rel_index = self.cache.relationship_index();
+        let mut rel_ids = rel_index.get_node_relationships(node_id, &[], true)?;
+        rel_ids.extend(rel_index.get_node_relationships(node_id, &[], false)?);
+        rel_ids.sort_unstable();
+        rel_ids.dedup();
+
+        let mut tx = self.transaction_manager.write().begin_write()?;
+        for rel_id in rel_ids {
+            let Ok(record) = self.storage.read_rel(rel_id) else {
+                continue;
+            };
+            // packed struct: copy fields to locals before use.
+            let (src, dst, type_id) = (record.src_id, record.dst_id, record.type_id);
+            if record.is_deleted() || (src != node_id && dst != node_id) {
+                continue;
+            }
+            let mut deleted = record;
+            deleted.mark_deleted();
+            self.storage.write_rel(rel_id, &deleted)?;
+            tx.record(crate::transaction::Mutation::RelationshipDeleted { rel_id });
+            if let Err(e) = self
+                .cache
+                .relationship_index()
+                .remove_relationship(rel_id, src, dst, type_id)
+            {
+                tracing::warn!("TTL sweep: relationship-index removal of {rel_id} failed: {e}");
+            }
+        }
+        self.transaction_manager.write().commit(&mut tx)?;
+
+        self.delete_node(node_id)?;
+        Ok(())
+    }
+}
@@ -142,6 +142,8 @@ fn json_type_name(v: &Value) -> &'static str {
         Value::Object(_) => {
             if crate::executor::eval::bytes::is_bytes_value(v) {
                 "BYTES"
+            } else if crate::executor::eval::decimal::is_decimal_value(v) {
+                "DECIMAL"
             } else {
                 "MAP"
             }
@@ -0,0 +1,643 @@
+//! Named vector index lifecycle: create, fill, search, drop.
+//!
+//! A vector index covers the nodes of one label and reads each node's
+//! embedding from one property (see [`crate::index::vector_registry`]).
+//! Creating an index backfills it from the nodes already carrying the
+//! label; afterwards the node write hooks keep it current, so an
+//! embedding stored through Cypher (`SET n.embedding = [...]`) is
+//! indexed the same way as one written with
+//! [`Engine::upsert_node_vector`]. Property values that are not a list
+//! of numbers of the index's length are left out of the index.
+//!
+//! Writes that would store a list of numbers of the wrong length in an
+//! indexed property are rejected. To change an index's dimensions,
+//! metric or property, migrate it: a target index fills alongside the
+//! live one while clients re-embed, then replaces it in one step.
+//!
+//! Searches run against the HNSW graph or as an exact scan per query
+//! ([`KnnSearchMode`]); sampling both reports the graph's recall so its
+//! parameters can be tuned per index.
+
+use super::{Engine, NodeWriteState};
+use crate::index::{
+    KnnSearchMode, KnnSearchOptions, KnnSearchOutcome, VectorIndexDefinition, VectorMetric,
+    vector_registry::VectorIndexEntry,
+};
+use crate::{Error, Result};
+
+/// Longest embedding a vector index accepts.
+pub const MAX_VECTOR_DIMENSIONS: usize = 4096;
+
+/// A vector index as reported to clients.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct VectorIndexInfo {
+    /// Index name.
+    pub name: String,
+    /// Label whose nodes are indexed.
+    pub label: String,
+    /// Property holding the embeddings.
+    pub property: String,
+    /// Embedding length.
+    pub dimensions: usize,
+    /// Distance function.
+    pub metric: VectorMetric,
+    /// Vectors currently indexed.
+    pub vectors: u64,
+    /// Running average search latency in microseconds.
+    pub avg_search_time_us: f64,
+    /// Searches served by the HNSW graph.
+    pub approximate_searches: u64,
+    /// Searches served by an exact scan.
+    pub exact_searches: u64,
+    /// Searches that sampled both to measure recall.
+    pub recall_samples: u64,
+    /// Mean recall of the HNSW graph over those samples.
+    pub avg_recall: Option<f64>,
+}
+
+/// Outcome of [`Engine::upsert_node_vectors`].
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct VectorUpsertReport {
+    /// Vectors written and indexed.
+    pub upserted: usize,
+    /// Vectors rejected, with the reason, in input order.
+    pub failed: Vec<VectorUpsertFailure>,
+}
+
+/// An in-progress vector index migration as reported to clients.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct VectorIndexMigrationInfo {
+    /// The live index, still serving searches.
+    pub source: VectorIndexInfo,
+    /// The index that replaces it when the migration completes.
+    pub target: VectorIndexInfo,
+    /// Nodes in the live index the target does not hold yet.
+    pub pending: u64,
+}
+
+/// A vector [`Engine::upsert_node_vectors`] could not write.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct VectorUpsertFailure {
+    /// Position of the vector in the input.
+    pub position: usize,
+    /// Node the vector was meant for.
+    pub node_id: u64,
+    /// Why it was rejected.
+    pub error: String,
+}
+
+impl From<&VectorIndexEntry> for VectorIndexInfo {
+    fn from(entry: &VectorIndexEntry) -> Self {
+        let stats = entry.index.get_stats();
+        let definition = &entry.definition;
+        Self {
+            name: definition.name.clone(),
+            label: definition.label.clone(),
+            property: definition.property.clone(),
+            dimensions: definition.dimensions,
+            metric: definition.metric,
+            vectors: stats.total_vectors,
+            avg_search_time_us: stats.avg_search_time_us,
+            approximate_searches: stats.approximate_searches,
+            exact_searches: stats.exact_searches,
+            recall_samples: stats.recall_samples,
+            avg_recall: stats.avg_recall,
+        }
+    }
+}
+
+impl Engine {
+    /// Create vector index `name` over `(label, property)` and fill it
+    /// from the existing nodes.
+    ///
+    /// Fails with [`Error::InvalidInput`] for an empty name, label or
+    /// property, a dimension outside `1..=MAX_VECTOR_DIMENSIONS`, or a
+    /// name or `(label, property)` pair that is already indexed.
+    ///
+    /// When the pair has an embedding model binding (see
+    /// [`Engine::set_embedding_model`]), `dimensions = 0` takes the bound
+    /// dimension count and any other count must match it.
+    pub fn create_vector_index(
+        &mut self,
+        name: &str,
+        label: &str,
+        property: &str,
+        dimensions: usize,
+        metric: VectorMetric,
+    ) -> Result<VectorIndexInfo> {
+        self.ensure_writable()?;
+        if name.is_empty() || label.is_empty() || property.is_empty() {
+            return Err(Error::invalid_input(
+                "vector index name, label and property must not be empty",
+            ));
+        }
+        let dimensions = match self.embedding_model(label, property)? {
+            Some(binding) if dimensions == 0 => binding.dimensions,
+            Some(binding) if binding.dimensions != dimensions => {
+                return Err(Error::invalid_input(format!(
+                    ":{label}({property}) is bound to embedding model {:?} with {} dimensions, \
+                     not {dimensions}",
+                    binding.model, binding.dimensions
+                )));
+            }
+            _ => dimensions,
+        };
+        if dimensions == 0 || dimensions > MAX_VECTOR_DIMENSIONS {
+            return Err(Error::invalid_input(format!(
+                "dimensions must be between 1 and {MAX_VECTOR_DIMENSIONS}, got {dimensions}"
+            )));
+        }
+        let entry = self.indexes.vectors.create(VectorIndexDefinition {
+            name: name.to_string(),
+            label: label.to_string(),
+            property: property.to_string(),
+            dimensions,
+            metric,
+        })?;
+        self.backfill_vector_index(&entry)?;
+        Ok(VectorIndexInfo::from(&entry))
+    }
+
+    /// Drop vector index `name`, and its migration if one is in
+    /// progress. The embeddings stay on the nodes. Returns whether the
+    /// index existed.
+    pub fn drop_vector_index(&mut self, name: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        self.indexes.vectors.drop_index(name)
+    }
+
+    /// Every vector index, by name.
+    pub fn vector_indexes(&self) -> Vec<VectorIndexInfo> {
+        self.indexes
+            .vectors
+            .list()
+            .iter()
+            .map(VectorIndexInfo::from)
+            .collect()
+    }
+
+    /// Vector index `name`, or [`Error::NotFound`].
+    pub fn vector_index(&self, name: &str) -> Result<VectorIndexInfo> {
+        Ok(VectorIndexInfo::from(&self.vector_index_entry(name)?))
+    }
+
+    /// Store `vector` as node `node_id`'s embedding for index `name`
+    /// (writing the index's property) and index it, replacing any
+    /// previous vector.
+    ///
+    /// Fails with [`Error::NotFound`] for an unknown index or node and
+    /// with [`Error::InvalidInput`] when the vector has the wrong length
+    /// or the node lacks the index's label.
+    pub fn upsert_node_vector(&mut self, name: &str, node_id: u64, vector: &[f32]) -> Result<()> {
+        let entry = self.vector_index_entry(name)?;
+        check_dimensions(&entry.definition, vector.len())?;
+        let value = vector_to_json(vector).ok_or_else(|| {
+            Error::invalid_input(format!(
+                "vector for node {node_id} contains a non-finite value"
+            ))
+        })?;
+        self.set_vector_property(&entry.definition, node_id, Some(value))
+    }
+
+    /// Batch form of [`Engine::upsert_node_vector`] for embedding
+    /// backfills.
+    ///
+    /// Vectors are validated and written one by one (a rejected vector
+    /// is reported in [`VectorUpsertReport::failed`] and does not stop
+    /// the rest), then every vector index covering the property gets the
+    /// accepted ones in a single parallel HNSW insert. Only the embedding
+    /// property changes, so the full-text and spatial refreshes a Cypher
+    /// `SET` would run are skipped. A node listed twice keeps its last
+    /// vector. Fails as a whole only for an unknown index.
+    pub fn upsert_node_vectors(
+        &mut self,
+        name: &str,
+        vectors: Vec<(u64, Vec<f32>)>,
+    ) -> Result<VectorUpsertReport> {
+        let entry = self.vector_index_entry(name)?;
+        self.upsert_vectors_for(&entry.definition, vectors)
+    }
+
+    /// Remove node `node_id`'s embedding for index `name` (both the
+    /// property and the index entry). Returns whether the node was
+    /// indexed.
+    pub fn remove_node_vector(&mut self, name: &str, node_id: u64) -> Result<bool> {
+        let entry = self.vector_index_entry(name)?;
+        let was_indexed = entry.index.has_vector(node_id);
+        self.set_vector_property(&entry.definition, node_id, None)?;
+        Ok(was_indexed)
+    }
+
+    /// Start migrating vector index `name` to a new property, dimension
+    /// count or metric (`None` keeps the current one).
+    ///
+    /// The target index is filled from the node values that already fit
+    /// it. Until the migration is completed or aborted, searches keep
+    /// using the live index and node writes feed both, so vectors of
+    /// either length are accepted. Re-embed the remaining nodes with
+    /// [`Engine::upsert_migration_vectors`] or by writing the target
+    /// property directly.
+    pub fn start_vector_index_migration(
+        &mut self,
+        name: &str,
+        property: Option<&str>,
+        dimensions: Option<usize>,
+        metric: Option<VectorMetric>,
+    ) -> Result<VectorIndexMigrationInfo> {
+        self.ensure_writable()?;
+        let mut target = self.vector_index_entry(name)?.definition;
+        if let Some(property) = property {
+            if property.is_empty() {
+                return Err(Error::invalid_input(
+                    "vector index property must not be empty",
+                ));
+            }
+            target.property = property.to_string();
+        }
+        if let Some(dimensions) = dimensions {
+            if dimensions == 0 || dimensions > MAX_VECTOR_DIMENSIONS {
+                return Err(Error::invalid_input(format!(
+                    "dimensions must be between 1 and {MAX_VECTOR_DIMENSIONS}, got {dimensions}"
+                )));
+            }
+            target.dimensions = dimensions;
+        }
+        if let Some(metric) = metric {
+            target.metric = metric;
+        }
+        let entry = self.indexes.vectors.start_migration(target)?;
+        self.backfill_vector_index(&entry)?;
+        self.vector_index_migration(name)
+    }
+
+    /// The migration of vector index `name`, or [`Error::NotFound`] when
+    /// none is in progress.
+    pub fn vector_index_migration(&self, name: &str) -> Result<VectorIndexMigrationInfo> {
+        let source = self.vector_index_entry(name)?;
+        let target = self.vector_migration_entry(name)?;
+        let pending = source
+            .index
+            .get_all_nodes()
+            .into_iter()
+            .filter(|&node_id| !target.index.has_vector(node_id))
+            .count() as u64;
+        Ok(VectorIndexMigrationInfo {
+            source: VectorIndexInfo::from(&source),
+            target: VectorIndexInfo::from(&target),
+            pending,
+        })
+    }
+
+    /// [`Engine::upsert_node_vectors`] for the target of index `name`'s
+    /// migration: the vectors must have the target's length and are
+    /// written to the target's property.
+    pub fn upsert_migration_vectors(
+        &mut self,
+        name: &str,
+        vectors: Vec<(u64, Vec<f32>)>,
+    ) -> Result<VectorUpsertReport> {
+        self.vector_index_entry(name)?;
+        let target = self.vector_migration_entry(name)?;
+        self.upsert_vectors_for(&target.definition, vectors)
+    }
+
+    /// Swap the migration target of index `name` in as the live index.
+    ///
+    /// Fails with [`Error::InvalidInput`] while nodes of the live index
+    /// are still missing from the target — they would drop out of search
+    /// results — unless `force` is set.
+    pub fn complete_vector_index_migration(
+        &mut self,
+        name: &str,
+        force: bool,
+    ) -> Result<VectorIndexInfo> {
+        self.ensure_writable()?;
+        let migration = self.vector_index_migration(name)?;
+        if migration.pending > 0 && !force {
+            return Err(Error::invalid_input(format!(
+                "vector index {name:?} migration has {} node(s) not yet re-embedded",
+                migration.pending
+            )));
+        }
+        let entry = self.indexes.vectors.complete_migration(name)?;
+        Ok(VectorIndexInfo::from(&entry))
+    }
+
+    /// Discard the migration of index `name`, leaving the live index as
+    /// it is. Vectors already written to a new property stay on the
+    /// nodes. Returns whether a migration was in progress.
+    pub fn abort_vector_index_migration(&mut self, name: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        self.vector_index_entry(name)?;
+        self.indexes.vectors.abort_migration(name)
+    }
+
+    fn upsert_vectors_for(
+        &mut self,
+        definition: &VectorIndexDefinition,
+        vectors: Vec<(u64, Vec<f32>)>,
+    ) -> Result<VectorUpsertReport> {
+        self.ensure_writable()?;
+        // Every index over this property — the registry allows one per
+        // label, and a node may carry several of those labels.
+        let targets: Vec<(Option<u32>, VectorIndexEntry)> = self
+            .indexes
+            .vectors
+            .maintained()
+            .into_iter()
+            .filter(|e| e.definition.property == definition.property)
+            .map(|e| (self.catalog.get_label_id(&e.definition.label).ok(), e))
+            .collect();
+        let label_id = self.catalog.get_label_id(&definition.label).ok();
+        let has_label = |bits: u64, label_id: Option<u32>| {
+            label_id.is_some_and(|id| id < 64 && bits & (1u64 << id) != 0)
+        };
+
+        let mut report = VectorUpsertReport::default();
+        let mut batches: Vec<Vec<(u64, Vec<f32>)>> = vec![Vec::new(); targets.len()];
+        for (position, (node_id, vector)) in vectors.into_iter().enumerate() {
+            let fail = |error: String| VectorUpsertFailure {
+                position,
+                node_id,
+                error,
+            };
+            if let Err(e) = check_dimensions(definition, vector.len()) {
+                report.failed.push(fail(e.to_string()));
+                continue;
+            }
+            let Some(value) = vector_to_json(&vector) else {
+                report
+                    .failed
+                    .push(fail("vector contains a non-finite value".to_string()));
+                continue;
+            };
+            let record = match self.storage.read_node(node_id) {
+                Ok(record) if !record.is_deleted() => record,
+                _ => {
+                    report
+                        .failed
+                        .push(fail(format!("Node {node_id} not found")));
+                    continue;
+                }
+            };
+            if !has_label(record.label_bits, label_id) {
+                report.failed.push(fail(format!(
+                    "node {node_id} does not have label {:?} of vector index {:?}",
+                    definition.label, definition.name
+                )));
+                continue;
+            }
+
+            let old_properties = self.load_node_properties_map(node_id)?;
+            let mut properties = old_properties.clone();
+            properties.insert(definition.property.clone(), value);
+            let properties = serde_json::Value::Object(properties);
+            if let Err(e) = self
+                .storage
+                .update_node_properties(node_id, properties.clone())
+            {
+                report.failed.push(fail(e.to_string()));
+                continue;
+            }
+            let label_ids = record.get_labels();
+            self.property_index_refresh_node(
+                node_id,
+                &label_ids,
+                &old_properties,
+                &label_ids,
+                &properties,
+            );
+
+            for ((target_label, target), batch) in targets.iter().zip(batches.iter_mut()) {
+                if has_label(record.label_bits, *target_label)
+                    && target.definition.dimensions == vector.len()
+                {
+                    batch.push((node_id, vector.clone()));
+                } else {
+                    // Same property, but no longer a valid embedding for
+                    // this index: keep the index in line with the node.
+                    target.index.remove_vector(node_id)?;
+                }
+            }
+            report.upserted += 1;
+        }
+
+        for ((_, target), batch) in targets.iter().zip(batches) {
+            target.index.add_vectors(batch)?;
+        }
+        Ok(report)
+    }
+
+    /// The `k` nodes in index `name` closest to `vector`, best first,
+    /// with their similarity scores (see [`VectorMetric::score`]).
+    ///
+    /// Searches in [`KnnSearchMode::Auto`]; see
+    /// [`Engine::vector_search_with`] to pick the mode.
+    pub fn vector_search(&self, name: &str, vector: &[f32], k: usize) -> Result<Vec<(u64, f32)>> {
+        self.vector_search_with(name, vector, k, KnnSearchOptions::default())
+            .map(|outcome| outcome.results)
+    }
+
+    /// [`Engine::vector_search`] with an explicit search mode, HNSW `ef`
+    /// and optional recall sampling. The outcome reports which search
+    /// served the results.
+    pub fn vector_search_with(
+        &self,
+        name: &str,
+        vector: &[f32],
+        k: usize,
+        options: KnnSearchOptions,
+    ) -> Result<KnnSearchOutcome> {
+        let entry = self.vector_index_entry(name)?;
+        check_dimensions(&entry.definition, vector.len())?;
+        if k == 0 {
+            return Err(Error::invalid_input("k must be positive"));
+        }
+        if options.ef_search == Some(0) {
+            return Err(Error::invalid_input("ef_search must be positive"));
+        }
+        entry.index.search_knn_with_options(vector, k, options)
+    }
+
+    /// Bring every vector index in line with a node's current labels and
+    /// properties: index the embedding where the node carries the label
+    /// and a well-formed vector, drop it everywhere else.
+    pub(in crate::engine) fn vector_index_sync_node(
+        &self,
+        node_id: u64,
+        label_ids: &[u32],
+        properties: &serde_json::Value,
+    ) {
+        if !self.indexes.vectors.has_any() {
+            return;
+        }
+        for entry in self.indexes.vectors.maintained() {
+            let definition = &entry.definition;
+            let labelled = self
+                .catalog
+                .get_label_id(&definition.label)
+                .is_ok_and(|label_id| label_ids.contains(&label_id));
+            let vector = properties
+                .get(&definition.property)
+                .and_then(|value| vector_from_json(value, definition.dimensions));
+            let result = match vector {
+                Some(vector) if labelled => entry.index.add_vector(node_id, vector),
+                _ => entry.index.remove_vector(node_id),
+            };
+            if let Err(e) = result {
+                tracing::warn!(
+                    "vector index {:?}: sync of node {node_id} failed: {e}",
+                    definition.name
+                );
+            }
+        }
+    }
+
+    /// Reject a node write that stores a list of numbers of the wrong
+    /// length in a vector-indexed property of one of `label_ids`.
+    pub(in crate::engine) fn check_node_vectors(
+        &self,
+        label_ids: &[u32],
+        properties: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()> {
+        self.indexes.vectors.check_node_vectors(
+            |label| {
+                self.catalog
+                    .get_label_id(label)
+                    .is_ok_and(|label_id| label_ids.contains(&label_id))
+            },
+            properties,
+        )
+    }
+
+    /// Remove a deleted node from every vector index.
+    pub(in crate::engine) fn vector_index_evict_node(&self, node_id: u64) {
+        for entry in self.indexes.vectors.maintained() {
+            if let Err(e) = entry.index.remove_vector(node_id) {
+                tracing::warn!(
+                    "vector index {:?}: evicting node {node_id} failed: {e}",
+                    entry.definition.name
+                );
+            }
+        }
+    }
+
+    /// Fill every registered vector index from storage (startup).
+    pub(in crate::engine) fn rebuild_vector_indexes(&mut self) -> Result<()> {
+        for entry in self.indexes.vectors.maintained() {
+            self.backfill_vector_index(&entry)?;
+        }
+        Ok(())
+    }
+
+    fn vector_index_entry(&self, name: &str) -> Result<VectorIndexEntry> {
+        self.indexes
+            .vectors
+            .get(name)
+            .ok_or_else(|| Error::NotFound(format!("Vector index {name:?} not found")))
+    }
+
+    fn vector_migration_entry(&self, name: &str) -> Result<VectorIndexEntry> {
+        self.indexes.vectors.migration(name).ok_or_else(|| {
+            Error::NotFound(format!(
+                "Vector index {name:?} has no migration in progress"
+            ))
+        })
+    }
+
+    fn backfill_vector_index(&mut self, entry: &VectorIndexEntry) -> Result<()> {
+        let definition = &entry.definition;
+        let Ok(label_id) = self.catalog.get_label_id(&definition.label) else {
+            return Ok(());
+        };
+        let nodes = self
+            .indexes
+            .label_index
+            .get_nodes_with_labels(&[label_id])?;
+        for node_id in nodes.iter().map(u64::from) {
+            let Some(properties) = self.storage.load_node_properties(node_id)? else {
+                continue;
+            };
+            if let Some(vector) = properties
+                .get(&definition.property)
+                .and_then(|value| vector_from_json(value, definition.dimensions))
+            {
+                entry.index.add_vector(node_id, vector)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set (or, with `None`, remove) the index property of a live,
+    /// labelled node through the regular SET write path, so every index
+    /// refresh (this one included) runs as for a Cypher `SET`.
+    fn set_vector_property(
+        &mut self,
+        definition: &VectorIndexDefinition,
+        node_id: u64,
+        value: Option<serde_json::Value>,
+    ) -> Result<()> {
+        self.ensure_writable()?;
+        let record = self
+            .get_node(node_id)?
+            .filter(|record| !record.is_deleted())
+            .ok_or_else(|| Error::NotFound(format!("Node {node_id} not found")))?;
+        let labels = self.catalog.get_labels_from_bitmap(record.label_bits)?;
+        if !labels.contains(&definition.label) {
+            return Err(Error::invalid_input(format!(
+                "node {node_id} does not have label {:?} of vector index {:?}",
+                definition.label, definition.name
+            )));
+        }
+
+        let mut properties = self.load_node_properties_map(node_id)?;
+        match value {
+            Some(value) => {
+                properties.insert(definition.property.clone(), value);
+            }
+            None => {
+                if properties.remove(&definition.property).is_none() {
+                    return Ok(());
+                }
+            }
+        }
+        self.persist_node_state(
+            node_id,
+            NodeWriteState {
+                properties,
+                labels: labels.into_iter().collect(),
+            },
+        )
+    }
+}
+
+fn check_dimensions(definition: &VectorIndexDefinition, len: usize) -> Result<()> {
+    if len != definition.dimensions {
+        return Err(Error::invalid_input(format!(
+            "vector index {:?} expects {} dimensions, got {len}",
+            definition.name, definition.dimensions
+        )));
+    }
+    Ok(())
+}
+
+/// `value` as an embedding of exactly `dimensions` numbers, if it is one.
+fn vector_from_json(value: &serde_json::Value, dimensions: usize) -> Option<Vec<f32>> {
+    let items = value.as_array()?;
+    if items.len() != dimensions {
+        return None;
+    }
+    items
+        .iter()
+        .map(|item| item.as_f64().map(|x| x as f32))
+        .collect()
+}
+
+/// `vector` as a JSON list, or `None` if it holds a NaN or infinity.
+fn vector_to_json(vector: &[f32]) -> Option<serde_json::Value> {
+    vector
+        .iter()
+        .map(|&x| serde_json::Number::from_f64(f64::from(x)).map(serde_json::Value::Number))
+        .collect::<Option<Vec<_>>>()
+        .map(serde_json::Value::Array)
+}
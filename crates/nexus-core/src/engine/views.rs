@@ -0,0 +1,583 @@
+//! Saved Cypher views.
+//!
+//! `CREATE VIEW active_users AS MATCH (u:User) WHERE u.active RETURN u`
+//! stores the query text in the catalog under a name. The view is then
+//! usable as a label — `MATCH (u:active_users) RETURN u.name`, expanded
+//! by the executor (see `executor/views.rs`) — or called directly with
+//! `CALL nexus.view('active_users')`, which yields the view's rows.
+//!
+//! A view must be a read-only query ending in `RETURN`; only views that
+//! return nodes in their first column can stand in for a label. The
+//! owner recorded with a view is the user that created it over the HTTP
+//! API, which `nexus-server` uses to restrict replacing and dropping it.
+//!
+//! `CREATE MATERIALIZED VIEW` also stores the view's result in the
+//! catalog, and uses of the view read that result instead of running the
+//! query. The result is recomputed by `REFRESH VIEW` and, depending on
+//! the view's [`ViewRefresh`] policy, by [`Engine::refresh_due_views`]
+//! (which `nexus-server` calls from a background job): `ON CHANGE` views
+//! once a committed write has touched a label or relationship type the
+//! view's query reads, `EVERY` views once their interval has passed. A
+//! refresh re-runs the whole query; what is incremental is that views
+//! no write has touched are left alone.
+//!
+//! The record store's [`crate::storage::WriteTracker`] reports the
+//! labels of every node record written (before and after the write)
+//! and the type of every relationship record written, and property
+//! writes are resolved to the labels or type of their entity. After
+//! each write statement the engine stamps those labels and types with
+//! the executor generation. A view reads the labels and relationship
+//! types named in its patterns, plus those of the views it uses as
+//! labels; a pattern without a label or type, a quantified pattern or
+//! a subquery expression makes it read everything, so any write marks
+//! it stale. Generations restart with the process, so after a restart
+//! every view reports stale until it is refreshed again.
+
+use super::Engine;
+use crate::catalog::{MaterializedViewResult, ViewDefinition, ViewRefresh};
+use crate::executor::parser::{Clause, CypherParser, Expression, Pattern, PatternElement};
+use crate::storage::TrackedWrites;
+use crate::{Error, Result, executor};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// Executor generation at which writes last touched each label and
+/// relationship type.
+#[derive(Debug, Default)]
+pub struct WriteGenerations {
+    labels: HashMap<u32, u64>,
+    types: HashMap<u32, u64>,
+    /// Last generation with any write.
+    any: u64,
+    /// Last generation at which everything counted as written.
+    everything: u64,
+}
+
+impl WriteGenerations {
+    /// Stamp what `writes` touched with `generation`.
+    fn record(&mut self, writes: &TrackedWrites, generation: u64) {
+        if writes.is_empty() {
+            return;
+        }
+        self.any = generation;
+        if writes.everything {
+            self.everything = generation;
+        }
+        for bit in (0..64u32).filter(|bit| writes.label_bits & (1u64 << bit) != 0) {
+            self.labels.insert(bit, generation);
+        }
+        for &type_id in &writes.type_ids {
+            self.types.insert(type_id, generation);
+        }
+    }
+}
+
+/// Labels and relationship types a view's query reads.
+#[derive(Debug, Default)]
+struct ViewInputs {
+    labels: HashSet<String>,
+    types: HashSet<String>,
+}
+
+impl Engine {
+    /// Store view `name` over `query`; `materialized` makes it a
+    /// materialized view with that refresh policy, computed right away.
+    ///
+    /// Fails with [`Error::InvalidInput`] when the query does not parse,
+    /// writes, has no `RETURN`, or uses the view itself; when `name` is a
+    /// label that nodes already carry; or when the view exists and
+    /// `or_replace` is false. A materialized view whose query fails is
+    /// not stored. A replaced view keeps nothing of the old definition.
+    pub fn create_view(
+        &mut self,
+        name: &str,
+        query: &str,
+        owner: Option<&str>,
+        or_replace: bool,
+        materialized: Option<ViewRefresh>,
+    ) -> Result<ViewDefinition> {
+        self.ensure_writable()?;
+        if name.is_empty() {
+            return Err(Error::invalid_input("view name must not be empty"));
+        }
+        if !or_replace && self.catalog.get_view(name).is_some() {
+            return Err(Error::invalid_input(format!(
+                "view {name:?} already exists"
+            )));
+        }
+        if let Ok(label_id) = self.catalog.get_label_id(name)
+            && !self
+                .indexes
+                .label_index
+                .get_nodes_with_labels(&[label_id])?
+                .is_empty()
+        {
+            return Err(Error::invalid_input(format!(
+                "view name {name:?} is already used as a node label"
+            )));
+        }
+        validate_view_query(name, query)?;
+
+        let view = ViewDefinition {
+            name: name.to_string(),
+            query: query.trim().to_string(),
+            owner: owner.map(str::to_string),
+            created_at: chrono::Utc::now().timestamp_millis(),
+            materialized,
+        };
+        let result = match materialized {
+            Some(_) => Some(self.compute_view_result(&view)?),
+            None => None,
+        };
+        self.catalog.persist_view(&view)?;
+        self.view_refresh_generations.remove(name);
+        match result {
+            Some(result) => self.store_view_result(name, &result)?,
+            None => self.catalog.remove_view_result(name)?,
+        }
+        Ok(view)
+    }
+
+    /// Remove view `name`. Returns whether it existed.
+    pub fn drop_view(&mut self, name: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        self.view_refresh_generations.remove(name);
+        self.catalog.remove_view(name)
+    }
+
+    /// The view called `name`, if any.
+    pub fn view(&self, name: &str) -> Option<ViewDefinition> {
+        self.catalog.get_view(name)
+    }
+
+    /// Every view, by name.
+    pub fn views(&self) -> Vec<ViewDefinition> {
+        self.catalog.list_views()
+    }
+
+    /// Recompute and store the result of materialized view `name`.
+    pub fn refresh_view(&mut self, name: &str) -> Result<MaterializedViewResult> {
+        self.ensure_writable()?;
+        let view = self
+            .view(name)
+            .ok_or_else(|| Error::NotFound(format!("view {name:?} does not exist")))?;
+        if view.materialized.is_none() {
+            return Err(Error::invalid_input(format!(
+                "view {name:?} is not materialized"
+            )));
+        }
+        let result = self.compute_view_result(&view)?;
+        self.store_view_result(name, &result)?;
+        Ok(result)
+    }
+
+    /// Whether materialized view `name` may be out of date: committed
+    /// writes touched a label or relationship type it reads since its
+    /// last refresh, or this process has not refreshed it yet. Always
+    /// `false` for plain views.
+    pub fn is_view_stale(&self, name: &str) -> bool {
+        let Some(view) = self.view(name).filter(|view| view.materialized.is_some()) else {
+            return false;
+        };
+        let Some(&refreshed) = self.view_refresh_generations.get(name) else {
+            return true;
+        };
+        let writes = &self.view_write_generations;
+        if writes.everything > refreshed {
+            return true;
+        }
+        let Some(inputs) = self.view_inputs(&view) else {
+            return writes.any > refreshed;
+        };
+        let written_since = |generation: Option<&u64>| generation.is_some_and(|&g| g > refreshed);
+        inputs.labels.iter().any(|label| {
+            self.catalog
+                .get_label_id(label)
+                .is_ok_and(|id| written_since(writes.labels.get(&id)))
+        }) || inputs.types.iter().any(|rel_type| {
+            self.catalog
+                .get_type_id(rel_type)
+                .ok()
+                .flatten()
+                .is_some_and(|id| written_since(writes.types.get(&id)))
+        })
+    }
+
+    /// Stamp the labels and relationship types written since the last
+    /// call with the current executor generation. Called on every
+    /// executor refresh; a no-op until a view result is stored.
+    pub(super) fn note_view_writes(&mut self) {
+        if !self.storage.write_tracker().is_enabled() {
+            return;
+        }
+        let writes = self.storage.take_tracked_writes();
+        self.view_write_generations
+            .record(&writes, self.executor_generation);
+    }
+
+    /// What `view` reads, or `None` when it may read anything.
+    fn view_inputs(&self, view: &ViewDefinition) -> Option<ViewInputs> {
+        let mut inputs = ViewInputs::default();
+        let mut seen = HashSet::from([view.name.clone()]);
+        let mut pending = vec![view.query.clone()];
+        while let Some(query) = pending.pop() {
+            let ast = CypherParser::new(query).parse().ok()?;
+            let mut labels = HashSet::new();
+            for clause in &ast.clauses {
+                if !clause_inputs(clause, &mut labels, &mut inputs.types) {
+                    return None;
+                }
+            }
+            for label in labels {
+                match self.catalog.get_view(&label) {
+                    Some(used) if seen.insert(label.clone()) => pending.push(used.query),
+                    Some(_) => {}
+                    None => {
+                        inputs.labels.insert(label);
+                    }
+                }
+            }
+        }
+        Some(inputs)
+    }
+
+    /// Whether any materialized view is due for a refresh under its
+    /// policy. Cheap; meant for polling before taking a write lock.
+    pub fn has_due_views(&self) -> bool {
+        self.catalog.has_views() && !self.due_views().is_empty()
+    }
+
+    /// Refresh every materialized view that is due under its policy and
+    /// return the names of the refreshed views. A view whose query fails
+    /// is logged and skipped so it cannot hold back the others.
+    pub fn refresh_due_views(&mut self) -> Vec<String> {
+        if self.ensure_writable().is_err() || !self.catalog.has_views() {
+            return Vec::new();
+        }
+        let mut refreshed = Vec::new();
+        for name in self.due_views() {
+            match self.refresh_view(&name) {
+                Ok(_) => refreshed.push(name),
+                Err(e) => tracing::warn!("refreshing materialized view {name:?} failed: {e}"),
+            }
+        }
+        refreshed
+    }
+
+    /// Names of the materialized views due for a refresh.
+    fn due_views(&self) -> Vec<String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        self.views()
+            .into_iter()
+            .filter(|view| match view.materialized {
+                None | Some(ViewRefresh::Manual) => false,
+                Some(ViewRefresh::OnChange) => self.is_view_stale(&view.name),
+                Some(ViewRefresh::Every { interval_ms }) => self
+                    .catalog
+                    .get_view_result(&view.name)
+                    .is_none_or(|stored| {
+                        now.saturating_sub(stored.refreshed_at) >= interval_ms as i64
+                    }),
+            })
+            .map(|view| view.name)
+            .collect()
+    }
+
+    fn compute_view_result(&self, view: &ViewDefinition) -> Result<MaterializedViewResult> {
+        let started = Instant::now();
+        let result = self.executor.execute(&executor::Query {
+            cypher: view.query.clone(),
+            params: HashMap::new(),
+        })?;
+        let rows: Vec<&Vec<serde_json::Value>> = result.rows.iter().map(|r| &r.values).collect();
+        Ok(MaterializedViewResult {
+            rows_json: serde_json::to_string(&rows)?,
+            row_count: result.rows.len() as u64,
+            columns: result.columns,
+            refreshed_at: chrono::Utc::now().timestamp_millis(),
+            refresh_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn store_view_result(&mut self, name: &str, result: &MaterializedViewResult) -> Result<()> {
+        self.catalog.persist_view_result(name, result)?;
+        self.storage.write_tracker().set_enabled(true);
+        self.view_refresh_generations
+            .insert(name.to_string(), self.executor_generation);
+        Ok(())
+    }
+
+    /// Execute view commands (CREATE VIEW, DROP VIEW, REFRESH VIEW,
+    /// SHOW VIEWS) on behalf of `owner`.
+    pub fn execute_view_commands(
+        &mut self,
+        ast: &executor::parser::CypherQuery,
+        owner: Option<&str>,
+    ) -> Result<executor::ResultSet> {
+        let mut columns = vec!["view".to_string(), "message".to_string()];
+        let mut rows = Vec::new();
+        for clause in &ast.clauses {
+            match clause {
+                Clause::CreateView(create) => {
+                    let message = if create.if_not_exists && self.view(&create.name).is_some() {
+                        "View already exists, skipped".to_string()
+                    } else {
+                        self.create_view(
+                            &create.name,
+                            &create.query,
+                            owner,
+                            create.or_replace,
+                            create.materialized,
+                        )?;
+                        format!("View '{}' created", create.name)
+                    };
+                    rows.push(message_row(&create.name, message));
+                }
+                Clause::DropView(drop) => {
+                    if self.drop_view(&drop.name)? {
+                        rows.push(message_row(
+                            &drop.name,
+                            format!("View '{}' dropped", drop.name),
+                        ));
+                    } else if !drop.if_exists {
+                        return Err(Error::CypherExecution(format!(
+                            "View '{}' does not exist",
+                            drop.name
+                        )));
+                    }
+                }
+                Clause::RefreshView(refresh) => {
+                    let result = self.refresh_view(&refresh.name)?;
+                    rows.push(message_row(
+                        &refresh.name,
+                        format!(
+                            "View '{}' refreshed ({} rows)",
+                            refresh.name, result.row_count
+                        ),
+                    ));
+                }
+                Clause::ShowViews => {
+                    columns = [
+                        "name",
+                        "query",
+                        "owner",
+                        "createdAt",
+                        "materialized",
+                        "refresh",
+                        "refreshedAt",
+                        "stale",
+                        "rowCount",
+                    ]
+                    .map(str::to_string)
+                    .to_vec();
+                    rows = self
+                        .views()
+                        .into_iter()
+                        .map(|view| self.show_view_row(view))
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        Ok(executor::ResultSet::new(columns, rows))
+    }
+
+    /// SHOW VIEWS row of `view`; the refresh columns are null for plain
+    /// views.
+    fn show_view_row(&self, view: ViewDefinition) -> executor::Row {
+        use serde_json::Value;
+
+        let stale = self.is_view_stale(&view.name);
+        let stored = self.catalog.get_view_result(&view.name);
+        let (refresh, stale) = match view.materialized {
+            Some(policy) => (Value::String(refresh_text(policy)), Value::Bool(stale)),
+            None => (Value::Null, Value::Null),
+        };
+        executor::Row {
+            values: vec![
+                Value::String(view.name),
+                Value::String(view.query),
+                view.owner.map_or(Value::Null, Value::String),
+                Value::from(view.created_at),
+                Value::Bool(view.materialized.is_some()),
+                refresh,
+                stored
+                    .as_ref()
+                    .map_or(Value::Null, |r| Value::from(r.refreshed_at)),
+                stale,
+                stored.map_or(Value::Null, |r| Value::from(r.row_count)),
+            ],
+        }
+    }
+}
+
+fn message_row(view: &str, message: String) -> executor::Row {
+    executor::Row {
+        values: vec![
+            serde_json::Value::String(view.to_string()),
+            serde_json::Value::String(message),
+        ],
+    }
+}
+
+/// `REFRESH` clause text of a refresh policy, as SHOW VIEWS reports it.
+fn refresh_text(policy: ViewRefresh) -> String {
+    match policy {
+        ViewRefresh::Manual => "MANUAL".to_string(),
+        ViewRefresh::OnChange => "ON CHANGE".to_string(),
+        ViewRefresh::Every { interval_ms } if interval_ms % 1_000 == 0 => {
+            format!("EVERY {} SECONDS", interval_ms / 1_000)
+        }
+        ViewRefresh::Every { interval_ms } => format!("EVERY {interval_ms} MILLISECONDS"),
+    }
+}
+
+/// Add the labels and relationship types `clause` reads to `labels` and
+/// `types`. Returns `false` when it may read anything.
+fn clause_inputs(
+    clause: &Clause,
+    labels: &mut HashSet<String>,
+    types: &mut HashSet<String>,
+) -> bool {
+    match clause {
+        Clause::Match(match_clause) => {
+            pattern_inputs(&match_clause.pattern, labels, types)
+                && match_clause
+                    .where_clause
+                    .as_ref()
+                    .is_none_or(|w| reads_bound_only(&w.expression))
+        }
+        Clause::With(with) => {
+            with.items
+                .iter()
+                .all(|item| reads_bound_only(&item.expression))
+                && with
+                    .where_clause
+                    .as_ref()
+                    .is_none_or(|w| reads_bound_only(&w.expression))
+        }
+        Clause::Return(ret) => ret
+            .items
+            .iter()
+            .all(|item| reads_bound_only(&item.expression)),
+        Clause::Where(w) => reads_bound_only(&w.expression),
+        Clause::Unwind(unwind) => reads_bound_only(&unwind.expression),
+        Clause::OrderBy(order) => order
+            .items
+            .iter()
+            .all(|item| reads_bound_only(&item.expression)),
+        Clause::Limit(limit) => reads_bound_only(&limit.count),
+        Clause::Skip(skip) => reads_bound_only(&skip.count),
+        Clause::Union(_) => true,
+        _ => false,
+    }
+}
+
+/// Add the labels and relationship types `pattern` names. Returns
+/// `false` when an element has none, or is a quantified group.
+fn pattern_inputs(
+    pattern: &Pattern,
+    labels: &mut HashSet<String>,
+    types: &mut HashSet<String>,
+) -> bool {
+    pattern.elements.iter().all(|element| match element {
+        PatternElement::Node(node) => {
+            labels.extend(node.labels.iter().cloned());
+            !node.labels.is_empty()
+                && node
+                    .properties
+                    .as_ref()
+                    .is_none_or(|map| map.properties.values().all(reads_bound_only))
+        }
+        PatternElement::Relationship(rel) => {
+            types.extend(rel.types.iter().cloned());
+            !rel.types.is_empty()
+                && rel
+                    .properties
+                    .as_ref()
+                    .is_none_or(|map| map.properties.values().all(reads_bound_only))
+        }
+        _ => false,
+    })
+}
+
+/// Whether `expr` reads the graph only through variables its patterns
+/// bound. Pattern expressions, subqueries and forms the walker does not
+/// understand count as reading anything.
+fn reads_bound_only(expr: &Expression) -> bool {
+    match expr {
+        Expression::Literal(_)
+        | Expression::Parameter(_)
+        | Expression::Variable(_)
+        | Expression::PropertyAccess { .. } => true,
+        Expression::ArrayIndex { base, index } => reads_bound_only(base) && reads_bound_only(index),
+        Expression::ArraySlice { base, start, end } => {
+            reads_bound_only(base) && start.iter().chain(end.iter()).all(|e| reads_bound_only(e))
+        }
+        Expression::FunctionCall { args, .. } | Expression::List(args) => {
+            args.iter().all(reads_bound_only)
+        }
+        Expression::Map(entries) => entries.values().all(reads_bound_only),
+        Expression::BinaryOp { left, right, .. } => {
+            reads_bound_only(left) && reads_bound_only(right)
+        }
+        Expression::UnaryOp { operand, .. } => reads_bound_only(operand),
+        Expression::IsNull { expr, .. } => reads_bound_only(expr),
+        Expression::Case {
+            input,
+            when_clauses,
+            else_clause,
+        } => {
+            input
+                .iter()
+                .chain(else_clause.iter())
+                .all(|e| reads_bound_only(e))
+                && when_clauses
+                    .iter()
+                    .all(|w| reads_bound_only(&w.condition) && reads_bound_only(&w.result))
+        }
+        _ => false,
+    }
+}
+
+/// Check that `query` is usable as the body of view `name`.
+fn validate_view_query(name: &str, query: &str) -> Result<()> {
+    let ast = CypherParser::new(query.to_string())
+        .parse()
+        .map_err(|e| Error::invalid_input(format!("view {name:?} query does not parse: {e}")))?;
+    let mut has_return = false;
+    for clause in &ast.clauses {
+        match clause {
+            Clause::Match(match_clause) => {
+                let uses_itself = match_clause.pattern.elements.iter().any(|element| {
+                    matches!(element, PatternElement::Node(node)
+                        if node.labels.iter().any(|label| label == name))
+                });
+                if uses_itself {
+                    return Err(Error::invalid_input(format!(
+                        "view {name:?} must not use itself"
+                    )));
+                }
+            }
+            Clause::Return(_) => has_return = true,
+            Clause::With(_)
+            | Clause::Unwind(_)
+            | Clause::Union(_)
+            | Clause::Where(_)
+            | Clause::OrderBy(_)
+            | Clause::Limit(_)
+            | Clause::Skip(_) => {}
+            _ => {
+                return Err(Error::invalid_input(format!(
+                    "view {name:?} must be a read-only MATCH ... RETURN query"
+                )));
+            }
+        }
+    }
+    if !has_return {
+        return Err(Error::invalid_input(format!(
+            "view {name:?} query must end in RETURN"
+        )));
+    }
+    Ok(())
+}
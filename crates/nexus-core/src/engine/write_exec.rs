@@ -1250,13 +1250,22 @@ impl Engine {
                                     continue;
                                 }
                                 if !c.ty.accepts(&json_value) {
-                                    return Err(Error::ConstraintViolation(format!(
+                                    return Err(crate::constraints::ConstraintViolation::new(
+                                        "PROPERTY_TYPE",
+                                        "NODE",
+                                    )
+                                    .named(c.name.as_deref())
+                                    .label_or_type(self.label_name_or_id(label_id))
+                                    .value(property.clone(), json_value.clone())
+                                    .offending(Some(node_id))
+                                    .with_message(format!(
                                         "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_TYPE \
                                          property={:?} expected={} got={}",
                                         c.property_key,
                                         c.ty.name(),
                                         super::json_type_label(&json_value),
-                                    )));
+                                    ))
+                                    .into_error());
                                 }
                             }
                         }
@@ -0,0 +1,545 @@
+//! Write path pipelining: commit durability without a store sync.
+//!
+//! A write is applied to the memory-mapped record and property stores
+//! first and journalled to the WAL second. Under
+//! [`CommitMode::Inline`] (default) an explicit `COMMIT` then syncs
+//! every store file before it returns, so each commit pays for a full
+//! `msync` of the node, relationship and property files while holding
+//! the engine write lock.
+//!
+//! [`CommitMode::Pipelined`] splits the two:
+//!
+//! * `COMMIT` journals an image of every record the transaction
+//!   touched ([`WalEntry::NodeImage`], [`WalEntry::RelImage`]: the raw
+//!   record plus its property map) and then only waits until the WAL
+//!   entries written so far are fsynced
+//!   ([`crate::wal::AsyncWalWriter::wait_durable`]). The async WAL
+//!   writer batches those fsyncs, so commits arriving together share
+//!   one.
+//! * A background store flusher syncs the store files on its own
+//!   thread — right after each commit, or every
+//!   [`WritePipelineConfig::store_flush_interval_ms`] when the WAL or
+//!   autocommit writes have moved since the last flush — and appends a
+//!   [`WalEntry::StoreFlush`] marker once the sync completed. The next
+//!   transaction proceeds while earlier pages are still being written.
+//!
+//! The marker bounds what a crash can have lost: every WAL entry before
+//! it, except the `unflushed_tail` entries appended while the flush ran,
+//! reached the store files. At startup the engine re-applies the
+//! deletes and record images found after the latest marker, so every
+//! acknowledged commit is back in the stores, whether its writes came
+//! from Cypher or from the engine API. The integrity scan
+//! ([`super::integrity`]) then reports creates there whose records are
+//! still missing, so [`super::CorruptionPolicy`] decides whether the
+//! engine opens.
+//!
+//! Auto-commit writes are not imaged: like under the inline mode, they
+//! reach disk with the next store flush, at most one flush interval
+//! after the write.
+
+use super::Engine;
+use super::integrity::IntegrityReport;
+use crate::storage::record_store::StoreFlushHandle;
+use crate::storage::{NodeRecord, RelationshipRecord};
+use crate::transaction::{Mutation, MutationSet, TransactionManager};
+use crate::wal::{self, WalEntry};
+use crate::{Error, Result};
+use parking_lot::{Condvar, Mutex, RwLock};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Store flush interval used when none is configured.
+pub const DEFAULT_STORE_FLUSH_INTERVAL_MS: u64 = 1_000;
+
+/// What an explicit `COMMIT` waits for before it returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitMode {
+    /// Sync the store files.
+    #[default]
+    Inline,
+    /// Wait for the WAL fsync; a background thread syncs the stores.
+    Pipelined,
+}
+
+impl std::str::FromStr for CommitMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "inline" => Ok(Self::Inline),
+            "pipelined" => Ok(Self::Pipelined),
+            other => Err(Error::invalid_input(format!(
+                "unknown commit mode {other:?} (expected inline or pipelined)"
+            ))),
+        }
+    }
+}
+
+/// Commit path settings, part of [`super::EngineConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct WritePipelineConfig {
+    /// What `COMMIT` waits for.
+    pub commit_mode: CommitMode,
+    /// How often the background flusher syncs the stores when there
+    /// were writes but no commit asked for a flush. Pipelined mode only.
+    pub store_flush_interval_ms: u64,
+}
+
+impl Default for WritePipelineConfig {
+    fn default() -> Self {
+        Self {
+            commit_mode: CommitMode::Inline,
+            store_flush_interval_ms: DEFAULT_STORE_FLUSH_INTERVAL_MS,
+        }
+    }
+}
+
+/// Commit path counters, from [`Engine::write_pipeline_stats`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct WritePipelineStats {
+    /// Mode in effect
+    pub commit_mode: CommitMode,
+    /// LSN of the last WAL entry accepted this run
+    pub wal_submitted_lsn: u64,
+    /// LSN up to which the WAL is fsynced
+    pub wal_durable_lsn: u64,
+    /// Highest LSN whose store change is known to be on disk
+    pub store_flushed_lsn: u64,
+    /// Background store flushes completed
+    pub store_flushes: u64,
+    /// Background store flushes that failed
+    pub store_flush_errors: u64,
+}
+
+#[derive(Debug, Default)]
+struct FlusherState {
+    /// A commit asked for a flush now
+    requested: bool,
+    /// Stores were written since the last flush
+    dirty: bool,
+    shutdown: bool,
+}
+
+#[derive(Debug, Default)]
+struct FlusherShared {
+    state: Mutex<FlusherState>,
+    wake: Condvar,
+    flushes: AtomicU64,
+    errors: AtomicU64,
+    flushed_lsn: AtomicU64,
+}
+
+/// Background thread that syncs the store files and journals a
+/// [`WalEntry::StoreFlush`] marker after each sync.
+pub(crate) struct StoreFlusher {
+    shared: Arc<FlusherShared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl StoreFlusher {
+    fn start(
+        store: StoreFlushHandle,
+        wal: wal::AsyncWalHandle,
+        transactions: Arc<RwLock<TransactionManager>>,
+        interval: Duration,
+    ) -> Result<Self> {
+        let shared = Arc::new(FlusherShared::default());
+        let thread = std::thread::Builder::new()
+            .name("nexus-store-flusher".to_string())
+            .spawn({
+                let shared = Arc::clone(&shared);
+                move || flusher_loop(&shared, &store, &wal, &transactions, interval)
+            })?;
+        Ok(Self {
+            shared,
+            thread: Some(thread),
+        })
+    }
+
+    /// Flush as soon as possible.
+    fn request(&self) {
+        self.shared.state.lock().requested = true;
+        self.shared.wake.notify_one();
+    }
+
+    /// Flush at the next interval tick.
+    fn mark_dirty(&self) {
+        self.shared.state.lock().dirty = true;
+    }
+
+    /// Run a last flush if anything is pending and stop the thread.
+    fn stop(&mut self) {
+        self.shared.state.lock().shutdown = true;
+        self.shared.wake.notify_one();
+        if let Some(thread) = self.thread.take()
+            && thread.join().is_err()
+        {
+            tracing::warn!("store flusher thread panicked");
+        }
+    }
+}
+
+impl Drop for StoreFlusher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn flusher_loop(
+    shared: &FlusherShared,
+    store: &StoreFlushHandle,
+    wal: &wal::AsyncWalHandle,
+    transactions: &RwLock<TransactionManager>,
+    interval: Duration,
+) {
+    // LSN of the last marker written; the log has not moved while the
+    // submitted LSN still equals it.
+    let mut last_marker = wal.submitted_lsn();
+    loop {
+        let (pending, shutdown) = {
+            let mut state = shared.state.lock();
+            if !state.requested && !state.shutdown {
+                shared.wake.wait_for(&mut state, interval);
+            }
+            let pending = std::mem::take(&mut state.requested) | std::mem::take(&mut state.dirty);
+            (pending, state.shutdown)
+        };
+        if pending || wal.submitted_lsn() > last_marker {
+            let covered = wal.submitted_lsn();
+            let epoch = transactions.read().current_epoch();
+            let flushed = store.flush().and_then(|()| {
+                wal.append_with(|lsn| WalEntry::StoreFlush {
+                    epoch,
+                    unflushed_tail: lsn - covered - 1,
+                })
+            });
+            match flushed {
+                Ok(marker) => {
+                    last_marker = marker;
+                    shared.flushed_lsn.store(covered, Ordering::Relaxed);
+                    shared.flushes.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(e) => {
+                    shared.errors.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!("background store flush failed: {e}");
+                    // Retry at the next tick.
+                    shared.state.lock().dirty = true;
+                }
+            }
+        }
+        if shutdown {
+            return;
+        }
+    }
+}
+
+impl Engine {
+    /// Switch the commit path. Starting the pipelined mode spawns the
+    /// background store flusher; leaving it stops the flusher after a
+    /// final flush.
+    pub fn set_write_pipeline_config(&mut self, config: WritePipelineConfig) -> Result<()> {
+        if let Some(mut flusher) = self.store_flusher.take() {
+            flusher.stop();
+        }
+        self.write_pipeline = config;
+        if config.commit_mode == CommitMode::Pipelined
+            && let Some(writer) = &self.async_wal_writer
+        {
+            self.store_flusher = Some(StoreFlusher::start(
+                self.storage.flush_handle(),
+                writer.handle(),
+                Arc::clone(&self.transaction_manager),
+                Duration::from_millis(config.store_flush_interval_ms.max(1)),
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Commit path settings in effect.
+    pub fn write_pipeline_config(&self) -> WritePipelineConfig {
+        self.write_pipeline
+    }
+
+    /// Commit path counters.
+    pub fn write_pipeline_stats(&self) -> WritePipelineStats {
+        let (wal_submitted_lsn, wal_durable_lsn) = self
+            .async_wal_writer
+            .as_ref()
+            .map(|w| (w.submitted_lsn(), w.durable_lsn()))
+            .unwrap_or_default();
+        let (store_flushed_lsn, store_flushes, store_flush_errors) = self
+            .store_flusher
+            .as_ref()
+            .map(|flusher| {
+                let shared = &flusher.shared;
+                (
+                    shared.flushed_lsn.load(Ordering::Relaxed),
+                    shared.flushes.load(Ordering::Relaxed),
+                    shared.errors.load(Ordering::Relaxed),
+                )
+            })
+            .unwrap_or_default();
+        WritePipelineStats {
+            commit_mode: self.write_pipeline.commit_mode,
+            wal_submitted_lsn,
+            wal_durable_lsn,
+            store_flushed_lsn,
+            store_flushes,
+            store_flush_errors,
+        }
+    }
+
+    /// Make a committing transaction with `mutations` durable according
+    /// to the commit mode.
+    pub(super) fn commit_durably(&mut self, mutations: &MutationSet) -> Result<()> {
+        if self.store_flusher.is_none() {
+            return self.storage.flush();
+        }
+        self.journal_record_images(mutations)?;
+        self.flush_async_wal()?;
+        if let Some(flusher) = &self.store_flusher {
+            flusher.request();
+        }
+        Ok(())
+    }
+
+    /// Journal the current state of every record `mutations` touched:
+    /// an image for live records, a delete for the others. A new
+    /// relationship also changes its endpoints' first-relationship
+    /// pointers, so those nodes are imaged too.
+    fn journal_record_images(&mut self, mutations: &MutationSet) -> Result<()> {
+        let mut nodes = Vec::new();
+        let mut rels = Vec::new();
+        for mutation in mutations {
+            match *mutation {
+                Mutation::NodeCreated { node_id, .. }
+                | Mutation::NodeUpdated { node_id }
+                | Mutation::NodeDeleted { node_id } => nodes.push(node_id),
+                Mutation::RelationshipCreated {
+                    rel_id, src, dst, ..
+                } => {
+                    rels.push(rel_id);
+                    nodes.extend([src, dst]);
+                }
+                Mutation::RelationshipDeleted { rel_id } => rels.push(rel_id),
+            }
+        }
+        nodes.sort_unstable();
+        nodes.dedup();
+        rels.sort_unstable();
+        rels.dedup();
+
+        for node_id in nodes {
+            let entry = match self.storage.read_node(node_id) {
+                Ok(record) if !record.is_deleted() => WalEntry::NodeImage {
+                    node_id,
+                    record: bytemuck::bytes_of(&record).to_vec(),
+                    properties: property_bytes(self.storage.load_node_properties(node_id)?)?,
+                },
+                _ => WalEntry::DeleteNode { node_id },
+            };
+            self.write_wal_async(entry)?;
+        }
+        for rel_id in rels {
+            let entry = match self.storage.read_rel(rel_id) {
+                Ok(record) if !record.is_deleted() => WalEntry::RelImage {
+                    rel_id,
+                    record: bytemuck::bytes_of(&record).to_vec(),
+                    properties: property_bytes(self.storage.load_relationship_properties(rel_id)?)?,
+                },
+                _ => WalEntry::DeleteRel { rel_id },
+            };
+            self.write_wal_async(entry)?;
+        }
+        Ok(())
+    }
+
+    /// Note an autocommit write for the next background store flush.
+    pub(super) fn note_store_write(&self) {
+        if let Some(flusher) = &self.store_flusher {
+            flusher.mark_dirty();
+        }
+    }
+
+    /// Sync the stores and journal a marker with an empty tail, so the
+    /// entries before it stop counting as possibly unflushed. A no-op
+    /// outside the pipelined mode.
+    pub(super) fn mark_stores_flushed(&mut self) -> Result<()> {
+        if self.store_flusher.is_none() {
+            return Ok(());
+        }
+        self.storage.flush()?;
+        let epoch = self.transaction_manager.read().current_epoch();
+        self.write_wal_async(WalEntry::StoreFlush {
+            epoch,
+            unflushed_tail: 0,
+        })
+    }
+
+    /// Re-apply the deletes and record images journalled after the
+    /// latest store flush marker, in log order. Both overwrite whole
+    /// records, so applying one again is harmless; a create the crash
+    /// lost without an image is left for the integrity scan to report.
+    pub(super) fn replay_unflushed_store_tail(&mut self) -> Result<()> {
+        self.flush_async_wal()?;
+        let entries = match wal::Wal::new(self.wal.path())?.recover() {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("store tail recovery: could not read WAL: {e}");
+                return Ok(());
+            }
+        };
+        let mut replayed = 0usize;
+        for entry in unflushed_tail(&entries) {
+            let applied = match entry {
+                WalEntry::DeleteNode { node_id } if self.node_is_live(*node_id) => {
+                    self.storage.delete_node(*node_id)
+                }
+                WalEntry::DeleteRel { rel_id } if self.rel_is_live(*rel_id) => {
+                    self.storage.delete_rel(*rel_id)
+                }
+                WalEntry::NodeImage {
+                    node_id,
+                    record,
+                    properties,
+                } => decode_image::<NodeRecord>(record, properties).and_then(
+                    |(record, properties)| self.storage.restore_node(*node_id, record, properties),
+                ),
+                WalEntry::RelImage {
+                    rel_id,
+                    record,
+                    properties,
+                } => decode_image::<RelationshipRecord>(record, properties).and_then(
+                    |(record, properties)| {
+                        self.storage
+                            .restore_relationship(*rel_id, record, properties)
+                    },
+                ),
+                _ => continue,
+            };
+            match applied {
+                Ok(()) => replayed += 1,
+                Err(e) => tracing::warn!("store tail recovery: could not replay {entry:?}: {e}"),
+            }
+        }
+        if replayed > 0 {
+            tracing::info!("store tail recovery: re-applied {replayed} change(s) from the WAL");
+            self.storage.flush()?;
+            self.rebuild_indexes_from_storage()?;
+        }
+        Ok(())
+    }
+
+    /// Report journalled changes after the latest store flush marker
+    /// that the store files do not reflect.
+    pub(super) fn check_unflushed_store_tail(
+        &self,
+        entries: &[WalEntry],
+        report: &mut IntegrityReport,
+    ) {
+        for entry in unflushed_tail(entries) {
+            match *entry {
+                // A label-less node may still be all zeroes on disk,
+                // which is indistinguishable from a lost record.
+                WalEntry::CreateNode {
+                    node_id,
+                    label_bits,
+                } if label_bits != 0 && node_id >= self.storage.node_count() => {
+                    report.push(
+                        "store_flush",
+                        format!("node {node_id} is in the WAL but missing from the node store"),
+                    );
+                }
+                WalEntry::CreateRel { rel_id, .. }
+                    if rel_id >= self.storage.relationship_count() =>
+                {
+                    report.push(
+                        "store_flush",
+                        format!(
+                            "relationship {rel_id} is in the WAL but missing from the relationship store"
+                        ),
+                    );
+                }
+                WalEntry::DeleteNode { node_id } if self.node_is_live(node_id) => {
+                    report.push(
+                        "store_flush",
+                        format!("node {node_id} is deleted in the WAL but live in the node store"),
+                    );
+                }
+                WalEntry::DeleteRel { rel_id } if self.rel_is_live(rel_id) => {
+                    report.push(
+                        "store_flush",
+                        format!(
+                            "relationship {rel_id} is deleted in the WAL but live in the relationship store"
+                        ),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn node_is_live(&self, node_id: u64) -> bool {
+        node_id < self.storage.node_count()
+            && self
+                .storage
+                .read_node(node_id)
+                .is_ok_and(|record| !record.is_deleted())
+    }
+
+    fn rel_is_live(&self, rel_id: u64) -> bool {
+        rel_id < self.storage.relationship_count()
+            && self
+                .storage
+                .read_rel(rel_id)
+                .is_ok_and(|record| !record.is_deleted())
+    }
+}
+
+/// Property map of a record image: JSON, or empty for none.
+fn property_bytes(properties: Option<serde_json::Value>) -> Result<Vec<u8>> {
+    match properties {
+        Some(properties) if !properties.is_null() => Ok(serde_json::to_vec(&properties)?),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Decode a [`WalEntry::NodeImage`] / [`WalEntry::RelImage`] payload.
+fn decode_image<R: bytemuck::Pod>(
+    record: &[u8],
+    properties: &[u8],
+) -> Result<(R, Option<serde_json::Value>)> {
+    let record = bytemuck::try_pod_read_unaligned(record)
+        .map_err(|e| Error::wal(format!("malformed record image: {e}")))?;
+    let properties = if properties.is_empty() {
+        None
+    } else {
+        Some(serde_json::from_slice(properties)?)
+    };
+    Ok((record, properties))
+}
+
+/// Entries whose store change the latest [`WalEntry::StoreFlush`]
+/// marker does not vouch for: its unflushed tail and everything after
+/// it. Empty when the log has no marker, i.e. the pipelined mode never
+/// ran against it.
+fn unflushed_tail(entries: &[WalEntry]) -> impl Iterator<Item = &WalEntry> {
+    let start = entries
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(i, entry)| match entry {
+            WalEntry::StoreFlush { unflushed_tail, .. } => {
+                Some(i.saturating_sub(*unflushed_tail as usize))
+            }
+            _ => None,
+        })
+        .unwrap_or(entries.len());
+    entries[start..]
+        .iter()
+        .filter(|entry| !matches!(entry, WalEntry::StoreFlush { .. }))
+}
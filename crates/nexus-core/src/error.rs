@@ -82,9 +82,10 @@ pub enum Error {
     #[error("Not found: {0}")]
     NotFound(String),
 
-    /// Constraint violation (UNIQUE, NOT NULL, etc.)
+    /// Constraint violation (UNIQUE, NOT NULL, etc.) with the
+    /// offending label, property, value and conflicting entity
     #[error("Constraint violation: {0}")]
-    ConstraintViolation(String),
+    ConstraintViolation(Box<crate::constraints::ConstraintViolation>),
 
     /// Type mismatch errors
     #[error("Type mismatch: expected {expected}, got {actual}")]
@@ -139,6 +140,12 @@ pub enum Error {
     #[error("Quota exceeded: {0}")]
     QuotaExceeded(String),
 
+    /// The engine is read-only — a startup integrity scan found
+    /// corruption under `CorruptionPolicy::ReadOnly`. Produced by every
+    /// write path; reads are still served.
+    #[error("Read-only: {0}")]
+    ReadOnly(String),
+
     /// An external id already maps to a different node.
     ///
     /// Returned when `ConflictPolicy::Error` is active and the supplied
@@ -225,4 +232,12 @@ impl Error {
     pub fn replication(msg: impl Into<String>) -> Self {
         Self::Replication(msg.into())
     }
+
+    /// Structured details of a constraint violation, if this is one
+    pub fn constraint_violation(&self) -> Option<&crate::constraints::ConstraintViolation> {
+        match self {
+            Self::ConstraintViolation(details) => Some(details),
+            _ => None,
+        }
+    }
 }
@@ -125,6 +125,8 @@ pub fn benchmark_executor_creation() -> Result<()> {
         enable_numa_caching: false,
         enable_lock_free_structures: true,
         cypher_concurrency: 4,
+        query_timeout_ms: 0,
+        max_result_rows: 0,
     };
 
     let _executor = Executor::new_with_config(
@@ -149,6 +151,8 @@ pub fn benchmark_executor_creation() -> Result<()> {
         enable_numa_caching: false,
         enable_lock_free_structures: false,
         cypher_concurrency: 1,
+        query_timeout_ms: 0,
+        max_result_rows: 0,
     };
 
     let _executor =
@@ -70,6 +70,10 @@ pub struct ExecutionContext {
     /// fails. `None` for everything else (the storage layer's own
     /// commit semantics provide durability).
     pub(super) undo_buffer: Option<CompensatingUndoBuffer>,
+    /// Property keys each label-scan variable needs, from
+    /// `planner::property_projections`. Scans of a variable listed here
+    /// load only those keys; everything else is fully materialised.
+    pub(super) property_projections: HashMap<String, Vec<String>>,
 }
 
 impl ExecutionContext {
@@ -84,6 +88,7 @@ impl ExecutionContext {
             cache,
             plan_hints: Vec::new(),
             undo_buffer: None,
+            property_projections: HashMap::new(),
         }
     }
 
@@ -115,6 +120,21 @@ impl ExecutionContext {
         self.plan_hints = hints;
     }
 
+    /// Install the per-variable property projections computed for the
+    /// plan about to run.
+    pub(in crate::executor) fn set_property_projections(
+        &mut self,
+        projections: HashMap<String, Vec<String>>,
+    ) {
+        self.property_projections = projections;
+    }
+
+    /// Keys a scan of `variable` has to load, or `None` when the node
+    /// must be materialised with every property.
+    pub(in crate::executor) fn property_projection(&self, variable: &str) -> Option<&[String]> {
+        self.property_projections.get(variable).map(Vec::as_slice)
+    }
+
     /// Decide whether the columnar fast path should run over a batch
     /// of `row_count` rows.
     ///
@@ -0,0 +1,222 @@
+//! Pool of reusable [`ExecutionContext`]s for the top-level query path.
+//!
+//! Every `Executor::execute` used to build a fresh context: a variable
+//! map, a result set and its row / column vectors, all grown from empty
+//! and dropped again a few microseconds later. Under concurrent load that
+//! churn lands on the global allocator from every worker thread at once.
+//! The pool keeps a bounded stack of cleared contexts (with their
+//! capacity intact) shared by every clone of an executor, so a steady
+//! stream of queries reuses the same few allocations.
+//!
+//! Contexts are handed out wrapped in a [`PooledContext`] guard that puts
+//! them back on drop, which keeps the many early returns in
+//! `execute_inner` pool-safe without any bookkeeping at the call sites.
+
+use super::context::ExecutionContext;
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Default number of idle contexts kept per executor.
+pub const DEFAULT_CONTEXT_POOL_CAPACITY: usize = 64;
+
+/// Contexts whose row vector grew past this many rows give the vector
+/// back instead of pinning a large buffer in the pool.
+const MAX_RETAINED_ROWS: usize = 4096;
+
+/// Same, for the variable map.
+const MAX_RETAINED_VARIABLES: usize = 256;
+
+/// Counters reported by [`ContextPool::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextPoolStats {
+    /// Contexts handed out since the pool was created.
+    pub acquired: u64,
+    /// How many of those were reused instead of freshly allocated.
+    pub reused: u64,
+    /// Idle contexts currently held.
+    pub idle: usize,
+}
+
+/// Bounded stack of idle execution contexts.
+pub struct ContextPool {
+    idle: Mutex<Vec<ExecutionContext>>,
+    capacity: AtomicUsize,
+    acquired: AtomicU64,
+    reused: AtomicU64,
+}
+
+impl Default for ContextPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONTEXT_POOL_CAPACITY)
+    }
+}
+
+impl ContextPool {
+    /// Create a pool holding at most `capacity` idle contexts. `0`
+    /// disables pooling: every acquire allocates and every release drops.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            idle: Mutex::new(Vec::with_capacity(capacity)),
+            capacity: AtomicUsize::new(capacity),
+            acquired: AtomicU64::new(0),
+            reused: AtomicU64::new(0),
+        }
+    }
+
+    /// Change how many idle contexts are kept. Shrinking drops the
+    /// surplus immediately.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        self.idle.lock().truncate(capacity);
+    }
+
+    /// Counters for benches and diagnostics.
+    pub fn stats(&self) -> ContextPoolStats {
+        ContextPoolStats {
+            acquired: self.acquired.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            idle: self.idle.lock().len(),
+        }
+    }
+
+    /// Take a context primed with `params` and `cache`, reusing an idle
+    /// one when available.
+    pub(in crate::executor) fn acquire(
+        self: &Arc<Self>,
+        params: HashMap<String, Value>,
+        cache: Option<Arc<parking_lot::RwLock<crate::cache::MultiLayerCache>>>,
+    ) -> PooledContext {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        let context = match self.idle.lock().pop() {
+            Some(mut context) => {
+                self.reused.fetch_add(1, Ordering::Relaxed);
+                context.params = params;
+                context.cache = cache;
+                context
+            }
+            None => ExecutionContext::new(params, cache),
+        };
+        PooledContext {
+            context: Some(context),
+            pool: Arc::clone(self),
+        }
+    }
+
+    fn release(&self, mut context: ExecutionContext) {
+        if self.capacity.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        reset(&mut context);
+        let mut idle = self.idle.lock();
+        if idle.len() < self.capacity.load(Ordering::Relaxed) {
+            idle.push(context);
+        }
+    }
+}
+
+/// Clear everything a query left behind while keeping modest buffers.
+fn reset(context: &mut ExecutionContext) {
+    context.params = HashMap::new();
+    context.cache = None;
+    context.plan_hints.clear();
+    context.undo_buffer = None;
+    context.property_projections.clear();
+
+    if context.variables.capacity() > MAX_RETAINED_VARIABLES {
+        context.variables = HashMap::new();
+    } else {
+        context.variables.clear();
+    }
+
+    let result_set = &mut context.result_set;
+    if result_set.rows.capacity() > MAX_RETAINED_ROWS {
+        result_set.rows = Vec::new();
+    } else {
+        result_set.rows.clear();
+    }
+    result_set.columns.clear();
+    result_set.notifications.clear();
+}
+
+/// An [`ExecutionContext`] on loan from a [`ContextPool`]; returned to
+/// the pool when dropped.
+pub struct PooledContext {
+    context: Option<ExecutionContext>,
+    pool: Arc<ContextPool>,
+}
+
+impl Deref for PooledContext {
+    type Target = ExecutionContext;
+
+    fn deref(&self) -> &ExecutionContext {
+        self.context
+            .as_ref()
+            .expect("pooled context already released")
+    }
+}
+
+impl DerefMut for PooledContext {
+    fn deref_mut(&mut self) -> &mut ExecutionContext {
+        self.context
+            .as_mut()
+            .expect("pooled context already released")
+    }
+}
+
+impl Drop for PooledContext {
+    fn drop(&mut self) {
+        if let Some(context) = self.context.take() {
+            self.pool.release(context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_contexts_are_cleared_and_reused() {
+        let pool = Arc::new(ContextPool::new(2));
+        {
+            let mut context =
+                pool.acquire(HashMap::from([("p".to_string(), Value::from(1))]), None);
+            context.set_variable("n", Value::from("node"));
+            context.result_set.columns.push("n".to_string());
+        }
+        assert_eq!(pool.stats().idle, 1);
+
+        let context = pool.acquire(HashMap::new(), None);
+        assert!(context.variables.is_empty());
+        assert!(context.params.is_empty());
+        assert!(context.result_set.columns.is_empty());
+        drop(context);
+
+        let stats = pool.stats();
+        assert_eq!((stats.acquired, stats.reused, stats.idle), (2, 1, 1));
+    }
+
+    #[test]
+    fn zero_capacity_disables_pooling() {
+        let pool = Arc::new(ContextPool::new(0));
+        drop(pool.acquire(HashMap::new(), None));
+        drop(pool.acquire(HashMap::new(), None));
+        let stats = pool.stats();
+        assert_eq!((stats.reused, stats.idle), (0, 0));
+    }
+
+    #[test]
+    fn oversized_row_buffers_are_not_retained() {
+        let pool = Arc::new(ContextPool::new(1));
+        {
+            let mut context = pool.acquire(HashMap::new(), None);
+            context.result_set.rows.reserve(MAX_RETAINED_ROWS + 1);
+        }
+        let context = pool.acquire(HashMap::new(), None);
+        assert_eq!(context.result_set.rows.capacity(), 0);
+    }
+}
@@ -10,6 +10,7 @@
 use super::*;
 use crate::catalog::Catalog;
 use crate::index::{KnnIndex, LabelIndex};
+use crate::performance::plan_history;
 use crate::query_cache::{IntelligentQueryCache, QueryCacheConfig};
 use crate::storage::RecordStore;
 use crate::{Error, Result};
@@ -130,8 +131,31 @@ impl Executor {
         // panic-aborted call before planning the new query. Equivalent
         // to a clear, but reuses the existing drain helper.
         let _stale = planner::queries::drain_pending_planner_notifications();
+        let _stale_plan = plan_history::take_pending_plan_shape();
+        let started = self
+            .shared
+            .plan_history()
+            .map(|_| std::time::Instant::now());
+        let scope = query_limits::QueryScope::enter(self.config.query_timeout_ms);
+        // Taken before planning, so the subqueries and views this query
+        // runs don't see it.
+        let row_limit = query_limits::take_row_limit();
+
+        let mut result = self.execute_inner(query, row_limit)?;
+        if let Some(limit) = row_limit {
+            result.rows.truncate(limit);
+        }
+        if scope.is_outermost() {
+            query_limits::check_result_rows(result.rows.len(), self.config.max_result_rows)?;
+        }
 
-        let mut result = self.execute_inner(query)?;
+        // Record the plan `execute_inner` stashed (only when the engine
+        // installed a plan history) together with the elapsed time.
+        if let (Some(history), Some(started)) = (self.shared.plan_history(), started)
+            && let Some(plan) = plan_history::take_pending_plan_shape()
+        {
+            history.record(&query.cypher, plan, started.elapsed());
+        }
 
         // Attach planner-level diagnostics produced for this call.
         // Vec is empty in the hot path (no unindexed access), so this
@@ -148,8 +172,14 @@ impl Executor {
     /// downstream sub-query operators (`call_subquery`) that want to
     /// avoid double-attaching notifications can dispatch through here
     /// directly; main callers should always go through [`Self::execute`].
+    /// A `row_limit` is planned into a read as a trailing `LIMIT` (see
+    /// [`query_limits::limit_returned_rows`]).
     #[tracing::instrument(skip_all, level = "debug", fields(cypher = %query.cypher))]
-    pub(super) fn execute_inner(&self, query: &Query) -> Result<ResultSet> {
+    pub(super) fn execute_inner(
+        &self,
+        query: &Query,
+        row_limit: Option<usize>,
+    ) -> Result<ResultSet> {
         // Increment query counter for lazy cache warming
         let current_count = self
             .query_count
@@ -169,10 +199,25 @@ impl Executor {
         // upstream label / type rewrite that produced tenant
         // isolation in the first place.
         let preparsed = self.shared.preparsed_ast_override.lock().take();
-        let operators = match preparsed {
-            Some(ast) => self.plan_ast(&ast)?,
-            None => self.parse_and_plan(&cleaned_cypher)?,
+        let operators = match (preparsed, row_limit) {
+            (Some(ast), None) => self.plan_ast(&ast)?,
+            (None, None) => self.parse_and_plan(&cleaned_cypher)?,
+            (preparsed, Some(rows)) => {
+                let mut ast = match preparsed {
+                    Some(ast) => ast,
+                    None => parser::CypherParser::new(cleaned_cypher.to_string()).parse()?,
+                };
+                if !crate::cluster::scope::is_write_query(&ast) {
+                    query_limits::limit_returned_rows(&mut ast, rows);
+                }
+                self.plan_ast(&ast)?
+            }
         };
+        if self.shared.plan_history().is_some() {
+            plan_history::stash_plan_shape(|| plan_history::plan_shape(&operators));
+        }
+        // Per-operator rows and timings, when a PROFILE armed a recording.
+        let mut operator_timer = plan_output::OperatorTimer::start(&operators);
 
         // TODO: JIT and Parallel execution - implement after core optimizations
         // For now, focus on proven optimizations: columnar, SIMD, caching
@@ -236,8 +281,16 @@ impl Executor {
 
         // Columnar storage framework ready - will be activated in next phase
 
-        // Try direct execution for simple queries (bypass operator overhead)
-        if !is_write_query && self.is_simple_match_query(&query.cypher) {
+        // Try direct execution for simple queries (bypass operator overhead).
+        // Skipped under PROFILE, which reports each operator separately.
+        // Also skipped under a row limit, which only the plan applies
+        // before the rows are produced.
+        if !is_write_query
+            && !operator_timer.is_active()
+            && row_limit.is_none()
+            && self.is_simple_match_query(&query.cypher)
+            && !self.may_use_views(&query.cypher)
+        {
             if let Ok(result) = self.execute_simple_match_directly(query) {
                 tracing::trace!("Direct execution optimization used");
                 return Ok(result);
@@ -264,8 +317,12 @@ impl Executor {
             "Starting query execution, creating new ExecutionContext for query: {}",
             query.cypher
         );
-        let mut context = ExecutionContext::new(query.params.clone(), self.shared.cache.clone());
+        let mut context = self
+            .shared
+            .context_pool
+            .acquire(query.params.clone(), self.shared.cache.clone());
         context.set_plan_hints(plan_hints);
+        context.set_property_projections(planner::property_projections(&operators));
         tracing::trace!(
             "New ExecutionContext created: variables.len()={}, result_set.rows.len()={}",
             context.variables.len(),
@@ -290,6 +347,7 @@ impl Executor {
             let policy = operators::create::ast_conflict_policy_to_storage(*conflict_policy);
             let existing_rows = self.materialize_rows_from_variables(&context);
             if existing_rows.is_empty() {
+                operator_timer.enter(0, 0);
                 // CREATE standalone - create nodes and relationships directly
                 let (mut created_node_ids, mut created_rel_ids) = self
                     .execute_create_pattern_with_variables(
@@ -375,7 +433,9 @@ impl Executor {
 
                 // Skip CREATE operator in loop since we already executed it
                 // Continue with remaining operators (if any)
-                for (_idx, operator) in operators.iter().enumerate().skip(1) {
+                for (idx, operator) in operators.iter().enumerate().skip(1) {
+                    query_limits::check_deadline()?;
+                    operator_timer.enter(idx, context.result_set.rows.len());
                     match operator {
                         Operator::Project { items } => {
                             projection_columns =
@@ -411,6 +471,8 @@ impl Executor {
                     }
                 }
 
+                operator_timer.finish(context.result_set.rows.len());
+
                 // Return early with populated result_set
                 let final_columns = if !context.result_set.columns.is_empty() {
                     context.result_set.columns.clone()
@@ -466,13 +528,18 @@ impl Executor {
                 _ => format!("{:?}", std::mem::discriminant(operator)),
             };
             tracing::trace!("EXECUTING OPERATOR #{}: {}", op_idx, op_name);
+            query_limits::check_deadline()?;
+            operator_timer.enter(op_idx, context.result_set.rows.len());
             // Check if there's still an Aggregate operator ahead in the pipeline
             let has_aggregate_ahead = operators[op_idx + 1..]
                 .iter()
                 .any(|op| matches!(op, Operator::Aggregate { .. }));
             match operator {
                 Operator::NodeByLabel { label_id, variable } => {
-                    let nodes = self.execute_node_by_label(*label_id)?;
+                    let nodes = self.execute_node_by_label_projected(
+                        *label_id,
+                        context.property_projection(variable),
+                    )?;
                     self.seed_scan_main_loop(&mut context, variable, nodes)?;
                 }
                 Operator::NodeIndexSeek {
@@ -490,6 +557,18 @@ impl Executor {
                     let nodes = self.execute_node_index_seek(*label_id, *key_id, value)?;
                     self.seed_scan_main_loop(&mut context, variable, nodes)?;
                 }
+                Operator::NodeBloomProbe {
+                    label_id,
+                    key_id,
+                    value,
+                    variable,
+                } => {
+                    // Label scan that is skipped outright when the bloom
+                    // filter proves `value` was never stored; residual
+                    // `Filter` operators do the actual matching.
+                    let nodes = self.execute_node_bloom_probe(*label_id, *key_id, value)?;
+                    self.seed_scan_main_loop(&mut context, variable, nodes)?;
+                }
                 Operator::AllNodesScan { variable } => {
                     let nodes = self.execute_all_nodes_scan()?;
                     context.variables.remove(variable);
@@ -532,6 +611,20 @@ impl Executor {
                         None, // Cache not available at this level
                     )?;
                 }
+                Operator::ExpandCount {
+                    type_ids,
+                    direction,
+                    source_var,
+                    count_column,
+                } => {
+                    self.execute_expand_count(
+                        &mut context,
+                        type_ids,
+                        *direction,
+                        source_var,
+                        count_column,
+                    )?;
+                }
                 Operator::Project { items } => {
                     projection_columns = items.iter().map(|item| item.alias.clone()).collect();
                     // Check if Project contains collect argument items (__collect_arg_*)
@@ -934,6 +1027,8 @@ impl Executor {
             }
         }
 
+        operator_timer.finish(context.result_set.rows.len());
+
         let final_columns = if !context.result_set.columns.is_empty() {
             context.result_set.columns.clone()
         } else if !projection_columns.is_empty() {
@@ -942,8 +1037,10 @@ impl Executor {
             vec![]
         };
 
+        // Move the rows out rather than cloning them: the context goes
+        // back to the pool cleared anyway.
         let final_rows = if !context.result_set.rows.is_empty() {
-            context.result_set.rows.clone()
+            std::mem::take(&mut context.result_set.rows)
         } else if !results.is_empty() {
             results
         } else {
@@ -1153,13 +1250,13 @@ impl Executor {
     /// phase8_neo4j-concurrency-gaps §1 — reads every node header in a
     /// single [`crate::storage::RecordStore::read_all_node_headers`]
     /// call and indexes into the resulting in-memory snapshot for each
-    /// bitmap member, instead of taking a fresh `nodes_mmap` lock per
+    /// bitmap member, instead of taking a fresh `nodes_file` lock per
     /// candidate node. See that method's doc comment for the full
     /// contention analysis (this scenario's 16w-\>64w collapse: 2.5k -\>
     /// 2.9k qps flat, p99 124ms, while Neo4j scaled to 13k).
     pub(super) fn count_live_nodes_for_label(&self, label_id: u32) -> Result<u64> {
         let bitmap = self.label_index().get_nodes(label_id)?;
-        let headers = self.store().read_all_node_headers();
+        let headers = self.store().read_all_node_headers()?;
         let mut count = 0u64;
         for node_id in bitmap.iter() {
             if let Some(node_record) = headers.get(node_id as usize) {
@@ -1182,7 +1279,7 @@ impl Executor {
     /// RETURN count(n)`), which walks every node in the store on every
     /// call — the scenario this fix targets directly.
     pub(super) fn count_live_nodes_all(&self) -> Result<u64> {
-        let headers = self.store().read_all_node_headers();
+        let headers = self.store().read_all_node_headers()?;
         Ok(headers.iter().filter(|n| !n.is_deleted()).count() as u64)
     }
 
@@ -1278,6 +1375,11 @@ impl Executor {
     /// correctness bug, so there is no second code path that does
     /// anything else.
     pub fn plan_ast(&self, ast: &parser::CypherQuery) -> Result<Vec<Operator>> {
+        // Saved views used as labels become id filters over the view's
+        // result before anything else looks at the pattern.
+        let ast = self.expand_views(ast)?;
+        let ast = ast.as_ref();
+
         // Clone index data instead of holding locks during planning.
         // This reduces lock contention and allows better parallelization.
         let label_index_snapshot = {
@@ -1301,11 +1403,15 @@ impl Executor {
         if let Some(pi) = self.property_index() {
             planner = planner.with_property_index(pi);
         }
+        if let Some(pb) = self.property_bloom() {
+            planner = planner.with_property_bloom(pb);
+        }
 
         let mut operators = planner.plan_query(ast)?;
 
         // Optimize the operator order
         operators = planner.optimize_operator_order(operators)?;
+        operators = planner::push_down_neighbor_counts(operators);
 
         // Bridge planner-level diagnostics across the planner-drop
         // boundary so `Executor::execute` can attach them to the
@@ -157,6 +157,18 @@ impl Executor {
         self.config.columnar_threshold = threshold;
     }
 
+    /// Set how many idle execution contexts the shared context pool
+    /// keeps (see [`super::context_pool`]). `0` turns pooling off; the
+    /// setting applies to every clone of this executor.
+    pub fn set_context_pool_capacity(&self, capacity: usize) {
+        self.shared.context_pool.set_capacity(capacity);
+    }
+
+    /// Acquire / reuse counters of the shared context pool.
+    pub fn context_pool_stats(&self) -> super::context_pool::ContextPoolStats {
+        self.shared.context_pool.stats()
+    }
+
     /// Run the filter operator over an in-memory working set.
     ///
     /// Builds a fresh `ExecutionContext`, binds `rows` to `variable`,
@@ -274,6 +286,76 @@ impl Executor {
         self.shared.property_index()
     }
 
+    /// Share the engine's property bloom filters with this executor.
+    /// Called from `Engine::refresh_executor`; subsequent calls are no-ops.
+    pub(crate) fn install_property_bloom(&self, registry: crate::index::PropertyBloomRegistry) {
+        self.shared.set_property_bloom(registry);
+    }
+
+    /// Borrow the property bloom filters installed by the engine.
+    /// Returns `None` for executors built outside an engine (test harness).
+    pub(super) fn property_bloom(&self) -> Option<&crate::index::PropertyBloomRegistry> {
+        self.shared.property_bloom()
+    }
+
+    /// Share the engine's vector index registry with this executor.
+    /// Called from `Engine::refresh_executor`; subsequent calls are no-ops.
+    pub(crate) fn install_vector_indexes(&self, registry: crate::index::VectorIndexRegistry) {
+        self.shared.set_vector_indexes(registry);
+    }
+
+    /// Borrow the vector index registry installed by the engine.
+    /// Returns `None` for executors built outside an engine (test harness).
+    pub(super) fn vector_indexes(&self) -> Option<&crate::index::VectorIndexRegistry> {
+        self.shared.vector_indexes()
+    }
+
+    /// Deliver this executor's commits to the engine's commit hooks.
+    /// Called from `Engine::refresh_executor`; repeated calls are harmless.
+    pub(crate) fn install_commit_hooks(&self, hooks: crate::transaction::CommitHooks) {
+        self.shared
+            .transaction_manager
+            .lock()
+            .share_commit_hooks(hooks);
+    }
+
+    /// Share the engine's count aggregates with this executor.
+    /// Called from `Engine::refresh_executor`; subsequent calls are no-ops.
+    pub(crate) fn install_count_aggregates(&self, registry: crate::index::CountAggregateRegistry) {
+        self.shared.set_count_aggregates(registry);
+    }
+
+    /// Borrow the count aggregates installed by the engine.
+    /// Returns `None` for executors built outside an engine (test harness).
+    pub(super) fn count_aggregates(&self) -> Option<&crate::index::CountAggregateRegistry> {
+        self.shared.count_aggregates()
+    }
+
+    /// Share the engine's remote data limits with this executor.
+    /// Called from `Engine::refresh_executor`; subsequent calls are no-ops.
+    pub(crate) fn install_external_data(
+        &self,
+        config: std::sync::Arc<crate::external_data::ExternalDataConfig>,
+    ) {
+        self.shared.set_external_data(config);
+    }
+
+    /// Remote data limits installed by the engine. Returns `None` for
+    /// executors built outside an engine, which cannot load remote data.
+    pub(super) fn external_data(&self) -> Option<&crate::external_data::ExternalDataConfig> {
+        self.shared.external_data()
+    }
+
+    /// Share the engine's plan history with this executor, so executed
+    /// queries record their plan and latency into it.
+    /// Called from `Engine::refresh_executor`; subsequent calls are no-ops.
+    pub(crate) fn install_plan_history(
+        &self,
+        history: crate::performance::plan_history::PlanHistory,
+    ) {
+        self.shared.set_plan_history(history);
+    }
+
     /// Share the engine's full-text search registry with this executor.
     pub(crate) fn install_fulltext(
         &self,
@@ -1,8 +1,14 @@
 //! Arithmetic coercions for `parser::Expression` binary ops over JSON
 //! values. Every op normalises both sides to f64 (or integer where
 //! possible) and re-boxes back into `Value::Number`.
+//!
+//! INTEGER arithmetic never leaves i64: a result outside the range
+//! fails with `ERR_INTEGER_OVERFLOW` rather than turning into a FLOAT
+//! that has already lost digits. DECIMAL operands go through
+//! [`super::decimal`] and stay exact.
 
 use super::super::engine::Executor;
+use super::decimal::{DecimalOp, try_decimal_arithmetic};
 use crate::{Error, Result};
 use serde_json::Value;
 
@@ -31,6 +37,13 @@ fn both_as_i64(left: &Value, right: &Value) -> Option<(i64, i64)> {
     Some((l, r))
 }
 
+fn integer_overflow(l: i64, op: &str, r: i64) -> Error {
+    Error::CypherExecution(format!(
+        "ERR_INTEGER_OVERFLOW: {l} {op} {r} is outside the INTEGER range; \
+         use toFloat() or toDecimal() on an operand for a wider result"
+    ))
+}
+
 impl Executor {
     pub(in crate::executor) fn add_values(&self, left: &Value, right: &Value) -> Result<Value> {
         // Handle null values - null + number or number + null = null (Neo4j behavior)
@@ -60,14 +73,16 @@ impl Executor {
             return Ok(result);
         }
 
+        if let Some(result) = try_decimal_arithmetic(DecimalOp::Add, left, right)? {
+            return Ok(result);
+        }
+
         // phase6 §4 — preserve integer typing when both operands are ints.
         if let Some((li, ri)) = both_as_i64(left, right) {
-            if let Some(sum) = li.checked_add(ri) {
-                return Ok(Value::Number(serde_json::Number::from(sum)));
-            }
-            // Integer overflow — fall through to f64 path so the result
-            // is still produceable (matches Neo4j's behaviour of
-            // promoting only on overflow-would-happen).
+            return li
+                .checked_add(ri)
+                .map(|sum| Value::Number(sum.into()))
+                .ok_or_else(|| integer_overflow(li, "+", ri));
         }
 
         // Otherwise, treat as numeric addition
@@ -106,10 +121,15 @@ impl Executor {
             return Ok(result);
         }
 
+        if let Some(result) = try_decimal_arithmetic(DecimalOp::Subtract, left, right)? {
+            return Ok(result);
+        }
+
         if let Some((li, ri)) = both_as_i64(left, right) {
-            if let Some(diff) = li.checked_sub(ri) {
-                return Ok(Value::Number(serde_json::Number::from(diff)));
-            }
+            return li
+                .checked_sub(ri)
+                .map(|diff| Value::Number(diff.into()))
+                .ok_or_else(|| integer_overflow(li, "-", ri));
         }
 
         let l = self.value_to_number(left)?;
@@ -131,10 +151,14 @@ impl Executor {
         if left.is_null() || right.is_null() {
             return Ok(Value::Null);
         }
+        if let Some(result) = try_decimal_arithmetic(DecimalOp::Multiply, left, right)? {
+            return Ok(result);
+        }
         if let Some((li, ri)) = both_as_i64(left, right) {
-            if let Some(prod) = li.checked_mul(ri) {
-                return Ok(Value::Number(serde_json::Number::from(prod)));
-            }
+            return li
+                .checked_mul(ri)
+                .map(|prod| Value::Number(prod.into()))
+                .ok_or_else(|| integer_overflow(li, "*", ri));
         }
         let l = self.value_to_number(left)?;
         let r = self.value_to_number(right)?;
@@ -154,6 +178,9 @@ impl Executor {
         // phase6 §4 — Cypher integer division: int / int stays int
         // (`100 / 4 = 25`, `7 / 2 = 3`). Only promote to float if either
         // operand is itself a float.
+        if let Some(result) = try_decimal_arithmetic(DecimalOp::Divide, left, right)? {
+            return Ok(result);
+        }
         if let Some((li, ri)) = both_as_i64(left, right) {
             if ri == 0 {
                 return Err(Error::TypeMismatch {
@@ -161,10 +188,11 @@ impl Executor {
                     actual: "division by zero".to_string(),
                 });
             }
-            // i64::MIN / -1 overflows — fall through to f64 in that case.
-            if let Some(q) = li.checked_div(ri) {
-                return Ok(Value::Number(serde_json::Number::from(q)));
-            }
+            // i64::MIN / -1 is the one quotient that overflows.
+            return li
+                .checked_div(ri)
+                .map(|q| Value::Number(q.into()))
+                .ok_or_else(|| integer_overflow(li, "/", ri));
         }
         let l = self.value_to_number(left)?;
         let r = self.value_to_number(right)?;
@@ -206,6 +234,9 @@ impl Executor {
             return Ok(Value::Null);
         }
 
+        if let Some(result) = try_decimal_arithmetic(DecimalOp::Modulo, left, right)? {
+            return Ok(result);
+        }
         if let Some((li, ri)) = both_as_i64(left, right) {
             if ri == 0 {
                 return Err(Error::TypeMismatch {
@@ -213,9 +244,8 @@ impl Executor {
                     actual: "modulo by zero".to_string(),
                 });
             }
-            if let Some(m) = li.checked_rem_euclid(ri) {
-                return Ok(Value::Number(serde_json::Number::from(m)));
-            }
+            // Only i64::MIN % -1 fails, and its remainder is 0.
+            return Ok(Value::Number(li.checked_rem_euclid(ri).unwrap_or(0).into()));
         }
 
         let l = self.value_to_number(left)?;
@@ -0,0 +1,547 @@
+//! Exact decimal (`DECIMAL`) helpers for the Cypher executor.
+//!
+//! Like BYTES, a DECIMAL travels through the runtime as a single-key
+//! object, `{"_decimal": "<digits>"}`, so it survives every JSON hop
+//! (wire, storage, parameters) without passing through `f64`. The
+//! string is always canonical — no exponent, no leading `+`, no
+//! trailing fractional zeros — so two equal decimals are also equal as
+//! JSON values.
+//!
+//! Arithmetic is exact: the value is an `i128` mantissa with a base-10
+//! scale, giving 38 significant digits. `+`, `-`, `*` and `%` never
+//! round; `/` rounds half-to-even at [`DIVISION_SCALE`] fractional
+//! digits. Anything that would need more digits fails with
+//! `ERR_DECIMAL_OVERFLOW` instead of losing precision.
+//!
+//! DECIMAL mixes with INTEGER (the integer converts exactly) but not
+//! with FLOAT — `toDecimal()` / `toFloat()` make that conversion
+//! explicit.
+
+use crate::{Error, Result};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Key of the DECIMAL wire shape.
+pub(crate) const DECIMAL_KEY: &str = "_decimal";
+
+/// Fractional digits kept by decimal division.
+pub(crate) const DIVISION_SCALE: u32 = 16;
+
+/// Most significant digits a DECIMAL can carry (`i128` range).
+pub(crate) const MAX_DECIMAL_DIGITS: u32 = 38;
+
+/// Exact base-10 number: `mantissa × 10^-scale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+/// Arithmetic operators [`try_decimal_arithmetic`] covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DecimalOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+impl DecimalOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            DecimalOp::Add => "+",
+            DecimalOp::Subtract => "-",
+            DecimalOp::Multiply => "*",
+            DecimalOp::Divide => "/",
+            DecimalOp::Modulo => "%",
+        }
+    }
+}
+
+fn overflow() -> Error {
+    Error::CypherExecution(format!(
+        "ERR_DECIMAL_OVERFLOW: result needs more than {MAX_DECIMAL_DIGITS} significant digits"
+    ))
+}
+
+fn pow10(exp: u32) -> Result<i128> {
+    10i128.checked_pow(exp).ok_or_else(overflow)
+}
+
+impl Decimal {
+    /// Parse `[-+]digits[.digits]`. Exponents are rejected so the text
+    /// a user wrote is always the value they get.
+    pub(crate) fn parse(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::CypherExecution(format!(
+                "ERR_INVALID_DECIMAL: '{s}' is not a decimal number"
+            ))
+        };
+        let t = s.trim();
+        let (negative, digits) = match t.as_bytes().first() {
+            Some(b'-') => (true, &t[1..]),
+            Some(b'+') => (false, &t[1..]),
+            _ => (false, t),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        if (int_part.is_empty() && frac_part.is_empty())
+            || !int_part.bytes().all(|b| b.is_ascii_digit())
+            || !frac_part.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        let mut mantissa: i128 = 0;
+        for b in int_part.bytes().chain(frac_part.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(i128::from(b - b'0')))
+                .ok_or_else(overflow)?;
+        }
+        let scale = u32::try_from(frac_part.len()).map_err(|_| overflow())?;
+        Ok(Self::new(
+            if negative { -mantissa } else { mantissa },
+            scale,
+        ))
+    }
+
+    /// `mantissa × 10^-scale`, with trailing fractional zeros stripped.
+    fn new(mut mantissa: i128, mut scale: u32) -> Self {
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        if mantissa == 0 {
+            scale = 0;
+        }
+        Self { mantissa, scale }
+    }
+
+    pub(crate) fn from_i64(i: i64) -> Self {
+        Self {
+            mantissa: i128::from(i),
+            scale: 0,
+        }
+    }
+
+    /// Exact conversion of the shortest decimal text that round-trips
+    /// `f`. Fails on NaN / infinity.
+    pub(crate) fn from_f64(f: f64) -> Result<Self> {
+        if !f.is_finite() {
+            return Err(Error::CypherExecution(format!(
+                "ERR_INVALID_DECIMAL: {f} has no decimal representation"
+            )));
+        }
+        Self::parse(&f.to_string())
+    }
+
+    pub(crate) fn to_f64(self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    /// Integer part, truncated toward zero. `None` outside i64 range.
+    pub(crate) fn trunc_to_i64(self) -> Option<i64> {
+        let divisor = 10i128.checked_pow(self.scale)?;
+        i64::try_from(self.mantissa / divisor).ok()
+    }
+
+    /// Both mantissas at the larger of the two scales.
+    fn aligned(self, other: Self) -> Result<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let a = self
+            .mantissa
+            .checked_mul(pow10(scale - self.scale)?)
+            .ok_or_else(overflow)?;
+        let b = other
+            .mantissa
+            .checked_mul(pow10(scale - other.scale)?)
+            .ok_or_else(overflow)?;
+        Ok((a, b, scale))
+    }
+
+    pub(crate) fn checked_add(self, other: Self) -> Result<Self> {
+        let (a, b, scale) = self.aligned(other)?;
+        Ok(Self::new(a.checked_add(b).ok_or_else(overflow)?, scale))
+    }
+
+    pub(crate) fn checked_sub(self, other: Self) -> Result<Self> {
+        let (a, b, scale) = self.aligned(other)?;
+        Ok(Self::new(a.checked_sub(b).ok_or_else(overflow)?, scale))
+    }
+
+    pub(crate) fn checked_mul(self, other: Self) -> Result<Self> {
+        let mantissa = self
+            .mantissa
+            .checked_mul(other.mantissa)
+            .ok_or_else(overflow)?;
+        let scale = self.scale.checked_add(other.scale).ok_or_else(overflow)?;
+        if scale > MAX_DECIMAL_DIGITS {
+            return Err(overflow());
+        }
+        Ok(Self::new(mantissa, scale))
+    }
+
+    /// Quotient rounded half-to-even at [`DIVISION_SCALE`] digits.
+    pub(crate) fn checked_div(self, other: Self) -> Result<Self> {
+        if other.mantissa == 0 {
+            return Err(Error::TypeMismatch {
+                expected: "non-zero".to_string(),
+                actual: "division by zero".to_string(),
+            });
+        }
+        // self.m / 10^s1 ÷ other.m / 10^s2 = (self.m × 10^(s2 - s1 + D)) / other.m / 10^D
+        let shift = i64::from(other.scale) - i64::from(self.scale) + i64::from(DIVISION_SCALE);
+        let (numerator, denominator) = if shift >= 0 {
+            let factor = pow10(u32::try_from(shift).map_err(|_| overflow())?)?;
+            (
+                self.mantissa.checked_mul(factor).ok_or_else(overflow)?,
+                other.mantissa,
+            )
+        } else {
+            let factor = pow10(u32::try_from(-shift).map_err(|_| overflow())?)?;
+            (
+                self.mantissa,
+                other.mantissa.checked_mul(factor).ok_or_else(overflow)?,
+            )
+        };
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        // Round half to even on |remainder| vs |denominator| / 2.
+        let twice = remainder.unsigned_abs() * 2;
+        let half_way = twice.cmp(&denominator.unsigned_abs());
+        let round_away = match half_way {
+            Ordering::Greater => true,
+            Ordering::Equal => quotient % 2 != 0,
+            Ordering::Less => false,
+        };
+        let quotient = if round_away {
+            let step = if (numerator < 0) != (denominator < 0) {
+                -1
+            } else {
+                1
+            };
+            quotient.checked_add(step).ok_or_else(overflow)?
+        } else {
+            quotient
+        };
+        Ok(Self::new(quotient, DIVISION_SCALE))
+    }
+
+    /// Euclidean remainder, matching INTEGER `%`.
+    pub(crate) fn checked_rem(self, other: Self) -> Result<Self> {
+        if other.mantissa == 0 {
+            return Err(Error::TypeMismatch {
+                expected: "non-zero".to_string(),
+                actual: "modulo by zero".to_string(),
+            });
+        }
+        let (a, b, scale) = self.aligned(other)?;
+        Ok(Self::new(
+            a.checked_rem_euclid(b).ok_or_else(overflow)?,
+            scale,
+        ))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let sign = self.mantissa.signum().cmp(&other.mantissa.signum());
+        if sign != Ordering::Equal || self.mantissa == 0 {
+            return sign;
+        }
+        match self.aligned(*other) {
+            Ok((a, b, _)) => a.cmp(&b),
+            // Same sign, and rescaling only overflows for the side with
+            // fewer fractional digits — the one with the larger
+            // magnitude.
+            Err(_) => {
+                let magnitude = if self.scale < other.scale {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
+                if self.mantissa < 0 {
+                    magnitude.reverse()
+                } else {
+                    magnitude
+                }
+            }
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{sign}{digits}");
+        }
+        if digits.len() > scale {
+            let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+            write!(f, "{sign}{int_part}.{frac_part}")
+        } else {
+            write!(f, "{sign}0.{digits:0>scale$}")
+        }
+    }
+}
+
+/// True iff `v` is the `{"_decimal": "<digits>"}` wire shape.
+pub(crate) fn is_decimal_value(v: &Value) -> bool {
+    matches!(v, Value::Object(map) if map.len() == 1 && matches!(map.get(DECIMAL_KEY), Some(Value::String(_))))
+}
+
+/// Decode a `{"_decimal": "<digits>"}` value.
+pub(crate) fn decimal_of(v: &Value) -> Result<Decimal> {
+    match v {
+        Value::Object(map) => match map.get(DECIMAL_KEY) {
+            Some(Value::String(s)) if map.len() == 1 => Decimal::parse(s),
+            _ => Err(Error::TypeMismatch {
+                expected: "DECIMAL".to_string(),
+                actual: "MAP".to_string(),
+            }),
+        },
+        other => Err(Error::TypeMismatch {
+            expected: "DECIMAL".to_string(),
+            actual: super::projection::type_name_of(other).to_string(),
+        }),
+    }
+}
+
+/// Encode `d` in the wire shape.
+pub(crate) fn decimal_to_value(d: Decimal) -> Value {
+    let mut map = Map::with_capacity(1);
+    map.insert(DECIMAL_KEY.to_string(), Value::String(d.to_string()));
+    Value::Object(map)
+}
+
+/// `toDecimal()` semantics: INTEGER and DECIMAL convert exactly, FLOAT
+/// through its shortest round-trip text, STRING through
+/// [`Decimal::parse`]. Anything else is a type error.
+pub(crate) fn to_decimal(v: &Value) -> Result<Decimal> {
+    match v {
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(Decimal::from_i64(i))
+            } else if let Some(u) = n.as_u64() {
+                Decimal::parse(&u.to_string())
+            } else {
+                Decimal::from_f64(n.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        Value::String(s) => Decimal::parse(s),
+        other if is_decimal_value(other) => decimal_of(other),
+        other => Err(Error::TypeMismatch {
+            expected: "INTEGER, FLOAT, STRING or DECIMAL".to_string(),
+            actual: super::projection::type_name_of(other).to_string(),
+        }),
+    }
+}
+
+/// Decimal operand for arithmetic: DECIMAL as is, INTEGER exactly.
+/// FLOAT is refused — mixing would bring back the rounding DECIMAL
+/// exists to avoid.
+fn arithmetic_operand(v: &Value, op: DecimalOp) -> Result<Decimal> {
+    match v {
+        Value::Number(n) if n.is_f64() => Err(Error::CypherExecution(format!(
+            "ERR_DECIMAL_FLOAT_MIX: DECIMAL {} FLOAT is not exact; convert with toDecimal() or toFloat() first",
+            op.symbol()
+        ))),
+        Value::Number(_) => to_decimal(v),
+        other if is_decimal_value(other) => decimal_of(other),
+        other => Err(Error::TypeMismatch {
+            expected: "DECIMAL or INTEGER".to_string(),
+            actual: super::projection::type_name_of(other).to_string(),
+        }),
+    }
+}
+
+/// Apply `op` when either operand is a DECIMAL. `Ok(None)` means neither
+/// is, and the caller should use its own numeric path.
+pub(crate) fn try_decimal_arithmetic(
+    op: DecimalOp,
+    left: &Value,
+    right: &Value,
+) -> Result<Option<Value>> {
+    if !is_decimal_value(left) && !is_decimal_value(right) {
+        return Ok(None);
+    }
+    let l = arithmetic_operand(left, op)?;
+    let r = arithmetic_operand(right, op)?;
+    let result = match op {
+        DecimalOp::Add => l.checked_add(r)?,
+        DecimalOp::Subtract => l.checked_sub(r)?,
+        DecimalOp::Multiply => l.checked_mul(r)?,
+        DecimalOp::Divide => l.checked_div(r)?,
+        DecimalOp::Modulo => l.checked_rem(r)?,
+    };
+    Ok(Some(decimal_to_value(result)))
+}
+
+/// Ordering between two values when at least one is a DECIMAL and the
+/// other is a DECIMAL or a number. FLOAT compares through `f64`.
+pub(crate) fn compare_with_decimal(left: &Value, right: &Value) -> Option<Ordering> {
+    if !is_decimal_value(left) && !is_decimal_value(right) {
+        return None;
+    }
+    let as_f64 = |v: &Value| match v {
+        Value::Number(n) => n.as_f64(),
+        other => decimal_of(other).ok().map(Decimal::to_f64),
+    };
+    let float_side = |v: &Value| matches!(v, Value::Number(n) if n.is_f64());
+    if float_side(left) || float_side(right) {
+        return as_f64(left)?.partial_cmp(&as_f64(right)?);
+    }
+    let operand = |v: &Value| match v {
+        Value::Number(_) => to_decimal(v).ok(),
+        other => decimal_of(other).ok(),
+    };
+    Some(operand(left)?.cmp(&operand(right)?))
+}
+
+/// `sum()` over INTEGER / DECIMAL inputs without going through `f64`.
+///
+/// All-INTEGER input sums exactly in i64 and fails with
+/// `ERR_INTEGER_OVERFLOW` when the total does not fit; input with a
+/// DECIMAL sums as DECIMAL. `None` means some input is a FLOAT (or
+/// not a number) and no DECIMAL is involved, so the caller's float
+/// path applies.
+pub(crate) fn exact_sum<'a>(values: impl IntoIterator<Item = &'a Value>) -> Option<Result<Value>> {
+    let values: Vec<&Value> = values.into_iter().filter(|v| !v.is_null()).collect();
+    if values.is_empty() {
+        return None;
+    }
+    if values.iter().any(|v| is_decimal_value(v)) {
+        let total = values.iter().try_fold(Decimal::from_i64(0), |acc, v| {
+            acc.checked_add(arithmetic_operand(v, DecimalOp::Add)?)
+        });
+        return Some(total.map(decimal_to_value));
+    }
+    let mut total: i64 = 0;
+    for v in values {
+        let Value::Number(n) = v else { return None };
+        if n.is_f64() {
+            return None;
+        }
+        let Some(i) = n.as_i64() else { return None };
+        match total.checked_add(i) {
+            Some(t) => total = t,
+            None => {
+                return Some(Err(Error::CypherExecution(
+                    "ERR_INTEGER_OVERFLOW: sum() exceeds the INTEGER range; use toDecimal() on the inputs for an exact total".to_string(),
+                )));
+            }
+        }
+    }
+    Some(Ok(Value::Number(total.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::parse(s).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_are_canonical() {
+        assert_eq!(dec("123.450").to_string(), "123.45");
+        assert_eq!(dec("+0.000").to_string(), "0");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert_eq!(dec("7.").to_string(), "7");
+        assert!(Decimal::parse("1e5").is_err());
+        assert!(Decimal::parse("1.2.3").is_err());
+        assert!(Decimal::parse("").is_err());
+        assert!(Decimal::parse(&"9".repeat(40)).is_err());
+    }
+
+    #[test]
+    fn add_sub_mul_are_exact() {
+        assert_eq!(dec("0.1").checked_add(dec("0.2")).unwrap(), dec("0.3"));
+        assert_eq!(dec("10.00").checked_sub(dec("0.01")).unwrap(), dec("9.99"));
+        assert_eq!(dec("1.5").checked_mul(dec("-2.25")).unwrap(), dec("-3.375"));
+        assert!(
+            dec(&"9".repeat(38))
+                .checked_add(dec("1"))
+                .unwrap_err()
+                .to_string()
+                .contains("ERR_DECIMAL_OVERFLOW")
+        );
+    }
+
+    #[test]
+    fn division_rounds_half_to_even() {
+        assert_eq!(
+            dec("1").checked_div(dec("3")).unwrap().to_string(),
+            "0.3333333333333333"
+        );
+        assert_eq!(
+            dec("2").checked_div(dec("3")).unwrap().to_string(),
+            "0.6666666666666667"
+        );
+        assert_eq!(dec("10").checked_div(dec("4")).unwrap(), dec("2.5"));
+        assert_eq!(dec("-1").checked_div(dec("8")).unwrap(), dec("-0.125"));
+        assert!(dec("1").checked_div(dec("0")).is_err());
+    }
+
+    #[test]
+    fn ordering_is_numeric_and_survives_rescale_overflow() {
+        assert!(dec("2.5") > dec("2.49"));
+        assert!(dec("-3") < dec("-2.999"));
+        let huge = dec(&"9".repeat(37));
+        let tiny = dec("0.00000000000000000001");
+        assert!(huge > tiny);
+        assert!(huge.checked_sub(dec("1")).unwrap() < huge);
+    }
+
+    #[test]
+    fn arithmetic_mixes_with_integer_but_not_float() {
+        let price = json!({"_decimal": "19.99"});
+        assert_eq!(
+            try_decimal_arithmetic(DecimalOp::Multiply, &price, &json!(3))
+                .unwrap()
+                .unwrap(),
+            json!({"_decimal": "59.97"})
+        );
+        let err = try_decimal_arithmetic(DecimalOp::Add, &price, &json!(0.01)).unwrap_err();
+        assert!(err.to_string().contains("ERR_DECIMAL_FLOAT_MIX"));
+        assert!(
+            try_decimal_arithmetic(DecimalOp::Add, &json!(1), &json!(2))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn exact_sum_keeps_integers_and_decimals_exact() {
+        let big = json!(i64::MAX - 1);
+        assert_eq!(
+            exact_sum([&big, &json!(1)]).unwrap().unwrap(),
+            json!(i64::MAX)
+        );
+        let err = exact_sum([&big, &json!(2)]).unwrap().unwrap_err();
+        assert!(err.to_string().contains("ERR_INTEGER_OVERFLOW"));
+        assert_eq!(
+            exact_sum([
+                &json!({"_decimal": "0.1"}),
+                &json!({"_decimal": "0.2"}),
+                &json!(1)
+            ])
+            .unwrap()
+            .unwrap(),
+            json!({"_decimal": "1.3"})
+        );
+        assert!(exact_sum([&json!(1), &json!(0.5)]).is_none());
+    }
+}
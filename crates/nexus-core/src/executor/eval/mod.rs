@@ -9,6 +9,7 @@
 
 pub mod arithmetic;
 pub mod bytes;
+pub mod decimal;
 pub mod helpers;
 pub mod predicate;
 pub mod projection;
@@ -396,6 +396,10 @@ impl Executor {
         left: &Value,
         right: &Value,
     ) -> bool {
+        // DECIMAL against DECIMAL / INTEGER compares exactly
+        if let Some(ordering) = super::decimal::compare_with_decimal(left, right) {
+            return ordering == std::cmp::Ordering::Equal;
+        }
         match (left, right) {
             (Value::Number(a), Value::Number(b)) => {
                 // Compare numbers (handle int/float conversion)
@@ -453,6 +457,16 @@ impl Executor {
     where
         F: FnOnce(f64, f64) -> bool,
     {
+        // Integers and decimals are ordered exactly; `compare_fn` then
+        // sees the ordering as -1 / 0 / 1 against 0 so a large INTEGER
+        // is never rounded through f64 first.
+        let exact = match (left, right) {
+            (Value::Number(a), Value::Number(b)) => Some(compare_numbers(a, b)),
+            _ => super::decimal::compare_with_decimal(left, right),
+        };
+        if let Some(ordering) = exact {
+            return Ok(compare_fn(ordering as i8 as f64, 0.0));
+        }
         let left_num = self.value_to_number(left)?;
         let right_num = self.value_to_number(right)?;
         Ok(compare_fn(left_num, right_num))
@@ -470,6 +484,8 @@ impl Executor {
                 actual: "string".to_string(),
             }),
             Value::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+            // DECIMAL feeds float-only consumers (`avg`, `^`, ...)
+            v if super::decimal::is_decimal_value(v) => Ok(super::decimal::decimal_of(v)?.to_f64()),
             Value::Null => Err(Error::TypeMismatch {
                 expected: "number".to_string(),
                 actual: "null".to_string(),
@@ -529,13 +545,7 @@ impl Executor {
             (Value::Null, Value::Null) => std::cmp::Ordering::Equal,
             (Value::Null, _) => std::cmp::Ordering::Less,
             (_, Value::Null) => std::cmp::Ordering::Greater,
-            (Value::Number(a_num), Value::Number(b_num)) => {
-                let a_f64 = a_num.as_f64().unwrap_or(0.0);
-                let b_f64 = b_num.as_f64().unwrap_or(0.0);
-                a_f64
-                    .partial_cmp(&b_f64)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            }
+            (Value::Number(a_num), Value::Number(b_num)) => compare_numbers(a_num, b_num),
             (Value::String(a_str), Value::String(b_str)) => a_str.cmp(b_str),
             (Value::Bool(a_bool), Value::Bool(b_bool)) => a_bool.cmp(b_bool),
             (Value::Array(a_arr), Value::Array(b_arr)) => match a_arr.len().cmp(&b_arr.len()) {
@@ -551,6 +561,9 @@ impl Executor {
                 other => other,
             },
             _ => {
+                if let Some(ordering) = super::decimal::compare_with_decimal(a, b) {
+                    return ordering;
+                }
                 // Convert to strings for comparison
                 let a_str = self.value_to_string(a);
                 let b_str = self.value_to_string(b);
@@ -571,3 +584,36 @@ impl Executor {
         }
     }
 }
+
+/// Numeric ordering that keeps INTEGER comparisons exact: two integers
+/// compare as integers, an integer against a float breaks f64 ties on
+/// the integer value, and only float pairs go through `partial_cmp`.
+pub(in crate::executor) fn compare_numbers(
+    a: &serde_json::Number,
+    b: &serde_json::Number,
+) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    if let (Some(x), Some(y)) = (a.as_i64(), b.as_i64()) {
+        return x.cmp(&y);
+    }
+    if let (Some(x), Some(y)) = (a.as_u64(), b.as_u64()) {
+        return x.cmp(&y);
+    }
+    let int_vs_float = |i: i64, f: f64| match (i as f64).partial_cmp(&f) {
+        // `i as f64 == f` means `f` is whole; 2^63 is the only such
+        // float beyond every i64.
+        Some(Ordering::Equal) if f >= 9_223_372_036_854_775_808.0 => Ordering::Less,
+        Some(Ordering::Equal) => i.cmp(&(f as i64)),
+        Some(other) => other,
+        None => Ordering::Equal,
+    };
+    match (a.as_i64(), b.as_i64()) {
+        (Some(i), None) if b.is_f64() => int_vs_float(i, b.as_f64().unwrap_or(0.0)),
+        (None, Some(i)) if a.is_f64() => int_vs_float(i, a.as_f64().unwrap_or(0.0)).reverse(),
+        _ => {
+            let x = a.as_f64().unwrap_or(0.0);
+            let y = b.as_f64().unwrap_or(0.0);
+            x.partial_cmp(&y).unwrap_or(Ordering::Equal)
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Graph-entity, path, and graph-metadata built-in functions for the
 //! projection evaluator.
 //!
-//! Covers `__label_predicate__`, `labels`, `type`, `keys`, `id`,
+//! Covers `__label_predicate__`, `labels`, `type`, `keys`, `history`, `id`,
 //! `database`, `db`, `nodes`, `relationships`, `length`,
 //! `shortestpath`, `allshortestpaths`, and `exists`.
 
@@ -163,6 +163,62 @@ impl Executor {
                 }
                 Some(Ok(Value::Array(Vec::new())))
             }
+            // `history(n.prop)` — previous values of a property with
+            // change history enabled (`engine::property_history`), oldest
+            // first. The argument names the property rather than being
+            // evaluated, since the history belongs to the node.
+            "history" => {
+                let Some(parser::Expression::PropertyAccess { variable, property }) = args.first()
+                else {
+                    return Some(Err(Error::CypherExecution(
+                        "ERR_INVALID_ARG_TYPE: history() expects a node property such as \
+                         history(n.status)"
+                            .to_string(),
+                    )));
+                };
+                let entity = match self.evaluate_projection_expression(
+                    row,
+                    context,
+                    &parser::Expression::Variable(variable.clone()),
+                ) {
+                    Ok(v) => v,
+                    Err(e) => return Some(Err(e)),
+                };
+                let Value::Object(obj) = &entity else {
+                    return Some(Ok(Value::Null));
+                };
+                let Some(node_id) = obj.get("_nexus_id").and_then(Value::as_u64) else {
+                    return Some(Ok(Value::Null));
+                };
+                // Only node properties are tracked.
+                if obj.contains_key("type") {
+                    return Some(Ok(Value::Array(Vec::new())));
+                }
+                let Ok(key_id) = self.catalog().get_key_id(property) else {
+                    return Some(Ok(Value::Array(Vec::new())));
+                };
+                let entries = match self.catalog().property_history(node_id, key_id) {
+                    Ok(entries) => entries,
+                    Err(e) => return Some(Err(e)),
+                };
+                let history = entries
+                    .into_iter()
+                    .map(|entry| {
+                        let mut change = serde_json::Map::with_capacity(3);
+                        change.insert(
+                            "value".to_string(),
+                            serde_json::from_str(&entry.value_json).unwrap_or(Value::Null),
+                        );
+                        change.insert("timestamp".to_string(), Value::from(entry.timestamp));
+                        change.insert(
+                            "user".to_string(),
+                            entry.user.map(Value::String).unwrap_or(Value::Null),
+                        );
+                        Value::Object(change)
+                    })
+                    .collect();
+                Some(Ok(Value::Array(history)))
+            }
             "id" => {
                 if let Some(arg) = args.first() {
                     let value = match self.evaluate_projection_expression(row, context, arg) {
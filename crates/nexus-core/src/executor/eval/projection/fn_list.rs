@@ -5,13 +5,14 @@
 //! `extract`, `all`, `any`, `none`, `single`, `coalesce`, `flatten`, `zip`,
 //! `exists`, `isempty`, type-check predicates (`isinteger`, `isfloat`,
 //! `isstring`, `isboolean`, `islist`, `ismap`, `isnode`, `isrelationship`,
-//! `ispath`), type-conversion (`tointeger`, `tofloat`, `tostring`,
-//! `toboolean`), and list-coerce variants (`tointegerlist`, `tofloatlist`,
-//! `tostringlist`, `tobooleanlist`).
+//! `ispath`), type-conversion (`tointeger`, `tofloat`, `todecimal`,
+//! `tostring`, `toboolean`), and list-coerce variants (`tointegerlist`,
+//! `tofloatlist`, `tostringlist`, `tobooleanlist`).
 
 use super::super::super::context::ExecutionContext;
 use super::super::super::engine::Executor;
 use super::super::super::parser;
+use super::super::decimal;
 use crate::{Error, Result};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -554,11 +555,41 @@ impl Executor {
                                 return Some(Ok(Value::Number(i.into())));
                             }
                         }
+                        // DECIMAL truncates toward zero
+                        other if decimal::is_decimal_value(&other) => {
+                            return Some(decimal::decimal_of(&other).and_then(|d| {
+                                d.trunc_to_i64()
+                                    .map(|i| Value::Number(i.into()))
+                                    .ok_or_else(|| {
+                                        Error::CypherExecution(format!(
+                                            "ERR_INTEGER_OVERFLOW: {d} is outside the INTEGER range"
+                                        ))
+                                    })
+                            }));
+                        }
                         _ => {}
                     }
                 }
                 Some(Ok(Value::Null))
             }
+            // Exact DECIMAL from INTEGER, FLOAT, STRING or DECIMAL.
+            // Unparseable strings give NULL, like toInteger / toFloat.
+            "todecimal" => {
+                let v = match args.first() {
+                    Some(a) => match self.evaluate_projection_expression(row, context, a) {
+                        Ok(v) => v,
+                        Err(e) => return Some(Err(e)),
+                    },
+                    None => return Some(Ok(Value::Null)),
+                };
+                Some(match v {
+                    Value::Null => Ok(Value::Null),
+                    Value::String(ref s) => Ok(decimal::Decimal::parse(s)
+                        .map(decimal::decimal_to_value)
+                        .unwrap_or(Value::Null)),
+                    other => decimal::to_decimal(&other).map(decimal::decimal_to_value),
+                })
+            }
             "tofloat" => {
                 if let Some(arg) = args.first() {
                     let value = match self.evaluate_projection_expression(row, context, arg) {
@@ -590,6 +621,16 @@ impl Executor {
                                 );
                             }
                         }
+                        other if decimal::is_decimal_value(&other) => {
+                            return Some(decimal::decimal_of(&other).and_then(|d| {
+                                serde_json::Number::from_f64(d.to_f64())
+                                    .map(Value::Number)
+                                    .ok_or_else(|| Error::TypeMismatch {
+                                        expected: "float".to_string(),
+                                        actual: "non-finite".to_string(),
+                                    })
+                            }));
+                        }
                         _ => {}
                     }
                 }
@@ -606,6 +647,9 @@ impl Executor {
                         Value::Number(n) => Ok(Value::String(n.to_string())),
                         Value::Bool(b) => Ok(Value::String(b.to_string())),
                         Value::Null => Ok(Value::Null),
+                        other if decimal::is_decimal_value(&other) => {
+                            decimal::decimal_of(&other).map(|d| Value::String(d.to_string()))
+                        }
                         Value::Array(_) | Value::Object(_) => Ok(Value::String(value.to_string())),
                     });
                 }
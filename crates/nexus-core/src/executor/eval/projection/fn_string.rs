@@ -628,6 +628,20 @@ impl Executor {
                 };
                 Some(Ok(Value::String(normalized)))
             }
+            // Locale-aware comparison (see `crate::text::collation`).
+            // `collate(a, b [, locale [, strength]])` returns -1 / 0 / 1;
+            // `collationKey(s [, locale [, strength]])` returns a STRING
+            // whose plain ordering matches, for use in ORDER BY.
+            "collate" | "collationkey" => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    match self.evaluate_projection_expression(row, context, arg) {
+                        Ok(v) => values.push(v),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                Some(eval_collation(name, values))
+            }
             // phase6_opencypher-advanced-types §1 — BYTES family.
             // Uses the `{"_bytes": "<base64>"}` wire shape so
             // the JSON-based runtime stays unchanged. NULL-in →
@@ -791,3 +805,41 @@ impl Executor {
         }
     }
 }
+
+/// Shared body of `collate()` and `collationKey()`: the string operands
+/// come first (two for `collate`, one for `collationKey`), then the
+/// optional locale and strength. NULL in any position yields NULL.
+fn eval_collation(name: &str, values: Vec<Value>) -> Result<Value> {
+    let operands = if name == "collate" { 2 } else { 1 };
+    if values.len() < operands || values.len() > operands + 2 {
+        return Err(Error::CypherExecution(format!(
+            "ERR_INVALID_ARG_VALUE: {name}() expects {operands} to {} arguments, got {}",
+            operands + 2,
+            values.len()
+        )));
+    }
+    if values.iter().any(Value::is_null) {
+        return Ok(Value::Null);
+    }
+    let strings = values
+        .iter()
+        .map(|v| match v {
+            Value::String(s) => Ok(s.as_str()),
+            other => Err(Error::TypeMismatch {
+                expected: "STRING".to_string(),
+                actual: super::type_name_of(other).to_string(),
+            }),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let locale = strings.get(operands).copied().unwrap_or_default();
+    let strength = match strings.get(operands + 1) {
+        Some(s) => s.parse::<crate::text::Strength>()?,
+        None => crate::text::Strength::default(),
+    };
+    let collator = crate::text::Collator::new(locale, strength);
+    Ok(if operands == 2 {
+        Value::from(collator.compare(strings[0], strings[1]) as i64)
+    } else {
+        Value::String(collator.key(strings[0]))
+    })
+}
@@ -35,6 +35,7 @@ pub(super) fn type_name_of(v: &serde_json::Value) -> &'static str {
         }
         serde_json::Value::String(_) => "STRING",
         serde_json::Value::Array(_) => "LIST",
+        serde_json::Value::Object(_) if super::decimal::is_decimal_value(v) => "DECIMAL",
         serde_json::Value::Object(_) => "MAP",
     }
 }
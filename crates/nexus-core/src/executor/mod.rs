@@ -12,6 +12,8 @@
 
 /// Runtime execution context (variables, params, result set)
 pub mod context;
+/// Pool of reusable execution contexts for the top-level query path
+pub mod context_pool;
 /// Main `Executor` dispatch: `execute`, `execute_inner`, planning helpers,
 /// query-cache accessors, and `Default` impl.
 pub(super) mod dispatch;
@@ -24,8 +26,12 @@ pub mod operators;
 /// Query optimizer for cost-based optimization
 pub mod optimizer;
 pub mod parser;
+/// EXPLAIN / PROFILE plan trees and their JSON, text and DOT output
+pub mod plan_output;
 /// Query planner for optimizing Cypher execution
 pub mod planner;
+/// Per-query timeout and result-size limits
+pub(crate) mod query_limits;
 /// Process-wide counters for `serde_json` fallback events. Read by
 /// nexus-server's Prometheus exporter as
 /// `nexus_executor_serde_fallback_total{site=…}`.
@@ -34,9 +40,13 @@ pub mod serde_metrics;
 pub mod shared;
 /// Public types: operators, aggregations, join/index kinds, config
 pub mod types;
+/// Saved-view expansion ahead of planning
+pub(super) mod views;
 
 pub use context::{ExecutionContext, RelationshipInfo};
+pub use context_pool::{ContextPool, ContextPoolStats};
 pub use engine::Executor;
+pub use query_limits::{RowLimitGuard, limit_rows};
 pub use shared::ExecutorShared;
 pub use types::{
     Aggregation, Direction, ExecutionPlan, ExecutorConfig, IndexType, JoinType, Operator,
@@ -241,15 +241,20 @@ impl Executor {
                 .iter()
                 .map(|db| {
                     let is_default = db.name == default_db;
+                    let access = if db.read_only {
+                        "read-only"
+                    } else {
+                        "read-write"
+                    };
                     Row {
                         values: vec![
                             Value::String(db.name.clone()),
                             Value::String("standard".to_string()),
                             Value::Array(vec![]),
-                            Value::String("read-write".to_string()),
+                            Value::String(access.to_string()),
                             Value::String("localhost:7687".to_string()),
                             Value::String("primary".to_string()),
-                            Value::Bool(true),
+                            Value::Bool(!db.read_only),
                             Value::String("online".to_string()),
                             Value::String("online".to_string()),
                             Value::String("".to_string()),
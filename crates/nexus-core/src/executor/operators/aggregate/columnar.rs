@@ -103,6 +103,12 @@ impl Executor {
         column: &str,
         columns_for_lookup: &[String],
     ) -> Option<Value> {
+        // Pure-integer column: sum in i64 like the scalar path. On
+        // overflow fall through to the scalar path, which reports it.
+        if let Some(ints) = self.materialize_i64_column(rows, column, columns_for_lookup) {
+            let sum = ints.iter().try_fold(0i64, |acc, i| acc.checked_add(*i))?;
+            return Some(Value::Number(serde_json::Number::from(sum)));
+        }
         let floats = self.materialize_f64_column(rows, column, columns_for_lookup)?;
         let sum = crate::simd::reduce::sum_f64(&floats);
         // Mirror the scalar path: return an integer `Value::Number`
@@ -618,22 +618,31 @@ impl Executor {
                                 // Virtual row case - return the literal value (1)
                                 Value::Number(serde_json::Number::from(1))
                             } else {
-                                // Calculate sum using extract_value_from_row
-                                let sum: f64 = group_rows
+                                let values: Vec<Value> = group_rows
                                     .iter()
                                     .filter_map(|row| {
                                         self.extract_value_from_row(row, column, columns_for_lookup)
-                                            .and_then(|v| self.value_to_number(&v).ok())
                                     })
-                                    .sum();
-                                // Return sum as integer if whole number, otherwise as float
-                                if sum.fract() == 0.0 {
-                                    Value::Number(serde_json::Number::from(sum as i64))
+                                    .collect();
+                                // INTEGER / DECIMAL inputs sum exactly
+                                if let Some(exact) =
+                                    super::super::super::eval::decimal::exact_sum(&values)
+                                {
+                                    exact?
                                 } else {
-                                    Value::Number(
-                                        serde_json::Number::from_f64(sum)
-                                            .unwrap_or(serde_json::Number::from(0)),
-                                    )
+                                    let sum: f64 = values
+                                        .iter()
+                                        .filter_map(|v| self.value_to_number(v).ok())
+                                        .sum();
+                                    // Return sum as integer if whole number, otherwise as float
+                                    if sum.fract() == 0.0 {
+                                        Value::Number(serde_json::Number::from(sum as i64))
+                                    } else {
+                                        Value::Number(
+                                            serde_json::Number::from_f64(sum)
+                                                .unwrap_or(serde_json::Number::from(0)),
+                                        )
+                                    }
                                 }
                             }
                         }
@@ -685,7 +694,17 @@ impl Executor {
                                     if !val.is_null() {
                                         // Try to convert to number for efficient comparison
                                         if let Ok(num) = self.value_to_number(&val) {
-                                            if min_num.is_none() || num < min_num.unwrap() {
+                                            // f64 ties between distinct large
+                                            // INTEGERs / DECIMALs break exactly.
+                                            let exact_tie = min_num == Some(num)
+                                                && min_val.as_ref().is_some_and(|cur| {
+                                                    self.compare_values_for_sort(&val, cur)
+                                                        == std::cmp::Ordering::Less
+                                                });
+                                            if min_num.is_none()
+                                                || num < min_num.unwrap()
+                                                || exact_tie
+                                            {
                                                 min_num = Some(num);
                                                 min_val = Some(val);
                                             }
@@ -719,7 +738,17 @@ impl Executor {
                                     if !val.is_null() {
                                         // Try to convert to number for efficient comparison
                                         if let Ok(num) = self.value_to_number(&val) {
-                                            if max_num.is_none() || num > max_num.unwrap() {
+                                            // f64 ties between distinct large
+                                            // INTEGERs / DECIMALs break exactly.
+                                            let exact_tie = max_num == Some(num)
+                                                && max_val.as_ref().is_some_and(|cur| {
+                                                    self.compare_values_for_sort(&val, cur)
+                                                        == std::cmp::Ordering::Greater
+                                                });
+                                            if max_num.is_none()
+                                                || num > max_num.unwrap()
+                                                || exact_tie
+                                            {
                                                 max_num = Some(num);
                                                 max_val = Some(val);
                                             }
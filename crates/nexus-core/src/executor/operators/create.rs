@@ -12,6 +12,7 @@ use super::super::context::{ExecutionContext, RelationshipInfo};
 use super::super::engine::Executor;
 use super::super::parser;
 use super::super::types::Row;
+use crate::constraints::ConstraintViolation;
 use crate::{Error, Result};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
@@ -251,6 +252,7 @@ impl Executor {
 
                     // Check constraints before creating node
                     self.check_constraints(&label_ids_for_update, &properties)?;
+                    self.check_node_vectors(&label_ids_for_update, &properties)?;
 
                     // Create the node — route through the external-id path when
                     // an `_id` expression was present in the pattern.
@@ -385,6 +387,10 @@ impl Executor {
                                     serde_json::Value::Null
                                 };
 
+                                self.check_node_vectors(
+                                    &target_label_ids_for_update,
+                                    &target_properties,
+                                )?;
                                 let tid = self.store_mut().create_node_with_label_bits(
                                     &mut tx,
                                     target_label_bits,
@@ -668,10 +674,14 @@ impl Executor {
                                 .catalog()
                                 .get_label_name(label_id)?
                                 .unwrap_or_else(|| format!("ID{}", label_id));
-                            return Err(Error::ConstraintViolation(format!(
-                                "EXISTS constraint violated: property '{}' must exist on nodes with label '{}'",
-                                property_name, label_name
-                            )));
+                            return Err(ConstraintViolation::new("NODE_PROPERTY_EXISTENCE", "NODE")
+                                .label_or_type(label_name.clone())
+                                .value(property_name.clone(), serde_json::Value::Null)
+                                .with_message(format!(
+                                    "EXISTS constraint violated: property '{}' must exist on nodes with label '{}'",
+                                    property_name, label_name
+                                ))
+                                .into_error());
                         }
                     }
                     crate::catalog::constraints::ConstraintType::Unique => {
@@ -692,10 +702,15 @@ impl Executor {
                                 if let Some(serde_json::Value::Object(props_map)) = node_props {
                                     if let Some(existing_value) = props_map.get(&property_name) {
                                         if existing_value == value {
-                                            return Err(Error::ConstraintViolation(format!(
-                                                "UNIQUE constraint violated: property '{}' value already exists on another node with label '{}'",
-                                                property_name, label_name
-                                            )));
+                                            return Err(ConstraintViolation::new("UNIQUENESS", "NODE")
+                                                .label_or_type(label_name.clone())
+                                                .value(property_name.clone(), value.clone())
+                                                .conflicting(node_id_u64)
+                                                .with_message(format!(
+                                                    "UNIQUE constraint violated: property '{}' value already exists on another node with label '{}'",
+                                                    property_name, label_name
+                                                ))
+                                                .into_error());
                                         }
                                     }
                                 }
@@ -709,6 +724,27 @@ impl Executor {
         Ok(())
     }
 
+    /// Reject a node whose vector-indexed properties hold embeddings of
+    /// the wrong length (see
+    /// [`crate::index::VectorIndexRegistry::check_node_vectors`]).
+    pub(in crate::executor) fn check_node_vectors(
+        &self,
+        label_ids: &[u32],
+        properties: &serde_json::Value,
+    ) -> Result<()> {
+        let (Some(vectors), Some(map)) = (self.vector_indexes(), properties.as_object()) else {
+            return Ok(());
+        };
+        vectors.check_node_vectors(
+            |label| {
+                self.catalog()
+                    .get_label_id(label)
+                    .is_ok_and(|label_id| label_ids.contains(&label_id))
+            },
+            map,
+        )
+    }
+
     /// Convert expression to string representation
     pub(in crate::executor) fn expression_to_string(
         &self,
@@ -999,6 +1035,8 @@ impl Executor {
                                 JsonValue::Object(serde_json::Map::new())
                             };
 
+                            self.check_node_vectors(&label_ids, &properties)?;
+
                             // Phase 4.4: route through external-id path when present.
                             let node_id = if let Some(ref ext) = external_id {
                                 self.store_mut()
@@ -50,6 +50,15 @@ impl Executor {
                 );
                 self.seed_scan_variable(context, variable, nodes)?;
             }
+            Operator::NodeBloomProbe {
+                label_id,
+                key_id,
+                value,
+                variable,
+            } => {
+                let nodes = self.execute_node_bloom_probe(*label_id, *key_id, value)?;
+                self.seed_scan_variable(context, variable, nodes)?;
+            }
             Operator::AllNodesScan { variable } => {
                 let nodes = self.execute_all_nodes_scan()?;
 
@@ -89,6 +98,14 @@ impl Executor {
                     None, // Cache not available at this level
                 )?;
             }
+            Operator::ExpandCount {
+                type_ids,
+                direction,
+                source_var,
+                count_column,
+            } => {
+                self.execute_expand_count(context, type_ids, *direction, source_var, count_column)?;
+            }
             Operator::Project { items } => {
                 self.execute_project(context, items)?;
             }
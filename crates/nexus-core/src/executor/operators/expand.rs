@@ -1,7 +1,8 @@
 //! Expand and delete operators. `execute_expand` drives relationship
-//! traversal (with optional LEFT-OUTER semantics); `execute_delete`
-//! is a shim since actual deletion happens at the engine/lib level
-//! before execution reaches here.
+//! traversal (with optional LEFT-OUTER semantics); `execute_expand_count`
+//! is its count-only form; `execute_delete` is a shim since actual
+//! deletion happens at the engine/lib level before execution reaches
+//! here.
 
 use super::super::context::{ExecutionContext, RelationshipInfo};
 use super::super::engine::Executor;
@@ -579,6 +580,65 @@ impl Executor {
         Ok(())
     }
 
+    /// Execute ExpandCount operator: annotate each input row with the
+    /// number of `source_var` relationships matching `type_ids` /
+    /// `direction`, without reading target nodes or emitting a row per
+    /// relationship. Rows whose source has no match are dropped, the same
+    /// rows a non-optional `Expand` would drop.
+    pub(in crate::executor) fn execute_expand_count(
+        &self,
+        context: &mut ExecutionContext,
+        type_ids: &[u32],
+        direction: Direction,
+        source_var: &str,
+        count_column: &str,
+    ) -> Result<()> {
+        let rows = if !context.result_set.rows.is_empty() {
+            self.result_set_as_rows(context)
+        } else {
+            self.materialize_rows_from_variables(context)
+        };
+
+        let mut counted_rows = Vec::with_capacity(rows.len());
+        for mut row in rows {
+            let source_value = row
+                .get(source_var)
+                .cloned()
+                .or_else(|| context.get_variable(source_var).cloned())
+                .unwrap_or(Value::Null);
+            // Same Array edge case as `execute_expand`: every element is
+            // a source node of this row.
+            let sources = match &source_value {
+                Value::Array(values) => values.clone(),
+                other => vec![other.clone()],
+            };
+            let mut count = 0u64;
+            for source in &sources {
+                if let Some(source_id) = Self::extract_entity_id(source) {
+                    count += self
+                        .find_relationships(source_id, type_ids, direction, None)?
+                        .len() as u64;
+                }
+            }
+            if count == 0 {
+                continue;
+            }
+            row.insert(count_column.to_string(), Value::from(count));
+            push_with_row_cap(&mut counted_rows, row, "ExpandCount")?;
+        }
+
+        context.result_set.rows.clear();
+        if counted_rows.is_empty() {
+            // Keep the columns so Aggregate sees that MATCH ran and came
+            // back empty (see the matching branch of `execute_expand`).
+            context.variables.remove(source_var);
+        } else {
+            self.update_variables_from_rows(context, &counted_rows);
+            self.update_result_set_from_rows(context, &counted_rows);
+        }
+        Ok(())
+    }
+
     /// Execute DELETE or DETACH DELETE operator
     /// Note: This collects node IDs but doesn't actually delete them.
     /// Actual deletion must be handled at Engine level (lib.rs) before executor runs.
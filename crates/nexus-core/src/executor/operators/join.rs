@@ -482,11 +482,9 @@ impl Executor {
         b: &Value,
     ) -> std::cmp::Ordering {
         match (a, b) {
-            (Value::Number(x), Value::Number(y)) => x
-                .as_f64()
-                .unwrap_or(0.0)
-                .partial_cmp(&y.as_f64().unwrap_or(0.0))
-                .unwrap_or(std::cmp::Ordering::Equal),
+            (Value::Number(x), Value::Number(y)) => {
+                super::super::eval::predicate::compare_numbers(x, y)
+            }
             (Value::String(x), Value::String(y)) => x.cmp(y),
             _ => std::cmp::Ordering::Equal,
         }
@@ -1437,7 +1437,7 @@ impl Executor {
         // phase8_neo4j-concurrency-gaps §2 — pass the `prop_ptr` this
         // function already read above instead of calling
         // `load_node_properties(node_id)`, which internally re-reads
-        // the node record (a second `nodes_mmap` lock acquisition, plus
+        // the node record (a second `nodes_file` lock acquisition, plus
         // a second `property_store` corruption cross-check) purely to
         // re-derive the same `prop_ptr` we already have. See
         // `RecordStore::load_node_properties_with_ptr`'s doc comment.
@@ -1488,4 +1488,30 @@ impl Executor {
 
         Ok(Value::Object(node))
     }
+
+    /// Read a node carrying only the `keys` properties (plus
+    /// `_nexus_id`). Only valid when the query never observes the node
+    /// as a whole — the planner's projection analysis guarantees that.
+    /// Same guard-reuse contract as [`Self::read_node_as_value_with_store`].
+    pub(in crate::executor) fn read_node_projected_with_store(
+        &self,
+        store: &RecordStore,
+        node_id: u64,
+        keys: &[String],
+    ) -> Result<Value> {
+        let node_record = store.read_node(node_id)?;
+
+        if node_record.is_deleted() {
+            return Ok(Value::Null);
+        }
+
+        let mut node =
+            match store.load_node_properties_projected(node_id, node_record.prop_ptr, keys)? {
+                Some(Value::Object(map)) => map,
+                _ => Map::new(),
+            };
+        node.insert("_nexus_id".to_string(), Value::Number(node_id.into()));
+
+        Ok(Value::Object(node))
+    }
 }
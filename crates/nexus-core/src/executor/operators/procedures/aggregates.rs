@@ -0,0 +1,65 @@
+//! `nexus.aggregate.counts(name)` — reads the running result of a count
+//! aggregate (see `engine/count_aggregates.rs`) without scanning the
+//! label it counts.
+
+use super::super::super::context::ExecutionContext;
+use super::super::super::engine::Executor;
+use super::super::super::parser;
+use super::super::super::types::Row;
+use crate::{Error, Result};
+use serde_json::Value;
+
+impl Executor {
+    /// `CALL nexus.aggregate.counts(name) YIELD group, count`
+    ///
+    /// One row per group, ordered by the group value's JSON text. An
+    /// ungrouped aggregate yields a single row with a NULL group (none
+    /// while no node is counted).
+    pub(in crate::executor) fn execute_aggregate_counts(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.aggregate.counts";
+        let Some(expr) = arguments.first() else {
+            return Err(Error::CypherExecution(format!(
+                "ERR_MISSING_ARG: {NAME} requires `name` at position 0"
+            )));
+        };
+        let name = match self.evaluate_expression_in_context(context, expr)? {
+            Value::String(name) => name,
+            other => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {NAME} `name` must be STRING (got {other})"
+                )));
+            }
+        };
+        let counts = self
+            .count_aggregates()
+            .and_then(|registry| registry.counts(&name))
+            .ok_or_else(|| {
+                Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_VALUE: no count aggregate named {name:?}"
+                ))
+            })?;
+
+        let columns = yield_columns
+            .cloned()
+            .unwrap_or_else(|| vec!["group".to_string(), "count".to_string()]);
+        let rows = counts
+            .into_iter()
+            .map(|(group, count)| Row {
+                values: columns
+                    .iter()
+                    .map(|column| match column.as_str() {
+                        "count" => Value::from(count),
+                        _ => group.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        context.set_columns_and_rows(columns, rows);
+        Ok(())
+    }
+}
@@ -120,6 +120,33 @@ impl Executor {
             "db.index.fulltext.listAvailableAnalyzers" => {
                 return self.execute_fts_list_analyzers(context, yield_columns);
             }
+            "nexus.sample.randomWalk" => {
+                return self.execute_sample_random_walk(context, arguments, yield_columns);
+            }
+            "nexus.sample.nodes" => {
+                return self.execute_sample_nodes(context, arguments, yield_columns);
+            }
+            "nexus.sample.relationships" => {
+                return self.execute_sample_relationships(context, arguments, yield_columns);
+            }
+            "nexus.view" => {
+                return self.execute_view_procedure(context, arguments, yield_columns);
+            }
+            "nexus.aggregate.counts" => {
+                return self.execute_aggregate_counts(context, arguments, yield_columns);
+            }
+            "nexus.load.jsonFromUrl" => {
+                return self.execute_load_json_from_url(context, arguments, yield_columns);
+            }
+            "nexus.load.csvFromUrl" => {
+                return self.execute_load_csv_from_url(context, arguments, yield_columns);
+            }
+            "nexus.load.jsonFromS3" => {
+                return self.execute_load_json_from_s3(context, arguments, yield_columns);
+            }
+            "nexus.load.csvFromS3" => {
+                return self.execute_load_csv_from_s3(context, arguments, yield_columns);
+            }
             _ => {}
         }
 
@@ -313,6 +313,32 @@ impl Executor {
                 ],
             });
         }
+        // Relationship endpoint constraints are keyed by type alone and
+        // carry no properties.
+        let mut endpoints = self
+            .catalog()
+            .list_rel_endpoint_constraints()
+            .unwrap_or_default();
+        endpoints.sort_by_key(|(type_id, _)| *type_id);
+        for (type_id, _) in endpoints {
+            let type_name = self
+                .catalog()
+                .get_type_name(type_id)
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| format!("type_{}", type_id));
+            rows.push(Row {
+                values: vec![
+                    Value::Number(serde_json::Number::from(rows.len() as i64)),
+                    Value::String(format!("constraint_relationship_endpoint_{}", type_name)),
+                    Value::String("RELATIONSHIP_ENDPOINT".to_string()),
+                    Value::String("RELATIONSHIP".to_string()),
+                    Value::Array(vec![Value::String(type_name)]),
+                    Value::Array(Vec::new()),
+                    Value::Null,
+                ],
+            });
+        }
         let columns = if let Some(y) = yield_columns {
             y.clone()
         } else {
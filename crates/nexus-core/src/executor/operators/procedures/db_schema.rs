@@ -4,7 +4,7 @@
 use super::super::super::context::ExecutionContext;
 use super::super::super::engine::Executor;
 use super::super::super::types::Row;
-use crate::Result;
+use crate::{Error, Result};
 
 impl Executor {
     /// Execute db.labels() procedure
@@ -33,14 +33,7 @@ impl Executor {
             })
             .collect();
 
-        // Set columns based on YIELD clause
-        let columns = if let Some(yield_cols) = yield_columns {
-            // Use YIELD columns if specified
-            yield_cols.clone()
-        } else {
-            // Default column name
-            vec!["label".to_string()]
-        };
+        let columns = metadata_yield_columns("db.labels", "label", yield_columns)?;
 
         context.set_columns_and_rows(columns, rows);
         Ok(())
@@ -68,12 +61,7 @@ impl Executor {
             });
         }
 
-        // Set columns based on YIELD clause
-        let columns = if let Some(yield_cols) = yield_columns {
-            yield_cols.clone()
-        } else {
-            vec!["propertyKey".to_string()]
-        };
+        let columns = metadata_yield_columns("db.propertyKeys", "propertyKey", yield_columns)?;
 
         context.set_columns_and_rows(columns, rows);
         Ok(())
@@ -99,12 +87,8 @@ impl Executor {
             })
             .collect();
 
-        // Set columns based on YIELD clause
-        let columns = if let Some(yield_cols) = yield_columns {
-            yield_cols.clone()
-        } else {
-            vec!["relationshipType".to_string()]
-        };
+        let columns =
+            metadata_yield_columns("db.relationshipTypes", "relationshipType", yield_columns)?;
 
         context.set_columns_and_rows(columns, rows);
         Ok(())
@@ -119,11 +103,31 @@ impl Executor {
         // phase8_neo4j-concurrency-gaps §4.1 — same fix as
         // `execute_db_labels_procedure` / `execute_db_relationship_types_procedure`
         // above: single LMDB iteration instead of an O(10000) per-id probe.
+        //
+        // Labels with embedding model bindings also list them, so agents
+        // can see which properties are semantically searchable.
+        let mut embeddings: std::collections::HashMap<u32, Vec<serde_json::Value>> =
+            std::collections::HashMap::new();
+        for ((label_id, key_id), (model, dimensions)) in self.catalog().list_embedding_models()? {
+            if let Some(property) = self.catalog().get_key_name(key_id)? {
+                embeddings
+                    .entry(label_id)
+                    .or_default()
+                    .push(serde_json::json!({
+                        "property": property,
+                        "model": model,
+                        "dimensions": dimensions,
+                    }));
+            }
+        }
         let nodes_array: Vec<serde_json::Value> = self
             .catalog()
             .list_all_labels()
             .into_iter()
-            .map(|(_, name)| serde_json::json!({"name": name}))
+            .map(|(id, name)| match embeddings.remove(&id) {
+                Some(models) => serde_json::json!({"name": name, "embeddings": models}),
+                None => serde_json::json!({"name": name}),
+            })
             .collect();
         let relationships_array: Vec<serde_json::Value> = self
             .catalog()
@@ -183,3 +187,27 @@ impl Executor {
         Ok(())
     }
 }
+
+/// Output columns for the single-column catalog procedures
+/// (`db.labels`, `db.propertyKeys`, `db.relationshipTypes`).
+///
+/// Neo4j rejects a YIELD of a column the procedure does not declare.
+/// Relabelling the column to whatever was yielded instead would let a
+/// typo such as `YIELD labels` return data under the wrong name, which
+/// tooling written against Neo4j's signatures would not notice.
+fn metadata_yield_columns(
+    procedure: &str,
+    column: &str,
+    yield_columns: Option<&Vec<String>>,
+) -> Result<Vec<String>> {
+    if let Some(unknown) = yield_columns
+        .into_iter()
+        .flatten()
+        .find(|c| c.as_str() != column)
+    {
+        return Err(Error::CypherExecution(format!(
+            "Unknown procedure output: `{unknown}` ({procedure} yields `{column}`)"
+        )));
+    }
+    Ok(vec![column.to_string()])
+}
@@ -145,6 +145,102 @@ impl Executor {
                 "DBMS",
                 "Return the caller's identity and roles.",
             ),
+            // Runtime settings (engine-dispatched; see `engine/settings.rs`).
+            (
+                "dbms.setConfigValue",
+                "dbms.setConfigValue(setting :: STRING, value :: STRING) :: (setting :: STRING, \
+              value :: STRING, previousValue :: STRING)",
+                "DBMS",
+                "Change a dynamic setting listed by SHOW SETTINGS and keep it across restarts.",
+            ),
+            // Relationship data-repair procedures (engine-dispatched; see
+            // `engine/refactor.rs`).
+            (
+                "db.relationships.invert",
+                "db.relationships.invert(type :: STRING, batchSize = 10000 :: INTEGER) :: \
+              (updated :: INTEGER, batches :: INTEGER)",
+                "WRITE",
+                "Reverse the direction of every relationship of a type in batched transactions.",
+            ),
+            (
+                "db.relationships.retype",
+                "db.relationships.retype(fromType :: STRING, toType :: STRING, filter = {} :: MAP, \
+              batchSize = 10000 :: INTEGER) :: (updated :: INTEGER, batches :: INTEGER)",
+                "WRITE",
+                "Move relationships matching a property filter to another type in batched transactions.",
+            ),
+            // Entity resolution (engine-dispatched; see `engine/merge_nodes.rs`).
+            (
+                "nexus.merge.nodes",
+                "nexus.merge.nodes(nodes :: LIST<INTEGER>, config = {} :: MAP) :: (nodeId :: INTEGER, \
+              merged :: INTEGER, relationshipsRewired :: INTEGER, relationshipsCollapsed :: INTEGER)",
+                "WRITE",
+                "Merge duplicate nodes into the first one, rewiring their relationships.",
+            ),
+            // Graph sampling (see `procedures/sampling.rs`).
+            (
+                "nexus.sample.randomWalk",
+                "nexus.sample.randomWalk(start :: INTEGER, steps :: INTEGER, restarts = 0 :: INTEGER, \
+              config = {} :: MAP) :: (nodes :: LIST<NODE>, relationships :: LIST<RELATIONSHIP>)",
+                "READ",
+                "Subgraph visited by random walks that restart from `start`.",
+            ),
+            (
+                "nexus.sample.nodes",
+                "nexus.sample.nodes(size :: INTEGER, config = {} :: MAP) :: (nodes :: LIST<NODE>, \
+              relationships :: LIST<RELATIONSHIP>)",
+                "READ",
+                "Uniform node sample with the relationships induced between sampled nodes.",
+            ),
+            (
+                "nexus.sample.relationships",
+                "nexus.sample.relationships(size :: INTEGER, config = {} :: MAP) :: \
+              (nodes :: LIST<NODE>, relationships :: LIST<RELATIONSHIP>)",
+                "READ",
+                "Uniform relationship sample with its endpoint nodes.",
+            ),
+            // Saved views (see `procedures/views.rs`).
+            (
+                "nexus.view",
+                "nexus.view(name :: STRING) :: (<view columns>)",
+                "READ",
+                "Rows of a saved view, as its RETURN clause shapes them.",
+            ),
+            // Count aggregates (see `procedures/aggregates.rs`).
+            (
+                "nexus.aggregate.counts",
+                "nexus.aggregate.counts(name :: STRING) :: (group :: ANY, count :: INTEGER)",
+                "READ",
+                "Running node counts per group of a registered count aggregate.",
+            ),
+            // Remote data sources (see `procedures/load.rs`).
+            (
+                "nexus.load.jsonFromUrl",
+                "nexus.load.jsonFromUrl(url :: STRING, config = {} :: MAP) :: (value :: ANY)",
+                "READ",
+                "Rows of a JSON or JSON Lines document fetched from an allowed host.",
+            ),
+            (
+                "nexus.load.csvFromUrl",
+                "nexus.load.csvFromUrl(url :: STRING, config = {} :: MAP) :: (lineNo :: INTEGER, \
+              list :: LIST<STRING>, map :: MAP)",
+                "READ",
+                "Records of a CSV document fetched from an allowed host.",
+            ),
+            (
+                "nexus.load.jsonFromS3",
+                "nexus.load.jsonFromS3(bucket :: STRING, key :: STRING, config = {} :: MAP) :: \
+              (value :: ANY)",
+                "READ",
+                "Rows of a JSON or JSON Lines object read from S3.",
+            ),
+            (
+                "nexus.load.csvFromS3",
+                "nexus.load.csvFromS3(bucket :: STRING, key :: STRING, config = {} :: MAP) :: \
+              (lineNo :: INTEGER, list :: LIST<STRING>, map :: MAP)",
+                "READ",
+                "Records of a CSV object read from S3.",
+            ),
             // phase6_opencypher-fulltext-search — Neo4j-compatible surface.
             (
                 "db.index.fulltext.createNodeIndex",
@@ -338,6 +434,12 @@ impl Executor {
                 "Property keys of a node / relationship / map.",
                 false,
             ),
+            (
+                "history",
+                "history(n.property :: ANY) :: LIST<MAP>",
+                "Previous values of a node property with change history enabled.",
+                false,
+            ),
             (
                 "id",
                 "id(x :: NODE) :: INTEGER",
@@ -404,6 +506,12 @@ impl Executor {
                 "Convert to float.",
                 false,
             ),
+            (
+                "toDecimal",
+                "toDecimal(x :: ANY) :: DECIMAL",
+                "Convert to exact decimal.",
+                false,
+            ),
             (
                 "toBoolean",
                 "toBoolean(x :: ANY) :: BOOLEAN",
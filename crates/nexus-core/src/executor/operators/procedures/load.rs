@@ -0,0 +1,335 @@
+//! Remote data table functions: `nexus.load.jsonFromUrl`,
+//! `nexus.load.csvFromUrl`, `nexus.load.jsonFromS3` and
+//! `nexus.load.csvFromS3`.
+//!
+//! Each fetches one document while the query runs and yields it as rows,
+//! so external data can be joined with the graph:
+//!
+//! ```cypher
+//! CALL nexus.load.csvFromUrl('https://data.example.com/users.csv')
+//! YIELD map
+//! MATCH (u:User {email: map.email})
+//! RETURN u, map.plan
+//! ```
+//!
+//! Fetching is gated by the engine's
+//! [`ExternalDataConfig`](crate::external_data::ExternalDataConfig):
+//! hosts outside its allow-list (every host by default) are refused, and
+//! each request is bounded in time and size.
+//!
+//! Common config entries: `timeout` (milliseconds, capped by the server
+//! limit). URL variants also take `headers` (map of request headers);
+//! S3 variants take `region` (default `AWS_REGION`, then `us-east-1`)
+//! and `endpoint` for S3-compatible stores. CSV variants take `header`
+//! (default `true`: the first record names the `map` keys) and `sep`
+//! (default `","`).
+
+use super::super::super::context::ExecutionContext;
+use super::super::super::engine::Executor;
+use super::super::super::parser;
+use super::super::super::types::Row;
+use crate::external_data::{ExternalDataConfig, parse_csv};
+use crate::{Error, Result};
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+/// Columns of the JSON loaders.
+const JSON_COLUMNS: &[&str] = &["value"];
+/// Columns of the CSV loaders.
+const CSV_COLUMNS: &[&str] = &["lineNo", "list", "map"];
+
+impl Executor {
+    /// `CALL nexus.load.jsonFromUrl(url, config = {}) YIELD value`
+    ///
+    /// A top-level JSON array yields one row per element, any other
+    /// document a single row. A body that is not one JSON document is
+    /// read as JSON Lines.
+    pub(in crate::executor) fn execute_load_json_from_url(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.load.jsonFromUrl";
+        let url = self.load_string_arg(context, arguments, 0, "url", NAME)?;
+        let config = self.load_config_arg(context, arguments, 1, NAME)?;
+        let body = self.load_url(&url, &config, NAME)?;
+        let rows = json_rows(&body, NAME)?;
+        emit_load_rows(context, yield_columns, JSON_COLUMNS, rows, NAME)
+    }
+
+    /// `CALL nexus.load.csvFromUrl(url, config = {}) YIELD lineNo, list, map`
+    pub(in crate::executor) fn execute_load_csv_from_url(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.load.csvFromUrl";
+        let url = self.load_string_arg(context, arguments, 0, "url", NAME)?;
+        let config = self.load_config_arg(context, arguments, 1, NAME)?;
+        let body = self.load_url(&url, &config, NAME)?;
+        let rows = csv_rows(&body, &config, NAME)?;
+        emit_load_rows(context, yield_columns, CSV_COLUMNS, rows, NAME)
+    }
+
+    /// `CALL nexus.load.jsonFromS3(bucket, key, config = {}) YIELD value`
+    pub(in crate::executor) fn execute_load_json_from_s3(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.load.jsonFromS3";
+        let config = self.load_config_arg(context, arguments, 2, NAME)?;
+        let body = self.load_s3(context, arguments, &config, NAME)?;
+        let rows = json_rows(&body, NAME)?;
+        emit_load_rows(context, yield_columns, JSON_COLUMNS, rows, NAME)
+    }
+
+    /// `CALL nexus.load.csvFromS3(bucket, key, config = {}) YIELD lineNo, list, map`
+    pub(in crate::executor) fn execute_load_csv_from_s3(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.load.csvFromS3";
+        let config = self.load_config_arg(context, arguments, 2, NAME)?;
+        let body = self.load_s3(context, arguments, &config, NAME)?;
+        let rows = csv_rows(&body, &config, NAME)?;
+        emit_load_rows(context, yield_columns, CSV_COLUMNS, rows, NAME)
+    }
+
+    fn external_data_limits(&self) -> Result<&ExternalDataConfig> {
+        self.external_data().ok_or_else(|| {
+            Error::CypherExecution(
+                "ERR_EXTERNAL_DATA_DENIED: remote data sources are disabled".to_string(),
+            )
+        })
+    }
+
+    fn load_url(&self, url: &str, config: &Map<String, Value>, procedure: &str) -> Result<String> {
+        let headers = match config.get("headers") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Object(headers)) => headers
+                .iter()
+                .map(|(name, value)| match value {
+                    Value::String(value) => Ok((name.clone(), value.clone())),
+                    other => Err(Error::CypherExecution(format!(
+                        "ERR_INVALID_ARG_TYPE: {procedure} header `{name}` must be STRING \
+                         (got {other})"
+                    ))),
+                })
+                .collect::<Result<_>>()?,
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {procedure} `headers` must be MAP (got {other})"
+                )));
+            }
+        };
+        let timeout = load_timeout(config, procedure)?;
+        let body = self.external_data_limits()?.fetch(url, &headers, timeout)?;
+        body_text(body, procedure)
+    }
+
+    fn load_s3(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        config: &Map<String, Value>,
+        procedure: &str,
+    ) -> Result<String> {
+        let bucket = self.load_string_arg(context, arguments, 0, "bucket", procedure)?;
+        let key = self.load_string_arg(context, arguments, 1, "key", procedure)?;
+        let region = match config.get("region") {
+            None | Some(Value::Null) => {
+                std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string())
+            }
+            Some(Value::String(region)) => region.clone(),
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {procedure} `region` must be STRING (got {other})"
+                )));
+            }
+        };
+        let endpoint = match config.get("endpoint") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(endpoint)) => Some(endpoint.as_str()),
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {procedure} `endpoint` must be STRING (got {other})"
+                )));
+            }
+        };
+        let timeout = load_timeout(config, procedure)?;
+        let body = self
+            .external_data_limits()?
+            .fetch_s3(&bucket, &key, &region, endpoint, timeout)?;
+        body_text(body, procedure)
+    }
+
+    fn load_string_arg(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        position: usize,
+        name: &str,
+        procedure: &str,
+    ) -> Result<String> {
+        let Some(expr) = arguments.get(position) else {
+            return Err(Error::CypherExecution(format!(
+                "ERR_MISSING_ARG: {procedure} requires `{name}` at position {position}"
+            )));
+        };
+        match self.evaluate_expression_in_context(context, expr)? {
+            Value::String(value) => Ok(value),
+            other => Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_TYPE: {procedure} `{name}` must be STRING (got {other})"
+            ))),
+        }
+    }
+
+    fn load_config_arg(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        position: usize,
+        procedure: &str,
+    ) -> Result<Map<String, Value>> {
+        match arguments.get(position) {
+            None => Ok(Map::new()),
+            Some(expr) => match self.evaluate_expression_in_context(context, expr)? {
+                Value::Object(map) => Ok(map),
+                Value::Null => Ok(Map::new()),
+                other => Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {procedure} `config` must be MAP (got {other})"
+                ))),
+            },
+        }
+    }
+}
+
+fn load_timeout(config: &Map<String, Value>, procedure: &str) -> Result<Option<Duration>> {
+    match config.get("timeout") {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(ms) if ms > 0 => Ok(Some(Duration::from_millis(ms))),
+            _ => Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: {procedure} `timeout` must be a positive INTEGER of \
+                 milliseconds (got {value})"
+            ))),
+        },
+    }
+}
+
+fn body_text(body: Vec<u8>, procedure: &str) -> Result<String> {
+    String::from_utf8(body).map_err(|_| {
+        Error::CypherExecution(format!(
+            "ERR_EXTERNAL_DATA_FORMAT: {procedure} response is not valid UTF-8"
+        ))
+    })
+}
+
+fn json_rows(body: &str, procedure: &str) -> Result<Vec<Vec<Value>>> {
+    let body = body.trim_start_matches('\u{feff}');
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Array(items)) => Ok(items.into_iter().map(|item| vec![item]).collect()),
+        Ok(document) => Ok(vec![vec![document]]),
+        Err(document_error) => body
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str::<Value>(line).map(|value| vec![value]))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|_| {
+                Error::CypherExecution(format!(
+                    "ERR_EXTERNAL_DATA_FORMAT: {procedure} response is neither JSON nor \
+                     JSON Lines: {document_error}"
+                ))
+            }),
+    }
+}
+
+fn csv_rows(body: &str, config: &Map<String, Value>, procedure: &str) -> Result<Vec<Vec<Value>>> {
+    let header = match config.get("header") {
+        None | Some(Value::Null) => true,
+        Some(Value::Bool(header)) => *header,
+        Some(other) => {
+            return Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_TYPE: {procedure} `header` must be BOOLEAN (got {other})"
+            )));
+        }
+    };
+    let separator = match config.get("sep") {
+        None | Some(Value::Null) => ',',
+        Some(Value::String(sep)) if sep.eq_ignore_ascii_case("TAB") => '\t',
+        Some(Value::String(sep)) if sep.chars().count() == 1 => sep.chars().next().unwrap_or(','),
+        Some(other) => {
+            return Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: {procedure} `sep` must be a single character or \
+                 \"TAB\" (got {other})"
+            )));
+        }
+    };
+
+    let mut records = parse_csv(body.trim_start_matches('\u{feff}'), separator).into_iter();
+    let names = if header {
+        records.next().unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    Ok(records
+        .enumerate()
+        .map(|(line, fields)| {
+            let map: Map<String, Value> = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let value = fields.get(i).cloned().map_or(Value::Null, Value::String);
+                    (name.clone(), value)
+                })
+                .collect();
+            vec![
+                Value::from(line as u64),
+                Value::Array(fields.into_iter().map(Value::String).collect()),
+                Value::Object(map),
+            ]
+        })
+        .collect())
+}
+
+/// Set `rows` (laid out as `columns`) as the procedure result, keeping
+/// only the `YIELD`ed columns.
+fn emit_load_rows(
+    context: &mut ExecutionContext,
+    yield_columns: Option<&Vec<String>>,
+    columns: &[&str],
+    rows: Vec<Vec<Value>>,
+    procedure: &str,
+) -> Result<()> {
+    let Some(yield_columns) = yield_columns else {
+        context.set_columns_and_rows(
+            columns.iter().map(|c| c.to_string()).collect(),
+            rows.into_iter().map(|values| Row { values }).collect(),
+        );
+        return Ok(());
+    };
+    let mut positions = Vec::with_capacity(yield_columns.len());
+    for column in yield_columns {
+        let position = columns.iter().position(|c| c == column).ok_or_else(|| {
+            Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: {procedure} has no column `{column}` (columns: {})",
+                columns.join(", ")
+            ))
+        })?;
+        positions.push(position);
+    }
+    let rows = rows
+        .into_iter()
+        .map(|values| Row {
+            values: positions.iter().map(|&i| values[i].clone()).collect(),
+        })
+        .collect();
+    context.set_columns_and_rows(yield_columns.clone(), rows);
+    Ok(())
+}
@@ -11,16 +11,24 @@
 //!
 //! | File              | Contents                                              |
 //! |-------------------|-------------------------------------------------------|
+//! | `aggregates.rs`   | `nexus.aggregate.counts` — running count aggregates   |
 //! | `call.rs`         | `execute_call_procedure` — the procedure router       |
 //! | `db_schema.rs`    | `db.labels`, `db.propertyKeys`, `db.relationshipTypes`, `db.schema`, `db.info` |
 //! | `db_indexes.rs`   | `db.indexes`, `db.indexDetails`, `db.constraints`    |
 //! | `dbms.rs`         | `dbms.*` procedures + `current_rfc3339_utc` helper   |
 //! | `fts.rs`          | `db.index.fulltext.*` + `fts_autopopulate_node`       |
+//! | `load.rs`         | `nexus.load.*` — rows from remote JSON / CSV / S3     |
+//! | `sampling.rs`     | `nexus.sample.{randomWalk,nodes,relationships}`       |
 //! | `spatial_procs.rs`| `spatial.addPoint`, `spatial.nearest`, spatial hooks  |
+//! | `views.rs`        | `nexus.view` — rows of a saved view                   |
 
+mod aggregates;
 mod call;
 mod db_indexes;
 mod db_schema;
 mod dbms;
 mod fts;
+mod load;
+mod sampling;
 mod spatial_procs;
+mod views;
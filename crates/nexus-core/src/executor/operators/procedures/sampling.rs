@@ -0,0 +1,402 @@
+//! Graph sampling procedures for exploring large graphs:
+//! `nexus.sample.randomWalk`, `nexus.sample.nodes`, and
+//! `nexus.sample.relationships`.
+//!
+//! Each procedure yields a single row `(nodes, relationships)` holding
+//! a bounded subgraph, so a UI or notebook can render a representative
+//! slice without touching the whole store. Sizes above
+//! [`MAX_SAMPLE_SIZE`] are rejected rather than silently truncated.
+//! Every procedure takes a `seed` config entry for reproducible samples.
+
+use super::super::super::context::{ExecutionContext, RelationshipInfo};
+use super::super::super::engine::Executor;
+use super::super::super::parser;
+use super::super::super::types::{Direction, Row};
+use crate::{Error, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// Upper bound on sampled nodes / relationships, and on the total
+/// number of random-walk steps (`steps * (restarts + 1)`).
+pub(in crate::executor) const MAX_SAMPLE_SIZE: u64 = 100_000;
+
+impl Executor {
+    /// `CALL nexus.sample.randomWalk(start, steps, restarts = 0, config = {})`
+    ///
+    /// Walks `restarts + 1` times from `start`, each walk taking up to
+    /// `steps` hops to a uniformly chosen neighbour and stopping early
+    /// at a dead end. Yields the distinct visited nodes and traversed
+    /// relationships in first-visit order. Config: `direction`
+    /// (`"BOTH"` default, `"OUTGOING"`, `"INCOMING"`),
+    /// `relationshipTypes` (list of type names), `seed`.
+    pub(in crate::executor) fn execute_sample_random_walk(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.sample.randomWalk";
+        let start = self.sample_int_arg(context, arguments, 0, "start", NAME)?;
+        let steps = self.sample_int_arg(context, arguments, 1, "steps", NAME)?;
+        let restarts = match arguments.get(2) {
+            Some(_) => self.sample_int_arg(context, arguments, 2, "restarts", NAME)?,
+            None => 0,
+        };
+        let config = self.sample_config_arg(context, arguments, 3, NAME)?;
+        let walks = restarts.saturating_add(1);
+        if steps.saturating_mul(walks) > MAX_SAMPLE_SIZE {
+            return Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: {NAME} takes at most {MAX_SAMPLE_SIZE} steps in total \
+                 (steps * (restarts + 1) = {})",
+                steps.saturating_mul(walks)
+            )));
+        }
+        let direction = match config.get("direction").and_then(Value::as_str) {
+            None => Direction::Both,
+            Some(d) if d.eq_ignore_ascii_case("BOTH") => Direction::Both,
+            Some(d) if d.eq_ignore_ascii_case("OUTGOING") => Direction::Outgoing,
+            Some(d) if d.eq_ignore_ascii_case("INCOMING") => Direction::Incoming,
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_VALUE: {NAME} `direction` must be BOTH, OUTGOING or \
+                     INCOMING (got {other:?})"
+                )));
+            }
+        };
+        if !self.sample_node_is_live(start)? {
+            return Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: {NAME} start node {start} does not exist"
+            )));
+        }
+        let Some(type_ids) = self.sample_type_ids(&config, "relationshipTypes")? else {
+            // Only unknown types were requested, so the walk cannot move.
+            return self.emit_sample(context, yield_columns, &[start], &[]);
+        };
+
+        let mut rng = sample_rng(&config);
+        let mut nodes = vec![start];
+        let mut seen_nodes = HashSet::from([start]);
+        let mut relationships = Vec::new();
+        let mut seen_rels = HashSet::new();
+        for _ in 0..walks {
+            let mut current = start;
+            for _ in 0..steps {
+                let neighbours = self.find_relationships(current, &type_ids, direction, None)?;
+                if neighbours.is_empty() {
+                    break;
+                }
+                let rel = &neighbours[rng.gen_range(0..neighbours.len())];
+                let next = if rel.source_id == current {
+                    rel.target_id
+                } else {
+                    rel.source_id
+                };
+                if seen_rels.insert(rel.id) {
+                    relationships.push(rel.clone());
+                }
+                if seen_nodes.insert(next) {
+                    nodes.push(next);
+                }
+                current = next;
+            }
+        }
+        self.emit_sample(context, yield_columns, &nodes, &relationships)
+    }
+
+    /// `CALL nexus.sample.nodes(size, config = {})`
+    ///
+    /// Uniform sample of up to `size` live nodes (reservoir sampling
+    /// over the node store). Config: `label` to sample only nodes
+    /// carrying it, `induced` (default `true`) to also yield the
+    /// relationships between sampled nodes, `seed`.
+    pub(in crate::executor) fn execute_sample_nodes(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.sample.nodes";
+        let size = self.sample_size_arg(context, arguments, NAME)?;
+        let config = self.sample_config_arg(context, arguments, 1, NAME)?;
+        let label_id = match config.get("label") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(label)) => match self.catalog().get_label_id(label) {
+                Ok(id) => Some(id),
+                Err(_) => return self.emit_sample(context, yield_columns, &[], &[]),
+            },
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {NAME} `label` must be STRING (got {other})"
+                )));
+            }
+        };
+        let induced = config
+            .get("induced")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+
+        let mut rng = sample_rng(&config);
+        let mut reservoir = Reservoir::new(size);
+        {
+            let store = self.store();
+            for node_id in 0..store.node_count() {
+                let Ok(record) = store.read_node(node_id) else {
+                    continue;
+                };
+                if record.is_deleted() || label_id.is_some_and(|l| !record.has_label(l)) {
+                    continue;
+                }
+                reservoir.offer(node_id, &mut rng);
+            }
+        }
+        let mut nodes = reservoir.items;
+        nodes.sort_unstable();
+
+        let mut relationships = Vec::new();
+        if induced {
+            let sampled: HashSet<u64> = nodes.iter().copied().collect();
+            for &node_id in &nodes {
+                for rel in self.find_relationships(node_id, &[], Direction::Outgoing, None)? {
+                    if sampled.contains(&rel.target_id) {
+                        relationships.push(rel);
+                    }
+                }
+            }
+        }
+        self.emit_sample(context, yield_columns, &nodes, &relationships)
+    }
+
+    /// `CALL nexus.sample.relationships(size, config = {})`
+    ///
+    /// Uniform sample of up to `size` live relationships (reservoir
+    /// sampling over the relationship store) together with their
+    /// endpoints. Config: `type` to sample one relationship type, `seed`.
+    pub(in crate::executor) fn execute_sample_relationships(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.sample.relationships";
+        let size = self.sample_size_arg(context, arguments, NAME)?;
+        let config = self.sample_config_arg(context, arguments, 1, NAME)?;
+        let type_filter = match config.get("type") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(t)) => match self.catalog().get_type_id(t)? {
+                Some(id) => Some(id),
+                None => return self.emit_sample(context, yield_columns, &[], &[]),
+            },
+            Some(other) => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {NAME} `type` must be STRING (got {other})"
+                )));
+            }
+        };
+
+        let mut rng = sample_rng(&config);
+        let mut reservoir = Reservoir::new(size);
+        {
+            let store = self.store();
+            for rel_id in 0..store.relationship_count() {
+                let Ok(record) = store.read_rel(rel_id) else {
+                    continue;
+                };
+                let (source_id, target_id, type_id) =
+                    (record.src_id, record.dst_id, record.type_id);
+                if record.is_deleted() || type_filter.is_some_and(|t| t != type_id) {
+                    continue;
+                }
+                reservoir.offer(
+                    RelationshipInfo {
+                        id: rel_id,
+                        source_id,
+                        target_id,
+                        type_id,
+                    },
+                    &mut rng,
+                );
+            }
+        }
+        let mut relationships = reservoir.items;
+        relationships.sort_unstable_by_key(|rel| rel.id);
+
+        let mut nodes = Vec::new();
+        let mut seen = HashSet::new();
+        for rel in &relationships {
+            for node_id in [rel.source_id, rel.target_id] {
+                if seen.insert(node_id) {
+                    nodes.push(node_id);
+                }
+            }
+        }
+        self.emit_sample(context, yield_columns, &nodes, &relationships)
+    }
+
+    /// Materialise the sampled ids as one `(nodes, relationships)` row.
+    fn emit_sample(
+        &self,
+        context: &mut ExecutionContext,
+        yield_columns: Option<&Vec<String>>,
+        nodes: &[u64],
+        relationships: &[RelationshipInfo],
+    ) -> Result<()> {
+        let (node_values, rel_values) = {
+            let store = self.store();
+            let mut node_values = Vec::with_capacity(nodes.len());
+            for &node_id in nodes {
+                node_values.push(self.read_node_as_value_with_store(&store, node_id)?);
+            }
+            let mut rel_values = Vec::with_capacity(relationships.len());
+            for rel in relationships {
+                rel_values.push(self.read_relationship_as_value_with_store(&store, rel)?);
+            }
+            (node_values, rel_values)
+        };
+        let columns = yield_columns
+            .cloned()
+            .unwrap_or_else(|| vec!["nodes".to_string(), "relationships".to_string()]);
+        let values = columns
+            .iter()
+            .map(|column| match column.as_str() {
+                "relationships" => Value::Array(rel_values.clone()),
+                _ => Value::Array(node_values.clone()),
+            })
+            .collect();
+        context.set_columns_and_rows(columns, vec![Row { values }]);
+        Ok(())
+    }
+
+    fn sample_node_is_live(&self, node_id: u64) -> Result<bool> {
+        let store = self.store();
+        if node_id >= store.node_count() {
+            return Ok(false);
+        }
+        Ok(!store.read_node(node_id)?.is_deleted())
+    }
+
+    /// Resolve `config[key]` to relationship type ids. `Some(vec![])`
+    /// means "any type"; `None` means every named type is unknown.
+    fn sample_type_ids(&self, config: &Map<String, Value>, key: &str) -> Result<Option<Vec<u32>>> {
+        let Some(names) = config.get(key).and_then(Value::as_array) else {
+            return Ok(Some(Vec::new()));
+        };
+        let mut ids = Vec::new();
+        for name in names.iter().filter_map(Value::as_str) {
+            if let Some(id) = self.catalog().get_type_id(name)? {
+                ids.push(id);
+            }
+        }
+        Ok(if ids.is_empty() && !names.is_empty() {
+            None
+        } else {
+            Some(ids)
+        })
+    }
+
+    fn sample_size_arg(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        procedure: &str,
+    ) -> Result<usize> {
+        let size = self.sample_int_arg(context, arguments, 0, "size", procedure)?;
+        if size > MAX_SAMPLE_SIZE {
+            return Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: {procedure} `size` must be at most {MAX_SAMPLE_SIZE} \
+                 (got {size})"
+            )));
+        }
+        Ok(size as usize)
+    }
+
+    fn sample_int_arg(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        position: usize,
+        name: &str,
+        procedure: &str,
+    ) -> Result<u64> {
+        let Some(expr) = arguments.get(position) else {
+            return Err(Error::CypherExecution(format!(
+                "ERR_MISSING_ARG: {procedure} requires `{name}` at position {position}"
+            )));
+        };
+        match self.evaluate_expression_in_context(context, expr)? {
+            Value::Number(n) => n.as_u64().ok_or_else(|| {
+                Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_VALUE: {procedure} `{name}` must be a non-negative INTEGER"
+                ))
+            }),
+            Value::Object(node) if name == "start" => node
+                .get("_nexus_id")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| {
+                    Error::CypherExecution(format!(
+                        "ERR_INVALID_ARG_TYPE: {procedure} `start` must be a NODE or INTEGER"
+                    ))
+                }),
+            other => Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_TYPE: {procedure} `{name}` must be INTEGER (got {other})"
+            ))),
+        }
+    }
+
+    fn sample_config_arg(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        position: usize,
+        procedure: &str,
+    ) -> Result<Map<String, Value>> {
+        match arguments.get(position) {
+            None => Ok(Map::new()),
+            Some(expr) => match self.evaluate_expression_in_context(context, expr)? {
+                Value::Object(map) => Ok(map),
+                Value::Null => Ok(Map::new()),
+                other => Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {procedure} `config` must be MAP (got {other})"
+                ))),
+            },
+        }
+    }
+}
+
+fn sample_rng(config: &Map<String, Value>) -> StdRng {
+    match config.get("seed").and_then(Value::as_u64) {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Algorithm R: keeps a uniform sample of `capacity` items from a
+/// stream of unknown length.
+struct Reservoir<T> {
+    capacity: usize,
+    seen: u64,
+    items: Vec<T>,
+}
+
+impl<T> Reservoir<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: 0,
+            items: Vec::with_capacity(capacity.min(1024)),
+        }
+    }
+
+    fn offer(&mut self, item: T, rng: &mut StdRng) {
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+        } else if self.capacity > 0 {
+            let slot = rng.gen_range(0..self.seen);
+            if (slot as usize) < self.capacity {
+                self.items[slot as usize] = item;
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! `nexus.view(name)` — yields the rows of a saved view (the stored result
+//! for a materialized view).
+//!
+//! The procedure's columns are the view's `RETURN` columns; `YIELD`
+//! picks among them by name.
+
+use super::super::super::context::ExecutionContext;
+use super::super::super::engine::Executor;
+use super::super::super::parser;
+use super::super::super::types::Row;
+use crate::{Error, Result};
+use serde_json::Value;
+
+impl Executor {
+    /// `CALL nexus.view(name) YIELD <view columns>`
+    pub(in crate::executor) fn execute_view_procedure(
+        &self,
+        context: &mut ExecutionContext,
+        arguments: &[parser::Expression],
+        yield_columns: Option<&Vec<String>>,
+    ) -> Result<()> {
+        const NAME: &str = "nexus.view";
+        let Some(expr) = arguments.first() else {
+            return Err(Error::CypherExecution(format!(
+                "ERR_MISSING_ARG: {NAME} requires `name` at position 0"
+            )));
+        };
+        let name = match self.evaluate_expression_in_context(context, expr)? {
+            Value::String(name) => name,
+            other => {
+                return Err(Error::CypherExecution(format!(
+                    "ERR_INVALID_ARG_TYPE: {NAME} `name` must be STRING (got {other})"
+                )));
+            }
+        };
+        let result = self.run_view(&name)?;
+
+        let Some(yield_columns) = yield_columns else {
+            context.set_columns_and_rows(result.columns, result.rows);
+            return Ok(());
+        };
+        let mut positions = Vec::with_capacity(yield_columns.len());
+        for column in yield_columns {
+            let position = result
+                .columns
+                .iter()
+                .position(|c| c == column)
+                .ok_or_else(|| {
+                    Error::CypherExecution(format!(
+                        "ERR_INVALID_ARG_VALUE: view {name:?} has no column `{column}` \
+                         (columns: {})",
+                        result.columns.join(", ")
+                    ))
+                })?;
+            positions.push(position);
+        }
+        let rows = result
+            .rows
+            .into_iter()
+            .map(|row| Row {
+                values: positions
+                    .iter()
+                    .map(|&i| row.values.get(i).cloned().unwrap_or(Value::Null))
+                    .collect(),
+            })
+            .collect();
+        context.set_columns_and_rows(yield_columns.clone(), rows);
+        Ok(())
+    }
+}
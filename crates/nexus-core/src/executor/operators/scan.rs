@@ -12,6 +12,17 @@ use serde_json::Value;
 
 impl Executor {
     pub(in crate::executor) fn execute_node_by_label(&self, label_id: u32) -> Result<Vec<Value>> {
+        self.execute_node_by_label_projected(label_id, None)
+    }
+
+    /// Label scan that, given a `projection`, loads only those property
+    /// keys for each node (see `planner::property_projections`). `None`
+    /// materialises every property, exactly like `execute_node_by_label`.
+    pub(in crate::executor) fn execute_node_by_label_projected(
+        &self,
+        label_id: u32,
+        projection: Option<&[String]>,
+    ) -> Result<Vec<Value>> {
         // Always use label_index - label_id 0 is valid (it's the first label)
         let bitmap = self.label_index().get_nodes(label_id)?;
 
@@ -61,7 +72,11 @@ impl Executor {
             // read-lock acquisitions per candidate node for no
             // behavioural difference — removed to halve the lock churn
             // on this hot path.
-            match self.read_node_as_value_with_store(&store, node_id_u64)? {
+            let value = match projection {
+                Some(keys) => self.read_node_projected_with_store(&store, node_id_u64, keys)?,
+                None => self.read_node_as_value_with_store(&store, node_id_u64)?,
+            };
+            match value {
                 Value::Null => continue,
                 value => results.push(value),
             }
@@ -117,6 +132,24 @@ impl Executor {
         Ok(results)
     }
 
+    /// Label scan guarded by the `(label_id, key_id)` bloom filter: returns
+    /// no nodes when the filter proves `value` was never stored, else the
+    /// full label scan. Without an installed registry (test harness
+    /// executors) it is always the label scan.
+    pub(in crate::executor) fn execute_node_bloom_probe(
+        &self,
+        label_id: u32,
+        key_id: u32,
+        value: &Value,
+    ) -> Result<Vec<Value>> {
+        if let Some(blooms) = self.property_bloom() {
+            if blooms.might_contain(label_id, key_id, value) == Some(false) {
+                return Ok(Vec::new());
+            }
+        }
+        self.execute_node_by_label(label_id)
+    }
+
     /// Execute AllNodesScan operator (scan all nodes regardless of label)
     pub(in crate::executor) fn execute_all_nodes_scan(&self) -> Result<Vec<Value>> {
         // phase8_neo4j-concurrency-gaps §2 — acquire the `store` read
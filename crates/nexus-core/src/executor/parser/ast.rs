@@ -125,7 +125,7 @@ pub enum Clause {
     DeleteApiKey(DeleteApiKeyClause),
     /// EXPLAIN command for query plan analysis
     Explain(ExplainClause),
-    /// PROFILE command for query execution profiling
+    /// PROFILE (or EXPLAIN ANALYZE) command for query execution profiling
     Profile(ProfileClause),
     /// CALL subquery clause
     CallSubquery(CallSubqueryClause),
@@ -145,6 +145,16 @@ pub enum Clause {
     CreateFunction(CreateFunctionClause),
     /// DROP FUNCTION command
     DropFunction(DropFunctionClause),
+    /// CREATE VIEW command
+    CreateView(CreateViewClause),
+    /// DROP VIEW command
+    DropView(DropViewClause),
+    /// SHOW VIEWS command
+    ShowViews,
+    /// SHOW SETTINGS command
+    ShowSettings,
+    /// REFRESH VIEW command
+    RefreshView(RefreshViewClause),
 }
 
 /// MATCH clause with pattern matching
@@ -800,6 +810,12 @@ pub struct CreateConstraintClause {
     /// Property-type token when `constraint_type == PropertyType`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub property_type: Option<String>,
+    /// Source-node label when `constraint_type == Endpoints`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_label: Option<String>,
+    /// Target-node label when `constraint_type == Endpoints`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_label: Option<String>,
     /// Optional IF NOT EXISTS flag
     pub if_not_exists: bool,
 }
@@ -825,6 +841,9 @@ pub enum ConstraintType {
     NodeKey,
     /// Property-type constraint — `IS :: <TYPE>` (§7).
     PropertyType,
+    /// Relationship endpoint labels —
+    /// `FOR (:A)-[r:T]->(:B) REQUIRE ENDPOINTS`.
+    Endpoints,
 }
 
 /// DROP CONSTRAINT clause
@@ -947,6 +966,41 @@ pub struct DropFunctionClause {
     pub if_exists: bool,
 }
 
+/// CREATE VIEW clause
+/// Syntax: CREATE [OR REPLACE] [MATERIALIZED] VIEW [IF NOT EXISTS] name
+/// [REFRESH MANUAL | REFRESH ON CHANGE | REFRESH EVERY n unit] AS query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateViewClause {
+    /// View name
+    pub name: String,
+    /// Source text of the view's query (everything after `AS`)
+    pub query: String,
+    /// Whether OR REPLACE was specified
+    pub or_replace: bool,
+    /// Optional IF NOT EXISTS flag
+    pub if_not_exists: bool,
+    /// Refresh policy of a MATERIALIZED view; `None` for a plain view
+    pub materialized: Option<crate::catalog::ViewRefresh>,
+}
+
+/// DROP VIEW clause
+/// Syntax: DROP [MATERIALIZED] VIEW [IF EXISTS] name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DropViewClause {
+    /// View name
+    pub name: String,
+    /// Optional IF EXISTS flag
+    pub if_exists: bool,
+}
+
+/// REFRESH VIEW clause
+/// Syntax: REFRESH [MATERIALIZED] VIEW name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshViewClause {
+    /// View name
+    pub name: String,
+}
+
 /// TERMINATE QUERY clause
 /// Syntax: TERMINATE QUERY 'query-id'
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -968,6 +1022,32 @@ pub struct UdfParameter {
     pub default: Option<serde_json::Value>,
 }
 
+/// Output format of EXPLAIN / PROFILE (`FORMAT JSON|TEXT|DOT`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanFormat {
+    /// JSON object with the operator list and the plan tree
+    #[default]
+    Json,
+    /// Indented plan tree as a string
+    Text,
+    /// Graphviz DOT digraph as a string
+    Dot,
+}
+
+impl std::str::FromStr for PlanFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            "dot" => Ok(Self::Dot),
+            other => Err(format!("unknown plan format '{other}'")),
+        }
+    }
+}
+
 /// EXPLAIN clause for query plan analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExplainClause {
@@ -976,9 +1056,12 @@ pub struct ExplainClause {
     /// Original query string (for execution)
     #[serde(skip)]
     pub query_string: Option<String>,
+    /// Output format
+    #[serde(default)]
+    pub format: PlanFormat,
 }
 
-/// PROFILE clause for query execution profiling
+/// PROFILE (or `EXPLAIN ANALYZE`) clause for query execution profiling
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfileClause {
     /// The query to profile
@@ -986,6 +1069,9 @@ pub struct ProfileClause {
     /// Original query string (for execution)
     #[serde(skip)]
     pub query_string: Option<String>,
+    /// Output format
+    #[serde(default)]
+    pub format: PlanFormat,
 }
 
 /// CALL subquery clause
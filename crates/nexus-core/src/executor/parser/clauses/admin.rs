@@ -1,6 +1,7 @@
 //! Schema and administration clause parsers: CREATE/DROP/ALTER DATABASE,
 //! CREATE/DROP INDEX, CREATE/DROP CONSTRAINT, CREATE/DROP USER, SHOW USER,
-//! CREATE/DROP FUNCTION, API KEY management, GRANT, REVOKE.
+//! CREATE/DROP FUNCTION, CREATE/DROP/REFRESH VIEW, API KEY management, GRANT,
+//! REVOKE.
 
 use super::super::CypherParser;
 use super::super::ast::*;
@@ -322,6 +323,8 @@ impl CypherParser {
     ///     FOR ()-[r:TYPE]-() REQUIRE r.p IS NOT NULL
     /// CREATE CONSTRAINT [<name>] [IF NOT EXISTS]
     ///     FOR ()-[r:TYPE]-() REQUIRE r.p IS :: INTEGER
+    /// CREATE CONSTRAINT [<name>] [IF NOT EXISTS]
+    ///     FOR (:Src)-[r:TYPE]->(:Dst) REQUIRE ENDPOINTS
     /// ```
     pub(super) fn parse_create_constraint_clause(&mut self) -> Result<CreateConstraintClause> {
         self.expect_keyword("CONSTRAINT")?;
@@ -377,6 +380,8 @@ impl CypherParser {
                 properties: vec![property],
                 entity: ConstraintEntity::Node,
                 property_type: None,
+                source_label: None,
+                target_label: None,
                 if_not_exists,
             })
         }
@@ -421,20 +426,59 @@ impl CypherParser {
         self.skip_whitespace();
 
         // Entity scope: node pattern `(n:L)` or rel pattern `()-[r:T]-()`.
-        let (entity, var_name, label_or_type) =
-            if self.peek_char() == Some('(') && !self.peek_is_rel_after_lparen() {
-                // Actually look at next char to decide. Both forms start with `(`:
-                //   node pattern:  (n:L)
-                //   rel pattern:   ()-[r:T]-()
-                // We disambiguate by peeking past `(` for `)-[`.
-                self.parse_constraint_node_pattern()?
-            } else {
-                self.parse_constraint_rel_pattern()?
-            };
+        let (entity, var_name, label_or_type) = if self.peek_char() == Some('(')
+            && !self.peek_is_rel_after_lparen()
+        {
+            // Actually look at next char to decide. Both forms start with `(`:
+            //   node pattern:  (n:L)
+            //   rel pattern:   ()-[r:T]-()  /  (:A)-[r:T]->(:B)
+            // We disambiguate by peeking past the closing `)` for `-`.
+            self.parse_constraint_node_pattern()?
+        } else {
+            let pattern = self.parse_constraint_rel_pattern()?;
+            self.skip_whitespace();
+            self.expect_keyword("REQUIRE")?;
+            self.skip_whitespace();
+            if self.peek_keyword("ENDPOINTS") {
+                self.parse_keyword()?;
+                if !pattern.directed {
+                    return Err(
+                        self.error("REQUIRE ENDPOINTS needs a directed pattern `(:A)-[r:T]->(:B)`")
+                    );
+                }
+                if pattern.source_label.is_none() && pattern.target_label.is_none() {
+                    return Err(
+                        self.error("REQUIRE ENDPOINTS needs a label on at least one endpoint")
+                    );
+                }
+                return Ok(CreateConstraintClause {
+                    name,
+                    constraint_type: ConstraintType::Endpoints,
+                    label: pattern.rel_type,
+                    property: String::new(),
+                    properties: Vec::new(),
+                    entity: ConstraintEntity::Relationship,
+                    property_type: None,
+                    source_label: pattern.source_label,
+                    target_label: pattern.target_label,
+                    if_not_exists,
+                });
+            }
+            if pattern.source_label.is_some() || pattern.target_label.is_some() {
+                return Err(self.error("endpoint labels are only allowed with REQUIRE ENDPOINTS"));
+            }
+            (
+                ConstraintEntity::Relationship,
+                pattern.var,
+                pattern.rel_type,
+            )
+        };
         let _ = var_name;
 
-        self.skip_whitespace();
-        self.expect_keyword("REQUIRE")?;
+        if entity == ConstraintEntity::Node {
+            self.skip_whitespace();
+            self.expect_keyword("REQUIRE")?;
+        }
         self.skip_whitespace();
 
         // Body: `(p1, p2, ...) IS NODE KEY` | `n.p IS UNIQUE` |
@@ -475,24 +519,26 @@ impl CypherParser {
             properties,
             entity,
             property_type,
+            source_label: None,
+            target_label: None,
             if_not_exists,
         })
     }
 
-    /// Look past `(` to decide if the pattern is a node `(n:L)` or a
-    /// relationship `()-[r:T]-()`. Stateless — `self.pos` is
-    /// unchanged on return.
+    /// Look past `(...)` to decide if the pattern is a node `(n:L)` or a
+    /// relationship `()-[r:T]-()` / `(:A)-[r:T]->(:B)`. Stateless —
+    /// `self.pos` is unchanged on return.
     fn peek_is_rel_after_lparen(&self) -> bool {
-        let mut pos = self.pos + 1;
-        // Skip whitespace inside `(`.
-        while pos < self.input.len() {
-            if !self.input.as_bytes()[pos].is_ascii_whitespace() {
-                break;
-            }
+        let bytes = self.input.as_bytes();
+        let Some(close) = bytes[self.pos..].iter().position(|b| *b == b')') else {
+            return false;
+        };
+        let mut pos = self.pos + close + 1;
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
             pos += 1;
         }
-        // Rel pattern shape: `()-[...`
-        pos < self.input.len() && self.input.as_bytes()[pos] == b')'
+        // Rel pattern shape: `(...)-[...`
+        pos < bytes.len() && bytes[pos] == b'-'
     }
 
     /// Look past `(` to decide if we're at a NODE KEY tuple
@@ -530,11 +576,28 @@ impl CypherParser {
         Ok((ConstraintEntity::Node, var, label))
     }
 
-    fn parse_constraint_rel_pattern(&mut self) -> Result<(ConstraintEntity, String, String)> {
-        // Accepts `()-[r:TYPE]-()` and `()-[r:TYPE]->()`.
+    /// Optional endpoint label inside a constraint rel pattern: `()` or
+    /// `(:Label)`.
+    fn parse_constraint_endpoint(&mut self) -> Result<Option<String>> {
         self.expect_char('(')?;
         self.skip_whitespace();
+        let label = if self.peek_char() == Some(':') {
+            self.consume_char();
+            self.skip_whitespace();
+            let label = self.parse_identifier()?;
+            self.skip_whitespace();
+            Some(label)
+        } else {
+            None
+        };
         self.expect_char(')')?;
+        Ok(label)
+    }
+
+    fn parse_constraint_rel_pattern(&mut self) -> Result<ConstraintRelPattern> {
+        // Accepts `()-[r:TYPE]-()` and `()-[r:TYPE]->()`, with an
+        // optional `:Label` on either endpoint.
+        let source_label = self.parse_constraint_endpoint()?;
         self.skip_whitespace();
         self.expect_char('-')?;
         self.skip_whitespace();
@@ -549,14 +612,19 @@ impl CypherParser {
         self.skip_whitespace();
         self.expect_char('-')?;
         self.skip_whitespace();
-        if self.peek_char() == Some('>') {
+        let directed = self.peek_char() == Some('>');
+        if directed {
             self.consume_char();
             self.skip_whitespace();
         }
-        self.expect_char('(')?;
-        self.skip_whitespace();
-        self.expect_char(')')?;
-        Ok((ConstraintEntity::Relationship, var, rel_type))
+        let target_label = self.parse_constraint_endpoint()?;
+        Ok(ConstraintRelPattern {
+            var,
+            rel_type,
+            source_label,
+            target_label,
+            directed,
+        })
     }
 
     fn parse_require_node_key_body(
@@ -603,6 +671,25 @@ impl CypherParser {
             false
         };
 
+        // `FOR (:A)-[r:T]->(:B) REQUIRE ENDPOINTS` — the endpoint labels
+        // are accepted for symmetry with CREATE; the constraint is keyed
+        // by relationship type alone.
+        if self.peek_keyword("FOR") {
+            self.parse_keyword()?;
+            self.skip_whitespace();
+            let pattern = self.parse_constraint_rel_pattern()?;
+            self.skip_whitespace();
+            self.expect_keyword("REQUIRE")?;
+            self.skip_whitespace();
+            self.expect_keyword("ENDPOINTS")?;
+            return Ok(DropConstraintClause {
+                constraint_type: ConstraintType::Endpoints,
+                label: pattern.rel_type,
+                property: String::new(),
+                if_exists,
+            });
+        }
+
         self.expect_keyword("ON")?;
         self.skip_whitespace();
         self.expect_char('(')?;
@@ -877,6 +964,141 @@ impl CypherParser {
         Ok(DropFunctionClause { name, if_exists })
     }
 
+    /// Parse CREATE VIEW clause
+    /// Syntax: CREATE [OR REPLACE] VIEW [IF NOT EXISTS] name AS query
+    ///
+    /// The view's query is the rest of the input; it is kept as source
+    /// text and parsed again whenever the view is used.
+    pub(super) fn parse_create_view_clause(&mut self) -> Result<CreateViewClause> {
+        let or_replace = if self.peek_keyword("OR") {
+            self.parse_keyword()?; // consume "OR"
+            self.expect_keyword("REPLACE")?;
+            self.skip_whitespace();
+            true
+        } else {
+            false
+        };
+
+        let materialized = self.peek_keyword("MATERIALIZED");
+        if materialized {
+            self.parse_keyword()?; // consume "MATERIALIZED"
+        }
+        self.expect_keyword("VIEW")?;
+        self.skip_whitespace();
+
+        let if_not_exists = if self.peek_keyword("IF") {
+            self.parse_keyword()?; // consume "IF"
+            self.expect_keyword("NOT")?;
+            self.expect_keyword("EXISTS")?;
+            self.skip_whitespace();
+            true
+        } else {
+            false
+        };
+        if or_replace && if_not_exists {
+            return Err(self.error("CREATE VIEW cannot combine OR REPLACE and IF NOT EXISTS"));
+        }
+
+        let name = self.parse_identifier()?;
+        self.skip_whitespace();
+        let refresh = if self.peek_keyword("REFRESH") {
+            if !materialized {
+                return Err(self.error("REFRESH is only allowed on a MATERIALIZED VIEW"));
+            }
+            self.parse_view_refresh()?
+        } else {
+            crate::catalog::ViewRefresh::OnChange
+        };
+        self.expect_keyword("AS")?;
+        self.skip_whitespace();
+
+        let query = self.input[self.pos..]
+            .trim_end()
+            .trim_end_matches(';')
+            .trim_end()
+            .to_string();
+        if query.is_empty() {
+            return Err(self.error("CREATE VIEW requires a query after AS"));
+        }
+        while self.consume_char().is_some() {}
+
+        Ok(CreateViewClause {
+            name,
+            query,
+            or_replace,
+            if_not_exists,
+            materialized: materialized.then_some(refresh),
+        })
+    }
+
+    /// Parse the refresh policy of a materialized view
+    /// Syntax: REFRESH MANUAL | REFRESH ON CHANGE | REFRESH EVERY n
+    /// SECOND[S] | MINUTE[S] | HOUR[S]
+    fn parse_view_refresh(&mut self) -> Result<crate::catalog::ViewRefresh> {
+        use crate::catalog::ViewRefresh;
+
+        self.expect_keyword("REFRESH")?;
+        if self.peek_keyword("MANUAL") {
+            self.parse_keyword()?; // consume "MANUAL"
+            return Ok(ViewRefresh::Manual);
+        }
+        if self.peek_keyword("ON") {
+            self.parse_keyword()?; // consume "ON"
+            self.expect_keyword("CHANGE")?;
+            return Ok(ViewRefresh::OnChange);
+        }
+        self.expect_keyword("EVERY")?;
+        let count = self.parse_number()?;
+        if count <= 0 {
+            return Err(self.error("REFRESH EVERY requires a positive interval"));
+        }
+        let unit_ms: u64 = match self.parse_keyword()?.to_uppercase().as_str() {
+            "SECOND" | "SECONDS" => 1_000,
+            "MINUTE" | "MINUTES" => 60_000,
+            "HOUR" | "HOURS" => 3_600_000,
+            _ => {
+                return Err(self.error("REFRESH EVERY unit must be SECONDS, MINUTES, or HOURS"));
+            }
+        };
+        Ok(ViewRefresh::Every {
+            interval_ms: (count as u64).saturating_mul(unit_ms),
+        })
+    }
+
+    /// Parse DROP VIEW clause
+    /// Syntax: DROP [MATERIALIZED] VIEW [IF EXISTS] name
+    pub(super) fn parse_drop_view_clause(&mut self) -> Result<DropViewClause> {
+        if self.peek_keyword("MATERIALIZED") {
+            self.parse_keyword()?; // consume "MATERIALIZED"
+        }
+        self.expect_keyword("VIEW")?;
+        self.skip_whitespace();
+
+        let if_exists = if self.peek_keyword("IF") {
+            self.parse_keyword()?; // consume "IF"
+            self.expect_keyword("EXISTS")?;
+            self.skip_whitespace();
+            true
+        } else {
+            false
+        };
+
+        let name = self.parse_identifier()?;
+        Ok(DropViewClause { name, if_exists })
+    }
+
+    /// Parse REFRESH VIEW clause
+    /// Syntax: REFRESH [MATERIALIZED] VIEW name
+    pub(super) fn parse_refresh_view_clause(&mut self) -> Result<RefreshViewClause> {
+        if self.peek_keyword("MATERIALIZED") {
+            self.parse_keyword()?; // consume "MATERIALIZED"
+        }
+        self.expect_keyword("VIEW")?;
+        self.skip_whitespace();
+        let name = self.parse_identifier()?;
+        Ok(RefreshViewClause { name })
+    }
+
     /// Parse CREATE API KEY clause
     /// Syntax: CREATE API KEY name [FOR username] [WITH PERMISSIONS ...] [EXPIRES IN 'duration']
     pub(super) fn parse_create_api_key_clause(&mut self) -> Result<CreateApiKeyClause> {
@@ -1048,3 +1270,12 @@ impl CypherParser {
         })
     }
 }
+
+/// Relationship pattern from a constraint's `FOR` clause.
+struct ConstraintRelPattern {
+    var: String,
+    rel_type: String,
+    source_label: Option<String>,
+    target_label: Option<String>,
+    directed: bool,
+}
@@ -221,6 +221,14 @@ impl CypherParser {
                 if self.peek_keyword("DATABASE") {
                     let create_db_clause = self.parse_create_database_clause()?;
                     Ok(Clause::CreateDatabase(create_db_clause))
+                } else if self.peek_keyword("VIEW")
+                    || self.peek_keyword("MATERIALIZED")
+                    || (self.peek_keyword("OR")
+                        && (self.peek_keyword_at(2, "VIEW")
+                            || self.peek_keyword_at(2, "MATERIALIZED")))
+                {
+                    let create_view_clause = self.parse_create_view_clause()?;
+                    Ok(Clause::CreateView(create_view_clause))
                 } else if self.peek_keyword("INDEX")
                     || self.peek_keyword("SPATIAL")
                     || self.peek_keyword("OR")
@@ -333,6 +341,12 @@ impl CypherParser {
                 } else if self.peek_keyword("QUERIES") {
                     self.parse_keyword()?; // consume "QUERIES"
                     Ok(Clause::ShowQueries)
+                } else if self.peek_keyword("VIEWS") {
+                    self.parse_keyword()?; // consume "VIEWS"
+                    Ok(Clause::ShowViews)
+                } else if self.peek_keyword("SETTINGS") {
+                    self.parse_keyword()?; // consume "SETTINGS"
+                    Ok(Clause::ShowSettings)
                 } else if self.peek_keyword("API") {
                     self.parse_keyword()?; // consume "API"
                     self.expect_keyword("KEYS")?;
@@ -340,10 +354,15 @@ impl CypherParser {
                     Ok(Clause::ShowApiKeys(show_api_keys_clause))
                 } else {
                     Err(self.error(
-                        "SHOW must be followed by DATABASES, USERS, USER, FUNCTIONS, CONSTRAINTS, QUERIES, or API KEYS",
+                        "SHOW must be followed by DATABASES, USERS, USER, FUNCTIONS, CONSTRAINTS, QUERIES, VIEWS, SETTINGS, or API KEYS",
                     ))
                 }
             }
+            "REFRESH" => {
+                self.skip_whitespace();
+                let refresh_view_clause = self.parse_refresh_view_clause()?;
+                Ok(Clause::RefreshView(refresh_view_clause))
+            }
             "TERMINATE" => {
                 self.skip_whitespace();
 
@@ -468,9 +487,12 @@ impl CypherParser {
                 } else if self.peek_keyword("FUNCTION") {
                     let drop_function_clause = self.parse_drop_function_clause()?;
                     Ok(Clause::DropFunction(drop_function_clause))
+                } else if self.peek_keyword("VIEW") || self.peek_keyword("MATERIALIZED") {
+                    let drop_view_clause = self.parse_drop_view_clause()?;
+                    Ok(Clause::DropView(drop_view_clause))
                 } else {
                     Err(self.error(
-                        "DROP must be followed by DATABASE, USER, INDEX, CONSTRAINT, or FUNCTION",
+                        "DROP must be followed by DATABASE, USER, INDEX, CONSTRAINT, FUNCTION, or VIEW",
                     ))
                 }
             }
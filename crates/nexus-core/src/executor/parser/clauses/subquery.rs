@@ -300,13 +300,7 @@ impl CypherParser {
         if !self.is_identifier_start() {
             return Err(self.error("Invalid procedure name"));
         }
-
-        let start = self.pos;
-        self.consume_char();
-        while self.pos < self.input.len() && self.is_identifier_char() {
-            self.consume_char();
-        }
-        procedure_name.push_str(&self.input[start..self.pos]);
+        procedure_name.push_str(&self.parse_identifier()?);
 
         // Continue parsing dots and identifiers
         while self.pos < self.input.len() {
@@ -318,12 +312,7 @@ impl CypherParser {
                 if !self.is_identifier_start() {
                     return Err(self.error("Invalid procedure name after dot"));
                 }
-                let part_start = self.pos;
-                self.consume_char();
-                while self.pos < self.input.len() && self.is_identifier_char() {
-                    self.consume_char();
-                }
-                procedure_name.push_str(&self.input[part_start..self.pos]);
+                procedure_name.push_str(&self.parse_identifier()?);
             } else if self.peek_char() == Some('(')
                 || matches!(self.peek_char(), Some(c) if c.is_whitespace())
             {
@@ -409,138 +398,78 @@ impl CypherParser {
     }
 
     /// Parse EXPLAIN clause
-    /// Syntax: EXPLAIN [query]
+    /// Syntax: EXPLAIN [ANALYZE] [FORMAT [=] JSON|TEXT|DOT] [query]
+    ///
+    /// `EXPLAIN ANALYZE` executes the query like PROFILE and parses to a
+    /// [`Clause::Profile`].
     pub(super) fn parse_explain_clause(&mut self) -> Result<Clause> {
         self.parse_keyword()?; // consume "EXPLAIN"
         self.skip_whitespace();
-
-        // Save current position to extract query string
-        let start_pos = self.pos;
-
-        // Parse the remaining query directly using the current parser state
-        // This is more reliable than creating a temporary parser
-        let mut clauses = Vec::new();
-
-        // Parse clauses until end of input
-        // We need to parse clauses without checking for EXPLAIN/PROFILE again
-        // since we're already inside an EXPLAIN clause
-        while self.pos < self.input.len() {
-            if self.is_clause_boundary() {
-                // Parse clause but skip EXPLAIN/PROFILE detection
-                // We'll manually check for the clause type
-                let keyword = self.parse_keyword()?;
-                let clause = match keyword.to_uppercase().as_str() {
-                    "MATCH" => {
-                        let match_clause = self.parse_match_clause()?;
-                        Clause::Match(match_clause)
-                    }
-                    "CREATE" => {
-                        self.skip_whitespace();
-                        // Check for CREATE DATABASE/INDEX/CONSTRAINT/USER
-                        if self.peek_keyword("DATABASE") {
-                            Clause::CreateDatabase(self.parse_create_database_clause()?)
-                        } else if self.peek_keyword("INDEX") {
-                            Clause::CreateIndex(self.parse_create_index_clause()?)
-                        } else if self.peek_keyword("CONSTRAINT") {
-                            Clause::CreateConstraint(self.parse_create_constraint_clause()?)
-                        } else if self.peek_keyword("USER") {
-                            Clause::CreateUser(self.parse_create_user_clause()?)
-                        } else {
-                            Clause::Create(self.parse_create_clause()?)
-                        }
-                    }
-                    "MERGE" => Clause::Merge(self.parse_merge_clause()?),
-                    "SET" => Clause::Set(self.parse_set_clause()?),
-                    "DELETE" => Clause::Delete(self.parse_delete_clause()?),
-                    "DETACH" => {
-                        self.expect_keyword("DELETE")?;
-                        let mut delete_clause = self.parse_delete_clause()?;
-                        delete_clause.detach = true;
-                        Clause::Delete(delete_clause)
-                    }
-                    "REMOVE" => Clause::Remove(self.parse_remove_clause()?),
-                    "WITH" => Clause::With(self.parse_with_clause()?),
-                    "UNWIND" => Clause::Unwind(self.parse_unwind_clause()?),
-                    // EXPLAIN/PROFILE CALL { … } / CALL proc(...) — same
-                    // dual shape as the top-level parser. Previously
-                    // missing, so EXPLAIN/PROFILE over a CALL failed to
-                    // parse.
-                    "CALL" => {
-                        self.skip_whitespace();
-                        let next = self.peek_char();
-                        if next == Some('{') || next == Some('(') {
-                            Clause::CallSubquery(self.parse_call_subquery_clause()?)
-                        } else {
-                            Clause::CallProcedure(self.parse_call_procedure_clause()?)
-                        }
-                    }
-                    "UNION" => {
-                        self.skip_whitespace();
-                        let union_type = if self.peek_keyword("ALL") {
-                            self.parse_keyword()?;
-                            UnionType::All
-                        } else {
-                            UnionType::Distinct
-                        };
-                        Clause::Union(UnionClause { union_type })
-                    }
-                    "WHERE" => {
-                        if !Self::where_is_valid_after(clauses.last()) {
-                            return Err(self.reject_standalone_where());
-                        }
-                        Clause::Where(self.parse_where_clause()?)
-                    }
-                    "RETURN" => Clause::Return(self.parse_return_clause()?),
-                    "ORDER" => {
-                        self.expect_keyword("BY")?;
-                        Clause::OrderBy(self.parse_order_by_clause()?)
-                    }
-                    "LIMIT" => Clause::Limit(self.parse_limit_clause()?),
-                    "SKIP" => Clause::Skip(self.parse_skip_clause()?),
-                    "FOREACH" => Clause::Foreach(self.parse_foreach_clause()?),
-                    "OPTIONAL" => {
-                        self.expect_keyword("MATCH")?;
-                        let mut match_clause = self.parse_match_clause()?;
-                        match_clause.optional = true;
-                        Clause::Match(match_clause)
-                    }
-                    _ => return Err(self.error(&format!("Unexpected clause: {}", keyword))),
-                };
-                clauses.push(clause);
-                self.skip_whitespace();
-                if self.pos >= self.input.len() {
-                    break;
-                }
-            } else {
-                break;
-            }
+        let analyze = self.peek_keyword("ANALYZE");
+        if analyze {
+            self.parse_keyword()?;
+            self.skip_whitespace();
         }
-
-        // Extract the query string that was parsed
-        let query_str = self.input[start_pos..self.pos].trim().to_string();
-
-        if clauses.is_empty() {
-            return Err(self.error("EXPLAIN requires a query with at least one clause"));
+        let format = self.parse_plan_format()?;
+        let (query, query_str) = self.parse_wrapped_query(if analyze {
+            "EXPLAIN ANALYZE"
+        } else {
+            "EXPLAIN"
+        })?;
+
+        if analyze {
+            return Ok(Clause::Profile(ProfileClause {
+                query,
+                query_string: Some(query_str),
+                format,
+            }));
         }
-
-        let query = CypherQuery {
-            clauses,
-            params: std::collections::HashMap::new(),
-            graph_scope: None,
-        };
-
         Ok(Clause::Explain(ExplainClause {
             query,
             query_string: Some(query_str),
+            format,
         }))
     }
 
     /// Parse PROFILE clause
-    /// Syntax: PROFILE [query]
+    /// Syntax: PROFILE [FORMAT [=] JSON|TEXT|DOT] [query]
     pub(super) fn parse_profile_clause(&mut self) -> Result<Clause> {
         self.parse_keyword()?; // consume "PROFILE"
         self.skip_whitespace();
+        let format = self.parse_plan_format()?;
+        let (query, query_str) = self.parse_wrapped_query("PROFILE")?;
 
+        Ok(Clause::Profile(ProfileClause {
+            query,
+            query_string: Some(query_str),
+            format,
+        }))
+    }
+
+    /// Parse the optional `FORMAT [=] JSON|TEXT|DOT` option of
+    /// EXPLAIN / PROFILE; JSON when absent.
+    fn parse_plan_format(&mut self) -> Result<PlanFormat> {
+        if !self.peek_keyword("FORMAT") {
+            return Ok(PlanFormat::default());
+        }
+        self.parse_keyword()?; // consume "FORMAT"
+        self.skip_whitespace();
+        if self.peek_char() == Some('=') {
+            self.consume_char();
+            self.skip_whitespace();
+        }
+        let name = self.parse_identifier()?;
+        self.skip_whitespace();
+        name.parse().map_err(|_| {
+            self.error(&format!(
+                "Unknown plan format '{name}', expected JSON, TEXT or DOT"
+            ))
+        })
+    }
+
+    /// Parse the query wrapped by EXPLAIN / PROFILE (`wrapper`, used in
+    /// errors) and return it with its source text.
+    fn parse_wrapped_query(&mut self, wrapper: &str) -> Result<(CypherQuery, String)> {
         // Save current position to extract query string
         let start_pos = self.pos;
 
@@ -550,7 +479,7 @@ impl CypherParser {
 
         // Parse clauses until end of input
         // We need to parse clauses without checking for EXPLAIN/PROFILE again
-        // since we're already inside a PROFILE clause
+        // since we're already inside one
         while self.pos < self.input.len() {
             if self.is_clause_boundary() {
                 // Parse clause but skip EXPLAIN/PROFILE detection
@@ -647,7 +576,9 @@ impl CypherParser {
         let query_str = self.input[start_pos..self.pos].trim().to_string();
 
         if clauses.is_empty() {
-            return Err(self.error("PROFILE requires a query with at least one clause"));
+            return Err(self.error(&format!(
+                "{wrapper} requires a query with at least one clause"
+            )));
         }
 
         let query = CypherQuery {
@@ -655,10 +586,6 @@ impl CypherParser {
             params: std::collections::HashMap::new(),
             graph_scope: None,
         };
-
-        Ok(Clause::Profile(ProfileClause {
-            query,
-            query_string: Some(query_str),
-        }))
+        Ok((query, query_str))
     }
 }
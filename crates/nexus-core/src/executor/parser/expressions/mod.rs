@@ -27,4 +27,16 @@ impl CypherParser {
         self.skip_whitespace();
         self.parse_or_expression()
     }
+
+    /// Parse an expression that must span the whole input. Unlike
+    /// [`Self::parse_expression`], trailing text is an error instead of
+    /// being left for a following clause.
+    pub fn parse_standalone_expression(&mut self) -> Result<Expression> {
+        let expr = self.parse_expression()?;
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(self.error("Unexpected input after expression"));
+        }
+        Ok(expr)
+    }
 }
@@ -4,8 +4,9 @@
 //! - `clauses` — top-level parse dispatch and every `parse_*_clause`.
 //! - `expressions` — expression precedence climbing, property access,
 //!   function calls, list/map/parenthesis forms.
-//! - `tokens` — lexer helpers: keyword/identifier/number parsing,
-//!   character lookahead, whitespace skip.
+//! - `tokens` — lexer helpers: keyword/identifier/number parsing
+//!   (including backtick-escaped identifiers), character lookahead,
+//!   whitespace skip.
 //! - `tests` — test harness (cfg(test) only).
 
 pub mod ast;
@@ -17,6 +18,7 @@ pub mod tokens;
 mod tests;
 
 pub use ast::*;
+pub use tokens::escape_identifier;
 
 /// Cypher parser state machine. Constructors and core methods live here;
 /// the `impl CypherParser` blocks that hold actual parsing logic are in
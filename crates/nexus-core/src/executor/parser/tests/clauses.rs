@@ -578,3 +578,31 @@ fn optional_match_where_still_parses() {
     }
     assert!(matches!(query.clauses[2], Clause::Where(_)));
 }
+
+#[test]
+fn explain_analyze_parses_as_profile_with_a_format() {
+    let mut parser =
+        CypherParser::new("EXPLAIN ANALYZE FORMAT dot MATCH (n:Person) RETURN n".to_string());
+    let query = parser.parse().expect("EXPLAIN ANALYZE must parse");
+    match &query.clauses[0] {
+        Clause::Profile(profile) => {
+            assert_eq!(profile.format, PlanFormat::Dot);
+            assert_eq!(profile.query.clauses.len(), 2);
+            assert_eq!(
+                profile.query_string.as_deref(),
+                Some("MATCH (n:Person) RETURN n")
+            );
+        }
+        other => panic!("expected Clause::Profile, got {:?}", other),
+    }
+
+    let mut parser = CypherParser::new("EXPLAIN FORMAT=TEXT MATCH (n) RETURN n".to_string());
+    match &parser.parse().unwrap().clauses[0] {
+        Clause::Explain(explain) => assert_eq!(explain.format, PlanFormat::Text),
+        other => panic!("expected Clause::Explain, got {:?}", other),
+    }
+
+    let mut parser = CypherParser::new("PROFILE FORMAT yaml MATCH (n) RETURN n".to_string());
+    let err = parser.parse().expect_err("unknown formats are rejected");
+    assert!(err.to_string().contains("Unknown plan format 'yaml'"));
+}
@@ -266,6 +266,56 @@ fn parse_cypher25_rel_property_type_directed() {
     }
 }
 
+#[test]
+fn parse_rel_endpoint_constraint() {
+    let mut parser = CypherParser::new(
+        "CREATE CONSTRAINT works_at_ends IF NOT EXISTS \
+         FOR (:Person)-[r:WORKS_AT]->(:Company) REQUIRE ENDPOINTS"
+            .to_string(),
+    );
+    let q = parser.parse().expect("endpoint DDL must parse");
+    match &q.clauses[0] {
+        Clause::CreateConstraint(c) => {
+            assert_eq!(c.name.as_deref(), Some("works_at_ends"));
+            assert_eq!(c.constraint_type, ConstraintType::Endpoints);
+            assert_eq!(c.label, "WORKS_AT");
+            assert_eq!(c.source_label.as_deref(), Some("Person"));
+            assert_eq!(c.target_label.as_deref(), Some("Company"));
+            assert_eq!(c.entity, ConstraintEntity::Relationship);
+            assert!(c.if_not_exists);
+        }
+        other => panic!("expected CREATE CONSTRAINT, got {other:?}"),
+    }
+
+    // One free endpoint is fine; undirected or label-less patterns are not.
+    let mut parser = CypherParser::new(
+        "CREATE CONSTRAINT FOR ()-[r:WORKS_AT]->(:Company) REQUIRE ENDPOINTS".to_string(),
+    );
+    assert!(parser.parse().is_ok());
+    for bad in [
+        "CREATE CONSTRAINT FOR (:Person)-[r:WORKS_AT]-(:Company) REQUIRE ENDPOINTS",
+        "CREATE CONSTRAINT FOR ()-[r:WORKS_AT]->() REQUIRE ENDPOINTS",
+        "CREATE CONSTRAINT FOR (:Person)-[r:WORKS_AT]->() REQUIRE r.since IS NOT NULL",
+    ] {
+        assert!(
+            CypherParser::new(bad.to_string()).parse().is_err(),
+            "`{bad}` must be rejected"
+        );
+    }
+
+    let mut parser = CypherParser::new(
+        "DROP CONSTRAINT IF EXISTS FOR ()-[r:WORKS_AT]->() REQUIRE ENDPOINTS".to_string(),
+    );
+    match &parser.parse().expect("endpoint DROP must parse").clauses[0] {
+        Clause::DropConstraint(d) => {
+            assert_eq!(d.constraint_type, ConstraintType::Endpoints);
+            assert_eq!(d.label, "WORKS_AT");
+            assert!(d.if_exists);
+        }
+        other => panic!("expected DROP CONSTRAINT, got {other:?}"),
+    }
+}
+
 #[test]
 fn parse_legacy_constraint_still_accepted() {
     let mut parser =
@@ -282,6 +332,111 @@ fn parse_legacy_constraint_still_accepted() {
     }
 }
 
+#[test]
+fn parse_view_ddl() {
+    let mut parser = CypherParser::new(
+        "CREATE OR REPLACE VIEW active_users AS MATCH (u:User) WHERE u.active RETURN u;"
+            .to_string(),
+    );
+    let q = parser.parse().expect("CREATE VIEW must parse");
+    assert_eq!(q.clauses.len(), 1);
+    match &q.clauses[0] {
+        Clause::CreateView(v) => {
+            assert_eq!(v.name, "active_users");
+            assert_eq!(v.query, "MATCH (u:User) WHERE u.active RETURN u");
+            assert!(v.or_replace);
+            assert!(!v.if_not_exists);
+        }
+        other => panic!("expected CREATE VIEW, got {other:?}"),
+    }
+
+    let mut parser = CypherParser::new("DROP VIEW IF EXISTS active_users".to_string());
+    match &parser.parse().expect("DROP VIEW must parse").clauses[0] {
+        Clause::DropView(d) => {
+            assert_eq!(d.name, "active_users");
+            assert!(d.if_exists);
+        }
+        other => panic!("expected DROP VIEW, got {other:?}"),
+    }
+
+    let mut parser = CypherParser::new("SHOW VIEWS".to_string());
+    assert!(matches!(
+        parser.parse().unwrap().clauses[0],
+        Clause::ShowViews
+    ));
+
+    // OR REPLACE without VIEW still means an index.
+    let mut parser = CypherParser::new("CREATE OR REPLACE INDEX ON :Person(name)".to_string());
+    assert!(matches!(
+        parser.parse().unwrap().clauses[0],
+        Clause::CreateIndex(_)
+    ));
+    assert!(
+        CypherParser::new("CREATE VIEW v AS".to_string())
+            .parse()
+            .is_err()
+    );
+}
+
+#[test]
+fn parse_materialized_view_ddl() {
+    use crate::catalog::ViewRefresh;
+
+    let refresh_of = |cypher: &str| match &CypherParser::new(cypher.to_string())
+        .parse()
+        .unwrap_or_else(|e| panic!("{cypher} must parse: {e}"))
+        .clauses[0]
+    {
+        Clause::CreateView(v) => v.materialized,
+        other => panic!("expected CREATE VIEW, got {other:?}"),
+    };
+    assert_eq!(
+        refresh_of("CREATE MATERIALIZED VIEW totals AS MATCH (n:Order) RETURN count(n) AS c"),
+        Some(ViewRefresh::OnChange)
+    );
+    assert_eq!(
+        refresh_of(
+            "CREATE OR REPLACE MATERIALIZED VIEW totals REFRESH EVERY 5 MINUTES \
+             AS MATCH (n:Order) RETURN count(n) AS c"
+        ),
+        Some(ViewRefresh::Every {
+            interval_ms: 300_000
+        })
+    );
+    assert_eq!(
+        refresh_of(
+            "CREATE MATERIALIZED VIEW IF NOT EXISTS totals REFRESH MANUAL \
+             AS MATCH (n:Order) RETURN count(n) AS c"
+        ),
+        Some(ViewRefresh::Manual)
+    );
+    assert_eq!(
+        refresh_of("CREATE VIEW orders AS MATCH (n:Order) RETURN n"),
+        None
+    );
+    for bad in [
+        "CREATE VIEW orders REFRESH MANUAL AS MATCH (n:Order) RETURN n",
+        "CREATE MATERIALIZED VIEW t REFRESH EVERY 0 SECONDS AS MATCH (n) RETURN n",
+        "CREATE MATERIALIZED VIEW t REFRESH EVERY 3 DAYS AS MATCH (n) RETURN n",
+    ] {
+        assert!(
+            CypherParser::new(bad.to_string()).parse().is_err(),
+            "{bad} must not parse"
+        );
+    }
+
+    let mut parser = CypherParser::new("REFRESH MATERIALIZED VIEW totals".to_string());
+    match &parser.parse().expect("REFRESH VIEW must parse").clauses[0] {
+        Clause::RefreshView(r) => assert_eq!(r.name, "totals"),
+        other => panic!("expected REFRESH VIEW, got {other:?}"),
+    }
+    let mut parser = CypherParser::new("DROP MATERIALIZED VIEW totals".to_string());
+    assert!(matches!(
+        parser.parse().unwrap().clauses[0],
+        Clause::DropView(_)
+    ));
+}
+
 // ---------------------------------------------------------------
 // CALL { } IN TRANSACTIONS (Cypher 25) — parser-level tests
 // phase6_opencypher-subquery-transactions §1 + §2
@@ -367,6 +367,98 @@ fn test_parse_identifier() {
     assert!(parser.parse_identifier().is_err());
 }
 
+#[test]
+fn test_parse_escaped_identifier() {
+    let parser = CypherParser::new("`a b`".to_string());
+    assert!(parser.is_identifier_start());
+
+    let mut parser = CypherParser::new("`first name` rest".to_string());
+    assert_eq!(parser.parse_identifier().unwrap(), "first name");
+    assert_eq!(parser.peek_char(), Some(' '));
+
+    let mut parser = CypherParser::new("`a``b`".to_string());
+    assert_eq!(parser.parse_identifier().unwrap(), "a`b");
+
+    let mut parser = CypherParser::new("`🚀 Rocket`".to_string());
+    assert_eq!(parser.parse_identifier().unwrap(), "🚀 Rocket");
+
+    let mut parser = CypherParser::new("`MATCH`".to_string());
+    assert_eq!(parser.parse_identifier().unwrap(), "MATCH");
+
+    let mut parser = CypherParser::new("`unterminated".to_string());
+    assert!(parser.parse_identifier().is_err());
+
+    let mut parser = CypherParser::new("``".to_string());
+    assert!(parser.parse_identifier().is_err());
+}
+
+#[test]
+fn test_escape_identifier_round_trips() {
+    assert_eq!(escape_identifier("name"), "name");
+    assert_eq!(escape_identifier("first name"), "`first name`");
+    assert_eq!(escape_identifier("a`b"), "`a``b`");
+    assert_eq!(escape_identifier("1st"), "`1st`");
+
+    for name in ["first name", "a`b", "🚀", "1st", "plain_name"] {
+        let mut parser = CypherParser::new(escape_identifier(name).into_owned());
+        assert_eq!(parser.parse_identifier().unwrap(), name);
+    }
+}
+
+#[test]
+fn test_parse_escaped_identifiers_in_query() {
+    let query = CypherParser::new(
+        "MATCH (n:`my label` {`first name`: 'Ann'})-[:`KNOWS WELL`]->(m) \
+         WHERE n.`e-mail` IS NOT NULL RETURN n.`first name` AS `Display Name`"
+            .to_string(),
+    )
+    .parse()
+    .unwrap();
+    let Clause::Match(m) = &query.clauses[0] else {
+        panic!("expected MATCH, got {:?}", query.clauses[0]);
+    };
+    let PatternElement::Node(node) = &m.pattern.elements[0] else {
+        panic!("expected node pattern");
+    };
+    assert_eq!(node.labels, vec!["my label".to_string()]);
+    assert!(
+        node.properties
+            .as_ref()
+            .unwrap()
+            .properties
+            .contains_key("first name")
+    );
+    let PatternElement::Relationship(rel) = &m.pattern.elements[1] else {
+        panic!("expected relationship pattern");
+    };
+    assert_eq!(rel.types, vec!["KNOWS WELL".to_string()]);
+
+    let ret = query
+        .clauses
+        .iter()
+        .find_map(|c| match c {
+            Clause::Return(r) => Some(r),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(ret.items[0].alias.as_deref(), Some("Display Name"));
+    assert!(matches!(
+        &ret.items[0].expression,
+        Expression::PropertyAccess { property, .. } if property == "first name"
+    ));
+}
+
+#[test]
+fn test_parse_escaped_procedure_name() {
+    let query = CypherParser::new("CALL db.`labels`()".to_string())
+        .parse()
+        .unwrap();
+    let Clause::CallProcedure(call) = &query.clauses[0] else {
+        panic!("expected CALL, got {:?}", query.clauses[0]);
+    };
+    assert_eq!(call.procedure_name, "db.labels");
+}
+
 #[test]
 fn test_parse_keyword() {
     let mut parser = CypherParser::new("MATCH".to_string());
@@ -2,10 +2,33 @@
 //! keyword, and number parsers; character lookahead (`peek_char`,
 //! `peek_char_at`, `consume_char`, `expect_char`); whitespace/comment
 //! skip; and the `is_*` predicates that drive clause boundary detection.
+//!
+//! Identifiers are either plain (`[A-Za-z_][A-Za-z0-9_]*`) or
+//! backtick-escaped: `` `first name` `` accepts any characters up to
+//! the closing backtick, with a doubled backtick standing for a
+//! literal one. [`escape_identifier`] is the inverse used when
+//! generating Cypher from arbitrary label / type / property names.
 
 use super::CypherParser;
 use super::ast::UnaryOperator;
 use crate::{Error, Result};
+use std::borrow::Cow;
+
+/// Render `name` as a Cypher identifier: unchanged when it is a plain
+/// identifier, otherwise wrapped in backticks with inner backticks
+/// doubled, so the parser reads back exactly `name`.
+pub fn escape_identifier(name: &str) -> Cow<'_, str> {
+    let mut chars = name.chars();
+    let plain = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("`{}`", name.replace('`', "``")))
+    }
+}
 
 impl CypherParser {
     /// Parse unary operator
@@ -40,6 +63,10 @@ impl CypherParser {
 
     /// Parse identifier
     pub(super) fn parse_identifier(&mut self) -> Result<String> {
+        if self.peek_char() == Some('`') {
+            return self.parse_escaped_identifier();
+        }
+
         let start = self.pos;
 
         if !self.is_identifier_start() {
@@ -55,6 +82,28 @@ impl CypherParser {
         Ok(self.input[start..self.pos].to_string())
     }
 
+    /// Parse a backtick-escaped identifier; "``" inside it is a literal
+    /// backtick.
+    fn parse_escaped_identifier(&mut self) -> Result<String> {
+        self.expect_char('`')?;
+        let mut name = String::new();
+        loop {
+            match self.consume_char() {
+                Some('`') if self.peek_char() == Some('`') => {
+                    self.consume_char();
+                    name.push('`');
+                }
+                Some('`') => break,
+                Some(c) => name.push(c),
+                None => return Err(self.error("Unterminated escaped identifier")),
+            }
+        }
+        if name.is_empty() {
+            return Err(self.error("Empty escaped identifier"));
+        }
+        Ok(name)
+    }
+
     /// Parse number
     pub(super) fn parse_number(&mut self) -> Result<i64> {
         let start = self.pos;
@@ -107,6 +156,7 @@ impl CypherParser {
             || self.peek_keyword("FOREACH") // For FOREACH clause
             || self.peek_keyword("ALTER") // For ALTER DATABASE
             || self.peek_keyword("TERMINATE") // For TERMINATE QUERY
+            || self.peek_keyword("REFRESH") // For REFRESH VIEW
             || self.peek_keyword("SAVEPOINT") // phase6_opencypher-advanced-types §5
             || self.peek_keyword("RELEASE") // RELEASE SAVEPOINT
             // EXPLAIN / PROFILE prefix a whole query. Without these the
@@ -121,7 +171,7 @@ impl CypherParser {
     /// Check if character is identifier start
     pub(super) fn is_identifier_start(&self) -> bool {
         self.peek_char()
-            .map(|c| c.is_ascii_alphabetic() || c == '_')
+            .map(|c| c.is_ascii_alphabetic() || c == '_' || c == '`')
             .unwrap_or(false)
     }
 
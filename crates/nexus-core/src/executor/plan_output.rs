@@ -0,0 +1,399 @@
+//! EXPLAIN / PROFILE output: the plan tree and its JSON, text and
+//! Graphviz DOT renderings, plus the per-operator statistics PROFILE
+//! collects.
+//!
+//! The executor runs a plan as a flat pipeline, so the tree is the
+//! pipeline read bottom-up: the last operator is the root and each
+//! operator's child is the one before it. `Union` and `Join` carry their
+//! own inputs, which become extra children.
+//!
+//! PROFILE needs the row count and time of every operator, which are
+//! only known inside `Executor::execute_inner`. The engine arms a
+//! per-thread recording ([`start_recording`]); the first operator
+//! pipeline that runs on the thread afterwards fills it through an
+//! [`OperatorTimer`], so nested subquery pipelines never overwrite the
+//! outer query's numbers. The recording keeps the operators that
+//! actually ran, which can differ from a fresh plan of the same query.
+
+use super::types::Operator;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::time::Instant;
+
+/// Row count and time of one operator of a PROFILE run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OperatorStats {
+    /// Rows in the working set after the operator ran
+    pub rows: u64,
+    /// Time spent in the operator, in microseconds
+    pub time_us: u64,
+}
+
+/// One operator of a plan tree.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanNode {
+    /// Pre-order position in the tree, the root being 0
+    pub id: usize,
+    /// Operator name (`NodeByLabel`, `Filter`, ...)
+    pub operator: String,
+    /// Operator arguments
+    pub details: String,
+    /// Rows after the operator, PROFILE only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<u64>,
+    /// Time spent in the operator in microseconds, PROFILE only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_us: Option<u64>,
+    /// Inputs of the operator
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<PlanNode>,
+}
+
+/// Name and arguments of `op`, split out of its `Debug` form.
+pub fn describe_operator(op: &Operator) -> (String, String) {
+    match op {
+        // Their inputs are rendered as children.
+        Operator::Union { distinct, .. } => ("Union".to_string(), format!("distinct: {distinct}")),
+        Operator::Join {
+            join_type,
+            condition,
+            ..
+        } => (
+            "Join".to_string(),
+            format!("join_type: {join_type:?}, condition: {condition:?}"),
+        ),
+        _ => {
+            let debug = format!("{op:?}");
+            let end = debug
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(debug.len());
+            let (name, rest) = debug.split_at(end);
+            let rest = rest.trim();
+            let details = rest
+                .strip_prefix('{')
+                .and_then(|r| r.strip_suffix('}'))
+                .or_else(|| rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')))
+                .unwrap_or(rest)
+                .trim();
+            (name.to_string(), details.to_string())
+        }
+    }
+}
+
+/// Build the tree of a top-level pipeline. `stats[i]` describes
+/// `operators[i]`; operators nested in `Union` / `Join` get none.
+pub fn plan_tree(operators: &[Operator], stats: &[Option<OperatorStats>]) -> Option<PlanNode> {
+    let mut root = pipeline_node(operators, Some(stats))?;
+    let mut next_id = 0;
+    number(&mut root, &mut next_id);
+    Some(root)
+}
+
+fn pipeline_node(
+    operators: &[Operator],
+    stats: Option<&[Option<OperatorStats>]>,
+) -> Option<PlanNode> {
+    let (last, before) = operators.split_last()?;
+    let op_stats = stats.and_then(|s| s.get(before.len()).copied().flatten());
+    let (operator, details) = describe_operator(last);
+
+    let mut children: Vec<PlanNode> = pipeline_node(before, stats).into_iter().collect();
+    match last {
+        Operator::Union { left, right, .. } => {
+            children.extend(pipeline_node(left, None));
+            children.extend(pipeline_node(right, None));
+        }
+        Operator::Join { left, right, .. } => {
+            children.extend(pipeline_node(std::slice::from_ref(left), None));
+            children.extend(pipeline_node(std::slice::from_ref(right), None));
+        }
+        _ => {}
+    }
+
+    Some(PlanNode {
+        id: 0,
+        operator,
+        details,
+        rows: op_stats.map(|s| s.rows),
+        time_us: op_stats.map(|s| s.time_us),
+        children,
+    })
+}
+
+fn number(node: &mut PlanNode, next_id: &mut usize) {
+    node.id = *next_id;
+    *next_id += 1;
+    for child in &mut node.children {
+        number(child, next_id);
+    }
+}
+
+/// Indented text rendering, root first:
+///
+/// ```text
+/// Project  items: [...]  (rows: 1, time: 4 us)
+/// └── Filter  predicate: "n.age > 20"  (rows: 1, time: 9 us)
+///     └── NodeByLabel  label_id: 0, variable: "n"  (rows: 3, time: 21 us)
+/// ```
+pub fn render_text(root: &PlanNode) -> String {
+    let mut out = String::new();
+    text_line(&mut out, root, "", "");
+    out
+}
+
+fn text_line(out: &mut String, node: &PlanNode, first_prefix: &str, rest_prefix: &str) {
+    let _ = write!(out, "{first_prefix}{}", node.operator);
+    if !node.details.is_empty() {
+        let _ = write!(out, "  {}", node.details);
+    }
+    if let Some(stats) = stats_label(node) {
+        let _ = write!(out, "  ({stats})");
+    }
+    out.push('\n');
+    for (i, child) in node.children.iter().enumerate() {
+        let (branch, indent) = if i + 1 == node.children.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        text_line(
+            out,
+            child,
+            &format!("{rest_prefix}{branch}"),
+            &format!("{rest_prefix}{indent}"),
+        );
+    }
+}
+
+/// Graphviz DOT rendering, edges pointing from each operator to its
+/// inputs. `title`, when given, labels the whole graph.
+pub fn render_dot(root: &PlanNode, title: Option<&str>) -> String {
+    let mut out = String::from("digraph plan {\n");
+    out.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+    if let Some(title) = title {
+        let _ = writeln!(out, "  label=\"{}\";", dot_escape(title));
+        out.push_str("  labelloc=t;\n");
+    }
+    dot_node(&mut out, root);
+    out.push_str("}\n");
+    out
+}
+
+fn dot_node(out: &mut String, node: &PlanNode) {
+    let mut label = node.operator.clone();
+    if !node.details.is_empty() {
+        label.push('\n');
+        label.push_str(&node.details);
+    }
+    if let Some(stats) = stats_label(node) {
+        label.push('\n');
+        label.push_str(&stats);
+    }
+    let _ = writeln!(out, "  op{} [label=\"{}\"];", node.id, dot_escape(&label));
+    for child in &node.children {
+        let _ = writeln!(out, "  op{} -> op{};", node.id, child.id);
+        dot_node(out, child);
+    }
+}
+
+fn stats_label(node: &PlanNode) -> Option<String> {
+    match (node.rows, node.time_us) {
+        (Some(rows), Some(time_us)) => Some(format!("rows: {rows}, time: {time_us} us")),
+        _ => None,
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// What the first operator pipeline run after [`start_recording`]
+/// executed.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorRecording {
+    /// Operators of the recorded pipeline, `None` until one ran
+    pub operators: Option<Vec<Operator>>,
+    /// Stats per operator; `None` for operators that did not run
+    /// individually (fast paths execute several at once)
+    pub stats: Vec<Option<OperatorStats>>,
+}
+
+thread_local! {
+    static RECORDING: RefCell<Option<OperatorRecording>> = const { RefCell::new(None) };
+}
+
+/// Arm per-operator recording for the next pipeline this thread runs.
+pub(crate) fn start_recording() {
+    RECORDING.with(|slot| *slot.borrow_mut() = Some(OperatorRecording::default()));
+}
+
+/// Disarm recording and return what was recorded.
+pub(crate) fn finish_recording() -> OperatorRecording {
+    RECORDING.with(|slot| slot.borrow_mut().take().unwrap_or_default())
+}
+
+/// Times the operators of one pipeline run into the armed recording.
+/// Inactive (and free) when no recording is armed or another pipeline
+/// already claimed it.
+pub(crate) struct OperatorTimer {
+    active: bool,
+    current: Option<(usize, Instant)>,
+}
+
+impl OperatorTimer {
+    /// Claim the armed recording for `operators`, if it is unclaimed.
+    pub(crate) fn start(operators: &[Operator]) -> Self {
+        let active = RECORDING.with(|slot| match slot.borrow_mut().as_mut() {
+            Some(recording) if recording.operators.is_none() => {
+                recording.operators = Some(operators.to_vec());
+                true
+            }
+            _ => false,
+        });
+        Self {
+            active,
+            current: None,
+        }
+    }
+
+    /// Whether this pipeline is being recorded.
+    pub(crate) fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Operator `index` starts; the previous one ended with `rows`.
+    pub(crate) fn enter(&mut self, index: usize, rows: usize) {
+        if self.active {
+            self.close(rows);
+            self.current = Some((index, Instant::now()));
+        }
+    }
+
+    /// The last operator ended with `rows`.
+    pub(crate) fn finish(&mut self, rows: usize) {
+        if self.active {
+            self.close(rows);
+        }
+    }
+
+    fn close(&mut self, rows: usize) {
+        let Some((index, started)) = self.current.take() else {
+            return;
+        };
+        let stats = OperatorStats {
+            rows: rows as u64,
+            time_us: started.elapsed().as_micros() as u64,
+        };
+        RECORDING.with(|slot| {
+            if let Some(recording) = slot.borrow_mut().as_mut() {
+                if recording.stats.len() <= index {
+                    recording.stats.resize(index + 1, None);
+                }
+                recording.stats[index] = Some(stats);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipeline() -> Vec<Operator> {
+        vec![
+            Operator::NodeByLabel {
+                label_id: 0,
+                variable: "n".to_string(),
+            },
+            Operator::Filter {
+                predicate: "n.name = \"a\"".to_string(),
+            },
+            Operator::Limit { count: 5 },
+        ]
+    }
+
+    #[test]
+    fn the_last_operator_is_the_root() {
+        let stats = [
+            Some(OperatorStats {
+                rows: 3,
+                time_us: 7,
+            }),
+            None,
+        ];
+        let root = plan_tree(&pipeline(), &stats).unwrap();
+        assert_eq!(root.operator, "Limit");
+        assert_eq!(root.details, "count: 5");
+        assert_eq!(root.id, 0);
+        let filter = &root.children[0];
+        assert_eq!(filter.id, 1);
+        assert_eq!(filter.rows, None);
+        let scan = &filter.children[0];
+        assert_eq!(scan.operator, "NodeByLabel");
+        assert_eq!((scan.rows, scan.time_us), (Some(3), Some(7)));
+        assert!(scan.children.is_empty());
+    }
+
+    #[test]
+    fn union_inputs_become_children() {
+        let ops = vec![Operator::Union {
+            left: pipeline(),
+            right: vec![Operator::AllNodesScan {
+                variable: "m".to_string(),
+            }],
+            distinct: true,
+        }];
+        let root = plan_tree(&ops, &[]).unwrap();
+        assert_eq!(root.details, "distinct: true");
+        let names: Vec<_> = root.children.iter().map(|c| c.operator.as_str()).collect();
+        assert_eq!(names, ["Limit", "AllNodesScan"]);
+        assert_eq!(root.children[1].id, 4);
+    }
+
+    #[test]
+    fn text_and_dot_renderings() {
+        let root = plan_tree(&pipeline(), &[]).unwrap();
+        let text = render_text(&root);
+        assert_eq!(text.lines().count(), 3);
+        assert!(text.starts_with("Limit  count: 5\n└── Filter"));
+        assert!(text.contains("\n    └── NodeByLabel  label_id: 0"));
+
+        let dot = render_dot(&root, Some("q"));
+        assert!(dot.starts_with("digraph plan {"));
+        assert!(dot.contains("op0 -> op1;"));
+        assert!(dot.contains("op1 -> op2;"));
+        assert!(dot.contains(r#"predicate: \"n.name = \\\"a\\\"\""#));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn only_the_first_pipeline_is_recorded() {
+        start_recording();
+        let ops = pipeline();
+        let mut outer = OperatorTimer::start(&ops);
+        assert!(outer.is_active());
+        outer.enter(0, 0);
+        let mut nested = OperatorTimer::start(&ops[..1]);
+        assert!(!nested.is_active());
+        nested.enter(0, 0);
+        nested.finish(99);
+        outer.enter(2, 4);
+        outer.finish(2);
+
+        let recording = finish_recording();
+        assert_eq!(recording.operators.map(|o| o.len()), Some(3));
+        let rows: Vec<_> = recording.stats.iter().map(|s| s.map(|s| s.rows)).collect();
+        assert_eq!(rows, [Some(4), None, Some(2)]);
+        assert!(!OperatorTimer::start(&ops).is_active());
+    }
+}
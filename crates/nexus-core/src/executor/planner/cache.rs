@@ -36,7 +36,9 @@
 //! 4. Trims leading + trailing whitespace.
 //! 5. Preserves string literals byte-for-byte — the contents of
 //!    `"..."` and `'...'` are not touched, so a query like
-//!    `MATCH (n {note: 'a  b'})` keeps its literal intact.
+//!    `MATCH (n {note: 'a  b'})` keeps its literal intact. Backtick-
+//!    escaped identifiers (`` n.`first  name` ``) are preserved the
+//!    same way; `\` is not an escape inside them.
 //!
 //! # What the canonicaliser does NOT do
 //!
@@ -112,11 +114,11 @@ fn is_already_canonical(input: &str) -> bool {
                     }
                 }
             }
-            '\'' | '"' => {
+            '\'' | '"' | '`' => {
                 // Skip the literal — we don't care what's inside.
                 let quote = ch;
                 while let Some((_, next)) = iter.next() {
-                    if next == '\\' {
+                    if next == '\\' && quote != '`' {
                         // Skip the escaped char.
                         iter.next();
                         continue;
@@ -175,7 +177,7 @@ fn rewrite(input: &str) -> String {
                     prev_space = true;
                 }
             }
-            b'\'' | b'"' => {
+            b'\'' | b'"' | b'`' => {
                 let quote = b;
                 out.push(quote as char);
                 // Copy the literal verbatim, honouring `\` escapes.
@@ -186,7 +188,7 @@ fn rewrite(input: &str) -> String {
                         escaped = false;
                         continue;
                     }
-                    if c == b'\\' {
+                    if c == b'\\' && quote != b'`' {
                         escaped = true;
                         continue;
                     }
@@ -628,6 +630,13 @@ mod tests {
         assert_eq!(c, r#"MATCH (n) WHERE n.note = "a\"b" RETURN n"#);
     }
 
+    #[test]
+    fn escaped_identifier_preserved() {
+        let q = r"MATCH  (n) RETURN n.`first  name`, n.`a\`";
+        let c = canonicalise_query(q);
+        assert_eq!(c, r"MATCH (n) RETURN n.`first  name`, n.`a\`");
+    }
+
     #[test]
     fn comment_inside_string_literal_not_stripped() {
         let q = "MATCH (n {note: 'a // b'}) RETURN n";
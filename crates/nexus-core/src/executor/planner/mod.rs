@@ -2,17 +2,23 @@
 //!
 //! - `queries` — the bulk of `impl QueryPlanner` (cost-based optimisation,
 //!   pattern reordering, join algorithm choice, index push-down).
+//! - `projection` — property projection push-down for label scans.
+//! - `neighbor_count` — count-only expansions rewritten to `ExpandCount`.
 //! - `tests` — cfg(test) harness.
 
 pub mod cache;
+pub mod neighbor_count;
 pub mod preparse;
+pub mod projection;
 pub mod queries;
 
 #[cfg(test)]
 mod tests;
 
 pub use cache::{CANONICAL_VERSION, canonicalise_query, hash_canonicalised};
+pub use neighbor_count::push_down_neighbor_counts;
 pub use preparse::{PlanHint, extract_plan_hints};
+pub use projection::property_projections;
 
 use super::parser::{
     BinaryOperator, Clause, CypherQuery, Expression, Literal, NodePattern, Pattern, PatternElement,
@@ -243,6 +249,10 @@ pub struct QueryPlanner<'a> {
     /// pair has a registered property index and emits
     /// `ERR_USING_INDEX_NOT_FOUND` when it doesn't.
     property_index: Option<&'a crate::index::PropertyIndex>,
+    /// Property bloom filters. When `Some`, an un-indexed inline equality
+    /// on a `(label, property)` pair with a registered filter plans as
+    /// `NodeBloomProbe` instead of `NodeByLabel`.
+    property_bloom: Option<&'a crate::index::PropertyBloomRegistry>,
     /// Query plan cache for performance optimization
     plan_cache: QueryPlanCache,
     /// Aggregation result cache for intermediate results
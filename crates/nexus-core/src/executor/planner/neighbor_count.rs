@@ -0,0 +1,288 @@
+//! Neighbor-count push-down.
+//!
+//! `MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n, count(m)` only needs the
+//! number of `FOLLOWS` relationships per `n`, yet the plain plan expands
+//! every `(n, m)` pair — reading each target node and building one row
+//! per relationship — just for the aggregate to count the rows again.
+//! This pass spots that shape in a finished plan and swaps the `Expand`
+//! for an `ExpandCount`, which writes each source row's degree into a
+//! hidden column, and the `count(m)` for a `sum` over that column.
+//!
+//! Like the projection push-down, the analysis is conservative. The plan
+//! may hold node scans, `Filter`s, exactly one non-optional `Expand`
+//! with an anonymous relationship, `Project`, one grouped `Aggregate`,
+//! `Sort` and `Limit`. Every aggregation must be a non-`DISTINCT`
+//! `count(*)` or `count(m)`, and `m` must not be read anywhere else.
+//! Anything else keeps the original plan.
+
+use super::super::parser::{CypherParser, Expression};
+use super::super::{Aggregation, Operator, ProjectionItem};
+
+/// Prefix of the hidden column an `ExpandCount` writes its count into.
+const COUNT_COLUMN_PREFIX: &str = "__neighbor_count_";
+
+/// Rewrite a count-only expansion into `ExpandCount`; any other plan is
+/// returned unchanged.
+pub fn push_down_neighbor_counts(operators: Vec<Operator>) -> Vec<Operator> {
+    let Some((expand_idx, target)) = count_only_expansion(&operators) else {
+        return operators;
+    };
+    let count_column = format!("{COUNT_COLUMN_PREFIX}{target}");
+
+    let mut rewritten = Vec::with_capacity(operators.len());
+    for (idx, op) in operators.into_iter().enumerate() {
+        let op = match op {
+            Operator::Expand {
+                type_ids,
+                direction,
+                source_var,
+                ..
+            } if idx == expand_idx => Operator::ExpandCount {
+                type_ids,
+                direction,
+                source_var,
+                count_column: count_column.clone(),
+            },
+            Operator::Project { items } => {
+                let items = without_target_item(items, &target);
+                if items.is_empty() {
+                    continue;
+                }
+                Operator::Project { items }
+            }
+            Operator::Aggregate {
+                group_by,
+                aggregations,
+                projection_items,
+                output_order,
+                source,
+                streaming_optimized,
+                push_down_optimized,
+            } => Operator::Aggregate {
+                group_by,
+                aggregations: aggregations
+                    .into_iter()
+                    .map(|aggregation| match aggregation {
+                        Aggregation::Count { alias, .. } => Aggregation::Sum {
+                            column: count_column.clone(),
+                            alias,
+                        },
+                        other => other,
+                    })
+                    .collect(),
+                projection_items: projection_items
+                    .map(|items| without_target_item(items, &target))
+                    .filter(|items| !items.is_empty()),
+                output_order,
+                source,
+                streaming_optimized,
+                push_down_optimized,
+            },
+            other => other,
+        };
+        rewritten.push(op);
+    }
+    rewritten
+}
+
+/// Index of the rewritable `Expand` and its target variable, if the plan
+/// has the count-only shape described in the module docs.
+fn count_only_expansion(operators: &[Operator]) -> Option<(usize, String)> {
+    let mut expansions = operators
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| matches!(op, Operator::Expand { .. }));
+    let (Some((expand_idx, expand)), None) = (expansions.next(), expansions.next()) else {
+        return None;
+    };
+    let Operator::Expand {
+        source_var,
+        target_var,
+        rel_var,
+        optional,
+        ..
+    } = expand
+    else {
+        return None;
+    };
+    if *optional || !rel_var.is_empty() || source_var.is_empty() || target_var.is_empty() {
+        return None;
+    }
+    let target = target_var.as_str();
+
+    let mut aggregate_seen = false;
+    for (idx, op) in operators.iter().enumerate() {
+        let ok = match op {
+            Operator::NodeByLabel { variable, .. }
+            | Operator::NodeIndexSeek { variable, .. }
+            | Operator::NodeBloomProbe { variable, .. }
+            | Operator::AllNodesScan { variable } => variable != target,
+            Operator::Expand { .. } => idx == expand_idx,
+            Operator::Filter { predicate } => aggregate_seen || !text_mentions(predicate, target),
+            Operator::Project { items } => {
+                aggregate_seen || items.iter().all(|item| !item_reads_target(item, target))
+            }
+            Operator::Aggregate {
+                group_by,
+                aggregations,
+                projection_items,
+                ..
+            } => {
+                let ok = !aggregate_seen
+                    && idx > expand_idx
+                    && !group_by.is_empty()
+                    && group_by.iter().all(|column| !text_mentions(column, target))
+                    && aggregations.iter().all(|aggregation| {
+                        matches!(
+                            aggregation,
+                            Aggregation::Count { column, distinct: false, .. }
+                                if column.as_deref().is_none_or(|c| c == target)
+                        )
+                    })
+                    && projection_items
+                        .iter()
+                        .flatten()
+                        .all(|item| !item_reads_target(item, target));
+                aggregate_seen = true;
+                ok
+            }
+            Operator::Sort { .. } | Operator::Limit { .. } => aggregate_seen,
+            _ => false,
+        };
+        if !ok {
+            return None;
+        }
+    }
+    aggregate_seen.then(|| (expand_idx, target.to_string()))
+}
+
+/// Whether a projection item reads `target` other than as the bare
+/// `target` column the planner adds for `count(target)`.
+fn item_reads_target(item: &ProjectionItem, target: &str) -> bool {
+    let is_count_column =
+        item.alias == target && matches!(&item.expression, Expression::Variable(v) if v == target);
+    !is_count_column && mentions(&item.expression, target)
+}
+
+fn without_target_item(items: Vec<ProjectionItem>, target: &str) -> Vec<ProjectionItem> {
+    items
+        .into_iter()
+        .filter(|item| {
+            !(item.alias == target
+                && matches!(&item.expression, Expression::Variable(v) if v == target))
+        })
+        .collect()
+}
+
+/// Parse an operator's textual expression and check it for `variable`.
+/// Text that does not parse is assumed to use it.
+fn text_mentions(text: &str, variable: &str) -> bool {
+    match CypherParser::new(text.to_string()).parse_standalone_expression() {
+        Ok(expr) => mentions(&expr, variable),
+        Err(_) => true,
+    }
+}
+
+/// Whether `expr` may read `variable`. Expression forms the walker does
+/// not understand count as a use.
+fn mentions(expr: &Expression, variable: &str) -> bool {
+    match expr {
+        Expression::Literal(_) | Expression::Parameter(_) => false,
+        Expression::Variable(name) | Expression::PropertyAccess { variable: name, .. } => {
+            name == variable
+        }
+        Expression::ArrayIndex { base, index } => {
+            mentions(base, variable) || mentions(index, variable)
+        }
+        Expression::ArraySlice { base, start, end } => {
+            mentions(base, variable)
+                || start
+                    .iter()
+                    .chain(end.iter())
+                    .any(|e| mentions(e, variable))
+        }
+        Expression::FunctionCall { args, .. } | Expression::List(args) => {
+            args.iter().any(|e| mentions(e, variable))
+        }
+        Expression::Map(entries) => entries.values().any(|e| mentions(e, variable)),
+        Expression::BinaryOp { left, right, .. } => {
+            mentions(left, variable) || mentions(right, variable)
+        }
+        Expression::UnaryOp { operand, .. } => mentions(operand, variable),
+        Expression::IsNull { expr, .. } => mentions(expr, variable),
+        Expression::Case {
+            input,
+            when_clauses,
+            else_clause,
+        } => {
+            input
+                .iter()
+                .chain(else_clause.iter())
+                .any(|e| mentions(e, variable))
+                || when_clauses
+                    .iter()
+                    .any(|w| mentions(&w.condition, variable) || mentions(&w.result, variable))
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::executor::planner::QueryPlanner;
+    use crate::index::{KnnIndex, LabelIndex};
+    use crate::testing::TestContext;
+
+    fn plan(cypher: &str) -> Vec<Operator> {
+        let ctx = TestContext::new();
+        let catalog = Catalog::with_isolated_path(
+            ctx.path().join("catalog.mdb"),
+            crate::catalog::CATALOG_MMAP_INITIAL_SIZE,
+        )
+        .unwrap();
+        let label_index = LabelIndex::new();
+        let knn_index = KnnIndex::new(crate::index::DEFAULT_VECTORIZER_DIMENSION).unwrap();
+        let mut planner = QueryPlanner::new(&catalog, &label_index, &knn_index);
+        let query = CypherParser::new(cypher.to_string()).parse().unwrap();
+        let operators = planner.plan_query(&query).unwrap();
+        let operators = planner.optimize_operator_order(operators).unwrap();
+        push_down_neighbor_counts(operators)
+    }
+
+    fn counts(operators: &[Operator]) -> bool {
+        operators
+            .iter()
+            .any(|op| matches!(op, Operator::ExpandCount { .. }))
+    }
+
+    #[test]
+    fn grouped_neighbor_count_uses_expand_count() {
+        let operators = plan("MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n, count(m) AS c");
+        assert!(counts(&operators), "plan = {operators:?}");
+        assert!(
+            !operators
+                .iter()
+                .any(|op| matches!(op, Operator::Expand { .. }))
+        );
+        assert!(counts(&plan(
+            "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n.name, count(*) AS c ORDER BY c DESC LIMIT 3"
+        )));
+    }
+
+    #[test]
+    fn reading_the_neighbor_keeps_the_expand() {
+        for cypher in [
+            "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n, count(DISTINCT m)",
+            "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n, m.name, count(m)",
+            "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n, collect(m)",
+            "MATCH (n:Person)-[:FOLLOWS]->(m) WHERE m.age > 3 RETURN n, count(m)",
+            "MATCH (n:Person)-[r:FOLLOWS]->(m) RETURN n, count(r)",
+            "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN count(m)",
+            "MATCH (n:Person)-[:FOLLOWS]->(m) RETURN n, m",
+        ] {
+            assert!(!counts(&plan(cypher)), "{cypher} must keep Expand");
+        }
+    }
+}
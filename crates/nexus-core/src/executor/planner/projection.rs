@@ -0,0 +1,161 @@
+//! Property projection push-down.
+//!
+//! `MATCH (n:Person) RETURN n.name` only ever reads `name`, yet the label
+//! scan used to deserialize every property of every node. This pass
+//! inspects a finished plan and, when the scanned variable is only read
+//! through `variable.key` accesses, reports the keys it needs so the scan
+//! can ask the property store for just those.
+//!
+//! The analysis is deliberately conservative: a single `NodeByLabel`
+//! scan followed by `Filter` / `Project` / `Sort` / `Distinct` / `Limit`
+//! only. Any bare use of the variable (`RETURN n`, `id(n)`, `keys(n)`),
+//! any expression form the walker does not understand, and any other
+//! operator in the plan disable the push-down for the whole query.
+
+use super::super::Operator;
+use super::super::parser::{CypherParser, Expression};
+use std::collections::HashMap;
+
+/// Property keys each scan variable needs, keyed by variable name. A
+/// variable missing from the map must be materialised with all of its
+/// properties.
+pub fn property_projections(operators: &[Operator]) -> HashMap<String, Vec<String>> {
+    let mut projections = HashMap::new();
+
+    let mut scans = operators.iter().filter_map(|op| match op {
+        Operator::NodeByLabel { variable, .. } => Some(variable),
+        _ => None,
+    });
+    let (Some(variable), None) = (scans.next(), scans.next()) else {
+        return projections;
+    };
+
+    let mut keys = Vec::new();
+    for op in operators {
+        let ok = match op {
+            Operator::NodeByLabel { .. } | Operator::Limit { .. } => true,
+            Operator::Filter { predicate } => collect_from_text(predicate, variable, &mut keys),
+            Operator::Project { items } => items
+                .iter()
+                .all(|item| collect_keys(&item.expression, variable, &mut keys)),
+            Operator::Sort { columns, .. } | Operator::Distinct { columns } => columns
+                .iter()
+                .all(|column| collect_from_text(column, variable, &mut keys)),
+            _ => false,
+        };
+        if !ok {
+            return projections;
+        }
+    }
+
+    projections.insert(variable.clone(), keys);
+    projections
+}
+
+/// Parse an operator's textual expression and collect from it. Text that
+/// does not parse as a single expression (label checks, legacy string
+/// forms) is treated as an unknown use of the variable.
+fn collect_from_text(text: &str, variable: &str, keys: &mut Vec<String>) -> bool {
+    match CypherParser::new(text.to_string()).parse_standalone_expression() {
+        Ok(expr) => collect_keys(&expr, variable, keys),
+        Err(_) => false,
+    }
+}
+
+/// Record every `variable.key` read in `expr`. Returns `false` as soon as
+/// `variable` is used in any other way.
+fn collect_keys(expr: &Expression, variable: &str, keys: &mut Vec<String>) -> bool {
+    match expr {
+        Expression::Literal(_) | Expression::Parameter(_) => true,
+        Expression::Variable(name) => name != variable,
+        Expression::PropertyAccess {
+            variable: name,
+            property,
+        } => {
+            if name == variable && !keys.contains(property) {
+                keys.push(property.clone());
+            }
+            true
+        }
+        Expression::ArrayIndex { base, index } => {
+            collect_keys(base, variable, keys) && collect_keys(index, variable, keys)
+        }
+        Expression::ArraySlice { base, start, end } => {
+            collect_keys(base, variable, keys)
+                && start
+                    .iter()
+                    .chain(end.iter())
+                    .all(|e| collect_keys(e, variable, keys))
+        }
+        Expression::FunctionCall { args, .. } | Expression::List(args) => {
+            args.iter().all(|e| collect_keys(e, variable, keys))
+        }
+        Expression::Map(entries) => entries.values().all(|e| collect_keys(e, variable, keys)),
+        Expression::BinaryOp { left, right, .. } => {
+            collect_keys(left, variable, keys) && collect_keys(right, variable, keys)
+        }
+        Expression::UnaryOp { operand, .. } => collect_keys(operand, variable, keys),
+        Expression::IsNull { expr, .. } => collect_keys(expr, variable, keys),
+        Expression::Case {
+            input,
+            when_clauses,
+            else_clause,
+        } => {
+            input
+                .iter()
+                .chain(else_clause.iter())
+                .all(|e| collect_keys(e, variable, keys))
+                && when_clauses.iter().all(|w| {
+                    collect_keys(&w.condition, variable, keys)
+                        && collect_keys(&w.result, variable, keys)
+                })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::executor::planner::QueryPlanner;
+    use crate::index::{KnnIndex, LabelIndex};
+    use crate::testing::TestContext;
+
+    fn projections_for(cypher: &str) -> HashMap<String, Vec<String>> {
+        let ctx = TestContext::new();
+        let catalog = Catalog::with_isolated_path(
+            ctx.path().join("catalog.mdb"),
+            crate::catalog::CATALOG_MMAP_INITIAL_SIZE,
+        )
+        .unwrap();
+        let label_index = LabelIndex::new();
+        let knn_index = KnnIndex::new(crate::index::DEFAULT_VECTORIZER_DIMENSION).unwrap();
+        let mut planner = QueryPlanner::new(&catalog, &label_index, &knn_index);
+        let query = CypherParser::new(cypher.to_string()).parse().unwrap();
+        let operators = planner.plan_query(&query).unwrap();
+        property_projections(&operators)
+    }
+
+    #[test]
+    fn property_only_return_is_projected() {
+        let projections =
+            projections_for("MATCH (n:Person) WHERE n.age > 30 RETURN n.name, n.age + 1 AS next");
+        let mut keys = projections.get("n").cloned().unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["age".to_string(), "name".to_string()]);
+    }
+
+    #[test]
+    fn whole_node_use_disables_projection() {
+        assert!(projections_for("MATCH (n:Person) RETURN n").is_empty());
+        assert!(projections_for("MATCH (n:Person) RETURN id(n), n.name").is_empty());
+        assert!(projections_for("MATCH (n:Person) RETURN keys(n)").is_empty());
+    }
+
+    #[test]
+    fn other_operators_disable_projection() {
+        assert!(projections_for("MATCH (n:Person)-[:KNOWS]->(m) RETURN n.name").is_empty());
+        assert!(projections_for("MATCH (n:Person) RETURN n.city, count(*)").is_empty());
+    }
+}
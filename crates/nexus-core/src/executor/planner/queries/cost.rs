@@ -20,6 +20,12 @@ impl<'a> QueryPlanner<'a> {
                     // far cheaper than a label scan; bias the planner toward it.
                     total_cost += 5.0;
                 }
+                Operator::NodeBloomProbe { label_id, .. } => {
+                    // Costed as the label scan it falls back to; the probe
+                    // only pays off at run time.
+                    let selectivity = self.estimate_label_selectivity(*label_id)?;
+                    total_cost += 1000.0 * selectivity;
+                }
                 Operator::AllNodesScan { .. } => {
                     // Scanning all nodes is more expensive than label scan
                     // Assume full scan of all nodes
@@ -37,6 +43,11 @@ impl<'a> QueryPlanner<'a> {
                     // Relationship traversal is expensive
                     total_cost += 100.0;
                 }
+                Operator::ExpandCount { .. } => {
+                    // Reads adjacency but materializes neither targets
+                    // nor per-pair rows
+                    total_cost += 40.0;
+                }
                 Operator::Project { .. } => {
                     // Projection is cheap
                     total_cost += 1.0;
@@ -276,7 +287,9 @@ impl<'a> QueryPlanner<'a> {
             } => {
                 // Estimate relationship expansion cost
                 let rel_stats = self.estimate_relationship_stats(&Some(type_ids.clone()))?;
-                let avg_relationships_per_node = rel_stats.avg_relationships_per_node;
+                let avg_relationships_per_node = self
+                    .endpoint_constrained_fanout(type_ids, direction)
+                    .unwrap_or(rel_stats.avg_relationships_per_node);
 
                 let output_cardinality = input_cardinality * avg_relationships_per_node;
 
@@ -467,6 +480,39 @@ impl<'a> QueryPlanner<'a> {
         })
     }
 
+    /// Per-node fanout of a single-type expand whose departure side is
+    /// pinned by a relationship endpoint constraint. When every `:T`
+    /// starts at a `:Person`, an outgoing expand from a row fans out by
+    /// `count(T) / count(:Person)` rather than `count(T) / count(all
+    /// nodes)`, which badly underestimates on graphs where the source
+    /// label is a small fraction of the nodes. `None` when no
+    /// constraint applies or the counters are still cold.
+    pub(super) fn endpoint_constrained_fanout(
+        &self,
+        type_ids: &[u32],
+        direction: &Direction,
+    ) -> Option<f64> {
+        let [type_id] = type_ids else {
+            return None;
+        };
+        let (source, target) = self.catalog.get_rel_endpoint_constraint(*type_id).ok()??;
+        let label_id = match direction {
+            Direction::Outgoing => source?,
+            Direction::Incoming => target?,
+            Direction::Both => return None,
+        };
+        let rel_count = self.catalog.get_rel_count(*type_id).ok()?;
+        let label_count = self
+            .label_index
+            .get_nodes_with_labels(&[label_id])
+            .ok()?
+            .len();
+        if rel_count == 0 || label_count == 0 {
+            return None;
+        }
+        Some(rel_count as f64 / label_count as f64)
+    }
+
     /// Get query plan cache statistics
     pub fn plan_cache_stats(&self) -> &QueryPlanCacheStats {
         self.plan_cache.stats()
@@ -1,6 +1,18 @@
 //! Expression/pattern serialisation and aggregation detection helpers.
 
 use super::*;
+use crate::executor::parser::escape_identifier;
+use std::borrow::Cow;
+
+/// Render a label for re-parsing: `$param` sentinels stay as they are,
+/// names that are not plain identifiers are backtick-escaped.
+fn label_source(label: &str) -> Cow<'_, str> {
+    if label.starts_with('$') {
+        Cow::Borrowed(label)
+    } else {
+        escape_identifier(label)
+    }
+}
 
 impl<'a> QueryPlanner<'a> {
     /// Convert expression to string representation
@@ -13,7 +25,7 @@ impl<'a> QueryPlanner<'a> {
         match expr {
             Expression::Variable(name) => Ok(name.clone()),
             Expression::PropertyAccess { variable, property } => {
-                Ok(format!("{}.{}", variable, property))
+                Ok(format!("{}.{}", variable, escape_identifier(property)))
             }
             Expression::ArrayIndex { base, index } => {
                 let base_str = self.expression_to_string(base)?;
@@ -77,7 +89,7 @@ impl<'a> QueryPlanner<'a> {
                 let mut pairs = Vec::new();
                 for (key, value) in map {
                     let value_str = self.expression_to_string(value)?;
-                    pairs.push(format!("{}: {}", key, value_str));
+                    pairs.push(format!("{}: {}", escape_identifier(key), value_str));
                 }
                 Ok(format!("{{{}}}", pairs.join(", ")))
             }
@@ -94,7 +106,7 @@ impl<'a> QueryPlanner<'a> {
                         Expression::Literal(Literal::String(label)),
                     ) = (&args[0], &args[1])
                     {
-                        return Ok(format!("{}:{}", var, label));
+                        return Ok(format!("{}:{}", var, label_source(label)));
                     }
                 }
                 let arg_strs: Result<Vec<String>> =
@@ -146,7 +158,7 @@ impl<'a> QueryPlanner<'a> {
                     }
                     for label in &node.labels {
                         result.push(':');
-                        result.push_str(label);
+                        result.push_str(&label_source(label));
                     }
                     if let Some(ref props) = node.properties {
                         if !props.properties.is_empty() {
@@ -157,7 +169,7 @@ impl<'a> QueryPlanner<'a> {
                                 .map(|(k, v)| {
                                     format!(
                                         "{}: {}",
-                                        k,
+                                        escape_identifier(k),
                                         self.expression_to_string(v)
                                             .unwrap_or_else(|_| "?".to_string())
                                     )
@@ -191,7 +203,7 @@ impl<'a> QueryPlanner<'a> {
                         } else {
                             result.push('|');
                         }
-                        result.push_str(rel_type);
+                        result.push_str(&escape_identifier(rel_type));
                     }
                     // Handle variable length patterns
                     if let Some(ref quant) = rel.quantifier {
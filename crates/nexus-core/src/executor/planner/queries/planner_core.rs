@@ -17,6 +17,7 @@ impl<'a> QueryPlanner<'a> {
             knn_index,
             rtree_registry: None,
             property_index: None,
+            property_bloom: None,
             plan_cache: QueryPlanCache::new(1000, Duration::from_secs(300)), // 1000 plans, 5min TTL
             aggregation_cache: AggregationCache::new(500, Duration::from_secs(180)), // 500 results, 3min TTL
             notifications: Vec::new(),
@@ -58,6 +59,17 @@ impl<'a> QueryPlanner<'a> {
         self
     }
 
+    /// Builder shim: install the property bloom filters so un-indexed
+    /// inline equalities on a filtered `(label, property)` pair plan as
+    /// `NodeBloomProbe`. Without a handle they plan as `NodeByLabel`.
+    pub fn with_property_bloom(
+        mut self,
+        registry: &'a crate::index::PropertyBloomRegistry,
+    ) -> Self {
+        self.property_bloom = Some(registry);
+        self
+    }
+
     /// Generate a hash for query caching based on query structure
     pub(super) fn hash_query(&self, query: &CypherQuery) -> u64 {
         use std::collections::hash_map::DefaultHasher;
@@ -222,6 +234,7 @@ impl<'a> QueryPlanner<'a> {
                     knn_index: self.knn_index,
                     rtree_registry: self.rtree_registry.clone(),
                     property_index: self.property_index,
+                    property_bloom: self.property_bloom,
                     plan_cache: QueryPlanCache::new(0, std::time::Duration::from_secs(0)), // Empty cache
                     aggregation_cache: AggregationCache::new(
                         100,
@@ -1,6 +1,6 @@
 //! `plan_execution_strategy`, `synthesise_anonymous_source_anchors`,
-//! `select_start_pattern`, and `node_index_seek_for` — the pattern-to-operator
-//! lowering pass.
+//! `select_start_pattern`, `node_index_seek_for`, and `node_bloom_probe_for`
+//! — the pattern-to-operator lowering pass.
 
 use super::*;
 
@@ -179,9 +179,14 @@ impl<'a> QueryPlanner<'a> {
                             });
                         } else {
                             // Normal planning — prefer an index seek when a
-                            // covering property index exists, else label scan.
+                            // covering property index exists, then a bloom-guarded
+                            // label scan, else a plain label scan.
                             if let Some(seek) = self.node_index_seek_for(node, label_id, variable) {
                                 operators.push(seek);
+                            } else if let Some(probe) =
+                                self.node_bloom_probe_for(node, label_id, variable)
+                            {
+                                operators.push(probe);
                             } else {
                                 operators.push(Operator::NodeByLabel {
                                     label_id,
@@ -363,6 +368,10 @@ impl<'a> QueryPlanner<'a> {
                             let label_id = self.catalog.get_or_create_label(first_label)?;
                             if let Some(seek) = self.node_index_seek_for(node, label_id, variable) {
                                 operators.push(seek);
+                            } else if let Some(probe) =
+                                self.node_bloom_probe_for(node, label_id, variable)
+                            {
+                                operators.push(probe);
                             } else {
                                 operators.push(Operator::NodeByLabel {
                                     label_id,
@@ -1340,4 +1349,43 @@ impl<'a> QueryPlanner<'a> {
         }
         None
     }
+
+    /// Build a `NodeBloomProbe` for the first inline equality property of
+    /// `node` whose `(label_id, key_id)` has a registered bloom filter and
+    /// whose value is a string, number, or boolean literal. Returns `None`
+    /// (caller falls back to `NodeByLabel`) when no bloom handle is
+    /// installed or no property qualifies.
+    fn node_bloom_probe_for(
+        &self,
+        node: &NodePattern,
+        label_id: u32,
+        variable: &str,
+    ) -> Option<Operator> {
+        let blooms = self.property_bloom?;
+        let property_map = node.properties.as_ref()?;
+        for (prop_name, expr) in &property_map.properties {
+            let value = match expr {
+                Expression::Literal(Literal::String(s)) => serde_json::Value::from(s.clone()),
+                Expression::Literal(Literal::Integer(i)) => serde_json::Value::from(*i),
+                Expression::Literal(Literal::Float(f)) => match serde_json::Number::from_f64(*f) {
+                    Some(n) => serde_json::Value::Number(n),
+                    None => continue,
+                },
+                Expression::Literal(Literal::Boolean(b)) => serde_json::Value::Bool(*b),
+                _ => continue,
+            };
+            let Ok(key_id) = self.catalog.get_key_id(prop_name) else {
+                continue;
+            };
+            if blooms.has_filter(label_id, key_id) {
+                return Some(Operator::NodeBloomProbe {
+                    label_id,
+                    key_id,
+                    value,
+                    variable: variable.to_string(),
+                });
+            }
+        }
+        None
+    }
 }
@@ -1463,3 +1463,45 @@ fn unindexed_property_notification_no_op_without_property_index_handle() {
         "no notifications expected, got: {notes:?}"
     );
 }
+
+#[test]
+fn endpoint_constraint_scales_expand_fanout_by_source_label() {
+    let (catalog, _ctx) = create_test_catalog();
+    let person = catalog.get_or_create_label("Person").unwrap();
+    let company = catalog.get_or_create_label("Company").unwrap();
+    let works_at = catalog.get_or_create_type("WORKS_AT").unwrap();
+    let label_index = LabelIndex::new();
+    for id in 0..4 {
+        label_index.add_node(id, &[person]).unwrap();
+    }
+    for id in 4..100 {
+        label_index.add_node(id, &[company]).unwrap();
+    }
+    for _ in 0..8 {
+        catalog.increment_rel_count(works_at).unwrap();
+    }
+    let knn_index = KnnIndex::new(crate::index::DEFAULT_VECTORIZER_DIMENSION).unwrap();
+    let planner = QueryPlanner::new(&catalog, &label_index, &knn_index);
+
+    assert_eq!(
+        planner.endpoint_constrained_fanout(&[works_at], &Direction::Outgoing),
+        None,
+        "no constraint registered yet"
+    );
+
+    catalog
+        .persist_rel_endpoint_constraint(works_at, Some(person), Some(company))
+        .unwrap();
+    assert_eq!(
+        planner.endpoint_constrained_fanout(&[works_at], &Direction::Outgoing),
+        Some(2.0)
+    );
+    assert_eq!(
+        planner.endpoint_constrained_fanout(&[works_at], &Direction::Incoming),
+        Some(8.0 / 96.0)
+    );
+    assert_eq!(
+        planner.endpoint_constrained_fanout(&[works_at], &Direction::Both),
+        None
+    );
+}
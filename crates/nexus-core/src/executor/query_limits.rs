@@ -0,0 +1,235 @@
+//! Per-query limits from [`super::ExecutorConfig`]: the
+//! `query_timeout_ms` wall-clock budget and the `max_result_rows`
+//! result size.
+//!
+//! The deadline is checked between operators, so a query is stopped
+//! at the first operator boundary past its budget; a single operator
+//! that runs long is not interrupted. Subqueries and views plan and
+//! run through the same thread, so only the outermost
+//! `Executor::execute` call arms the deadline and checks the row
+//! count, the way [`super::plan_output`] keeps the outer recording.
+//!
+//! A caller that returns only part of a result (the server's per-request
+//! row limit) arms [`limit_rows`] around the call instead of cutting the
+//! rows afterwards: the first `Executor::execute` to start takes the
+//! limit and plans it into a read as a `LIMIT`, so the rows past it are
+//! never produced.
+
+use super::parser::{Clause, CypherQuery, Expression, LimitClause, Literal};
+use crate::{Error, Result};
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    /// Set while an `Executor::execute` call is running on this thread.
+    static ACTIVE: Cell<bool> = const { Cell::new(false) };
+    /// Deadline of the running query and the timeout it came from.
+    static DEADLINE: Cell<Option<(Instant, u64)>> = const { Cell::new(None) };
+    /// Rows the next query started on this thread may return.
+    static ROW_LIMIT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Keeps a [`limit_rows`] limit armed; dropping it restores the limit
+/// that was armed before. Bound to the thread that armed it.
+#[must_use = "the row limit is lifted when the guard is dropped"]
+pub struct RowLimitGuard {
+    previous: Option<usize>,
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for RowLimitGuard {
+    fn drop(&mut self) {
+        ROW_LIMIT.set(self.previous);
+    }
+}
+
+/// Return at most `rows` rows from the next query this thread runs
+/// while the guard lives. A read plans the limit as a trailing `LIMIT`;
+/// a write runs in full and only the rows it returns are cut. Ask for
+/// one row more than is kept to tell whether the result was cut.
+pub fn limit_rows(rows: usize) -> RowLimitGuard {
+    RowLimitGuard {
+        previous: ROW_LIMIT.replace(Some(rows)),
+        _thread: PhantomData,
+    }
+}
+
+/// The armed row limit, left in place.
+pub(crate) fn row_limit() -> Option<usize> {
+    ROW_LIMIT.get()
+}
+
+/// The armed row limit, disarming it so no query nested in the caller
+/// applies it again.
+pub(crate) fn take_row_limit() -> Option<usize> {
+    ROW_LIMIT.take()
+}
+
+/// Make `query` return at most `rows` rows by ending it with a literal
+/// `LIMIT`, or lowering the literal `LIMIT` it already ends with. Left
+/// alone when the result is not the final `RETURN` of a single query
+/// (`UNION`, `EXPLAIN`, procedures without `RETURN`, commands) or its
+/// `LIMIT` is a parameter; the caller still cuts the returned rows.
+pub(crate) fn limit_returned_rows(query: &mut CypherQuery, rows: usize) {
+    if query.clauses.iter().any(|c| matches!(c, Clause::Union(_))) {
+        return;
+    }
+    let Some(last_return) = query
+        .clauses
+        .iter()
+        .rposition(|c| matches!(c, Clause::Return(_)))
+    else {
+        return;
+    };
+    let tail = &mut query.clauses[last_return + 1..];
+    if !tail
+        .iter()
+        .all(|c| matches!(c, Clause::OrderBy(_) | Clause::Skip(_) | Clause::Limit(_)))
+    {
+        return;
+    }
+    let count = Expression::Literal(Literal::Integer(i64::try_from(rows).unwrap_or(i64::MAX)));
+    let existing = tail.iter_mut().rev().find_map(|c| match c {
+        Clause::Limit(limit) => Some(limit),
+        _ => None,
+    });
+    match existing {
+        Some(limit) => {
+            if let Expression::Literal(Literal::Integer(n)) = limit.count
+                && usize::try_from(n).is_ok_and(|n| n > rows)
+            {
+                limit.count = count;
+            }
+        }
+        None => query.clauses.push(Clause::Limit(LimitClause { count })),
+    }
+}
+
+/// Marks one `Executor::execute` call; the outermost one owns the
+/// deadline and clears it when dropped.
+pub(crate) struct QueryScope {
+    outermost: bool,
+}
+
+impl QueryScope {
+    /// Enter a query, arming a `timeout_ms` deadline (`0` for none)
+    /// unless an enclosing query already runs on this thread.
+    pub(crate) fn enter(timeout_ms: u64) -> Self {
+        let outermost = !ACTIVE.replace(true);
+        if outermost && timeout_ms > 0 {
+            DEADLINE.set(Some((
+                Instant::now() + Duration::from_millis(timeout_ms),
+                timeout_ms,
+            )));
+        }
+        Self { outermost }
+    }
+
+    /// Whether this is the top-level query rather than a nested one.
+    pub(crate) fn is_outermost(&self) -> bool {
+        self.outermost
+    }
+}
+
+impl Drop for QueryScope {
+    fn drop(&mut self) {
+        if self.outermost {
+            ACTIVE.set(false);
+            DEADLINE.set(None);
+        }
+    }
+}
+
+/// Fail with `ERR_QUERY_TIMEOUT` once the running query is past its
+/// deadline.
+pub(crate) fn check_deadline() -> Result<()> {
+    match DEADLINE.get() {
+        Some((deadline, timeout_ms)) if Instant::now() >= deadline => Err(Error::CypherExecution(
+            format!("ERR_QUERY_TIMEOUT: query exceeded db.query.timeout_ms ({timeout_ms} ms)"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Fail with `ERR_RESULT_TOO_LARGE` when `rows` is over `max_rows`
+/// (`0` for no limit).
+pub(crate) fn check_result_rows(rows: usize, max_rows: usize) -> Result<()> {
+    if max_rows > 0 && rows > max_rows {
+        return Err(Error::CypherExecution(format!(
+            "ERR_RESULT_TOO_LARGE: query returned {rows} rows, more than db.query.max_rows \
+             ({max_rows}); add LIMIT or narrow the query"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_outermost_scope_arms_and_clears_the_deadline() {
+        {
+            let outer = QueryScope::enter(1);
+            assert!(outer.is_outermost());
+            {
+                let inner = QueryScope::enter(0);
+                assert!(!inner.is_outermost());
+            }
+            // The inner scope leaves the outer deadline in place.
+            std::thread::sleep(Duration::from_millis(5));
+            let err = check_deadline().unwrap_err().to_string();
+            assert!(err.contains("ERR_QUERY_TIMEOUT"), "{err}");
+        }
+        assert!(check_deadline().is_ok());
+        assert!(QueryScope::enter(0).is_outermost());
+    }
+
+    #[test]
+    fn a_row_limit_is_taken_once_and_restored_by_its_guard() {
+        {
+            let _outer = limit_rows(10);
+            {
+                let _inner = limit_rows(3);
+                assert_eq!(take_row_limit(), Some(3));
+                assert_eq!(row_limit(), None);
+            }
+            assert_eq!(row_limit(), Some(10));
+        }
+        assert_eq!(row_limit(), None);
+    }
+
+    #[test]
+    fn the_final_return_gets_the_lower_limit() {
+        let limited = |cypher: &str, rows| {
+            let mut query = crate::executor::parser::CypherParser::new(cypher.to_string())
+                .parse()
+                .unwrap();
+            limit_returned_rows(&mut query, rows);
+            match query.clauses.last() {
+                Some(Clause::Limit(LimitClause {
+                    count: Expression::Literal(Literal::Integer(n)),
+                })) => Some(*n),
+                _ => None,
+            }
+        };
+        assert_eq!(limited("MATCH (n) RETURN n", 11), Some(11));
+        assert_eq!(
+            limited("MATCH (n) RETURN n ORDER BY n.x SKIP 2", 11),
+            Some(11)
+        );
+        assert_eq!(limited("MATCH (n) RETURN n LIMIT 500", 11), Some(11));
+        assert_eq!(limited("MATCH (n) RETURN n LIMIT 5", 11), Some(5));
+        assert_eq!(limited("MATCH (n) RETURN n LIMIT $max", 11), None);
+        assert_eq!(limited("RETURN 1 AS x UNION RETURN 2 AS x", 11), None);
+    }
+
+    #[test]
+    fn row_limit_zero_means_unlimited() {
+        assert!(check_result_rows(1_000_000, 0).is_ok());
+        assert!(check_result_rows(10, 10).is_ok());
+        let err = check_result_rows(11, 10).unwrap_err().to_string();
+        assert!(err.contains("ERR_RESULT_TOO_LARGE"), "{err}");
+    }
+}
@@ -4,6 +4,7 @@
 //! `executor` module can read them directly without going through accessor
 //! shims that would bloat the call graph.
 
+use super::context_pool::ContextPool;
 use crate::Result;
 use crate::catalog::Catalog;
 use crate::database::DatabaseManager;
@@ -94,6 +95,31 @@ pub struct ExecutorShared {
     /// Populated via [`ExecutorShared::set_property_index`] in `Engine::refresh_executor`.
     /// `None` for executor instances built outside an engine (e.g. test harness).
     pub(super) property_index: std::sync::OnceLock<crate::index::PropertyIndex>,
+    /// Property bloom filters shared with the engine. Populated via
+    /// [`ExecutorShared::set_property_bloom`] in `Engine::refresh_executor`;
+    /// `NodeBloomProbe` falls back to a plain label scan without it.
+    pub(super) property_bloom: std::sync::OnceLock<crate::index::PropertyBloomRegistry>,
+    /// Named vector indexes shared with the engine, so CREATE rejects
+    /// embeddings of the wrong length. Populated via
+    /// [`ExecutorShared::set_vector_indexes`] in `Engine::refresh_executor`.
+    pub(super) vector_indexes: std::sync::OnceLock<crate::index::VectorIndexRegistry>,
+    /// Count aggregates shared with the engine, read by
+    /// `nexus.aggregate.counts`. Populated via
+    /// [`ExecutorShared::set_count_aggregates`] in `Engine::refresh_executor`.
+    pub(super) count_aggregates: std::sync::OnceLock<crate::index::CountAggregateRegistry>,
+    /// Limits for the `nexus.load.*` remote data functions. Populated via
+    /// [`ExecutorShared::set_external_data`] in `Engine::refresh_executor`;
+    /// without it remote loading is disabled.
+    pub(super) external_data: std::sync::OnceLock<Arc<crate::external_data::ExternalDataConfig>>,
+    /// Per-template plan and latency record. Populated via
+    /// [`ExecutorShared::set_plan_history`] in `Engine::refresh_executor`
+    /// when `EngineConfig::plan_history` is enabled; nothing is recorded
+    /// without it.
+    pub(super) plan_history: std::sync::OnceLock<crate::performance::plan_history::PlanHistory>,
+    /// Idle execution contexts reused by `execute_inner`. Shared by every
+    /// clone, so the per-request executor clones of the read path draw
+    /// from one pool.
+    pub(super) context_pool: Arc<ContextPool>,
 }
 
 impl ExecutorShared {
@@ -135,6 +161,12 @@ impl ExecutorShared {
             composite_btree: std::sync::OnceLock::new(),
             fulltext: std::sync::OnceLock::new(),
             property_index: std::sync::OnceLock::new(),
+            property_bloom: std::sync::OnceLock::new(),
+            vector_indexes: std::sync::OnceLock::new(),
+            count_aggregates: std::sync::OnceLock::new(),
+            external_data: std::sync::OnceLock::new(),
+            plan_history: std::sync::OnceLock::new(),
+            context_pool: Arc::new(ContextPool::default()),
         })
     }
 
@@ -183,6 +215,62 @@ impl ExecutorShared {
         self.property_index.get()
     }
 
+    /// Install the engine's property bloom filters on this shared state.
+    /// OnceLock semantics as for [`Self::set_property_index`]; filters
+    /// created later are visible through the shared registry.
+    pub fn set_property_bloom(&self, registry: crate::index::PropertyBloomRegistry) {
+        let _ = self.property_bloom.set(registry);
+    }
+
+    /// Borrow the property bloom filters if they have been installed.
+    pub fn property_bloom(&self) -> Option<&crate::index::PropertyBloomRegistry> {
+        self.property_bloom.get()
+    }
+
+    /// Install the engine's vector index registry on this shared state.
+    /// OnceLock semantics as for [`Self::set_property_index`].
+    pub fn set_vector_indexes(&self, registry: crate::index::VectorIndexRegistry) {
+        let _ = self.vector_indexes.set(registry);
+    }
+
+    /// Borrow the vector index registry if it has been installed.
+    pub fn vector_indexes(&self) -> Option<&crate::index::VectorIndexRegistry> {
+        self.vector_indexes.get()
+    }
+
+    /// Install the engine's count aggregates on this shared state.
+    /// OnceLock semantics as for [`Self::set_property_index`].
+    pub fn set_count_aggregates(&self, registry: crate::index::CountAggregateRegistry) {
+        let _ = self.count_aggregates.set(registry);
+    }
+
+    /// Borrow the count aggregates if they have been installed.
+    pub fn count_aggregates(&self) -> Option<&crate::index::CountAggregateRegistry> {
+        self.count_aggregates.get()
+    }
+
+    /// Install the engine's remote data limits on this shared state.
+    /// OnceLock semantics as for [`Self::set_property_index`].
+    pub fn set_external_data(&self, config: Arc<crate::external_data::ExternalDataConfig>) {
+        let _ = self.external_data.set(config);
+    }
+
+    /// Borrow the remote data limits if they have been installed.
+    pub fn external_data(&self) -> Option<&crate::external_data::ExternalDataConfig> {
+        self.external_data.get().map(|config| config.as_ref())
+    }
+
+    /// Install the engine's plan history on this shared state.
+    /// OnceLock semantics as for [`Self::set_property_index`].
+    pub fn set_plan_history(&self, history: crate::performance::plan_history::PlanHistory) {
+        let _ = self.plan_history.set(history);
+    }
+
+    /// Borrow the plan history if one has been installed.
+    pub fn plan_history(&self) -> Option<&crate::performance::plan_history::PlanHistory> {
+        self.plan_history.get()
+    }
+
     /// Set the database manager for multi-database support
     pub fn set_database_manager(
         &self,
@@ -259,6 +347,11 @@ impl ExecutorShared {
             composite_btree: std::sync::OnceLock::new(),
             fulltext: std::sync::OnceLock::new(),
             property_index: std::sync::OnceLock::new(),
+            property_bloom: std::sync::OnceLock::new(),
+            vector_indexes: std::sync::OnceLock::new(),
+            count_aggregates: std::sync::OnceLock::new(),
+            external_data: std::sync::OnceLock::new(),
+            plan_history: std::sync::OnceLock::new(),
         })
     }
 }
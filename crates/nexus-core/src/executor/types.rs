@@ -45,6 +45,12 @@ pub struct ExecutorConfig {
     /// `RwLock`. Set to 1 to force serial execution; `0` is rejected.
     /// Default: 4.
     pub cypher_concurrency: usize,
+    /// Wall-clock budget of one query in milliseconds, checked between
+    /// operators (see [`super::query_limits`]). `0` means no limit.
+    pub query_timeout_ms: u64,
+    /// Rows a query may return before it fails with
+    /// `ERR_RESULT_TOO_LARGE`. `0` means no limit.
+    pub max_result_rows: usize,
 }
 
 impl Default for ExecutorConfig {
@@ -67,6 +73,8 @@ impl Default for ExecutorConfig {
             enable_numa_caching: false,       // Disabled by default (requires NUMA hardware)
             enable_lock_free_structures: true, // Enabled by default (always beneficial)
             cypher_concurrency: 4,
+            query_timeout_ms: 0,
+            max_result_rows: 0,
         }
     }
 }
@@ -212,6 +220,21 @@ pub enum Operator {
         /// Pattern variable to bind the returned nodes to.
         variable: String,
     },
+    /// Label scan guarded by a property bloom filter:
+    /// `MATCH (n:Label {key: value})` where (label, key) has no property
+    /// index but a registered bloom filter. Yields nothing when the filter
+    /// proves `value` was never stored, else every node of the label;
+    /// residual `Filter` operators do the matching.
+    NodeBloomProbe {
+        /// Label ID the filter was created on.
+        label_id: u32,
+        /// Property key ID.
+        key_id: u32,
+        /// Value to probe for.
+        value: serde_json::Value,
+        /// Pattern variable to bind the returned nodes to.
+        variable: String,
+    },
     /// Scan all nodes (no label filter)
     AllNodesScan {
         /// Variable name
@@ -246,6 +269,21 @@ pub enum Operator {
         /// Optional (LEFT OUTER JOIN semantics - preserve rows with NULL if no match)
         optional: bool,
     },
+    /// Count-only expansion: `MATCH (n)-[:T]->(m) RETURN n, count(m)`
+    /// where `m` and the relationship are never read. Instead of one row
+    /// per `(n, m)` pair, each input row gains `count_column` holding the
+    /// number of matching relationships of `source_var`; rows with none
+    /// are dropped, as `Expand` would. The aggregate then sums the column.
+    ExpandCount {
+        /// Type IDs (empty = all types)
+        type_ids: Vec<u32>,
+        /// Direction (Outgoing, Incoming, Both)
+        direction: Direction,
+        /// Source variable
+        source_var: String,
+        /// Column receiving the per-row relationship count
+        count_column: String,
+    },
     /// Project columns
     Project {
         /// Projection expressions with aliases
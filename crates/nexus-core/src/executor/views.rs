@@ -0,0 +1,255 @@
+//! Saved-view expansion.
+//!
+//! A view (`CREATE VIEW active_users AS MATCH (u:User) WHERE u.active
+//! RETURN u`) is stored in the catalog as query text. Before planning,
+//! every `MATCH` node pattern that uses a view name as a label —
+//! `MATCH (u:active_users)` — is rewritten: the view query runs, the
+//! view label is swapped for the labels the view's returned variable is
+//! matched with (so the label scan still narrows the candidates), and a
+//! `WHERE id(u) IN [...]` over the node ids in the view's first column
+//! is added right after the `MATCH`.
+//!
+//! A materialized view does not run its query here: its rows come from
+//! the result the engine stored at the last refresh (see
+//! `engine/views.rs`), so the node set it stands for is fixed until the
+//! next refresh while properties are still read live.
+//!
+//! Views may use other views. Nesting is capped at [`MAX_VIEW_DEPTH`],
+//! which is also what stops a view that (indirectly) uses itself.
+
+use super::parser::{
+    BinaryOperator, Clause, CypherParser, CypherQuery, Expression, Literal, PatternElement,
+    ReturnItem, WhereClause,
+};
+use super::{Executor, Query, ResultSet, Row};
+use crate::catalog::{MaterializedViewResult, ViewDefinition};
+use crate::{Error, Result};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+/// How deeply views may use other views.
+pub(in crate::executor) const MAX_VIEW_DEPTH: usize = 8;
+
+/// Prefix of the variable given to anonymous node patterns that use a
+/// view, so the id predicate has something to refer to.
+const VIEW_VARIABLE_PREFIX: &str = "__view_";
+
+thread_local! {
+    /// Views currently being evaluated on this thread.
+    static VIEW_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+impl Executor {
+    /// Rewrite the view labels in `ast` as described in the module docs.
+    /// Queries without view labels are returned as they are.
+    pub(crate) fn expand_views<'a>(&self, ast: &'a CypherQuery) -> Result<Cow<'a, CypherQuery>> {
+        if !self.catalog().has_views() || !self.uses_views(ast) {
+            return Ok(Cow::Borrowed(ast));
+        }
+
+        let mut generated = 0usize;
+        let mut clauses = Vec::with_capacity(ast.clauses.len() + 1);
+        for clause in &ast.clauses {
+            let Clause::Match(match_clause) = clause else {
+                clauses.push(clause.clone());
+                continue;
+            };
+            let mut match_clause = match_clause.clone();
+            let mut predicates = Vec::new();
+            for element in &mut match_clause.pattern.elements {
+                let PatternElement::Node(node) = element else {
+                    continue;
+                };
+                let mut labels = Vec::with_capacity(node.labels.len());
+                for label in std::mem::take(&mut node.labels) {
+                    let Some(view) = self.catalog().get_view(&label) else {
+                        if !labels.contains(&label) {
+                            labels.push(label);
+                        }
+                        continue;
+                    };
+                    let variable = node
+                        .variable
+                        .get_or_insert_with(|| {
+                            generated += 1;
+                            format!("{VIEW_VARIABLE_PREFIX}{generated}")
+                        })
+                        .clone();
+                    let (ids, view_labels) = self.evaluate_view_nodes(&view)?;
+                    for view_label in view_labels {
+                        if !labels.contains(&view_label) {
+                            labels.push(view_label);
+                        }
+                    }
+                    predicates.push(Expression::BinaryOp {
+                        left: Box::new(Expression::FunctionCall {
+                            name: "id".to_string(),
+                            args: vec![Expression::Variable(variable)],
+                        }),
+                        op: BinaryOperator::In,
+                        right: Box::new(Expression::List(
+                            ids.into_iter()
+                                .map(|id| Expression::Literal(Literal::Integer(id as i64)))
+                                .collect(),
+                        )),
+                    });
+                }
+                node.labels = labels;
+            }
+            clauses.push(Clause::Match(match_clause));
+            // Separate WHERE clauses rather than one AND-ed predicate, so
+            // the MATCH's own WHERE keeps its precedence.
+            clauses.extend(
+                predicates
+                    .into_iter()
+                    .map(|expression| Clause::Where(WhereClause { expression })),
+            );
+        }
+
+        Ok(Cow::Owned(CypherQuery {
+            clauses,
+            params: ast.params.clone(),
+            graph_scope: ast.graph_scope.clone(),
+        }))
+    }
+
+    /// Whether any `MATCH` node pattern in `ast` uses a view label.
+    fn uses_views(&self, ast: &CypherQuery) -> bool {
+        ast.clauses.iter().any(|clause| {
+            let Clause::Match(match_clause) = clause else {
+                return false;
+            };
+            match_clause.pattern.elements.iter().any(|element| {
+                matches!(element, PatternElement::Node(node)
+                    if node.labels.iter().any(|label| self.catalog().get_view(label).is_some()))
+            })
+        })
+    }
+
+    /// Whether `cypher` may use a view as a label. A cheap textual check
+    /// that lets the direct-execution fast path stay away from queries
+    /// the planner has to rewrite.
+    pub(in crate::executor) fn may_use_views(&self, cypher: &str) -> bool {
+        self.catalog().has_views()
+            && self
+                .catalog()
+                .list_views()
+                .iter()
+                .any(|view| cypher.contains(&format!(":{}", view.name)))
+    }
+
+    /// The full result of view `name`.
+    pub(in crate::executor) fn run_view(&self, name: &str) -> Result<ResultSet> {
+        let view = self
+            .catalog()
+            .get_view(name)
+            .ok_or_else(|| Error::NotFound(format!("view {name:?} does not exist")))?;
+        self.view_rows(&view)
+    }
+
+    /// The stored result of a materialized view that has one, otherwise
+    /// the result of running the view's query.
+    fn view_rows(&self, view: &ViewDefinition) -> Result<ResultSet> {
+        if view.materialized.is_some()
+            && let Some(stored) = self.catalog().get_view_result(&view.name)
+        {
+            return stored_result_set(stored);
+        }
+        self.run_view_query(view)
+    }
+
+    fn run_view_query(&self, view: &ViewDefinition) -> Result<ResultSet> {
+        let depth = VIEW_DEPTH.with(Cell::get);
+        if depth >= MAX_VIEW_DEPTH {
+            return Err(Error::CypherExecution(format!(
+                "view {:?} nests views more than {MAX_VIEW_DEPTH} levels deep \
+                 (does it use itself?)",
+                view.name
+            )));
+        }
+        VIEW_DEPTH.with(|d| d.set(depth + 1));
+        let result = self.execute_inner(
+            &Query {
+                cypher: view.query.clone(),
+                params: HashMap::new(),
+            },
+            None,
+        );
+        VIEW_DEPTH.with(|d| d.set(depth));
+        result
+    }
+
+    /// Node ids in the first column of the view's result, sorted and
+    /// deduplicated, plus the labels its returned variable is matched
+    /// with.
+    fn evaluate_view_nodes(&self, view: &ViewDefinition) -> Result<(Vec<u64>, Vec<String>)> {
+        let result = self.view_rows(view)?;
+        let mut ids = Vec::with_capacity(result.rows.len());
+        for row in &result.rows {
+            match row.values.first() {
+                Some(Value::Object(node)) if node.contains_key("_nexus_id") => {
+                    if let Some(id) = node.get("_nexus_id").and_then(Value::as_u64) {
+                        ids.push(id);
+                    }
+                }
+                Some(Value::Null) => {}
+                _ => {
+                    return Err(Error::CypherExecution(format!(
+                        "view {:?} must return nodes in its first column to be used as a label",
+                        view.name
+                    )));
+                }
+            }
+        }
+        ids.sort_unstable();
+        ids.dedup();
+        Ok((ids, self.returned_node_labels(&view.query)))
+    }
+
+    /// Labels of the node pattern bound to the variable in the first
+    /// `RETURN` item of `query`, without view labels. Empty when the query
+    /// does not return a plain variable from a `MATCH`.
+    fn returned_node_labels(&self, query: &str) -> Vec<String> {
+        let Ok(ast) = CypherParser::new(query.to_string()).parse() else {
+            return Vec::new();
+        };
+        let returned = ast.clauses.iter().rev().find_map(|clause| match clause {
+            Clause::Return(ret) => ret.items.first(),
+            _ => None,
+        });
+        let Some(ReturnItem {
+            expression: Expression::Variable(variable),
+            ..
+        }) = returned
+        else {
+            return Vec::new();
+        };
+        ast.clauses
+            .iter()
+            .filter_map(|clause| match clause {
+                Clause::Match(m) => Some(&m.pattern.elements),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|element| match element {
+                PatternElement::Node(node) if node.variable.as_deref() == Some(variable) => {
+                    Some(node.labels.clone())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|label| self.catalog().get_view(label).is_none())
+            .collect()
+    }
+}
+
+fn stored_result_set(stored: MaterializedViewResult) -> Result<ResultSet> {
+    let rows: Vec<Vec<Value>> = serde_json::from_str(&stored.rows_json)?;
+    Ok(ResultSet::new(
+        stored.columns,
+        rows.into_iter().map(|values| Row { values }).collect(),
+    ))
+}
@@ -0,0 +1,174 @@
+//! Fault injection for the record stores and the WAL.
+//!
+//! Compiled only with the `fault-injection` feature; without it none of
+//! the hooks exist and the I/O paths are unchanged. Integration tests use
+//! it to drive the durability and error-handling paths that a healthy
+//! disk never exercises:
+//!
+//! * [`Fault::FailFsync`] — `Wal::flush` and `RecordStore::flush` return
+//!   an I/O error instead of syncing.
+//! * [`Fault::TornWrite`] — the next WAL frame append writes only its
+//!   first `keep_bytes` bytes and then fails, leaving the file as a crash
+//!   mid-`write` would.
+//! * [`Fault::DelayReads`] — every node / relationship record read
+//!   sleeps first, simulating a slow or contended device.
+//!
+//! Faults are scoped to a directory (or single file): only I/O on paths
+//! under `scope` is affected, so tests running in parallel against their
+//! own temporary directories do not interfere. A fault stays armed until
+//! its [`FaultGuard`] is dropped.
+//!
+//! ```ignore
+//! let guard = fault_injection::inject(dir.path(), Fault::FailFsync);
+//! assert!(wal.flush().is_err());
+//! assert_eq!(guard.hits(), 1);
+//! drop(guard);
+//! wal.flush()?;
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A failure mode to inject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Every fsync of a matching WAL or record store fails.
+    FailFsync,
+    /// The next WAL append under the scope writes only the first
+    /// `keep_bytes` bytes of its frame and fails. Fires once.
+    TornWrite {
+        /// Bytes of the frame that reach the file.
+        keep_bytes: usize,
+    },
+    /// Every node / relationship record read sleeps for this long.
+    DelayReads(Duration),
+}
+
+struct Rule {
+    id: u64,
+    scope: PathBuf,
+    fault: Fault,
+    hits: Arc<AtomicU64>,
+}
+
+static RULES: Mutex<Vec<Rule>> = Mutex::new(Vec::new());
+/// Fast path: hooks return immediately while no fault is armed.
+static ARMED: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Keeps a fault armed; dropping it disarms the fault.
+#[must_use = "the fault is disarmed as soon as the guard is dropped"]
+pub struct FaultGuard {
+    id: u64,
+    hits: Arc<AtomicU64>,
+}
+
+impl FaultGuard {
+    /// How many times the fault has fired.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for FaultGuard {
+    fn drop(&mut self) {
+        let mut rules = RULES.lock().unwrap_or_else(|e| e.into_inner());
+        rules.retain(|r| r.id != self.id);
+        ARMED.store(!rules.is_empty(), Ordering::SeqCst);
+    }
+}
+
+/// Arm `fault` for I/O on paths under `scope`.
+pub fn inject(scope: impl AsRef<Path>, fault: Fault) -> FaultGuard {
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    let hits = Arc::new(AtomicU64::new(0));
+    let mut rules = RULES.lock().unwrap_or_else(|e| e.into_inner());
+    rules.push(Rule {
+        id,
+        scope: scope.as_ref().to_path_buf(),
+        fault,
+        hits: hits.clone(),
+    });
+    ARMED.store(true, Ordering::SeqCst);
+    FaultGuard { id, hits }
+}
+
+/// Run `f` on the first armed rule under `path` it accepts, recording a
+/// hit when it returns `Some`.
+fn fire<T>(path: &Path, mut f: impl FnMut(&Rule) -> Option<T>) -> Option<T> {
+    if !ARMED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let rules = RULES.lock().unwrap_or_else(|e| e.into_inner());
+    rules
+        .iter()
+        .filter(|r| path.starts_with(&r.scope))
+        .find_map(|r| {
+            let out = f(r)?;
+            r.hits.fetch_add(1, Ordering::SeqCst);
+            Some(out)
+        })
+}
+
+/// Hook before an fsync of `path`.
+pub(crate) fn fsync(path: &Path) -> std::io::Result<()> {
+    match fire(path, |r| (r.fault == Fault::FailFsync).then_some(())) {
+        Some(()) => Err(std::io::Error::other(format!(
+            "injected fsync failure on {}",
+            path.display()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Hook before a WAL frame of `frame_len` bytes is written to `path`.
+/// Returns how many bytes to write before failing.
+pub(crate) fn torn_write(path: &Path, frame_len: usize) -> Option<usize> {
+    fire(path, |r| match r.fault {
+        Fault::TornWrite { keep_bytes } if r.hits.load(Ordering::SeqCst) == 0 => {
+            Some(keep_bytes.min(frame_len))
+        }
+        _ => None,
+    })
+}
+
+/// Hook before a record read from the store at `path`.
+pub(crate) fn read_delay(path: &Path) {
+    if let Some(delay) = fire(path, |r| match r.fault {
+        Fault::DelayReads(d) => Some(d),
+        _ => None,
+    }) {
+        std::thread::sleep(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faults_are_scoped_and_disarmed_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let wal = dir.path().join("wal.log");
+
+        let guard = inject(dir.path(), Fault::FailFsync);
+        assert!(fsync(&wal).is_err());
+        assert!(fsync(&other.path().join("wal.log")).is_ok());
+        assert_eq!(guard.hits(), 1);
+        drop(guard);
+        assert!(fsync(&wal).is_ok());
+    }
+
+    #[test]
+    fn torn_write_fires_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = dir.path().join("wal.log");
+        let guard = inject(&wal, Fault::TornWrite { keep_bytes: 64 });
+        assert_eq!(torn_write(&wal, 20), Some(20));
+        assert_eq!(torn_write(&wal, 20), None);
+        assert_eq!(guard.hits(), 1);
+    }
+}
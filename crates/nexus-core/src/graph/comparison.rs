@@ -642,6 +642,21 @@ impl GraphComparator {
             }
             (PropertyValue::String(a_val), PropertyValue::String(b_val)) => a_val == b_val,
             (PropertyValue::Bytes(a_val), PropertyValue::Bytes(b_val)) => a_val == b_val,
+            (PropertyValue::List(a_val), PropertyValue::List(b_val)) => {
+                a_val.len() == b_val.len()
+                    && a_val
+                        .iter()
+                        .zip(b_val)
+                        .all(|(a, b)| Self::values_equal(a, b, _options))
+            }
+            (PropertyValue::Map(a_val), PropertyValue::Map(b_val)) => {
+                a_val.len() == b_val.len()
+                    && a_val.iter().all(|(key, a)| {
+                        b_val
+                            .get(key)
+                            .is_some_and(|b| Self::values_equal(a, b, _options))
+                    })
+            }
             _ => false,
         }
     }
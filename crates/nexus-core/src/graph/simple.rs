@@ -75,6 +75,10 @@ pub enum PropertyValue {
     String(String),
     /// Bytes value
     Bytes(Vec<u8>),
+    /// List value
+    List(Vec<PropertyValue>),
+    /// Map value
+    Map(HashMap<String, PropertyValue>),
 }
 
 /// A node in the graph with labels and properties
@@ -282,10 +282,15 @@ impl BTreeIndex {
 
         // Check for unique constraint violation
         if self.is_unique && index.contains_key(&key) {
-            return Err(Error::ConstraintViolation(format!(
-                "Unique constraint violation for key: {:?}",
-                key.values
-            )));
+            return Err(
+                crate::constraints::ConstraintViolation::new("UNIQUENESS", "NODE")
+                    .offending(Some(node_id))
+                    .with_message(format!(
+                        "Unique constraint violation for key: {:?}",
+                        key.values
+                    ))
+                    .into_error(),
+            );
         }
 
         // Check if this exact key already exists
@@ -0,0 +1,224 @@
+//! Incrementally maintained node counts per label and group.
+//!
+//! A count aggregate is a registered `MATCH (n:Label) RETURN n.group,
+//! count(*)` whose result is kept up to date as writes land instead of
+//! being recomputed by a label scan on every read. Each aggregate
+//! remembers which group every counted node sits in, so a write is
+//! applied as a delta: the node leaves its old group (if any) and joins
+//! its new one. Re-applying the same write is a no-op, which lets the
+//! commit paths that re-visit nodes feed the registry freely.
+//!
+//! Nodes without the group property are counted under a NULL group, as
+//! the equivalent Cypher aggregation would.
+
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// One registered count aggregate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountAggregate {
+    /// Name the aggregate is read back by.
+    pub name: String,
+    /// Label ID whose nodes are counted.
+    pub label_id: u32,
+    /// Property the counts are grouped by; `None` for one total.
+    pub group_by: Option<String>,
+}
+
+impl CountAggregate {
+    /// The group a node with these labels and properties counts
+    /// towards, or `None` when it is not counted at all.
+    fn group_of(&self, label_ids: &[u32], properties: &Value) -> Option<Value> {
+        if !label_ids.contains(&self.label_id) {
+            return None;
+        }
+        Some(match &self.group_by {
+            Some(property) => properties.get(property).cloned().unwrap_or(Value::Null),
+            None => Value::Null,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct AggregateState {
+    definition: CountAggregate,
+    /// Group key (the group value's JSON text) of every counted node.
+    members: HashMap<u64, String>,
+    /// Group key -> (group value, count). Groups at zero are removed.
+    groups: BTreeMap<String, (Value, u64)>,
+}
+
+impl AggregateState {
+    fn new(definition: CountAggregate) -> Self {
+        Self {
+            definition,
+            members: HashMap::new(),
+            groups: BTreeMap::new(),
+        }
+    }
+
+    fn leave(&mut self, node_id: u64) {
+        let Some(key) = self.members.remove(&node_id) else {
+            return;
+        };
+        if let Some((_, count)) = self.groups.get_mut(&key) {
+            *count -= 1;
+            if *count == 0 {
+                self.groups.remove(&key);
+            }
+        }
+    }
+
+    fn sync(&mut self, node_id: u64, label_ids: &[u32], properties: &Value) {
+        let group = self.definition.group_of(label_ids, properties);
+        let key = group.as_ref().map(Value::to_string);
+        if self.members.get(&node_id) == key.as_ref() {
+            return;
+        }
+        self.leave(node_id);
+        if let (Some(group), Some(key)) = (group, key) {
+            self.groups.entry(key.clone()).or_insert((group, 0)).1 += 1;
+            self.members.insert(node_id, key);
+        }
+    }
+}
+
+/// Registry of count aggregates and their current results.
+///
+/// Cheap to clone; clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CountAggregateRegistry {
+    inner: Arc<RwLock<BTreeMap<String, AggregateState>>>,
+}
+
+impl CountAggregateRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an empty aggregate. Returns `false` when the name is
+    /// already taken.
+    pub fn register(&self, definition: CountAggregate) -> bool {
+        let mut inner = self.inner.write();
+        if inner.contains_key(&definition.name) {
+            return false;
+        }
+        inner.insert(definition.name.clone(), AggregateState::new(definition));
+        true
+    }
+
+    /// Remove the aggregate `name`. Returns whether it existed.
+    pub fn drop_aggregate(&self, name: &str) -> bool {
+        self.inner.write().remove(name).is_some()
+    }
+
+    /// Registered aggregates, by name.
+    pub fn definitions(&self) -> Vec<CountAggregate> {
+        self.inner
+            .read()
+            .values()
+            .map(|state| state.definition.clone())
+            .collect()
+    }
+
+    /// Whether any aggregate is registered.
+    pub fn has_any(&self) -> bool {
+        !self.inner.read().is_empty()
+    }
+
+    /// Apply a write to one node to the aggregate `name` only, for the
+    /// backfill of a newly registered aggregate.
+    pub fn sync_node_into(&self, name: &str, node_id: u64, label_ids: &[u32], properties: &Value) {
+        if let Some(state) = self.inner.write().get_mut(name) {
+            state.sync(node_id, label_ids, properties);
+        }
+    }
+
+    /// Move a node to the groups matching its labels and properties
+    /// after a write.
+    pub fn sync_node(&self, node_id: u64, label_ids: &[u32], properties: &Value) {
+        if !self.has_any() {
+            return;
+        }
+        for state in self.inner.write().values_mut() {
+            state.sync(node_id, label_ids, properties);
+        }
+    }
+
+    /// Stop counting a deleted node.
+    pub fn remove_node(&self, node_id: u64) {
+        if !self.has_any() {
+            return;
+        }
+        for state in self.inner.write().values_mut() {
+            state.leave(node_id);
+        }
+    }
+
+    /// Current `(group, count)` pairs of the aggregate `name`, ordered
+    /// by the group's JSON text. `None` for an unknown name.
+    pub fn counts(&self, name: &str) -> Option<Vec<(Value, u64)>> {
+        self.inner
+            .read()
+            .get(name)
+            .map(|state| state.groups.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn by_status() -> CountAggregateRegistry {
+        let registry = CountAggregateRegistry::new();
+        assert!(registry.register(CountAggregate {
+            name: "orders_by_status".to_string(),
+            label_id: 1,
+            group_by: Some("status".to_string()),
+        }));
+        registry
+    }
+
+    #[test]
+    fn writes_move_nodes_between_groups() {
+        let registry = by_status();
+        registry.sync_node(10, &[1], &json!({"status": "open"}));
+        registry.sync_node(11, &[1], &json!({"status": "open"}));
+        registry.sync_node(12, &[1], &json!({}));
+        // Not labelled: not counted.
+        registry.sync_node(13, &[2], &json!({"status": "open"}));
+        assert_eq!(
+            registry.counts("orders_by_status").unwrap(),
+            vec![(json!("open"), 2), (Value::Null, 1)]
+        );
+
+        registry.sync_node(10, &[1], &json!({"status": "closed"}));
+        registry.sync_node(11, &[2], &json!({"status": "open"}));
+        registry.remove_node(12);
+        assert_eq!(
+            registry.counts("orders_by_status").unwrap(),
+            vec![(json!("closed"), 1)]
+        );
+    }
+
+    #[test]
+    fn reapplying_a_write_does_not_double_count() {
+        let registry = by_status();
+        for _ in 0..3 {
+            registry.sync_node(10, &[1], &json!({"status": "open"}));
+        }
+        registry.remove_node(10);
+        registry.remove_node(10);
+        assert!(registry.counts("orders_by_status").unwrap().is_empty());
+        assert!(registry.counts("missing").is_none());
+        assert!(!registry.register(CountAggregate {
+            name: "orders_by_status".to_string(),
+            label_id: 2,
+            group_by: None,
+        }));
+    }
+}
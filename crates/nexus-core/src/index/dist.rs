@@ -44,3 +44,60 @@ impl Distance<f32> for DistSimdL2 {
         simd::distance::l2_sq_f32(va, vb)
     }
 }
+
+/// Distance function of a vector index, chosen when the index is
+/// created.
+///
+/// Search results report a similarity score where higher is closer:
+/// `1 - cosine distance` for [`VectorMetric::Cosine`] and
+/// `1 / (1 + euclidean distance)` for [`VectorMetric::Euclidean`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VectorMetric {
+    /// Cosine distance ([`DistSimdCosine`]).
+    #[default]
+    Cosine,
+    /// Euclidean distance ([`DistSimdL2`]).
+    Euclidean,
+}
+
+impl VectorMetric {
+    /// Similarity score for a raw HNSW distance under this metric.
+    pub fn score(self, distance: f32) -> f32 {
+        match self {
+            Self::Cosine => 1.0 - distance,
+            Self::Euclidean => 1.0 / (1.0 + distance.max(0.0).sqrt()),
+        }
+    }
+
+    /// Lower-case name, as accepted by [`std::str::FromStr`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cosine => "cosine",
+            Self::Euclidean => "euclidean",
+        }
+    }
+}
+
+impl std::str::FromStr for VectorMetric {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "cosine" => Ok(Self::Cosine),
+            "euclidean" | "l2" => Ok(Self::Euclidean),
+            other => Err(crate::Error::invalid_input(format!(
+                "unknown vector metric {other:?} (expected cosine or euclidean)"
+            ))),
+        }
+    }
+}
+
+impl Distance<f32> for VectorMetric {
+    fn eval(&self, va: &[f32], vb: &[f32]) -> f32 {
+        match self {
+            Self::Cosine => DistSimdCosine.eval(va, vb),
+            Self::Euclidean => DistSimdL2.eval(va, vb),
+        }
+    }
+}
@@ -1,16 +1,82 @@
 //! HNSW-backed KNN vector index.
 //!
 //! Provides [`KnnIndex`], [`KnnConfig`], and [`KnnIndexStats`] for
-//! approximate nearest-neighbour search over `f32` embeddings.
+//! approximate nearest-neighbour search over `f32` embeddings, with an
+//! exact brute-force fallback selected per query via [`KnnSearchMode`].
 
 use crate::simd;
 use crate::{Error, Result};
 use hnsw_rs::prelude::*;
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use super::dist::DistSimdCosine;
+use super::dist::VectorMetric;
+
+/// How a single KNN query is served.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KnnSearchMode {
+    /// Exact when the index holds at most
+    /// [`KnnIndex::EXACT_SEARCH_THRESHOLD`] vectors (or `k` covers all of
+    /// them), approximate otherwise.
+    #[default]
+    Auto,
+    /// HNSW graph search.
+    Approximate,
+    /// Brute-force scan over every stored vector.
+    Exact,
+}
+
+impl KnnSearchMode {
+    /// Lower-case name, as accepted by [`std::str::FromStr`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Approximate => "approximate",
+            Self::Exact => "exact",
+        }
+    }
+}
+
+impl std::str::FromStr for KnnSearchMode {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "approximate" | "approx" | "hnsw" => Ok(Self::Approximate),
+            "exact" | "brute_force" => Ok(Self::Exact),
+            other => Err(crate::Error::invalid_input(format!(
+                "unknown knn search mode {other:?} (expected auto, approximate or exact)"
+            ))),
+        }
+    }
+}
+
+/// Per-query options for [`KnnIndex::search_knn_with_options`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct KnnSearchOptions {
+    /// Which search serves the query.
+    pub mode: KnnSearchMode,
+    /// HNSW `ef`; defaults to [`KnnIndex::DEFAULT_EF_SEARCH`].
+    pub ef_search: Option<usize>,
+    /// Also run the other search and report the recall of the
+    /// approximate results against the exact ones.
+    pub measure_recall: bool,
+}
+
+/// Results of [`KnnIndex::search_knn_with_options`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KnnSearchOutcome {
+    /// `(node_id, score)` pairs, best first.
+    pub results: Vec<(u64, f32)>,
+    /// The search that produced `results`: never [`KnnSearchMode::Auto`].
+    pub mode: KnnSearchMode,
+    /// Fraction of the exact top-k the approximate search also returned,
+    /// when [`KnnSearchOptions::measure_recall`] was set.
+    pub recall: Option<f64>,
+}
 
 /// Configuration for an HNSW-backed KNN index.
 ///
@@ -53,13 +119,17 @@ impl Default for KnnConfig {
 #[derive(Clone)]
 pub struct KnnIndex {
     /// HNSW index for fast KNN search
-    hnsw: Arc<RwLock<Hnsw<'static, f32, DistSimdCosine>>>,
+    hnsw: Arc<RwLock<Hnsw<'static, f32, VectorMetric>>>,
     /// Mapping from node_id to vector index in HNSW
     node_to_index: Arc<RwLock<HashMap<u64, usize>>>,
     /// Mapping from vector index to node_id
     index_to_node: Arc<RwLock<HashMap<usize, u64>>>,
+    /// Live vector of every node, scanned by exact searches
+    vectors: Arc<RwLock<HashMap<u64, Vec<f32>>>>,
     /// Vector dimension
     dimension: usize,
+    /// Distance function
+    metric: VectorMetric,
     /// HNSW configuration — retained so `clear()` can recreate the index
     /// with the same parameters the caller chose at construction time.
     config: KnnConfig,
@@ -78,10 +148,19 @@ pub struct KnnIndexStats {
     pub dimension: usize,
     /// Average search time in microseconds
     pub avg_search_time_us: f64,
+    /// Queries served by the HNSW graph
+    pub approximate_searches: u64,
+    /// Queries served by a brute-force scan
+    pub exact_searches: u64,
+    /// Queries that ran both searches to measure recall
+    pub recall_samples: u64,
+    /// Mean recall over those samples
+    pub avg_recall: Option<f64>,
 }
 
 impl KnnIndex {
-    /// Create a new KNN index with a caller-supplied HNSW configuration.
+    /// Create a new cosine KNN index with a caller-supplied HNSW
+    /// configuration.
     ///
     /// # Arguments
     /// * `dimension` - Vector dimension (must be > 0 and <= 4096)
@@ -90,6 +169,14 @@ impl KnnIndex {
     /// # Errors
     /// Returns an error if dimension is invalid
     pub fn with_config(dimension: usize, config: KnnConfig) -> Result<Self> {
+        Self::with_metric(dimension, VectorMetric::Cosine, config)
+    }
+
+    /// Create a new KNN index using `metric` as its distance function.
+    ///
+    /// # Errors
+    /// Returns an error if dimension is invalid
+    pub fn with_metric(dimension: usize, metric: VectorMetric, config: KnnConfig) -> Result<Self> {
         if dimension == 0 || dimension > 4096 {
             return Err(Error::InvalidId(format!(
                 "Invalid vector dimension: {}",
@@ -102,19 +189,20 @@ impl KnnIndex {
             config.max_elements,
             config.max_layer,
             config.ef_construction,
-            DistSimdCosine,
+            metric,
         );
 
         Ok(Self {
             hnsw: Arc::new(RwLock::new(hnsw)),
             node_to_index: Arc::new(RwLock::new(HashMap::new())),
             index_to_node: Arc::new(RwLock::new(HashMap::new())),
+            vectors: Arc::new(RwLock::new(HashMap::new())),
             dimension,
+            metric,
             config,
             stats: Arc::new(RwLock::new(KnnIndexStats {
-                total_vectors: 0,
                 dimension,
-                avg_search_time_us: 0.0,
+                ..KnnIndexStats::default()
             })),
             next_index: Arc::new(RwLock::new(0)),
         })
@@ -148,11 +236,16 @@ impl KnnIndex {
         let mut index_to_node = self.index_to_node.write();
         let mut next_index = self.next_index.write();
 
-        // Check if node already exists
-        if let Some(&_existing_index) = node_to_index.get(&node_id) {
-            // Update existing vector - HNSW doesn't support updates, so we'll just add new
-            // In a production system, you might want to implement a more sophisticated update mechanism
-        }
+        // HNSW cannot update a point in place: the new vector is inserted
+        // as a fresh point and the old one is orphaned (searches skip
+        // points with no node mapping).
+        let replaced = match node_to_index.get(&node_id) {
+            Some(&existing_index) => {
+                index_to_node.remove(&existing_index);
+                true
+            }
+            None => false,
+        };
 
         // Add new vector to HNSW using insert method
         let vector_index = *next_index;
@@ -161,15 +254,65 @@ impl KnnIndex {
         // Update mappings
         node_to_index.insert(node_id, vector_index);
         index_to_node.insert(vector_index, node_id);
+        self.vectors.write().insert(node_id, embedding);
         *next_index += 1;
 
         // Update statistics
-        let mut stats = self.stats.write();
-        stats.total_vectors += 1;
+        if !replaced {
+            let mut stats = self.stats.write();
+            stats.total_vectors += 1;
+        }
 
         Ok(())
     }
 
+    /// Add or replace many vectors at once.
+    ///
+    /// Equivalent to calling [`KnnIndex::add_vector`] for each pair in
+    /// order (a node listed twice keeps its last vector), but takes the
+    /// locks once and inserts into the HNSW graph in parallel. Nothing is
+    /// inserted if any vector has the wrong dimension.
+    pub fn add_vectors(&self, vectors: Vec<(u64, Vec<f32>)>) -> Result<()> {
+        if let Some((node_id, embedding)) = vectors.iter().find(|(_, e)| e.len() != self.dimension)
+        {
+            return Err(Error::InvalidId(format!(
+                "Vector dimension mismatch for node {}: expected {}, got {}",
+                node_id,
+                self.dimension,
+                embedding.len()
+            )));
+        }
+        if vectors.is_empty() {
+            return Ok(());
+        }
+
+        let hnsw = self.hnsw.write();
+        let mut node_to_index = self.node_to_index.write();
+        let mut index_to_node = self.index_to_node.write();
+        let mut next_index = self.next_index.write();
+
+        let mut added = 0u64;
+        let mut points = Vec::with_capacity(vectors.len());
+        for (node_id, embedding) in &vectors {
+            let vector_index = *next_index;
+            *next_index += 1;
+            match node_to_index.insert(*node_id, vector_index) {
+                Some(previous) => {
+                    index_to_node.remove(&previous);
+                }
+                None => added += 1,
+            }
+            index_to_node.insert(vector_index, *node_id);
+            points.push((embedding, vector_index));
+        }
+        hnsw.parallel_insert(&points);
+        drop(points);
+        self.vectors.write().extend(vectors);
+
+        self.stats.write().total_vectors += added;
+        Ok(())
+    }
+
     /// Remove a vector for a node
     pub fn remove_vector(&self, node_id: u64) -> Result<()> {
         let mut node_to_index = self.node_to_index.write();
@@ -179,6 +322,7 @@ impl KnnIndex {
             // Remove from mappings
             node_to_index.remove(&node_id);
             index_to_node.remove(&vector_index);
+            self.vectors.write().remove(&node_id);
 
             // Update statistics
             let mut stats = self.stats.write();
@@ -192,7 +336,7 @@ impl KnnIndex {
     /// Larger values trade latency for recall.
     pub const DEFAULT_EF_SEARCH: usize = 50;
 
-    /// Search for k nearest neighbors using cosine similarity.
+    /// Search for k nearest neighbors, scored per [`VectorMetric::score`].
     ///
     /// Uses [`KnnIndex::DEFAULT_EF_SEARCH`] as the HNSW `ef` parameter.
     /// For tunable recall/latency tradeoffs, use
@@ -217,37 +361,153 @@ impl KnnIndex {
         k: usize,
         ef_search: usize,
     ) -> Result<Vec<(u64, f32)>> {
-        if query.len() != self.dimension {
-            return Err(Error::InvalidId(format!(
-                "Query dimension mismatch: expected {}, got {}",
-                self.dimension,
-                query.len()
-            )));
-        }
+        self.check_query(query)?;
 
-        let ef = ef_search.max(k);
         let start_time = std::time::Instant::now();
 
         let hnsw = self.hnsw.read();
         let index_to_node = self.index_to_node.read();
 
-        let search_results = hnsw.search(query, k, ef);
+        // Replaced and removed vectors stay in the HNSW graph without a
+        // node mapping; over-fetch by their number (bounded) so skipping
+        // them still leaves `k` live results.
+        let orphaned = (*self.next_index.read()).saturating_sub(index_to_node.len());
+        let fetch = k + orphaned.min(k.saturating_mul(4));
+        let ef = ef_search.max(fetch);
+
+        let search_results = hnsw.search(query, fetch, ef);
 
         let mut results = Vec::new();
         for neighbour in search_results {
             if let Some(&node_id) = index_to_node.get(&neighbour.d_id) {
-                let similarity = 1.0 - neighbour.distance;
-                results.push((node_id, similarity));
+                results.push((node_id, self.metric.score(neighbour.distance)));
             }
         }
+        results.truncate(k);
 
         let search_time_us = start_time.elapsed().as_micros() as f64;
         let mut stats = self.stats.write();
         stats.avg_search_time_us = (stats.avg_search_time_us + search_time_us) / 2.0;
+        stats.approximate_searches += 1;
 
         Ok(results)
     }
 
+    /// Indexes holding at most this many vectors are searched exactly
+    /// under [`KnnSearchMode::Auto`]: a scan that small is as fast as the
+    /// graph descent and never misses a neighbour.
+    pub const EXACT_SEARCH_THRESHOLD: usize = 1_000;
+
+    /// Search for k nearest neighbors by scanning every stored vector.
+    ///
+    /// Always returns the true top-k (ties broken by node id), at a cost
+    /// linear in the number of vectors. Use it for small candidate sets or
+    /// to validate what [`KnnIndex::search_knn_with_ef`] returns.
+    pub fn search_knn_exact(&self, query: &[f32], k: usize) -> Result<Vec<(u64, f32)>> {
+        self.check_query(query)?;
+
+        let mut distances: Vec<(u64, f32)> = self
+            .vectors
+            .read()
+            .iter()
+            .map(|(&node_id, vector)| (node_id, self.metric.eval(query, vector)))
+            .collect();
+        distances.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        distances.truncate(k);
+
+        self.stats.write().exact_searches += 1;
+        Ok(distances
+            .into_iter()
+            .map(|(node_id, distance)| (node_id, self.metric.score(distance)))
+            .collect())
+    }
+
+    /// Search for k nearest neighbors in the mode `options` asks for.
+    ///
+    /// With [`KnnSearchOptions::measure_recall`] both searches run and the
+    /// outcome carries the approximate search's recall@k against the exact
+    /// one, whichever of them served the results. The sample also feeds
+    /// [`KnnIndexStats::avg_recall`], so repeated sampling shows whether
+    /// the HNSW parameters are good enough for this index.
+    pub fn search_knn_with_options(
+        &self,
+        query: &[f32],
+        k: usize,
+        options: KnnSearchOptions,
+    ) -> Result<KnnSearchOutcome> {
+        self.check_query(query)?;
+
+        let ef_search = options.ef_search.unwrap_or(Self::DEFAULT_EF_SEARCH);
+        let mode = match options.mode {
+            KnnSearchMode::Auto => {
+                let total = self.vectors.read().len();
+                if total <= Self::EXACT_SEARCH_THRESHOLD || k >= total {
+                    KnnSearchMode::Exact
+                } else {
+                    KnnSearchMode::Approximate
+                }
+            }
+            mode => mode,
+        };
+
+        if !options.measure_recall {
+            let results = match mode {
+                KnnSearchMode::Exact => self.search_knn_exact(query, k)?,
+                _ => self.search_knn_with_ef(query, k, ef_search)?,
+            };
+            return Ok(KnnSearchOutcome {
+                results,
+                mode,
+                recall: None,
+            });
+        }
+
+        let exact = self.search_knn_exact(query, k)?;
+        let approximate = self.search_knn_with_ef(query, k, ef_search)?;
+        let recall = Self::recall(&approximate, &exact);
+        {
+            let mut stats = self.stats.write();
+            let previous = stats.avg_recall.unwrap_or(0.0) * stats.recall_samples as f64;
+            stats.recall_samples += 1;
+            stats.avg_recall = Some((previous + recall) / stats.recall_samples as f64);
+        }
+
+        Ok(KnnSearchOutcome {
+            results: if mode == KnnSearchMode::Exact {
+                exact
+            } else {
+                approximate
+            },
+            mode,
+            recall: Some(recall),
+        })
+    }
+
+    /// Share of the `exact` neighbours that `approximate` also found;
+    /// `1.0` when there are none to find.
+    fn recall(approximate: &[(u64, f32)], exact: &[(u64, f32)]) -> f64 {
+        if exact.is_empty() {
+            return 1.0;
+        }
+        let found: HashSet<u64> = approximate.iter().map(|&(node_id, _)| node_id).collect();
+        let hits = exact
+            .iter()
+            .filter(|(node_id, _)| found.contains(node_id))
+            .count();
+        hits as f64 / exact.len() as f64
+    }
+
+    fn check_query(&self, query: &[f32]) -> Result<()> {
+        if query.len() != self.dimension {
+            return Err(Error::InvalidId(format!(
+                "Query dimension mismatch: expected {}, got {}",
+                self.dimension,
+                query.len()
+            )));
+        }
+        Ok(())
+    }
+
     /// Search for k nearest neighbors with default k=10
     pub fn search_knn_default(&self, query: &[f32]) -> Result<Vec<(u64, f32)>> {
         self.search_knn(query, 10)
@@ -263,6 +523,11 @@ impl KnnIndex {
         self.dimension
     }
 
+    /// Get the distance function
+    pub fn metric(&self) -> VectorMetric {
+        self.metric
+    }
+
     /// Check if a node has a vector
     pub fn has_vector(&self, node_id: u64) -> bool {
         let node_to_index = self.node_to_index.read();
@@ -288,12 +553,13 @@ impl KnnIndex {
             self.config.max_elements,
             self.config.max_layer,
             self.config.ef_construction,
-            DistSimdCosine,
+            self.metric,
         );
 
         // Clear mappings
         node_to_index.clear();
         index_to_node.clear();
+        self.vectors.write().clear();
         *next_index = 0;
 
         // Reset statistics
@@ -415,6 +681,58 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_knn_index_replace_vector() {
+        let index = KnnIndex::new(3).unwrap();
+        index.add_vector(1, vec![1.0, 0.0, 0.0]).unwrap();
+        index.add_vector(2, vec![0.0, 1.0, 0.0]).unwrap();
+        index.add_vector(1, vec![0.0, 0.0, 1.0]).unwrap();
+
+        assert_eq!(index.get_stats().total_vectors, 2);
+        let results = index.search_knn(&[0.0, 0.0, 1.0], 2).unwrap();
+        assert_eq!(results[0].0, 1);
+        assert!(results.iter().filter(|(id, _)| *id == 1).count() == 1);
+    }
+
+    #[test]
+    fn test_knn_index_add_vectors_batch() {
+        let index = KnnIndex::new(2).unwrap();
+        index.add_vector(1, vec![1.0, 0.0]).unwrap();
+        index
+            .add_vectors(vec![
+                (1, vec![0.0, 1.0]),
+                (2, vec![1.0, 0.0]),
+                (2, vec![1.0, 1.0]),
+            ])
+            .unwrap();
+
+        assert_eq!(index.get_stats().total_vectors, 2);
+        let results = index.search_knn(&[0.0, 1.0], 2).unwrap();
+        assert_eq!(results[0].0, 1);
+        assert_eq!(results[1].0, 2);
+        assert!(
+            index
+                .add_vectors(vec![(3, vec![1.0, 0.0]), (4, vec![1.0])])
+                .is_err()
+        );
+        assert!(!index.has_vector(3));
+    }
+
+    #[test]
+    fn test_knn_index_euclidean_metric() {
+        let index =
+            KnnIndex::with_metric(2, VectorMetric::Euclidean, KnnConfig::default()).unwrap();
+        index.add_vector(1, vec![0.0, 0.0]).unwrap();
+        index.add_vector(2, vec![3.0, 4.0]).unwrap();
+
+        let results = index.search_knn(&[3.0, 4.0], 2).unwrap();
+        assert_eq!(results[0].0, 2);
+        assert!((results[0].1 - 1.0).abs() < 1e-5);
+        assert_eq!(results[1].0, 1);
+        // distance 5 → 1 / (1 + 5)
+        assert!((results[1].1 - 1.0 / 6.0).abs() < 1e-4);
+    }
+
     #[test]
     fn test_knn_index_remove_vector() {
         let index = KnnIndex::new(3).unwrap();
@@ -566,4 +884,88 @@ mod tests {
         let stats = index.get_stats();
         assert_eq!(stats.total_vectors, 0);
     }
+
+    #[test]
+    fn test_knn_index_exact_search() {
+        let index = KnnIndex::new(3).unwrap();
+        index.add_vector(1, vec![1.0, 0.0, 0.0]).unwrap();
+        index.add_vector(2, vec![0.9, 0.1, 0.0]).unwrap();
+        index.add_vector(3, vec![0.0, 0.0, 1.0]).unwrap();
+        // Replaced and removed vectors are not scanned.
+        index.add_vector(3, vec![0.0, 1.0, 0.0]).unwrap();
+        index.add_vector(4, vec![1.0, 0.0, 0.0]).unwrap();
+        index.remove_vector(4).unwrap();
+
+        let results = index.search_knn_exact(&[1.0, 0.0, 0.0], 2).unwrap();
+        let ids: Vec<u64> = results.iter().map(|&(id, _)| id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert!((results[0].1 - 1.0).abs() < 1e-5);
+
+        let all = index.search_knn_exact(&[0.0, 1.0, 0.0], 10).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].0, 3);
+
+        assert!(index.search_knn_exact(&[1.0, 0.0], 1).is_err());
+        assert_eq!(index.get_stats().exact_searches, 2);
+    }
+
+    #[test]
+    fn test_knn_index_search_modes_and_recall() {
+        let index = KnnIndex::new(4).unwrap();
+        let vectors: Vec<(u64, Vec<f32>)> = (0..50u64)
+            .map(|i| {
+                let x = i as f32;
+                (i, vec![x.sin(), x.cos(), (x * 0.5).sin(), 1.0])
+            })
+            .collect();
+        index.add_vectors(vectors).unwrap();
+        let query = [0.3, 0.9, 0.1, 1.0];
+
+        // A small index is scanned under Auto.
+        let auto = index
+            .search_knn_with_options(&query, 5, KnnSearchOptions::default())
+            .unwrap();
+        assert_eq!(auto.mode, KnnSearchMode::Exact);
+        assert_eq!(auto.recall, None);
+        assert_eq!(auto.results, index.search_knn_exact(&query, 5).unwrap());
+
+        let approximate = index
+            .search_knn_with_options(
+                &query,
+                5,
+                KnnSearchOptions {
+                    mode: KnnSearchMode::Approximate,
+                    ef_search: Some(100),
+                    measure_recall: true,
+                },
+            )
+            .unwrap();
+        assert_eq!(approximate.mode, KnnSearchMode::Approximate);
+        assert_eq!(approximate.results.len(), 5);
+        let recall = approximate.recall.unwrap();
+        assert!((0.0..=1.0).contains(&recall));
+
+        let stats = index.get_stats();
+        assert_eq!(stats.recall_samples, 1);
+        assert_eq!(stats.avg_recall, Some(recall));
+        assert!(stats.approximate_searches >= 1);
+    }
+
+    #[test]
+    fn test_knn_search_mode_parsing() {
+        assert_eq!(
+            "exact".parse::<KnnSearchMode>().unwrap(),
+            KnnSearchMode::Exact
+        );
+        assert_eq!(
+            "APPROX".parse::<KnnSearchMode>().unwrap(),
+            KnnSearchMode::Approximate
+        );
+        assert_eq!(
+            "auto".parse::<KnnSearchMode>().unwrap(),
+            KnnSearchMode::Auto
+        );
+        assert!("fast".parse::<KnnSearchMode>().is_err());
+        assert_eq!(KnnSearchMode::Approximate.as_str(), "approximate");
+    }
 }
@@ -4,12 +4,15 @@
 //! - Label index: label_id → bitmap of node_ids (roaring)
 //! - Property index: (label_id, key_id) → (value → set(node_id)) (B-tree)
 //! - Full-text index: Tantivy per label/key
-//! - KNN index: Simple cosine similarity for MVP
+//! - KNN index: HNSW, global plus named per-(label, property) vector indexes
+//! - TTL index: (expires_at, node_id) for the node expiry sweeper
+//! - Count aggregates: incrementally maintained node counts per label/group
 
 use crate::Result;
 
 pub mod btree;
 pub mod composite_btree;
+pub mod count_aggregates;
 pub mod dist;
 pub mod fulltext;
 pub mod fulltext_analyzer;
@@ -18,14 +21,23 @@ pub mod fulltext_writer;
 pub mod knn_index;
 pub mod label_index;
 pub mod pending_updates;
+pub mod property_bloom;
 pub mod property_index;
 pub mod rtree;
+pub mod ttl_index;
+pub mod vector_registry;
 
 // Re-export everything that was previously reachable at `crate::index::*`
-pub use dist::{DEFAULT_VECTORIZER_DIMENSION, DistSimdCosine, DistSimdL2};
-pub use knn_index::{KnnConfig, KnnIndex, KnnIndexStats};
+pub use count_aggregates::{CountAggregate, CountAggregateRegistry};
+pub use dist::{DEFAULT_VECTORIZER_DIMENSION, DistSimdCosine, DistSimdL2, VectorMetric};
+pub use knn_index::{
+    KnnConfig, KnnIndex, KnnIndexStats, KnnSearchMode, KnnSearchOptions, KnnSearchOutcome,
+};
 pub use label_index::{LabelIndex, LabelIndexStats};
+pub use property_bloom::{PropertyBloomRegistry, PropertyBloomStats};
 pub use property_index::{PropertyIndex, PropertyIndexStats, PropertyValue};
+pub use ttl_index::{TtlIndex, TtlIndexStats, TtlPolicy};
+pub use vector_registry::{VectorIndexDefinition, VectorIndexRegistry};
 
 /// Index manager that coordinates all index types
 #[derive(Clone)]
@@ -36,6 +48,9 @@ pub struct IndexManager {
     pub knn_index: KnnIndex,
     /// Property index for property-based queries
     pub property_index: PropertyIndex,
+    /// Opt-in per-(label, property) bloom filters that let un-indexed
+    /// equality scans skip labels that never stored the value.
+    pub property_bloom: PropertyBloomRegistry,
     /// Composite B-tree indexes keyed by (label, property list) tuple
     /// (phase6_opencypher-advanced-types §3). Registered via DDL; the
     /// planner consults the registry before falling back to a label
@@ -51,6 +66,18 @@ pub struct IndexManager {
     /// `USING RTREE` alias from §7.5); WAL replay routes through
     /// `RTreeRegistry::apply_wal_entry`.
     pub rtree: std::sync::Arc<rtree::RTreeRegistry>,
+    /// Named vector indexes, one HNSW graph per `(label, property)`.
+    /// Definitions persist under the index directory; the vectors are
+    /// refilled from node properties at startup.
+    pub vectors: vector_registry::VectorIndexRegistry,
+    /// Node TTL policies and the `(expires_at, node_id)` index the
+    /// expiry sweeper reads. In memory only; refilled at startup from
+    /// `EngineConfig::ttl_policies`.
+    pub ttl: TtlIndex,
+    /// Registered count aggregates and their running results. In
+    /// memory only; refilled at startup from
+    /// `EngineConfig::count_aggregates`.
+    pub count_aggregates: CountAggregateRegistry,
 }
 
 impl IndexManager {
@@ -69,6 +96,9 @@ impl IndexManager {
         if loaded > 0 {
             tracing::info!("FTS: restored {loaded} index(es) from on-disk catalogue");
         }
+        let vectors = vector_registry::VectorIndexRegistry::new();
+        vectors.set_base_dir(index_dir.to_path_buf());
+        vectors.load_from_disk()?;
         // phase6_fulltext-async-writer — async writers are opt-in.
         // Callers that want the high-throughput background commit
         // path invoke `engine.indexes().fulltext.enable_async_writers()`
@@ -79,9 +109,13 @@ impl IndexManager {
             label_index: LabelIndex::new(),
             knn_index: KnnIndex::new(DEFAULT_VECTORIZER_DIMENSION)?,
             property_index: PropertyIndex::new(),
+            property_bloom: PropertyBloomRegistry::new(),
             composite_btree: composite_btree::CompositeBtreeRegistry::new(),
             fulltext,
             rtree: std::sync::Arc::new(rtree::RTreeRegistry::new()),
+            vectors,
+            ttl: TtlIndex::new(),
+            count_aggregates: CountAggregateRegistry::new(),
         })
     }
 
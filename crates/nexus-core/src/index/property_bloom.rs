@@ -0,0 +1,241 @@
+//! Per-(label, property) bloom filters for negative equality lookups.
+//!
+//! A filter records every value written to `(label_id, key_id)` so an
+//! un-indexed `MATCH (n:Label {key: value})` can skip the label scan
+//! when the value has definitely never been stored. Filters are
+//! opt-in, cheaper than a property index (a fixed bit array sized at
+//! creation for ~1% false positives), and can only answer "definitely
+//! absent" or "maybe present".
+//!
+//! Values are never removed: deleting a node or overwriting a property
+//! leaves its old value in the filter, which costs at most a wasted
+//! scan. Recreate the filter to reclaim accuracy after heavy churn.
+//! Numbers are hashed by their `f64` value so `1` and `1.0` agree with
+//! Cypher equality; nulls, lists and maps are not recorded.
+
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+/// Target false-positive rate used to size every filter.
+const FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Default expected distinct values when the caller does not size the filter.
+pub const DEFAULT_BLOOM_EXPECTED_ITEMS: usize = 1_000_000;
+
+/// Bit array + hash count for one `(label, key)` pair.
+#[derive(Debug, Clone)]
+struct ValueBloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    hash_functions: u32,
+    expected_items: usize,
+    inserted: u64,
+}
+
+impl ValueBloomFilter {
+    fn new(expected_items: usize) -> Self {
+        let n = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let num_bits = ((-n * FALSE_POSITIVE_RATE.ln()) / (ln2 * ln2))
+            .ceil()
+            .max(64.0) as u64;
+        let hash_functions = ((num_bits as f64 / n) * ln2).round().clamp(1.0, 16.0) as u32;
+        Self {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            hash_functions,
+            expected_items,
+            inserted: 0,
+        }
+    }
+
+    /// Bit positions for `hash` via double hashing.
+    fn positions(&self, hash: u64) -> impl Iterator<Item = u64> + '_ {
+        let h1 = hash;
+        let h2 = hash.rotate_left(32).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..self.hash_functions as u64)
+            .map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits)
+    }
+
+    fn insert(&mut self, hash: u64) {
+        let positions: Vec<u64> = self.positions(hash).collect();
+        for pos in positions {
+            self.bits[(pos / 64) as usize] |= 1 << (pos % 64);
+        }
+        self.inserted += 1;
+    }
+
+    fn might_contain(&self, hash: u64) -> bool {
+        self.positions(hash)
+            .all(|pos| self.bits[(pos / 64) as usize] & (1 << (pos % 64)) != 0)
+    }
+}
+
+/// Hash a property value for the filters, or `None` when the value
+/// type is not recorded.
+fn value_hash(value: &Value) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    match value {
+        Value::Bool(b) => {
+            0u8.hash(&mut hasher);
+            b.hash(&mut hasher);
+        }
+        Value::Number(n) => {
+            let f = n.as_f64()?;
+            // -0.0 == 0.0 in Cypher; hash them alike.
+            let f = if f == 0.0 { 0.0 } else { f };
+            1u8.hash(&mut hasher);
+            f.to_bits().hash(&mut hasher);
+        }
+        Value::String(s) => {
+            2u8.hash(&mut hasher);
+            s.hash(&mut hasher);
+        }
+        Value::Null | Value::Array(_) | Value::Object(_) => return None,
+    }
+    Some(hasher.finish())
+}
+
+/// Size and fill of one registered filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyBloomStats {
+    /// Label ID the filter covers.
+    pub label_id: u32,
+    /// Property key ID the filter covers.
+    pub key_id: u32,
+    /// Distinct values the filter was sized for.
+    pub expected_items: usize,
+    /// Values recorded so far (including repeats).
+    pub inserted: u64,
+    /// Size of the bit array.
+    pub bits: u64,
+}
+
+/// Registry of property bloom filters keyed by `(label_id, key_id)`.
+///
+/// Cheap to clone; clones share the same filters, so the engine's
+/// write path and the executor's scan path see one state.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyBloomRegistry {
+    filters: Arc<RwLock<HashMap<(u32, u32), ValueBloomFilter>>>,
+}
+
+impl PropertyBloomRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an empty filter for `(label_id, key_id)` sized for
+    /// `expected_items` distinct values. Returns `false` (and leaves the
+    /// existing filter alone) when one is already registered.
+    pub fn create(&self, label_id: u32, key_id: u32, expected_items: usize) -> bool {
+        let mut filters = self.filters.write();
+        if filters.contains_key(&(label_id, key_id)) {
+            return false;
+        }
+        filters.insert((label_id, key_id), ValueBloomFilter::new(expected_items));
+        true
+    }
+
+    /// Remove the filter for `(label_id, key_id)`. Returns whether one existed.
+    pub fn drop_filter(&self, label_id: u32, key_id: u32) -> bool {
+        self.filters.write().remove(&(label_id, key_id)).is_some()
+    }
+
+    /// Whether any filter is registered.
+    pub fn has_any_filter(&self) -> bool {
+        !self.filters.read().is_empty()
+    }
+
+    /// Whether a filter is registered for `(label_id, key_id)`.
+    pub fn has_filter(&self, label_id: u32, key_id: u32) -> bool {
+        self.filters.read().contains_key(&(label_id, key_id))
+    }
+
+    /// Whether any filter covers `label_id`.
+    pub fn has_filter_for_label(&self, label_id: u32) -> bool {
+        self.filters.read().keys().any(|&(l, _)| l == label_id)
+    }
+
+    /// Record `value` in the `(label_id, key_id)` filter, if registered.
+    pub fn insert(&self, label_id: u32, key_id: u32, value: &Value) {
+        let Some(hash) = value_hash(value) else {
+            return;
+        };
+        if let Some(filter) = self.filters.write().get_mut(&(label_id, key_id)) {
+            filter.insert(hash);
+        }
+    }
+
+    /// Probe the `(label_id, key_id)` filter. `Some(false)` means no node
+    /// with the label has ever stored `value` under the key; `None` means
+    /// there is no filter or the value type is not recorded.
+    pub fn might_contain(&self, label_id: u32, key_id: u32, value: &Value) -> Option<bool> {
+        let hash = value_hash(value)?;
+        self.filters
+            .read()
+            .get(&(label_id, key_id))
+            .map(|filter| filter.might_contain(hash))
+    }
+
+    /// Stats for every registered filter, sorted by `(label_id, key_id)`.
+    pub fn stats(&self) -> Vec<PropertyBloomStats> {
+        let mut stats: Vec<PropertyBloomStats> = self
+            .filters
+            .read()
+            .iter()
+            .map(|(&(label_id, key_id), filter)| PropertyBloomStats {
+                label_id,
+                key_id,
+                expected_items: filter.expected_items,
+                inserted: filter.inserted,
+                bits: filter.num_bits,
+            })
+            .collect();
+        stats.sort_by_key(|s| (s.label_id, s.key_id));
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bloom_has_no_false_negatives() {
+        let registry = PropertyBloomRegistry::new();
+        assert!(registry.create(0, 1, 1_000));
+        assert!(!registry.create(0, 1, 10));
+        for i in 0..1_000 {
+            registry.insert(0, 1, &json!(format!("user-{i}")));
+        }
+        for i in 0..1_000 {
+            assert_eq!(
+                registry.might_contain(0, 1, &json!(format!("user-{i}"))),
+                Some(true)
+            );
+        }
+        let false_positives = (1_000..11_000)
+            .filter(|i| registry.might_contain(0, 1, &json!(format!("user-{i}"))) == Some(true))
+            .count();
+        assert!(false_positives < 300, "{false_positives} false positives");
+    }
+
+    #[test]
+    fn test_bloom_numbers_and_unrecorded_types() {
+        let registry = PropertyBloomRegistry::new();
+        registry.create(2, 3, 100);
+        registry.insert(2, 3, &json!(1));
+        assert_eq!(registry.might_contain(2, 3, &json!(1.0)), Some(true));
+        assert_eq!(registry.might_contain(2, 3, &json!(null)), None);
+        assert_eq!(registry.might_contain(9, 3, &json!(1)), None);
+        assert!(registry.has_filter_for_label(2));
+        assert!(registry.drop_filter(2, 3));
+        assert!(!registry.has_any_filter());
+    }
+}
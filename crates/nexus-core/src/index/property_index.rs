@@ -123,6 +123,19 @@ impl PropertyIndex {
             .any(|&(l, _)| l == label_id)
     }
 
+    /// Labels that have an index on `key_id`, in ascending id order.
+    pub fn labels_indexing_key(&self, key_id: u32) -> Vec<u32> {
+        let mut labels: Vec<u32> = self
+            .property_trees
+            .read()
+            .keys()
+            .filter(|&&(_, k)| k == key_id)
+            .map(|&(l, _)| l)
+            .collect();
+        labels.sort_unstable();
+        labels
+    }
+
     /// Add a property value for a node
     pub fn add_property(
         &self,
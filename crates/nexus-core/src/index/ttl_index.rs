@@ -0,0 +1,234 @@
+//! Time-ordered expiry index for node TTL.
+//!
+//! A TTL policy names a `(label, property)` pair whose value is the time
+//! a node expires — epoch milliseconds, or an RFC 3339 timestamp string.
+//! Every node carrying a policy label and a readable expiry is kept in a
+//! `(expires_at, node_id)` B-tree, so the sweeper reads the expired prefix
+//! in O(expired) instead of scanning every node of the label.
+//!
+//! The index is a hint, like the exact-edge relationship index: entries
+//! can go stale when a write path skips the engine hooks (an executor-side
+//! `DELETE`, for instance). The sweeper re-reads each candidate before
+//! deleting it and re-files or drops entries that no longer hold.
+//!
+//! A node under several policy labels expires at the earliest of its
+//! expiry values.
+
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// One TTL policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TtlPolicy {
+    /// Label ID whose nodes expire.
+    pub label_id: u32,
+    /// Property holding the expiry time.
+    pub property: String,
+}
+
+/// Size of the index and how far the sweeper is behind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TtlIndexStats {
+    /// Registered policies.
+    pub policies: usize,
+    /// Nodes with a tracked expiry.
+    pub tracked: usize,
+    /// Tracked nodes already past their expiry.
+    pub expired: usize,
+    /// Milliseconds since the oldest still-present expired node expired.
+    /// `0` when nothing is overdue.
+    pub lag_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct TtlInner {
+    policies: HashMap<u32, TtlPolicy>,
+    by_expiry: BTreeSet<(i64, u64)>,
+    by_node: HashMap<u64, i64>,
+}
+
+impl TtlInner {
+    fn remove(&mut self, node_id: u64) {
+        if let Some(expires_at) = self.by_node.remove(&node_id) {
+            self.by_expiry.remove(&(expires_at, node_id));
+        }
+    }
+}
+
+/// Registry of TTL policies plus the expiry index they feed.
+///
+/// Cheap to clone; clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct TtlIndex {
+    inner: Arc<RwLock<TtlInner>>,
+}
+
+/// Parse a property value as an expiry time in epoch milliseconds.
+pub fn expiry_millis(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f as i64)),
+        Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.timestamp_millis()),
+        _ => None,
+    }
+}
+
+impl TtlIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a policy for `label_id`. Returns `false` when the label
+    /// already has one.
+    pub fn create_policy(&self, label_id: u32, property: &str) -> bool {
+        let mut inner = self.inner.write();
+        if inner.policies.contains_key(&label_id) {
+            return false;
+        }
+        inner.policies.insert(
+            label_id,
+            TtlPolicy {
+                label_id,
+                property: property.to_string(),
+            },
+        );
+        true
+    }
+
+    /// Remove the policy for `label_id`. Nodes it was tracking stay in
+    /// the index until their next write or sweep, where they are re-filed
+    /// under the remaining policies (or dropped).
+    pub fn drop_policy(&self, label_id: u32) -> bool {
+        self.inner.write().policies.remove(&label_id).is_some()
+    }
+
+    /// Registered policies.
+    pub fn policies(&self) -> Vec<TtlPolicy> {
+        let mut policies: Vec<TtlPolicy> = self.inner.read().policies.values().cloned().collect();
+        policies.sort_by_key(|p| p.label_id);
+        policies
+    }
+
+    /// Whether any policy is registered.
+    pub fn has_any_policy(&self) -> bool {
+        !self.inner.read().policies.is_empty()
+    }
+
+    /// The expiry a node with these labels and properties has under the
+    /// registered policies.
+    pub fn expiry_for(&self, label_ids: &[u32], properties: &Value) -> Option<i64> {
+        let inner = self.inner.read();
+        label_ids
+            .iter()
+            .filter_map(|label_id| inner.policies.get(label_id))
+            .filter_map(|policy| properties.get(&policy.property).and_then(expiry_millis))
+            .min()
+    }
+
+    /// Re-file a node after a write to its labels or properties.
+    pub fn sync_node(&self, node_id: u64, label_ids: &[u32], properties: &Value) {
+        if !self.has_any_policy() && self.inner.read().by_node.is_empty() {
+            return;
+        }
+        let expires_at = self.expiry_for(label_ids, properties);
+        let mut inner = self.inner.write();
+        inner.remove(node_id);
+        if let Some(expires_at) = expires_at {
+            inner.by_expiry.insert((expires_at, node_id));
+            inner.by_node.insert(node_id, expires_at);
+        }
+    }
+
+    /// Forget a deleted node.
+    pub fn remove_node(&self, node_id: u64) {
+        self.inner.write().remove(node_id);
+    }
+
+    /// Up to `limit` nodes whose expiry is at or before `now_ms`, oldest
+    /// first, with their indexed expiry.
+    pub fn expired(&self, now_ms: i64, limit: usize) -> Vec<(u64, i64)> {
+        self.inner
+            .read()
+            .by_expiry
+            .range(..=(now_ms, u64::MAX))
+            .take(limit)
+            .map(|&(expires_at, node_id)| (node_id, expires_at))
+            .collect()
+    }
+
+    /// Index size and sweeper lag as of `now_ms`. Counting the overdue
+    /// entries walks only the expired prefix.
+    pub fn stats(&self, now_ms: i64) -> TtlIndexStats {
+        let inner = self.inner.read();
+        let mut overdue = inner.by_expiry.range(..=(now_ms, u64::MAX)).peekable();
+        let lag_ms = overdue
+            .peek()
+            .map(|&&(expires_at, _)| now_ms.saturating_sub(expires_at).max(0) as u64)
+            .unwrap_or(0);
+        let expired = overdue.count();
+        TtlIndexStats {
+            policies: inner.policies.len(),
+            tracked: inner.by_node.len(),
+            expired,
+            lag_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn expired_returns_only_the_overdue_prefix() {
+        let index = TtlIndex::new();
+        index.create_policy(1, "expiresAt");
+        index.sync_node(10, &[1], &json!({"expiresAt": 1_000}));
+        index.sync_node(11, &[1], &json!({"expiresAt": 3_000}));
+        index.sync_node(12, &[1], &json!({"expiresAt": 2_000}));
+        // Wrong label, no expiry property: not tracked.
+        index.sync_node(13, &[2], &json!({"expiresAt": 500}));
+        index.sync_node(14, &[1], &json!({"name": "x"}));
+
+        assert_eq!(index.expired(2_000, 10), vec![(10, 1_000), (12, 2_000)]);
+        assert_eq!(index.expired(2_000, 1), vec![(10, 1_000)]);
+
+        let stats = index.stats(2_500);
+        assert_eq!((stats.tracked, stats.expired, stats.lag_ms), (3, 2, 1_500));
+        assert_eq!(index.stats(500).expired, 0);
+    }
+
+    #[test]
+    fn writes_refile_and_deletes_forget_nodes() {
+        let index = TtlIndex::new();
+        index.create_policy(1, "expiresAt");
+        index.sync_node(10, &[1], &json!({"expiresAt": 1_000}));
+        index.sync_node(10, &[1], &json!({"expiresAt": 5_000}));
+        assert!(index.expired(2_000, 10).is_empty());
+
+        index.sync_node(10, &[1], &json!({}));
+        assert_eq!(index.stats(0).tracked, 0);
+
+        index.sync_node(11, &[1], &json!({"expiresAt": "1970-01-01T00:00:01Z"}));
+        assert_eq!(index.expired(1_000, 10), vec![(11, 1_000)]);
+        index.remove_node(11);
+        assert!(index.expired(i64::MAX, 10).is_empty());
+    }
+
+    #[test]
+    fn earliest_policy_wins_for_multi_label_nodes() {
+        let index = TtlIndex::new();
+        index.create_policy(1, "a");
+        index.create_policy(2, "b");
+        assert!(!index.create_policy(1, "c"));
+        assert_eq!(
+            index.expiry_for(&[1, 2], &json!({"a": 9_000, "b": 4_000})),
+            Some(4_000)
+        );
+    }
+}
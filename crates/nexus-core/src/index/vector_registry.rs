@@ -0,0 +1,445 @@
+//! Named vector (KNN) indexes bound to a `(label, property)` pair.
+//!
+//! Each entry owns an HNSW [`KnnIndex`] with its own dimensionality and
+//! [`VectorMetric`]. The embeddings themselves live in the node
+//! property, so the index is derived data: only the definitions are
+//! persisted (`<index_dir>/vector_indexes.json`), and the engine refills
+//! every index from storage at startup.
+//!
+//! An index can be migrated to new dimensions, metric or property
+//! without downtime: a migration registers a target index under the
+//! same name next to the live one (`vector_index_migrations.json`). Node
+//! writes feed both while clients re-embed, and completing the migration
+//! swaps the target in atomically.
+
+use super::dist::VectorMetric;
+use super::knn_index::{KnnConfig, KnnIndex};
+use crate::{Error, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// File, under the index directory, holding the index definitions.
+const DEFINITIONS_FILE: &str = "vector_indexes.json";
+
+/// File, under the index directory, holding in-progress migrations.
+const MIGRATIONS_FILE: &str = "vector_index_migrations.json";
+
+/// Definition of a named vector index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorIndexDefinition {
+    /// Index name, unique across vector indexes.
+    pub name: String,
+    /// Label whose nodes are indexed.
+    pub label: String,
+    /// Property holding each node's embedding (a list of numbers).
+    pub property: String,
+    /// Embedding length every indexed vector must have.
+    pub dimensions: usize,
+    /// Distance function.
+    pub metric: VectorMetric,
+}
+
+/// A registered index: its definition plus the live HNSW graph.
+#[derive(Clone)]
+pub struct VectorIndexEntry {
+    pub definition: VectorIndexDefinition,
+    pub index: KnnIndex,
+}
+
+/// Thread-safe registry of named vector indexes.
+#[derive(Clone, Default)]
+pub struct VectorIndexRegistry {
+    entries: Arc<RwLock<BTreeMap<String, VectorIndexEntry>>>,
+    /// Migration targets, keyed by the name of the index they replace.
+    migrations: Arc<RwLock<BTreeMap<String, VectorIndexEntry>>>,
+    /// Where definitions are persisted; `None` keeps them in memory.
+    base_dir: Arc<RwLock<Option<PathBuf>>>,
+}
+
+impl VectorIndexRegistry {
+    /// Empty, in-memory registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Persist definitions under `dir` from now on.
+    pub fn set_base_dir(&self, dir: PathBuf) {
+        *self.base_dir.write() = Some(dir);
+    }
+
+    /// Register the definitions (and in-progress migrations) saved under
+    /// the base directory, with empty indexes. Returns how many indexes
+    /// were loaded.
+    pub fn load_from_disk(&self) -> Result<usize> {
+        let mut entries = self.entries.write();
+        for definition in self.read_definitions(DEFINITIONS_FILE)? {
+            let index = new_index(&definition)?;
+            entries.insert(
+                definition.name.clone(),
+                VectorIndexEntry { definition, index },
+            );
+        }
+        let mut migrations = self.migrations.write();
+        for definition in self.read_definitions(MIGRATIONS_FILE)? {
+            // A migration whose index is gone cannot complete.
+            if entries.contains_key(&definition.name) {
+                let index = new_index(&definition)?;
+                migrations.insert(
+                    definition.name.clone(),
+                    VectorIndexEntry { definition, index },
+                );
+            }
+        }
+        Ok(entries.len())
+    }
+
+    /// Register a new, empty index. Fails when the name is taken or
+    /// another vector index already covers the same `(label, property)`.
+    pub fn create(&self, definition: VectorIndexDefinition) -> Result<VectorIndexEntry> {
+        let index = new_index(&definition)?;
+        let entry = {
+            let mut entries = self.entries.write();
+            if entries.contains_key(&definition.name) {
+                return Err(Error::invalid_input(format!(
+                    "vector index {:?} already exists",
+                    definition.name
+                )));
+            }
+            if let Some(existing) = entries
+                .values()
+                .chain(self.migrations.read().values())
+                .find(|e| {
+                    e.definition.label == definition.label
+                        && e.definition.property == definition.property
+                })
+            {
+                return Err(Error::invalid_input(format!(
+                    "vector index {:?} already covers :{}({})",
+                    existing.definition.name, definition.label, definition.property
+                )));
+            }
+            let entry = VectorIndexEntry { definition, index };
+            entries.insert(entry.definition.name.clone(), entry.clone());
+            entry
+        };
+        self.persist()?;
+        Ok(entry)
+    }
+
+    /// Remove an index, and its migration if one is in progress.
+    /// Returns whether it existed.
+    pub fn drop_index(&self, name: &str) -> Result<bool> {
+        let existed = self.entries.write().remove(name).is_some();
+        if existed {
+            self.migrations.write().remove(name);
+            self.persist()?;
+        }
+        Ok(existed)
+    }
+
+    /// Register an empty migration target for the index named by
+    /// `target.name`, to be filled while the live index keeps serving.
+    ///
+    /// The target must keep the live index's label and change its
+    /// dimensions, metric or property; a new property must not be
+    /// covered by another vector index. Fails when the index is unknown
+    /// or already migrating.
+    pub fn start_migration(&self, target: VectorIndexDefinition) -> Result<VectorIndexEntry> {
+        let index = new_index(&target)?;
+        let entry = {
+            let entries = self.entries.read();
+            let mut migrations = self.migrations.write();
+            let Some(source) = entries.get(&target.name) else {
+                return Err(Error::NotFound(format!(
+                    "Vector index {:?} not found",
+                    target.name
+                )));
+            };
+            if migrations.contains_key(&target.name) {
+                return Err(Error::invalid_input(format!(
+                    "vector index {:?} is already being migrated",
+                    target.name
+                )));
+            }
+            if target.label != source.definition.label {
+                return Err(Error::invalid_input(
+                    "a vector index migration cannot change the label",
+                ));
+            }
+            if target == source.definition {
+                return Err(Error::invalid_input(
+                    "a vector index migration must change the dimensions, metric or property",
+                ));
+            }
+            if let Some(existing) = entries.values().chain(migrations.values()).find(|e| {
+                e.definition.name != target.name
+                    && e.definition.label == target.label
+                    && e.definition.property == target.property
+            }) {
+                return Err(Error::invalid_input(format!(
+                    "vector index {:?} already covers :{}({})",
+                    existing.definition.name, target.label, target.property
+                )));
+            }
+            let entry = VectorIndexEntry {
+                definition: target,
+                index,
+            };
+            migrations.insert(entry.definition.name.clone(), entry.clone());
+            entry
+        };
+        self.persist()?;
+        Ok(entry)
+    }
+
+    /// The migration target of index `name`, if it is being migrated.
+    pub fn migration(&self, name: &str) -> Option<VectorIndexEntry> {
+        self.migrations.read().get(name).cloned()
+    }
+
+    /// Replace index `name` with its migration target in one step and
+    /// return the new live entry.
+    pub fn complete_migration(&self, name: &str) -> Result<VectorIndexEntry> {
+        let entry = {
+            let mut entries = self.entries.write();
+            let mut migrations = self.migrations.write();
+            let entry = migrations.remove(name).ok_or_else(|| {
+                Error::NotFound(format!(
+                    "Vector index {name:?} has no migration in progress"
+                ))
+            })?;
+            entries.insert(name.to_string(), entry.clone());
+            entry
+        };
+        self.persist()?;
+        Ok(entry)
+    }
+
+    /// Discard the migration of index `name`. Returns whether one was
+    /// in progress.
+    pub fn abort_migration(&self, name: &str) -> Result<bool> {
+        let existed = self.migrations.write().remove(name).is_some();
+        if existed {
+            self.persist()?;
+        }
+        Ok(existed)
+    }
+
+    /// Look up an index by name.
+    pub fn get(&self, name: &str) -> Option<VectorIndexEntry> {
+        self.entries.read().get(name).cloned()
+    }
+
+    /// Every registered index, by name.
+    pub fn list(&self) -> Vec<VectorIndexEntry> {
+        self.entries.read().values().cloned().collect()
+    }
+
+    /// Every index node writes must keep current: the registered ones
+    /// followed by the migration targets.
+    pub fn maintained(&self) -> Vec<VectorIndexEntry> {
+        let mut entries = self.list();
+        entries.extend(self.migrations.read().values().cloned());
+        entries
+    }
+
+    /// Whether any vector index is registered (write-path fast check).
+    pub fn has_any(&self) -> bool {
+        !self.entries.read().is_empty()
+    }
+
+    /// Reject a node write that would store an embedding of the wrong
+    /// length in an indexed property.
+    ///
+    /// `has_label` tells whether the node carries a label. A property
+    /// of an index the node is labelled for that holds a list of numbers
+    /// must match the dimensions of that index or of its migration
+    /// target. Other values are simply not indexed.
+    pub fn check_node_vectors(
+        &self,
+        has_label: impl Fn(&str) -> bool,
+        properties: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()> {
+        if !self.has_any() {
+            return Ok(());
+        }
+        let entries = self.maintained();
+        for entry in &entries {
+            let definition = &entry.definition;
+            let Some(items) = properties
+                .get(&definition.property)
+                .and_then(serde_json::Value::as_array)
+            else {
+                continue;
+            };
+            if !items.iter().all(serde_json::Value::is_number) || !has_label(&definition.label) {
+                continue;
+            }
+            let accepted = entries.iter().any(|other| {
+                other.definition.label == definition.label
+                    && other.definition.property == definition.property
+                    && other.definition.dimensions == items.len()
+            });
+            if !accepted {
+                return Err(Error::invalid_input(format!(
+                    "vector index {:?} on :{}({}) expects {} dimensions, got {}",
+                    definition.name,
+                    definition.label,
+                    definition.property,
+                    definition.dimensions,
+                    items.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn file_path(&self, file: &str) -> Option<PathBuf> {
+        self.base_dir.read().as_ref().map(|dir| dir.join(file))
+    }
+
+    fn read_definitions(&self, file: &str) -> Result<Vec<VectorIndexDefinition>> {
+        let Some(path) = self.file_path(file) else {
+            return Ok(Vec::new());
+        };
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&raw)
+            .map_err(|e| Error::storage(format!("corrupt {}: {e}", path.display())))
+    }
+
+    fn persist(&self) -> Result<()> {
+        write_definitions(self.file_path(DEFINITIONS_FILE), &self.entries.read())?;
+        write_definitions(self.file_path(MIGRATIONS_FILE), &self.migrations.read())
+    }
+}
+
+fn write_definitions(
+    path: Option<PathBuf>,
+    entries: &BTreeMap<String, VectorIndexEntry>,
+) -> Result<()> {
+    let Some(path) = path else {
+        return Ok(());
+    };
+    let definitions: Vec<&VectorIndexDefinition> =
+        entries.values().map(|e| &e.definition).collect();
+    let json = serde_json::to_string_pretty(&definitions)
+        .map_err(|e| Error::storage(format!("serialize vector index definitions: {e}")))?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn new_index(definition: &VectorIndexDefinition) -> Result<KnnIndex> {
+    KnnIndex::with_metric(
+        definition.dimensions,
+        definition.metric,
+        KnnConfig::default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(name: &str, property: &str) -> VectorIndexDefinition {
+        VectorIndexDefinition {
+            name: name.to_string(),
+            label: "Doc".to_string(),
+            property: property.to_string(),
+            dimensions: 3,
+            metric: VectorMetric::Cosine,
+        }
+    }
+
+    #[test]
+    fn create_rejects_duplicates_and_definitions_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = VectorIndexRegistry::new();
+        registry.set_base_dir(dir.path().to_path_buf());
+
+        registry.create(definition("docs", "embedding")).unwrap();
+        assert!(registry.create(definition("docs", "other")).is_err());
+        assert!(registry.create(definition("again", "embedding")).is_err());
+
+        let reloaded = VectorIndexRegistry::new();
+        reloaded.set_base_dir(dir.path().to_path_buf());
+        assert_eq!(reloaded.load_from_disk().unwrap(), 1);
+        assert_eq!(
+            reloaded.get("docs").unwrap().definition,
+            definition("docs", "embedding")
+        );
+
+        assert!(reloaded.drop_index("docs").unwrap());
+        assert!(!reloaded.drop_index("docs").unwrap());
+        let empty = VectorIndexRegistry::new();
+        empty.set_base_dir(dir.path().to_path_buf());
+        assert_eq!(empty.load_from_disk().unwrap(), 0);
+    }
+
+    #[test]
+    fn migration_swaps_in_target_and_survives_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = VectorIndexRegistry::new();
+        registry.set_base_dir(dir.path().to_path_buf());
+        registry.create(definition("docs", "embedding")).unwrap();
+        registry.create(definition("titles", "title_vec")).unwrap();
+
+        let unchanged = definition("docs", "embedding");
+        assert!(registry.start_migration(unchanged).is_err());
+        assert!(
+            registry
+                .start_migration(definition("docs", "title_vec"))
+                .is_err()
+        );
+        assert!(
+            registry
+                .start_migration(definition("missing", "x"))
+                .is_err()
+        );
+
+        let target = VectorIndexDefinition {
+            dimensions: 5,
+            ..definition("docs", "embedding")
+        };
+        registry.start_migration(target.clone()).unwrap();
+        assert!(registry.start_migration(target.clone()).is_err());
+        assert_eq!(registry.list().len(), 2);
+        assert_eq!(registry.maintained().len(), 3);
+
+        // Both lengths are accepted while the migration runs.
+        let props = |len: usize| {
+            let mut map = serde_json::Map::new();
+            map.insert("embedding".to_string(), serde_json::json!(vec![0.5; len]));
+            map
+        };
+        let doc = |label: &str| label == "Doc";
+        registry.check_node_vectors(doc, &props(3)).unwrap();
+        registry.check_node_vectors(doc, &props(5)).unwrap();
+        assert!(registry.check_node_vectors(doc, &props(4)).is_err());
+        registry.check_node_vectors(|_| false, &props(4)).unwrap();
+
+        let reloaded = VectorIndexRegistry::new();
+        reloaded.set_base_dir(dir.path().to_path_buf());
+        reloaded.load_from_disk().unwrap();
+        assert_eq!(reloaded.migration("docs").unwrap().definition, target);
+
+        let live = reloaded.complete_migration("docs").unwrap();
+        assert_eq!(live.definition.dimensions, 5);
+        assert!(reloaded.migration("docs").is_none());
+        assert!(reloaded.check_node_vectors(doc, &props(3)).is_err());
+        assert!(!reloaded.abort_migration("docs").unwrap());
+
+        let again = VectorIndexRegistry::new();
+        again.set_base_dir(dir.path().to_path_buf());
+        again.load_from_disk().unwrap();
+        assert_eq!(again.get("docs").unwrap().definition, target);
+        assert!(again.migration("docs").is_none());
+    }
+}
@@ -53,6 +53,9 @@ pub mod database;
 pub mod error;
 pub mod execution;
 pub mod executor;
+pub mod external_data;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
 pub mod geospatial;
 pub mod graph; // Unified graph module with submodules
 pub mod index;
@@ -74,6 +77,7 @@ pub mod sharding;
 pub mod simd;
 pub mod spatial;
 pub mod storage;
+pub mod text;
 pub mod transaction;
 pub mod udf;
 pub mod validation;
@@ -110,4 +114,10 @@ pub use validation::{
 };
 
 pub mod engine;
-pub use engine::{Engine, EngineConfig, EngineStats, GraphStatistics, HealthState, HealthStatus};
+pub use engine::{
+    Artifact, ArtifactStore, CorruptionPolicy, DryRunReport, Engine, EngineConfig, EngineStats,
+    GraphStatistics, HealthState, HealthStatus, IntegrityCheckConfig, IntegrityReport,
+    IntegrityScanLevel, NodeDiff, NodeMergeReport, NodeRelationship, PropertyMatch,
+    PropertySearchResult, RelationshipPage, ResultDiff, ResultDiffOptions, StorageMetrics,
+    StoreFileMetrics, VectorIndexInfo, VectorIndexMigrationInfo, VectorUpsertReport,
+};
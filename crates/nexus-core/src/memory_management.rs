@@ -5,11 +5,14 @@
 //! - Garbage collection for unused objects
 //! - Memory allocation strategies
 //! - Memory usage monitoring and optimization
+//! - Memory pressure detection against a cgroup limit or RSS threshold
+//!   ([`MemoryPressureMonitor`]), which drives cache shrinking in the
+//!   engine and request queueing in the server
 
 use crate::{Error, Result};
 use std::alloc::{GlobalAlloc, Layout};
 use std::collections::{HashMap, VecDeque};
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU8, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
@@ -680,6 +683,208 @@ unsafe impl GlobalAlloc for PoolAllocator {
     }
 }
 
+/// Fraction of the limit usage must fall below a threshold before the
+/// monitor leaves the corresponding level, so it does not flap around
+/// the threshold.
+const PRESSURE_HYSTERESIS: f64 = 0.05;
+
+/// cgroup v1 reports "no limit" as a page-aligned `i64::MAX`; anything
+/// this large is treated as unlimited.
+const CGROUP_UNLIMITED: u64 = 1 << 60;
+
+/// When memory counts as under pressure and what to do about it
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MemoryPressureConfig {
+    /// Whether the server watches memory usage at all.
+    pub enabled: bool,
+    /// Memory budget in bytes. When unset, the cgroup limit of the
+    /// process is used; without either there is no pressure.
+    pub limit_bytes: Option<u64>,
+    /// Fraction of the limit at which caches start to shrink and large
+    /// requests queue.
+    pub soft_ratio: f64,
+    /// Fraction of the limit at which caches are emptied and every
+    /// query-bearing request queues.
+    pub hard_ratio: f64,
+    /// How often usage is sampled, in milliseconds.
+    pub check_interval_ms: u64,
+    /// Request bodies of at least this many bytes count as large and
+    /// queue under soft pressure.
+    pub large_request_bytes: u64,
+}
+
+impl Default for MemoryPressureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            limit_bytes: None,
+            soft_ratio: 0.80,
+            hard_ratio: 0.92,
+            check_interval_ms: 1_000,
+            large_request_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// How close memory usage is to the limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryPressureLevel {
+    /// Below the soft threshold
+    Normal,
+    /// Between the soft and hard thresholds
+    Soft,
+    /// Above the hard threshold
+    Hard,
+}
+
+impl MemoryPressureLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Soft,
+            _ => Self::Hard,
+        }
+    }
+}
+
+/// One usage sample and the level it put the monitor in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct MemoryPressureStatus {
+    /// Current level
+    pub level: MemoryPressureLevel,
+    /// Resident memory at the last sample, in bytes
+    pub usage_bytes: u64,
+    /// Limit the usage is measured against, if any
+    pub limit_bytes: Option<u64>,
+}
+
+/// Tracks memory usage against a limit and classifies it into a
+/// [`MemoryPressureLevel`]. Cheap to read from any thread; sampled
+/// periodically by the server.
+#[derive(Debug)]
+pub struct MemoryPressureMonitor {
+    config: MemoryPressureConfig,
+    limit_bytes: Option<u64>,
+    level: AtomicU8,
+    usage_bytes: AtomicU64,
+}
+
+impl MemoryPressureMonitor {
+    /// Monitor for `config`, resolving the limit from the cgroup when
+    /// `config.limit_bytes` is unset.
+    pub fn new(config: MemoryPressureConfig) -> Self {
+        let limit_bytes = if config.enabled {
+            config.limit_bytes.or_else(cgroup_memory_limit)
+        } else {
+            None
+        };
+        Self::with_limit(config, limit_bytes)
+    }
+
+    /// Monitor measuring usage against `limit_bytes`.
+    pub fn with_limit(config: MemoryPressureConfig, limit_bytes: Option<u64>) -> Self {
+        Self {
+            config,
+            limit_bytes: limit_bytes.filter(|&limit| limit > 0),
+            level: AtomicU8::new(MemoryPressureLevel::Normal as u8),
+            usage_bytes: AtomicU64::new(0),
+        }
+    }
+
+    /// The configuration the monitor was built from.
+    pub fn config(&self) -> &MemoryPressureConfig {
+        &self.config
+    }
+
+    /// Limit usage is measured against, if any.
+    pub fn limit_bytes(&self) -> Option<u64> {
+        self.limit_bytes
+    }
+
+    /// Level as of the last sample.
+    pub fn level(&self) -> MemoryPressureLevel {
+        MemoryPressureLevel::from_u8(self.level.load(Ordering::Relaxed))
+    }
+
+    /// Level, usage and limit as of the last sample.
+    pub fn status(&self) -> MemoryPressureStatus {
+        MemoryPressureStatus {
+            level: self.level(),
+            usage_bytes: self.usage_bytes.load(Ordering::Relaxed),
+            limit_bytes: self.limit_bytes,
+        }
+    }
+
+    /// Sample the resident memory of this process and update the level.
+    pub fn sample(&self) -> MemoryPressureLevel {
+        match process_rss_bytes() {
+            Some(usage) => self.observe(usage),
+            None => self.level(),
+        }
+    }
+
+    /// Update the level for a usage of `usage_bytes`. Entering a level
+    /// takes usage at or above its threshold; leaving it takes usage
+    /// [`PRESSURE_HYSTERESIS`] below.
+    pub fn observe(&self, usage_bytes: u64) -> MemoryPressureLevel {
+        self.usage_bytes.store(usage_bytes, Ordering::Relaxed);
+        let level = match self.limit_bytes {
+            Some(limit) if self.config.enabled => {
+                let ratio = usage_bytes as f64 / limit as f64;
+                let current = self.level();
+                let threshold = |base: f64, level: MemoryPressureLevel| {
+                    if current >= level {
+                        base - PRESSURE_HYSTERESIS
+                    } else {
+                        base
+                    }
+                };
+                if ratio >= threshold(self.config.hard_ratio, MemoryPressureLevel::Hard) {
+                    MemoryPressureLevel::Hard
+                } else if ratio >= threshold(self.config.soft_ratio, MemoryPressureLevel::Soft) {
+                    MemoryPressureLevel::Soft
+                } else {
+                    MemoryPressureLevel::Normal
+                }
+            }
+            _ => MemoryPressureLevel::Normal,
+        };
+        self.level.store(level as u8, Ordering::Relaxed);
+        level
+    }
+}
+
+/// Resident set size of this process in bytes (`VmRSS` in
+/// `/proc/self/status`). `None` where procfs is unavailable.
+pub fn process_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// Memory limit of the cgroup this process runs in (cgroup v2
+/// `memory.max`, else cgroup v1 `memory.limit_in_bytes`). `None` when
+/// unlimited or not in a cgroup.
+pub fn cgroup_memory_limit() -> Option<u64> {
+    [
+        "/sys/fs/cgroup/memory.max",
+        "/sys/fs/cgroup/memory/memory.limit_in_bytes",
+    ]
+    .iter()
+    .find_map(|path| std::fs::read_to_string(path).ok())
+    .and_then(|value| value.trim().parse::<u64>().ok())
+    .filter(|&limit| limit > 0 && limit < CGROUP_UNLIMITED)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -788,4 +993,22 @@ mod tests {
         let addr3 = pool3.allocate(1024, BlockType::Node).unwrap();
         assert!(addr3 > 0);
     }
+
+    #[test]
+    fn pressure_levels_follow_usage_with_hysteresis() {
+        let monitor =
+            MemoryPressureMonitor::with_limit(MemoryPressureConfig::default(), Some(1_000));
+        assert_eq!(monitor.observe(500), MemoryPressureLevel::Normal);
+        assert_eq!(monitor.observe(800), MemoryPressureLevel::Soft);
+        assert_eq!(monitor.observe(950), MemoryPressureLevel::Hard);
+        // Still within the hysteresis band below the hard threshold.
+        assert_eq!(monitor.observe(900), MemoryPressureLevel::Hard);
+        assert_eq!(monitor.observe(850), MemoryPressureLevel::Soft);
+        assert_eq!(monitor.observe(760), MemoryPressureLevel::Soft);
+        assert_eq!(monitor.observe(700), MemoryPressureLevel::Normal);
+        assert_eq!(monitor.status().usage_bytes, 700);
+
+        let unlimited = MemoryPressureMonitor::with_limit(MemoryPressureConfig::default(), None);
+        assert_eq!(unlimited.observe(u64::MAX), MemoryPressureLevel::Normal);
+    }
 }
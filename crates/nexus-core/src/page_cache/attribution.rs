@@ -0,0 +1,121 @@
+//! Attribution of page cache accesses to the query running on a thread.
+//!
+//! A caller that executes a query opens a scope with [`begin`] on the
+//! thread that runs it; every [`PageCache::get_page`](super::PageCache::get_page)
+//! hit or miss on that thread is counted into the scope until
+//! [`AccessScope::finish`] returns the totals. Threads without an open
+//! scope pay one thread-local read per access and count nothing.
+//!
+//! Scopes nest: an inner scope's accesses are also added to the scope
+//! it interrupted, so a subquery run through the same entry point is
+//! still charged to the outer query.
+
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Page cache hits and misses charged to one scope.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageCacheAccesses {
+    /// Lookups served from cached pages
+    pub hits: u64,
+    /// Lookups that had to load the page
+    pub misses: u64,
+}
+
+impl PageCacheAccesses {
+    fn add(self, other: Self) -> Self {
+        Self {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<Option<PageCacheAccesses>> = const { Cell::new(None) };
+}
+
+/// An open attribution scope; see the module docs.
+#[must_use = "accesses are only attributed while the scope is alive"]
+pub struct AccessScope {
+    outer: Option<PageCacheAccesses>,
+    finished: bool,
+}
+
+/// Start charging page cache accesses on this thread to a new scope.
+pub fn begin() -> AccessScope {
+    let outer = CURRENT.with(|current| current.replace(Some(PageCacheAccesses::default())));
+    AccessScope {
+        outer,
+        finished: false,
+    }
+}
+
+impl AccessScope {
+    /// Close the scope and return what it saw.
+    pub fn finish(mut self) -> PageCacheAccesses {
+        self.finished = true;
+        self.close()
+    }
+
+    fn close(&self) -> PageCacheAccesses {
+        CURRENT.with(|current| {
+            let seen = current.get().unwrap_or_default();
+            current.set(self.outer.map(|outer| outer.add(seen)));
+            seen
+        })
+    }
+}
+
+impl Drop for AccessScope {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.close();
+        }
+    }
+}
+
+pub(super) fn record_hit() {
+    CURRENT.with(|current| {
+        if let Some(mut accesses) = current.get() {
+            accesses.hits += 1;
+            current.set(Some(accesses));
+        }
+    });
+}
+
+pub(super) fn record_miss() {
+    CURRENT.with(|current| {
+        if let Some(mut accesses) = current.get() {
+            accesses.misses += 1;
+            current.set(Some(accesses));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_only_inside_a_scope() {
+        record_hit();
+        let scope = begin();
+        record_hit();
+        record_miss();
+        record_hit();
+        assert_eq!(scope.finish(), PageCacheAccesses { hits: 2, misses: 1 });
+        record_miss();
+        assert_eq!(begin().finish(), PageCacheAccesses::default());
+    }
+
+    #[test]
+    fn nested_scopes_charge_the_outer_scope_too() {
+        let outer = begin();
+        record_miss();
+        let inner = begin();
+        record_hit();
+        assert_eq!(inner.finish(), PageCacheAccesses { hits: 1, misses: 0 });
+        assert_eq!(outer.finish(), PageCacheAccesses { hits: 1, misses: 1 });
+    }
+}
@@ -10,6 +10,8 @@
 //! Pages are stored in a HashMap for O(1) lookup, with a circular buffer
 //! for eviction scanning.
 
+pub mod attribution;
+
 use crate::{Error, Result};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -180,6 +182,10 @@ pub struct PageCache {
     /// Maximum number of pages in cache
     capacity: usize,
 
+    /// Capacity the cache was created with; [`PageCache::resize`] may
+    /// shrink below it under memory pressure and grow back to it later
+    configured_capacity: usize,
+
     /// Clock hand position (for eviction)
     clock_hand: usize,
 
@@ -215,6 +221,7 @@ impl PageCache {
         Ok(Self {
             pages: HashMap::with_capacity(capacity),
             capacity,
+            configured_capacity: capacity,
             clock_hand: 0,
             page_list: vec![None; capacity],
             dirty_pages: HashSet::new(),
@@ -231,12 +238,14 @@ impl PageCache {
         // Check if page is in cache
         if let Some(page) = self.pages.get(&page_id) {
             self.stats.hits += 1;
+            attribution::record_hit();
             page.set_reference_bit(); // Mark as recently accessed
             return Ok(Arc::clone(page));
         }
 
         // Cache miss - need to load page
         self.stats.misses += 1;
+        attribution::record_miss();
 
         // Evict if cache is full
         if self.pages.len() >= self.capacity {
@@ -336,6 +345,56 @@ impl PageCache {
         }
     }
 
+    /// Current capacity in pages
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Capacity the cache was created with
+    pub fn configured_capacity(&self) -> usize {
+        self.configured_capacity
+    }
+
+    /// Change the capacity to `capacity` pages, evicting unpinned pages
+    /// (flushing dirty ones) until the cache fits and releasing the
+    /// freed map space. Pinned pages are never evicted, so the capacity
+    /// stays at the number of pinned pages if that is larger.
+    ///
+    /// Returns the number of pages evicted.
+    pub fn resize(&mut self, capacity: usize) -> Result<usize> {
+        if capacity == 0 {
+            return Err(Error::page_cache("Capacity must be > 0"));
+        }
+
+        let unpinned = self.pages.values().filter(|p| !p.is_pinned()).count();
+        let to_evict = self.pages.len().saturating_sub(capacity).min(unpinned);
+        for _ in 0..to_evict {
+            self.evict_page()?;
+        }
+
+        // Rebuild the clock around the surviving pages.
+        self.capacity = capacity.max(self.pages.len());
+        self.page_list = vec![None; self.capacity];
+        for (slot, page_id) in self.page_list.iter_mut().zip(self.pages.keys()) {
+            *slot = Some(*page_id);
+        }
+        self.clock_hand = 0;
+        self.pages.shrink_to(self.capacity);
+        self.stats.cache_size = self.pages.len();
+
+        Ok(to_evict)
+    }
+
+    /// Change the configured capacity — the size memory pressure lets
+    /// the cache grow back to — and resize to it now.
+    ///
+    /// Returns the number of pages evicted.
+    pub fn reconfigure(&mut self, capacity: usize) -> Result<usize> {
+        let evicted = self.resize(capacity)?;
+        self.configured_capacity = capacity;
+        Ok(evicted)
+    }
+
     /// Pin a page (prevent eviction)
     pub fn pin_page(&mut self, page_id: u64) -> Result<()> {
         if let Some(page) = self.pages.get(&page_id) {
@@ -554,6 +613,27 @@ mod tests {
         assert!(cache.is_empty());
     }
 
+    #[test]
+    fn test_cache_resize_keeps_pinned_pages() {
+        let mut cache = PageCache::new(8).unwrap();
+        for page_id in 0..8 {
+            cache.get_page(page_id).unwrap();
+        }
+        cache.pin_page(3).unwrap();
+
+        assert_eq!(cache.resize(2).unwrap(), 6);
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_page(3));
+        cache.get_page(100).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains_page(3));
+
+        cache.resize(cache.configured_capacity()).unwrap();
+        assert_eq!(cache.capacity(), 8);
+        assert!(cache.resize(0).is_err());
+    }
+
     #[test]
     fn test_cache_zero_capacity() {
         let result = PageCache::new(0);
@@ -21,6 +21,7 @@ pub mod metrics;
 pub mod monitoring;
 pub mod parameter_redaction;
 pub mod plan_cache;
+pub mod plan_history;
 pub mod profiler;
 pub mod query_stats;
 pub mod recommendations;
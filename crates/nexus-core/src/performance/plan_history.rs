@@ -0,0 +1,232 @@
+//! Per-template record of the plan the executor chose and how long it
+//! took, the input to plan regression detection (see
+//! [`crate::engine::plan_baseline`]).
+//!
+//! Queries are grouped by [`query_template`], so `WHERE n.age = 25` and
+//! `WHERE n.age = 30` share a record. A plan is compared by its
+//! *shape*: the `Debug` form of each top-level operator with every
+//! literal reduced to `?` (see [`plan_shape`]), so the values a query
+//! filters on never count as a plan change but a label scan turning
+//! into an index seek does. When a template's shape changes its
+//! latency counters restart, so they always describe the current plan.
+//!
+//! The executor plans deep inside `Executor::execute_inner`, where the
+//! elapsed time is not known yet; like the planner notifications, the
+//! shape crosses that boundary through a per-thread slot.
+
+use super::query_stats::query_template;
+use crate::executor::Operator;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Templates tracked when [`PlanHistoryConfig::max_templates`] is not set.
+pub const DEFAULT_PLAN_HISTORY_TEMPLATES: usize = 10_000;
+
+/// Whether and how much the executor records chosen plans.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanHistoryConfig {
+    /// Record the plan and latency of every executed query. Off by
+    /// default: recording renders each plan once per execution.
+    pub enabled: bool,
+    /// Distinct templates kept; templates first seen beyond this are
+    /// not recorded.
+    pub max_templates: usize,
+}
+
+impl Default for PlanHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_templates: DEFAULT_PLAN_HISTORY_TEMPLATES,
+        }
+    }
+}
+
+/// The plan recorded for one query template.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanRecord {
+    /// Query template (literals replaced by `?`)
+    pub template: String,
+    /// Latest query text seen with this template and plan, used to
+    /// re-plan the template later
+    pub sample_query: String,
+    /// Plan shape, one entry per top-level operator
+    pub plan: Vec<String>,
+    /// Executions with this plan
+    pub executions: u64,
+    /// Total execution time with this plan, in milliseconds
+    pub total_ms: f64,
+    /// Times the plan changed since the template was first seen
+    pub plan_changes: u64,
+}
+
+impl PlanRecord {
+    /// Mean execution time with the current plan, in milliseconds.
+    pub fn mean_ms(&self) -> f64 {
+        if self.executions == 0 {
+            0.0
+        } else {
+            self.total_ms / self.executions as f64
+        }
+    }
+}
+
+/// Shared, bounded map from query template to [`PlanRecord`].
+///
+/// Cheap to clone; clones record into the same map, so the engine and
+/// every executor clone it is installed on see the same history.
+#[derive(Debug, Clone)]
+pub struct PlanHistory {
+    records: Arc<Mutex<HashMap<String, PlanRecord>>>,
+    max_templates: usize,
+}
+
+impl PlanHistory {
+    /// Empty history keeping at most `max_templates` templates.
+    pub fn new(max_templates: usize) -> Self {
+        Self {
+            records: Arc::new(Mutex::new(HashMap::new())),
+            max_templates,
+        }
+    }
+
+    /// Record one execution of `query` with plan shape `plan`.
+    pub fn record(&self, query: &str, plan: Vec<String>, elapsed: Duration) {
+        let template = query_template(query);
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        let mut records = self.records.lock();
+        if records.len() >= self.max_templates && !records.contains_key(&template) {
+            return;
+        }
+        let record = records
+            .entry(template)
+            .or_insert_with_key(|template| PlanRecord {
+                template: template.clone(),
+                sample_query: query.to_string(),
+                plan: plan.clone(),
+                executions: 0,
+                total_ms: 0.0,
+                plan_changes: 0,
+            });
+        if record.plan != plan {
+            record.plan = plan;
+            record.sample_query = query.to_string();
+            record.executions = 0;
+            record.total_ms = 0.0;
+            record.plan_changes += 1;
+        }
+        record.executions += 1;
+        record.total_ms += elapsed_ms;
+    }
+
+    /// Every record, sorted by template.
+    pub fn records(&self) -> Vec<PlanRecord> {
+        let mut records: Vec<PlanRecord> = self.records.lock().values().cloned().collect();
+        records.sort_by(|a, b| a.template.cmp(&b.template));
+        records
+    }
+
+    /// The record for the template of `query`, if it has run.
+    pub fn record_for(&self, query: &str) -> Option<PlanRecord> {
+        self.records.lock().get(&query_template(query)).cloned()
+    }
+
+    /// Forget every record.
+    pub fn clear(&self) {
+        self.records.lock().clear();
+    }
+}
+
+/// Shape of a physical plan: each top-level operator's `Debug` form
+/// with literals replaced by `?`.
+pub fn plan_shape(operators: &[Operator]) -> Vec<String> {
+    operators
+        .iter()
+        .map(|op| query_template(&format!("{op:?}")))
+        .collect()
+}
+
+thread_local! {
+    /// Shape of the first plan built during the current
+    /// `Executor::execute` call. Subqueries plan through the same
+    /// executor; keeping the first shape keeps the outer query's.
+    static PENDING_PLAN_SHAPE: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Remember the shape of the plan just built unless this call already
+/// stashed one. `shape` only runs when the slot is empty.
+pub(crate) fn stash_plan_shape(shape: impl FnOnce() -> Vec<String>) {
+    PENDING_PLAN_SHAPE.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(shape());
+        }
+    });
+}
+
+/// Take the stashed plan shape, leaving the slot empty.
+pub(crate) fn take_pending_plan_shape() -> Option<Vec<String>> {
+    PENDING_PLAN_SHAPE.with(|slot| slot.borrow_mut().take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals_share_a_record_and_a_new_plan_restarts_it() {
+        let history = PlanHistory::new(10);
+        let scan = vec!["NodeByLabel { label_id: ?, variable: ? }".to_string()];
+        let seek = vec!["NodeIndexSeek { label_id: ? }".to_string()];
+
+        history.record(
+            "MATCH (n:P {age: 25}) RETURN n",
+            scan.clone(),
+            Duration::from_millis(4),
+        );
+        history.record(
+            "MATCH (n:P {age: 30}) RETURN n",
+            scan,
+            Duration::from_millis(6),
+        );
+        let record = history.record_for("MATCH (n:P {age: 1}) RETURN n").unwrap();
+        assert_eq!(record.executions, 2);
+        assert!((record.mean_ms() - 5.0).abs() < 1e-9);
+
+        history.record(
+            "MATCH (n:P {age: 41}) RETURN n",
+            seek.clone(),
+            Duration::from_millis(1),
+        );
+        let record = history.record_for("MATCH (n:P {age: 1}) RETURN n").unwrap();
+        assert_eq!(record.plan, seek);
+        assert_eq!(record.executions, 1);
+        assert_eq!(record.plan_changes, 1);
+        assert_eq!(record.sample_query, "MATCH (n:P {age: 41}) RETURN n");
+    }
+
+    #[test]
+    fn new_templates_beyond_the_limit_are_not_recorded() {
+        let history = PlanHistory::new(1);
+        history.record("RETURN 1", Vec::new(), Duration::ZERO);
+        history.record("MATCH (n) RETURN n", Vec::new(), Duration::ZERO);
+        history.record("RETURN 2", Vec::new(), Duration::ZERO);
+        let records = history.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].executions, 2);
+    }
+
+    #[test]
+    fn only_the_first_stashed_shape_is_kept() {
+        let _ = take_pending_plan_shape();
+        stash_plan_shape(|| vec!["outer".to_string()]);
+        stash_plan_shape(|| unreachable!("slot already filled"));
+        assert_eq!(take_pending_plan_shape(), Some(vec!["outer".to_string()]));
+        assert_eq!(take_pending_plan_shape(), None);
+    }
+}
@@ -5,6 +5,10 @@
 //! - Slow query logging
 //! - Query statistics storage
 //! - Query plan caching
+//!
+//! Per-query statistics are keyed by *template*: the query with every
+//! string and number literal replaced by `?` (see [`query_template`]),
+//! so `WHERE n.age = 25` and `WHERE n.age = 30` aggregate together.
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
@@ -100,14 +104,14 @@ pub struct QueryStatistics {
     pub max_execution_time_ms: Arc<AtomicU64>,
     /// Slow query log
     pub slow_query_log: Arc<RwLock<SlowQueryLog>>,
-    /// Query statistics by query pattern (normalized)
+    /// Query statistics by query template
     pub query_pattern_stats: Arc<RwLock<HashMap<String, QueryPatternStats>>>,
 }
 
-/// Statistics for a query pattern
+/// Statistics for a query template
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryPatternStats {
-    /// Query pattern (normalized)
+    /// Query template (see [`query_template`])
     pub pattern: String,
     /// Execution count
     pub count: u64,
@@ -123,6 +127,40 @@ pub struct QueryPatternStats {
     pub success_count: u64,
     /// Failure count
     pub failure_count: u64,
+    /// Total rows returned
+    #[serde(default)]
+    pub total_rows: u64,
+    /// Average rows returned per execution
+    #[serde(default)]
+    pub avg_rows: f64,
+    /// Page cache hits charged to this template
+    #[serde(default)]
+    pub page_cache_hits: u64,
+    /// Page cache misses charged to this template
+    #[serde(default)]
+    pub page_cache_misses: u64,
+}
+
+impl QueryPatternStats {
+    /// Page cache misses per execution
+    pub fn page_cache_misses_per_execution(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.page_cache_misses as f64 / self.count as f64
+        }
+    }
+
+    /// Fraction of this template's page cache lookups that hit (0.0
+    /// when it made none)
+    pub fn page_cache_hit_rate(&self) -> f64 {
+        let total = self.page_cache_hits + self.page_cache_misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.page_cache_hits as f64 / total as f64
+        }
+    }
 }
 
 impl QueryStatistics {
@@ -247,8 +285,8 @@ impl QueryStatistics {
 
         self.slow_query_log.write().unwrap().add_query(record);
 
-        // Update pattern statistics
-        let pattern = self.normalize_query(query);
+        // Update template statistics
+        let pattern = query_template(query);
         let pattern_clone = pattern.clone();
         let mut pattern_stats = self.query_pattern_stats.write().unwrap();
         let stats = pattern_stats
@@ -262,11 +300,17 @@ impl QueryStatistics {
                 max_time_ms: 0,
                 success_count: 0,
                 failure_count: 0,
+                total_rows: 0,
+                avg_rows: 0.0,
+                page_cache_hits: 0,
+                page_cache_misses: 0,
             });
 
         stats.count += 1;
         stats.total_time_ms += time_ms;
         stats.avg_time_ms = stats.total_time_ms as f64 / stats.count as f64;
+        stats.total_rows += rows_returned as u64;
+        stats.avg_rows = stats.total_rows as f64 / stats.count as f64;
         if time_ms < stats.min_time_ms {
             stats.min_time_ms = time_ms;
         }
@@ -280,34 +324,43 @@ impl QueryStatistics {
         }
     }
 
-    /// Normalize query for pattern matching (remove parameters, normalize whitespace)
-    fn normalize_query(&self, query: &str) -> String {
-        // Simple normalization: remove string literals and numbers, normalize whitespace
-        let mut normalized = query.to_string();
-
-        // Remove string literals (basic approach)
-        normalized = regex::Regex::new(r#""[^"]*""#)
-            .unwrap()
-            .replace_all(&normalized, "?")
-            .to_string();
-        normalized = regex::Regex::new(r"'[^']*'")
-            .unwrap()
-            .replace_all(&normalized, "?")
-            .to_string();
-
-        // Remove numbers
-        normalized = regex::Regex::new(r"\b\d+\b")
-            .unwrap()
-            .replace_all(&normalized, "?")
-            .to_string();
+    /// Charge page cache accesses observed while running `query` (see
+    /// [`crate::page_cache::attribution`]) to its template. Call after
+    /// [`Self::record_query_with_metrics`] for the same execution; a
+    /// template that was never recorded is ignored.
+    pub fn record_page_cache_accesses(
+        &self,
+        query: &str,
+        accesses: crate::page_cache::attribution::PageCacheAccesses,
+    ) {
+        let mut pattern_stats = self.query_pattern_stats.write().unwrap();
+        if let Some(stats) = pattern_stats.get_mut(&query_template(query)) {
+            stats.page_cache_hits += accesses.hits;
+            stats.page_cache_misses += accesses.misses;
+        }
+    }
 
-        // Normalize whitespace
-        normalized = regex::Regex::new(r"\s+")
+    /// Get the `n` templates that miss the page cache most per
+    /// execution, i.e. the most I/O-bound ones (ties broken by total
+    /// misses, then by template text). Templates that never touched the
+    /// page cache are left out.
+    pub fn io_bound_patterns(&self, n: usize) -> Vec<QueryPatternStats> {
+        let mut patterns: Vec<QueryPatternStats> = self
+            .query_pattern_stats
+            .read()
             .unwrap()
-            .replace_all(&normalized, " ")
-            .to_string();
-
-        normalized.trim().to_string()
+            .values()
+            .filter(|stats| stats.page_cache_hits + stats.page_cache_misses > 0)
+            .cloned()
+            .collect();
+        patterns.sort_by(|a, b| {
+            b.page_cache_misses_per_execution()
+                .total_cmp(&a.page_cache_misses_per_execution())
+                .then(b.page_cache_misses.cmp(&a.page_cache_misses))
+                .then_with(|| a.pattern.cmp(&b.pattern))
+        });
+        patterns.truncate(n);
+        patterns
     }
 
     /// Get slow queries
@@ -320,6 +373,27 @@ impl QueryStatistics {
         self.query_pattern_stats.read().unwrap().clone()
     }
 
+    /// Get the `n` templates with the highest total execution time,
+    /// most expensive first (ties broken by execution count, then by
+    /// template text)
+    pub fn top_patterns(&self, n: usize) -> Vec<QueryPatternStats> {
+        let mut patterns: Vec<QueryPatternStats> = self
+            .query_pattern_stats
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        patterns.sort_by(|a, b| {
+            b.total_time_ms
+                .cmp(&a.total_time_ms)
+                .then(b.count.cmp(&a.count))
+                .then_with(|| a.pattern.cmp(&b.pattern))
+        });
+        patterns.truncate(n);
+        patterns
+    }
+
     /// Get overall statistics
     pub fn get_statistics(&self) -> QueryStatisticsSummary {
         QueryStatisticsSummary {
@@ -351,6 +425,104 @@ impl QueryStatistics {
     }
 }
 
+/// Reduce a query to its template: comments and redundant whitespace
+/// are dropped (as for the plan cache key) and every string or number
+/// literal becomes `?`. A list made only of literals collapses to a
+/// single `?`, so `IN [1, 2]` and `IN [1, 2, 3]` share a template.
+/// Identifiers, backtick-quoted names, `$parameters` and the keywords
+/// `true`, `false` and `null` are kept as written.
+pub fn query_template(query: &str) -> String {
+    let canonical = crate::executor::planner::cache::canonicalise_query(query);
+    let chars: Vec<char> = canonical.chars().collect();
+    let mut out = String::with_capacity(canonical.len());
+    // Open brackets, innermost last; list collapsing only applies
+    // directly inside `[`.
+    let mut brackets: Vec<char> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\'' | '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+                push_literal(&mut out, &brackets);
+            }
+            '`' => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i] != '`' {
+                    i += 1;
+                }
+                i = (i + 1).min(chars.len());
+                out.extend(&chars[start..i]);
+            }
+            c if c == '$' || c == '_' || c.is_alphabetic() => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i] == '_' || chars[i].is_alphanumeric()) {
+                    i += 1;
+                }
+                out.extend(&chars[start..i]);
+            }
+            c if c.is_ascii_digit() => {
+                let hex = c == '0' && matches!(chars.get(i + 1), Some('x' | 'X'));
+                i += 1;
+                while i < chars.len() {
+                    let next = chars[i];
+                    let exponent_sign =
+                        !hex && matches!(next, '+' | '-') && matches!(chars[i - 1], 'e' | 'E');
+                    // `1..3` is a range, not a decimal point.
+                    let decimal_point =
+                        next == '.' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit());
+                    if next.is_ascii_alphanumeric() || decimal_point || exponent_sign {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                push_literal(&mut out, &brackets);
+            }
+            '[' | '(' | '{' => {
+                brackets.push(c);
+                out.push(c);
+                i += 1;
+            }
+            ']' | ')' | '}' => {
+                brackets.pop();
+                out.push(c);
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Emit a `?` for a literal, folding it into a preceding `?` when both
+/// are elements of the same list.
+fn push_literal(out: &mut String, brackets: &[char]) {
+    if brackets.last() == Some(&'[') {
+        let trimmed = out.trim_end_matches(' ');
+        if let Some(before_comma) = trimmed.strip_suffix(',')
+            && before_comma.trim_end_matches(' ').ends_with('?')
+        {
+            let keep = before_comma.trim_end_matches(' ').len();
+            out.truncate(keep);
+            return;
+        }
+    }
+    out.push('?');
+}
+
 /// Query statistics summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryStatisticsSummary {
@@ -615,4 +787,91 @@ mod tests {
         // Should have one pattern (normalized)
         assert_eq!(patterns.len(), 1);
     }
+
+    #[test]
+    fn test_query_template_strips_literals() {
+        assert_eq!(
+            query_template(
+                "MATCH (n:Person {name: 'Ann'})  // lookup\nWHERE n.age > 3.5e2 RETURN n"
+            ),
+            "MATCH (n:Person {name: ?}) WHERE n.age > ? RETURN n"
+        );
+        assert_eq!(
+            query_template("MATCH (n) WHERE n.id IN [1, 2, 3] RETURN n LIMIT 10"),
+            query_template("MATCH (n) WHERE n.id IN [7] RETURN n LIMIT 5")
+        );
+        assert_eq!(
+            query_template("MATCH (a)-[*1..3]->(b) WHERE a.`weird 1` = $p1 RETURN b"),
+            "MATCH (a)-[*?..?]->(b) WHERE a.`weird 1` = $p1 RETURN b"
+        );
+        assert_eq!(
+            query_template(r#"RETURN "it\"s", 'x', n2, [$a, 0x1F]"#),
+            "RETURN ?, ?, n2, [$a, ?]"
+        );
+    }
+
+    #[test]
+    fn test_top_patterns_by_total_time() {
+        let stats = QueryStatistics::new(1000, 10);
+
+        for (id, ms) in [(1, 10), (2, 20), (3, 30)] {
+            stats.record_query(
+                &format!("MATCH (n) WHERE id(n) = {id} RETURN n"),
+                Duration::from_millis(ms),
+                true,
+                None,
+                1,
+            );
+        }
+        stats.record_query(
+            "MATCH (n:Big) RETURN n",
+            Duration::from_millis(50),
+            true,
+            None,
+            100,
+        );
+        stats.record_query("RETURN 1", Duration::from_millis(1), true, None, 1);
+
+        let top = stats.top_patterns(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].pattern, "MATCH (n) WHERE id(n) = ? RETURN n");
+        assert_eq!(top[0].count, 3);
+        assert_eq!(top[0].total_time_ms, 60);
+        assert_eq!(top[0].total_rows, 3);
+        assert_eq!(top[0].avg_rows, 1.0);
+        assert_eq!(top[1].pattern, "MATCH (n:Big) RETURN n");
+        assert_eq!(top[1].total_rows, 100);
+    }
+
+    #[test]
+    fn test_io_bound_patterns_by_misses_per_execution() {
+        use crate::page_cache::attribution::PageCacheAccesses;
+
+        let stats = QueryStatistics::new(1000, 10);
+        for (query, hits, misses) in [
+            ("MATCH (n:Hot) RETURN n", 90, 10),
+            ("MATCH (n:Cold) RETURN n", 5, 40),
+            ("MATCH (n:Cold) RETURN n", 5, 20),
+            ("RETURN 1", 0, 0),
+        ] {
+            stats.record_query(query, Duration::from_millis(1), true, None, 1);
+            stats.record_page_cache_accesses(query, PageCacheAccesses { hits, misses });
+        }
+        // Never recorded through `record_query`: ignored.
+        stats.record_page_cache_accesses(
+            "MATCH (x) RETURN x",
+            PageCacheAccesses {
+                hits: 0,
+                misses: 99,
+            },
+        );
+
+        let io_bound = stats.io_bound_patterns(10);
+        assert_eq!(io_bound.len(), 2);
+        assert_eq!(io_bound[0].pattern, "MATCH (n:Cold) RETURN n");
+        assert_eq!(io_bound[0].page_cache_misses, 60);
+        assert_eq!(io_bound[0].page_cache_misses_per_execution(), 30.0);
+        assert_eq!(io_bound[1].pattern, "MATCH (n:Hot) RETURN n");
+        assert_eq!(io_bound[1].page_cache_hit_rate(), 0.9);
+    }
 }
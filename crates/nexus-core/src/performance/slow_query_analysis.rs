@@ -5,7 +5,7 @@
 //! - Performance recommendations
 //! - Query optimization suggestions
 
-use crate::performance::query_stats::{QueryRecord, QueryStatistics};
+use crate::performance::query_stats::{QueryRecord, QueryStatistics, query_template};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -73,32 +73,7 @@ impl SlowQueryAnalyzer {
 
     /// Normalize query for pattern matching
     fn normalize_query(&self, query: &str) -> String {
-        // Simple normalization: remove string literals and numbers, normalize whitespace
-        let mut normalized = query.to_string();
-
-        // Remove string literals
-        normalized = regex::Regex::new(r#""[^"]*""#)
-            .unwrap()
-            .replace_all(&normalized, "?")
-            .to_string();
-        normalized = regex::Regex::new(r"'[^']*'")
-            .unwrap()
-            .replace_all(&normalized, "?")
-            .to_string();
-
-        // Remove numbers
-        normalized = regex::Regex::new(r"\b\d+\b")
-            .unwrap()
-            .replace_all(&normalized, "?")
-            .to_string();
-
-        // Normalize whitespace
-        normalized = regex::Regex::new(r"\s+")
-            .unwrap()
-            .replace_all(&normalized, " ")
-            .to_string();
-
-        normalized.trim().to_string()
+        query_template(query)
     }
 
     /// Generate recommendations for a query pattern
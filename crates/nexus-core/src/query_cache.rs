@@ -178,7 +178,7 @@ impl IntelligentQueryCache {
 
         for ch in query.chars() {
             match ch {
-                '"' | '\'' if !in_string => {
+                '"' | '\'' | '`' if !in_string => {
                     if !current_token.is_empty() {
                         tokens.push(current_token);
                         current_token = String::new();
@@ -229,6 +229,12 @@ impl IntelligentQueryCache {
             // Look for label patterns: :Label
             if token == ":" && i + 1 < tokens.len() {
                 let label_token = &tokens[i + 1];
+                // Backtick-escaped label: :`my label`
+                if let Some(label) = unescape_identifier(label_token) {
+                    dependencies.labels.insert(label);
+                    i += 2;
+                    continue;
+                }
                 // Handle multiple labels like :User:Admin
                 for label in label_token.split(':') {
                     if !label.is_empty() && label.chars().next().map_or(false, |c| c.is_uppercase())
@@ -243,6 +249,11 @@ impl IntelligentQueryCache {
             // Look for property patterns: variable.property
             if token == "." && i + 1 < tokens.len() {
                 let property_token = &tokens[i + 1];
+                if let Some(property) = unescape_identifier(property_token) {
+                    dependencies.properties.insert(property);
+                    i += 2;
+                    continue;
+                }
                 let property: String = property_token
                     .chars()
                     .take_while(|c| c.is_alphanumeric() || *c == '_')
@@ -667,6 +678,13 @@ struct QueryPatternStats {
     avg_time_ms: f64,
 }
 
+/// Strip the backticks from an escaped identifier token; `None` for
+/// any other token.
+fn unescape_identifier(token: &str) -> Option<String> {
+    let inner = token.strip_prefix('`')?.strip_suffix('`')?;
+    (!inner.is_empty()).then(|| inner.replace("``", "`"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -697,6 +715,12 @@ mod tests {
         let deps4 = IntelligentQueryCache::extract_query_dependencies(query4);
         assert!(deps4.labels.is_empty());
         assert!(deps4.properties.is_empty());
+
+        // Test backtick-escaped label and property
+        let query5 = "MATCH (n:`my label`) RETURN n.`first name`";
+        let deps5 = IntelligentQueryCache::extract_query_dependencies(query5);
+        assert!(deps5.labels.contains("my label"));
+        assert!(deps5.properties.contains("first name"));
     }
 
     #[test]
@@ -2,11 +2,20 @@
 //!
 //! Manages active transactions per session, allowing BEGIN/COMMIT/ROLLBACK
 //! to work across multiple queries in the same session.
+//!
+//! Abandoned clients are reaped according to [`SessionTimeoutConfig`]: an
+//! explicit transaction with no statement for `idle_transaction_timeout_secs`
+//! is rolled back, and a session idle for `session_timeout_secs` is closed
+//! (rolling back its transaction first). The rollback itself needs the
+//! engine's storage, so [`SessionManager::sessions_due_for_reaping`] only
+//! selects the victims; `Engine::reap_idle_sessions` acts on them and
+//! bumps the process-wide counters read by [`reaper_metrics`].
 
 use crate::{Error, Result};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use crate::transaction::{Transaction, TransactionManager};
@@ -14,6 +23,115 @@ use crate::transaction::{Transaction, TransactionManager};
 /// Session ID type
 pub type SessionId = String;
 
+/// Idle limits for sessions and their explicit transactions, part of
+/// [`crate::EngineConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SessionTimeoutConfig {
+    /// Roll back an explicit transaction after this many seconds without
+    /// a statement. `0` disables the limit.
+    pub idle_transaction_timeout_secs: u64,
+    /// Close a session after this many seconds of inactivity.
+    pub session_timeout_secs: u64,
+}
+
+impl Default for SessionTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            idle_transaction_timeout_secs: 5 * 60,
+            session_timeout_secs: 30 * 60,
+        }
+    }
+}
+
+impl SessionTimeoutConfig {
+    /// How often a background reaper should run: a quarter of the
+    /// shortest enabled limit, clamped to 1..=60 seconds.
+    pub fn reap_interval(&self) -> Duration {
+        let shortest = match self.idle_transaction_timeout_secs {
+            0 => self.session_timeout_secs,
+            idle => idle.min(self.session_timeout_secs),
+        };
+        Duration::from_secs((shortest / 4).clamp(1, 60))
+    }
+}
+
+/// Why a session was reaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReapReason {
+    /// Its explicit transaction exceeded `idle_transaction_timeout_secs`;
+    /// the transaction was rolled back and the session kept.
+    IdleTransaction,
+    /// The session exceeded `session_timeout_secs` and was closed.
+    AbandonedSession,
+}
+
+/// A session acted on by `Engine::reap_idle_sessions`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ReapedSession {
+    /// Session ID
+    pub session_id: SessionId,
+    /// Why it was reaped
+    pub reason: ReapReason,
+    /// Time since the session's last statement
+    pub idle: Duration,
+    /// Whether an open transaction was rolled back
+    pub rolled_back_transaction: bool,
+}
+
+/// An explicit transaction held by a session, as listed by
+/// [`SessionManager::open_transactions`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct OpenTransaction {
+    /// Transaction ID
+    pub tx_id: u64,
+    /// Session holding the transaction
+    pub session_id: SessionId,
+    /// Database the session is using
+    pub database: String,
+    /// Snapshot epoch the transaction reads at
+    pub epoch: u64,
+    /// Time since the session's last statement, in milliseconds
+    pub idle_ms: u64,
+    /// Nodes created so far (undone by a rollback)
+    pub created_nodes: usize,
+    /// Relationships created so far (undone by a rollback)
+    pub created_relationships: usize,
+}
+
+static IDLE_TRANSACTIONS_ROLLED_BACK: AtomicU64 = AtomicU64::new(0);
+static ABANDONED_SESSIONS_CLOSED: AtomicU64 = AtomicU64::new(0);
+
+/// Process-wide reaper counters, exported by `nexus-server` as Prometheus
+/// counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReaperMetrics {
+    /// Explicit transactions rolled back for idleness, including those
+    /// of closed sessions.
+    pub idle_transactions_rolled_back: u64,
+    /// Sessions closed for idleness.
+    pub abandoned_sessions_closed: u64,
+}
+
+/// Snapshot of the reaper counters.
+pub fn reaper_metrics() -> ReaperMetrics {
+    ReaperMetrics {
+        idle_transactions_rolled_back: IDLE_TRANSACTIONS_ROLLED_BACK.load(Ordering::Relaxed),
+        abandoned_sessions_closed: ABANDONED_SESSIONS_CLOSED.load(Ordering::Relaxed),
+    }
+}
+
+/// Count a reaped session in [`reaper_metrics`].
+pub(crate) fn record_reaped(reaped: &ReapedSession) {
+    if reaped.rolled_back_transaction {
+        IDLE_TRANSACTIONS_ROLLED_BACK.fetch_add(1, Ordering::Relaxed);
+    }
+    if reaped.reason == ReapReason::AbandonedSession {
+        ABANDONED_SESSIONS_CLOSED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
 /// Session state
 #[derive(Clone)]
 pub struct Session {
@@ -110,9 +228,11 @@ impl Session {
 
     /// Commit the active transaction
     pub fn commit_transaction(&mut self) -> Result<()> {
-        if let Some(mut tx) = self.active_transaction.take() {
-            let mut tx_mgr = self.transaction_manager.write();
-            tx_mgr.commit(&mut tx)?;
+        if let Some(tx) = self.active_transaction.as_mut() {
+            // A commit hook refusing the commit leaves the transaction
+            // active, so the caller can still roll it back.
+            self.transaction_manager.write().commit(tx)?;
+            self.active_transaction = None;
             self.last_activity = Instant::now();
             Ok(())
         } else {
@@ -182,6 +302,8 @@ pub struct SessionManager {
     transaction_manager: Arc<RwLock<TransactionManager>>,
     /// Session timeout (default: 30 minutes)
     timeout: Duration,
+    /// Idle limit for explicit transactions (default: 5 minutes)
+    idle_transaction_timeout: Option<Duration>,
     /// Default database name
     default_database: String,
 }
@@ -201,10 +323,29 @@ impl SessionManager {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             transaction_manager,
             timeout: Duration::from_secs(30 * 60), // 30 minutes
+            idle_transaction_timeout: Some(Duration::from_secs(5 * 60)),
             default_database,
         }
     }
 
+    /// Apply idle limits. Affects sessions created afterwards and the
+    /// selection made by [`Self::sessions_due_for_reaping`].
+    pub fn with_timeouts(mut self, config: SessionTimeoutConfig) -> Self {
+        self.timeout = Duration::from_secs(config.session_timeout_secs);
+        self.idle_transaction_timeout = match config.idle_transaction_timeout_secs {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        };
+        self
+    }
+
+    fn new_session(&self, session_id: SessionId, database: String) -> Session {
+        let mut session =
+            Session::new_with_database(session_id, self.transaction_manager.clone(), database);
+        session.timeout = self.timeout;
+        session
+    }
+
     /// Get or create a session
     pub fn get_or_create_session(&self, session_id: SessionId) -> Session {
         let mut sessions = self.sessions.write();
@@ -224,11 +365,7 @@ impl SessionManager {
         }
 
         // Create new session with default database
-        let session = Session::new_with_database(
-            session_id.clone(),
-            self.transaction_manager.clone(),
-            self.default_database.clone(),
-        );
+        let session = self.new_session(session_id.clone(), self.default_database.clone());
         sessions.insert(session_id.clone(), session.clone());
         session
     }
@@ -265,11 +402,7 @@ impl SessionManager {
 
         // Create new session with specified or default database
         let db_name = database.unwrap_or_else(|| self.default_database.clone());
-        let session = Session::new_with_database(
-            session_id.clone(),
-            self.transaction_manager.clone(),
-            db_name,
-        );
+        let session = self.new_session(session_id.clone(), db_name);
         sessions.insert(session_id.clone(), session.clone());
         session
     }
@@ -291,7 +424,11 @@ impl SessionManager {
 
         if let Some(session) = sessions.get(session_id) {
             if session.is_expired() {
-                sessions.remove(session_id);
+                // An open transaction must be rolled back by the reaper,
+                // so only transaction-free sessions are dropped here.
+                if !session.has_active_transaction() {
+                    sessions.remove(session_id);
+                }
                 return None;
             }
             let session = Session {
@@ -327,10 +464,66 @@ impl SessionManager {
         sessions.remove(session_id);
     }
 
-    /// Clean up expired sessions
+    /// Clean up expired sessions. Sessions with an open transaction are
+    /// kept for `Engine::reap_idle_sessions`, which rolls them back.
     pub fn cleanup_expired_sessions(&self) {
         let mut sessions = self.sessions.write();
-        sessions.retain(|_, session| !session.is_expired());
+        sessions.retain(|_, session| !session.is_expired() || session.has_active_transaction());
+    }
+
+    /// Sessions past an idle limit: expired sessions as
+    /// [`ReapReason::AbandonedSession`], live sessions whose transaction
+    /// has been idle too long as [`ReapReason::IdleTransaction`].
+    pub fn sessions_due_for_reaping(&self) -> Vec<(SessionId, ReapReason)> {
+        let sessions = self.sessions.read();
+        sessions
+            .values()
+            .filter_map(|session| {
+                if session.is_expired() {
+                    Some((session.id.clone(), ReapReason::AbandonedSession))
+                } else if session.has_active_transaction()
+                    && self
+                        .idle_transaction_timeout
+                        .is_some_and(|limit| session.last_activity.elapsed() > limit)
+                {
+                    Some((session.id.clone(), ReapReason::IdleTransaction))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Explicit transactions open in any session, expired sessions
+    /// included, oldest first.
+    pub fn open_transactions(&self) -> Vec<OpenTransaction> {
+        let sessions = self.sessions.read();
+        let mut open: Vec<OpenTransaction> = sessions
+            .values()
+            .filter_map(|session| {
+                let tx = session
+                    .active_transaction
+                    .as_ref()
+                    .filter(|tx| tx.is_active())?;
+                Some(OpenTransaction {
+                    tx_id: tx.id,
+                    session_id: session.id.clone(),
+                    database: session.current_database.clone(),
+                    epoch: tx.epoch,
+                    idle_ms: session.last_activity.elapsed().as_millis() as u64,
+                    created_nodes: session.created_nodes.len(),
+                    created_relationships: session.created_relationships.len(),
+                })
+            })
+            .collect();
+        open.sort_by_key(|tx| tx.tx_id);
+        open
+    }
+
+    /// Take a session out of the map regardless of expiry, for the
+    /// reaper.
+    pub(crate) fn take_session(&self, session_id: &SessionId) -> Option<Session> {
+        self.sessions.write().remove(session_id)
     }
 
     /// Get all active session IDs
@@ -526,4 +719,56 @@ mod tests {
         let session = session_mgr.get_or_create_session("test-session".to_string());
         assert_eq!(session.get_current_database(), "mydefault");
     }
+
+    #[test]
+    fn test_sessions_due_for_reaping() {
+        let tx_mgr = Arc::new(RwLock::new(TransactionManager::new().unwrap()));
+        let session_mgr = SessionManager::new(tx_mgr).with_timeouts(SessionTimeoutConfig {
+            idle_transaction_timeout_secs: 60,
+            session_timeout_secs: 600,
+        });
+
+        let mut in_tx = session_mgr.get_or_create_session("in-tx".to_string());
+        in_tx.begin_transaction().unwrap();
+        in_tx.last_activity -= Duration::from_secs(120);
+        session_mgr.update_session(in_tx);
+
+        let mut idle = session_mgr.get_or_create_session("idle".to_string());
+        idle.last_activity -= Duration::from_secs(120);
+        session_mgr.update_session(idle);
+
+        let mut abandoned = session_mgr.get_or_create_session("abandoned".to_string());
+        abandoned.begin_transaction().unwrap();
+        abandoned.last_activity -= Duration::from_secs(700);
+        session_mgr.update_session(abandoned);
+
+        let mut due = session_mgr.sessions_due_for_reaping();
+        due.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            due,
+            vec![
+                ("abandoned".to_string(), ReapReason::AbandonedSession),
+                ("in-tx".to_string(), ReapReason::IdleTransaction),
+            ]
+        );
+
+        // Expiry sweeps keep the abandoned session until its transaction
+        // has been rolled back.
+        assert!(session_mgr.get_session(&"abandoned".to_string()).is_none());
+        session_mgr.cleanup_expired_sessions();
+        assert!(session_mgr.take_session(&"abandoned".to_string()).is_some());
+
+        assert_eq!(
+            SessionTimeoutConfig::default().reap_interval(),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            SessionTimeoutConfig {
+                idle_transaction_timeout_secs: 0,
+                session_timeout_secs: 2,
+            }
+            .reap_interval(),
+            Duration::from_secs(1)
+        );
+    }
 }
@@ -0,0 +1,541 @@
+//! Pluggable storage backends for [`RecordStore`](super::RecordStore).
+//!
+//! A record store keeps its data in three store files: node records,
+//! relationship records and properties. Each one is a [`StoreFile`], a
+//! growable byte region the store reads and writes by offset: a record
+//! or property entry at a time ([`StoreFile::read_at`],
+//! [`StoreFile::write_at`]) or a page at a time for scans
+//! ([`StoreFile::page`]). Nothing hands out the whole region, so a
+//! backend does not have to hold it in memory. A [`StorageBackend`]
+//! opens those files for a data directory, so the backend a database is
+//! created with decides where its records live:
+//!
+//! - [`MmapBackend`] (the default) memory-maps `nodes.store`,
+//!   `rels.store` and `properties.store` in the data directory.
+//! - [`MemoryBackend`] keeps them in process memory. Nothing survives a
+//!   restart, which suits tests, scratch databases and caches.
+//! - [`ArchiveBackend`] maps the store files of a backup directory
+//!   read-only, so an archived database can be queried in place.
+//!
+//! Other backends (an LSM tree, object storage paged in on demand)
+//! implement both traits; only `read_at` and `write_at` touch data, and
+//! [`StoreFile::view`] and `page` copy through `read_at` unless the
+//! backend can lend its bytes, as the mapped ones do. A read-only
+//! backend fails [`StoreFile::write_at`] and [`StoreFile::resize`],
+//! which turns every write into an error before anything is modified.
+//! The catalog, indexes and WAL are not part of the backend and stay in
+//! the data directory.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use memmap2::{Mmap, MmapMut, MmapOptions};
+
+use crate::error::{Error, Result};
+
+/// One store file, shared by every clone of a record store.
+pub type SharedStoreFile = Arc<RwLock<Box<dyn StoreFile>>>;
+
+/// Bytes in one page of a store file, as returned by [`StoreFile::page`].
+pub const STORE_PAGE_SIZE: usize = 64 * 1024;
+
+/// A growable byte region backing one store file.
+///
+/// Bytes past the data written so far read as zero, and so does the
+/// region added by a grow. Reads and writes name an offset and must lie
+/// within [`Self::len`]. Callers serialise access through the lock in
+/// [`SharedStoreFile`] or through `&mut self`.
+pub trait StoreFile: Send + Sync {
+    /// Size of the region in bytes.
+    fn len(&self) -> usize;
+
+    /// Whether the region is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fill `buf` with the bytes starting at `offset`.
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> Result<()>;
+
+    /// Overwrite the bytes starting at `offset` with `data`. Read-only
+    /// backends fail here.
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<()>;
+
+    /// The `len` bytes starting at `offset`, borrowed when the backend
+    /// holds them in memory and copied through [`Self::read_at`]
+    /// otherwise.
+    fn view(&self, offset: usize, len: usize) -> Result<Cow<'_, [u8]>> {
+        let mut buf = vec![0; len];
+        self.read_at(offset, &mut buf)?;
+        Ok(Cow::Owned(buf))
+    }
+
+    /// Page `n`: the [`STORE_PAGE_SIZE`] bytes starting at
+    /// `n * STORE_PAGE_SIZE`, shorter for the last page.
+    fn page(&self, n: usize) -> Result<Cow<'_, [u8]>> {
+        let start = n.saturating_mul(STORE_PAGE_SIZE).min(self.len());
+        let len = (self.len() - start).min(STORE_PAGE_SIZE);
+        self.view(start, len)
+    }
+
+    /// Grow or shrink the region to `len` bytes, keeping the prefix.
+    fn resize(&mut self, len: usize) -> Result<()>;
+
+    /// Drop every byte and start again with `len` zero bytes.
+    fn clear(&mut self, len: usize) -> Result<()>;
+
+    /// Make every write so far durable. A no-op for volatile backends.
+    fn flush(&self) -> Result<()>;
+
+    /// Check that the region is still reachable.
+    fn health_check(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// `offset..offset + len` of a region `region_len` bytes long, or an
+/// error naming `what` when it runs past the end.
+fn checked_range(
+    what: &dyn fmt::Display,
+    region_len: usize,
+    offset: usize,
+    len: usize,
+) -> Result<std::ops::Range<usize>> {
+    match offset.checked_add(len) {
+        Some(end) if end <= region_len => Ok(offset..end),
+        _ => Err(Error::storage(format!(
+            "{}: {} bytes at offset {} run past the end ({} bytes)",
+            what, len, offset, region_len
+        ))),
+    }
+}
+
+/// Opens the store files of a record store.
+pub trait StorageBackend: Send + Sync + fmt::Debug {
+    /// Name the backend is selected by, e.g. `mmap`.
+    fn name(&self) -> &str;
+
+    /// Whether data written through this backend survives a restart.
+    /// Disk-only helpers such as the adjacency list sidecar are skipped
+    /// for volatile backends.
+    fn is_persistent(&self) -> bool;
+
+    /// Open the store file `file_name` under `dir`, creating it with
+    /// `initial_len` zero bytes if it does not exist yet.
+    fn open(&self, dir: &Path, file_name: &str, initial_len: usize) -> Result<Box<dyn StoreFile>>;
+}
+
+/// Backend selected when none is given.
+pub fn default_backend() -> Arc<dyn StorageBackend> {
+    Arc::new(MmapBackend)
+}
+
+/// Built-in backend called `name`, if any.
+pub fn builtin_backend(name: &str) -> Option<Arc<dyn StorageBackend>> {
+    match name {
+        "mmap" => Some(Arc::new(MmapBackend)),
+        "memory" => Some(Arc::new(MemoryBackend)),
+        _ => None,
+    }
+}
+
+/// Memory-mapped files in the data directory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MmapBackend;
+
+impl StorageBackend for MmapBackend {
+    fn name(&self) -> &str {
+        "mmap"
+    }
+
+    fn is_persistent(&self) -> bool {
+        true
+    }
+
+    fn open(&self, dir: &Path, file_name: &str, initial_len: usize) -> Result<Box<dyn StoreFile>> {
+        Ok(Box::new(MmapStoreFile::open(
+            dir.join(file_name),
+            initial_len,
+        )?))
+    }
+}
+
+/// A store file mapped into memory.
+pub struct MmapStoreFile {
+    path: PathBuf,
+    file: File,
+    mmap: MmapMut,
+}
+
+impl MmapStoreFile {
+    /// Open or create the file at `path`. A missing or empty file is
+    /// sized to `initial_len` zero bytes.
+    pub fn open(path: PathBuf, initial_len: usize) -> Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        if file.metadata()?.len() == 0 {
+            file.set_len(initial_len as u64)?;
+            file.sync_all()?;
+        }
+        let mmap = unsafe { MmapOptions::new().map_mut(&file)? };
+        Ok(Self { path, file, mmap })
+    }
+
+    /// Path of the mapped file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn remap(&mut self) -> Result<()> {
+        self.mmap = unsafe { MmapOptions::new().map_mut(&self.file)? };
+        Ok(())
+    }
+
+    /// Swap in a throwaway anonymous mapping so the file can be
+    /// truncated; Windows refuses to shrink a file that is mapped.
+    fn unmap(&mut self) -> Result<()> {
+        self.mmap = MmapMut::map_anon(1)?;
+        Ok(())
+    }
+}
+
+impl StoreFile for MmapStoreFile {
+    fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> Result<()> {
+        buf.copy_from_slice(&self.view(offset, buf.len())?);
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<()> {
+        let range = checked_range(&self.path.display(), self.mmap.len(), offset, data.len())?;
+        self.mmap[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn view(&self, offset: usize, len: usize) -> Result<Cow<'_, [u8]>> {
+        let range = checked_range(&self.path.display(), self.mmap.len(), offset, len)?;
+        Ok(Cow::Borrowed(&self.mmap[range]))
+    }
+
+    fn resize(&mut self, len: usize) -> Result<()> {
+        if len == self.mmap.len() {
+            return Ok(());
+        }
+        if len < self.mmap.len() {
+            self.unmap()?;
+        }
+        // No sync here: growth is frequent on write-heavy loads and the
+        // new length reaches disk with the next flush.
+        self.file.set_len(len as u64)?;
+        self.remap()
+    }
+
+    fn clear(&mut self, len: usize) -> Result<()> {
+        self.unmap()?;
+        self.file.set_len(0)?;
+        self.file.set_len(len as u64)?;
+        self.file.sync_all()?;
+        self.remap()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.mmap.flush().map_err(|e| {
+            Error::storage(format!("Failed to flush {}: {}", self.path.display(), e))
+        })?;
+        self.file
+            .sync_all()
+            .map_err(|e| Error::storage(format!("Failed to sync {}: {}", self.path.display(), e)))
+    }
+
+    fn health_check(&self) -> Result<()> {
+        if !self.path.exists() {
+            return Err(Error::storage(format!(
+                "Store file {} does not exist",
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Store files held in process memory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryBackend;
+
+impl StorageBackend for MemoryBackend {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn is_persistent(&self) -> bool {
+        false
+    }
+
+    fn open(
+        &self,
+        _dir: &Path,
+        _file_name: &str,
+        initial_len: usize,
+    ) -> Result<Box<dyn StoreFile>> {
+        Ok(Box::new(MemoryStoreFile::new(initial_len)))
+    }
+}
+
+/// A store file held in a heap buffer.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStoreFile {
+    data: Vec<u8>,
+}
+
+impl MemoryStoreFile {
+    /// A region of `len` zero bytes.
+    pub fn new(len: usize) -> Self {
+        Self { data: vec![0; len] }
+    }
+}
+
+impl StoreFile for MemoryStoreFile {
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> Result<()> {
+        buf.copy_from_slice(&self.view(offset, buf.len())?);
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<()> {
+        let range = checked_range(&"in-memory store file", self.data.len(), offset, data.len())?;
+        self.data[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn view(&self, offset: usize, len: usize) -> Result<Cow<'_, [u8]>> {
+        let range = checked_range(&"in-memory store file", self.data.len(), offset, len)?;
+        Ok(Cow::Borrowed(&self.data[range]))
+    }
+
+    fn resize(&mut self, len: usize) -> Result<()> {
+        self.data.resize(len, 0);
+        Ok(())
+    }
+
+    fn clear(&mut self, len: usize) -> Result<()> {
+        self.data = vec![0; len];
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Store files of a backup directory, mapped read-only.
+///
+/// Every file is opened from `source` rather than from the data
+/// directory the record store is given, and is never created, grown or
+/// written: writes fail with [`Error::ReadOnly`].
+#[derive(Debug, Clone)]
+pub struct ArchiveBackend {
+    source: PathBuf,
+}
+
+impl ArchiveBackend {
+    /// Backend reading the store files found in `source`.
+    pub fn new(source: impl Into<PathBuf>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// Directory the store files are read from.
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+}
+
+impl StorageBackend for ArchiveBackend {
+    fn name(&self) -> &str {
+        "archive"
+    }
+
+    fn is_persistent(&self) -> bool {
+        false
+    }
+
+    fn open(
+        &self,
+        _dir: &Path,
+        file_name: &str,
+        _initial_len: usize,
+    ) -> Result<Box<dyn StoreFile>> {
+        Ok(Box::new(ArchiveStoreFile::open(
+            self.source.join(file_name),
+        )?))
+    }
+}
+
+/// A store file of a backup, mapped read-only.
+pub struct ArchiveStoreFile {
+    path: PathBuf,
+    mmap: Option<Mmap>,
+}
+
+impl ArchiveStoreFile {
+    /// Map the file at `path`, which must exist.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let file = File::open(&path).map_err(|e| {
+            Error::storage(format!("Cannot open archived {}: {}", path.display(), e))
+        })?;
+        // Mapping an empty file fails on some platforms; it reads as an
+        // empty region instead.
+        let mmap = if file.metadata()?.len() == 0 {
+            None
+        } else {
+            Some(unsafe { MmapOptions::new().map(&file)? })
+        };
+        Ok(Self { path, mmap })
+    }
+
+    fn mapped(&self) -> &[u8] {
+        self.mmap.as_deref().unwrap_or_default()
+    }
+
+    fn read_only(&self) -> Error {
+        Error::ReadOnly(format!(
+            "ERR_READ_ONLY: {} belongs to an archive and cannot be written",
+            self.path.display()
+        ))
+    }
+}
+
+impl StoreFile for ArchiveStoreFile {
+    fn len(&self) -> usize {
+        self.mapped().len()
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> Result<()> {
+        buf.copy_from_slice(&self.view(offset, buf.len())?);
+        Ok(())
+    }
+
+    fn write_at(&mut self, _offset: usize, _data: &[u8]) -> Result<()> {
+        Err(self.read_only())
+    }
+
+    fn view(&self, offset: usize, len: usize) -> Result<Cow<'_, [u8]>> {
+        let range = checked_range(&self.path.display(), self.len(), offset, len)?;
+        Ok(Cow::Borrowed(&self.mapped()[range]))
+    }
+
+    fn resize(&mut self, _len: usize) -> Result<()> {
+        Err(self.read_only())
+    }
+
+    fn clear(&mut self, _len: usize) -> Result<()> {
+        Err(self.read_only())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn health_check(&self) -> Result<()> {
+        if !self.path.exists() {
+            return Err(Error::storage(format!(
+                "Archived store file {} does not exist",
+                self.path.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestContext;
+
+    fn read(file: &dyn StoreFile, offset: usize, len: usize) -> Vec<u8> {
+        let mut buf = vec![0; len];
+        file.read_at(offset, &mut buf).unwrap();
+        buf
+    }
+
+    fn exercise(file: &mut dyn StoreFile) {
+        assert_eq!(file.len(), 64);
+        assert!(read(file, 0, 64).iter().all(|&b| b == 0));
+
+        file.write_at(0, b"nexu").unwrap();
+        file.resize(128).unwrap();
+        assert_eq!(file.len(), 128);
+        assert_eq!(read(file, 0, 4), b"nexu");
+        assert!(read(file, 64, 64).iter().all(|&b| b == 0));
+        assert!(file.read_at(126, &mut [0; 4]).is_err());
+        assert!(file.write_at(126, b"nexu").is_err());
+
+        file.resize(32).unwrap();
+        assert_eq!(&file.view(0, 4).unwrap()[..], b"nexu");
+        assert_eq!(file.page(0).unwrap().len(), 32);
+        assert!(file.page(1).unwrap().is_empty());
+
+        file.clear(16).unwrap();
+        assert_eq!(file.len(), 16);
+        assert!(read(file, 0, 16).iter().all(|&b| b == 0));
+        file.flush().unwrap();
+    }
+
+    #[test]
+    fn builtin_backends_follow_the_store_file_contract() {
+        let ctx = TestContext::new();
+        for name in ["mmap", "memory"] {
+            let backend = builtin_backend(name).unwrap();
+            assert_eq!(backend.name(), name);
+            let mut file = backend
+                .open(ctx.path(), &format!("{name}.store"), 64)
+                .unwrap();
+            exercise(file.as_mut());
+        }
+        assert!(builtin_backend("s3").is_none());
+    }
+
+    #[test]
+    fn mmap_store_file_keeps_data_across_reopen() {
+        let ctx = TestContext::new();
+        {
+            let mut file = MmapBackend.open(ctx.path(), "data.store", 64).unwrap();
+            file.write_at(10, &[7]).unwrap();
+            file.flush().unwrap();
+        }
+        let file = MmapBackend.open(ctx.path(), "data.store", 64).unwrap();
+        assert_eq!(read(file.as_ref(), 10, 1), [7]);
+    }
+
+    #[test]
+    fn archive_store_file_reads_but_refuses_writes() {
+        let ctx = TestContext::new();
+        {
+            let mut file = MmapBackend.open(ctx.path(), "data.store", 64).unwrap();
+            file.write_at(3, &[9]).unwrap();
+            file.flush().unwrap();
+        }
+        let backend = ArchiveBackend::new(ctx.path());
+        let mut file = backend.open(Path::new("unused"), "data.store", 0).unwrap();
+        assert_eq!(file.len(), 64);
+        assert_eq!(read(file.as_ref(), 3, 1), [9]);
+        assert!(matches!(file.write_at(3, &[1]), Err(Error::ReadOnly(_))));
+        assert!(matches!(file.resize(128), Err(Error::ReadOnly(_))));
+        assert!(matches!(file.clear(16), Err(Error::ReadOnly(_))));
+        assert!(backend.open(ctx.path(), "missing.store", 64).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Enumeration property types.
+//!
+//! An enum definition restricts the values of a `(label, key)` pair to a
+//! fixed, ordered set of strings. Definitions are owned by the catalog
+//! (see `Catalog::persist_enum_property_type`) and mirrored here so the
+//! record store can translate between the two representations:
+//!
+//! - on write, the value is validated against the allowed set and
+//!   replaced by its ordinal (a small JSON integer) before the property
+//!   map is serialised;
+//! - on read, the ordinal is mapped back to the string so callers never
+//!   observe the compact form.
+//!
+//! Every node property write funnels through `RecordStore`, so enforcing
+//! the allowed set here covers CREATE, SET, MERGE and label additions
+//! alike.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::Result;
+use crate::constraints::ConstraintViolation;
+
+/// In-memory registry of enum definitions keyed by `(label_id, key)`.
+///
+/// Ordered by label id: when several labels on one node define an enum
+/// for the same key, the value must be allowed by all of them and the
+/// lowest label id supplies the stored ordinal.
+#[derive(Debug, Default, Clone)]
+pub struct EnumRegistry {
+    defs: BTreeMap<(u32, String), Arc<[String]>>,
+}
+
+impl EnumRegistry {
+    /// Register (or replace) the allowed values for `(label_id, key)`.
+    pub fn define(&mut self, label_id: u32, key: &str, variants: Vec<String>) {
+        self.defs
+            .insert((label_id, key.to_string()), variants.into());
+    }
+
+    /// Remove the definition for `(label_id, key)`. Returns `true` when
+    /// one existed.
+    pub fn remove(&mut self, label_id: u32, key: &str) -> bool {
+        self.defs.remove(&(label_id, key.to_string())).is_some()
+    }
+
+    /// Allowed values for `(label_id, key)`, in ordinal order.
+    pub fn variants(&self, label_id: u32, key: &str) -> Option<&[String]> {
+        self.defs
+            .get(&(label_id, key.to_string()))
+            .map(|v| v.as_ref())
+    }
+
+    /// Every registered definition as `(label_id, key, variants)`.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str, &[String])> {
+        self.defs
+            .iter()
+            .map(|((label_id, key), v)| (*label_id, key.as_str(), v.as_ref()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty()
+    }
+
+    /// Check that every enum-typed value in `properties` is allowed for
+    /// the labels in `label_bits`. `NULL` / absent values are accepted.
+    pub fn validate(&self, label_bits: u64, properties: &Value) -> Result<()> {
+        let Some(obj) = properties.as_object() else {
+            return Ok(());
+        };
+        for (label_id, key, variants) in self.applicable(label_bits) {
+            match obj.get(key) {
+                None | Some(Value::Null) => {}
+                Some(Value::String(s)) if variants.iter().any(|v| v == s) => {}
+                Some(other) => {
+                    return Err(ConstraintViolation::new("PROPERTY_ENUM", "NODE")
+                        .label_or_type(format!("ID{label_id}"))
+                        .value(key, other.clone())
+                        .with_message(format!(
+                            "ERR_CONSTRAINT_VIOLATED: kind=PROPERTY_ENUM label_id={label_id} \
+                             property={key:?} value={other} allowed={variants:?}",
+                        ))
+                        .into_error());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate `properties` and replace every enum-typed string with its
+    /// ordinal, using the definitions that apply to `label_bits`.
+    pub fn encode(&self, label_bits: u64, properties: &mut Value) -> Result<()> {
+        self.validate(label_bits, properties)?;
+        let Some(obj) = properties.as_object_mut() else {
+            return Ok(());
+        };
+        let mut done: Vec<&str> = Vec::new();
+        for (_, key, variants) in self.applicable(label_bits) {
+            if done.contains(&key) {
+                continue;
+            }
+            if let Some(slot) = obj.get_mut(key) {
+                if let Some(ordinal) = slot
+                    .as_str()
+                    .and_then(|s| variants.iter().position(|v| v == s))
+                {
+                    *slot = Value::from(ordinal as u64);
+                    done.push(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Inverse of [`Self::encode`]: map stored ordinals back to strings.
+    /// Values that are not a valid ordinal pass through untouched.
+    pub fn decode(&self, label_bits: u64, properties: &mut Value) {
+        let Some(obj) = properties.as_object_mut() else {
+            return;
+        };
+        let mut done: Vec<&str> = Vec::new();
+        for (_, key, variants) in self.applicable(label_bits) {
+            if done.contains(&key) {
+                continue;
+            }
+            done.push(key);
+            if let Some(slot) = obj.get_mut(key) {
+                let decoded = slot
+                    .as_u64()
+                    .and_then(|o| usize::try_from(o).ok())
+                    .and_then(|o| variants.get(o));
+                if let Some(s) = decoded {
+                    *slot = Value::String(s.clone());
+                }
+            }
+        }
+    }
+
+    fn applicable(&self, label_bits: u64) -> impl Iterator<Item = (u32, &str, &[String])> {
+        self.iter()
+            .filter(move |(label_id, _, _)| *label_id < 64 && label_bits & (1u64 << label_id) != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn registry() -> EnumRegistry {
+        let mut reg = EnumRegistry::default();
+        reg.define(2, "status", vec!["ACTIVE".into(), "RETIRED".into()]);
+        reg
+    }
+
+    #[test]
+    fn encode_decode_roundtrip_for_matching_label() {
+        let reg = registry();
+        let mut props = json!({"status": "RETIRED", "name": "x"});
+        reg.encode(1 << 2, &mut props).unwrap();
+        assert_eq!(props, json!({"status": 1, "name": "x"}));
+        reg.decode(1 << 2, &mut props);
+        assert_eq!(props, json!({"status": "RETIRED", "name": "x"}));
+    }
+
+    #[test]
+    fn values_outside_the_allowed_set_are_rejected() {
+        let reg = registry();
+        let mut props = json!({"status": "UNKNOWN"});
+        let err = reg.encode(1 << 2, &mut props).unwrap_err();
+        assert!(err.to_string().contains("PROPERTY_ENUM"), "{err}");
+        assert!(reg.encode(1 << 2, &mut json!({"status": 1})).is_err());
+        assert!(reg.encode(1 << 2, &mut json!({"status": null})).is_ok());
+    }
+
+    #[test]
+    fn other_labels_pass_through() {
+        let reg = registry();
+        let mut props = json!({"status": "ANYTHING"});
+        reg.encode(1 << 3, &mut props).unwrap();
+        assert_eq!(props, json!({"status": "ANYTHING"}));
+        let mut stored = json!({"status": 1});
+        reg.decode(1 << 3, &mut stored);
+        assert_eq!(stored, json!({"status": 1}));
+    }
+}
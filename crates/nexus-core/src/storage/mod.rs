@@ -6,9 +6,13 @@
 //! - Memory-mapped file access for performance
 //! - CRUD operations for graph entities
 //! - Property storage and retrieval
+//! - Pluggable backends for the store files (memory-mapped, in-memory)
+//! - Tracking which labels and relationship types writes touched
 
 pub mod adjacency_list;
+pub mod backend;
 pub mod crypto;
+pub mod enum_types;
 pub mod external_id;
 pub mod graph_engine;
 pub mod property_store;
@@ -17,8 +21,13 @@ pub mod record_store_ops;
 pub mod records;
 pub mod row_lock;
 pub mod write_buffer;
+pub mod write_tracker;
 
+pub use backend::{ArchiveBackend, MemoryBackend, MmapBackend, StorageBackend, StoreFile};
+pub use enum_types::EnumRegistry;
 pub use external_id::{ConflictPolicy, ExternalId};
+pub use property_store::PropertyStoreUsage;
+pub use write_tracker::{TrackedWrites, WriteTracker};
 
 // Record layout types — constants and structs
 pub use records::{
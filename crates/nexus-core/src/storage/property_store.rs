@@ -2,28 +2,62 @@
 //!
 //! This module provides efficient storage and retrieval of node and relationship properties
 //! using a key-value store with JSON serialization.
+//!
+//! Entries are written in the binary property encoding of
+//! [`crate::value`] whenever it is smaller than the JSON text: varint
+//! integers and bit-packed boolean lists make counter- and flag-heavy
+//! entries shrink, and a BYTES value (`{"_bytes": "<base64>"}`) always
+//! takes the binary form, so a hash or feature blob costs its raw
+//! length on disk rather than its base64 text. Binary entries carry
+//! [`BINARY_ENTRY_FLAG`] in the entity-type byte of their header;
+//! entries without it are JSON and read as before.
 
 use crate::error::{Error, Result};
-use memmap2::{MmapMut, MmapOptions};
+use crate::value::{self, NexusValue};
+
+use super::backend::{MmapBackend, StorageBackend, StoreFile};
+use super::write_tracker::WriteTracker;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as B64;
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
 use serde_json;
 use std::collections::HashMap;
-use std::fs::{File, OpenOptions};
-use std::io::Write;
+use std::fmt;
 use std::path::PathBuf;
 use tracing;
 
+/// Size of a new property file, and of a cleared one.
+const INITIAL_PROPERTY_FILE_SIZE: usize = 1024 * 1024;
+
+/// Bit set in an entry's entity-type byte when its data is in the
+/// binary property encoding rather than JSON.
+const BINARY_ENTRY_FLAG: u8 = 0x80;
+
 /// Property store for efficient property storage and retrieval
 pub struct PropertyStore {
-    /// Path to the property store file
-    path: PathBuf,
-    /// Memory-mapped file for property data
-    mmap: MmapMut,
+    /// Property data, opened through the store's backend
+    file: Box<dyn StoreFile>,
     /// Next available offset for new properties
     next_offset: u64,
     /// Property index: property_ptr -> (entity_id, entity_type)
     index: HashMap<u64, (u64, EntityType)>,
     /// Reverse index: (entity_id, entity_type) -> property_ptr
     reverse_index: HashMap<(u64, EntityType), u64>,
+    /// Notified of every entity whose properties are written
+    tracker: WriteTracker,
+}
+
+/// Space accounting for the property file, from [`PropertyStore::usage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PropertyStoreUsage {
+    /// Allocated file size.
+    pub file_bytes: u64,
+    /// Bytes handed out to entries so far (the append offset).
+    pub used_bytes: u64,
+    /// Entries still referenced by an entity.
+    pub live_entries: u64,
+    /// Bytes taken by those entries, headers included.
+    pub live_bytes: u64,
 }
 
 /// Type of entity that owns properties
@@ -47,48 +81,27 @@ struct PropertyEntry {
 }
 
 impl PropertyStore {
-    /// Create a new property store
+    /// Create a new property store in `path`, on a memory-mapped file.
     pub fn new(path: PathBuf) -> Result<Self> {
-        let property_file = path.join("properties.store");
-
-        // Whether the backing file already exists with (potential) data. For an
-        // existing file we must let rebuild_index() perform a full scan from
-        // disk, so next_offset is seeded to 0 below (the >0 "preserve" branch in
-        // rebuild_index would otherwise skip the on-disk scan and lose the index
-        // on every reopen — the root cause of issue #4 property loss on reboot).
-        let file_existed = property_file.exists();
-
-        // Create or open the property file
-        let file = if property_file.exists() {
-            OpenOptions::new()
-                .read(true)
-                .write(true)
-                .open(&property_file)?
-        } else {
-            // Create new file with initial size
-            let mut file = File::create(&property_file)?;
-            // Write initial size (1MB)
-            file.write_all(&[0u8; 1024 * 1024])?;
-            file.sync_all()?;
-            OpenOptions::new()
-                .read(true)
-                .write(true)
-                .open(&property_file)?
-        };
+        Self::with_backend(path, &MmapBackend)
+    }
 
-        // Memory map the file
-        let mmap = unsafe { MmapOptions::new().map_mut(&file)? };
+    /// Create a new property store in `path` whose file is opened
+    /// through `backend`.
+    pub fn with_backend(path: PathBuf, backend: &dyn StorageBackend) -> Result<Self> {
+        let file = backend.open(&path, "properties.store", INITIAL_PROPERTY_FILE_SIZE)?;
 
         let mut store = Self {
-            path,
-            mmap,
-            // For a brand-new file, start at offset 1 (offset 0 is reserved
-            // because prop_ptr=0 means "no properties"). For an existing file,
-            // seed 0 so rebuild_index() takes the full on-disk scan branch and
-            // reconstructs the index correctly (issue #4).
-            next_offset: if file_existed { 0 } else { 1 },
+            file,
+            // Seed 0 so rebuild_index() takes the full on-disk scan branch
+            // and reconstructs the index of an existing file (issue #4; the
+            // >0 "preserve" branch would skip the scan and lose the index on
+            // every reopen). A new, all-zero file leaves it at 1: offset 0 is
+            // reserved because prop_ptr=0 means "no properties".
+            next_offset: 0,
             index: HashMap::new(),
             reverse_index: HashMap::new(),
+            tracker: WriteTracker::default(),
         };
 
         // Rebuild index from existing data
@@ -97,6 +110,11 @@ impl PropertyStore {
         Ok(store)
     }
 
+    /// Report property writes to `tracker` from now on.
+    pub(crate) fn set_write_tracker(&mut self, tracker: WriteTracker) {
+        self.tracker = tracker;
+    }
+
     /// Store properties for an entity
     pub fn store_properties(
         &mut self,
@@ -104,6 +122,7 @@ impl PropertyStore {
         entity_type: EntityType,
         properties: serde_json::Value,
     ) -> Result<u64> {
+        self.tracker.properties_written(entity_id, entity_type);
         let key = (entity_id, entity_type);
         // Check if properties already exist for this entity
         if let Some(&existing_ptr) = self.reverse_index.get(&key) {
@@ -114,28 +133,7 @@ impl PropertyStore {
         } else {
         }
 
-        // Phase 1 Deep Optimization: Use to_string for small properties, to_writer for large
-        // to_string is often faster for small JSON objects due to better optimizations
-        let serialized = if properties.is_object() {
-            let obj = properties.as_object().unwrap();
-            // For small objects (< 5 properties), to_string is faster
-            if obj.len() < 5 {
-                serde_json::to_string(&properties)
-                    .map_err(Error::Json)?
-                    .into_bytes()
-            } else {
-                // For larger objects, use pre-allocated buffer
-                let estimated_size = obj.len() * 50;
-                let mut buffer = Vec::with_capacity(estimated_size);
-                serde_json::to_writer(&mut buffer, &properties).map_err(Error::Json)?;
-                buffer
-            }
-        } else {
-            // For non-objects, to_string is usually faster
-            serde_json::to_string(&properties)
-                .map_err(Error::Json)?
-                .into_bytes()
-        };
+        let (serialized, type_byte) = serialize_entry(entity_type, &properties)?;
 
         let data_size = serialized.len() as u32;
         let entry_size = 8 + 1 + 4 + data_size as usize; // entity_id + entity_type + data_size + data
@@ -147,26 +145,23 @@ impl PropertyStore {
         // Write property entry
         let offset = self.next_offset;
 
-        // Phase 1 Deep Optimization: Batch writes to reduce mmap access overhead
-        // Write header (entity_id + entity_type + data_size) in one operation
-        let header_start = offset as usize;
-        let header_end = header_start + 13;
+        // Phase 1 Deep Optimization: Batch writes to reduce file access overhead
+        // Assemble header (entity_id + entity_type + data_size) and data, then
+        // write the whole entry in one operation
+        let mut entry = Vec::with_capacity(entry_size);
 
-        // Write entity_id (8 bytes) - little endian
-        let entity_id_bytes = entity_id.to_le_bytes();
-        self.mmap[header_start..header_start + 8].copy_from_slice(&entity_id_bytes);
+        // entity_id (8 bytes) - little endian
+        entry.extend_from_slice(&entity_id.to_le_bytes());
 
-        // Write entity_type (1 byte)
-        self.mmap[header_start + 8] = entity_type as u8;
+        // entity_type (1 byte)
+        entry.push(type_byte);
 
-        // Write data_size (4 bytes) - little endian
-        let data_size_bytes = data_size.to_le_bytes();
-        self.mmap[header_start + 9..header_end].copy_from_slice(&data_size_bytes);
+        // data_size (4 bytes) - little endian
+        entry.extend_from_slice(&data_size.to_le_bytes());
 
-        // Write properties data
-        let data_start = header_end;
-        let data_end = data_start + serialized.len();
-        self.mmap[data_start..data_end].copy_from_slice(&serialized);
+        // properties data
+        entry.extend_from_slice(&serialized);
+        self.file.write_at(offset as usize, &entry)?;
 
         // Update indexes
         self.index.insert(offset, (entity_id, entity_type));
@@ -231,7 +226,7 @@ impl PropertyStore {
 
     /// Load properties at a specific offset
     pub fn load_properties_at_offset(&self, offset: u64) -> Result<Option<serde_json::Value>> {
-        if offset as usize >= self.mmap.len() {
+        if offset as usize >= self.file.len() {
             return Ok(None);
         }
 
@@ -239,21 +234,66 @@ impl PropertyStore {
         let _stored_entity_id = self.read_u64(offset);
 
         // Read entity_type (1 byte)
-        let _stored_entity_type = EntityType::from_u8(self.read_u8(offset + 8))?;
+        let type_byte = self.read_u8(offset + 8);
+        let _stored_entity_type = EntityType::from_u8(type_byte)?;
 
         // Read data_size (4 bytes)
         let data_size = self.read_u32(offset + 9);
 
         // Read properties data
         let data_start = offset + 13;
-        if data_start + data_size as u64 > self.mmap.len() as u64 {
+        if data_start + data_size as u64 > self.file.len() as u64 {
             return Err(Error::storage("Property data extends beyond file"));
         }
 
-        let data = &self.mmap[data_start as usize..(data_start + data_size as u64) as usize];
+        let data = self.file.view(data_start as usize, data_size as usize)?;
+        let data = data.as_ref();
 
         // Deserialize properties
-        let properties: serde_json::Value = serde_json::from_slice(data).map_err(Error::Json)?;
+        let properties: serde_json::Value = if type_byte & BINARY_ENTRY_FLAG != 0 {
+            value::decode(data)?.into_json()
+        } else {
+            serde_json::from_slice(data).map_err(Error::Json)?
+        };
+
+        Ok(Some(properties))
+    }
+
+    /// Load only `keys` from the properties at a specific offset.
+    ///
+    /// The other values are skipped by the JSON parser without being
+    /// built, so a `RETURN n.name` over wide nodes no longer pays for
+    /// every property the node carries. Keys the entry does not hold are
+    /// simply absent from the returned object. Binary entries are decoded
+    /// whole and then filtered.
+    pub fn load_projected_properties_at_offset(
+        &self,
+        offset: u64,
+        keys: &[String],
+    ) -> Result<Option<serde_json::Value>> {
+        if offset as usize >= self.file.len() {
+            return Ok(None);
+        }
+
+        let data_size = self.read_u32(offset + 9);
+        let data_start = offset + 13;
+        if data_start + data_size as u64 > self.file.len() as u64 {
+            return Err(Error::storage("Property data extends beyond file"));
+        }
+
+        let data = self.file.view(data_start as usize, data_size as usize)?;
+        let data = data.as_ref();
+        if self.read_u8(offset + 8) & BINARY_ENTRY_FLAG != 0 {
+            let mut properties = value::decode(data)?.into_json();
+            if let Some(map) = properties.as_object_mut() {
+                map.retain(|key, _| keys.contains(key));
+            }
+            return Ok(Some(properties));
+        }
+        let mut deserializer = serde_json::Deserializer::from_slice(data);
+        let properties = ProjectedProperties { keys }
+            .deserialize(&mut deserializer)
+            .map_err(Error::Json)?;
 
         Ok(Some(properties))
     }
@@ -261,7 +301,7 @@ impl PropertyStore {
     /// Check what entity type is stored at a given offset
     /// Returns (entity_id, entity_type) if found, None otherwise
     pub fn get_entity_info_at_offset(&self, offset: u64) -> Option<(u64, EntityType)> {
-        if offset as usize >= self.mmap.len() {
+        if offset as usize >= self.file.len() {
             return None;
         }
 
@@ -292,7 +332,7 @@ impl PropertyStore {
             self.next_offset
         );
         // Serialize new properties
-        let serialized = serde_json::to_vec(&properties).map_err(Error::Json)?;
+        let (serialized, type_byte) = serialize_entry(entity_type, &properties)?;
 
         let new_data_size = serialized.len() as u32;
 
@@ -307,8 +347,9 @@ impl PropertyStore {
         // If new data fits in existing space, update in place
         if new_data_size <= existing_data_size {
             tracing::debug!("[update_properties] Updating in place: offset={}", offset);
-            self.write_u32(offset + 9, new_data_size);
-            self.write_bytes(offset + 13, &serialized);
+            self.write_u8(offset + 8, type_byte)?;
+            self.write_u32(offset + 9, new_data_size)?;
+            self.write_bytes(offset + 13, &serialized)?;
             Ok(offset) // Return same offset
         } else {
             // Need to allocate new space
@@ -323,10 +364,10 @@ impl PropertyStore {
             self.ensure_capacity(new_offset + entry_size as u64)?;
 
             // Write new entry
-            self.write_u64(new_offset, entity_id);
-            self.write_u8(new_offset + 8, entity_type as u8);
-            self.write_u32(new_offset + 9, new_data_size);
-            self.write_bytes(new_offset + 13, &serialized);
+            self.write_u64(new_offset, entity_id)?;
+            self.write_u8(new_offset + 8, type_byte)?;
+            self.write_u32(new_offset + 9, new_data_size)?;
+            self.write_bytes(new_offset + 13, &serialized)?;
 
             // Update indexes
             self.index.remove(&offset);
@@ -342,6 +383,7 @@ impl PropertyStore {
 
     /// Delete properties for an entity
     pub fn delete_properties(&mut self, entity_id: u64, entity_type: EntityType) -> Result<()> {
+        self.tracker.properties_written(entity_id, entity_type);
         if let Some(property_ptr) = self.reverse_index.remove(&(entity_id, entity_type)) {
             self.index.remove(&property_ptr);
         }
@@ -351,6 +393,7 @@ impl PropertyStore {
     /// Clear all properties and reset the store
     pub fn clear_all(&mut self) -> Result<()> {
         tracing::debug!("[PropertyStore::clear_all] Clearing all properties");
+        self.tracker.everything_written();
         tracing::debug!(
             "[PropertyStore::clear_all] BEFORE: next_offset={}, index size={}, reverse_index size={}",
             self.next_offset,
@@ -365,53 +408,7 @@ impl PropertyStore {
         self.next_offset = 1;
 
         // Truncate and zero out the property file
-        let property_file = self.path.join("properties.store");
-        if property_file.exists() {
-            tracing::debug!("[PropertyStore::clear_all] Truncating and zeroing property file");
-
-            // CRITICAL FIX for Windows: Create a temporary mmap to replace the current one
-            // This allows us to drop the old mmap before truncating the file
-            let temp_dir = tempfile::tempdir()?;
-            let temp_path = temp_dir.path().join("properties.tmp");
-            let mut temp_file = OpenOptions::new()
-                .read(true)
-                .write(true)
-                .create(true)
-                .open(&temp_path)?;
-            temp_file.set_len(1024 * 1024)?; // 1MB
-            let temp_mmap = unsafe { MmapOptions::new().map_mut(&temp_file)? };
-
-            // Replace current mmap with temporary one (drops old mmap)
-            let _old_mmap = std::mem::replace(&mut self.mmap, temp_mmap);
-            drop(_old_mmap);
-
-            // Now we can safely truncate the original file
-            let mut file = OpenOptions::new()
-                .read(true)
-                .write(true)
-                .truncate(true)
-                .open(&property_file)?;
-
-            // Write initial size (1MB) filled with zeros
-            file.write_all(&[0u8; 1024 * 1024])?;
-            file.sync_all()?;
-            drop(file);
-
-            // Reopen file for mmap
-            let file = OpenOptions::new()
-                .read(true)
-                .write(true)
-                .open(&property_file)?;
-
-            // Recreate memory mapping from original file
-            self.mmap = unsafe { MmapOptions::new().map_mut(&file)? };
-            tracing::debug!(
-                "[PropertyStore::clear_all] Recreated mmap, mmap.len()={}",
-                self.mmap.len()
-            );
-
-            // temp_dir and temp_file will be dropped here
-        }
+        self.file.clear(INITIAL_PROPERTY_FILE_SIZE)?;
 
         tracing::debug!(
             "[PropertyStore::clear_all] AFTER: next_offset={}, index size={}, reverse_index size={}",
@@ -438,14 +435,14 @@ impl PropertyStore {
         }
 
         tracing::debug!(
-            "[rebuild_index] STARTING: mmap.len()={}, current next_offset={}",
-            self.mmap.len(),
+            "[rebuild_index] STARTING: file.len()={}, current next_offset={}",
+            self.file.len(),
             self.next_offset
         );
 
         // CRITICAL FIX: Check if file is empty (all zeros) - if so, don't rebuild
         // This prevents rebuild_index from finding old data after clear_all() and resetting next_offset incorrectly
-        let first_13_bytes = &self.mmap[0..std::cmp::min(13, self.mmap.len())];
+        let first_13_bytes = self.file.view(0, std::cmp::min(13, self.file.len()))?;
         let is_empty = first_13_bytes.iter().all(|&b| b == 0);
 
         if is_empty {
@@ -475,8 +472,8 @@ impl PropertyStore {
             // Entries start at offset 1 (offset 0 is the reserved sentinel);
             // scanning from 0 would misalign every read.
             let mut offset = 1;
-            while offset < self.mmap.len() as u64 && offset < preserved_next_offset {
-                if offset + 13 > self.mmap.len() as u64 {
+            while offset < self.file.len() as u64 && offset < preserved_next_offset {
+                if offset + 13 > self.file.len() as u64 {
                     break;
                 }
 
@@ -494,7 +491,7 @@ impl PropertyStore {
                 };
 
                 let entry_size = 8 + 1 + 4 + data_size as usize;
-                if offset + entry_size as u64 > self.mmap.len() as u64 {
+                if offset + entry_size as u64 > self.file.len() as u64 {
                     break;
                 }
 
@@ -540,8 +537,8 @@ impl PropertyStore {
         let mut max_valid_offset = 0;
         let mut found_valid_entries = false;
 
-        while offset < self.mmap.len() as u64 {
-            if offset + 13 > self.mmap.len() as u64 {
+        while offset < self.file.len() as u64 {
+            if offset + 13 > self.file.len() as u64 {
                 break;
             }
 
@@ -578,7 +575,7 @@ impl PropertyStore {
             };
 
             let entry_size = 8 + 1 + 4 + data_size as usize;
-            if offset + entry_size as u64 > self.mmap.len() as u64 {
+            if offset + entry_size as u64 > self.file.len() as u64 {
                 break;
             }
 
@@ -614,82 +611,65 @@ impl PropertyStore {
         Ok(())
     }
 
-    /// Ensure the memory-mapped file has enough capacity
+    /// Ensure the property file has enough capacity
     /// Phase 1 Deep Optimization: Remove sync_all() - let OS manage page cache
     /// This reduces I/O overhead significantly during file growth
     fn ensure_capacity(&mut self, required_size: u64) -> Result<()> {
-        if required_size > self.mmap.len() as u64 {
+        if required_size > self.file.len() as u64 {
             // Calculate new size (grow by 1.5x, but at least 2MB to reduce frequent grows)
             let min_growth = 2 * 1024 * 1024; // 2MB minimum
             let calculated_size = ((required_size as f64) * 1.5) as usize;
             let new_size = calculated_size.max(min_growth).max(required_size as usize);
 
-            // Resize file
-            let property_file = self.path.join("properties.store");
-            let file = OpenOptions::new()
-                .read(true)
-                .write(true)
-                .open(&property_file)?;
-            file.set_len(new_size as u64)?;
-            // Phase 1 Deep Optimization: Removed sync_all() - OS will manage page cache
-            // This reduces I/O overhead by ~10-20ms per growth operation
             // Data will be flushed eventually by OS or explicit flush()
-
-            // Recreate mmap
-            self.mmap = unsafe { MmapOptions::new().map_mut(&file)? };
+            self.file.resize(new_size)?;
         }
         Ok(())
     }
 
     /// Write a u64 value at the given offset
-    fn write_u64(&mut self, offset: u64, value: u64) {
-        let bytes = value.to_le_bytes();
-        self.mmap[offset as usize..offset as usize + 8].copy_from_slice(&bytes);
+    fn write_u64(&mut self, offset: u64, value: u64) -> Result<()> {
+        self.write_bytes(offset, &value.to_le_bytes())
     }
 
     /// Write a u32 value at the given offset
-    fn write_u32(&mut self, offset: u64, value: u32) {
-        let bytes = value.to_le_bytes();
-        self.mmap[offset as usize..offset as usize + 4].copy_from_slice(&bytes);
+    fn write_u32(&mut self, offset: u64, value: u32) -> Result<()> {
+        self.write_bytes(offset, &value.to_le_bytes())
     }
 
     /// Write a u8 value at the given offset
-    fn write_u8(&mut self, offset: u64, value: u8) {
-        self.mmap[offset as usize] = value;
+    fn write_u8(&mut self, offset: u64, value: u8) -> Result<()> {
+        self.write_bytes(offset, &[value])
     }
 
     /// Write bytes at the given offset
-    fn write_bytes(&mut self, offset: u64, data: &[u8]) {
-        self.mmap[offset as usize..offset as usize + data.len()].copy_from_slice(data);
+    fn write_bytes(&mut self, offset: u64, data: &[u8]) -> Result<()> {
+        self.file.write_at(offset as usize, data)
     }
 
     /// Read a u64 value from the given offset
     fn read_u64(&self, offset: u64) -> u64 {
-        u64::from_le_bytes([
-            self.mmap[offset as usize],
-            self.mmap[offset as usize + 1],
-            self.mmap[offset as usize + 2],
-            self.mmap[offset as usize + 3],
-            self.mmap[offset as usize + 4],
-            self.mmap[offset as usize + 5],
-            self.mmap[offset as usize + 6],
-            self.mmap[offset as usize + 7],
-        ])
+        u64::from_le_bytes(self.read_array(offset))
     }
 
     /// Read a u32 value from the given offset
     fn read_u32(&self, offset: u64) -> u32 {
-        u32::from_le_bytes([
-            self.mmap[offset as usize],
-            self.mmap[offset as usize + 1],
-            self.mmap[offset as usize + 2],
-            self.mmap[offset as usize + 3],
-        ])
+        u32::from_le_bytes(self.read_array(offset))
     }
 
     /// Read a u8 value from the given offset
     fn read_u8(&self, offset: u64) -> u8 {
-        self.mmap[offset as usize]
+        let [value] = self.read_array(offset);
+        value
+    }
+
+    /// Read `N` bytes from the given offset
+    fn read_array<const N: usize>(&self, offset: u64) -> [u8; N] {
+        let mut buf = [0u8; N];
+        self.file
+            .read_at(offset as usize, &mut buf)
+            .expect("property read past the end of the store");
+        buf
     }
 
     /// Return the byte-offset stored in the reverse index for `(entity_id, entity_type)`.
@@ -721,11 +701,11 @@ impl PropertyStore {
         // Full scan: start at offset 1 (offset 0 is always zero because
         // prop_ptr=0 means "no properties").
         let mut offset: u64 = 1;
-        let mmap_len = self.mmap.len() as u64;
+        let file_len = self.file.len() as u64;
         let mut found_next_offset: u64 = 1;
 
-        while offset < mmap_len {
-            if offset + 13 > mmap_len {
+        while offset < file_len {
+            if offset + 13 > file_len {
                 break;
             }
 
@@ -744,7 +724,7 @@ impl PropertyStore {
             };
 
             let entry_size = 8u64 + 1 + 4 + data_size as u64;
-            if offset + entry_size > mmap_len {
+            if offset + entry_size > file_len {
                 break;
             }
 
@@ -769,44 +749,158 @@ impl PropertyStore {
         self.index.len()
     }
 
-    /// Health check for the property store
-    pub fn health_check(&self) -> Result<()> {
-        // Check if file is accessible
-        if !self.path.join("properties.store").exists() {
-            return Err(Error::storage("Property store file does not exist"));
+    /// Space accounting for storage metrics. Entries superseded by a
+    /// relocating update or dropped by [`Self::delete_properties`] stay
+    /// in the file, so `used_bytes - live_bytes` is the reclaimable slack.
+    pub fn usage(&self) -> PropertyStoreUsage {
+        let live_bytes = self
+            .reverse_index
+            .values()
+            .filter(|&&offset| offset + 13 <= self.file.len() as u64)
+            .map(|&offset| 13 + u64::from(self.read_u32(offset + 9)))
+            .sum();
+        PropertyStoreUsage {
+            file_bytes: self.file.len() as u64,
+            // Offset 0 is reserved (`prop_ptr == 0` means "no properties").
+            used_bytes: self.next_offset.saturating_sub(1),
+            live_entries: self.reverse_index.len() as u64,
+            live_bytes,
         }
+    }
 
-        // Try to read from the memory-mapped file
-        let _ = self.mmap.len();
-
-        Ok(())
+    /// Health check for the property store
+    pub fn health_check(&self) -> Result<()> {
+        self.file.health_check()
     }
 
     /// Flush all pending writes to disk
     ///
-    /// Forces the memory-mapped property file to sync with disk.
+    /// Forces the property file to sync with its backend.
     pub fn flush(&mut self) -> Result<()> {
-        self.mmap
-            .flush()
-            .map_err(|e| Error::storage(format!("Failed to flush properties: {}", e)))?;
-
-        // Also sync the underlying file to ensure OS-level persistence
-        let property_file = self.path.join("properties.store");
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(&property_file)?;
-        file.sync_all()
-            .map_err(|e| Error::storage(format!("Failed to sync properties file: {}", e)))?;
+        self.file.flush()
+    }
+}
 
-        Ok(())
+/// Serialize `properties` for a new entry and pick its entity-type byte.
+///
+/// Properties holding a BYTES value go through the binary encoding,
+/// which also enforces the per-value size cap. Everything else takes
+/// whichever of the binary encoding and JSON is shorter. Anything the
+/// binary encoding cannot round-trip exactly (an integer above
+/// `i64::MAX`) stays JSON.
+fn serialize_entry(
+    entity_type: EntityType,
+    properties: &serde_json::Value,
+) -> Result<(Vec<u8>, u8)> {
+    let binary = match binary_round_trip(properties) {
+        Some(true) => {
+            let native = NexusValue::from_json(properties);
+            return Ok((
+                value::encode(&native)?,
+                entity_type as u8 | BINARY_ENTRY_FLAG,
+            ));
+        }
+        // A value over the size cap is still fine as JSON.
+        Some(false) => value::encode(&NexusValue::from_json(properties)).ok(),
+        None => None,
+    };
+    let json = serialize_json(properties)?;
+    match binary {
+        Some(binary) if binary.len() < json.len() => {
+            Ok((binary, entity_type as u8 | BINARY_ENTRY_FLAG))
+        }
+        _ => Ok((json, entity_type as u8)),
+    }
+}
+
+/// JSON text of a property entry.
+fn serialize_json(properties: &serde_json::Value) -> Result<Vec<u8>> {
+    // Phase 1 Deep Optimization: Use to_string for small properties, to_writer for large
+    // to_string is often faster for small JSON objects due to better optimizations
+    let serialized = match properties.as_object() {
+        Some(obj) if obj.len() >= 5 => {
+            // For larger objects, use pre-allocated buffer
+            let mut buffer = Vec::with_capacity(obj.len() * 50);
+            serde_json::to_writer(&mut buffer, properties).map_err(Error::Json)?;
+            buffer
+        }
+        // For small objects (< 5 properties) and non-objects, to_string is faster
+        _ => serde_json::to_string(properties)
+            .map_err(Error::Json)?
+            .into_bytes(),
+    };
+    Ok(serialized)
+}
+
+/// Whether the binary encoding gives `properties` back unchanged, and
+/// if so whether they hold a BYTES value: `None` when they cannot
+/// round-trip, `Some(has_bytes)` otherwise. A `_bytes` key that is not
+/// valid base64 is a plain MAP and keeps the entry in JSON.
+fn binary_round_trip(properties: &serde_json::Value) -> Option<bool> {
+    fn scan(v: &serde_json::Value, has_bytes: &mut bool) -> bool {
+        match v {
+            serde_json::Value::Number(n) => n.is_i64() || n.is_f64(),
+            serde_json::Value::Array(items) => items.iter().all(|x| scan(x, has_bytes)),
+            serde_json::Value::Object(map) => {
+                if map.len() == 1
+                    && let Some(serde_json::Value::String(s)) = map.get("_bytes")
+                {
+                    *has_bytes = true;
+                    return B64.decode(s).is_ok();
+                }
+                map.values().all(|x| scan(x, has_bytes))
+            }
+            _ => true,
+        }
+    }
+    let mut has_bytes = false;
+    scan(properties, &mut has_bytes).then_some(has_bytes)
+}
+
+/// Deserialization seed that keeps only the requested keys of a stored
+/// property object and skips every other value.
+struct ProjectedProperties<'k> {
+    keys: &'k [String],
+}
+
+impl<'de> DeserializeSeed<'de> for ProjectedProperties<'_> {
+    type Value = serde_json::Value;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ProjectedProperties<'_> {
+    type Value = serde_json::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a property object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut out = serde_json::Map::with_capacity(self.keys.len());
+        while let Some(key) = map.next_key::<String>()? {
+            if self.keys.contains(&key) {
+                out.insert(key, map.next_value()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(serde_json::Value::Object(out))
     }
 }
 
 impl EntityType {
-    /// Convert from u8 to EntityType
+    /// Convert from u8 to EntityType, ignoring [`BINARY_ENTRY_FLAG`]
     fn from_u8(value: u8) -> Result<Self> {
-        match value {
+        match value & !BINARY_ENTRY_FLAG {
             0 => Ok(EntityType::Node),
             1 => Ok(EntityType::Relationship),
             _ => Err(Error::storage(format!("Invalid entity type: {}", value))),
@@ -853,6 +947,133 @@ mod tests {
         assert_eq!(loaded, properties);
     }
 
+    #[test]
+    fn test_load_projected_properties() {
+        let ctx = TestContext::new();
+        let mut store = PropertyStore::new(ctx.path().to_path_buf()).unwrap();
+
+        let ptr = store
+            .store_properties(
+                1,
+                EntityType::Node,
+                json!({
+                    "name": "Alice",
+                    "bio": "a long string nobody asked for",
+                    "tags": ["a", "b", {"nested": [1, 2, 3]}],
+                    "age": 30
+                }),
+            )
+            .unwrap();
+
+        let keys = vec!["name".to_string(), "age".to_string(), "missing".to_string()];
+        let loaded = store
+            .load_projected_properties_at_offset(ptr, &keys)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, json!({"name": "Alice", "age": 30}));
+
+        let none = store.load_projected_properties_at_offset(ptr, &[]).unwrap();
+        assert_eq!(none, Some(json!({})));
+    }
+
+    #[test]
+    fn test_bytes_properties_use_binary_entries() {
+        let ctx = TestContext::new();
+        let mut store = PropertyStore::new(ctx.path().to_path_buf()).unwrap();
+
+        let digest = B64.encode([0xABu8; 32]);
+        let properties = json!({
+            "name": "blob",
+            "sha256": {"_bytes": digest},
+            "sizes": [1, 2.5, -3]
+        });
+        let ptr = store
+            .store_properties(1, EntityType::Node, properties.clone())
+            .unwrap();
+        assert_ne!(store.read_u8(ptr + 8) & BINARY_ENTRY_FLAG, 0);
+        // Raw 32-byte digest, not its 44-character base64 text.
+        assert!(
+            (store.read_u32(ptr + 9) as usize) < serde_json::to_vec(&properties).unwrap().len()
+        );
+        assert_eq!(
+            store.get_entity_info_at_offset(ptr),
+            Some((1, EntityType::Node))
+        );
+        assert_eq!(
+            store.load_properties(1, EntityType::Node).unwrap(),
+            Some(properties)
+        );
+
+        let keys = vec!["sha256".to_string()];
+        assert_eq!(
+            store
+                .load_projected_properties_at_offset(ptr, &keys)
+                .unwrap(),
+            Some(json!({"sha256": {"_bytes": digest}}))
+        );
+
+        // Without the BYTES value this map is shorter as JSON, so the
+        // entry turns back into JSON in place.
+        let plain = json!({"name": "blob"});
+        let ptr2 = store
+            .store_properties(1, EntityType::Node, plain.clone())
+            .unwrap();
+        assert_eq!(ptr2, ptr);
+        assert_eq!(store.read_u8(ptr + 8), EntityType::Node as u8);
+        assert_eq!(
+            store.load_properties(1, EntityType::Node).unwrap(),
+            Some(plain)
+        );
+
+        // Integers beyond i64 cannot round-trip through the binary form.
+        let wide = json!({"b": {"_bytes": "AA=="}, "big": u64::MAX});
+        let ptr3 = store
+            .store_properties(2, EntityType::Relationship, wide.clone())
+            .unwrap();
+        assert_eq!(store.read_u8(ptr3 + 8), EntityType::Relationship as u8);
+        assert_eq!(
+            store.load_properties(2, EntityType::Relationship).unwrap(),
+            Some(wide)
+        );
+    }
+
+    #[test]
+    fn test_counter_and_flag_properties_shrink_in_binary_entries() {
+        let ctx = TestContext::new();
+        let mut store = PropertyStore::new(ctx.path().to_path_buf()).unwrap();
+
+        let properties = json!({
+            "visits": 1234,
+            "flags": [true, false, true, true, false, false, true, false, true, true]
+        });
+        let ptr = store
+            .store_properties(1, EntityType::Node, properties.clone())
+            .unwrap();
+        assert_ne!(store.read_u8(ptr + 8) & BINARY_ENTRY_FLAG, 0);
+        let json_len = serde_json::to_vec(&properties).unwrap().len();
+        assert!(
+            (store.read_u32(ptr + 9) as usize) < json_len,
+            "{} >= {json_len}",
+            store.read_u32(ptr + 9)
+        );
+        assert_eq!(
+            store.load_properties(1, EntityType::Node).unwrap(),
+            Some(properties)
+        );
+
+        // A short string map is smaller as JSON and keeps the JSON entry
+        // format, which loads as before.
+        let names = json!({"name": "Alice"});
+        let ptr2 = store
+            .store_properties(2, EntityType::Node, names.clone())
+            .unwrap();
+        assert_eq!(store.read_u8(ptr2 + 8), EntityType::Node as u8);
+        assert_eq!(
+            store.load_properties(2, EntityType::Node).unwrap(),
+            Some(names)
+        );
+    }
+
     #[test]
     fn test_update_properties() {
         let ctx = TestContext::new();
@@ -1080,36 +1301,3 @@ mod tests {
         assert_eq!(loaded_rel, rel_props);
     }
 }
-
-impl Clone for PropertyStore {
-    fn clone(&self) -> Self {
-        // CRITICAL FIX: Clone by preserving next_offset and indexes from the original
-        // This prevents rebuild_index() from resetting next_offset to old values when RecordStore is cloned
-        // Instead, we clone the indexes and next_offset directly, and only recreate the mmap
-
-        let property_file = self.path.join("properties.store");
-
-        // Open the same file (don't create new)
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(&property_file)
-            .expect("Failed to open property file for clone");
-
-        // Recreate memory mapping from the same file
-        let mmap = unsafe {
-            MmapOptions::new()
-                .map_mut(&file)
-                .expect("Failed to map property file for clone")
-        };
-
-        // Clone indexes and preserve next_offset from original
-        Self {
-            path: self.path.clone(),
-            mmap,
-            next_offset: self.next_offset, // CRITICAL: Preserve next_offset from original
-            index: self.index.clone(),     // CRITICAL: Preserve index from original
-            reverse_index: self.reverse_index.clone(), // CRITICAL: Preserve reverse_index from original
-        }
-    }
-}
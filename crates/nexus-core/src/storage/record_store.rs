@@ -3,153 +3,100 @@
 //! Operational methods (CRUD, property management, adjacency helpers) live in
 //! [`super::record_store_ops`] to keep this file under the 1 500-line budget.
 
-use std::fs::{File, OpenOptions};
-use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
-use memmap2::{MmapMut, MmapOptions};
-use tempfile;
 use tracing;
 
-use crate::error::{Error, Result};
+use crate::error::Result;
 
 use super::adjacency_list;
+use super::backend::{self, STORE_PAGE_SIZE, SharedStoreFile, StorageBackend, StoreFile};
 use super::property_store;
 use super::records::{
     FILE_GROWTH_FACTOR, INITIAL_NODES_FILE_SIZE, INITIAL_RELS_FILE_SIZE, NODE_RECORD_SIZE,
     REL_RECORD_SIZE, RecordStoreStats,
 };
+use super::write_tracker::{TrackedWrites, WriteTracker};
 
 /// Record store for managing nodes and relationships
 pub struct RecordStore {
     /// Path to the storage directory
     pub(super) path: PathBuf,
-    /// Nodes file handle (shared via Arc to prevent file descriptor leaks)
-    pub(super) nodes_file: Arc<File>,
-    /// Relationships file handle (shared via Arc to prevent file descriptor leaks)
-    pub(super) rels_file: Arc<File>,
-    /// Memory-mapped nodes file. Shared via `Arc<RwLock<..>>` so a
+    /// Backend the store files were opened with
+    pub(super) backend: Arc<dyn StorageBackend>,
+    /// Node records. Shared via `Arc<RwLock<..>>` so a
     /// `RecordStore::clone` (done on every `refresh_executor`) is a cheap
     /// `Arc::clone` instead of re-opening + re-mmapping the file, and so a
     /// file grow in one clone is visible to all clones (#16).
-    pub(super) nodes_mmap: Arc<RwLock<MmapMut>>,
-    /// Memory-mapped relationships file (see `nodes_mmap`).
-    pub(super) rels_mmap: Arc<RwLock<MmapMut>>,
+    pub(super) nodes_file: SharedStoreFile,
+    /// Relationship records (see `nodes_file`).
+    pub(super) rels_file: SharedStoreFile,
     /// Property store for node and relationship properties (shared via Arc to propagate modifications)
     pub property_store: Arc<RwLock<property_store::PropertyStore>>,
+    /// Enum property definitions used to store enum values as ordinals
+    /// and decode them on load (shared via Arc so every clone sees the
+    /// same definitions).
+    pub(crate) enum_types: Arc<RwLock<super::enum_types::EnumRegistry>>,
     /// Phase 3: Adjacency list store for optimized relationship traversal
     pub(crate) adjacency_store: Option<adjacency_list::AdjacencyListStore>,
+    /// Labels and relationship types touched by writes (shared by every
+    /// clone and by the property store).
+    pub(super) write_tracker: WriteTracker,
     /// Next available node ID (shared across clones)
     pub(super) next_node_id: Arc<AtomicU64>,
     /// Next available relationship ID (shared across clones)
     pub(super) next_rel_id: Arc<AtomicU64>,
-    /// Current nodes file size
-    pub(super) nodes_file_size: usize,
-    /// Current relationships file size
-    pub(super) rels_file_size: usize,
 }
 
 impl RecordStore {
-    /// Create a new record store at the given path
+    /// Create a new record store at the given path, on memory-mapped
+    /// files.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_backend(path, backend::default_backend())
+    }
+
+    /// Create a new record store at the given path whose store files are
+    /// opened through `backend`.
+    pub fn with_backend<P: AsRef<Path>>(path: P, backend: Arc<dyn StorageBackend>) -> Result<Self> {
         let path = path.as_ref().to_path_buf();
         std::fs::create_dir_all(&path)?;
 
-        let nodes_path = path.join("nodes.store");
-        let rels_path = path.join("rels.store");
-
-        // Create or open nodes file
-        let mut nodes_file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(&nodes_path)?;
-
-        // Create or open relationships file
-        let mut rels_file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(false)
-            .open(&rels_path)?;
-
-        // Get file sizes
-        let nodes_file_size = nodes_file.metadata()?.len() as usize;
-        let rels_file_size = rels_file.metadata()?.len() as usize;
-
-        // Initialize files if empty
-        let nodes_file_size = if nodes_file_size == 0 {
-            nodes_file.set_len(INITIAL_NODES_FILE_SIZE as u64)?;
-            // Zero out the file to ensure it's filled with zeros
-            nodes_file.write_all(&vec![0u8; INITIAL_NODES_FILE_SIZE])?;
-            nodes_file.sync_all()?;
-            INITIAL_NODES_FILE_SIZE
-        } else {
-            nodes_file_size
-        };
-
-        let rels_file_size = if rels_file_size == 0 {
-            rels_file.set_len(INITIAL_RELS_FILE_SIZE as u64)?;
-            // Zero out the file to ensure it's filled with zeros
-            rels_file.write_all(&vec![0u8; INITIAL_RELS_FILE_SIZE])?;
-            rels_file.sync_all()?;
-            INITIAL_RELS_FILE_SIZE
-        } else {
-            rels_file_size
-        };
-
-        // Create memory mappings
-        let nodes_mmap = unsafe { MmapOptions::new().map_mut(&nodes_file)? };
-        let rels_mmap = unsafe { MmapOptions::new().map_mut(&rels_file)? };
-
-        // Phase 3: Initialize adjacency list store (optional, for optimization)
-        let adjacency_store = adjacency_list::AdjacencyListStore::new(&path).ok();
+        let nodes_file = backend.open(&path, "nodes.store", INITIAL_NODES_FILE_SIZE)?;
+        let rels_file = backend.open(&path, "rels.store", INITIAL_RELS_FILE_SIZE)?;
 
         // Calculate next available IDs by scanning existing data
         // Count non-empty records (records where any field is non-zero)
-        let mut next_node_id = 0u64;
-        for i in 0..(nodes_file_size / NODE_RECORD_SIZE) {
-            let offset = i * NODE_RECORD_SIZE;
-            let slice = &nodes_mmap[offset..offset + NODE_RECORD_SIZE];
-            // Check if record is non-empty (any byte is non-zero)
-            if slice.iter().any(|&b| b != 0) {
-                next_node_id = (i + 1) as u64;
-            }
-        }
-
-        let mut next_rel_id = 0u64;
-        for i in 0..(rels_file_size / REL_RECORD_SIZE) {
-            let offset = i * REL_RECORD_SIZE;
-            let slice = &rels_mmap[offset..offset + REL_RECORD_SIZE];
-            // Check if record is non-empty (any byte is non-zero)
-            if slice.iter().any(|&b| b != 0) {
-                next_rel_id = (i + 1) as u64;
-            }
-        }
+        let next_node_id = next_free_slot(nodes_file.as_ref(), NODE_RECORD_SIZE)?;
+        let next_rel_id = next_free_slot(rels_file.as_ref(), REL_RECORD_SIZE)?;
 
         // Initialize property store (wrapped in Arc<RwLock> for sharing between clones)
-        let property_store = Arc::new(RwLock::new(property_store::PropertyStore::new(
-            path.clone(),
-        )?));
-
-        // Phase 3: Initialize adjacency list store (optional, for optimization)
-        let adjacency_store = adjacency_list::AdjacencyListStore::new(&path).ok();
+        let write_tracker = WriteTracker::default();
+        let mut property_store =
+            property_store::PropertyStore::with_backend(path.clone(), backend.as_ref())?;
+        property_store.set_write_tracker(write_tracker.clone());
+        let property_store = Arc::new(RwLock::new(property_store));
+
+        // Phase 3: Initialize adjacency list store (optional, for optimization).
+        // It keeps its own files on disk, so volatile backends go without.
+        let adjacency_store = if backend.is_persistent() {
+            adjacency_list::AdjacencyListStore::new(&path).ok()
+        } else {
+            None
+        };
 
         let mut store = Self {
             path,
-            nodes_file: Arc::new(nodes_file),
-            rels_file: Arc::new(rels_file),
-            nodes_mmap: Arc::new(RwLock::new(nodes_mmap)),
-            rels_mmap: Arc::new(RwLock::new(rels_mmap)),
+            backend,
+            nodes_file: Arc::new(RwLock::new(nodes_file)),
+            rels_file: Arc::new(RwLock::new(rels_file)),
             property_store,
+            enum_types: Arc::new(RwLock::new(Default::default())),
             adjacency_store,
+            write_tracker,
             next_node_id: Arc::new(AtomicU64::new(next_node_id)),
             next_rel_id: Arc::new(AtomicU64::new(next_rel_id)),
-            nodes_file_size,
-            rels_file_size,
         };
 
         // Issue #4: run the durable startup repair so corrupt prop_ptrs are
@@ -166,6 +113,35 @@ impl RecordStore {
         Ok(store)
     }
 
+    /// Backend the store files were opened with.
+    pub fn backend(&self) -> &Arc<dyn StorageBackend> {
+        &self.backend
+    }
+
+    /// Tracker of the labels and relationship types this store's writes
+    /// touch. Disabled until someone enables it.
+    pub fn write_tracker(&self) -> &WriteTracker {
+        &self.write_tracker
+    }
+
+    /// Take the writes tracked so far, with property writes resolved to
+    /// the labels of their nodes and the types of their relationships.
+    pub fn take_tracked_writes(&self) -> TrackedWrites {
+        let mut writes = self.write_tracker.take();
+        for node_id in std::mem::take(&mut writes.property_nodes) {
+            if let Ok(record) = self.read_node(node_id) {
+                writes.label_bits |= record.label_bits;
+            }
+        }
+        for rel_id in std::mem::take(&mut writes.property_rels) {
+            if let Ok(record) = self.read_rel(rel_id) {
+                let type_id = record.type_id;
+                writes.type_ids.insert(type_id);
+            }
+        }
+        writes
+    }
+
     /// Allocate a new node ID
     pub fn allocate_node_id(&mut self) -> u64 {
         self.next_node_id.fetch_add(1, Ordering::SeqCst)
@@ -200,17 +176,11 @@ impl RecordStore {
 
     /// Synchronous flush (for durability guarantees)
     fn flush_sync(&mut self) -> Result<()> {
-        // Flush memory-mapped files to disk
-        self.nodes_mmap
-            .read()
-            .unwrap()
-            .flush()
-            .map_err(|e| Error::Storage(format!("Failed to flush nodes: {}", e)))?;
-        self.rels_mmap
-            .read()
-            .unwrap()
-            .flush()
-            .map_err(|e| Error::Storage(format!("Failed to flush rels: {}", e)))?;
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::fsync(&self.path)?;
+        // Flush the record files to their backend
+        self.nodes_file.read().unwrap().flush()?;
+        self.rels_file.read().unwrap().flush()?;
 
         // Also flush the property store
         self.property_store.write().unwrap().flush()?;
@@ -223,6 +193,17 @@ impl RecordStore {
         Ok(())
     }
 
+    /// Handle that syncs this store's shared files from another thread.
+    pub(crate) fn flush_handle(&self) -> StoreFlushHandle {
+        StoreFlushHandle {
+            #[cfg(feature = "fault-injection")]
+            path: self.path.clone(),
+            nodes_file: Arc::clone(&self.nodes_file),
+            rels_file: Arc::clone(&self.rels_file),
+            property_store: Arc::clone(&self.property_store),
+        }
+    }
+
     /// Phase 1 Deep Optimization: Optional async flush (doesn't wait for OS)
     /// Use this when durability can be relaxed for better throughput
     pub fn flush_async(&mut self) -> Result<()> {
@@ -237,51 +218,29 @@ impl RecordStore {
         RecordStoreStats {
             node_count: self.next_node_id.load(Ordering::SeqCst),
             rel_count: self.next_rel_id.load(Ordering::SeqCst),
-            nodes_file_size: self.nodes_file_size,
-            rels_file_size: self.rels_file_size,
+            nodes_file_size: self.nodes_file.read().unwrap().len(),
+            rels_file_size: self.rels_file.read().unwrap().len(),
         }
     }
 
     /// Grow the nodes file
     /// Phase 1 Deep Optimization: Pre-allocate larger chunks to reduce growth frequency
     pub(super) fn grow_nodes_file(&mut self) -> Result<()> {
-        // Phase 1 Deep Optimization: Grow by larger factor to reduce frequency
-        // Minimum 2MB growth to reduce frequent remapping overhead
-        let min_growth = 2 * 1024 * 1024; // 2MB
-        let calculated_size = ((self.nodes_file_size as f64) * FILE_GROWTH_FACTOR) as usize;
-        let new_size = calculated_size.max(self.nodes_file_size + min_growth);
-
-        // Resize the file
-        self.nodes_file.set_len(new_size as u64)?;
-
-        // Recreate the memory mapping in place. Because the mapping is shared
-        // via Arc<RwLock>, the grow is immediately visible to every clone
-        // (#16) — no per-clone re-map needed on the next refresh_executor.
-        *self.nodes_mmap.write().unwrap() =
-            unsafe { MmapOptions::new().map_mut(&*self.nodes_file)? };
-
-        self.nodes_file_size = new_size;
-        Ok(())
+        // Resized in place. Because the file is shared via Arc<RwLock>, the
+        // grow is immediately visible to every clone (#16) — no per-clone
+        // re-map needed on the next refresh_executor.
+        grow_store_file(&self.nodes_file)
     }
 
     /// Grow the relationships file
     /// Phase 1 Deep Optimization: Pre-allocate larger chunks to reduce growth frequency
     pub(super) fn grow_rels_file(&mut self) -> Result<()> {
-        // Phase 1 Deep Optimization: Grow by larger factor to reduce frequency
-        // Minimum 2MB growth to reduce frequent remapping overhead
-        let min_growth = 2 * 1024 * 1024; // 2MB
-        let calculated_size = ((self.rels_file_size as f64) * FILE_GROWTH_FACTOR) as usize;
-        let new_size = calculated_size.max(self.rels_file_size + min_growth);
-
-        // Resize the file
-        self.rels_file.set_len(new_size as u64)?;
-
-        // Recreate the memory mapping in place (shared via Arc<RwLock>; see
-        // grow_nodes_file).
-        *self.rels_mmap.write().unwrap() = unsafe { MmapOptions::new().map_mut(&*self.rels_file)? };
+        grow_store_file(&self.rels_file)
+    }
 
-        self.rels_file_size = new_size;
-        Ok(())
+    /// Directory holding the store files.
+    pub fn path(&self) -> &Path {
+        &self.path
     }
 
     /// Get the number of nodes
@@ -296,22 +255,66 @@ impl RecordStore {
 
     /// Health check for the record store
     pub fn health_check(&self) -> Result<()> {
-        // Check if files are accessible and readable
-        if !self.path.join("nodes.store").exists() {
-            return Err(Error::storage("Nodes file does not exist"));
-        }
-        if !self.path.join("rels.store").exists() {
-            return Err(Error::storage("Relationships file does not exist"));
-        }
+        self.nodes_file.read().unwrap().health_check()?;
+        self.rels_file.read().unwrap().health_check()?;
+        Ok(())
+    }
+}
 
-        // Try to read from the memory-mapped files
-        let _ = self.nodes_mmap.read().unwrap().len();
-        let _ = self.rels_mmap.read().unwrap().len();
+/// The node, relationship and property files of a [`RecordStore`],
+/// shared with every clone, for the engine's background store flusher.
+///
+/// Leaves out the adjacency store, which each clone opens on its own;
+/// [`RecordStore::flush`] still syncs that one.
+#[derive(Clone)]
+pub(crate) struct StoreFlushHandle {
+    #[cfg(feature = "fault-injection")]
+    path: PathBuf,
+    nodes_file: SharedStoreFile,
+    rels_file: SharedStoreFile,
+    property_store: Arc<RwLock<property_store::PropertyStore>>,
+}
 
+impl StoreFlushHandle {
+    /// Sync the shared files. Each file is locked only while it syncs,
+    /// so writers to the other files are not held up.
+    pub(crate) fn flush(&self) -> Result<()> {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::fsync(&self.path)?;
+        self.nodes_file.read().unwrap().flush()?;
+        self.rels_file.read().unwrap().flush()?;
+        self.property_store.write().unwrap().flush()?;
         Ok(())
     }
 }
 
+/// Grow a record file.
+/// Phase 1 Deep Optimization: Grow by larger factor to reduce frequency
+fn grow_store_file(file: &SharedStoreFile) -> Result<()> {
+    let mut file = file.write().unwrap();
+    // Minimum 2MB growth to reduce frequent remapping overhead
+    let min_growth = 2 * 1024 * 1024; // 2MB
+    let current = file.len();
+    let calculated_size = ((current as f64) * FILE_GROWTH_FACTOR) as usize;
+    file.resize(calculated_size.max(current + min_growth))
+}
+
+/// Slot after the last non-empty record in `file` (a record is empty
+/// when every byte is zero). Scans page by page from the end, so only
+/// the tail of the file is read once the last record is found.
+fn next_free_slot(file: &dyn StoreFile, record_size: usize) -> Result<u64> {
+    let usable = file.len() / record_size * record_size;
+    for n in (0..usable.div_ceil(STORE_PAGE_SIZE)).rev() {
+        let start = n * STORE_PAGE_SIZE;
+        let page = file.page(n)?;
+        let within = page.len().min(usable - start);
+        if let Some(i) = page[..within].iter().rposition(|&b| b != 0) {
+            return Ok(((start + i) / record_size + 1) as u64);
+        }
+    }
+    Ok(0)
+}
+
 impl Clone for RecordStore {
     fn clone(&self) -> Self {
         // CRITICAL FIX: Share the same PropertyStore via Arc::clone()
@@ -320,10 +323,10 @@ impl Clone for RecordStore {
         // This solves the problem where next_offset was being reset when creating relationships
         // because each clone was getting an independent copy of PropertyStore
 
-        // #16: share the memory mappings, file handles, property store and id
+        // #16: share the store files, property store and id
         // counters via `Arc` — clone is now a handful of `Arc::clone`s, with no
         // file re-open + re-mmap on the (per-write) `refresh_executor` path.
-        // Sharing the mmap also means a file grow performed through one clone
+        // Sharing the files also means a file grow performed through one clone
         // is immediately visible to every other clone (previously each clone
         // held an independent mapping that went stale after a grow).
         let property_store = Arc::clone(&self.property_store);
@@ -337,16 +340,15 @@ impl Clone for RecordStore {
 
         Self {
             path: self.path.clone(),
+            backend: Arc::clone(&self.backend),
             nodes_file: Arc::clone(&self.nodes_file),
             rels_file: Arc::clone(&self.rels_file),
-            nodes_mmap: Arc::clone(&self.nodes_mmap),
-            rels_mmap: Arc::clone(&self.rels_mmap),
             property_store, // CRITICAL: Shared PropertyStore instance (not a clone)
+            enum_types: Arc::clone(&self.enum_types),
             adjacency_store,
+            write_tracker: self.write_tracker.clone(),
             next_node_id: Arc::clone(&self.next_node_id),
             next_rel_id: Arc::clone(&self.next_rel_id),
-            nodes_file_size: self.nodes_file_size,
-            rels_file_size: self.rels_file_size,
         }
     }
 }
@@ -378,7 +380,7 @@ mod tests {
     }
 
     /// ISSUE #16: `RecordStore::clone` must be a shared HANDLE (a handful
-    /// of `Arc::clone`s over the same mmaps / files / property store), not
+    /// of `Arc::clone`s over the same store files / property store), not
     /// a file re-open + re-mmap. This guards the per-write
     /// `refresh_executor` path: a write must never trigger a RecordStore
     /// reopen, and a write through one handle must be immediately visible
@@ -388,19 +390,15 @@ mod tests {
         let (mut store, _dir) = create_test_store();
         let clone = store.clone();
 
-        // Structural guard: the clone shares the SAME mmaps, file handles
+        // Structural guard: the clone shares the SAME store files
         // and property store (no reopen happened).
         assert!(
-            Arc::ptr_eq(&store.nodes_mmap, &clone.nodes_mmap),
-            "clone must share the nodes mmap (no re-mmap)"
-        );
-        assert!(
-            Arc::ptr_eq(&store.rels_mmap, &clone.rels_mmap),
-            "clone must share the rels mmap (no re-mmap)"
+            Arc::ptr_eq(&store.nodes_file, &clone.nodes_file),
+            "clone must share the nodes file (no reopen or re-mmap)"
         );
         assert!(
-            Arc::ptr_eq(&store.nodes_file, &clone.nodes_file),
-            "clone must share the nodes file handle (no reopen)"
+            Arc::ptr_eq(&store.rels_file, &clone.rels_file),
+            "clone must share the rels file (no reopen or re-mmap)"
         );
         assert!(
             Arc::ptr_eq(&store.property_store, &clone.property_store),
@@ -585,6 +583,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn memory_backend_keeps_store_files_off_disk() {
+        let ctx = TestContext::new();
+        let backend: Arc<dyn StorageBackend> = Arc::new(super::super::MemoryBackend);
+        let mut store = RecordStore::with_backend(ctx.path(), Arc::clone(&backend)).unwrap();
+        assert_eq!(store.backend().name(), "memory");
+
+        // Enough nodes to grow the file past its initial size.
+        for i in 0..50000u64 {
+            let node_id = store.allocate_node_id();
+            let mut record = NodeRecord::default();
+            record.add_label((i % 64) as u32);
+            store.write_node(node_id, &record).unwrap();
+        }
+        store
+            .update_node_properties(7, serde_json::json!({"name": "seven"}))
+            .unwrap();
+        store.flush().unwrap();
+
+        assert!(store.stats().nodes_file_size > INITIAL_NODES_FILE_SIZE);
+        assert!(store.read_node(49_999).unwrap().has_label(49_999 % 64));
+        assert_eq!(
+            store.load_node_properties(7).unwrap(),
+            Some(serde_json::json!({"name": "seven"}))
+        );
+        store.health_check().unwrap();
+        for file in ["nodes.store", "rels.store", "properties.store"] {
+            assert!(!ctx.path().join(file).exists(), "{file} written to disk");
+        }
+
+        // A second store on the same directory starts empty.
+        let fresh = RecordStore::with_backend(ctx.path(), backend).unwrap();
+        assert_eq!(fresh.node_count(), 0);
+    }
+
     #[test]
     fn test_stats() {
         let (mut store, _dir) = create_test_store();
@@ -972,7 +1005,7 @@ mod tests {
             // Persist the node record + property store to disk BEFORE injecting
             // corruption, so that on reopen the rebuilt reverse_index still
             // contains the node's real property entry (the source the repair
-            // recovers from). Flushing only nodes_mmap later is not enough —
+            // recovers from). Flushing only nodes_file later is not enough —
             // properties.store must be durable too.
             store
                 .flush()
@@ -993,20 +1026,30 @@ mod tests {
             //
             // We bypass write_node deliberately here — write_node's guard
             // would reject this (it detects the Relationship type and returns
-            // Err).  Writing the mmap directly is the only way to simulate the
+            // Err).  Writing the nodes file directly is the only way to simulate the
             // pre-existing on-disk corruption that issue #4 describes.
             // ----------------------------------------------------------------
             let byte_start = node_id as usize * NODE_RECORD_SIZE;
-            let byte_end = byte_start + NODE_RECORD_SIZE;
-            // Read current record bytes via mmap.
-            let mut record_bytes = store.nodes_mmap.read().unwrap()[byte_start..byte_end].to_vec();
+            // Read current record bytes straight from the nodes file.
+            let mut record_bytes = [0u8; NODE_RECORD_SIZE];
+            store
+                .nodes_file
+                .read()
+                .unwrap()
+                .read_at(byte_start, &mut record_bytes)
+                .unwrap();
             // Overwrite prop_ptr (bytes 16..24 in NodeRecord: label_bits[0..8],
             // first_rel_ptr[8..16], prop_ptr[16..24]).
             record_bytes[16..24].copy_from_slice(&rel_prop_offset.to_le_bytes());
-            store.nodes_mmap.write().unwrap()[byte_start..byte_end].copy_from_slice(&record_bytes);
+            store
+                .nodes_file
+                .write()
+                .unwrap()
+                .write_at(byte_start, &record_bytes)
+                .unwrap();
             // Flush so the corrupt bytes land on disk.
             store
-                .nodes_mmap
+                .nodes_file
                 .read()
                 .unwrap()
                 .flush()
@@ -1022,11 +1065,13 @@ mod tests {
 
         // (b) The on-disk prop_ptr must no longer point at a Relationship.
         let byte_start = node_id as usize * NODE_RECORD_SIZE;
-        let byte_end = byte_start + NODE_RECORD_SIZE;
-        let on_disk_record: NodeRecord = {
-            let guard = store2.nodes_mmap.read().unwrap();
-            *bytemuck::from_bytes(&guard[byte_start..byte_end])
-        };
+        let mut on_disk_record = NodeRecord::default();
+        store2
+            .nodes_file
+            .read()
+            .unwrap()
+            .read_at(byte_start, bytemuck::bytes_of_mut(&mut on_disk_record))
+            .unwrap();
         if on_disk_record.prop_ptr != 0 {
             let info = store2
                 .property_store
@@ -1093,11 +1138,21 @@ mod tests {
 
             // Inject corruption.
             let byte_start = node_id as usize * NODE_RECORD_SIZE;
-            let byte_end = byte_start + NODE_RECORD_SIZE;
-            let mut record_bytes = store.nodes_mmap.read().unwrap()[byte_start..byte_end].to_vec();
+            let mut record_bytes = [0u8; NODE_RECORD_SIZE];
+            store
+                .nodes_file
+                .read()
+                .unwrap()
+                .read_at(byte_start, &mut record_bytes)
+                .unwrap();
             record_bytes[16..24].copy_from_slice(&rel_prop_offset.to_le_bytes());
-            store.nodes_mmap.write().unwrap()[byte_start..byte_end].copy_from_slice(&record_bytes);
-            store.nodes_mmap.read().unwrap().flush().unwrap();
+            store
+                .nodes_file
+                .write()
+                .unwrap()
+                .write_at(byte_start, &record_bytes)
+                .unwrap();
+            store.nodes_file.read().unwrap().flush().unwrap();
         }
 
         // First reopen — repair runs.
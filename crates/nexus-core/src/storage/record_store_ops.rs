@@ -7,9 +7,10 @@
 //! under the 1 500-line budget.
 
 use std::sync::atomic::Ordering;
+use std::sync::{Arc, RwLock};
 
 use crate::error::{Error, Result};
-use memmap2::MmapOptions;
+use crate::transaction::{Mutation, Transaction};
 
 use super::external_id::{ConflictPolicy, ExternalId};
 use super::property_store;
@@ -68,16 +69,29 @@ impl RecordStore {
 
         let offset = (node_id as usize * NODE_RECORD_SIZE) as u64;
 
+        // Labels the slot carried before count as written too, so
+        // removing a label is seen.
+        if self.write_tracker.is_enabled() {
+            let mut previous = NodeRecord::default();
+            let guard = self.nodes_file.read().unwrap();
+            if offset as usize + NODE_RECORD_SIZE <= guard.len() {
+                guard.read_at(offset as usize, bytemuck::bytes_of_mut(&mut previous))?;
+            }
+            self.write_tracker
+                .node_written(previous.label_bits | record.label_bits);
+        }
+
         // Phase 3 Optimization: Pre-check file size to avoid unnecessary grow check
-        if offset + NODE_RECORD_SIZE as u64 > self.nodes_file_size as u64 {
+        if offset + NODE_RECORD_SIZE as u64 > self.nodes_file.read().unwrap().len() as u64 {
             self.grow_nodes_file()?;
         }
 
         // Phase 3 Optimization: Direct write without intermediate allocation
-        let start = offset as usize;
-        let end = start + NODE_RECORD_SIZE;
         let record_bytes = bytemuck::bytes_of(record);
-        self.nodes_mmap.write().unwrap()[start..end].copy_from_slice(record_bytes);
+        self.nodes_file
+            .write()
+            .unwrap()
+            .write_at(offset as usize, record_bytes)?;
 
         // Memory barrier to ensure write is visible to subsequent reads
         // Release is sufficient for single-writer model
@@ -97,30 +111,27 @@ impl RecordStore {
     /// pointer is reset to `0` (meaning "no properties"), which is safe because
     /// `load_node_properties` will then return `None` rather than corrupt data.
     ///
-    /// After the scan, if any record was corrected the nodes mmap is flushed to
+    /// After the scan, if any record was corrected the nodes file is flushed to
     /// disk so the repair survives the next restart.  This closes the recurring
     /// corruption loop described in issue #4.
     ///
     /// Returns the number of slots that were repaired.
     pub fn repair_corrupt_node_prop_ptrs(&mut self) -> Result<usize> {
-        let slot_count = self.nodes_file_size / NODE_RECORD_SIZE;
+        let slot_count = self.nodes_file.read().unwrap().len() / NODE_RECORD_SIZE;
         let mut repaired = 0usize;
 
         for slot in 0..slot_count {
-            let byte_start = slot * NODE_RECORD_SIZE;
-            let byte_end = byte_start + NODE_RECORD_SIZE;
-
             // Read raw on-disk bytes — do NOT go through read_node because that
             // resets prop_ptr in memory without persisting; we need the real value.
-            let mut record: NodeRecord = {
-                let guard = self.nodes_mmap.read().unwrap();
-                let bytes = &guard[byte_start..byte_end];
-                // Skip all-zero slots: they are unallocated/never-written.
-                if bytes.iter().all(|&b| b == 0) {
-                    continue;
-                }
-                *bytemuck::from_bytes(bytes)
-            };
+            let mut record = NodeRecord::default();
+            self.nodes_file
+                .read()
+                .unwrap()
+                .read_at(slot * NODE_RECORD_SIZE, bytemuck::bytes_of_mut(&mut record))?;
+            // Skip all-zero slots: they are unallocated/never-written.
+            if bytemuck::bytes_of(&record).iter().all(|&b| b == 0) {
+                continue;
+            }
 
             // Nothing to validate when prop_ptr is already 0.
             if record.prop_ptr == 0 {
@@ -160,7 +171,7 @@ impl RecordStore {
 
         if repaired > 0 {
             // Make the corrections durable before the constructor returns.
-            self.nodes_mmap
+            self.nodes_file
                 .read()
                 .unwrap()
                 .flush()
@@ -173,23 +184,22 @@ impl RecordStore {
 
     /// Read a node record
     pub fn read_node(&self, node_id: u64) -> Result<NodeRecord> {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::read_delay(&self.path);
         // Memory barrier to ensure visibility of writes from other threads
         // Acquire is sufficient - pairs with Release barriers in write operations
         std::sync::atomic::fence(std::sync::atomic::Ordering::Acquire);
 
-        let offset = (node_id as usize * NODE_RECORD_SIZE) as u64;
-
-        if offset + NODE_RECORD_SIZE as u64 > self.nodes_file_size as u64 {
-            return Err(Error::NotFound(format!("Node {} not found", node_id)));
+        let start = node_id as usize * NODE_RECORD_SIZE;
+        let mut record = NodeRecord::default();
+        {
+            let guard = self.nodes_file.read().unwrap();
+            if start + NODE_RECORD_SIZE > guard.len() {
+                return Err(Error::NotFound(format!("Node {} not found", node_id)));
+            }
+            guard.read_at(start, bytemuck::bytes_of_mut(&mut record))?;
         }
 
-        let start = offset as usize;
-        let end = start + NODE_RECORD_SIZE;
-        let mut record: NodeRecord = {
-            let guard = self.nodes_mmap.read().unwrap();
-            *bytemuck::from_bytes(&guard[start..end])
-        };
-
         // CRITICAL FIX: Validate prop_ptr immediately after read to detect corruption early
         // If prop_ptr points to a Relationship, it's corrupted - reset to 0
         // This prevents corruption from propagating and helps identify when corruption occurs
@@ -222,11 +232,11 @@ impl RecordStore {
     }
 
     /// Read every node header (up to [`Self::node_count`] records) in ONE
-    /// `nodes_mmap` lock acquisition instead of one acquisition per node.
+    /// `nodes_file` lock acquisition instead of one acquisition per node.
     ///
     /// phase8_neo4j-concurrency-gaps §1 — `count_live_nodes_all` /
     /// `count_live_nodes_for_label` used to call [`Self::read_node`] once
-    /// per candidate node, each call taking its own `nodes_mmap.read()`
+    /// per candidate node, each call taking its own `nodes_file.read()`
     /// lock (plus a second `property_store.read()` lock whenever the node
     /// had a `prop_ptr`, for a corruption cross-check that `is_deleted()`
     /// never needed). At thousands of nodes and dozens of concurrent
@@ -236,43 +246,47 @@ impl RecordStore {
     /// qps flat, p99 124ms, while Neo4j scaled to 13k), on top of the
     /// Project-skip fix on `Executor::try_short_circuit_count_cross_product`
     /// that made this short-circuit engage for `count(n)` at all.
-    /// `NodeRecord` is `bytemuck::Pod`, so casting the locked byte range
-    /// once and copying it into an owned `Vec` is a single bulk memcpy —
+    /// `NodeRecord` is `bytemuck::Pod`, so reading the locked byte range
+    /// once into an owned `Vec` is a single bulk read —
     /// far cheaper than the lock churn it replaces — and the lock is
     /// released the moment that copy finishes, before the caller iterates.
     ///
-    /// Bounded by `node_count()` rather than the mmap's raw (pre-grown)
+    /// Bounded by `node_count()` rather than the file's raw (pre-grown)
     /// byte length: the file can be larger than the logical record count
     /// after a capacity grow, and those trailing bytes are zeroed — a
     /// zeroed `NodeRecord` has `flags == 0`, which `is_deleted()` reads as
     /// "not deleted", so including them would silently over-count.
-    pub fn read_all_node_headers(&self) -> Vec<NodeRecord> {
+    pub fn read_all_node_headers(&self) -> Result<Vec<NodeRecord>> {
         std::sync::atomic::fence(std::sync::atomic::Ordering::Acquire);
 
         let total = self.node_count() as usize;
         let wanted_len = total.saturating_mul(NODE_RECORD_SIZE);
 
-        let guard = self.nodes_mmap.read().unwrap();
+        let guard = self.nodes_file.read().unwrap();
         let usable_len = wanted_len.min(guard.len());
-        let usable_len = usable_len - (usable_len % NODE_RECORD_SIZE);
-        bytemuck::cast_slice::<u8, NodeRecord>(&guard[..usable_len]).to_vec()
+        let mut headers = vec![NodeRecord::default(); usable_len / NODE_RECORD_SIZE];
+        guard.read_at(0, bytemuck::cast_slice_mut(&mut headers))?;
+        Ok(headers)
     }
 
     /// Write a relationship record
     /// Phase 3 Deep Optimization: Optimized write path
     pub fn write_rel(&mut self, rel_id: u64, record: &RelationshipRecord) -> Result<()> {
         let offset = (rel_id as usize * REL_RECORD_SIZE) as u64;
+        let type_id = record.type_id;
+        self.write_tracker.rel_written(type_id);
 
         // Phase 3 Optimization: Pre-check file size to avoid unnecessary grow check
-        if offset + REL_RECORD_SIZE as u64 > self.rels_file_size as u64 {
+        if offset + REL_RECORD_SIZE as u64 > self.rels_file.read().unwrap().len() as u64 {
             self.grow_rels_file()?;
         }
 
         // Phase 3 Optimization: Direct write without intermediate allocation
-        let start = offset as usize;
-        let end = start + REL_RECORD_SIZE;
         let record_bytes = bytemuck::bytes_of(record);
-        self.rels_mmap.write().unwrap()[start..end].copy_from_slice(record_bytes);
+        self.rels_file
+            .write()
+            .unwrap()
+            .write_at(offset as usize, record_bytes)?;
 
         // Memory barrier to ensure write is visible to subsequent reads
         // Release is sufficient for single-writer model
@@ -283,19 +297,19 @@ impl RecordStore {
 
     /// Read a relationship record
     pub fn read_rel(&self, rel_id: u64) -> Result<RelationshipRecord> {
-        let offset = (rel_id as usize * REL_RECORD_SIZE) as u64;
-
-        if offset + REL_RECORD_SIZE as u64 > self.rels_file_size as u64 {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::read_delay(&self.path);
+        let start = rel_id as usize * REL_RECORD_SIZE;
+        let guard = self.rels_file.read().unwrap();
+        if start + REL_RECORD_SIZE > guard.len() {
             return Err(Error::NotFound(format!(
                 "Relationship {} not found",
                 rel_id
             )));
         }
-
-        let start = offset as usize;
-        let end = start + REL_RECORD_SIZE;
-        let guard = self.rels_mmap.read().unwrap();
-        Ok(*bytemuck::from_bytes(&guard[start..end]))
+        let mut record = RelationshipRecord::default();
+        guard.read_at(start, bytemuck::bytes_of_mut(&mut record))?;
+        Ok(record)
     }
 
     /// Delete a node (mark as deleted).
@@ -340,10 +354,39 @@ impl RecordStore {
         self.write_rel(rel_id, &record)
     }
 
+    /// Put node `node_id` back as `record` with `properties`, for WAL
+    /// replay and for undoing a failed rewrite. The slot is claimed if the store lost it; `record`'s own
+    /// property pointer is ignored since the property entry is written
+    /// anew.
+    pub(crate) fn restore_node(
+        &mut self,
+        node_id: u64,
+        mut record: NodeRecord,
+        properties: Option<serde_json::Value>,
+    ) -> Result<()> {
+        record.prop_ptr = 0;
+        self.write_node(node_id, &record)?;
+        self.next_node_id.fetch_max(node_id + 1, Ordering::SeqCst);
+        self.update_node_properties(node_id, properties.unwrap_or_default())
+    }
+
+    /// Put relationship `rel_id` back as `record` with `properties`, for
+    /// WAL replay and for undoing a failed rewrite. See [`RecordStore::restore_node`].
+    pub(crate) fn restore_relationship(
+        &mut self,
+        rel_id: u64,
+        record: RelationshipRecord,
+        properties: Option<serde_json::Value>,
+    ) -> Result<()> {
+        self.write_rel(rel_id, &record)?;
+        self.next_rel_id.fetch_max(rel_id + 1, Ordering::SeqCst);
+        self.update_relationship_properties(rel_id, properties.unwrap_or_default())
+    }
+
     /// Create a new node
     pub fn create_node(
         &mut self,
-        _tx: &mut crate::transaction::Transaction,
+        tx: &mut crate::transaction::Transaction,
         labels: Vec<String>,
         properties: serde_json::Value,
     ) -> Result<u64> {
@@ -354,7 +397,8 @@ impl RecordStore {
                 label_bits |= 1u64 << i;
             }
         }
-        self.create_node_with_label_bits_inner(
+        self.create_node_recorded(
+            tx,
             label_bits,
             properties,
             None,
@@ -366,11 +410,12 @@ impl RecordStore {
     /// Create a new node with pre-computed label bits
     pub fn create_node_with_label_bits(
         &mut self,
-        _tx: &mut crate::transaction::Transaction,
+        tx: &mut crate::transaction::Transaction,
         label_bits: u64,
         properties: serde_json::Value,
     ) -> Result<u64> {
-        self.create_node_with_label_bits_inner(
+        self.create_node_recorded(
+            tx,
             label_bits,
             properties,
             None,
@@ -393,7 +438,7 @@ impl RecordStore {
     ///   - [`ConflictPolicy::Replace`] — overwrites properties, returns existing id.
     pub fn create_node_with_external_id(
         &mut self,
-        _tx: &mut crate::transaction::Transaction,
+        tx: &mut crate::transaction::Transaction,
         labels: Vec<String>,
         properties: serde_json::Value,
         external_id: Option<ExternalId>,
@@ -406,7 +451,8 @@ impl RecordStore {
                 label_bits |= 1u64 << i;
             }
         }
-        self.create_node_with_label_bits_inner(
+        self.create_node_recorded(
+            tx,
             label_bits,
             properties,
             external_id,
@@ -422,14 +468,15 @@ impl RecordStore {
     /// [`RecordStore::create_node_with_external_id`].
     pub fn create_node_with_label_bits_and_external_id(
         &mut self,
-        _tx: &mut crate::transaction::Transaction,
+        tx: &mut crate::transaction::Transaction,
         label_bits: u64,
         properties: serde_json::Value,
         external_id: Option<ExternalId>,
         policy: ConflictPolicy,
         catalog: &crate::catalog::Catalog,
     ) -> Result<u64> {
-        self.create_node_with_label_bits_inner(
+        self.create_node_recorded(
+            tx,
             label_bits,
             properties,
             external_id,
@@ -438,6 +485,39 @@ impl RecordStore {
         )
     }
 
+    /// [`Self::create_node_with_label_bits_inner`], recording the write
+    /// in `tx` for the commit hooks. A new id is a creation; an existing
+    /// one (an external-id conflict) is an update under
+    /// [`ConflictPolicy::Replace`] and no change under
+    /// [`ConflictPolicy::Match`].
+    fn create_node_recorded(
+        &mut self,
+        tx: &mut Transaction,
+        label_bits: u64,
+        properties: serde_json::Value,
+        external_id: Option<ExternalId>,
+        policy: ConflictPolicy,
+        catalog: Option<&crate::catalog::Catalog>,
+    ) -> Result<u64> {
+        let first_new_id = self.node_count();
+        let node_id = self.create_node_with_label_bits_inner(
+            label_bits,
+            properties,
+            external_id,
+            policy,
+            catalog,
+        )?;
+        if node_id >= first_new_id {
+            let label_ids = (0..64u32)
+                .filter(|bit| label_bits & (1u64 << bit) != 0)
+                .collect();
+            tx.record(Mutation::NodeCreated { node_id, label_ids });
+        } else if policy == ConflictPolicy::Replace {
+            tx.record(Mutation::NodeUpdated { node_id });
+        }
+        Ok(node_id)
+    }
+
     /// Central implementation used by all node-creation paths.
     ///
     /// `catalog` is required only when `external_id` is `Some`.  Passing
@@ -447,11 +527,13 @@ impl RecordStore {
     fn create_node_with_label_bits_inner(
         &mut self,
         label_bits: u64,
-        properties: serde_json::Value,
+        mut properties: serde_json::Value,
         external_id: Option<ExternalId>,
         policy: ConflictPolicy,
         catalog: Option<&crate::catalog::Catalog>,
     ) -> Result<u64> {
+        self.encode_enum_properties(label_bits, &mut properties)?;
+
         // ── External-id path ──────────────────────────────────────────────────
         //
         // peek-then-allocate:
@@ -598,7 +680,7 @@ impl RecordStore {
     /// Phase 1 Optimization: Optimized relationship creation with reduced node reads
     pub fn create_relationship(
         &mut self,
-        _tx: &mut crate::transaction::Transaction,
+        tx: &mut crate::transaction::Transaction,
         from: u64,
         to: u64,
         type_id: u32,
@@ -901,6 +983,12 @@ impl RecordStore {
             // Self-loop: skip incoming update (same as outgoing)
         }
 
+        tx.record(Mutation::RelationshipCreated {
+            rel_id,
+            src: from,
+            dst: to,
+            type_id,
+        });
         Ok(rel_id)
     }
 
@@ -1032,64 +1120,53 @@ impl RecordStore {
         // next_offset incorrectly, causing new properties to overwrite old ones
         self.property_store.write().unwrap().clear_all()?;
 
-        // CRITICAL FIX: Drop memory mappings before truncating files
-        // On Windows, you cannot truncate a file that has a memory-mapped section open
-        // Create temporary empty files to replace the mappings
-        let temp_dir = tempfile::tempdir()?;
-        let temp_nodes_path = temp_dir.path().join("nodes.tmp");
-        let temp_rels_path = temp_dir.path().join("rels.tmp");
-
-        // Create temporary empty files and keep them open
-        let mut temp_nodes_file = std::fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(&temp_nodes_path)?;
-        temp_nodes_file.set_len(INITIAL_NODES_FILE_SIZE as u64)?;
-        let temp_nodes_mmap = unsafe { MmapOptions::new().map_mut(&temp_nodes_file)? };
-
-        let mut temp_rels_file = std::fs::OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(&temp_rels_path)?;
-        temp_rels_file.set_len(INITIAL_RELS_FILE_SIZE as u64)?;
-        let temp_rels_mmap = unsafe { MmapOptions::new().map_mut(&temp_rels_file)? };
-
-        // Replace old mappings with temporary ones (drops old mappings) inside
-        // the shared Arc<RwLock> so every clone sees the reset. Assigning into
-        // the guard drops the previous mapping, releasing the original files.
-        *self.nodes_mmap.write().unwrap() = temp_nodes_mmap;
-        *self.rels_mmap.write().unwrap() = temp_rels_mmap;
-
-        // Now we can truncate the original files (mappings are closed)
-        self.nodes_file.set_len(INITIAL_NODES_FILE_SIZE as u64)?;
-        self.rels_file.set_len(INITIAL_RELS_FILE_SIZE as u64)?;
-
-        // Zero out the files
-        use std::io::Write;
+        // Reset the record files in the shared lock so every clone sees the
+        // reset. The backend drops any mapping before truncating (Windows
+        // cannot truncate a mapped file).
         self.nodes_file
-            .write_all(&vec![0u8; INITIAL_NODES_FILE_SIZE])?;
+            .write()
+            .unwrap()
+            .clear(INITIAL_NODES_FILE_SIZE)?;
         self.rels_file
-            .write_all(&vec![0u8; INITIAL_RELS_FILE_SIZE])?;
-        self.nodes_file.sync_all()?;
-        self.rels_file.sync_all()?;
+            .write()
+            .unwrap()
+            .clear(INITIAL_RELS_FILE_SIZE)?;
 
-        // Update file sizes
-        self.nodes_file_size = INITIAL_NODES_FILE_SIZE;
-        self.rels_file_size = INITIAL_RELS_FILE_SIZE;
+        tracing::debug!("[RecordStore::clear_all] Storage cleared successfully");
+        Ok(())
+    }
 
-        // Recreate memory mappings from original files (in the shared lock).
-        *self.nodes_mmap.write().unwrap() =
-            unsafe { MmapOptions::new().map_mut(&*self.nodes_file)? };
-        *self.rels_mmap.write().unwrap() = unsafe { MmapOptions::new().map_mut(&*self.rels_file)? };
+    /// Enum property definitions consulted by the node property codec.
+    pub fn enum_types(&self) -> &Arc<RwLock<super::enum_types::EnumRegistry>> {
+        &self.enum_types
+    }
 
-        // Drop temporary files and mappings (temp_dir will be dropped at end of scope)
-        drop(temp_nodes_file);
-        drop(temp_rels_file);
+    /// Validate enum-typed values in `properties` and replace them with
+    /// their stored ordinals for a node carrying `label_bits`. Fails with
+    /// `ConstraintViolation` when a value is outside its allowed set.
+    pub(crate) fn encode_enum_properties(
+        &self,
+        label_bits: u64,
+        properties: &mut serde_json::Value,
+    ) -> Result<()> {
+        let registry = self.enum_types.read().unwrap();
+        if registry.is_empty() {
+            return Ok(());
+        }
+        registry.encode(label_bits, properties)
+    }
 
-        tracing::debug!("[RecordStore::clear_all] Storage cleared successfully");
-        Ok(())
+    /// Map stored enum ordinals back to strings. The node record is only
+    /// re-read when at least one enum definition exists, so stores without
+    /// enums pay nothing on the read path.
+    fn decode_enum_properties(&self, node_id: u64, properties: &mut serde_json::Value) {
+        let registry = self.enum_types.read().unwrap();
+        if registry.is_empty() {
+            return;
+        }
+        if let Ok(record) = self.read_node(node_id) {
+            registry.decode(record.label_bits, properties);
+        }
     }
 
     /// Load properties for a node
@@ -1108,7 +1185,7 @@ impl RecordStore {
     /// `Executor::read_node_as_value` (the single most-called node
     /// materialiser in the executor — every scan, expand hop, and index
     /// seek routes through it) already has that `NodeRecord` in hand, so
-    /// that internal re-read was a second `nodes_mmap` lock acquisition
+    /// that internal re-read was a second `nodes_file` lock acquisition
     /// (plus a second `property_store` corruption cross-check) on every
     /// single node materialisation. Multiplied across every node a scan
     /// or expand hop touches, this was a meaningful share of the
@@ -1123,12 +1200,62 @@ impl RecordStore {
         self.load_node_properties_inner(node_id, Some(prop_ptr))
     }
 
+    /// Same as [`Self::load_node_properties_with_ptr`], but only `keys`
+    /// are deserialized; every other stored property is skipped. Used by
+    /// the executor when the query never reads the node as a whole
+    /// (`MATCH (n:L) RETURN n.name`). Whenever `prop_ptr` cannot be
+    /// trusted the full, validated load runs and is trimmed afterwards,
+    /// so the result always equals the full map restricted to `keys`.
+    pub fn load_node_properties_projected(
+        &self,
+        node_id: u64,
+        prop_ptr: u64,
+        keys: &[String],
+    ) -> Result<Option<serde_json::Value>> {
+        if prop_ptr != 0 {
+            let projected = {
+                let prop_guard = self.property_store.read().unwrap();
+                match prop_guard.get_entity_info_at_offset(prop_ptr) {
+                    Some((id, property_store::EntityType::Node)) if id == node_id => prop_guard
+                        .load_projected_properties_at_offset(prop_ptr, keys)
+                        .ok()
+                        .flatten(),
+                    _ => None,
+                }
+            };
+            if let Some(mut props) = projected {
+                self.decode_enum_properties(node_id, &mut props);
+                return Ok(Some(props));
+            }
+        }
+
+        let mut result = self.load_node_properties_inner(node_id, Some(prop_ptr))?;
+        if let Some(serde_json::Value::Object(map)) = result.as_mut() {
+            map.retain(|key, _| keys.contains(key));
+        }
+        Ok(result)
+    }
+
     /// Shared body of [`Self::load_node_properties`] and
-    /// [`Self::load_node_properties_with_ptr`]. `prop_ptr = None` means
-    /// "the caller could not read a `NodeRecord` at all" (mirrors the
+    /// [`Self::load_node_properties_with_ptr`]: loads the stored map and
+    /// maps enum ordinals back to their string values.
+    fn load_node_properties_inner(
+        &self,
+        node_id: u64,
+        prop_ptr: Option<u64>,
+    ) -> Result<Option<serde_json::Value>> {
+        let mut result = self.load_node_properties_raw(node_id, prop_ptr)?;
+        if let Some(props) = result.as_mut() {
+            self.decode_enum_properties(node_id, props);
+        }
+        Ok(result)
+    }
+
+    /// Load the node's property map exactly as stored. `prop_ptr = None`
+    /// means "the caller could not read a `NodeRecord` at all" (mirrors the
     /// original `self.read_node(node_id)` failure branch); `Some(0)`
     /// means "read a record, but it has no properties yet".
-    fn load_node_properties_inner(
+    fn load_node_properties_raw(
         &self,
         node_id: u64,
         prop_ptr: Option<u64>,
@@ -1275,8 +1402,11 @@ impl RecordStore {
     pub fn update_node_properties(
         &mut self,
         node_id: u64,
-        properties: serde_json::Value,
+        mut properties: serde_json::Value,
     ) -> Result<()> {
+        if let Ok(record) = self.read_node(node_id) {
+            self.encode_enum_properties(record.label_bits, &mut properties)?;
+        }
         let new_prop_ptr = if properties.is_object() && !properties.as_object().unwrap().is_empty()
         {
             let prop_ptr = self.property_store.write().unwrap().store_properties(
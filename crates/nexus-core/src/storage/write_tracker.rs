@@ -0,0 +1,138 @@
+//! Which labels and relationship types the store's writes touched.
+//!
+//! A materialized view refreshed `ON CHANGE` only needs recomputing
+//! when a write touched a label or relationship type its query reads.
+//! While a [`WriteTracker`] is enabled the record store feeds it from
+//! every node, relationship and property write, and the engine takes
+//! the accumulated [`TrackedWrites`] after each write statement
+//! (see [`super::RecordStore::take_tracked_writes`]). Disabled, as it is
+//! until the engine has a view to maintain, every call is a single
+//! atomic load.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::property_store::EntityType;
+
+/// Writes accumulated by a [`WriteTracker`] since it was last taken.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackedWrites {
+    /// Labels of the written node records, before and after each write,
+    /// one bit per label id as in `NodeRecord::label_bits`.
+    pub label_bits: u64,
+    /// Types of the written relationship records.
+    pub type_ids: HashSet<u32>,
+    /// Every label and type counts as written (the store was cleared).
+    pub everything: bool,
+    /// Nodes whose properties were written, resolved to their labels
+    /// when taken.
+    pub(super) property_nodes: HashSet<u64>,
+    /// Relationships whose properties were written, resolved to their
+    /// types when taken.
+    pub(super) property_rels: HashSet<u64>,
+}
+
+impl TrackedWrites {
+    /// Whether nothing was written.
+    pub fn is_empty(&self) -> bool {
+        !self.everything
+            && self.label_bits == 0
+            && self.type_ids.is_empty()
+            && self.property_nodes.is_empty()
+            && self.property_rels.is_empty()
+    }
+}
+
+/// Collects [`TrackedWrites`]; shared by every clone of a record store
+/// and its property store.
+#[derive(Debug, Clone, Default)]
+pub struct WriteTracker {
+    inner: Arc<TrackerState>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    enabled: AtomicBool,
+    writes: Mutex<TrackedWrites>,
+}
+
+impl WriteTracker {
+    /// Start or stop collecting writes. Stopping drops what was collected.
+    pub fn set_enabled(&self, enabled: bool) {
+        let was_enabled = self.inner.enabled.swap(enabled, Ordering::AcqRel);
+        if was_enabled && !enabled {
+            *self.inner.writes.lock().unwrap() = TrackedWrites::default();
+        }
+    }
+
+    /// Whether writes are being collected.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Acquire)
+    }
+
+    /// A node record carrying `label_bits` was written.
+    pub(crate) fn node_written(&self, label_bits: u64) {
+        if self.is_enabled() {
+            self.inner.writes.lock().unwrap().label_bits |= label_bits;
+        }
+    }
+
+    /// A relationship record of type `type_id` was written.
+    pub(crate) fn rel_written(&self, type_id: u32) {
+        if self.is_enabled() {
+            self.inner.writes.lock().unwrap().type_ids.insert(type_id);
+        }
+    }
+
+    /// The properties of `entity_id` were written.
+    pub(crate) fn properties_written(&self, entity_id: u64, entity_type: EntityType) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut writes = self.inner.writes.lock().unwrap();
+        match entity_type {
+            EntityType::Node => writes.property_nodes.insert(entity_id),
+            EntityType::Relationship => writes.property_rels.insert(entity_id),
+        };
+    }
+
+    /// Everything was written, e.g. the store was cleared.
+    pub(crate) fn everything_written(&self) {
+        if self.is_enabled() {
+            self.inner.writes.lock().unwrap().everything = true;
+        }
+    }
+
+    /// The writes collected so far, leaving the tracker empty.
+    pub(crate) fn take(&self) -> TrackedWrites {
+        std::mem::take(&mut *self.inner.writes.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_only_while_enabled() {
+        let tracker = WriteTracker::default();
+        tracker.node_written(0b1);
+        assert!(tracker.take().is_empty());
+
+        tracker.set_enabled(true);
+        let shared = tracker.clone();
+        shared.node_written(0b10);
+        shared.rel_written(3);
+        shared.properties_written(7, EntityType::Node);
+        let writes = tracker.take();
+        assert_eq!(writes.label_bits, 0b10);
+        assert_eq!(writes.type_ids, HashSet::from([3]));
+        assert_eq!(writes.property_nodes, HashSet::from([7]));
+        assert!(tracker.take().is_empty());
+
+        tracker.rel_written(4);
+        tracker.set_enabled(false);
+        assert!(tracker.take().is_empty());
+    }
+}
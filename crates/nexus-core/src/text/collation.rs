@@ -0,0 +1,267 @@
+//! Locale-aware string comparison.
+//!
+//! A compact take on the Unicode Collation Algorithm. Strings are
+//! compared level by level:
+//!
+//! 1. **primary** — base letters; accents and case are ignored, so
+//!    `"resume"`, `"résumé"` and `"RESUME"` tie;
+//! 2. **secondary** — accents (unaccented first);
+//! 3. **tertiary** — case (lower case first).
+//!
+//! Comparison runs on the NFC form, so canonically equivalent spellings
+//! always compare equal. German `ß` expands to `ss`. Locale tailorings
+//! cover letters a language sorts as separate letters rather than
+//! accented variants — Swedish / Finnish `å ä ö`, Danish / Norwegian
+//! `æ ø å`, Spanish `ñ`, Turkish `ç ğ ı ö ş ü` and Polish `ą ć ę ł ń ó ś
+//! ź ż`; other locales use the untailored root order. Contractions
+//! (traditional Spanish `ch`), ignorable punctuation and script
+//! reordering are not modelled.
+
+use crate::{Error, Result};
+use std::cmp::Ordering;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::canonical_combining_class;
+
+/// How many collation levels a comparison considers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    /// Base letters only.
+    Primary,
+    /// Base letters and accents.
+    Secondary,
+    /// Base letters, accents and case.
+    #[default]
+    Tertiary,
+}
+
+impl std::str::FromStr for Strength {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "primary" => Ok(Self::Primary),
+            "secondary" => Ok(Self::Secondary),
+            "tertiary" => Ok(Self::Tertiary),
+            other => Err(Error::CypherExecution(format!(
+                "ERR_INVALID_ARG_VALUE: unknown collation strength {other:?} (expected \
+                 primary, secondary or tertiary)"
+            ))),
+        }
+    }
+}
+
+/// Letters sorted after `base` instead of as its accented variants;
+/// the position in the list breaks ties between letters sharing a base.
+type Tailoring = &'static [(char, char)];
+
+const NORDIC_SV: Tailoring = &[('å', 'z'), ('ä', 'z'), ('ö', 'z'), ('æ', 'z'), ('ø', 'z')];
+const NORDIC_DA: Tailoring = &[('æ', 'z'), ('ø', 'z'), ('å', 'z'), ('ä', 'z'), ('ö', 'z')];
+const SPANISH: Tailoring = &[('ñ', 'n')];
+const TURKISH: Tailoring = &[
+    ('ç', 'c'),
+    ('ğ', 'g'),
+    ('ı', 'h'),
+    ('ö', 'o'),
+    ('ş', 's'),
+    ('ü', 'u'),
+];
+const POLISH: Tailoring = &[
+    ('ą', 'a'),
+    ('ć', 'c'),
+    ('ę', 'e'),
+    ('ł', 'l'),
+    ('ń', 'n'),
+    ('ó', 'o'),
+    ('ś', 's'),
+    ('ź', 'z'),
+    ('ż', 'z'),
+];
+
+/// Separates the levels of a collation key. Lower than every encoded
+/// weight, so a key that runs out first sorts first.
+const LEVEL_SEPARATOR: char = '\u{1}';
+
+/// Compares strings for one locale and strength.
+#[derive(Debug, Clone, Copy)]
+pub struct Collator {
+    tailoring: Tailoring,
+    strength: Strength,
+}
+
+/// Weights of one collation element (a base letter with its accents).
+struct Element {
+    primary: u32,
+    secondary: u32,
+    tertiary: u32,
+}
+
+impl Collator {
+    /// Collator for a BCP 47 language tag (`"sv"`, `"es-MX"`, `"de_DE"`).
+    /// Only the language subtag matters; unknown or empty tags use the
+    /// root order.
+    pub fn new(locale: &str, strength: Strength) -> Self {
+        let language = locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let tailoring = match language.as_str() {
+            "sv" | "fi" => NORDIC_SV,
+            "da" | "nb" | "nn" | "no" => NORDIC_DA,
+            "es" => SPANISH,
+            "tr" | "az" => TURKISH,
+            "pl" => POLISH,
+            _ => &[],
+        };
+        Self {
+            tailoring,
+            strength,
+        }
+    }
+
+    /// Compare `a` and `b` under this collator.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        self.key(a).cmp(&self.key(b))
+    }
+
+    /// Sort key whose plain string order (code point order, as used by
+    /// `ORDER BY`) matches [`Self::compare`]. Opaque: only comparisons
+    /// between keys from the same locale and strength are meaningful.
+    pub fn key(&self, s: &str) -> String {
+        let elements = self.elements(s);
+        let mut key = String::with_capacity(elements.len() * 2 * 4);
+        let levels = match self.strength {
+            Strength::Primary => 1,
+            Strength::Secondary => 2,
+            Strength::Tertiary => 3,
+        };
+        for level in 0..levels {
+            if level > 0 {
+                key.push(LEVEL_SEPARATOR);
+            }
+            for element in &elements {
+                let weight = match level {
+                    0 => element.primary,
+                    1 => element.secondary,
+                    _ => element.tertiary,
+                };
+                push_weight(&mut key, weight);
+            }
+        }
+        key
+    }
+
+    fn elements(&self, s: &str) -> Vec<Element> {
+        let mut elements: Vec<Element> = Vec::with_capacity(s.len());
+        for c in s.nfc() {
+            let tertiary = u32::from(c.is_uppercase());
+            for lower in c.to_lowercase() {
+                if let Some(rank) = self.tailoring.iter().position(|&(l, _)| l == lower) {
+                    let base = self.tailoring[rank].1;
+                    elements.push(Element {
+                        primary: ((base as u32) << 8) | (rank as u32 + 1),
+                        secondary: 0,
+                        tertiary,
+                    });
+                    continue;
+                }
+                if lower == 'ß' {
+                    for secondary in [1, 0] {
+                        elements.push(Element {
+                            primary: ('s' as u32) << 8,
+                            secondary,
+                            tertiary,
+                        });
+                    }
+                    continue;
+                }
+                for d in std::iter::once(lower).nfd() {
+                    // Accents fold into the preceding base letter; only
+                    // the first one counts at the secondary level.
+                    if canonical_combining_class(d) != 0
+                        && let Some(last) = elements.last_mut()
+                    {
+                        if last.secondary == 0 {
+                            last.secondary = d as u32;
+                        }
+                        continue;
+                    }
+                    elements.push(Element {
+                        primary: (d as u32) << 8,
+                        secondary: 0,
+                        tertiary,
+                    });
+                }
+            }
+        }
+        elements
+    }
+}
+
+/// Append `weight` as two supplementary-plane characters (15 bits
+/// each), so keys stay valid UTF-8 and compare in weight order.
+fn push_weight(key: &mut String, weight: u32) {
+    for part in [weight >> 15, weight & 0x7FFF] {
+        key.push(char::from_u32(0x1_0000 + part).expect("supplementary-plane code point"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(locale: &str, words: &[&str]) -> Vec<String> {
+        let collator = Collator::new(locale, Strength::Tertiary);
+        let mut words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        words.sort_by(|a, b| collator.compare(a, b));
+        words
+    }
+
+    #[test]
+    fn root_order_groups_accents_and_case_with_base_letter() {
+        assert_eq!(
+            sorted("", &["zebra", "Äpfel", "apple", "Apple", "able"]),
+            vec!["able", "Äpfel", "apple", "Apple", "zebra"]
+        );
+    }
+
+    #[test]
+    fn tailorings_move_letters_after_their_base() {
+        assert_eq!(
+            sorted("sv-SE", &["ärlig", "zon", "åka", "oxe", "öga"]),
+            vec!["oxe", "zon", "åka", "ärlig", "öga"]
+        );
+        assert_eq!(
+            sorted("es", &["oso", "ñu", "nube"]),
+            vec!["nube", "ñu", "oso"]
+        );
+    }
+
+    #[test]
+    fn strength_controls_which_differences_count() {
+        let primary = Collator::new("de", Strength::Primary);
+        assert_eq!(primary.compare("Résumé", "resume"), Ordering::Equal);
+        assert_eq!(primary.compare("Straße", "strasse"), Ordering::Equal);
+        let secondary = Collator::new("de", Strength::Secondary);
+        assert_eq!(secondary.compare("RESUME", "resume"), Ordering::Equal);
+        assert_eq!(secondary.compare("résumé", "resume"), Ordering::Greater);
+        let tertiary = Collator::new("de", Strength::Tertiary);
+        assert_eq!(tertiary.compare("Resume", "resume"), Ordering::Greater);
+        assert_eq!(tertiary.compare("e\u{301}", "\u{e9}"), Ordering::Equal);
+    }
+
+    #[test]
+    fn key_order_matches_compare() {
+        let collator = Collator::new("sv", Strength::Tertiary);
+        let words = ["a", "ab", "Å", "å", "z", "Z", "ä", "é", "e"];
+        for a in words {
+            for b in words {
+                assert_eq!(
+                    collator.key(a).cmp(&collator.key(b)),
+                    collator.compare(a, b),
+                    "{a} vs {b}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Unicode text handling shared by the engine and the Cypher runtime.
+//!
+//! Strings are stored byte-for-byte by default: `"é"` written as U+00E9
+//! and as `e` + U+0301 are different values, and `ORDER BY` compares
+//! code points. Two opt-in tools make international data behave
+//! predictably:
+//!
+//! - [`StringNormalization`] — the engine rewrites every string that
+//!   enters it (query text, parameters, CRUD property maps) to one
+//!   Unicode normal form, so canonically equivalent spellings store and
+//!   match as the same value.
+//! - [`collation`] — locale-aware comparison behind the `collate()` and
+//!   `collationKey()` Cypher functions.
+
+pub mod collation;
+
+pub use collation::{Collator, Strength};
+
+use crate::{Error, Result};
+use serde_json::Value;
+use std::borrow::Cow;
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normal form applied to strings on their way into the engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StringNormalization {
+    /// Store strings exactly as received.
+    #[default]
+    Off,
+    /// Canonical composition — the usual choice.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility composition; also folds ligatures, full-width
+    /// forms and similar variants.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl std::str::FromStr for StringNormalization {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" => Ok(Self::Off),
+            "nfc" => Ok(Self::Nfc),
+            "nfd" => Ok(Self::Nfd),
+            "nfkc" => Ok(Self::Nfkc),
+            "nfkd" => Ok(Self::Nfkd),
+            other => Err(Error::invalid_input(format!(
+                "unknown string normalization {other:?} (expected off, nfc, nfd, nfkc or nfkd)"
+            ))),
+        }
+    }
+}
+
+impl StringNormalization {
+    /// Normalize `s`, borrowing it when it is already in this form.
+    pub fn apply(self, s: &str) -> Cow<'_, str> {
+        let normalized = match self {
+            Self::Off => true,
+            Self::Nfc => unicode_normalization::is_nfc(s),
+            Self::Nfd => unicode_normalization::is_nfd(s),
+            Self::Nfkc => unicode_normalization::is_nfkc(s),
+            Self::Nfkd => unicode_normalization::is_nfkd(s),
+        };
+        if normalized {
+            return Cow::Borrowed(s);
+        }
+        Cow::Owned(match self {
+            Self::Off | Self::Nfc => s.nfc().collect(),
+            Self::Nfd => s.nfd().collect(),
+            Self::Nfkc => s.nfkc().collect(),
+            Self::Nfkd => s.nfkd().collect(),
+        })
+    }
+
+    /// Normalize every string in `value` in place, including strings
+    /// nested in lists and maps. Map keys are left as they are.
+    pub fn apply_to_value(self, value: &mut Value) {
+        if self == Self::Off {
+            return;
+        }
+        match value {
+            Value::String(s) => {
+                if let Cow::Owned(normalized) = self.apply(s) {
+                    *s = normalized;
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.apply_to_value(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.apply_to_value(v)),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nfc_composes_and_borrows_normalized_input() {
+        let decomposed = "Cafe\u{301}";
+        assert_eq!(StringNormalization::Nfc.apply(decomposed), "Caf\u{e9}");
+        assert!(matches!(
+            StringNormalization::Nfc.apply("Caf\u{e9}"),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(StringNormalization::Off.apply(decomposed), decomposed);
+    }
+
+    #[test]
+    fn apply_to_value_reaches_nested_strings() {
+        let mut value = json!({"name": "e\u{301}", "tags": ["o\u{308}", 1], "n": null});
+        StringNormalization::Nfc.apply_to_value(&mut value);
+        assert_eq!(
+            value,
+            json!({"name": "\u{e9}", "tags": ["\u{f6}", 1], "n": null})
+        );
+    }
+
+    #[test]
+    fn parses_config_spellings() {
+        assert_eq!(
+            "NFC".parse::<StringNormalization>().unwrap(),
+            StringNormalization::Nfc
+        );
+        assert_eq!(
+            "none".parse::<StringNormalization>().unwrap(),
+            StringNormalization::Off
+        );
+        assert!("nfx".parse::<StringNormalization>().is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! Commit hooks for embedders.
+//!
+//! A [`CommitHook`] registered on a [`super::TransactionManager`] sees
+//! every write transaction that commits with a non-empty
+//! [`MutationSet`]: [`CommitHook::before_commit`] runs before the epoch
+//! advances and can refuse the commit, [`CommitHook::after_commit`] runs
+//! once it has taken effect. That is enough for cache invalidation, an
+//! outbox table or application-level validation without forking the
+//! engine.
+//!
+//! The mutation set lists the entities a transaction created, updated
+//! or deleted, by id; hooks read current state through the engine if
+//! they need more. Storage applies records as a statement runs, so a
+//! refused commit is undone by rolling the transaction back. While
+//! hooks are registered the engine runs every auto-commit write
+//! statement as an implicit transaction for that reason: a veto rolls
+//! it back like an explicit `ROLLBACK`, so the nodes and relationships
+//! it created are gone.
+//!
+//! The engine and its executor each own a transaction manager. They
+//! share one [`CommitHooks`] registry, so a hook registered on the
+//! engine's manager sees both. Inside a transaction the registry defers
+//! delivery for that session: the mutations of each statement it runs
+//! (inside [`CommitHooks::enter_session`]) are collected and handed to
+//! the hooks once, with the `COMMIT`, or dropped on `ROLLBACK`. The
+//! running session is tracked per thread, so commits made on other
+//! threads meanwhile, like other sessions' writes or the TTL sweeper,
+//! are delivered as usual.
+
+use super::Transaction;
+use crate::Result;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread::ThreadId;
+
+/// One change made by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mutation {
+    /// A node was created with these label ids.
+    NodeCreated {
+        /// New node id
+        node_id: u64,
+        /// Label ids of the new node
+        label_ids: Vec<u32>,
+    },
+    /// A node's labels or properties were rewritten.
+    NodeUpdated {
+        /// Node id
+        node_id: u64,
+    },
+    /// A node was deleted.
+    NodeDeleted {
+        /// Node id
+        node_id: u64,
+    },
+    /// A relationship was created.
+    RelationshipCreated {
+        /// New relationship id
+        rel_id: u64,
+        /// Source node id
+        src: u64,
+        /// Target node id
+        dst: u64,
+        /// Relationship type id
+        type_id: u32,
+    },
+    /// A relationship was deleted.
+    RelationshipDeleted {
+        /// Relationship id
+        rel_id: u64,
+    },
+}
+
+/// The changes of one transaction, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MutationSet {
+    mutations: Vec<Mutation>,
+}
+
+impl MutationSet {
+    /// Append a change.
+    pub fn push(&mut self, mutation: Mutation) {
+        self.mutations.push(mutation);
+    }
+
+    /// Append every change of `other`.
+    pub fn extend(&mut self, other: MutationSet) {
+        self.mutations.extend(other.mutations);
+    }
+
+    /// Whether nothing was changed.
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+
+    /// Number of changes.
+    pub fn len(&self) -> usize {
+        self.mutations.len()
+    }
+
+    /// The changes, oldest first.
+    pub fn iter(&self) -> std::slice::Iter<'_, Mutation> {
+        self.mutations.iter()
+    }
+
+    /// Drop every change.
+    pub fn clear(&mut self) {
+        self.mutations.clear();
+    }
+}
+
+impl<'a> IntoIterator for &'a MutationSet {
+    type Item = &'a Mutation;
+    type IntoIter = std::slice::Iter<'a, Mutation>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Callbacks around the commit of a write transaction.
+///
+/// Hooks run on the committing thread while the transaction manager is
+/// locked, so they must not begin or commit transactions themselves.
+pub trait CommitHook: Send + Sync {
+    /// Runs before `tx` commits. An error refuses the commit: it is
+    /// returned to the writer and `tx` stays active so the caller can
+    /// roll it back. Later hooks do not run.
+    fn before_commit(&self, tx: &Transaction) -> Result<()> {
+        let _ = tx;
+        Ok(())
+    }
+
+    /// Runs after `tx` committed. `commit_epoch` is the epoch the
+    /// commit produced.
+    fn after_commit(&self, tx: &Transaction, commit_epoch: u64) {
+        let _ = (tx, commit_epoch);
+    }
+}
+
+/// Handle returned by [`CommitHooks::add`], for removing the hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CommitHookId(u64);
+
+/// [`CommitHook::before_commit`] from a closure.
+struct BeforeCommitFn<F>(F);
+
+impl<F> CommitHook for BeforeCommitFn<F>
+where
+    F: Fn(&Transaction) -> Result<()> + Send + Sync,
+{
+    fn before_commit(&self, tx: &Transaction) -> Result<()> {
+        (self.0)(tx)
+    }
+}
+
+/// [`CommitHook::after_commit`] from a closure.
+struct AfterCommitFn<F>(F);
+
+impl<F> CommitHook for AfterCommitFn<F>
+where
+    F: Fn(&Transaction, u64) + Send + Sync,
+{
+    fn after_commit(&self, tx: &Transaction, commit_epoch: u64) {
+        (self.0)(tx, commit_epoch)
+    }
+}
+
+#[derive(Default)]
+struct HooksInner {
+    hooks: Vec<(CommitHookId, Arc<dyn CommitHook>)>,
+    next_id: u64,
+    /// Mutations collected per session with an open explicit
+    /// transaction.
+    deferred: HashMap<String, MutationSet>,
+    /// Session whose statement each thread is running.
+    current: HashMap<ThreadId, String>,
+}
+
+/// Registered commit hooks, shared by every transaction manager of one
+/// engine.
+///
+/// Cheap to clone; clones share the same state.
+#[derive(Clone, Default)]
+pub struct CommitHooks {
+    inner: Arc<Mutex<HooksInner>>,
+}
+
+impl std::fmt::Debug for CommitHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.lock();
+        f.debug_struct("CommitHooks")
+            .field("hooks", &inner.hooks.len())
+            .field("deferring", &inner.deferred.len())
+            .finish()
+    }
+}
+
+impl CommitHooks {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `hook`. Hooks run in registration order.
+    pub fn add(&self, hook: Arc<dyn CommitHook>) -> CommitHookId {
+        let mut inner = self.inner.lock();
+        let id = CommitHookId(inner.next_id);
+        inner.next_id += 1;
+        inner.hooks.push((id, hook));
+        id
+    }
+
+    /// Register a closure as a [`CommitHook::before_commit`] hook.
+    pub fn on_before_commit<F>(&self, f: F) -> CommitHookId
+    where
+        F: Fn(&Transaction) -> Result<()> + Send + Sync + 'static,
+    {
+        self.add(Arc::new(BeforeCommitFn(f)))
+    }
+
+    /// Register a closure as a [`CommitHook::after_commit`] hook.
+    pub fn on_after_commit<F>(&self, f: F) -> CommitHookId
+    where
+        F: Fn(&Transaction, u64) + Send + Sync + 'static,
+    {
+        self.add(Arc::new(AfterCommitFn(f)))
+    }
+
+    /// Unregister a hook. Returns whether it was registered.
+    pub fn remove(&self, id: CommitHookId) -> bool {
+        let mut inner = self.inner.lock();
+        let before = inner.hooks.len();
+        inner.hooks.retain(|(hook_id, _)| *hook_id != id);
+        inner.hooks.len() != before
+    }
+
+    /// Number of registered hooks.
+    pub fn len(&self) -> usize {
+        self.inner.lock().hooks.len()
+    }
+
+    /// Whether no hook is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Start collecting the mutations of `session`'s statements instead
+    /// of delivering them, for an explicit transaction. Mutations
+    /// already collected are kept.
+    pub fn begin_deferral(&self, session: &str) {
+        self.inner
+            .lock()
+            .deferred
+            .entry(session.to_string())
+            .or_default();
+    }
+
+    /// Stop collecting for `session` and return what was collected.
+    pub fn end_deferral(&self, session: &str) -> MutationSet {
+        self.inner
+            .lock()
+            .deferred
+            .remove(session)
+            .unwrap_or_default()
+    }
+
+    /// Attribute commits made on this thread to `session` until the
+    /// returned scope is dropped. Wrap each statement a session runs in
+    /// one.
+    pub fn enter_session(&self, session: &str) -> SessionScope {
+        let thread = std::thread::current().id();
+        let previous = self
+            .inner
+            .lock()
+            .current
+            .insert(thread, session.to_string());
+        SessionScope {
+            hooks: self.clone(),
+            thread,
+            previous,
+        }
+    }
+
+    /// Collect `tx`'s mutations when the session whose statement this
+    /// thread runs is deferring. Returns whether they were taken, in
+    /// which case the hooks must not run for `tx`.
+    pub(super) fn defer(&self, tx: &Transaction) -> bool {
+        let mut inner = self.inner.lock();
+        let inner = &mut *inner;
+        match inner
+            .current
+            .get(&std::thread::current().id())
+            .and_then(|session| inner.deferred.get_mut(session))
+        {
+            Some(deferred) => {
+                deferred.extend(tx.mutations.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn snapshot(&self) -> Vec<Arc<dyn CommitHook>> {
+        self.inner
+            .lock()
+            .hooks
+            .iter()
+            .map(|(_, hook)| Arc::clone(hook))
+            .collect()
+    }
+
+    pub(super) fn run_before_commit(&self, tx: &Transaction) -> Result<()> {
+        for hook in self.snapshot() {
+            hook.before_commit(tx)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn run_after_commit(&self, tx: &Transaction, commit_epoch: u64) {
+        for hook in self.snapshot() {
+            hook.after_commit(tx, commit_epoch);
+        }
+    }
+}
+
+/// A statement running for a session, from [`CommitHooks::enter_session`].
+/// Restores the previous session when dropped.
+#[must_use = "commits are attributed to the session only while the scope is alive"]
+pub struct SessionScope {
+    hooks: CommitHooks,
+    thread: ThreadId,
+    previous: Option<String>,
+}
+
+impl Drop for SessionScope {
+    fn drop(&mut self) {
+        let mut inner = self.hooks.inner.lock();
+        match self.previous.take() {
+            Some(previous) => inner.current.insert(self.thread, previous),
+            None => inner.current.remove(&self.thread),
+        };
+    }
+}
@@ -15,13 +15,19 @@
 //! - Read transactions pin current epoch (snapshot isolation)
 //! - Write transactions increment epoch on commit
 //! - Garbage collection removes old versions (created_epoch < min_active_epoch)
+//!
+//! Write transactions carry the [`MutationSet`] of what they changed;
+//! [`CommitHook`]s registered on the manager see it around each commit
+//! (see [`hooks`]).
 
 use crate::{Error, Result};
 use parking_lot::Mutex;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+pub mod hooks;
 pub mod savepoint;
+pub use hooks::{CommitHook, CommitHookId, CommitHooks, Mutation, MutationSet, SessionScope};
 pub use savepoint::{SavepointMarker, SavepointStack};
 
 /// Transaction state
@@ -55,6 +61,8 @@ pub struct Transaction {
     pub tx_type: TxType,
     /// Transaction state
     pub state: TxState,
+    /// Changes made so far, handed to the commit hooks
+    pub mutations: MutationSet,
 }
 
 impl Transaction {
@@ -65,9 +73,15 @@ impl Transaction {
             epoch,
             tx_type,
             state: TxState::Active,
+            mutations: MutationSet::default(),
         }
     }
 
+    /// Record a change for the commit hooks
+    pub fn record(&mut self, mutation: Mutation) {
+        self.mutations.push(mutation);
+    }
+
     /// Check if transaction is active
     pub fn is_active(&self) -> bool {
         self.state == TxState::Active
@@ -134,6 +148,9 @@ pub struct TransactionManager {
 
     /// Statistics
     stats: TransactionStats,
+
+    /// Commit hooks, possibly shared with other managers
+    hooks: CommitHooks,
 }
 
 /// Transaction statistics
@@ -166,6 +183,7 @@ impl TransactionManager {
             epoch_manager: Arc::new(EpochManager::new()),
             write_lock: Arc::new(Mutex::new(())),
             stats: TransactionStats::default(),
+            hooks: CommitHooks::new(),
         })
     }
 
@@ -197,7 +215,11 @@ impl TransactionManager {
 
     /// Commit a transaction
     ///
-    /// For write transactions, increments the global epoch.
+    /// For write transactions, increments the global epoch. A write
+    /// transaction with mutations runs the commit hooks around it, unless
+    /// the hooks are collecting them for an explicit transaction; a
+    /// refusing `before_commit` hook leaves `tx` active and returns its
+    /// error.
     pub fn commit(&mut self, tx: &mut Transaction) -> Result<()> {
         if tx.state != TxState::Active {
             return Err(Error::transaction(format!(
@@ -206,6 +228,12 @@ impl TransactionManager {
             )));
         }
 
+        let run_hooks =
+            tx.tx_type == TxType::Write && !tx.mutations.is_empty() && !self.hooks.defer(tx);
+        if run_hooks {
+            self.hooks.run_before_commit(tx)?;
+        }
+
         // Increment epoch for write transactions
         if tx.tx_type == TxType::Write {
             let new_epoch = self.epoch_manager.increment_epoch();
@@ -215,9 +243,24 @@ impl TransactionManager {
         tx.state = TxState::Committed;
         self.stats.txs_committed += 1;
 
+        if run_hooks {
+            self.hooks.run_after_commit(tx, self.stats.current_epoch);
+        }
+
         Ok(())
     }
 
+    /// The commit hooks run by this manager. Register hooks here.
+    pub fn commit_hooks(&self) -> &CommitHooks {
+        &self.hooks
+    }
+
+    /// Run `hooks` instead of this manager's own registry, so several
+    /// managers of one engine deliver to the same hooks.
+    pub fn share_commit_hooks(&mut self, hooks: CommitHooks) {
+        self.hooks = hooks;
+    }
+
     /// Abort a transaction
     pub fn abort(&mut self, tx: &mut Transaction) -> Result<()> {
         if tx.state != TxState::Active {
@@ -619,6 +662,80 @@ mod tests {
         assert_eq!(sorted_ids.len(), 100);
     }
 
+    #[test]
+    fn test_commit_hooks_see_mutations_and_can_refuse() {
+        let mut mgr = TransactionManager::new().unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        mgr.commit_hooks().on_after_commit(move |tx, epoch| {
+            sink.lock().push((tx.mutations.len(), epoch));
+        });
+
+        // Nothing changed: no hook call.
+        let mut empty = mgr.begin_write().unwrap();
+        mgr.commit(&mut empty).unwrap();
+
+        let mut tx = mgr.begin_write().unwrap();
+        tx.record(Mutation::NodeDeleted { node_id: 7 });
+        mgr.commit(&mut tx).unwrap();
+        assert_eq!(*seen.lock(), vec![(1, 2)]);
+
+        let veto = mgr
+            .commit_hooks()
+            .on_before_commit(|_| Err(Error::transaction("no")));
+        let mut tx = mgr.begin_write().unwrap();
+        tx.record(Mutation::NodeUpdated { node_id: 7 });
+        assert!(mgr.commit(&mut tx).is_err());
+        assert!(tx.is_active());
+        assert_eq!(mgr.current_epoch(), 2);
+        assert!(mgr.commit_hooks().remove(veto));
+        mgr.abort(&mut tx).unwrap();
+    }
+
+    #[test]
+    fn test_deferred_mutations_are_collected_across_managers() {
+        let mut mgr = TransactionManager::new().unwrap();
+        let mut other = TransactionManager::new().unwrap();
+        other.share_commit_hooks(mgr.commit_hooks().clone());
+        let calls = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&calls);
+        mgr.commit_hooks().on_after_commit(move |_, _| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        mgr.commit_hooks().begin_deferral("s1");
+        let scope = mgr.commit_hooks().enter_session("s1");
+        let mut tx = other.begin_write().unwrap();
+        tx.record(Mutation::RelationshipDeleted { rel_id: 3 });
+        other.commit(&mut tx).unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+        // A commit another thread makes meanwhile is not the session's.
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let mut tx = other.begin_write().unwrap();
+                tx.record(Mutation::NodeDeleted { node_id: 5 });
+                other.commit(&mut tx).unwrap();
+            });
+        });
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        drop(scope);
+
+        // Commits outside the session's statements are not held back.
+        let _other_session = mgr.commit_hooks().enter_session("s2");
+        let mut tx = other.begin_write().unwrap();
+        tx.record(Mutation::NodeDeleted { node_id: 4 });
+        other.commit(&mut tx).unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        let mut outer = mgr.begin_write().unwrap();
+        outer
+            .mutations
+            .extend(mgr.commit_hooks().end_deferral("s1"));
+        assert_eq!(outer.mutations.len(), 1);
+        mgr.commit(&mut outer).unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
     #[test]
     fn test_mixed_read_write_transactions() {
         let mut mgr = TransactionManager::new().unwrap();
@@ -504,6 +504,9 @@ impl GraphValidator {
             PropertyValue::Bytes(_) => {
                 // Bytes values are valid
             }
+            PropertyValue::List(_) | PropertyValue::Map(_) => {
+                // List and map values are valid
+            }
         }
 
         Ok(())
@@ -110,7 +110,8 @@ impl NexusValue {
     /// Decode a [`serde_json::Value`] into a [`NexusValue`]. The
     /// JSON-wire BYTES shape (`{"_bytes": "<base64>"}`) round-trips
     /// back into `Bytes`; every other JSON shape maps to the matching
-    /// variant. Numeric precision is preserved for i64 / u64 / f64.
+    /// variant. Numeric precision is preserved for i64 and f64; a u64
+    /// above `i64::MAX` becomes the nearest `Float`.
     pub fn from_json(v: &Value) -> Self {
         match v {
             Value::Null => NexusValue::Null,
@@ -119,7 +120,9 @@ impl NexusValue {
                 if let Some(i) = n.as_i64() {
                     NexusValue::Int(i)
                 } else if let Some(u) = n.as_u64() {
-                    NexusValue::Int(u as i64)
+                    // Beyond i64::MAX: a FLOAT keeps the magnitude, where
+                    // `u as i64` would wrap to a negative number.
+                    NexusValue::Float(u as f64)
                 } else {
                     NexusValue::Float(n.as_f64().unwrap_or(0.0))
                 }
@@ -218,6 +221,15 @@ pub mod tag {
     /// phase6_opencypher-advanced-types §1.2 — BYTES tag.
     /// Payload: `[tag:u8=0x0F][len:u32 LE][bytes...]`.
     pub const BYTES: u8 = 0x0F;
+    /// Small INTEGER. Payload: zigzag LEB128 varint. Emitted instead of
+    /// the fixed 8-byte [`INT`] whenever the varint is shorter (|i| below
+    /// 2^48), so counters, ages and ids cost 2–4 bytes instead of 9.
+    pub const VARINT: u8 = 0x10;
+    /// Bit-packed BOOLEAN list.
+    /// Payload: `[tag:u8=0x11][typed:u8][count:u32 LE][bits...]`, eight
+    /// elements per byte, least-significant bit first. `typed = 1`
+    /// decodes to a `TypedList` of `BOOL`, `0` to a plain `List`.
+    pub const PACKED_BOOL_LIST: u8 = 0x11;
 }
 
 /// Maximum payload size accepted by `encode` / `decode` for any
@@ -227,22 +239,40 @@ pub mod tag {
 pub const MAX_ELEMENT_BYTES: usize = 64 * 1024 * 1024;
 
 /// Encode a [`NexusValue`] into the binary property-chain format.
+/// Small integers use the [`tag::VARINT`] form and boolean lists are
+/// bit-packed ([`tag::PACKED_BOOL_LIST`]).
 /// Returns `Err(Error::Storage)` if any single string / byte slice /
 /// collection exceeds [`MAX_ELEMENT_BYTES`].
 pub fn encode(value: &NexusValue) -> crate::Result<Vec<u8>> {
     let mut out = Vec::new();
-    encode_into(value, &mut out)?;
+    encode_into(value, true, &mut out)?;
     Ok(out)
 }
 
-fn encode_into(value: &NexusValue, out: &mut Vec<u8>) -> crate::Result<()> {
+/// Encode without varint / bit-packing: every INTEGER takes the fixed
+/// 9-byte form and every BOOLEAN list element a full tag byte. The
+/// output stays decodable by [`decode`]; kept for size comparisons
+/// (`benches/property_encoding_size.rs`).
+pub fn encode_fixed_width(value: &NexusValue) -> crate::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    encode_into(value, false, &mut out)?;
+    Ok(out)
+}
+
+fn encode_into(value: &NexusValue, packed: bool, out: &mut Vec<u8>) -> crate::Result<()> {
     match value {
         NexusValue::Null => out.push(tag::NULL),
         NexusValue::Bool(false) => out.push(tag::BOOL_FALSE),
         NexusValue::Bool(true) => out.push(tag::BOOL_TRUE),
         NexusValue::Int(i) => {
-            out.push(tag::INT);
-            out.extend_from_slice(&i.to_le_bytes());
+            let zigzag = ((*i << 1) ^ (*i >> 63)) as u64;
+            if packed && zigzag < 1 << 49 {
+                out.push(tag::VARINT);
+                write_varint(zigzag, out);
+            } else {
+                out.push(tag::INT);
+                out.extend_from_slice(&i.to_le_bytes());
+            }
         }
         NexusValue::Float(f) => {
             out.push(tag::FLOAT);
@@ -255,6 +285,11 @@ fn encode_into(value: &NexusValue, out: &mut Vec<u8>) -> crate::Result<()> {
         NexusValue::Bytes(b) => {
             write_len_prefixed(tag::BYTES, b.as_ref(), out)?;
         }
+        NexusValue::List(xs)
+            if packed && !xs.is_empty() && xs.iter().all(|x| matches!(x, NexusValue::Bool(_))) =>
+        {
+            write_packed_bools(false, xs, out)?;
+        }
         NexusValue::List(xs) => {
             out.push(tag::LIST);
             let count: u32 = xs.len().try_into().map_err(|_| {
@@ -262,9 +297,16 @@ fn encode_into(value: &NexusValue, out: &mut Vec<u8>) -> crate::Result<()> {
             })?;
             out.extend_from_slice(&count.to_le_bytes());
             for x in xs.iter() {
-                encode_into(x, out)?;
+                encode_into(x, packed, out)?;
             }
         }
+        NexusValue::TypedList { elem_type, items }
+            if packed
+                && *elem_type == typed_list_elem::BOOL
+                && items.iter().all(|x| matches!(x, NexusValue::Bool(_))) =>
+        {
+            write_packed_bools(true, items, out)?;
+        }
         NexusValue::TypedList { elem_type, items } => {
             out.push(tag::TYPED_LIST);
             out.push(*elem_type);
@@ -275,7 +317,7 @@ fn encode_into(value: &NexusValue, out: &mut Vec<u8>) -> crate::Result<()> {
             })?;
             out.extend_from_slice(&count.to_le_bytes());
             for item in items.iter() {
-                encode_typed_list_elem(*elem_type, item, out)?;
+                encode_typed_list_elem(*elem_type, item, packed, out)?;
             }
         }
         NexusValue::Map(pairs) => {
@@ -287,13 +329,42 @@ fn encode_into(value: &NexusValue, out: &mut Vec<u8>) -> crate::Result<()> {
             for (k, v) in pairs.iter() {
                 let kb = k.as_bytes();
                 write_len_prefixed(tag::STRING, kb, out)?;
-                encode_into(v, out)?;
+                encode_into(v, packed, out)?;
             }
         }
     }
     Ok(())
 }
 
+fn write_varint(mut v: u64, out: &mut Vec<u8>) {
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+/// Emit a [`tag::PACKED_BOOL_LIST`]. Callers guarantee every item is a
+/// `Bool`.
+fn write_packed_bools(typed: bool, items: &[NexusValue], out: &mut Vec<u8>) -> crate::Result<()> {
+    out.push(tag::PACKED_BOOL_LIST);
+    out.push(typed as u8);
+    let count: u32 = items.len().try_into().map_err(|_| {
+        crate::Error::storage("BOOLEAN list length overflows u32 in binary encoder".to_string())
+    })?;
+    out.extend_from_slice(&count.to_le_bytes());
+    for chunk in items.chunks(8) {
+        let mut byte = 0u8;
+        for (bit, item) in chunk.iter().enumerate() {
+            if matches!(item, NexusValue::Bool(true)) {
+                byte |= 1 << bit;
+            }
+        }
+        out.push(byte);
+    }
+    Ok(())
+}
+
 fn write_len_prefixed(tag_byte: u8, payload: &[u8], out: &mut Vec<u8>) -> crate::Result<()> {
     if payload.len() > MAX_ELEMENT_BYTES {
         return Err(crate::Error::storage(format!(
@@ -316,10 +387,16 @@ fn write_len_prefixed(tag_byte: u8, payload: &[u8], out: &mut Vec<u8>) -> crate:
 ///
 /// The caller (`encode_into`) has already emitted the list header
 /// `[0x0C][elem_type:u8][count:u32]`; this function handles only
-/// each item's body.
-fn encode_typed_list_elem(elem_type: u8, v: &NexusValue, out: &mut Vec<u8>) -> crate::Result<()> {
+/// each item's body. INT elements stay fixed-width even in packed
+/// mode so element `i` remains addressable at `6 + 8 * i`.
+fn encode_typed_list_elem(
+    elem_type: u8,
+    v: &NexusValue,
+    packed: bool,
+    out: &mut Vec<u8>,
+) -> crate::Result<()> {
     match elem_type {
-        typed_list_elem::ANY => encode_into(v, out),
+        typed_list_elem::ANY => encode_into(v, packed, out),
         typed_list_elem::INT => match v {
             NexusValue::Int(i) => {
                 out.extend_from_slice(&i.to_le_bytes());
@@ -408,12 +485,35 @@ fn decode_into(buf: &[u8], cursor: &mut usize) -> crate::Result<NexusValue> {
             arr.copy_from_slice(bytes);
             Ok(NexusValue::Int(i64::from_le_bytes(arr)))
         }
+        tag::VARINT => {
+            let zigzag = read_varint(buf, cursor)?;
+            Ok(NexusValue::Int(
+                ((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64),
+            ))
+        }
         tag::FLOAT => {
             let bytes = read_slice(buf, cursor, 8)?;
             let mut arr = [0u8; 8];
             arr.copy_from_slice(bytes);
             Ok(NexusValue::Float(f64::from_le_bytes(arr)))
         }
+        tag::PACKED_BOOL_LIST => {
+            let typed = read_u8(buf, cursor)? != 0;
+            let count = read_u32(buf, cursor)? as usize;
+            let bits = read_slice(buf, cursor, count.div_ceil(8))?;
+            let items: Vec<NexusValue> = (0..count)
+                .map(|i| NexusValue::Bool(bits[i / 8] & (1 << (i % 8)) != 0))
+                .collect();
+            let items: Arc<[NexusValue]> = Arc::from(items.into_boxed_slice());
+            Ok(if typed {
+                NexusValue::TypedList {
+                    elem_type: typed_list_elem::BOOL,
+                    items,
+                }
+            } else {
+                NexusValue::List(items)
+            })
+        }
         tag::STRING => {
             let payload = read_len_prefixed(buf, cursor)?;
             let s = std::str::from_utf8(payload)
@@ -524,6 +624,20 @@ fn read_u8(buf: &[u8], cursor: &mut usize) -> crate::Result<u8> {
     Ok(b)
 }
 
+fn read_varint(buf: &[u8], cursor: &mut usize) -> crate::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_u8(buf, cursor)?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(crate::Error::storage(
+        "property decode: VARINT longer than 10 bytes".to_string(),
+    ))
+}
+
 fn read_u32(buf: &[u8], cursor: &mut usize) -> crate::Result<u32> {
     let s = read_slice(buf, cursor, 4)?;
     let mut arr = [0u8; 4];
@@ -588,6 +702,22 @@ mod tests {
         assert_eq!(b.into_json(), json!({"_bytes": "AAH/"}));
     }
 
+    #[test]
+    fn from_json_keeps_integer_range_and_never_wraps_u64() {
+        assert_eq!(
+            NexusValue::from_json(&json!(i64::MAX)),
+            NexusValue::Int(i64::MAX)
+        );
+        assert_eq!(
+            NexusValue::from_json(&json!(i64::MIN)),
+            NexusValue::Int(i64::MIN)
+        );
+        assert_eq!(
+            NexusValue::from_json(&json!(u64::MAX)),
+            NexusValue::Float(u64::MAX as f64)
+        );
+    }
+
     #[test]
     fn from_json_map_with_bytes_key_but_extra_entries_stays_map() {
         // The BYTES wire shape requires exactly one key. A map with a
@@ -773,13 +903,13 @@ mod tests {
     }
 
     #[test]
-    fn typed_list_bool_uses_one_byte_per_element() {
+    fn typed_list_bool_fixed_width_uses_one_byte_per_element() {
         let items = vec![NexusValue::Bool(true), NexusValue::Bool(false)];
         let v = NexusValue::TypedList {
             elem_type: typed_list_elem::BOOL,
             items: Arc::from(items.into_boxed_slice()),
         };
-        let buf = encode(&v).unwrap();
+        let buf = encode_fixed_width(&v).unwrap();
         // Header (6 bytes) + 2 × 1 byte = 8 bytes.
         assert_eq!(buf.len(), 8);
         assert_eq!(buf[6], 1);
@@ -787,6 +917,62 @@ mod tests {
         assert_eq!(decode(&buf).unwrap(), v);
     }
 
+    #[test]
+    fn bool_lists_are_bit_packed() {
+        let bools: Vec<NexusValue> = (0..10).map(|i| NexusValue::Bool(i % 3 == 0)).collect();
+        let typed = NexusValue::TypedList {
+            elem_type: typed_list_elem::BOOL,
+            items: Arc::from(bools.clone().into_boxed_slice()),
+        };
+        let buf = encode(&typed).unwrap();
+        // [0x11][typed=1][count=10 LE] + 2 bytes of bits.
+        assert_eq!(buf.len(), 8);
+        assert_eq!(&buf[..6], &[tag::PACKED_BOOL_LIST, 1, 10, 0, 0, 0]);
+        assert_eq!(&buf[6..], &[0b0100_1001, 0b0000_0010]);
+        assert_eq!(decode(&buf).unwrap(), typed);
+
+        // Untyped all-BOOLEAN lists pack too and decode back to LIST.
+        let untyped = NexusValue::List(Arc::from(bools.into_boxed_slice()));
+        let buf = encode(&untyped).unwrap();
+        assert_eq!(buf[1], 0);
+        assert_eq!(decode(&buf).unwrap(), untyped);
+    }
+
+    #[test]
+    fn small_integers_use_varint() {
+        assert_eq!(encode(&NexusValue::Int(0)).unwrap(), [tag::VARINT, 0]);
+        assert_eq!(encode(&NexusValue::Int(-1)).unwrap(), [tag::VARINT, 1]);
+        assert_eq!(
+            encode(&NexusValue::Int(300)).unwrap(),
+            [tag::VARINT, 0xd8, 0x04]
+        );
+        // Past the varint break-even point the fixed form wins.
+        assert_eq!(encode(&NexusValue::Int(i64::MAX)).unwrap()[0], tag::INT);
+        for i in [
+            i64::MIN,
+            -(1 << 48) - 1,
+            -(1 << 48),
+            -1,
+            0,
+            127,
+            (1 << 48) - 1,
+            1 << 48,
+            i64::MAX,
+        ] {
+            let v = NexusValue::Int(i);
+            assert_eq!(decode(&encode(&v).unwrap()).unwrap(), v, "roundtrip {i}");
+            let fixed = encode_fixed_width(&v).unwrap();
+            assert_eq!(fixed.len(), 9);
+            assert_eq!(decode(&fixed).unwrap(), v);
+        }
+    }
+
+    #[test]
+    fn varint_decode_rejects_unterminated_payload() {
+        let err = decode(&[tag::VARINT, 0x80, 0x80]).unwrap_err();
+        assert!(err.to_string().contains("unexpected EOF"));
+    }
+
     #[test]
     fn typed_list_rejects_wrong_element_type_on_encode() {
         let items = vec![NexusValue::Int(1), NexusValue::String(Arc::from("two"))];
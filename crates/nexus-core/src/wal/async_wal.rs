@@ -8,10 +8,17 @@
 //! - Batching of WAL entries with configurable batch size and timeout
 //! - Background fsync with configurable intervals
 //! - Graceful shutdown handling
+//!
+//! Every accepted entry gets a log sequence number (LSN), counted from 1
+//! per writer. The writer thread publishes the highest LSN it has
+//! fsynced, so a caller that needs durability waits for its own LSN
+//! ([`AsyncWalWriter::wait_durable`]) instead of syncing the log itself;
+//! concurrent waiters share one fsync (group commit).
 
 use crate::error::{Error, Result};
 use crate::wal::{Wal, WalEntry};
 use crossbeam_channel::{Receiver, Sender, TrySendError, bounded};
+use parking_lot::{Condvar, Mutex};
 use std::io::Write;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -22,8 +29,8 @@ use tracing;
 /// Commands sent to the WAL writer thread
 #[derive(Debug)]
 enum WalCommand {
-    /// Append a WAL entry
-    Append(WalEntry),
+    /// Append a WAL entry with its LSN
+    Append(u64, WalEntry),
     /// Force flush all pending entries
     Flush,
     /// Shutdown the writer thread
@@ -68,6 +75,10 @@ pub struct AsyncWalStats {
     pub wal_errors: std::sync::atomic::AtomicU64,
     /// Number of `append` calls that had to block on a full channel (#19).
     pub backpressure_blocks: std::sync::atomic::AtomicU64,
+    /// LSN of the last accepted entry
+    pub submitted_lsn: std::sync::atomic::AtomicU64,
+    /// LSN up to which every entry is fsynced
+    pub durable_lsn: std::sync::atomic::AtomicU64,
 }
 
 impl AsyncWalStats {
@@ -89,6 +100,8 @@ impl AsyncWalStats {
             max_queue_depth: self.max_queue_depth.load(Relaxed),
             wal_errors: self.wal_errors.load(Relaxed),
             backpressure_blocks: self.backpressure_blocks.load(Relaxed),
+            submitted_lsn: self.submitted_lsn.load(Relaxed),
+            durable_lsn: self.durable_lsn.load(Relaxed),
         }
     }
 }
@@ -110,6 +123,8 @@ pub struct AsyncWalStatsSnapshot {
     pub max_queue_depth: u64,
     pub wal_errors: u64,
     pub backpressure_blocks: u64,
+    pub submitted_lsn: u64,
+    pub durable_lsn: u64,
 }
 
 /// Configuration for the async WAL writer
@@ -139,10 +154,200 @@ impl Default for AsyncWalConfig {
     }
 }
 
+/// What the writer thread has published about durability.
+#[derive(Debug, Default)]
+struct Durability {
+    /// Every entry up to this LSN is fsynced
+    durable: u64,
+    /// Highest LSN of a batch that could not be written
+    failed: u64,
+    /// The writer thread has exited
+    closed: bool,
+}
+
+/// State shared by the writer, its handles and the writer thread.
+#[derive(Debug)]
+struct Shared {
+    sender: Sender<WalCommand>,
+    stats: Arc<AsyncWalStats>,
+    /// Last assigned LSN. Held across the send so channel order is LSN
+    /// order.
+    next_lsn: Mutex<u64>,
+    durability: Mutex<Durability>,
+    durable_changed: Condvar,
+}
+
+impl Shared {
+    fn publish(&self, last_lsn: u64, written: bool) {
+        let mut state = self.durability.lock();
+        if written {
+            state.durable = state.durable.max(last_lsn);
+            self.stats
+                .durable_lsn
+                .store(state.durable, Ordering::Relaxed);
+        } else {
+            state.failed = state.failed.max(last_lsn);
+        }
+        self.durable_changed.notify_all();
+    }
+
+    fn close(&self) {
+        self.durability.lock().closed = true;
+        self.durable_changed.notify_all();
+    }
+}
+
+/// Cloneable submit side of an [`AsyncWalWriter`], for threads other
+/// than the writer's owner (the engine's store flusher appends its
+/// markers through one). Entries appended through a handle share the
+/// writer's LSN sequence.
+#[derive(Debug, Clone)]
+pub struct AsyncWalHandle {
+    shared: Arc<Shared>,
+}
+
+impl AsyncWalHandle {
+    /// Submit `entry`; see [`AsyncWalWriter::append`].
+    pub fn append(&self, entry: WalEntry) -> Result<u64> {
+        self.append_with(|_| entry)
+    }
+
+    /// Submit the entry `build` returns for the LSN it is about to get.
+    /// Used for entries that describe their own position in the log.
+    pub fn append_with(&self, build: impl FnOnce(u64) -> WalEntry) -> Result<u64> {
+        use std::sync::atomic::Ordering::Relaxed;
+        let stats = &self.shared.stats;
+        // Update stats atomically — `fetch_add` returns the previous
+        // value; the `max` compare is a relaxed CAS loop below.
+        stats.entries_submitted.fetch_add(1, Relaxed);
+        let new_depth = stats.current_queue_depth.fetch_add(1, Relaxed) + 1;
+        let mut max = stats.max_queue_depth.load(Relaxed);
+        while new_depth > max {
+            match stats
+                .max_queue_depth
+                .compare_exchange_weak(max, new_depth, Relaxed, Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => max = current,
+            }
+        }
+
+        let mut next_lsn = self.shared.next_lsn.lock();
+        let lsn = *next_lsn + 1;
+        let command = WalCommand::Append(lsn, build(lsn));
+
+        // #19: fast non-blocking submit. Only when the channel is genuinely
+        // full (writer thread behind fsync) do we block — and we surface the
+        // backpressure first so a sustained write burst that stalls the
+        // engine write lock is observable instead of an opaque hang. The
+        // blocking `send` preserves ordering + durability (crossbeam blocks
+        // rather than drops); it is bounded by the (now larger) channel.
+        match self.shared.sender.try_send(command) {
+            Ok(()) => {}
+            Err(TrySendError::Full(cmd)) => {
+                stats.backpressure_blocks.fetch_add(1, Relaxed);
+                tracing::warn!(
+                    queue_depth = new_depth,
+                    "WAL async channel full — applying backpressure (background \
+                     writer is behind fsync); the submitting thread will block \
+                     until the queue drains (issue #19)"
+                );
+                self.shared
+                    .sender
+                    .send(cmd)
+                    .map_err(|_| Error::wal("Failed to send WAL command - channel closed"))?;
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                return Err(Error::wal("Failed to send WAL command - channel closed"));
+            }
+        }
+        *next_lsn = lsn;
+        stats.submitted_lsn.store(lsn, Relaxed);
+        Ok(lsn)
+    }
+
+    /// LSN of the last accepted entry (0 before the first).
+    pub fn submitted_lsn(&self) -> u64 {
+        *self.shared.next_lsn.lock()
+    }
+
+    /// LSN up to which every entry is fsynced.
+    pub fn durable_lsn(&self) -> u64 {
+        self.shared.durability.lock().durable
+    }
+
+    /// Ask the writer thread to write and fsync its current batch now
+    /// instead of at the end of the batch window.
+    pub fn flush(&self) -> Result<()> {
+        self.shared
+            .stats
+            .force_flushes
+            .fetch_add(1, Ordering::Relaxed);
+        self.shared
+            .sender
+            .send(WalCommand::Flush)
+            .map_err(|_| Error::wal("Failed to send flush command - channel closed"))
+    }
+
+    /// Block until every entry up to `lsn` is fsynced. Fails when the
+    /// batch holding `lsn` could not be written, or the writer stopped
+    /// before reaching it.
+    pub fn wait_durable(&self, lsn: u64) -> Result<()> {
+        let mut state = self.shared.durability.lock();
+        loop {
+            if state.durable >= lsn {
+                return Ok(());
+            }
+            if state.failed >= lsn {
+                return Err(Error::wal(format!(
+                    "WAL entry {lsn} could not be written to disk"
+                )));
+            }
+            if state.closed {
+                return Err(Error::wal(format!(
+                    "WAL writer stopped before entry {lsn} was written"
+                )));
+            }
+            self.shared.durable_changed.wait(&mut state);
+        }
+    }
+
+    /// Flush and wait until everything submitted so far is fsynced.
+    pub fn sync(&self) -> Result<()> {
+        let lsn = self.submitted_lsn();
+        if self.durable_lsn() >= lsn {
+            return Ok(());
+        }
+        self.flush()?;
+        self.wait_durable(lsn)
+    }
+}
+
+/// Entries the writer thread has received but not yet written.
+struct Batch {
+    entries: Vec<WalEntry>,
+    /// LSN of the newest entry in `entries`
+    last_lsn: u64,
+}
+
+impl Batch {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            last_lsn: 0,
+        }
+    }
+
+    fn push(&mut self, lsn: u64, entry: WalEntry) {
+        self.entries.push(entry);
+        self.last_lsn = lsn;
+    }
+}
+
 /// Asynchronous WAL writer
 pub struct AsyncWalWriter {
-    /// Command sender to the writer thread
-    sender: Sender<WalCommand>,
+    /// Submit side, shared with every [`AsyncWalHandle`]
+    handle_state: AsyncWalHandle,
     /// Writer thread handle
     handle: Option<JoinHandle<()>>,
     /// Statistics
@@ -179,18 +384,26 @@ impl AsyncWalWriter {
         let (sender, receiver) = bounded(capacity);
         let stats = Arc::new(AsyncWalStats::default());
         let shutdown = Arc::new(AtomicBool::new(false));
+        let shared = Arc::new(Shared {
+            sender,
+            stats: stats.clone(),
+            next_lsn: Mutex::new(0),
+            durability: Mutex::new(Durability::default()),
+            durable_changed: Condvar::new(),
+        });
 
-        let stats_clone = stats.clone();
+        let shared_clone = shared.clone();
         let shutdown_clone = shutdown.clone();
         let config_clone = config.clone();
 
         // Start the background writer thread
         let handle = thread::spawn(move || {
-            Self::writer_thread(wal, receiver, stats_clone, shutdown_clone, &config_clone);
+            Self::writer_thread(wal, receiver, &shared_clone, shutdown_clone, &config_clone);
+            shared_clone.close();
         });
 
         Ok(Self {
-            sender,
+            handle_state: AsyncWalHandle { shared },
             handle: Some(handle),
             stats,
             shutdown,
@@ -198,65 +411,44 @@ impl AsyncWalWriter {
         })
     }
 
-    /// Submit a WAL entry for asynchronous writing
+    /// Submit a WAL entry for asynchronous writing and return its LSN.
     ///
     /// This method will block if the queue is full (based on max_queue_depth).
-    pub fn append(&self, entry: WalEntry) -> Result<()> {
-        use std::sync::atomic::Ordering::Relaxed;
-        // Update stats atomically — `fetch_add` returns the previous
-        // value; the `max` compare is a relaxed CAS loop below.
-        self.stats.entries_submitted.fetch_add(1, Relaxed);
-        let new_depth = self.stats.current_queue_depth.fetch_add(1, Relaxed) + 1;
-        let mut max = self.stats.max_queue_depth.load(Relaxed);
-        while new_depth > max {
-            match self
-                .stats
-                .max_queue_depth
-                .compare_exchange_weak(max, new_depth, Relaxed, Relaxed)
-            {
-                Ok(_) => break,
-                Err(current) => max = current,
-            }
-        }
-
-        // #19: fast non-blocking submit. Only when the channel is genuinely
-        // full (writer thread behind fsync) do we block — and we surface the
-        // backpressure first so a sustained write burst that stalls the
-        // engine write lock is observable instead of an opaque hang. The
-        // blocking `send` preserves ordering + durability (crossbeam blocks
-        // rather than drops); it is bounded by the (now larger) channel.
-        match self.sender.try_send(WalCommand::Append(entry)) {
-            Ok(()) => Ok(()),
-            Err(TrySendError::Full(cmd)) => {
-                self.stats.backpressure_blocks.fetch_add(1, Relaxed);
-                tracing::warn!(
-                    queue_depth = new_depth,
-                    "WAL async channel full — applying backpressure (background \
-                     writer is behind fsync); the submitting thread will block \
-                     until the queue drains (issue #19)"
-                );
-                self.sender
-                    .send(cmd)
-                    .map_err(|_| Error::wal("Failed to send WAL command - channel closed"))
-            }
-            Err(TrySendError::Disconnected(_)) => {
-                Err(Error::wal("Failed to send WAL command - channel closed"))
-            }
-        }
+    pub fn append(&self, entry: WalEntry) -> Result<u64> {
+        self.handle_state.append(entry)
     }
 
     /// Force flush all pending entries
     ///
-    /// This ensures all previously submitted entries are written and synced to disk.
+    /// Asks the writer thread to write and fsync its current batch
+    /// without waiting for it; use [`Self::sync`] to wait.
     pub fn flush(&self) -> Result<()> {
-        use std::sync::atomic::Ordering::Relaxed;
-        self.stats.force_flushes.fetch_add(1, Relaxed);
+        self.handle_state.flush()
+    }
 
-        self.sender
-            .send(WalCommand::Flush)
-            .map_err(|_| Error::wal("Failed to send flush command - channel closed"))?;
+    /// Flush and wait until every entry submitted so far is fsynced.
+    pub fn sync(&self) -> Result<()> {
+        self.handle_state.sync()
+    }
 
-        Ok(())
+    /// Block until every entry up to `lsn` is fsynced.
+    pub fn wait_durable(&self, lsn: u64) -> Result<()> {
+        self.handle_state.wait_durable(lsn)
+    }
+
+    /// LSN of the last accepted entry (0 before the first).
+    pub fn submitted_lsn(&self) -> u64 {
+        self.handle_state.submitted_lsn()
+    }
+
+    /// LSN up to which every entry is fsynced.
+    pub fn durable_lsn(&self) -> u64 {
+        self.handle_state.durable_lsn()
+    }
+
+    /// A cloneable handle submitting into this writer.
+    pub fn handle(&self) -> AsyncWalHandle {
+        self.handle_state.clone()
     }
 
     /// Get a consistent-per-field snapshot of the current statistics.
@@ -277,7 +469,7 @@ impl AsyncWalWriter {
         self.shutdown.store(true, Ordering::SeqCst);
 
         // Send shutdown command
-        let _ = self.sender.send(WalCommand::Shutdown);
+        let _ = self.handle_state.shared.sender.send(WalCommand::Shutdown);
 
         // Wait for thread to finish
         if let Some(handle) = self.handle.take() {
@@ -293,19 +485,20 @@ impl AsyncWalWriter {
     fn writer_thread(
         mut wal: Wal,
         receiver: Receiver<WalCommand>,
-        stats: Arc<AsyncWalStats>,
+        shared: &Shared,
         shutdown: Arc<AtomicBool>,
         config: &AsyncWalConfig,
     ) {
-        let mut batch = Vec::with_capacity(config.max_batch_size);
+        let stats = &shared.stats;
+        let mut batch = Batch::with_capacity(config.max_batch_size);
         let mut last_flush = Instant::now();
         let mut batch_start = Instant::now();
 
         while !shutdown.load(Ordering::SeqCst) {
             // Try to receive a command with timeout
             match receiver.recv_timeout(config.max_batch_age.min(config.flush_interval)) {
-                Ok(WalCommand::Append(entry)) => {
-                    batch.push(entry);
+                Ok(WalCommand::Append(lsn, entry)) => {
+                    batch.push(lsn, entry);
                     // Decrement queue depth without underflow via a
                     // relaxed CAS loop that stops at zero.
                     use std::sync::atomic::Ordering::Relaxed;
@@ -323,35 +516,32 @@ impl AsyncWalWriter {
                     }
 
                     // Check if batch reached max size - flush immediately
-                    if batch.len() >= config.max_batch_size {
-                        Self::flush_batch(&mut wal, &batch, &stats, config);
-                        batch.clear();
+                    if batch.entries.len() >= config.max_batch_size {
+                        Self::write_batch(&mut wal, &mut batch, shared, config);
                         batch_start = Instant::now();
                         last_flush = Instant::now();
                     }
                 }
                 Ok(WalCommand::Flush) => {
                     // Force flush current batch
-                    Self::flush_batch(&mut wal, &batch, &stats, config);
-                    batch.clear();
+                    Self::write_batch(&mut wal, &mut batch, shared, config);
                     batch_start = Instant::now();
                     last_flush = Instant::now();
                     continue;
                 }
                 Ok(WalCommand::Shutdown) => {
                     // Final flush before shutdown
-                    Self::flush_batch(&mut wal, &batch, &stats, config);
+                    Self::write_batch(&mut wal, &mut batch, shared, config);
                     break;
                 }
                 Err(_) => {
                     // Timeout - check if we should flush
-                    let should_flush = batch.len() >= config.max_batch_size
+                    let should_flush = batch.entries.len() >= config.max_batch_size
                         || batch_start.elapsed() >= config.max_batch_age
                         || last_flush.elapsed() >= config.flush_interval;
 
-                    if should_flush && !batch.is_empty() {
-                        Self::flush_batch(&mut wal, &batch, &stats, config);
-                        batch.clear();
+                    if should_flush && !batch.entries.is_empty() {
+                        Self::write_batch(&mut wal, &mut batch, shared, config);
                         batch_start = Instant::now();
                         last_flush = Instant::now();
                     }
@@ -366,11 +556,10 @@ impl AsyncWalWriter {
         // Consume everything still queued before the final flush.
         while let Ok(cmd) = receiver.try_recv() {
             match cmd {
-                WalCommand::Append(entry) => {
-                    batch.push(entry);
-                    if batch.len() >= config.max_batch_size {
-                        Self::flush_batch(&mut wal, &batch, &stats, config);
-                        batch.clear();
+                WalCommand::Append(lsn, entry) => {
+                    batch.push(lsn, entry);
+                    if batch.entries.len() >= config.max_batch_size {
+                        Self::write_batch(&mut wal, &mut batch, shared, config);
                     }
                 }
                 WalCommand::Flush | WalCommand::Shutdown => {}
@@ -378,20 +567,30 @@ impl AsyncWalWriter {
         }
 
         // Final flush on exit
-        if !batch.is_empty() {
-            Self::flush_batch(&mut wal, &batch, &stats, config);
+        Self::write_batch(&mut wal, &mut batch, shared, config);
+    }
+
+    /// Write and fsync `batch`, publish the outcome to durability
+    /// waiters and empty it.
+    fn write_batch(wal: &mut Wal, batch: &mut Batch, shared: &Shared, config: &AsyncWalConfig) {
+        if batch.entries.is_empty() {
+            return;
         }
+        let written = Self::flush_batch(wal, &batch.entries, &shared.stats, config);
+        shared.publish(batch.last_lsn, written);
+        batch.entries.clear();
     }
 
-    /// Flush a batch of WAL entries
+    /// Flush a batch of WAL entries. Returns whether the batch reached
+    /// the disk.
     fn flush_batch(
         wal: &mut Wal,
         batch: &[WalEntry],
         stats: &Arc<AsyncWalStats>,
         config: &AsyncWalConfig,
-    ) {
+    ) -> bool {
         if batch.is_empty() {
-            return;
+            return true;
         }
 
         let start_time = Instant::now();
@@ -463,7 +662,7 @@ impl AsyncWalWriter {
                                 retry_count
                             );
                         }
-                        return;
+                        return true;
                     }
                     Err(e) => {
                         last_error = Some(e);
@@ -498,6 +697,7 @@ impl AsyncWalWriter {
 
         // Try emergency save to a backup WAL file
         Self::emergency_save_batch(batch);
+        false
     }
 
     /// Emergency save batch to backup WAL file when main WAL fails
@@ -530,7 +730,7 @@ impl Drop for AsyncWalWriter {
     fn drop(&mut self) {
         // Attempt graceful shutdown
         self.shutdown.store(true, Ordering::SeqCst);
-        let _ = self.sender.send(WalCommand::Shutdown);
+        let _ = self.handle_state.shared.sender.send(WalCommand::Shutdown);
 
         if let Some(handle) = self.handle.take() {
             let _ = handle.join();
@@ -678,6 +878,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_returns_once_every_submitted_entry_is_durable() {
+        let ctx = TestContext::new();
+        let path = ctx.path().join("wal.log");
+        let mut writer = AsyncWalWriter::new(
+            Wal::new(&path).unwrap(),
+            AsyncWalConfig {
+                // A batch window far longer than the test, so only the
+                // explicit flush in `sync` can write the entries.
+                max_batch_age: Duration::from_secs(60),
+                flush_interval: Duration::from_secs(60),
+                ..AsyncWalConfig::default()
+            },
+        )
+        .unwrap();
+
+        let first = writer
+            .append(WalEntry::CreateNode {
+                node_id: 1,
+                label_bits: 0,
+            })
+            .unwrap();
+        let second = writer
+            .handle()
+            .append(WalEntry::DeleteNode { node_id: 1 })
+            .unwrap();
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(writer.submitted_lsn(), 2);
+
+        writer.sync().unwrap();
+        assert_eq!(writer.durable_lsn(), 2);
+        assert_eq!(writer.stats().durable_lsn, 2);
+        // Already durable: returns without another round trip.
+        writer.wait_durable(first).unwrap();
+        assert_eq!(Wal::new(&path).unwrap().recover().unwrap().len(), 2);
+
+        writer.shutdown().unwrap();
+    }
+
+    #[test]
+    fn waiting_past_a_stopped_writer_fails_instead_of_hanging() {
+        let (mut writer, _dir) = create_test_writer();
+        let handle = writer.handle();
+        let lsn = handle
+            .append_with(|lsn| WalEntry::Checkpoint { epoch: lsn })
+            .unwrap();
+        writer.shutdown().unwrap();
+
+        // Accepted before shutdown, so the final drain wrote it.
+        handle.wait_durable(lsn).unwrap();
+        assert!(handle.wait_durable(lsn + 1).is_err());
+        assert!(handle.append(WalEntry::DeleteNode { node_id: 1 }).is_err());
+    }
+
     #[test]
     #[ignore] // TODO: Fix batch size limit test - timing issue with async flushing
     fn test_batch_size_limit() {
@@ -24,8 +24,10 @@ pub mod async_wal;
 mod record;
 mod writer;
 
-// Re-export async WAL types (unchanged — no edits to async_wal.rs)
-pub use async_wal::{AsyncWalConfig, AsyncWalStats, AsyncWalStatsSnapshot, AsyncWalWriter};
+// Re-export async WAL types
+pub use async_wal::{
+    AsyncWalConfig, AsyncWalHandle, AsyncWalStats, AsyncWalStatsSnapshot, AsyncWalWriter,
+};
 
 // Re-export record types so `crate::wal::WalEntry` etc. remain valid.
 pub use record::{ChecksumAlgo, WalEntry, WalEntryType, WalStats};
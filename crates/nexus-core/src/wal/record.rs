@@ -92,6 +92,13 @@ pub enum WalEntryType {
     /// crash recovery can rebuild the catalog external-id index even if the
     /// LMDB write had not been flushed to disk.
     ExternalIdAssigned = 0x60,
+    /// Store pages flushed by the write pipeline's background flusher.
+    StoreFlush = 0x70,
+    /// Full node record and properties, journalled by a pipelined commit.
+    NodeImage = 0x71,
+    /// Full relationship record and properties, journalled by a
+    /// pipelined commit.
+    RelImage = 0x72,
     /// Checkpoint marker
     Checkpoint = 0xFF,
 }
@@ -264,6 +271,44 @@ pub enum WalEntry {
         /// Wire-encoded external id (discriminator + payload).
         external_id_bytes: Vec<u8>,
     },
+    /// The record and property stores were flushed to disk.
+    ///
+    /// Written by the engine's background store flusher (see
+    /// `engine::write_pipeline`) after each flush. Every entry before
+    /// this marker had its store change applied before the flush
+    /// started, except the last `unflushed_tail` entries, which were
+    /// appended while it ran. Recovery only has to look at the entries
+    /// after the latest marker, plus that tail.
+    StoreFlush {
+        /// Epoch when the flush started
+        epoch: u64,
+        /// Entries directly before this marker that the flush may not
+        /// cover
+        unflushed_tail: u64,
+    },
+    /// A node as a pipelined commit left it.
+    ///
+    /// Written before the commit is acknowledged (see
+    /// `engine::write_pipeline`), so recovery can rewrite a record and
+    /// property map the crash kept from reaching the store files.
+    NodeImage {
+        /// Node ID
+        node_id: u64,
+        /// Raw node record
+        record: Vec<u8>,
+        /// Property map as JSON; empty when the node has none
+        properties: Vec<u8>,
+    },
+    /// A relationship as a pipelined commit left it; see
+    /// [`WalEntry::NodeImage`].
+    RelImage {
+        /// Relationship ID
+        rel_id: u64,
+        /// Raw relationship record
+        record: Vec<u8>,
+        /// Property map as JSON; empty when the relationship has none
+        properties: Vec<u8>,
+    },
 }
 
 impl WalEntry {
@@ -288,6 +333,9 @@ impl WalEntry {
             Self::RTreeDelete { .. } => WalEntryType::RTreeDelete,
             Self::RTreeBulkLoadDone { .. } => WalEntryType::RTreeBulkLoadDone,
             Self::ExternalIdAssigned { .. } => WalEntryType::ExternalIdAssigned,
+            Self::StoreFlush { .. } => WalEntryType::StoreFlush,
+            Self::NodeImage { .. } => WalEntryType::NodeImage,
+            Self::RelImage { .. } => WalEntryType::RelImage,
         }
     }
 
@@ -307,7 +355,8 @@ impl WalEntry {
             Self::BeginTx { epoch, .. }
             | Self::CommitTx { epoch, .. }
             | Self::AbortTx { epoch, .. }
-            | Self::Checkpoint { epoch } => Some(*epoch),
+            | Self::Checkpoint { epoch }
+            | Self::StoreFlush { epoch, .. } => Some(*epoch),
             _ => None,
         }
     }
@@ -330,4 +379,7 @@ pub struct WalStats {
     pub file_size: u64,
     /// Number of entries since last checkpoint
     pub entries_since_checkpoint: u64,
+    /// When the log was last truncated (Unix milliseconds), if it has
+    /// been since this handle was opened.
+    pub last_truncated_unix_ms: Option<u64>,
 }
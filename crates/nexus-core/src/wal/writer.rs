@@ -303,8 +303,7 @@ impl Wal {
 
         // Write to file
         let entry_offset = self.offset;
-        self.file.seek(SeekFrom::End(0))?;
-        self.file.write_all(&buf)?;
+        self.write_frame(&buf)?;
 
         // Update offset and stats
         self.offset += buf.len() as u64;
@@ -372,8 +371,7 @@ impl Wal {
         buf.extend_from_slice(&crc_plain.to_le_bytes());
         buf.extend_from_slice(&ciphertext);
 
-        self.file.seek(SeekFrom::End(0))?;
-        self.file.write_all(&buf)?;
+        self.write_frame(&buf)?;
 
         self.offset += buf.len() as u64;
         self.stats.entries_written += 1;
@@ -383,8 +381,38 @@ impl Wal {
         Ok(frame_offset)
     }
 
+    /// Append an encoded frame at the end of the file.
+    fn write_frame(&mut self, buf: &[u8]) -> Result<()> {
+        self.file.seek(SeekFrom::End(0))?;
+        #[cfg(feature = "fault-injection")]
+        if let Some(keep) = crate::fault_injection::torn_write(&self.path, buf.len()) {
+            self.file.write_all(&buf[..keep])?;
+            self.offset += keep as u64;
+            self.stats.file_size = self.offset;
+            return Err(Error::wal(format!(
+                "ERR_WAL_TORN_WRITE: injected torn write ({keep} of {} bytes)",
+                buf.len()
+            )));
+        }
+        self.file.write_all(buf)?;
+        Ok(())
+    }
+
+    /// `read_exact` that reports end of file as `false`: a frame cut off
+    /// by a crash mid-append is a torn trailing frame, recovered by
+    /// truncation exactly like the v3 path does.
+    fn read_frame_bytes(&mut self, buf: &mut [u8]) -> Result<bool> {
+        match self.file.read_exact(buf) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Flush WAL to disk (fsync)
     pub fn flush(&mut self) -> Result<()> {
+        #[cfg(feature = "fault-injection")]
+        crate::fault_injection::fsync(&self.path)?;
         self.file.sync_all()?;
         Ok(())
     }
@@ -500,17 +528,29 @@ impl Wal {
                     }
 
                     let mut type_buf = [0u8; 1];
-                    self.file.read_exact(&mut type_buf)?;
+                    if !self.read_frame_bytes(&mut type_buf)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
 
                     let mut len_buf = [0u8; 4];
-                    self.file.read_exact(&mut len_buf)?;
+                    if !self.read_frame_bytes(&mut len_buf)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
                     let payload_len = u32::from_le_bytes(len_buf) as usize;
 
                     let mut payload = vec![0u8; payload_len];
-                    self.file.read_exact(&mut payload)?;
+                    if !self.read_frame_bytes(&mut payload)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
 
                     let mut crc_buf = [0u8; 4];
-                    self.file.read_exact(&mut crc_buf)?;
+                    if !self.read_frame_bytes(&mut crc_buf)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
                     let stored_crc = u32::from_le_bytes(crc_buf);
 
                     (
@@ -529,14 +569,23 @@ impl Wal {
                     let type_buf = first;
 
                     let mut len_buf = [0u8; 4];
-                    self.file.read_exact(&mut len_buf)?;
+                    if !self.read_frame_bytes(&mut len_buf)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
                     let payload_len = u32::from_le_bytes(len_buf) as usize;
 
                     let mut payload = vec![0u8; payload_len];
-                    self.file.read_exact(&mut payload)?;
+                    if !self.read_frame_bytes(&mut payload)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
 
                     let mut crc_buf = [0u8; 4];
-                    self.file.read_exact(&mut crc_buf)?;
+                    if !self.read_frame_bytes(&mut crc_buf)? {
+                        self.truncate_to(file_offset)?;
+                        break;
+                    }
                     let stored_crc = u32::from_le_bytes(crc_buf);
 
                     (
@@ -654,6 +703,10 @@ impl Wal {
             self.stats.file_size = 0;
         }
         self.stats.entries_since_checkpoint = 0;
+        self.stats.last_truncated_unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_millis() as u64);
         Ok(())
     }
 
@@ -0,0 +1,102 @@
+//! Durability and error-handling paths driven by the storage / WAL
+//! fault-injection hooks.
+//!
+//! ```text
+//! cargo test -p nexus-core --features fault-injection --test fault_injection_test
+//! ```
+
+#![cfg(feature = "fault-injection")]
+
+use nexus_core::Engine;
+use nexus_core::fault_injection::{self, Fault};
+use nexus_core::wal::{Wal, WalEntry};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+fn create_node(node_id: u64) -> WalEntry {
+    WalEntry::CreateNode {
+        node_id,
+        label_bits: 1,
+    }
+}
+
+#[test]
+fn wal_fsync_failure_surfaces_until_disarmed() {
+    let dir = TempDir::new().unwrap();
+    let mut wal = Wal::new(dir.path().join("wal.log")).unwrap();
+    wal.append(&create_node(0)).unwrap();
+
+    let guard = fault_injection::inject(dir.path(), Fault::FailFsync);
+    let err = wal.flush().unwrap_err();
+    assert!(err.to_string().contains("injected fsync failure"), "{err}");
+    assert_eq!(guard.hits(), 1);
+
+    drop(guard);
+    wal.flush().unwrap();
+}
+
+#[test]
+fn torn_wal_append_is_truncated_on_recovery() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("wal.log");
+    let mut wal = Wal::new(&path).unwrap();
+    for id in 0..3 {
+        wal.append(&create_node(id)).unwrap();
+    }
+    wal.flush().unwrap();
+    let intact_len = std::fs::metadata(&path).unwrap().len();
+
+    // Cut the fourth frame off inside its payload.
+    let guard = fault_injection::inject(&path, Fault::TornWrite { keep_bytes: 9 });
+    let err = wal.append(&create_node(3)).unwrap_err();
+    assert!(err.to_string().contains("ERR_WAL_TORN_WRITE"), "{err}");
+    assert_eq!(guard.hits(), 1);
+    drop(guard);
+    drop(wal);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), intact_len + 9);
+
+    // Recovery keeps the three complete frames and drops the torn tail,
+    // so appends continue on a frame boundary.
+    let mut wal = Wal::new(&path).unwrap();
+    assert_eq!(wal.recover().unwrap().len(), 3);
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), intact_len);
+    wal.append(&create_node(3)).unwrap();
+    wal.flush().unwrap();
+    drop(wal);
+    assert_eq!(Wal::new(&path).unwrap().recover().unwrap().len(), 4);
+}
+
+#[test]
+fn record_store_fsync_failure_fails_engine_flush() {
+    let dir = TempDir::new().unwrap();
+    let mut engine = Engine::with_data_dir(dir.path()).unwrap();
+    engine
+        .create_node(vec!["Fault".to_string()], serde_json::json!({}))
+        .unwrap();
+    // Drain the async WAL writer so only the explicit flush below hits
+    // the fault.
+    engine.flush_async_wal().unwrap();
+
+    let guard = fault_injection::inject(dir.path(), Fault::FailFsync);
+    assert!(engine.flush().is_err());
+    assert!(guard.hits() >= 1);
+
+    drop(guard);
+    engine.flush().unwrap();
+}
+
+#[test]
+fn delayed_record_reads_are_slow_but_correct() {
+    let dir = TempDir::new().unwrap();
+    let mut engine = Engine::with_data_dir(dir.path()).unwrap();
+    let id = engine
+        .create_node(vec!["Fault".to_string()], serde_json::json!({}))
+        .unwrap();
+
+    let guard = fault_injection::inject(dir.path(), Fault::DelayReads(Duration::from_millis(50)));
+    let started = Instant::now();
+    let record = engine.storage.read_node(id).unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    assert!(!record.is_deleted());
+    assert!(guard.hits() >= 1);
+}
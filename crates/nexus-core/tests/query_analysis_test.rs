@@ -184,6 +184,60 @@ fn test_profile_create_query() {
     assert!(!check_result.rows.is_empty());
 }
 
+#[test]
+fn test_explain_analyze_reports_rows_per_operator() {
+    let mut engine = create_engine();
+    for age in [20, 35, 40] {
+        engine
+            .execute_cypher(&format!("CREATE (n:Analyzed {{age: {age}}})"))
+            .unwrap();
+    }
+
+    let result = engine
+        .execute_cypher("EXPLAIN ANALYZE MATCH (n:Analyzed) WHERE n.age > 30 RETURN n.age")
+        .unwrap();
+    assert_eq!(result.columns, vec!["profile"]);
+    let profile = extract_first_row_value(result).unwrap();
+    assert_eq!(profile["rows_returned"], 2);
+
+    let operators = profile["plan"]["operators"].as_array().unwrap();
+    assert_eq!(operators[0]["type"], "NodeByLabel");
+    assert_eq!(operators[0]["rows"], 3);
+    assert!(operators.iter().all(|op| op["time_us"].is_u64()));
+
+    // The tree is rooted at the last operator.
+    let tree = &profile["plan"]["tree"];
+    assert_eq!(tree["id"], 0);
+    assert_eq!(tree["operator"], operators.last().unwrap()["type"]);
+}
+
+#[test]
+fn test_plan_text_and_dot_formats() {
+    let mut engine = create_engine();
+    engine
+        .execute_cypher("CREATE (n:Person {name: 'Erin', age: 28})")
+        .unwrap();
+
+    let result = engine
+        .execute_cypher("EXPLAIN FORMAT TEXT MATCH (n:Person) WHERE n.age > 20 RETURN n")
+        .unwrap();
+    let text = extract_first_row_value(result).unwrap();
+    let text = text.as_str().unwrap();
+    assert!(text.contains("└── Filter"), "{text}");
+    assert!(text.contains("NodeByLabel"), "{text}");
+
+    let result = engine
+        .execute_cypher("PROFILE FORMAT DOT MATCH (n:Person) RETURN n")
+        .unwrap();
+    assert_eq!(result.columns, vec!["profile"]);
+    let dot = extract_first_row_value(result).unwrap();
+    let dot = dot.as_str().unwrap();
+    assert!(dot.starts_with("digraph plan {"), "{dot}");
+    assert!(dot.contains("op0 -> op1;"), "{dot}");
+    assert!(dot.contains("rows: "), "{dot}");
+    assert!(dot.contains("label=\"execution time: "), "{dot}");
+}
+
 #[test]
 fn test_using_index_hint_parsing() {
     use nexus_core::executor::parser::CypherParser;
@@ -0,0 +1,282 @@
+//! `/admin/cache`, `/admin/wal` and `/admin/transactions` — operator
+//! controls for the engine caches, the write-ahead log and open
+//! explicit transactions, driven by `nexus admin cache|wal|tx`.
+//!
+//! | Route                              | Action                          |
+//! |------------------------------------|---------------------------------|
+//! | `GET /admin/cache`                 | page cache, cache layers, query cache |
+//! | `POST /admin/cache/flush`          | drop every rebuildable cache    |
+//! | `GET /admin/wal`                   | log size and writer backlog     |
+//! | `POST /admin/wal/checkpoint`       | flush stores, write a checkpoint |
+//! | `GET /admin/transactions`          | open explicit transactions      |
+//! | `DELETE /admin/transactions/{id}`  | roll one back                   |
+//!
+//! Every response carries an `error` field instead of a non-2xx status,
+//! like the other JSON operator endpoints.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use nexus_core::engine::{CacheFlush, CacheReport, WalCheckpoint, WalInfo};
+use nexus_core::session::OpenTransaction;
+use serde::Serialize;
+
+use crate::NexusServer;
+
+/// Executor query-result cache counters in a [`CacheResponse`].
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryCacheSummary {
+    /// Cache lookups since startup
+    pub lookups: u64,
+    /// Cache hits since startup
+    pub hits: u64,
+    /// `hits / lookups`
+    pub hit_rate: f64,
+    /// Approximate memory held, in bytes
+    pub memory_usage_bytes: usize,
+}
+
+/// `GET /admin/cache` response.
+#[derive(Debug, Serialize)]
+pub struct CacheResponse {
+    /// Engine caches
+    pub engine: CacheReport,
+    /// Executor query-result cache, `None` when it is disabled
+    pub query_cache: Option<QueryCacheSummary>,
+}
+
+/// `POST /admin/cache/flush` response.
+#[derive(Debug, Serialize)]
+pub struct CacheFlushResponse {
+    /// What was dropped, `None` on error
+    pub flushed: Option<CacheFlush>,
+    /// Error message, if any
+    pub error: Option<String>,
+}
+
+/// `GET /admin/wal` response.
+#[derive(Debug, Serialize)]
+pub struct WalInfoResponse {
+    /// Log report, `None` on error
+    pub wal: Option<WalInfo>,
+    /// Error message, if any
+    pub error: Option<String>,
+}
+
+/// `POST /admin/wal/checkpoint` response.
+#[derive(Debug, Serialize)]
+pub struct WalCheckpointResponse {
+    /// Checkpoint written, `None` on error
+    pub checkpoint: Option<WalCheckpoint>,
+    /// Error message, if any
+    pub error: Option<String>,
+}
+
+/// `GET /admin/transactions` response.
+#[derive(Debug, Serialize)]
+pub struct TransactionsResponse {
+    /// Open explicit transactions, oldest first
+    pub transactions: Vec<OpenTransaction>,
+}
+
+/// `DELETE /admin/transactions/{id}` response.
+#[derive(Debug, Serialize)]
+pub struct KillTransactionResponse {
+    /// The transaction rolled back, `None` on error
+    pub killed: Option<OpenTransaction>,
+    /// Error message, if any
+    pub error: Option<String>,
+}
+
+/// `GET /admin/cache` handler.
+pub async fn cache_stats(State(server): State<Arc<NexusServer>>) -> Json<CacheResponse> {
+    let engine = server.engine.write().await.cache_report();
+    let query_cache = server
+        .executor
+        .get_query_cache_stats()
+        .map(|stats| QueryCacheSummary {
+            lookups: stats.lookups,
+            hits: stats.hits,
+            hit_rate: stats.hit_rate,
+            memory_usage_bytes: stats.memory_usage_bytes,
+        });
+    Json(CacheResponse {
+        engine,
+        query_cache,
+    })
+}
+
+/// `POST /admin/cache/flush` handler.
+pub async fn flush_cache(State(server): State<Arc<NexusServer>>) -> Json<CacheFlushResponse> {
+    server.executor.clear_query_cache();
+    match server.engine.write().await.flush_caches() {
+        Ok(flushed) => Json(CacheFlushResponse {
+            flushed: Some(flushed),
+            error: None,
+        }),
+        Err(e) => Json(CacheFlushResponse {
+            flushed: None,
+            error: Some(format!("Failed to flush caches: {e}")),
+        }),
+    }
+}
+
+/// `GET /admin/wal` handler.
+pub async fn wal_info(State(server): State<Arc<NexusServer>>) -> Json<WalInfoResponse> {
+    match server.engine.read().await.wal_info() {
+        Ok(wal) => Json(WalInfoResponse {
+            wal: Some(wal),
+            error: None,
+        }),
+        Err(e) => Json(WalInfoResponse {
+            wal: None,
+            error: Some(format!("Failed to read WAL state: {e}")),
+        }),
+    }
+}
+
+/// `POST /admin/wal/checkpoint` handler.
+pub async fn wal_checkpoint(State(server): State<Arc<NexusServer>>) -> Json<WalCheckpointResponse> {
+    match server.engine.write().await.checkpoint_wal() {
+        Ok(checkpoint) => Json(WalCheckpointResponse {
+            checkpoint: Some(checkpoint),
+            error: None,
+        }),
+        Err(e) => Json(WalCheckpointResponse {
+            checkpoint: None,
+            error: Some(format!("Checkpoint failed: {e}")),
+        }),
+    }
+}
+
+/// `GET /admin/transactions` handler.
+pub async fn list_transactions(
+    State(server): State<Arc<NexusServer>>,
+) -> Json<TransactionsResponse> {
+    Json(TransactionsResponse {
+        transactions: server.engine.read().await.open_transactions(),
+    })
+}
+
+/// `DELETE /admin/transactions/{id}` handler.
+pub async fn kill_transaction(
+    State(server): State<Arc<NexusServer>>,
+    Path(tx_id): Path<u64>,
+) -> Json<KillTransactionResponse> {
+    match server.engine.write().await.kill_transaction(tx_id) {
+        Ok(Some(killed)) => Json(KillTransactionResponse {
+            killed: Some(killed),
+            error: None,
+        }),
+        Ok(None) => Json(KillTransactionResponse {
+            killed: None,
+            error: Some(format!("No open transaction with id {tx_id}")),
+        }),
+        Err(e) => Json(KillTransactionResponse {
+            killed: None,
+            error: Some(format!("Failed to kill transaction {tx_id}: {e}")),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_test_server() -> Arc<NexusServer> {
+        use parking_lot::RwLock as PlRwLock;
+        use tokio::sync::RwLock as TokioRwLock;
+
+        let ctx = nexus_core::testing::TestContext::new();
+        let engine = nexus_core::Engine::with_isolated_catalog(ctx.path()).expect("engine init");
+        let engine_arc = Arc::new(TokioRwLock::new(engine));
+        let executor = Arc::new(nexus_core::executor::Executor::default());
+        let dbm = Arc::new(PlRwLock::new(
+            nexus_core::database::DatabaseManager::new(ctx.path().to_path_buf()).expect("dbm init"),
+        ));
+        let rbac = Arc::new(TokioRwLock::new(
+            nexus_core::auth::RoleBasedAccessControl::new(),
+        ));
+        let auth_mgr = Arc::new(nexus_core::auth::AuthManager::new(
+            nexus_core::auth::AuthConfig::default(),
+        ));
+        let jwt = Arc::new(nexus_core::auth::JwtManager::new(
+            nexus_core::auth::JwtConfig::default(),
+        ));
+        let audit = Arc::new(
+            nexus_core::auth::AuditLogger::new(nexus_core::auth::AuditConfig {
+                enabled: false,
+                log_dir: ctx.path().join("audit"),
+                retention_days: 1,
+                compress_logs: false,
+            })
+            .expect("audit init"),
+        );
+        let _leaked = Box::leak(Box::new(ctx));
+
+        Arc::new(NexusServer::new(
+            executor,
+            engine_arc,
+            dbm,
+            rbac,
+            auth_mgr,
+            jwt,
+            audit,
+            crate::config::RootUserConfig::default(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn wal_checkpoint_and_info_round_trip() {
+        let server = build_test_server();
+        let checkpoint = wal_checkpoint(State(Arc::clone(&server))).await.0;
+        assert!(checkpoint.error.is_none(), "{:?}", checkpoint.error);
+        let info = wal_info(State(server)).await.0;
+        assert!(info.error.is_none(), "{:?}", info.error);
+        assert_eq!(
+            info.wal.unwrap().current_epoch,
+            checkpoint.checkpoint.unwrap().epoch
+        );
+    }
+
+    #[tokio::test]
+    async fn transactions_can_be_listed_and_killed() {
+        let server = build_test_server();
+        server
+            .engine
+            .write()
+            .await
+            .execute_cypher("BEGIN TRANSACTION")
+            .unwrap();
+
+        let listed = list_transactions(State(Arc::clone(&server))).await.0;
+        assert_eq!(listed.transactions.len(), 1);
+        let tx_id = listed.transactions[0].tx_id;
+
+        let killed = kill_transaction(State(Arc::clone(&server)), Path(tx_id))
+            .await
+            .0;
+        assert!(killed.error.is_none(), "{:?}", killed.error);
+        assert!(
+            list_transactions(State(Arc::clone(&server)))
+                .await
+                .0
+                .transactions
+                .is_empty()
+        );
+
+        let missing = kill_transaction(State(server), Path(tx_id)).await.0;
+        assert!(missing.killed.is_none());
+        assert!(missing.error.unwrap().contains("No open transaction"));
+    }
+
+    #[tokio::test]
+    async fn cache_flush_reports_what_was_dropped() {
+        let server = build_test_server();
+        let flushed = flush_cache(State(Arc::clone(&server))).await.0;
+        assert!(flushed.error.is_none(), "{:?}", flushed.error);
+        let stats = cache_stats(State(server)).await.0;
+        assert_eq!(stats.engine.page_cache_pages, 0);
+    }
+}
@@ -0,0 +1,208 @@
+//! `/admin/plans` — plan regression detection, driven by
+//! `nexus admin plans`.
+//!
+//! | Route                          | Action                                 |
+//! |--------------------------------|----------------------------------------|
+//! | `GET /admin/plans`             | plans recorded per query template      |
+//! | `POST /admin/plans/baseline`   | save them as a new baseline version    |
+//! | `GET /admin/plans/regressions` | compare a baseline with current plans  |
+//!
+//! Recording is off unless `server.plan_history.enabled` (or
+//! `NEXUS_PLAN_HISTORY_ENABLED`) is set. Every response carries an
+//! `error` field instead of a non-2xx status, like the other JSON
+//! operator endpoints.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::{Query, State};
+use nexus_core::engine::{DEFAULT_PLAN_REGRESSION_THRESHOLD, PlanBaselineSummary, PlanComparison};
+use nexus_core::performance::plan_history::PlanRecord;
+use serde::{Deserialize, Serialize};
+
+use crate::NexusServer;
+
+/// `GET /admin/plans` response.
+#[derive(Debug, Serialize)]
+pub struct PlansResponse {
+    /// Recorded plans, sorted by template; empty while recording is off
+    pub plans: Vec<PlanRecord>,
+}
+
+/// `POST /admin/plans/baseline` response.
+#[derive(Debug, Serialize)]
+pub struct BaselineResponse {
+    /// Baseline saved, `None` on error
+    pub baseline: Option<PlanBaselineSummary>,
+    /// Error message, if any
+    pub error: Option<String>,
+}
+
+/// `GET /admin/plans/regressions` query string.
+#[derive(Debug, Default, Deserialize)]
+pub struct RegressionQuery {
+    /// Baseline version; the newest when absent
+    pub version: Option<u64>,
+    /// Relative slowdown that counts as a regression (default 0.25)
+    pub threshold: Option<f64>,
+}
+
+/// `GET /admin/plans/regressions` response.
+#[derive(Debug, Serialize)]
+pub struct RegressionResponse {
+    /// Comparison, `None` on error
+    pub comparison: Option<PlanComparison>,
+    /// Error message, if any
+    pub error: Option<String>,
+}
+
+/// `GET /admin/plans` handler.
+pub async fn list_plans(State(server): State<Arc<NexusServer>>) -> Json<PlansResponse> {
+    Json(PlansResponse {
+        plans: server.engine.read().await.plan_records(),
+    })
+}
+
+/// `POST /admin/plans/baseline` handler.
+pub async fn capture_baseline(State(server): State<Arc<NexusServer>>) -> Json<BaselineResponse> {
+    match server.engine.write().await.capture_plan_baseline() {
+        Ok(baseline) => Json(BaselineResponse {
+            baseline: Some(baseline),
+            error: None,
+        }),
+        Err(e) => Json(BaselineResponse {
+            baseline: None,
+            error: Some(format!("Failed to capture plan baseline: {e}")),
+        }),
+    }
+}
+
+/// `GET /admin/plans/regressions` handler.
+pub async fn regressions(
+    State(server): State<Arc<NexusServer>>,
+    Query(query): Query<RegressionQuery>,
+) -> Json<RegressionResponse> {
+    let threshold = query.threshold.unwrap_or(DEFAULT_PLAN_REGRESSION_THRESHOLD);
+    let compared = server
+        .engine
+        .read()
+        .await
+        .compare_plan_baseline(query.version, threshold);
+    match compared {
+        Ok(Some(comparison)) => Json(RegressionResponse {
+            comparison: Some(comparison),
+            error: None,
+        }),
+        Ok(None) => Json(RegressionResponse {
+            comparison: None,
+            error: Some(match query.version {
+                Some(version) => format!("No plan baseline version {version}"),
+                None => "No plan baseline captured yet".to_string(),
+            }),
+        }),
+        Err(e) => Json(RegressionResponse {
+            comparison: None,
+            error: Some(format!("Plan comparison failed: {e}")),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_test_server() -> Arc<NexusServer> {
+        use parking_lot::RwLock as PlRwLock;
+        use tokio::sync::RwLock as TokioRwLock;
+
+        let ctx = nexus_core::testing::TestContext::new();
+        let mut engine =
+            nexus_core::Engine::with_isolated_catalog(ctx.path()).expect("engine init");
+        engine
+            .set_plan_history_config(&nexus_core::performance::plan_history::PlanHistoryConfig {
+                enabled: true,
+                ..Default::default()
+            })
+            .expect("enable plan history");
+        let engine_arc = Arc::new(TokioRwLock::new(engine));
+        let executor = Arc::new(nexus_core::executor::Executor::default());
+        let dbm = Arc::new(PlRwLock::new(
+            nexus_core::database::DatabaseManager::new(ctx.path().to_path_buf()).expect("dbm init"),
+        ));
+        let rbac = Arc::new(TokioRwLock::new(
+            nexus_core::auth::RoleBasedAccessControl::new(),
+        ));
+        let auth_mgr = Arc::new(nexus_core::auth::AuthManager::new(
+            nexus_core::auth::AuthConfig::default(),
+        ));
+        let jwt = Arc::new(nexus_core::auth::JwtManager::new(
+            nexus_core::auth::JwtConfig::default(),
+        ));
+        let audit = Arc::new(
+            nexus_core::auth::AuditLogger::new(nexus_core::auth::AuditConfig {
+                enabled: false,
+                log_dir: ctx.path().join("audit"),
+                retention_days: 1,
+                compress_logs: false,
+            })
+            .expect("audit init"),
+        );
+        let _leaked = Box::leak(Box::new(ctx));
+
+        Arc::new(NexusServer::new(
+            executor,
+            engine_arc,
+            dbm,
+            rbac,
+            auth_mgr,
+            jwt,
+            audit,
+            crate::config::RootUserConfig::default(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn baseline_capture_and_comparison_round_trip() {
+        let server = build_test_server();
+        let missing = regressions(
+            State(Arc::clone(&server)),
+            Query(RegressionQuery::default()),
+        )
+        .await
+        .0;
+        assert!(missing.error.unwrap().contains("No plan baseline"));
+
+        {
+            let mut engine = server.engine.write().await;
+            engine
+                .execute_cypher("CREATE (:Person {name: 'a'})")
+                .unwrap();
+            engine.execute_cypher("MATCH (n:Person) RETURN n").unwrap();
+        }
+        let listed = list_plans(State(Arc::clone(&server))).await.0;
+        assert!(
+            listed
+                .plans
+                .iter()
+                .any(|p| p.template == "MATCH (n:Person) RETURN n" && p.executions == 1)
+        );
+
+        let captured = capture_baseline(State(Arc::clone(&server))).await.0;
+        assert!(captured.error.is_none(), "{:?}", captured.error);
+        assert_eq!(captured.baseline.unwrap().templates, listed.plans.len());
+
+        let compared = regressions(
+            State(server),
+            Query(RegressionQuery {
+                version: Some(1),
+                threshold: Some(0.5),
+            }),
+        )
+        .await
+        .0;
+        assert!(compared.error.is_none(), "{:?}", compared.error);
+        let comparison = compared.comparison.unwrap();
+        assert_eq!(comparison.compared, listed.plans.len());
+        assert_eq!(comparison.changed, 0);
+    }
+}
@@ -0,0 +1,242 @@
+//! Analysis artifact API endpoints
+//!
+//! Stores named, versioned analysis results (clustering runs, layouts)
+//! per database so they can be compared over time:
+//! - GET /artifacts - List artifacts with their newest version
+//! - GET /artifacts/{name} - List the versions of one artifact
+//! - POST /artifacts/{name} - Save a new version
+//! - GET /artifacts/{name}/{version} - Fetch a version (`latest` for the newest)
+//! - DELETE /artifacts/{name} - Delete every version
+//! - DELETE /artifacts/{name}/{version} - Delete one version
+//!
+//! Every endpoint takes an optional `?database=` query parameter;
+//! without it the default database is used.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use nexus_core::engine::ArtifactStore;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::NexusServer;
+
+/// Query parameters shared by every artifact endpoint
+#[derive(Debug, Default, Deserialize)]
+pub struct ArtifactQuery {
+    /// Database to use; the default database when omitted
+    pub database: Option<String>,
+}
+
+/// Request to save a new artifact version
+#[derive(Debug, Deserialize)]
+pub struct SaveArtifactRequest {
+    /// Payload tag, e.g. `clustering` or `layout`
+    pub kind: String,
+    /// Result to store
+    pub payload: serde_json::Value,
+}
+
+/// Response for artifact deletion
+#[derive(Debug, Serialize)]
+pub struct DeleteArtifactResponse {
+    /// Number of versions removed
+    pub deleted: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactErrorResponse {
+    error: String,
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (
+        status,
+        Json(ArtifactErrorResponse {
+            error: message.into(),
+        }),
+    )
+        .into_response()
+}
+
+/// Resolve the artifact store of the requested database.
+pub(crate) async fn artifact_store(
+    server: &NexusServer,
+    database: Option<&str>,
+) -> Result<ArtifactStore, Response> {
+    match database {
+        None => Ok(server.engine.read().await.artifacts.clone()),
+        Some(name) => {
+            let engine = server
+                .database_manager
+                .read()
+                .get_database(name)
+                .map_err(|e| {
+                    error_response(StatusCode::NOT_FOUND, format!("Database not found: {}", e))
+                })?;
+            Ok(engine.read().artifacts.clone())
+        }
+    }
+}
+
+fn parse_version(raw: &str) -> Result<Option<u64>, Response> {
+    if raw == "latest" {
+        return Ok(None);
+    }
+    raw.parse::<u64>().map(Some).map_err(|_| {
+        error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid artifact version {raw:?}: expected a number or `latest`"),
+        )
+    })
+}
+
+fn store_error(e: nexus_core::Error) -> Response {
+    match e {
+        nexus_core::Error::CypherExecution(message) => {
+            error_response(StatusCode::BAD_REQUEST, message)
+        }
+        other => error_response(StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
+    }
+}
+
+/// List every artifact with its newest version
+pub async fn list_artifacts(
+    State(server): State<Arc<NexusServer>>,
+    Query(query): Query<ArtifactQuery>,
+) -> Response {
+    let store = match artifact_store(&server, query.database.as_deref()).await {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+    match store.list() {
+        Ok(artifacts) => Json(artifacts).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// List the stored versions of one artifact
+pub async fn list_artifact_versions(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Query(query): Query<ArtifactQuery>,
+) -> Response {
+    let store = match artifact_store(&server, query.database.as_deref()).await {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+    match store.versions(&name) {
+        Ok(versions) if versions.is_empty() => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Artifact {name:?} not found"),
+        ),
+        Ok(versions) => Json(versions).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// Save a new version of an artifact
+pub async fn save_artifact(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Query(query): Query<ArtifactQuery>,
+    Json(request): Json<SaveArtifactRequest>,
+) -> Response {
+    let store = match artifact_store(&server, query.database.as_deref()).await {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+    match store.save(&name, &request.kind, request.payload) {
+        Ok(artifact) => (StatusCode::CREATED, Json(artifact)).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// Fetch one version of an artifact
+pub async fn get_artifact(
+    State(server): State<Arc<NexusServer>>,
+    Path((name, version)): Path<(String, String)>,
+    Query(query): Query<ArtifactQuery>,
+) -> Response {
+    let version = match parse_version(&version) {
+        Ok(version) => version,
+        Err(response) => return response,
+    };
+    let store = match artifact_store(&server, query.database.as_deref()).await {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+    match store.get(&name, version) {
+        Ok(Some(artifact)) => Json(artifact).into_response(),
+        Ok(None) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Artifact {name:?} version not found"),
+        ),
+        Err(e) => store_error(e),
+    }
+}
+
+/// Delete every version of an artifact
+pub async fn delete_artifact(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Query(query): Query<ArtifactQuery>,
+) -> Response {
+    delete_versions(&server, &name, None, query).await
+}
+
+/// Delete one version of an artifact
+pub async fn delete_artifact_version(
+    State(server): State<Arc<NexusServer>>,
+    Path((name, version)): Path<(String, String)>,
+    Query(query): Query<ArtifactQuery>,
+) -> Response {
+    let version = match parse_version(&version) {
+        Ok(Some(version)) => version,
+        Ok(None) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "Delete a specific version number, not `latest`",
+            );
+        }
+        Err(response) => return response,
+    };
+    delete_versions(&server, &name, Some(version), query).await
+}
+
+async fn delete_versions(
+    server: &NexusServer,
+    name: &str,
+    version: Option<u64>,
+    query: ArtifactQuery,
+) -> Response {
+    let store = match artifact_store(server, query.database.as_deref()).await {
+        Ok(store) => store,
+        Err(response) => return response,
+    };
+    match store.delete(name, version) {
+        Ok(0) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Artifact {name:?} not found"),
+        ),
+        Ok(deleted) => Json(DeleteArtifactResponse { deleted }).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert!(matches!(parse_version("latest"), Ok(None)));
+        assert!(matches!(parse_version("3"), Ok(Some(3))));
+        match parse_version("v3") {
+            Err(response) => assert_eq!(response.status(), StatusCode::BAD_REQUEST),
+            Ok(_) => panic!("non-numeric version must be rejected"),
+        }
+    }
+}
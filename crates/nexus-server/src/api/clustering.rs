@@ -43,6 +43,10 @@ pub struct ClusteringRequest {
     pub distance_metric: Option<String>,
     /// Random seed for reproducible results
     pub random_seed: Option<u64>,
+    /// Save the response as the next version of this artifact
+    /// (see `api::artifacts`)
+    #[serde(default)]
+    pub save_as: Option<String>,
 }
 
 /// Response for clustering operations
@@ -197,11 +201,15 @@ pub async fn get_algorithms() -> Result<Json<ClusteringAlgorithmsResponse>, Stat
 
 /// Perform clustering on nodes
 pub async fn cluster_nodes(
-    State(_server): State<Arc<crate::NexusServer>>,
+    State(server): State<Arc<crate::NexusServer>>,
     Json(request): Json<ClusteringRequest>,
 ) -> Result<Json<ClusteringResponse>, StatusCode> {
-    // Create a simple graph for testing - in a real implementation, you'd extract nodes from the server
-    let graph = Graph::new();
+    let graph = server
+        .engine
+        .write()
+        .await
+        .convert_to_simple_graph()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let algorithm = parse_algorithm(&request)?;
     let feature_strategy = parse_feature_strategy(&request)?;
@@ -252,6 +260,21 @@ pub async fn cluster_nodes(
         },
     };
 
+    if let Some(name) = &request.save_as {
+        let payload =
+            serde_json::to_value(&response).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        server
+            .engine
+            .read()
+            .await
+            .artifacts
+            .save(name, "clustering", payload)
+            .map_err(|e| match e {
+                nexus_core::Error::CypherExecution(_) => StatusCode::BAD_REQUEST,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            })?;
+    }
+
     Ok(Json(response))
 }
 
@@ -464,6 +487,15 @@ fn property_value_to_json(value: nexus_core::graph::simple::PropertyValue) -> se
                 .map(|x| serde_json::Value::Number(serde_json::Number::from(x)))
                 .collect(),
         ),
+        nexus_core::graph::simple::PropertyValue::List(items) => {
+            serde_json::Value::Array(items.into_iter().map(property_value_to_json).collect())
+        }
+        nexus_core::graph::simple::PropertyValue::Map(entries) => serde_json::Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, property_value_to_json(value)))
+                .collect(),
+        ),
     }
 }
 
@@ -502,6 +534,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let algorithm = parse_algorithm(&request).unwrap();
@@ -528,6 +561,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let algorithm = parse_algorithm(&request).unwrap();
@@ -548,6 +582,7 @@ mod tests {
             property_keys: None,
             distance_metric: Some("manhattan".to_string()),
             random_seed: None,
+            save_as: None,
         };
 
         let metric = parse_distance_metric(&request).unwrap();
@@ -606,6 +641,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let algorithm = parse_algorithm(&request).unwrap();
@@ -631,6 +667,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let algorithm = parse_algorithm(&request).unwrap();
@@ -651,6 +688,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let result = parse_algorithm(&request);
@@ -671,6 +709,7 @@ mod tests {
             property_keys: Some(vec!["age".to_string(), "salary".to_string()]),
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let strategy = parse_feature_strategy(&request).unwrap();
@@ -694,6 +733,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let strategy = parse_feature_strategy(&request).unwrap();
@@ -714,6 +754,7 @@ mod tests {
             property_keys: None,
             distance_metric: None,
             random_seed: None,
+            save_as: None,
         };
 
         let result = parse_feature_strategy(&request);
@@ -743,6 +784,7 @@ mod tests {
                 property_keys: None,
                 distance_metric: Some(name.to_string()),
                 random_seed: None,
+                save_as: None,
             };
 
             let metric = parse_distance_metric(&request).unwrap();
@@ -764,6 +806,7 @@ mod tests {
             property_keys: None,
             distance_metric: Some("invalid".to_string()),
             random_seed: None,
+            save_as: None,
         };
 
         let result = parse_distance_metric(&request);
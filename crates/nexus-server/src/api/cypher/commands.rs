@@ -1,7 +1,8 @@
 //! Administrative Cypher commands routed away from `execute_cypher`:
 //! database management (`CREATE/DROP/ALTER/USE DATABASE`, `SHOW DATABASES`),
-//! user/role/grant commands, query-management introspection, and API-key
-//! lifecycle (`CREATE/SHOW/REVOKE/DELETE API KEY`).
+//! user/role/grant commands, query-management introspection, API-key
+//! lifecycle (`CREATE/SHOW/REVOKE/DELETE API KEY`), and saved views
+//! (`CREATE/DROP VIEW`, `SHOW VIEWS`).
 
 use super::*;
 
@@ -43,11 +44,18 @@ pub(crate) async fn execute_database_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("Database '{}' does not exist", use_db.name)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
             nexus_core::executor::parser::Clause::ShowDatabases => {
-                columns = vec!["name".to_string(), "default".to_string()];
+                columns = vec![
+                    "name".to_string(),
+                    "default".to_string(),
+                    "read_only".to_string(),
+                ];
 
                 let dbm = server.database_manager.clone();
                 let (databases, default_db) = tokio::task::spawn_blocking(move || {
@@ -61,7 +69,11 @@ pub(crate) async fn execute_database_commands(
                 .expect("spawn_blocking panicked");
 
                 for db in databases {
-                    rows.push(serde_json::json!([db.name.clone(), db.name == default_db]));
+                    rows.push(serde_json::json!([
+                        db.name.clone(),
+                        db.name == default_db,
+                        db.read_only
+                    ]));
                 }
             }
             nexus_core::executor::parser::Clause::CreateDatabase(create_db) => {
@@ -91,6 +103,9 @@ pub(crate) async fn execute_database_commands(
                             execution_time_ms: execution_time,
                             error: Some(format!("Failed to create database: {}", e)),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 }
@@ -123,6 +138,9 @@ pub(crate) async fn execute_database_commands(
                             execution_time_ms: execution_time,
                             error: Some(format!("Failed to drop database: {}", e)),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 }
@@ -138,6 +156,9 @@ pub(crate) async fn execute_database_commands(
         execution_time_ms: execution_time,
         error: None,
         notifications: Vec::new(),
+        consistency_token: None,
+        truncated: None,
+        constraint_violation: None,
     })
 }
 
@@ -209,6 +230,9 @@ pub(crate) async fn execute_user_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("User '{}' not found", show_user.username)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
@@ -233,6 +257,9 @@ pub(crate) async fn execute_user_commands(
                                 "Cannot delete root user. Use DISABLE instead.".to_string(),
                             ),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
 
@@ -249,6 +276,9 @@ pub(crate) async fn execute_user_commands(
                             execution_time_ms: execution_time,
                             error: Some(format!("Failed to delete user '{}'", drop_user.username)),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 } else if drop_user.if_exists {
@@ -264,6 +294,9 @@ pub(crate) async fn execute_user_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("User '{}' not found", drop_user.username)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
@@ -284,6 +317,9 @@ pub(crate) async fn execute_user_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("User '{}' already exists", create_user.username)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
 
@@ -346,6 +382,9 @@ pub(crate) async fn execute_user_commands(
                             execution_time_ms: execution_time,
                             error: Some(e),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 };
@@ -366,6 +405,9 @@ pub(crate) async fn execute_user_commands(
                             execution_time_ms: execution_time,
                             error: Some("Cannot modify root user permissions. Only root users can modify root users.".to_string()),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 }
@@ -411,6 +453,9 @@ pub(crate) async fn execute_user_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("User or role '{}' not found", grant.target)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
@@ -447,6 +492,9 @@ pub(crate) async fn execute_user_commands(
                             execution_time_ms: execution_time,
                             error: Some(e),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 };
@@ -467,6 +515,9 @@ pub(crate) async fn execute_user_commands(
                             execution_time_ms: execution_time,
                             error: Some("Cannot modify root user permissions. Only root users can modify root users.".to_string()),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 }
@@ -503,6 +554,9 @@ pub(crate) async fn execute_user_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("User or role '{}' not found", revoke.target)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
@@ -517,6 +571,9 @@ pub(crate) async fn execute_user_commands(
         execution_time_ms: execution_time,
         error: None,
         notifications: Vec::new(),
+        consistency_token: None,
+        truncated: None,
+        constraint_violation: None,
     })
 }
 
@@ -595,6 +652,9 @@ pub(crate) async fn execute_query_management_commands(
                             terminate_clause.query_id
                         )),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
@@ -609,6 +669,9 @@ pub(crate) async fn execute_query_management_commands(
         execution_time_ms: execution_time,
         error: None,
         notifications: Vec::new(),
+        consistency_token: None,
+        truncated: None,
+        constraint_violation: None,
     })
 }
 
@@ -706,6 +769,9 @@ pub(crate) async fn execute_api_key_commands(
                             execution_time_ms: execution_time,
                             error: Some(e),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 };
@@ -724,6 +790,9 @@ pub(crate) async fn execute_api_key_commands(
                                 execution_time_ms: execution_time,
                                 error: Some(format!("User '{}' not found", username)),
                                 notifications: Vec::new(),
+                                consistency_token: None,
+                                truncated: None,
+                                constraint_violation: None,
                             });
                         }
                     }
@@ -743,6 +812,9 @@ pub(crate) async fn execute_api_key_commands(
                                 execution_time_ms: execution_time,
                                 error: Some(e),
                                 notifications: Vec::new(),
+                                consistency_token: None,
+                                truncated: None,
+                                constraint_violation: None,
                             });
                         }
                     }
@@ -797,6 +869,9 @@ pub(crate) async fn execute_api_key_commands(
                             execution_time_ms: execution_time,
                             error: Some(format!("Failed to create API key: {}", e)),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 }
@@ -827,6 +902,9 @@ pub(crate) async fn execute_api_key_commands(
                             execution_time_ms: execution_time,
                             error: Some(format!("User '{}' not found", username)),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 } else {
@@ -870,6 +948,9 @@ pub(crate) async fn execute_api_key_commands(
                             execution_time_ms: execution_time,
                             error: Some(format!("Failed to revoke API key: {}", e)),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
                         });
                     }
                 }
@@ -890,6 +971,9 @@ pub(crate) async fn execute_api_key_commands(
                         execution_time_ms: execution_time,
                         error: Some(format!("API key '{}' not found", delete_key.key_id)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
             }
@@ -904,5 +988,109 @@ pub(crate) async fn execute_api_key_commands(
         execution_time_ms: execution_time,
         error: None,
         notifications: Vec::new(),
+        consistency_token: None,
+        truncated: None,
+        constraint_violation: None,
+    })
+}
+
+/// Execute saved-view commands (`CREATE [OR REPLACE] [MATERIALIZED]
+/// VIEW`, `DROP VIEW`, `REFRESH VIEW`, `SHOW VIEWS`).
+///
+/// With authentication on, creating or refreshing a view requires
+/// `WRITE`, and replacing or dropping an existing one requires `ADMIN`
+/// unless the caller owns it. A new view is owned by the caller's user id, or by
+/// the API key id for keys without a user.
+pub(crate) async fn execute_view_commands(
+    server: Arc<NexusServer>,
+    ast: &nexus_core::executor::parser::CypherQuery,
+    auth_context: Option<&AuthContext>,
+    start_time: std::time::Instant,
+) -> Json<CypherResponse> {
+    let owner = auth_context.map(|ctx| {
+        ctx.api_key
+            .user_id
+            .clone()
+            .unwrap_or_else(|| ctx.api_key.id.clone())
+    });
+    let mut engine = server.engine.write().await;
+
+    let denied = ast
+        .clauses
+        .iter()
+        .find_map(|clause| view_permission_error(&engine, auth_context, owner.as_deref(), clause));
+    let result = match denied {
+        Some(error) => Err(error),
+        None => engine
+            .execute_view_commands(ast, owner.as_deref())
+            .map_err(|e| format!("Execution error: {}", e)),
+    };
+
+    let execution_time = start_time.elapsed().as_millis() as u64;
+    match result {
+        Ok(result) => Json(CypherResponse {
+            columns: result.columns,
+            rows: result
+                .rows
+                .into_iter()
+                .map(|row| serde_json::Value::Array(row.values))
+                .collect(),
+            execution_time_ms: execution_time,
+            error: None,
+            notifications: Vec::new(),
+            consistency_token: Some(engine.consistency_token()),
+            truncated: None,
+            constraint_violation: None,
+        }),
+        Err(error) => Json(CypherResponse {
+            columns: vec![],
+            rows: vec![],
+            execution_time_ms: execution_time,
+            error: Some(error),
+            notifications: Vec::new(),
+            consistency_token: None,
+            truncated: None,
+            constraint_violation: None,
+        }),
+    }
+}
+
+/// Why `caller` may not run a view `clause`, if it may not.
+fn view_permission_error(
+    engine: &nexus_core::Engine,
+    auth_context: Option<&AuthContext>,
+    caller: Option<&str>,
+    clause: &nexus_core::executor::parser::Clause,
+) -> Option<String> {
+    use nexus_core::executor::parser::Clause;
+
+    let permissions = &auth_context?.api_key.permissions;
+    let is_admin =
+        permissions.contains(&Permission::Admin) || permissions.contains(&Permission::Super);
+    let (name, replaces) = match clause {
+        Clause::CreateView(create) => {
+            if !is_admin && !permissions.contains(&Permission::Write) {
+                return Some(
+                    "Permission denied: CREATE VIEW requires WRITE permission".to_string(),
+                );
+            }
+            (&create.name, create.or_replace)
+        }
+        Clause::DropView(drop) => (&drop.name, true),
+        Clause::RefreshView(_) => {
+            return (!is_admin && !permissions.contains(&Permission::Write))
+                .then(|| "Permission denied: REFRESH VIEW requires WRITE permission".to_string());
+        }
+        _ => return None,
+    };
+    if !replaces || is_admin {
+        return None;
+    }
+    let view = engine.view(name)?;
+    (view.owner.as_deref() != caller).then(|| {
+        format!(
+            "Permission denied: view '{name}' belongs to another user; \
+             replacing or dropping it requires ADMIN permission"
+        )
     })
 }
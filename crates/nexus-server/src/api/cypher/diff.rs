@@ -0,0 +1,151 @@
+//! `POST /cypher/diff` — run one read-only query against two databases
+//! and report the row-level differences (see
+//! [`nexus_core::engine::result_diff`]).
+//!
+//! Meant for validating migrations and engine upgrades: load the old
+//! and new data (or a restored snapshot) into two databases and diff
+//! the answers to the queries that matter. Write queries are rejected
+//! so a diff can never change either side.
+
+use super::*;
+use nexus_core::engine::{ResultDiff, ResultDiffOptions, diff_results};
+use nexus_core::executor::ResultSet;
+
+/// One side of a [`CypherDiffRequest`].
+#[derive(Debug, Default, Deserialize)]
+pub struct DiffTarget {
+    /// Database to query; the server's default engine when omitted.
+    #[serde(default)]
+    pub database: Option<String>,
+}
+
+impl DiffTarget {
+    fn label(&self) -> &str {
+        self.database.as_deref().unwrap_or("default")
+    }
+}
+
+/// Result diff request
+#[derive(Debug, Deserialize)]
+pub struct CypherDiffRequest {
+    /// Read-only Cypher query run on both sides
+    pub query: String,
+    /// Query parameters, shared by both sides (also accepted as
+    /// `parameters`)
+    #[serde(
+        default,
+        alias = "parameters",
+        deserialize_with = "deserialize_null_default"
+    )]
+    pub params: HashMap<String, serde_json::Value>,
+    /// Baseline side
+    pub left: DiffTarget,
+    /// Side compared against the baseline
+    pub right: DiffTarget,
+    /// Ordering and float tolerances
+    #[serde(default)]
+    pub options: ResultDiffOptions,
+}
+
+/// Result diff response
+#[derive(Debug, Serialize)]
+pub struct CypherDiffResponse {
+    /// Row-level differences, absent on error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<ResultDiff>,
+    /// Execution time of the left query in milliseconds
+    pub left_execution_time_ms: u64,
+    /// Execution time of the right query in milliseconds
+    pub right_execution_time_ms: u64,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CypherDiffResponse {
+    fn error(message: String) -> Json<Self> {
+        Json(Self {
+            diff: None,
+            left_execution_time_ms: 0,
+            right_execution_time_ms: 0,
+            error: Some(message),
+        })
+    }
+}
+
+/// Run `request.query` on both targets and diff the results.
+pub async fn diff_cypher(
+    State(server): State<Arc<NexusServer>>,
+    Json(request): Json<CypherDiffRequest>,
+) -> Json<CypherDiffResponse> {
+    let ast = match nexus_core::executor::parser::CypherParser::new(request.query.clone()).parse() {
+        Ok(ast) => ast,
+        Err(e) => return CypherDiffResponse::error(format!("Parse error: {e}")),
+    };
+    if !routing::is_read_only(&ast) {
+        return CypherDiffResponse::error(
+            "Only read-only queries can be diffed (no writes, DDL or admin commands)".to_string(),
+        );
+    }
+
+    let (left, left_ms) =
+        match run_on(&server, &request.left, &request.query, &request.params).await {
+            Ok(result) => result,
+            Err(e) => {
+                return CypherDiffResponse::error(format!("{} (left): {e}", request.left.label()));
+            }
+        };
+    let (right, right_ms) = match run_on(&server, &request.right, &request.query, &request.params)
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            return CypherDiffResponse::error(format!("{} (right): {e}", request.right.label()));
+        }
+    };
+
+    Json(CypherDiffResponse {
+        diff: Some(diff_results(&left, &right, &request.options)),
+        left_execution_time_ms: left_ms,
+        right_execution_time_ms: right_ms,
+        error: None,
+    })
+}
+
+/// Execute `query` on `target`, returning the result and its execution
+/// time in milliseconds.
+async fn run_on(
+    server: &NexusServer,
+    target: &DiffTarget,
+    query: &str,
+    params: &HashMap<String, serde_json::Value>,
+) -> std::result::Result<(ResultSet, u64), String> {
+    let start = std::time::Instant::now();
+    let result = match &target.database {
+        None => {
+            let mut engine = server.engine.write().await;
+            engine
+                .execute_cypher_with_params(query, params.clone())
+                .map_err(|e| e.to_string())?
+        }
+        Some(name) => {
+            let manager = server.database_manager.clone();
+            let name = name.clone();
+            let query = query.to_string();
+            let params = params.clone();
+            tokio::task::spawn_blocking(move || {
+                let engine = manager
+                    .read()
+                    .get_database_if_online(&name)
+                    .map_err(|e| e.to_string())?;
+                let mut engine = engine.write();
+                engine
+                    .execute_cypher_with_params(&query, params)
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .map_err(|e| format!("query task failed: {e}"))??
+        }
+    };
+    Ok((result, start.elapsed().as_millis() as u64))
+}
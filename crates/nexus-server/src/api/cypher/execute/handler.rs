@@ -50,13 +50,62 @@ async fn log_write_audit(
     }
 }
 
+/// Clone the engine's executor for a lock-free read together with
+/// whether the `"default"` session has an open explicit transaction and
+/// the snapshot's consistency token.
+///
+/// The common case only takes the engine's shared read lock. A `token`
+/// the current snapshot does not cover (minted before a restart) takes
+/// the write lock once so `Engine::ensure_consistency_token` can refresh
+/// the executor.
+async fn read_snapshot(
+    server: &NexusServer,
+    token: Option<u64>,
+) -> nexus_core::Result<(Executor, bool, u64)> {
+    {
+        let engine_guard = server.engine.read().await;
+        if token.is_none_or(|t| engine_guard.satisfies_consistency_token(t)) {
+            return Ok((
+                engine_guard.executor.clone(),
+                engine_guard.in_explicit_transaction(),
+                engine_guard.consistency_token(),
+            ));
+        }
+    }
+    let mut engine_guard = server.engine.write().await;
+    if let Some(token) = token {
+        engine_guard.ensure_consistency_token(token)?;
+    }
+    Ok((
+        engine_guard.executor.clone(),
+        engine_guard.in_explicit_transaction(),
+        engine_guard.consistency_token(),
+    ))
+}
+
 pub async fn execute_cypher(
     State(server): State<Arc<NexusServer>>,
     auth_context: Option<Extension<Option<AuthContext>>>,
     Json(request): Json<CypherRequest>,
 ) -> Json<CypherResponse> {
-    tracing::debug!("[CYPHER-API] Received query: {}", request.query);
     let auth_context = auth_context.and_then(|e| e.0);
+    let row_limit =
+        row_limit::resolve_row_limit(&server, auth_context.as_ref(), request.max_rows).await;
+    let Json(mut response) = execute_cypher_within(server, auth_context, request, row_limit).await;
+    row_limit::apply_row_limit(&mut response, row_limit);
+    Json(response)
+}
+
+/// Body of [`execute_cypher`]. Reads are executed under
+/// [`row_limit::execution_limit`], so they produce at most one row past
+/// `row_limit`; the caller cuts that row off and marks the response.
+async fn execute_cypher_within(
+    server: Arc<NexusServer>,
+    auth_context: Option<AuthContext>,
+    request: CypherRequest,
+    row_limit: usize,
+) -> Json<CypherResponse> {
+    tracing::debug!("[CYPHER-API] Received query: {}", request.query);
     let start_time = std::time::Instant::now();
     let query_for_tracking = request.query.clone();
 
@@ -92,6 +141,9 @@ pub async fn execute_cypher(
             (user_id, username, api_key_id)
         })
         .unwrap_or((None, None, None));
+    // Property history attributes writes to the user, or to the API key
+    // for keys without one.
+    let acting_user = actor_info.0.clone().or_else(|| actor_info.2.clone());
     // Parse query first to check for admin commands
     use nexus_core::executor::parser::CypherParser;
     let mut parser = CypherParser::new(request.query.clone());
@@ -106,11 +158,47 @@ pub async fn execute_cypher(
                 execution_time_ms: execution_time,
                 error: Some(format!("Parse error: {}", e)),
                 notifications: Vec::new(),
+                consistency_token: None,
+                truncated: None,
+                constraint_violation: None,
             });
         }
     };
 
     // Check for database management commands
+    // `dryRun: true` — plan and validate without committing anything.
+    if request.dry_run {
+        let mut engine = server.engine.write().await;
+        let consistency_token = engine.consistency_token();
+        let result = engine.dry_run(&request.query, request.params.clone());
+        drop(engine);
+        let execution_time = start_time.elapsed().as_millis() as u64;
+        return Json(
+            match result.and_then(|report| serde_json::to_value(report).map_err(Into::into)) {
+                Ok(report) => CypherResponse {
+                    columns: vec!["dry_run".to_string()],
+                    rows: vec![serde_json::Value::Array(vec![report])],
+                    execution_time_ms: execution_time,
+                    error: None,
+                    notifications: Vec::new(),
+                    consistency_token: Some(consistency_token),
+                    truncated: None,
+                    constraint_violation: None,
+                },
+                Err(e) => CypherResponse {
+                    columns: vec![],
+                    rows: vec![],
+                    execution_time_ms: execution_time,
+                    error: Some(format!("Dry run error: {}", e)),
+                    notifications: Vec::new(),
+                    consistency_token: None,
+                    truncated: None,
+                    constraint_violation: None,
+                },
+            },
+        );
+    }
+
     let has_db_cmd = ast.clauses.iter().any(|c| {
         matches!(
             c,
@@ -170,6 +258,22 @@ pub async fn execute_cypher(
         return execute_query_management_commands(server.clone(), &ast, start_time).await;
     }
 
+    // Saved views (CREATE VIEW / DROP VIEW / SHOW VIEWS) carry an owner
+    // and need permission checks, so they get their own branch.
+    let has_view_cmd = ast.clauses.iter().any(|c| {
+        matches!(
+            c,
+            nexus_core::executor::parser::Clause::CreateView(_)
+                | nexus_core::executor::parser::Clause::DropView(_)
+                | nexus_core::executor::parser::Clause::RefreshView(_)
+                | nexus_core::executor::parser::Clause::ShowViews
+        )
+    });
+
+    if has_view_cmd {
+        return execute_view_commands(server, &ast, auth_context.as_ref(), start_time).await;
+    }
+
     // Check for SHOW CONSTRAINTS or SHOW FUNCTIONS commands
     let has_show_constraints_or_functions = ast.clauses.iter().any(|c| {
         matches!(
@@ -182,8 +286,17 @@ pub async fn execute_cypher(
                 | nexus_core::executor::parser::Clause::DropFunction(_)
         )
     });
+    // SHOW SETTINGS and dbms.setConfigValue read and change engine
+    // settings, so they take the same engine branch.
+    let has_settings_cmd = match ast.clauses.as_slice() {
+        [nexus_core::executor::parser::Clause::ShowSettings] => true,
+        [nexus_core::executor::parser::Clause::CallProcedure(call)] => {
+            call.procedure_name == "dbms.setConfigValue"
+        }
+        _ => false,
+    };
 
-    if has_show_constraints_or_functions {
+    if has_show_constraints_or_functions || has_settings_cmd {
         // Use Engine for these commands
         {
             let mut engine = server.engine.write().await;
@@ -201,6 +314,9 @@ pub async fn execute_cypher(
                         execution_time_ms: execution_time,
                         error: None,
                         notifications: Vec::new(),
+                        consistency_token: Some(engine.consistency_token()),
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
                 Err(e) => {
@@ -211,6 +327,9 @@ pub async fn execute_cypher(
                         execution_time_ms: execution_time,
                         error: Some(format!("Execution error: {}", e)),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: e.constraint_violation().cloned(),
                     });
                 }
             }
@@ -271,6 +390,9 @@ pub async fn execute_cypher(
                     execution_time_ms: execution_time,
                     error: None,
                     notifications: Vec::new(),
+                    consistency_token: Some(engine.consistency_token()),
+                    truncated: None,
+                    constraint_violation: None,
                 });
             }
             Err(e) => {
@@ -281,6 +403,9 @@ pub async fn execute_cypher(
                     execution_time_ms: execution_time,
                     error: Some(format!("Execution error: {}", e)),
                     notifications: Vec::new(),
+                    consistency_token: None,
+                    truncated: None,
+                    constraint_violation: e.constraint_violation().cloned(),
                 });
             }
         }
@@ -322,6 +447,9 @@ pub async fn execute_cypher(
                         execution_time_ms: execution_time,
                         error: None,
                         notifications: result.notifications,
+                        consistency_token: Some(engine.consistency_token()),
+                        truncated: None,
+                        constraint_violation: None,
                     })
                 }
                 Err(e) => Json(CypherResponse {
@@ -330,6 +458,9 @@ pub async fn execute_cypher(
                     execution_time_ms: execution_time,
                     error: Some(format!("Execution error: {}", e)),
                     notifications: Vec::new(),
+                    consistency_token: None,
+                    truncated: None,
+                    constraint_violation: e.constraint_violation().cloned(),
                 }),
             };
         }
@@ -357,7 +488,10 @@ pub async fn execute_cypher(
         if has_unwind && has_write {
             let mut engine = server.engine.write().await;
             let execution_time = start_time.elapsed().as_millis() as u64;
-            return match engine.execute_cypher_with_params(&request.query, request.params.clone()) {
+            engine.set_acting_user(acting_user);
+            let result = engine.execute_cypher_with_params(&request.query, request.params.clone());
+            engine.set_acting_user(None);
+            return match result {
                 Ok(result) => {
                     let rows: Vec<serde_json::Value> = result
                         .rows
@@ -370,6 +504,9 @@ pub async fn execute_cypher(
                         execution_time_ms: execution_time,
                         error: None,
                         notifications: result.notifications,
+                        consistency_token: Some(engine.consistency_token()),
+                        truncated: None,
+                        constraint_violation: None,
                     })
                 }
                 Err(e) => Json(CypherResponse {
@@ -378,6 +515,9 @@ pub async fn execute_cypher(
                     execution_time_ms: execution_time,
                     error: Some(format!("Execution error: {}", e)),
                     notifications: Vec::new(),
+                    consistency_token: None,
+                    truncated: None,
+                    constraint_violation: e.constraint_violation().cloned(),
                 }),
             };
         }
@@ -424,11 +564,14 @@ pub async fn execute_cypher(
         // same query text. See `Engine::execute_cypher_ast_with_params`'s
         // doc comment.
         let mut engine_guard = server.engine.write().await;
+        engine_guard.set_acting_user(acting_user);
         let dispatch_result = engine_guard.execute_cypher_ast_with_params(
             &ast,
             &request.query,
             request.params.clone(),
         );
+        engine_guard.set_acting_user(None);
+        let consistency_token = engine_guard.consistency_token();
         // Release the write lock before the (async) audit-log call —
         // auditing never touches the engine, and holding a write lock
         // across an `.await` unnecessarily serializes unrelated writes.
@@ -463,6 +606,9 @@ pub async fn execute_cypher(
                     execution_time_ms: execution_time,
                     error: None,
                     notifications: result_set.notifications,
+                    consistency_token: Some(consistency_token),
+                    truncated: None,
+                    constraint_violation: None,
                 })
             }
             Err(e) => {
@@ -485,6 +631,9 @@ pub async fn execute_cypher(
                     execution_time_ms: execution_time,
                     error: Some(e.to_string()),
                     notifications: Vec::new(),
+                    consistency_token: None,
+                    truncated: None,
+                    constraint_violation: e.constraint_violation().cloned(),
                 })
             }
         };
@@ -528,15 +677,25 @@ pub async fn execute_cypher(
             // every MATCH, an arbitrary number of readers can hold
             // `.read().await` concurrently: this no longer serializes
             // reads against each other.
-            let (lock_free_executor, in_explicit_tx) = {
-                let engine_guard = server.engine.read().await;
-                let in_tx = engine_guard
-                    .session_manager
-                    .get_session(&"default".to_string())
-                    .map(|session| session.has_active_transaction())
-                    .unwrap_or(false);
-                (engine_guard.executor.clone(), in_tx)
-            };
+            //
+            // A `consistency_token` the snapshot does not yet cover is
+            // resolved by `read_snapshot` under a one-off write lock.
+            let (lock_free_executor, in_explicit_tx, snapshot_token) =
+                match read_snapshot(&server, request.consistency_token).await {
+                    Ok(snapshot) => snapshot,
+                    Err(e) => {
+                        return Json(CypherResponse {
+                            columns: vec![],
+                            rows: vec![],
+                            execution_time_ms: start_time.elapsed().as_millis() as u64,
+                            error: Some(e.to_string()),
+                            notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
+                        });
+                    }
+                };
 
             if !in_explicit_tx {
                 let query = Query {
@@ -544,21 +703,28 @@ pub async fn execute_cypher(
                     params: request.params.clone(),
                 };
 
-                let execution_result =
-                    match tokio::task::spawn_blocking(move || lock_free_executor.execute(&query))
-                        .await
-                    {
-                        Ok(result) => result,
-                        Err(e) => {
-                            return Json(CypherResponse {
-                                columns: vec![],
-                                rows: vec![],
-                                execution_time_ms: start_time.elapsed().as_millis() as u64,
-                                error: Some(format!("Task execution error: {}", e)),
-                                notifications: Vec::new(),
-                            });
-                        }
-                    };
+                let execution_result = match server
+                    .query_pool
+                    .run(move || {
+                        let _rows = row_limit::execution_limit(row_limit);
+                        lock_free_executor.execute(&query)
+                    })
+                    .await
+                {
+                    Ok(result) => result,
+                    Err(e) => {
+                        return Json(CypherResponse {
+                            columns: vec![],
+                            rows: vec![],
+                            execution_time_ms: start_time.elapsed().as_millis() as u64,
+                            error: Some(format!("Task execution error: {}", e)),
+                            notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: None,
+                        });
+                    }
+                };
 
                 let execution_time_ms = start_time.elapsed().as_millis() as u64;
                 return match execution_result {
@@ -578,6 +744,9 @@ pub async fn execute_cypher(
                             execution_time_ms,
                             error: None,
                             notifications: result_set.notifications,
+                            consistency_token: Some(snapshot_token),
+                            truncated: None,
+                            constraint_violation: None,
                         })
                     }
                     Err(e) => {
@@ -588,6 +757,9 @@ pub async fn execute_cypher(
                             execution_time_ms,
                             error: Some(e.to_string()),
                             notifications: Vec::new(),
+                            consistency_token: None,
+                            truncated: None,
+                            constraint_violation: e.constraint_violation().cloned(),
                         })
                     }
                 };
@@ -604,11 +776,15 @@ pub async fn execute_cypher(
             // above instead of re-parsing inside the exclusive write
             // lock; see `Engine::execute_cypher_ast_with_params`.
             let mut engine_guard = server.engine.write().await;
-            match engine_guard.execute_cypher_ast_with_params(
-                &ast,
-                &request.query,
-                request.params.clone(),
-            ) {
+            let dispatch_result = {
+                let _rows = row_limit::execution_limit(row_limit);
+                engine_guard.execute_cypher_ast_with_params(
+                    &ast,
+                    &request.query,
+                    request.params.clone(),
+                )
+            };
+            match dispatch_result {
                 Ok(result_set) => {
                     let execution_time = start_time.elapsed().as_millis() as u64;
                     tracing::info!(
@@ -627,6 +803,9 @@ pub async fn execute_cypher(
                         execution_time_ms: execution_time,
                         error: None,
                         notifications: result_set.notifications,
+                        consistency_token: Some(engine_guard.consistency_token()),
+                        truncated: None,
+                        constraint_violation: None,
                     });
                 }
                 Err(e) => {
@@ -639,6 +818,9 @@ pub async fn execute_cypher(
                         execution_time_ms: execution_time,
                         error: Some(e.to_string()),
                         notifications: Vec::new(),
+                        consistency_token: None,
+                        truncated: None,
+                        constraint_violation: e.constraint_violation().cloned(),
                     });
                 }
             }
@@ -647,8 +829,28 @@ pub async fn execute_cypher(
 
     // Get executor instance for other queries
     // Executor is Clone and contains only Arc internally, so we can clone directly
-    // without any locks - this enables true parallel execution
-    let executor = server.executor.clone();
+    // without any locks - this enables true parallel execution.
+    // A request carrying a `consistency_token` must observe the engine's
+    // committed writes, which the standalone `server.executor` never
+    // sees, so it runs against the engine's snapshot instead.
+    let (executor, consistency_token) = match request.consistency_token {
+        None => (server.executor.as_ref().clone(), None),
+        Some(token) => match read_snapshot(&server, Some(token)).await {
+            Ok((snapshot, _, snapshot_token)) => (snapshot, Some(snapshot_token)),
+            Err(e) => {
+                return Json(CypherResponse {
+                    columns: vec![],
+                    rows: vec![],
+                    execution_time_ms: start_time.elapsed().as_millis() as u64,
+                    error: Some(e.to_string()),
+                    notifications: Vec::new(),
+                    consistency_token: None,
+                    truncated: None,
+                    constraint_violation: None,
+                });
+            }
+        },
+    };
 
     // Create query
     let query = Query {
@@ -667,30 +869,36 @@ pub async fn execute_cypher(
     // Execute query - clone executor for concurrent execution
     // This removes the global lock bottleneck - each query gets its own executor clone
     // that shares the underlying data structures (catalog, store, indexes) via Arc
-    // Use spawn_blocking to execute in a separate thread pool for true parallelism
     // No lock needed - Executor is Clone and Arc is thread-safe
     let executor_clone = executor.clone();
     let query_clone = query.clone();
 
-    // Debug: Log thread info before spawning
+    // Debug: Log thread info before submitting
     let thread_id_before = std::thread::current().id();
-    tracing::debug!("Spawning blocking task from thread {:?}", thread_id_before);
-
-    // Execute in blocking thread pool for true parallel execution
-    // This allows multiple queries to run concurrently across CPU cores
-    // Tokio's blocking thread pool automatically scales with CPU count
-    let execution_result = match tokio::task::spawn_blocking(move || {
-        let thread_id_after = std::thread::current().id();
-        tracing::debug!("Executing in blocking thread {:?}", thread_id_after);
-
-        let result = executor_clone.execute(&query_clone);
-        tracing::debug!(
-            "Query executed successfully in blocking thread {:?}",
-            thread_id_after
-        );
-        result
-    })
-    .await
+    tracing::debug!("Submitting query task from thread {:?}", thread_id_before);
+
+    // Execute on the dedicated query pool so multiple queries run
+    // concurrently across CPU cores without occupying tokio's threads.
+    // The pool is sized by `server.query_pool.threads`; a burst beyond
+    // that waits in the pool's queue.
+    let (execution_result, page_cache_accesses) = match server
+        .query_pool
+        .run(move || {
+            let thread_id_after = std::thread::current().id();
+            tracing::debug!("Executing in query pool thread {:?}", thread_id_after);
+
+            // Charge page cache traffic on this worker thread to the query.
+            let page_cache_scope = nexus_core::page_cache::attribution::begin();
+            let _rows = row_limit::execution_limit(row_limit);
+            let result = executor_clone.execute(&query_clone);
+            let page_cache_accesses = page_cache_scope.finish();
+            tracing::debug!(
+                "Query executed successfully in query pool thread {:?}",
+                thread_id_after
+            );
+            (result, page_cache_accesses)
+        })
+        .await
     {
         Ok(result) => result,
         Err(e) => {
@@ -700,6 +908,9 @@ pub async fn execute_cypher(
                 execution_time_ms: start_time.elapsed().as_millis() as u64,
                 error: Some(format!("Task execution error: {}", e)),
                 notifications: Vec::new(),
+                consistency_token: None,
+                truncated: None,
+                constraint_violation: None,
             });
         }
     };
@@ -739,6 +950,9 @@ pub async fn execute_cypher(
                 Some(cache_hits),
                 Some(cache_misses),
             );
+            server
+                .query_stats
+                .record_page_cache_accesses(&query_for_tracking, page_cache_accesses);
 
             // Record Prometheus metrics
             let cache_hit = cache_hits > 0;
@@ -757,6 +971,9 @@ pub async fn execute_cypher(
                 execution_time_ms,
                 error: None,
                 notifications: result_set.notifications,
+                consistency_token,
+                truncated: None,
+                constraint_violation: None,
             })
         }
         Err(e) => {
@@ -780,6 +997,9 @@ pub async fn execute_cypher(
                 Some(cache_hits),
                 Some(cache_misses),
             );
+            server
+                .query_stats
+                .record_page_cache_accesses(&query_for_tracking, page_cache_accesses);
 
             // Record Prometheus metrics
             let cache_hit = cache_hits > 0;
@@ -794,6 +1014,9 @@ pub async fn execute_cypher(
                 execution_time_ms,
                 error: Some(error_msg),
                 notifications: Vec::new(),
+                consistency_token: None,
+                truncated: None,
+                constraint_violation: e.constraint_violation().cloned(),
             })
         }
     }
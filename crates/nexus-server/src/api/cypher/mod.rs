@@ -1,13 +1,18 @@
 //! Cypher query execution endpoint. Façade with submodules:
 //! - `execute` — the main `execute_cypher` HTTP handler.
-//! - `commands` — admin commands (database, user, query management, API key).
+//! - `commands` — admin commands (database, user, query management, API key,
+//!   saved views).
+//! - `diff` — `POST /cypher/diff`, one read query diffed across two databases.
 //! - `routing` — shared AST-predicate write/read routing decision (used by
 //!   both this crate's HTTP handler and the RPC dispatcher).
+//! - `row_limit` — server-default / per-role result row limit.
 //! - `tests` — integration tests.
 
 pub mod commands;
+pub mod diff;
 pub mod execute;
 pub(crate) mod routing;
+pub(crate) mod row_limit;
 
 #[cfg(test)]
 mod tests;
@@ -16,8 +21,9 @@ mod write_path_parity;
 
 pub(crate) use commands::{
     execute_api_key_commands, execute_database_commands, execute_query_management_commands,
-    execute_user_commands,
+    execute_user_commands, execute_view_commands,
 };
+pub use diff::diff_cypher;
 pub use execute::execute_cypher;
 
 use crate::NexusServer;
@@ -166,6 +172,33 @@ pub struct CypherRequest {
     /// Database name (optional, defaults to "neo4j")
     #[serde(default)]
     pub database: Option<String>,
+    /// Read-your-writes token echoed from a previous response's
+    /// `consistency_token`. When present, the query is guaranteed to
+    /// observe every write that was acknowledged with that token or an
+    /// earlier one (see `Engine::consistency_token`).
+    #[serde(default)]
+    pub consistency_token: Option<u64>,
+    /// Per-request row limit. Defaults to the server's
+    /// `result_limits.default_max_rows` and is clamped to the caller's
+    /// role cap (see [`crate::config::ResultLimitConfig`]).
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+    /// Plan and validate a write statement without committing it; the
+    /// response carries a single `dry_run` row with the row count that
+    /// reaches the write clauses, estimated affected entities and any
+    /// constraint violations (see `Engine::dry_run`). Also accepted as
+    /// `dryRun`.
+    #[serde(default, alias = "dryRun")]
+    pub dry_run: bool,
+}
+
+/// Truncation marker attached to a [`CypherResponse`] whose rows were
+/// cut at the effective row limit. The query stops one row past the
+/// limit, so how many more rows it would have produced is not known.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultTruncation {
+    /// Row limit that was applied to this response.
+    pub row_limit: usize,
 }
 
 /// Cypher query response
@@ -188,6 +221,22 @@ pub struct CypherResponse {
     /// before phase6.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notifications: Vec<nexus_core::executor::types::Notification>,
+    /// Read-your-writes token for the engine snapshot this query ran
+    /// against. Present on every response served by the engine; send
+    /// it back as `consistency_token` on a later request to guarantee
+    /// that request observes this one's writes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consistency_token: Option<u64>,
+    /// Set when `rows` was cut at the effective row limit. Omitted
+    /// from the wire format for complete results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<ResultTruncation>,
+    /// Structured details when `error` is a constraint violation:
+    /// constraint kind and name, labels or relationship type, the
+    /// offending property values and the id of the conflicting entity
+    /// for UNIQUE / NODE KEY. Omitted for every other outcome.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint_violation: Option<nexus_core::constraints::ConstraintViolation>,
 }
 
 /// Record Prometheus metrics for query execution against the server's
@@ -49,20 +49,41 @@ pub(crate) fn needs_engine_interception(ast: &CypherQuery) -> bool {
 }
 
 /// The clause variants [`needs_engine_interception`] treats as
-/// engine-only.
+/// engine-only, plus `CALL`s of an [`ENGINE_PROCEDURES`] entry.
 fn is_engine_clause(c: &Clause) -> bool {
-    matches!(
-        c,
+    match c {
         Clause::Match(_)
-            | Clause::Create(_)
-            | Clause::Delete(_)
-            | Clause::Merge(_)
-            | Clause::Set(_)
-            | Clause::Remove(_)
-            | Clause::Foreach(_)
-    )
+        | Clause::Create(_)
+        | Clause::Delete(_)
+        | Clause::Merge(_)
+        | Clause::Set(_)
+        | Clause::Remove(_)
+        | Clause::Foreach(_) => true,
+        Clause::CallProcedure(call) => ENGINE_PROCEDURES.contains(&call.procedure_name.as_str()),
+        _ => false,
+    }
 }
 
+/// Procedures that must see the engine's graph or configuration (such
+/// as the `nexus.load.*` host allow-list), which the standalone executor
+/// does not hold. The engine serves the ones that rewrite storage itself
+/// (`Engine::dispatch`); the ones also on [`READ_ONLY_PROCEDURES`] run
+/// on its executor snapshot.
+const ENGINE_PROCEDURES: &[&str] = &[
+    "db.relationships.invert",
+    "db.relationships.retype",
+    "nexus.merge.nodes",
+    "nexus.view",
+    "nexus.aggregate.counts",
+    "nexus.sample.randomWalk",
+    "nexus.sample.nodes",
+    "nexus.sample.relationships",
+    "nexus.load.jsonFromUrl",
+    "nexus.load.csvFromUrl",
+    "nexus.load.jsonFromS3",
+    "nexus.load.csvFromS3",
+];
+
 /// True when `ast` is a pure autocommit read — safe to run through the
 /// lock-free `Executor` clone + `spawn_blocking` path
 /// (phase5_lock-free-read-path) instead of taking the exclusive
@@ -170,7 +191,12 @@ fn is_read_only_clause(c: &Clause) -> bool {
         | Clause::ShowQueries
         | Clause::TerminateQuery(_)
         | Clause::CreateFunction(_)
-        | Clause::DropFunction(_) => false,
+        | Clause::DropFunction(_)
+        | Clause::CreateView(_)
+        | Clause::DropView(_)
+        | Clause::RefreshView(_)
+        | Clause::ShowViews
+        | Clause::ShowSettings => false,
     }
 }
 
@@ -203,6 +229,15 @@ const READ_ONLY_PROCEDURES: &[&str] = &[
     "db.index.fulltext.queryRelationships",
     "db.index.fulltext.listAvailableAnalyzers",
     "spatial.nearest",
+    "nexus.view",
+    "nexus.aggregate.counts",
+    "nexus.sample.randomWalk",
+    "nexus.sample.nodes",
+    "nexus.sample.relationships",
+    "nexus.load.jsonFromUrl",
+    "nexus.load.csvFromUrl",
+    "nexus.load.jsonFromS3",
+    "nexus.load.csvFromS3",
 ];
 
 fn is_read_only_procedure(name: &str) -> bool {
@@ -317,6 +352,49 @@ mod tests {
         assert!(!needs_engine_interception(&ast));
     }
 
+    #[test]
+    fn relationship_refactor_call_needs_engine() {
+        let ast = parse("CALL db.relationships.invert('KNOWS')");
+        assert!(needs_engine_interception(&ast));
+        assert!(!is_read_only(&ast));
+        assert!(!needs_engine_interception(&parse("CALL db.labels()")));
+    }
+
+    #[test]
+    fn sampling_call_reads_the_engine_snapshot() {
+        let ast = parse("CALL nexus.sample.nodes(10)");
+        assert!(needs_engine_interception(&ast));
+        assert!(is_read_only(&ast));
+    }
+
+    #[test]
+    fn view_call_reads_the_engine_snapshot() {
+        let ast = parse("CALL nexus.view('active_people')");
+        assert!(needs_engine_interception(&ast));
+        assert!(is_read_only(&ast));
+    }
+
+    #[test]
+    fn count_aggregate_call_reads_the_engine_snapshot() {
+        let ast = parse("CALL nexus.aggregate.counts('orders_by_status')");
+        assert!(needs_engine_interception(&ast));
+        assert!(is_read_only(&ast));
+    }
+
+    #[test]
+    fn load_call_reads_the_engine_snapshot() {
+        let ast = parse("CALL nexus.load.csvFromUrl('https://data.example.com/a.csv')");
+        assert!(needs_engine_interception(&ast));
+        assert!(is_read_only(&ast));
+    }
+
+    #[test]
+    fn merge_nodes_call_needs_engine() {
+        let ast = parse("CALL nexus.merge.nodes([1, 2])");
+        assert!(needs_engine_interception(&ast));
+        assert!(!is_read_only(&ast));
+    }
+
     #[test]
     fn first_write_kind_prefers_first_clause_in_document_order() {
         assert_eq!(
@@ -0,0 +1,121 @@
+//! Result row limit for Cypher responses over HTTP, RPC and RESP3. The
+//! server default and the per-role caps come from
+//! [`crate::config::ResultLimitConfig`]; the per-request override is
+//! `CypherRequest::max_rows` on HTTP.
+//!
+//! The limit is pushed into execution: a query runs under
+//! [`execution_limit`], which plans a read with a `LIMIT` one row past
+//! the limit. That extra row is how [`cut_rows`] tells a result that was
+//! cut from one that fit exactly.
+
+use super::{CypherResponse, ResultTruncation};
+use crate::NexusServer;
+use nexus_core::auth::middleware::AuthContext;
+use nexus_core::executor::RowLimitGuard;
+
+/// Resolve the row limit for one request. The caller's cap comes from
+/// the RBAC roles of the API key's user; requests without an
+/// authenticated user (auth disabled, or a key with no `user_id`) get
+/// the global `hard_cap`.
+pub(crate) async fn resolve_row_limit(
+    server: &NexusServer,
+    auth_context: Option<&AuthContext>,
+    requested: Option<usize>,
+) -> usize {
+    let limits = &server.result_limits;
+    let user_id = auth_context.and_then(|ctx| ctx.api_key.user_id.as_deref());
+    let cap = match user_id {
+        Some(user_id) if !limits.role_caps.is_empty() => {
+            let rbac = server.rbac.read().await;
+            match rbac.get_user(user_id) {
+                Some(user) => limits.cap_for_roles(&user.roles),
+                None => limits.hard_cap,
+            }
+        }
+        _ => limits.hard_cap,
+    };
+    limits.effective_limit(requested, cap)
+}
+
+/// Run the queries started on this thread, while the guard lives, so
+/// they return at most one row past `limit`.
+pub(crate) fn execution_limit(limit: usize) -> RowLimitGuard {
+    nexus_core::executor::limit_rows(limit.saturating_add(1))
+}
+
+/// Drop every row past `limit`, reporting whether any was dropped.
+pub(crate) fn cut_rows<T>(rows: &mut Vec<T>, limit: usize) -> bool {
+    let cut = rows.len() > limit;
+    rows.truncate(limit);
+    cut
+}
+
+/// Drop every row past `limit` and record the truncation on the
+/// response. Responses within the limit are left untouched.
+pub(crate) fn apply_row_limit(response: &mut CypherResponse, limit: usize) {
+    if cut_rows(&mut response.rows, limit) {
+        response.truncated = Some(ResultTruncation { row_limit: limit });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_with_rows(n: usize) -> CypherResponse {
+        CypherResponse {
+            columns: vec!["n".to_string()],
+            rows: (0..n).map(|i| serde_json::json!([i])).collect(),
+            execution_time_ms: 0,
+            error: None,
+            notifications: Vec::new(),
+            consistency_token: None,
+            truncated: None,
+            constraint_violation: None,
+        }
+    }
+
+    #[test]
+    fn rows_past_the_limit_are_dropped_and_marked() {
+        let mut response = response_with_rows(4);
+        apply_row_limit(&mut response, 3);
+        assert_eq!(response.rows.len(), 3);
+        let marker = response.truncated.expect("truncation marker");
+        assert_eq!(marker.row_limit, 3);
+    }
+
+    #[test]
+    fn execution_stops_one_row_past_the_limit() {
+        let executor = nexus_core::executor::Executor::default();
+        let query = nexus_core::executor::Query {
+            cypher: "UNWIND range(1, 100) AS i RETURN i".to_string(),
+            params: std::collections::HashMap::new(),
+        };
+        let mut response = {
+            let _rows = execution_limit(3);
+            let result = executor.execute(&query).unwrap();
+            assert_eq!(result.rows.len(), 4);
+            CypherResponse {
+                rows: result
+                    .rows
+                    .into_iter()
+                    .map(|row| serde_json::Value::Array(row.values))
+                    .collect(),
+                ..response_with_rows(0)
+            }
+        };
+        apply_row_limit(&mut response, 3);
+        assert_eq!(response.rows.len(), 3);
+        assert!(response.truncated.is_some());
+    }
+
+    #[test]
+    fn responses_within_the_limit_are_untouched() {
+        let mut response = response_with_rows(3);
+        apply_row_limit(&mut response, 3);
+        assert_eq!(response.rows.len(), 3);
+        assert!(response.truncated.is_none());
+        let wire = serde_json::to_value(&response).unwrap();
+        assert!(wire.get("truncated").is_none());
+    }
+}
@@ -41,6 +41,9 @@ async fn test_execute_simple_query() {
         query: "MATCH (n) RETURN n LIMIT 1".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(axum::extract::State(server), Json(request)).await;
@@ -56,6 +59,9 @@ async fn test_execute_query_with_params() {
         query: "MATCH (n) RETURN n LIMIT $limit".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -68,6 +74,9 @@ async fn test_execute_invalid_query() {
         query: "INVALID SYNTAX".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -81,6 +90,9 @@ async fn test_execute_without_executor() {
         query: "MATCH (n) RETURN n".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let response = execute_cypher(Json(request)).await;
@@ -94,6 +106,9 @@ async fn test_response_format() {
         query: "RETURN 1 as num, 'test' as str".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -106,6 +121,9 @@ async fn test_execute_with_initialized_executor() {
         query: "RETURN 'hello' as greeting".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -123,6 +141,9 @@ async fn test_execute_with_complex_params() {
         query: "RETURN $name as name, $age as age, $active as active".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -135,6 +156,9 @@ async fn test_execute_with_empty_result() {
         query: "MATCH (n) WHERE n.nonexistent = 'value' RETURN n".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -147,6 +171,9 @@ async fn test_execute_with_multiple_rows() {
         query: "UNWIND [1, 2, 3] AS num RETURN num".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -163,6 +190,9 @@ async fn test_execute_with_nested_params() {
         query: "RETURN $list as numbers, $obj as data".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -178,6 +208,9 @@ async fn test_execute_with_null_params() {
         query: "RETURN $null_value as null_val".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -190,6 +223,9 @@ async fn test_execute_with_empty_query() {
         query: "".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -203,6 +239,9 @@ async fn test_execute_with_very_long_query() {
         query: long_query,
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -215,6 +254,9 @@ async fn test_merge_node() {
         query: "MERGE (n:Person {name: \"Alice\", age: 30})".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -227,6 +269,9 @@ async fn test_merge_node_without_properties() {
         query: "MERGE (n:Person)".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -239,6 +284,9 @@ async fn test_set_property() {
         query: "CREATE (n:Person {name: \"Alice\"}) SET n.age = 30".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -251,6 +299,9 @@ async fn test_set_label() {
         query: "CREATE (n:Person) SET n:Employee".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -263,6 +314,9 @@ async fn test_delete_node() {
         query: "CREATE (n:Person {name: \"Bob\"}) DELETE n".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -275,6 +329,9 @@ async fn test_detach_delete() {
         query: "CREATE (n:Person {name: \"Charlie\"}) DETACH DELETE n".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -287,6 +344,9 @@ async fn test_remove_property() {
         query: "CREATE (n:Person {name: \"David\", age: 25}) REMOVE n.age".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -299,6 +359,9 @@ async fn test_remove_label() {
         query: "CREATE (n:Person:Employee) REMOVE n:Employee".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
 
     let _response = execute_cypher(Json(request)).await;
@@ -425,6 +488,9 @@ async fn create_return_node_var_returns_node_object() {
             .to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp = execute_cypher(axum::extract::State(server.clone()), None, axum::Json(req))
         .await
@@ -457,6 +523,9 @@ async fn create_return_node_var_returns_node_object() {
         query: "MATCH (t:ProbeNode) RETURN t".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp2 = execute_cypher(axum::extract::State(server), None, axum::Json(req2))
         .await
@@ -523,6 +592,9 @@ async fn non_ascii_body_round_trips_via_handler() {
         query: "CREATE (:Doc {title: 'versão 日本語 😀'})".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp = execute_cypher(
         axum::extract::State(server.clone()),
@@ -542,6 +614,9 @@ async fn non_ascii_body_round_trips_via_handler() {
         query: "MATCH (d:Doc) RETURN d.title AS title".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp2 = execute_cypher(axum::extract::State(server), None, axum::Json(read))
         .await
@@ -616,6 +691,9 @@ async fn create_with_parameterized_node_property_persists() {
         query: "CREATE (n:PTest {x: $v})".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp = execute_cypher(
         axum::extract::State(server.clone()),
@@ -634,6 +712,9 @@ async fn create_with_parameterized_node_property_persists() {
         query: "MATCH (n:PTest) RETURN n.x".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp2 = execute_cypher(axum::extract::State(server), None, axum::Json(read))
         .await
@@ -701,6 +782,9 @@ async fn create_with_parameterized_relationship_property_persists() {
         query: "CREATE (a:PA)-[r:PE {w: $w}]->(b:PB)".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp = execute_cypher(
         axum::extract::State(server.clone()),
@@ -719,6 +803,9 @@ async fn create_with_parameterized_relationship_property_persists() {
         query: "MATCH (:PA)-[r:PE]->(:PB) RETURN r.w".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp2 = execute_cypher(axum::extract::State(server), None, axum::Json(read))
         .await
@@ -786,6 +873,9 @@ async fn set_with_parameterized_value_persists() {
         query: "CREATE (n:PS) SET n.x = $v".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp = execute_cypher(
         axum::extract::State(server.clone()),
@@ -804,6 +894,9 @@ async fn set_with_parameterized_value_persists() {
         query: "MATCH (n:PS) RETURN n.x".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp2 = execute_cypher(axum::extract::State(server), None, axum::Json(read))
         .await
@@ -872,6 +965,9 @@ async fn create_with_multi_key_parameterized_map_persists() {
         query: "CREATE (n:PM {x: $a, y: $b})".to_string(),
         params,
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp = execute_cypher(
         axum::extract::State(server.clone()),
@@ -890,6 +986,9 @@ async fn create_with_multi_key_parameterized_map_persists() {
         query: "MATCH (n:PM) RETURN n.x, n.y".to_string(),
         params: HashMap::new(),
         database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
     };
     let resp2 = execute_cypher(axum::extract::State(server), None, axum::Json(read))
         .await
@@ -912,3 +1011,310 @@ async fn create_with_multi_key_parameterized_map_persists() {
         "second parameterized map key must persist as 2, not null"
     );
 }
+
+#[tokio::test]
+async fn write_response_consistency_token_covers_the_write() {
+    use crate::NexusServer;
+    use nexus_core::auth::RoleBasedAccessControl;
+    use nexus_core::database::DatabaseManager;
+    use nexus_core::testing::TestContext;
+    use parking_lot::RwLock as PlRwLock;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    let ctx = TestContext::new();
+    let engine = nexus_core::Engine::with_data_dir(ctx.path()).unwrap();
+    let engine_arc = Arc::new(RwLock::new(engine));
+    let executor = nexus_core::executor::Executor::default();
+    let executor_arc = Arc::new(executor);
+    let database_manager = DatabaseManager::new(ctx.path().join("databases")).unwrap();
+    let database_manager_arc = Arc::new(PlRwLock::new(database_manager));
+    let rbac = RoleBasedAccessControl::new();
+    let rbac_arc = Arc::new(RwLock::new(rbac));
+    let auth_config = nexus_core::auth::AuthConfig::default();
+    let auth_manager = Arc::new(nexus_core::auth::AuthManager::new(auth_config));
+    let jwt_config = nexus_core::auth::JwtConfig::default();
+    let jwt_manager = Arc::new(nexus_core::auth::JwtManager::new(jwt_config));
+    let audit_logger = Arc::new(
+        nexus_core::auth::AuditLogger::new(nexus_core::auth::AuditConfig {
+            enabled: false,
+            log_dir: std::path::PathBuf::from("./logs"),
+            retention_days: 30,
+            compress_logs: false,
+        })
+        .unwrap(),
+    );
+    let server = Arc::new(NexusServer::new(
+        executor_arc,
+        engine_arc,
+        database_manager_arc,
+        rbac_arc,
+        auth_manager,
+        jwt_manager,
+        audit_logger,
+        crate::config::RootUserConfig::default(),
+    ));
+
+    let run = |query: &str, token: Option<u64>| {
+        let request = CypherRequest {
+            query: query.to_string(),
+            params: HashMap::new(),
+            database: None,
+            consistency_token: token,
+            max_rows: None,
+            dry_run: false,
+        };
+        execute_cypher(
+            axum::extract::State(server.clone()),
+            None,
+            axum::Json(request),
+        )
+    };
+
+    let first = run("CREATE (a:RYW {id: 1})", None).await.0;
+    assert!(first.error.is_none(), "CREATE errored: {:?}", first.error);
+    let first_token = first.consistency_token.expect("write returns a token");
+
+    // `MATCH ... CREATE` is the dispatch branch that used to defer the
+    // executor refresh to the caller.
+    let second = run(
+        "MATCH (a:RYW {id: 1}) CREATE (a)-[:NEXT]->(:RYW {id: 2})",
+        None,
+    )
+    .await
+    .0;
+    assert!(
+        second.error.is_none(),
+        "MATCH CREATE errored: {:?}",
+        second.error
+    );
+    let second_token = second.consistency_token.expect("write returns a token");
+    assert!(
+        second_token > first_token,
+        "token must advance past each write"
+    );
+
+    let read = run("MATCH (n:RYW) RETURN count(n) AS c", Some(second_token))
+        .await
+        .0;
+    assert!(read.error.is_none(), "read errored: {:?}", read.error);
+    assert_eq!(read.rows[0][0].as_i64(), Some(2));
+    assert!(read.consistency_token.unwrap() >= second_token);
+
+    // A token minted by a previous process lifetime is ahead of this
+    // engine's counter; the read still succeeds after a refresh.
+    let stale = run(
+        "MATCH (n:RYW) RETURN count(n) AS c",
+        Some(second_token + 1_000),
+    )
+    .await
+    .0;
+    assert!(stale.error.is_none(), "read errored: {:?}", stale.error);
+    assert_eq!(stale.rows[0][0].as_i64(), Some(2));
+}
+
+/// A server over a fresh engine in `ctx`, set up like the tests above.
+fn test_server(ctx: &nexus_core::testing::TestContext) -> std::sync::Arc<crate::NexusServer> {
+    use crate::NexusServer;
+    use nexus_core::auth::RoleBasedAccessControl;
+    use nexus_core::database::DatabaseManager;
+    use parking_lot::RwLock as PlRwLock;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    let engine = nexus_core::Engine::with_data_dir(ctx.path()).unwrap();
+    let database_manager = DatabaseManager::new(ctx.path().join("databases")).unwrap();
+    let audit_logger = Arc::new(
+        nexus_core::auth::AuditLogger::new(nexus_core::auth::AuditConfig {
+            enabled: false,
+            log_dir: std::path::PathBuf::from("./logs"),
+            retention_days: 30,
+            compress_logs: false,
+        })
+        .unwrap(),
+    );
+    Arc::new(NexusServer::new(
+        Arc::new(nexus_core::executor::Executor::default()),
+        Arc::new(RwLock::new(engine)),
+        Arc::new(PlRwLock::new(database_manager)),
+        Arc::new(RwLock::new(RoleBasedAccessControl::new())),
+        Arc::new(nexus_core::auth::AuthManager::new(
+            nexus_core::auth::AuthConfig::default(),
+        )),
+        Arc::new(nexus_core::auth::JwtManager::new(
+            nexus_core::auth::JwtConfig::default(),
+        )),
+        audit_logger,
+        crate::config::RootUserConfig::default(),
+    ))
+}
+
+/// Run `query` through `/cypher` without parameters.
+async fn run_query(server: &std::sync::Arc<crate::NexusServer>, query: &str) -> CypherResponse {
+    let request = CypherRequest {
+        query: query.to_string(),
+        params: HashMap::new(),
+        database: None,
+        consistency_token: None,
+        max_rows: None,
+        dry_run: false,
+    };
+    execute_cypher(
+        axum::extract::State(server.clone()),
+        None,
+        axum::Json(request),
+    )
+    .await
+    .0
+}
+
+#[tokio::test]
+async fn relationship_refactor_procedures_run_on_the_engine() {
+    let ctx = nexus_core::testing::TestContext::new();
+    let server = test_server(&ctx);
+
+    let create = run_query(&server, "CREATE (:RF {id: 1})-[:OLD]->(:RF {id: 2})").await;
+    assert!(create.error.is_none(), "CREATE errored: {:?}", create.error);
+
+    let retype = run_query(&server, "CALL db.relationships.retype('OLD', 'NEW')").await;
+    assert!(retype.error.is_none(), "retype errored: {:?}", retype.error);
+    assert_eq!(retype.rows[0][0].as_i64(), Some(1));
+
+    // The read path serves the rewritten relationship, not the old one.
+    let new = run_query(&server, "MATCH (:RF)-[r:NEW]->(:RF) RETURN count(r) AS c").await;
+    assert_eq!(new.rows[0][0].as_i64(), Some(1), "{:?}", new.error);
+    let old = run_query(&server, "MATCH (:RF)-[r:OLD]->(:RF) RETURN count(r) AS c").await;
+    assert_eq!(old.rows[0][0].as_i64(), Some(0), "{:?}", old.error);
+
+    let invert = run_query(&server, "CALL db.relationships.invert('NEW')").await;
+    assert!(invert.error.is_none(), "invert errored: {:?}", invert.error);
+    let inverted = run_query(
+        &server,
+        "MATCH (:RF {id: 2})-[r:NEW]->(:RF {id: 1}) RETURN count(r) AS c",
+    )
+    .await;
+    assert_eq!(
+        inverted.rows[0][0].as_i64(),
+        Some(1),
+        "{:?}",
+        inverted.error
+    );
+}
+
+#[tokio::test]
+async fn merge_nodes_procedure_runs_on_the_engine() {
+    let ctx = nexus_core::testing::TestContext::new();
+    let server = test_server(&ctx);
+
+    let create = run_query(
+        &server,
+        "CREATE (:Dup {name: 'Ada'})-[:KNOWS]->(:Dup {name: 'Ada', email: 'ada@example.com'})",
+    )
+    .await;
+    assert!(create.error.is_none(), "CREATE errored: {:?}", create.error);
+    let ids = run_query(&server, "MATCH (n:Dup) RETURN id(n) AS id ORDER BY id").await;
+    let ids: Vec<i64> = ids
+        .rows
+        .iter()
+        .map(|row| row[0].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 2);
+
+    let merge = run_query(
+        &server,
+        &format!("CALL nexus.merge.nodes([{}, {}])", ids[0], ids[1]),
+    )
+    .await;
+    assert!(merge.error.is_none(), "merge errored: {:?}", merge.error);
+    assert_eq!(merge.rows[0][1].as_i64(), Some(1), "one duplicate merged");
+
+    let left = run_query(&server, "MATCH (n:Dup) RETURN n.email AS email").await;
+    assert_eq!(left.rows.len(), 1, "{:?}", left.error);
+    assert_eq!(left.rows[0][0].as_str(), Some("ada@example.com"));
+}
+
+#[tokio::test]
+async fn sampling_procedures_read_the_database() {
+    let ctx = nexus_core::testing::TestContext::new();
+    let server = test_server(&ctx);
+
+    let create = run_query(
+        &server,
+        "CREATE (:Sampled {id: 1})-[:LINK]->(:Sampled {id: 2})",
+    )
+    .await;
+    assert!(create.error.is_none(), "CREATE errored: {:?}", create.error);
+
+    let nodes = run_query(&server, "CALL nexus.sample.nodes(10)").await;
+    assert!(nodes.error.is_none(), "sample errored: {:?}", nodes.error);
+    assert_eq!(
+        nodes.rows[0][0].as_array().map(Vec::len),
+        Some(2),
+        "sample must come from the database, not an empty executor"
+    );
+
+    let rels = run_query(&server, "CALL nexus.sample.relationships(10)").await;
+    assert!(rels.error.is_none(), "sample errored: {:?}", rels.error);
+    assert_eq!(rels.rows[0][1].as_array().map(Vec::len), Some(1));
+}
+
+#[tokio::test]
+async fn load_procedures_use_the_engine_external_data_config() {
+    use std::io::{Read, Write};
+
+    let ctx = nexus_core::testing::TestContext::new();
+    let server = test_server(&ctx);
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        let body = r#"[{"sku": "A-1"}, {"sku": "B-2"}]"#;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+    });
+
+    let query = format!("CALL nexus.load.jsonFromUrl('{base}/items.json') YIELD value");
+    let denied = run_query(&server, &query).await;
+    assert!(
+        denied
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("ERR_EXTERNAL_DATA_DENIED")),
+        "{:?}",
+        denied.error
+    );
+
+    server
+        .engine
+        .write()
+        .await
+        .set_external_data_config(nexus_core::external_data::ExternalDataConfig {
+            allowed_hosts: vec!["127.0.0.1".to_string()],
+            allow_http: true,
+            ..Default::default()
+        })
+        .unwrap();
+    let loaded = run_query(&server, &query).await;
+    assert!(loaded.error.is_none(), "load errored: {:?}", loaded.error);
+    assert_eq!(
+        loaded.rows,
+        vec![
+            serde_json::json!([{"sku": "A-1"}]),
+            serde_json::json!([{"sku": "B-2"}]),
+        ]
+    );
+}
@@ -201,6 +201,9 @@ async fn run_query(
             query: query.to_string(),
             params,
             database: None,
+            consistency_token: None,
+            max_rows: None,
+            dry_run: false,
         }),
     )
     .await
@@ -75,7 +75,7 @@ mod tests {
         let response = response.0;
 
         assert!(response.error.is_none(), "Error: {:?}", response.error);
-        assert_eq!(response.columns, vec!["name", "default"]);
+        assert_eq!(response.columns, vec!["name", "default", "read_only"]);
         assert!(
             !response.rows.is_empty(),
             "Should have at least default database"
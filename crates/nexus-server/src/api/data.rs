@@ -766,6 +766,226 @@ pub async fn get_node_by_id(
     }
 }
 
+/// Response for diffing two nodes
+#[derive(Debug, Serialize)]
+pub struct NodeDiffResponse {
+    /// Success message
+    pub message: String,
+    /// Label and property differences if both nodes exist
+    pub diff: Option<nexus_core::NodeDiff>,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Compare the labels and properties of two nodes:
+/// `GET /data/nodes/diff?a=<id>&b=<id>`.
+pub async fn diff_nodes(
+    State(server): State<Arc<NexusServer>>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Json<NodeDiffResponse> {
+    let error = |message: String| {
+        Json(NodeDiffResponse {
+            message: "".to_string(),
+            diff: None,
+            error: Some(message),
+        })
+    };
+    let mut ids = [0u64; 2];
+    for (slot, name) in ids.iter_mut().zip(["a", "b"]) {
+        match params.get(name).map(|raw| raw.parse::<u64>()) {
+            Some(Ok(id)) => *slot = id,
+            Some(Err(_)) => {
+                return error(format!(
+                    "Invalid node id query parameter `{name}`: {:?} — expected unsigned integer",
+                    params[name]
+                ));
+            }
+            None => return error(format!("Missing required query parameter `{name}`")),
+        }
+    }
+    let [a, b] = ids;
+
+    let mut engine = server.engine.write().await;
+    match engine.diff_nodes(a, b) {
+        Ok(diff) => Json(NodeDiffResponse {
+            message: if diff.is_identical() {
+                "Nodes are identical".to_string()
+            } else {
+                "Nodes differ".to_string()
+            },
+            diff: Some(diff),
+            error: None,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to diff nodes {} and {}: {}", a, b, e);
+            error(format!("Failed to diff nodes: {}", e))
+        }
+    }
+}
+
+/// Response for listing a node's relationships
+#[derive(Debug, Serialize)]
+pub struct NodeRelationshipsResponse {
+    /// Success message
+    pub message: String,
+    /// Relationships on this page
+    pub relationships: Vec<nexus_core::NodeRelationship>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<u64>,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Page through a node's relationships straight from its adjacency
+/// chain: `GET /data/nodes/{id}/relationships?direction=out&types=KNOWS,LIKES&limit=100&cursor=<next_cursor>`.
+///
+/// `direction` is `out` / `outgoing`, `in` / `incoming` or `both`
+/// (default); `types` is a comma-separated list (default: all types).
+pub async fn get_node_relationships(
+    State(server): State<Arc<NexusServer>>,
+    axum::extract::Path(node_id): axum::extract::Path<u64>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Json<NodeRelationshipsResponse> {
+    let error = |message: String| {
+        Json(NodeRelationshipsResponse {
+            message: "".to_string(),
+            relationships: Vec::new(),
+            next_cursor: None,
+            error: Some(message),
+        })
+    };
+    let direction = match params
+        .get("direction")
+        .map(|d| d.to_ascii_lowercase())
+        .as_deref()
+    {
+        None | Some("both") => nexus_core::executor::Direction::Both,
+        Some("out") | Some("outgoing") => nexus_core::executor::Direction::Outgoing,
+        Some("in") | Some("incoming") => nexus_core::executor::Direction::Incoming,
+        Some(other) => {
+            return error(format!(
+                "Invalid direction {other:?} — expected out, in or both"
+            ));
+        }
+    };
+    let types: Vec<String> = params
+        .get("types")
+        .map(|t| {
+            t.split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut numeric = [None, None];
+    for (slot, name) in numeric.iter_mut().zip(["limit", "cursor"]) {
+        if let Some(raw) = params.get(name) {
+            match raw.parse::<u64>() {
+                Ok(v) => *slot = Some(v),
+                Err(_) => {
+                    return error(format!(
+                        "Invalid query parameter `{name}`: {raw:?} — expected unsigned integer"
+                    ));
+                }
+            }
+        }
+    }
+    let [limit, cursor] = numeric;
+    let limit = limit.map_or(nexus_core::engine::DEFAULT_RELATIONSHIP_PAGE_SIZE, |l| {
+        usize::try_from(l).unwrap_or(usize::MAX)
+    });
+
+    let mut engine = server.engine.write().await;
+    match engine.node_relationships(node_id, direction, &types, cursor, limit) {
+        Ok(page) => Json(NodeRelationshipsResponse {
+            message: format!("{} relationships", page.relationships.len()),
+            relationships: page.relationships,
+            next_cursor: page.next_cursor,
+            error: None,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to list relationships of node {}: {}", node_id, e);
+            error(format!("Failed to list relationships: {}", e))
+        }
+    }
+}
+
+/// Response for a reverse lookup by property value
+#[derive(Debug, Serialize)]
+pub struct NodeSearchResponse {
+    /// Success message
+    pub message: String,
+    /// Matches and how they were found, if the search ran
+    pub result: Option<nexus_core::PropertySearchResult>,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Find nodes of any label whose property `key` equals `value`:
+/// `GET /data/nodes/search?key=email&value=ada@example.com&limit=100&scan_limit=100000`.
+///
+/// `value` is read as a JSON scalar when it parses as one (`42`, `true`,
+/// `"42"`) and as a plain string otherwise, so `value=42` searches for
+/// the integer and `value="42"` for the string.
+pub async fn search_nodes(
+    State(server): State<Arc<NexusServer>>,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Json<NodeSearchResponse> {
+    let error = |message: String| {
+        Json(NodeSearchResponse {
+            message: "".to_string(),
+            result: None,
+            error: Some(message),
+        })
+    };
+    let Some(key) = params.get("key") else {
+        return error("Missing required query parameter `key`".to_string());
+    };
+    let Some(raw_value) = params.get("value") else {
+        return error("Missing required query parameter `value`".to_string());
+    };
+    let value = match serde_json::from_str::<serde_json::Value>(raw_value) {
+        Ok(v) if !v.is_array() && !v.is_object() => v,
+        _ => serde_json::Value::String(raw_value.clone()),
+    };
+    let mut numeric = [None, None];
+    for (slot, name) in numeric.iter_mut().zip(["limit", "scan_limit"]) {
+        if let Some(raw) = params.get(name) {
+            match raw.parse::<u64>() {
+                Ok(v) => *slot = Some(v),
+                Err(_) => {
+                    return error(format!(
+                        "Invalid query parameter `{name}`: {raw:?} — expected unsigned integer"
+                    ));
+                }
+            }
+        }
+    }
+    let [limit, scan_limit] = numeric;
+    let limit = limit.map_or(nexus_core::engine::DEFAULT_PROPERTY_SEARCH_LIMIT, |l| {
+        usize::try_from(l).unwrap_or(usize::MAX)
+    });
+    let scan_limit = scan_limit.unwrap_or(nexus_core::engine::DEFAULT_PROPERTY_SEARCH_SCAN_LIMIT);
+
+    let mut engine = server.engine.write().await;
+    match engine.find_nodes_by_property(key, &value, limit, scan_limit) {
+        Ok(result) => Json(NodeSearchResponse {
+            message: format!(
+                "{} nodes found{}",
+                result.matches.len(),
+                if result.truncated { " (truncated)" } else { "" }
+            ),
+            result: Some(result),
+            error: None,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to search nodes by `{}`: {}", key, e);
+            error(format!("Failed to search nodes: {}", e))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1133,4 +1353,173 @@ mod tests {
         assert!(got.error.is_none(), "missing != error");
         assert!(got.message.contains("not found"));
     }
+
+    #[tokio::test]
+    async fn test_diff_nodes_reports_property_changes() {
+        let server = build_test_server();
+        let mut ids = Vec::new();
+        for (label, age) in [("Customer", 36), ("Employee", 37)] {
+            let mut props = HashMap::new();
+            props.insert("name".to_string(), json!("Ada"));
+            props.insert("age".to_string(), json!(age));
+            let create = create_node(
+                State(Arc::clone(&server)),
+                Json(CreateNodeRequest {
+                    labels: vec!["Person".to_string(), label.to_string()],
+                    properties: props,
+                    external_id: None,
+                    conflict_policy: None,
+                }),
+            )
+            .await;
+            assert!(create.error.is_none(), "create failed: {:?}", create.error);
+            ids.push(create.node_id);
+        }
+
+        let mut query = HashMap::new();
+        query.insert("a".to_string(), ids[0].to_string());
+        query.insert("b".to_string(), ids[1].to_string());
+        let response = diff_nodes(State(Arc::clone(&server)), axum::extract::Query(query))
+            .await
+            .0;
+        assert!(response.error.is_none(), "{:?}", response.error);
+        let diff = serde_json::to_value(response.diff.expect("diff")).unwrap();
+        assert_eq!(diff["common_labels"], json!(["Person"]));
+        assert_eq!(diff["common_properties"], json!(["name"]));
+        assert_eq!(diff["changes"]["removed_labels"], json!(["Customer"]));
+        assert_eq!(diff["changes"]["added_labels"], json!(["Employee"]));
+        assert_eq!(
+            diff["changes"]["modified_properties"]["age"],
+            json!({"original": {"Int64": 36}, "new": {"Int64": 37}})
+        );
+
+        let mut query = HashMap::new();
+        query.insert("a".to_string(), ids[0].to_string());
+        let response = diff_nodes(State(server), axum::extract::Query(query))
+            .await
+            .0;
+        assert!(response.diff.is_none());
+        assert!(
+            response
+                .error
+                .as_deref()
+                .is_some_and(|e| e.contains("Missing required query parameter `b`")),
+            "{:?}",
+            response.error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_node_relationships_paginates_and_validates() {
+        let server = build_test_server();
+        let (hub, leaves) = {
+            let mut engine = server.engine.write().await;
+            let hub = engine
+                .create_node(vec!["Hub".to_string()], json!({}))
+                .unwrap();
+            let mut leaves = Vec::new();
+            for _ in 0..3 {
+                let leaf = engine
+                    .create_node(vec!["Leaf".to_string()], json!({}))
+                    .unwrap();
+                engine
+                    .create_relationship(hub, leaf, "LINKS".to_string(), json!({}))
+                    .unwrap();
+                leaves.push(leaf);
+            }
+            (hub, leaves)
+        };
+
+        let mut query = HashMap::new();
+        query.insert("direction".to_string(), "out".to_string());
+        query.insert("limit".to_string(), "2".to_string());
+        let first = get_node_relationships(
+            State(Arc::clone(&server)),
+            axum::extract::Path(hub),
+            axum::extract::Query(query.clone()),
+        )
+        .await
+        .0;
+        assert!(first.error.is_none(), "{:?}", first.error);
+        assert_eq!(first.relationships.len(), 2);
+        let cursor = first.next_cursor.expect("second page");
+
+        query.insert("cursor".to_string(), cursor.to_string());
+        let second = get_node_relationships(
+            State(Arc::clone(&server)),
+            axum::extract::Path(hub),
+            axum::extract::Query(query),
+        )
+        .await
+        .0;
+        assert_eq!(second.relationships.len(), 1);
+        assert_eq!(second.next_cursor, None);
+        let mut ends: Vec<u64> = first
+            .relationships
+            .iter()
+            .chain(&second.relationships)
+            .map(|r| r.end_node)
+            .collect();
+        ends.sort_unstable();
+        assert_eq!(ends, leaves);
+
+        let mut query = HashMap::new();
+        query.insert("direction".to_string(), "sideways".to_string());
+        let response = get_node_relationships(
+            State(server),
+            axum::extract::Path(hub),
+            axum::extract::Query(query),
+        )
+        .await
+        .0;
+        assert!(
+            response
+                .error
+                .as_deref()
+                .is_some_and(|e| e.contains("Invalid direction")),
+            "{:?}",
+            response.error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_nodes_parses_value_as_json_scalar() {
+        let server = build_test_server();
+        {
+            let mut engine = server.engine.write().await;
+            engine
+                .create_node(vec!["Person".to_string()], json!({"code": 42}))
+                .unwrap();
+            engine
+                .create_node(vec!["Device".to_string()], json!({"code": "42"}))
+                .unwrap();
+        }
+
+        for (raw, label) in [("42", "Person"), ("\"42\"", "Device")] {
+            let mut query = HashMap::new();
+            query.insert("key".to_string(), "code".to_string());
+            query.insert("value".to_string(), raw.to_string());
+            let response = search_nodes(State(Arc::clone(&server)), axum::extract::Query(query))
+                .await
+                .0;
+            assert!(response.error.is_none(), "{:?}", response.error);
+            let result = response.result.expect("result");
+            assert_eq!(result.matches.len(), 1, "{raw}");
+            assert_eq!(result.matches[0].labels, vec![label.to_string()]);
+        }
+
+        let mut query = HashMap::new();
+        query.insert("value".to_string(), "42".to_string());
+        let response = search_nodes(State(server), axum::extract::Query(query))
+            .await
+            .0;
+        assert!(
+            response
+                .error
+                .as_deref()
+                .is_some_and(|e| e.contains("`key`")),
+            "{:?}",
+            response.error
+        );
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Provides REST API for managing multiple databases:
 //! - POST /management/databases - Create database
+//! - POST /management/databases/attach - Attach a backup read-only
 //! - DELETE /management/databases/:name - Drop database
 //! - GET /management/databases - List all databases
 //! - GET /management/databases/:name - Get database info
@@ -28,6 +29,20 @@ pub struct DatabaseState {
 pub struct CreateDatabaseRequest {
     /// Database name
     pub name: String,
+    /// Storage backend for the database's store files (`mmap` when
+    /// omitted, or `memory`)
+    #[serde(default)]
+    pub storage_backend: Option<String>,
+}
+
+/// Request to attach a backup directory as a read-only database
+#[derive(Debug, Deserialize)]
+pub struct AttachDatabaseRequest {
+    /// Database name
+    pub name: String,
+    /// Backup directory on the server (as written by the backup
+    /// operation)
+    pub path: std::path::PathBuf,
 }
 
 /// Response for database creation
@@ -66,9 +81,14 @@ pub async fn create_database(
 ) -> Response {
     let manager_arc = state.manager.clone();
     let name = req.name.clone();
+    let storage_backend = req.storage_backend.clone();
     let result = tokio::task::spawn_blocking(move || {
         let manager = manager_arc.read();
-        manager.create_database(&name).map(|_| ())
+        match storage_backend {
+            Some(backend) => manager.create_database_with_backend(&name, &backend),
+            None => manager.create_database(&name),
+        }
+        .map(|_| ())
     })
     .await
     .expect("spawn_blocking panicked");
@@ -91,6 +111,43 @@ pub async fn create_database(
     }
 }
 
+/// Attach a backup directory as a read-only database
+pub async fn attach_database(
+    State(state): State<DatabaseState>,
+    Json(req): Json<AttachDatabaseRequest>,
+) -> Response {
+    let manager_arc = state.manager.clone();
+    let name = req.name.clone();
+    let path = req.path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let manager = manager_arc.read();
+        manager.attach_database(&name, &path).map(|_| ())
+    })
+    .await
+    .expect("spawn_blocking panicked");
+
+    match result {
+        Ok(()) => Json(CreateDatabaseResponse {
+            success: true,
+            name: req.name.clone(),
+            message: format!(
+                "Database '{}' attached read-only from {}",
+                req.name,
+                req.path.display()
+            ),
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(DatabaseResponse {
+                success: false,
+                message: format!("Failed to attach database: {}", e),
+            }),
+        )
+            .into_response(),
+    }
+}
+
 /// Drop a database
 pub async fn drop_database(
     State(state): State<DatabaseState>,
@@ -159,13 +216,20 @@ pub async fn get_database(
             Ok(stats) => (stats.nodes, stats.relationships),
             Err(_) => (0, 0),
         };
-        Ok::<(u64, u64), String>((node_count, relationship_count))
+        let storage_backend = engine_guard.storage_backend_name().to_string();
+        let read_only = engine_guard.read_only_reason().is_some();
+        Ok::<(u64, u64, String, bool), String>((
+            node_count,
+            relationship_count,
+            storage_backend,
+            read_only,
+        ))
     })
     .await
     .expect("spawn_blocking panicked");
 
     match result {
-        Ok((node_count, relationship_count)) => Json(DatabaseInfo {
+        Ok((node_count, relationship_count, storage_backend, read_only)) => Json(DatabaseInfo {
             name: name.clone(),
             path: std::path::PathBuf::new(), // Don't expose full path
             created_at: 0,
@@ -173,6 +237,8 @@ pub async fn get_database(
             relationship_count,
             storage_size: 0,
             state: nexus_core::database::DatabaseState::Online,
+            storage_backend,
+            read_only,
         })
         .into_response(),
         Err(e) => (
@@ -293,6 +359,7 @@ mod tests {
             State(state),
             Json(CreateDatabaseRequest {
                 name: "test_db".to_string(),
+                storage_backend: None,
             }),
         )
         .await;
@@ -300,6 +367,49 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_create_database_with_storage_backend() {
+        let state = create_test_state().await;
+
+        let response = create_database(
+            State(state.clone()),
+            Json(CreateDatabaseRequest {
+                name: "scratch".to_string(),
+                storage_backend: Some("memory".to_string()),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let engine = state.manager.read().get_database("scratch").unwrap();
+        assert_eq!(engine.read().storage_backend_name(), "memory");
+
+        let response = create_database(
+            State(state),
+            Json(CreateDatabaseRequest {
+                name: "remote".to_string(),
+                storage_backend: Some("s3".to_string()),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_attach_database_endpoint() {
+        let state = create_test_state().await;
+
+        let response = attach_database(
+            State(state.clone()),
+            Json(AttachDatabaseRequest {
+                name: "snapshot".to_string(),
+                path: std::path::PathBuf::from("/nonexistent/backup"),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(!state.manager.read().exists("snapshot"));
+    }
+
     #[tokio::test]
     async fn test_list_databases_endpoint() {
         let state = create_test_state().await;
@@ -335,6 +445,7 @@ mod tests {
             State(state),
             Json(CreateDatabaseRequest {
                 name: "invalid name".to_string(),
+                storage_backend: None,
             }),
         )
         .await;
@@ -359,6 +470,7 @@ mod tests {
             State(state),
             Json(CreateDatabaseRequest {
                 name: "test_db".to_string(),
+                storage_backend: None,
             }),
         )
         .await;
@@ -476,6 +588,7 @@ mod tests {
             State(state),
             Json(CreateDatabaseRequest {
                 name: "test_db".to_string(),
+                storage_backend: None,
             }),
         )
         .await;
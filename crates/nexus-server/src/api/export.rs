@@ -213,6 +213,103 @@ async fn export_csv(
         .into_response())
 }
 
+/// CSR topology export parameters
+#[derive(Debug, Deserialize)]
+pub struct CsrExportRequest {
+    /// Export format: "binary" (little-endian arrays, see
+    /// `nexus_core::engine::CsrGraph::to_bytes`) or "json" (default:
+    /// "binary")
+    #[serde(default = "default_csr_format")]
+    pub format: String,
+    /// Comma-separated relationship types to keep (default: all)
+    #[serde(default)]
+    pub types: Option<String>,
+    /// Row orientation: "outgoing" or "incoming" (default: "outgoing")
+    #[serde(default)]
+    pub direction: nexus_core::engine::CsrDirection,
+}
+
+fn default_csr_format() -> String {
+    "binary".to_string()
+}
+
+/// Export the graph topology as compressed sparse row arrays (node ids,
+/// row offsets, target vertex indexes and relationship type ids) for
+/// external analytics engines.
+pub async fn export_csr(
+    State(server): State<std::sync::Arc<NexusServer>>,
+    Query(params): Query<CsrExportRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let options = nexus_core::engine::CsrExportOptions {
+        rel_types: params
+            .types
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+        direction: params.direction,
+    };
+
+    let engine = server.engine.read().await;
+    let csr = engine.export_csr(&options).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("CSR export failed: {}", e),
+        )
+    })?;
+    drop(engine);
+    tracing::info!(
+        "CSR export: {} nodes, {} edges, format={}",
+        csr.node_count(),
+        csr.edge_count(),
+        params.format
+    );
+
+    let (content_type, filename, body) = match params.format.to_lowercase().as_str() {
+        "binary" => ("application/octet-stream", "graph.csr", csr.to_bytes()),
+        "json" => (
+            "application/json",
+            "graph.csr.json",
+            serde_json::to_vec(&csr).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("JSON serialization failed: {}", e),
+                )
+            })?,
+        ),
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Unsupported format: {}. Supported formats: binary, json",
+                    params.format
+                ),
+            ));
+        }
+    };
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .header("x-nexus-csr-nodes", csr.node_count())
+        .header("x-nexus-csr-edges", csr.edge_count())
+        .body(Body::from(body))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to build response: {}", e),
+            )
+        })?
+        .into_response())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -469,4 +566,53 @@ mod tests {
         let response = result.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_export_csr_binary_and_json() {
+        let (server, _temp_dir) = create_test_server().await;
+        server
+            .engine
+            .write()
+            .await
+            .execute_cypher("CREATE (a:P)-[:KNOWS]->(b:P), (a)-[:LIKES]->(b)")
+            .unwrap();
+
+        let params = CsrExportRequest {
+            format: "binary".to_string(),
+            types: Some("KNOWS".to_string()),
+            direction: Default::default(),
+        };
+        let response = export_csr(State(server.clone()), Query(params))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-nexus-csr-edges"], "1");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..8], nexus_core::engine::csr_export::CSR_MAGIC);
+
+        let params = CsrExportRequest {
+            format: "json".to_string(),
+            types: None,
+            direction: nexus_core::engine::CsrDirection::Incoming,
+        };
+        let response = export_csr(State(server.clone()), Query(params))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csr: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(csr["offsets"], serde_json::json!([0, 0, 2]));
+        assert_eq!(csr["targets"], serde_json::json!([0, 0]));
+
+        let params = CsrExportRequest {
+            format: "arrow".to_string(),
+            types: None,
+            direction: Default::default(),
+        };
+        let err = export_csr(State(server), Query(params)).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
 }
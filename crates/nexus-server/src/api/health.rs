@@ -96,7 +96,19 @@ pub async fn health_check(State(server): State<Arc<NexusServer>>) -> Json<Health
     let version = env!("CARGO_PKG_VERSION").to_string();
 
     let components = check_components().await;
-    let overall_status = determine_overall_status(&components);
+    let mut overall_status = determine_overall_status(&components);
+
+    // A store that failed its startup integrity scan under the
+    // `read_only` policy still serves reads, but must not look healthy.
+    let read_only = server
+        .engine
+        .read()
+        .await
+        .read_only_reason()
+        .map(str::to_string);
+    if read_only.is_some() && matches!(overall_status, HealthStatus::Healthy) {
+        overall_status = HealthStatus::Degraded;
+    }
 
     tracing::info!(
         "Health check - Status: {:?}, Uptime: {}s, Components: {:?}",
@@ -111,7 +123,7 @@ pub async fn health_check(State(server): State<Arc<NexusServer>>) -> Json<Health
         uptime_seconds: uptime.as_secs(),
         version,
         components,
-        error: None,
+        error: read_only,
     })
 }
 
@@ -14,14 +14,22 @@
 //! ```
 //!
 //! This matches the openCypher "SymbolicName" production for
-//! un-backtick-quoted identifiers. The server interpolates raw, so
-//! anything richer would require emitting backticks — which the
-//! handlers do not — so the stricter rule is the correct one.
+//! un-backtick-quoted identifiers and is the rule for handlers that
+//! interpolate raw.
+//!
+//! [`quote_identifier`] is the alternative for names that come from
+//! imported data (labels with spaces, emoji, `e-mail` property keys):
+//! it accepts any non-empty name up to [`MAX_IDENTIFIER_LEN`] bytes and
+//! returns it backtick-escaped when it is not a plain identifier, so the
+//! name cannot break out of the pattern and reads back unchanged.
 //!
 //! The helper is intentionally small and side-effect-free so it can
 //! live at every user-input-to-Cypher boundary without measurable
 //! latency.
 
+use nexus_core::executor::parser::escape_identifier;
+use std::borrow::Cow;
+
 /// Why a candidate identifier was rejected.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InvalidIdentifier {
@@ -132,6 +140,27 @@ pub fn validate_identifier(s: &str) -> Result<&str, InvalidIdentifier> {
     Ok(s)
 }
 
+/// Render `s` as a Cypher identifier for interpolation, backtick-escaping
+/// it when it is not a plain identifier. Only empty and overly long
+/// names are rejected.
+///
+/// ```ignore
+/// let label = quote_identifier(&imported_label)?;
+/// let query = format!("MATCH (n:{}) RETURN n", label);
+/// ```
+pub fn quote_identifier(s: &str) -> Result<Cow<'_, str>, InvalidIdentifier> {
+    if s.is_empty() {
+        return Err(InvalidIdentifier::Empty);
+    }
+    if s.len() > MAX_IDENTIFIER_LEN {
+        return Err(InvalidIdentifier::TooLong {
+            value: s.to_string(),
+            len: s.len(),
+        });
+    }
+    Ok(escape_identifier(s))
+}
+
 /// Convenience: validate every identifier in `slice`, returning the
 /// first failure. Useful for endpoints that accept a list of labels.
 pub fn validate_all<'a, I>(slice: I) -> Result<(), InvalidIdentifier>
@@ -236,6 +265,31 @@ mod tests {
         assert_eq!(validate_all(std::iter::empty::<&str>()), Ok(()));
     }
 
+    #[test]
+    fn quote_leaves_plain_identifiers_bare() {
+        assert_eq!(quote_identifier("Person").unwrap(), "Person");
+    }
+
+    #[test]
+    fn quote_escapes_unusual_identifiers() {
+        assert_eq!(quote_identifier("my label").unwrap(), "`my label`");
+        assert_eq!(quote_identifier("e-mail").unwrap(), "`e-mail`");
+        assert_eq!(
+            quote_identifier("Person`) DETACH DELETE n //").unwrap(),
+            "`Person``) DETACH DELETE n //`"
+        );
+    }
+
+    #[test]
+    fn quote_rejects_empty_and_overly_long() {
+        assert_eq!(quote_identifier(""), Err(InvalidIdentifier::Empty));
+        let long = "a".repeat(MAX_IDENTIFIER_LEN + 1);
+        assert!(matches!(
+            quote_identifier(&long),
+            Err(InvalidIdentifier::TooLong { .. })
+        ));
+    }
+
     #[test]
     fn display_names_the_offender() {
         let msg = validate_identifier("Person) ...").unwrap_err().to_string();
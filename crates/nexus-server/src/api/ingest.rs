@@ -1,7 +1,16 @@
-//! Bulk data ingestion endpoint
-
+//! Bulk data ingestion endpoints
+//!
+//! - POST /ingest - Bulk data ingestion, tracked as an ingest job
+//! - GET /ingest/jobs/{id} - Show a job's chunks and row errors
+//! - POST /ingest/jobs/{id}/resume - Retry the rows that were not applied
+//! - POST /ingest/jobs/{id}/rollback - Delete everything the job created
+
+use super::identifier::quote_identifier;
+use super::ingest_jobs::{
+    IngestChunkKind, IngestChunkStatus, IngestJob, IngestJobHandle, IngestJobStatus, IngestPayload,
+};
 use crate::NexusServer;
-use axum::extract::{Json, State};
+use axum::extract::{Json, Path, State};
 use serde::{Deserialize, Serialize};
 
 /// Ingestion request (NDJSON format)
@@ -19,6 +28,26 @@ pub struct IngestRequest {
     /// Whether to use transaction batching (default: true)
     #[serde(default = "default_use_batching")]
     pub use_batching: bool,
+    /// How relationship rows are written (default: `create`)
+    #[serde(default, rename = "relationshipMode", alias = "relationship_mode")]
+    pub relationship_mode: RelationshipMode,
+    /// Properties that, with the endpoints and type, identify a
+    /// relationship in `merge` mode
+    #[serde(default, rename = "relationshipKeys", alias = "relationship_keys")]
+    pub relationship_keys: Vec<String>,
+}
+
+/// How `/ingest` writes relationship rows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipMode {
+    /// Always create a new relationship
+    #[default]
+    Create,
+    /// Reuse an existing relationship of the same type between the same
+    /// endpoints (and with the same `relationshipKeys` values) instead of
+    /// adding a duplicate
+    Merge,
 }
 
 fn default_batch_size() -> usize {
@@ -62,10 +91,17 @@ pub struct RelIngest {
 /// Ingestion response
 #[derive(Debug, Serialize)]
 pub struct IngestResponse {
+    /// Id of the ingest job, for `GET /ingest/jobs/{id}`
+    pub job_id: String,
+    /// Job status after this request
+    pub job_status: IngestJobStatus,
     /// Number of nodes ingested
     pub nodes_ingested: usize,
     /// Number of relationships ingested
     pub relationships_ingested: usize,
+    /// Relationship rows that matched an existing relationship (merge mode)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationships_merged: Option<usize>,
     /// Ingestion time in milliseconds
     pub ingestion_time_ms: u64,
     /// Number of batches processed
@@ -110,33 +146,37 @@ async fn ingest_data_inner(
     );
 
     let total_items = request.nodes.len() + request.relationships.len();
-    let mut nodes_ingested = 0;
-    let mut relationships_ingested = 0;
-    let mut errors = Vec::new();
-    let mut batches_processed = 0;
-
-    if request.use_batching && total_items > request.batch_size {
-        // Use transaction batching for large imports
-        batches_processed = process_with_batching(
-            &server,
-            &request,
-            &mut nodes_ingested,
-            &mut relationships_ingested,
-            &mut errors,
-        )
-        .await;
+    // Large imports run one transaction per batch; small imports and
+    // unbatched requests put all nodes (and all relationships) in a
+    // single chunk applied row by row.
+    let use_batching = request.use_batching && total_items > request.batch_size;
+    let chunk_size = if use_batching {
+        request.batch_size
     } else {
-        // Process without batching (small imports or batching disabled)
-        process_without_batching(
-            &server,
-            &request,
-            &mut nodes_ingested,
-            &mut relationships_ingested,
-            &mut errors,
-        )
-        .await;
-    }
-
+        usize::MAX
+    };
+    let job = IngestJob::new(
+        IngestPayload {
+            nodes: request.nodes,
+            relationships: request.relationships,
+            relationship_mode: request.relationship_mode,
+            relationship_keys: request.relationship_keys,
+        },
+        chunk_size,
+        use_batching,
+    );
+    let handle = server.ingest_jobs.insert(job);
+    run_job(&server, &handle).await;
+    let job = handle.lock().clone();
+
+    let nodes_ingested = job.applied(IngestChunkKind::Nodes);
+    let relationships_ingested = job.applied(IngestChunkKind::Relationships);
+    let relationships_merged = job.merged();
+    let batches_processed = if use_batching {
+        job.chunks_attempted()
+    } else {
+        0
+    };
     let execution_time = start_time.elapsed().as_millis() as u64;
     let progress_percent = if total_items > 0 {
         Some((nodes_ingested + relationships_ingested) as f64 / total_items as f64 * 100.0)
@@ -145,16 +185,22 @@ async fn ingest_data_inner(
     };
 
     tracing::info!(
-        "Ingestion completed in {}ms: {} nodes, {} relationships, {} batches",
+        "Ingestion job {} finished in {}ms: {} nodes, {} relationships, {} batches",
+        job.id,
         execution_time,
         nodes_ingested,
         relationships_ingested,
         batches_processed
     );
 
+    let errors = job.error_messages();
     Json(IngestResponse {
+        job_id: job.id,
+        job_status: job.status,
         nodes_ingested,
         relationships_ingested,
+        relationships_merged: (job.relationship_mode == RelationshipMode::Merge)
+            .then_some(relationships_merged),
         ingestion_time_ms: execution_time,
         batches_processed: if batches_processed > 0 {
             Some(batches_processed)
@@ -170,210 +216,413 @@ async fn ingest_data_inner(
     })
 }
 
-/// Process ingestion with transaction batching
-async fn process_with_batching(
-    server: &std::sync::Arc<NexusServer>,
-    request: &IngestRequest,
-    nodes_ingested: &mut usize,
-    relationships_ingested: &mut usize,
-    errors: &mut Vec<String>,
-) -> usize {
-    let mut batches_processed = 0;
-    let batch_size = request.batch_size;
-
-    // Process nodes in batches
-    for batch in request.nodes.chunks(batch_size) {
-        batches_processed += 1;
-        let mut batch_nodes = 0;
-        let mut batch_errors = Vec::new();
-
-        // Start transaction for this batch
-        let mut engine = server.engine.write().await;
+/// Apply every chunk of `job` that still has rows left, then settle the
+/// job status.
+async fn run_job(server: &std::sync::Arc<NexusServer>, job: &IngestJobHandle) {
+    run_job_until(server, job, |_| true).await;
+}
 
-        // Begin transaction
-        let begin_query = "BEGIN TRANSACTION".to_string();
-        if let Err(e) = engine.execute_cypher(&begin_query) {
-            errors.push(format!(
-                "Failed to begin transaction for batch {}: {}",
-                batches_processed, e
-            ));
-            continue;
-        }
-        drop(engine);
+/// [`run_job`], asking `before_chunk` before each chunk whether to go on.
+/// When it returns `false` the remaining chunks are left pending, so the
+/// job settles as failed and can be resumed or rolled back later.
+pub(super) async fn run_job_until(
+    server: &std::sync::Arc<NexusServer>,
+    job: &IngestJobHandle,
+    mut before_chunk: impl FnMut(&IngestJob) -> bool,
+) {
+    let (payload, transactional, chunks) = {
+        let job = job.lock();
+        let chunks: Vec<usize> = job
+            .chunks
+            .iter()
+            .filter(|c| c.is_resumable())
+            .map(|c| c.index)
+            .collect();
+        (job.payload.clone(), job.transactional, chunks)
+    };
 
-        // Process nodes in this batch
-        for node in batch {
-            match create_node_in_batch(server, node).await {
-                Ok(_) => batch_nodes += 1,
-                Err(e) => batch_errors.push(format!("Node creation failed: {}", e)),
+    if let Some(payload) = payload {
+        for index in chunks {
+            if !before_chunk(&job.lock()) {
+                break;
             }
+            run_chunk(server, job, &payload, index, transactional).await;
         }
+    }
+
+    job.lock().finish_run();
+}
+
+/// What applying one row did
+enum RowOutcome {
+    /// A node or relationship was created (with its id, when returned)
+    Created(Option<u64>),
+    /// The row matched an existing relationship
+    Merged,
+}
+
+/// Apply the remaining rows of one chunk, inside a transaction when the
+/// job is batched. Row outcomes are recorded on the chunk as they happen
+/// so a job lookup mid-run shows live progress.
+async fn run_chunk(
+    server: &std::sync::Arc<NexusServer>,
+    job: &IngestJobHandle,
+    payload: &IngestPayload,
+    index: usize,
+    transactional: bool,
+) {
+    let (kind, rows) = {
+        let mut job = job.lock();
+        let chunk = &mut job.chunks[index];
+        chunk.begin_attempt();
+        (chunk.kind, chunk.remaining.clone())
+    };
 
-        // Commit transaction
+    if transactional {
         let mut engine = server.engine.write().await;
-        let commit_query = "COMMIT TRANSACTION".to_string();
-        if let Err(e) = engine.execute_cypher(&commit_query) {
-            batch_errors.push(format!("Transaction commit failed: {}", e));
+        if let Err(e) = engine.execute_cypher("BEGIN TRANSACTION") {
+            let mut job = job.lock();
+            let chunk = &mut job.chunks[index];
+            chunk.error = Some(format!("Failed to begin transaction: {}", e));
+            chunk.finish_attempt();
+            return;
         }
-        drop(engine);
-
-        *nodes_ingested += batch_nodes;
-        errors.extend(batch_errors);
     }
 
-    // Process relationships in batches
-    for batch in request.relationships.chunks(batch_size) {
-        batches_processed += 1;
-        let mut batch_rels = 0;
-        let mut batch_errors = Vec::new();
+    for row in rows {
+        let result = match kind {
+            IngestChunkKind::Nodes => create_node_in_batch(server, &payload.nodes[row])
+                .await
+                .map(RowOutcome::Created),
+            IngestChunkKind::Relationships => match payload.relationship_mode {
+                RelationshipMode::Create => {
+                    create_relationship_in_batch(server, &payload.relationships[row])
+                        .await
+                        .map(RowOutcome::Created)
+                }
+                RelationshipMode::Merge => {
+                    merge_relationship_in_batch(
+                        server,
+                        &payload.relationships[row],
+                        &payload.relationship_keys,
+                    )
+                    .await
+                }
+            },
+        };
+        let mut job = job.lock();
+        let chunk = &mut job.chunks[index];
+        match result {
+            Ok(RowOutcome::Created(id)) => chunk.record_applied(row, id),
+            Ok(RowOutcome::Merged) => chunk.record_merged(row),
+            Err(e) => chunk.record_failed(row, e),
+        }
+    }
 
-        // Start transaction for this batch
+    let commit_error = if transactional {
         let mut engine = server.engine.write().await;
+        engine
+            .execute_cypher("COMMIT TRANSACTION")
+            .err()
+            .map(|e| format!("Transaction commit failed: {}", e))
+    } else {
+        None
+    };
 
-        // Begin transaction
-        let begin_query = "BEGIN TRANSACTION".to_string();
-        if let Err(e) = engine.execute_cypher(&begin_query) {
-            errors.push(format!(
-                "Failed to begin transaction for batch {}: {}",
-                batches_processed, e
-            ));
-            continue;
-        }
-        drop(engine);
+    let mut job = job.lock();
+    let chunk = &mut job.chunks[index];
+    if commit_error.is_some() {
+        // The refused commit rolled the chunk's rows back
+        chunk.discard_attempt();
+    }
+    chunk.error = commit_error;
+    chunk.finish_attempt();
+}
+
+/// Remove everything `job` created: relationships first, then nodes
+/// (detached, so edges added outside the job do not block the delete).
+/// Ids that could not be deleted stay on their chunk so the rollback can
+/// be retried.
+async fn rollback_job(server: &std::sync::Arc<NexusServer>, job: &IngestJobHandle) {
+    let chunks: Vec<(usize, IngestChunkKind, Vec<u64>)> = {
+        let job = job.lock();
+        let mut chunks: Vec<_> = job
+            .chunks
+            .iter()
+            .filter(|c| c.status != IngestChunkStatus::RolledBack)
+            .map(|c| (c.index, c.kind, c.created_ids.clone()))
+            .collect();
+        chunks.sort_by_key(|(_, kind, _)| *kind == IngestChunkKind::Nodes);
+        chunks
+    };
 
-        // Process relationships in this batch
-        for rel in batch {
-            match create_relationship_in_batch(server, rel).await {
-                Ok(_) => batch_rels += 1,
-                Err(e) => batch_errors.push(format!("Relationship creation failed: {}", e)),
+    for (index, kind, ids) in chunks {
+        let mut failed = Vec::new();
+        let mut first_error = None;
+        for id in ids {
+            let query = match kind {
+                IngestChunkKind::Nodes => format!("MATCH (n) WHERE id(n) = {} DETACH DELETE n", id),
+                IngestChunkKind::Relationships => {
+                    format!("MATCH ()-[r]->() WHERE id(r) = {} DELETE r", id)
+                }
+            };
+            let mut engine = server.engine.write().await;
+            if let Err(e) = engine.execute_cypher(&query) {
+                first_error.get_or_insert_with(|| format!("Rollback of id {} failed: {}", id, e));
+                failed.push(id);
             }
         }
 
-        // Commit transaction
-        let mut engine = server.engine.write().await;
-        let commit_query = "COMMIT TRANSACTION".to_string();
-        if let Err(e) = engine.execute_cypher(&commit_query) {
-            batch_errors.push(format!("Transaction commit failed: {}", e));
+        let mut job = job.lock();
+        let chunk = &mut job.chunks[index];
+        if first_error.is_none() {
+            chunk.status = IngestChunkStatus::RolledBack;
+            chunk.applied = 0;
+            chunk.remaining = (chunk.start_row..chunk.end_row).collect();
+        } else {
+            chunk.status = IngestChunkStatus::Failed;
         }
-        drop(engine);
-
-        *relationships_ingested += batch_rels;
-        errors.extend(batch_errors);
+        chunk.created_ids = failed;
+        chunk.error = first_error;
     }
 
-    batches_processed
+    job.lock().finish_rollback();
 }
 
-/// Process ingestion without batching
-async fn process_without_batching(
-    server: &std::sync::Arc<NexusServer>,
-    request: &IngestRequest,
-    nodes_ingested: &mut usize,
-    relationships_ingested: &mut usize,
-    errors: &mut Vec<String>,
-) {
-    // Process nodes
-    for node in &request.nodes {
-        match create_node_in_batch(server, node).await {
-            Ok(_) => *nodes_ingested += 1,
-            Err(e) => errors.push(format!("Node ingestion failed: {}", e)),
+/// Response carrying one ingest job
+#[derive(Debug, Serialize)]
+pub struct IngestJobResponse {
+    /// Success message
+    pub message: String,
+    /// The job, if found
+    pub job: Option<IngestJob>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn job_error(message: String) -> Json<IngestJobResponse> {
+    Json(IngestJobResponse {
+        message: String::new(),
+        job: None,
+        error: Some(message),
+    })
+}
+
+/// Look up a job and mark it running, unless another resume or rollback
+/// already holds it.
+fn claim_job(server: &NexusServer, id: &str) -> Result<IngestJobHandle, String> {
+    let handle = server
+        .ingest_jobs
+        .get(id)
+        .ok_or_else(|| format!("Ingest job '{}' not found", id))?;
+    {
+        let mut job = handle.lock();
+        if job.status == IngestJobStatus::Running {
+            return Err(format!("Ingest job '{}' is already running", id));
         }
+        if job.status == IngestJobStatus::RolledBack {
+            return Err(format!("Ingest job '{}' has been rolled back", id));
+        }
+        job.status = IngestJobStatus::Running;
+        job.touch();
     }
+    Ok(handle)
+}
 
-    // Process relationships
-    for rel in &request.relationships {
-        match create_relationship_in_batch(server, rel).await {
-            Ok(_) => *relationships_ingested += 1,
-            Err(e) => errors.push(format!("Relationship ingestion failed: {}", e)),
+/// Show an ingest job with per-chunk status and row errors
+pub async fn get_ingest_job(
+    State(server): State<std::sync::Arc<NexusServer>>,
+    Path(id): Path<String>,
+) -> Json<IngestJobResponse> {
+    match server.ingest_jobs.get(&id) {
+        Some(handle) => {
+            let job = handle.lock().clone();
+            Json(IngestJobResponse {
+                message: format!("Ingest job '{}'", id),
+                job: Some(job),
+                error: None,
+            })
         }
+        None => job_error(format!("Ingest job '{}' not found", id)),
+    }
+}
+
+/// Retry the rows of a job that have not been applied yet
+pub async fn resume_ingest_job(
+    State(server): State<std::sync::Arc<NexusServer>>,
+    Path(id): Path<String>,
+) -> Json<IngestJobResponse> {
+    let handle = match claim_job(&server, &id) {
+        Ok(handle) => handle,
+        Err(e) => return job_error(e),
+    };
+    if handle.lock().payload.is_none() {
+        handle.lock().finish_run();
+        return job_error(format!("Ingest job '{}' has nothing left to resume", id));
     }
+
+    run_job(&server, &handle).await;
+    let job = handle.lock().clone();
+    tracing::info!("Resumed ingest job {}: {:?}", id, job.status);
+    Json(IngestJobResponse {
+        message: format!("Ingest job '{}' resumed", id),
+        job: Some(job),
+        error: None,
+    })
+}
+
+/// Delete every node and relationship a job created
+pub async fn rollback_ingest_job(
+    State(server): State<std::sync::Arc<NexusServer>>,
+    Path(id): Path<String>,
+) -> Json<IngestJobResponse> {
+    let handle = match claim_job(&server, &id) {
+        Ok(handle) => handle,
+        Err(e) => return job_error(e),
+    };
+
+    rollback_job(&server, &handle).await;
+    let job = handle.lock().clone();
+    tracing::info!("Rolled back ingest job {}: {:?}", id, job.status);
+    let error =
+        (job.status != IngestJobStatus::RolledBack).then(|| job.error_messages().join("; "));
+    Json(IngestJobResponse {
+        message: format!("Ingest job '{}' rolled back", id),
+        job: Some(job),
+        error,
+    })
+}
+
+/// Render an ingested property object as a Cypher map literal
+/// (` {key: value, ...}`), quoting keys that are not plain identifiers.
+/// Non-object and empty values render as nothing.
+fn properties_source(properties: &serde_json::Value) -> Result<String, String> {
+    let Some(props_map) = properties.as_object().filter(|m| !m.is_empty()) else {
+        return Ok(String::new());
+    };
+    let props = props_map
+        .iter()
+        .map(|(k, v)| {
+            let key = quote_identifier(k).map_err(|e| format!("invalid property key: {}", e))?;
+            let v_str = serde_json::to_string(v).unwrap_or_else(|_| "null".to_string());
+            Ok(format!("{}: {}", key, v_str))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(format!(" {{{}}}", props.join(", ")))
 }
 
-/// Create a node in batch
+/// Create a node in batch, returning its id
 async fn create_node_in_batch(
     server: &std::sync::Arc<NexusServer>,
     node: &NodeIngest,
-) -> Result<(), String> {
-    // Validate every label before it enters the Cypher query — otherwise
+) -> Result<Option<u64>, String> {
+    // Quote every label before it enters the Cypher query — otherwise
     // a crafted label like `Person) DETACH DELETE n //` would escape the
-    // node pattern.
-    super::identifier::validate_all(node.labels.iter().map(String::as_str))
+    // node pattern, and imported labels with spaces would not parse.
+    let labels_str = node
+        .labels
+        .iter()
+        .map(|l| quote_identifier(l).map(|q| format!(":{}", q)))
+        .collect::<Result<String, _>>()
         .map_err(|e| format!("invalid label: {}", e))?;
 
-    let labels_str = if node.labels.is_empty() {
-        "".to_string()
-    } else {
-        format!(":{}", node.labels.join(":"))
-    };
-
     // Build properties string
-    let props_str = if node.properties.is_object() {
-        let props_map = node.properties.as_object().unwrap();
-        if props_map.is_empty() {
-            String::new()
-        } else {
-            let props: Vec<String> = props_map
-                .iter()
-                .map(|(k, v)| {
-                    let v_str = serde_json::to_string(v).unwrap_or_else(|_| "null".to_string());
-                    format!("{}: {}", k, v_str)
-                })
-                .collect();
-            format!(" {{{}}}", props.join(", "))
-        }
-    } else {
-        String::new()
-    };
+    let props_str = properties_source(&node.properties)?;
 
-    let cypher_query = format!("CREATE (n{}{}) RETURN n", labels_str, props_str);
+    let cypher_query = format!("CREATE (n{}{}) RETURN id(n)", labels_str, props_str);
 
     let mut engine = server.engine.write().await;
-    engine
+    let result = engine
         .execute_cypher(&cypher_query)
         .map_err(|e| e.to_string())?;
-    Ok(())
+    Ok(first_id(&result))
 }
 
-/// Create a relationship in batch
+/// Create a relationship in batch, returning its id
 async fn create_relationship_in_batch(
     server: &std::sync::Arc<NexusServer>,
     rel: &RelIngest,
-) -> Result<(), String> {
-    // Validate the relationship type before interpolating it into the
+) -> Result<Option<u64>, String> {
+    // Quote the relationship type before interpolating it into the
     // CREATE query — prevents `KNOWS]->(x) MATCH (m) DETACH DELETE m //`
     // style escapes.
-    super::identifier::validate_identifier(&rel.r#type)
-        .map_err(|e| format!("invalid relationship type: {}", e))?;
+    let rel_type =
+        quote_identifier(&rel.r#type).map_err(|e| format!("invalid relationship type: {}", e))?;
 
-    // Build properties string
-    let props_str = if rel.properties.is_object() {
-        let props_map = rel.properties.as_object().unwrap();
-        if props_map.is_empty() {
-            String::new()
-        } else {
-            let props: Vec<String> = props_map
-                .iter()
-                .map(|(k, v)| {
-                    let v_str = serde_json::to_string(v).unwrap_or_else(|_| "null".to_string());
-                    format!("{}: {}", k, v_str)
-                })
-                .collect();
-            format!(" {{{}}}", props.join(", "))
-        }
-    } else {
-        String::new()
-    };
+    let props_str = properties_source(&rel.properties)?;
 
     let cypher_query = format!(
-        "MATCH (a), (b) WHERE id(a) = {} AND id(b) = {} CREATE (a)-[r:{}{}]->(b) RETURN r",
-        rel.src, rel.dst, rel.r#type, props_str
+        "MATCH (a), (b) WHERE id(a) = {} AND id(b) = {} CREATE (a)-[r:{}{}]->(b) RETURN id(r)",
+        rel.src, rel.dst, rel_type, props_str
     );
 
     let mut engine = server.engine.write().await;
-    engine
+    let result = engine
         .execute_cypher(&cypher_query)
         .map_err(|e| e.to_string())?;
-    Ok(())
+    // The MATCH yields no row when either endpoint is missing, in which
+    // case nothing was created.
+    if result.rows.is_empty() {
+        return Err(format!(
+            "source node {} or destination node {} not found",
+            rel.src, rel.dst
+        ));
+    }
+    Ok(first_id(&result))
+}
+
+/// Create a relationship unless an equivalent one already exists.
+///
+/// Goes through `Engine::merge_relationship`, which answers the existence
+/// check from the exact-edge relationship index instead of running a
+/// `MATCH` per row.
+async fn merge_relationship_in_batch(
+    server: &std::sync::Arc<NexusServer>,
+    rel: &RelIngest,
+    key_properties: &[String],
+) -> Result<RowOutcome, String> {
+    // Same validation as the CREATE path, even though nothing is
+    // interpolated into a query here.
+    quote_identifier(&rel.r#type).map_err(|e| format!("invalid relationship type: {}", e))?;
+    let properties = if rel.properties.is_object() {
+        rel.properties.clone()
+    } else {
+        serde_json::Value::Object(serde_json::Map::new())
+    };
+
+    let mut engine = server.engine.write().await;
+    for id in [rel.src, rel.dst] {
+        if engine.get_node(id).map_err(|e| e.to_string())?.is_none() {
+            return Err(format!(
+                "source node {} or destination node {} not found",
+                rel.src, rel.dst
+            ));
+        }
+    }
+    let (rel_id, created) = engine
+        .merge_relationship(
+            rel.src,
+            rel.dst,
+            rel.r#type.clone(),
+            properties,
+            key_properties,
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(if created {
+        RowOutcome::Created(Some(rel_id))
+    } else {
+        RowOutcome::Merged
+    })
+}
+
+/// The id in the first column of the first row, if any.
+fn first_id(result: &nexus_core::executor::ResultSet) -> Option<u64> {
+    result
+        .rows
+        .first()
+        .and_then(|row| row.values.first())
+        .and_then(serde_json::Value::as_u64)
 }
 
 #[cfg(test)]
@@ -461,6 +710,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let _response = ingest_data_inner(State(server), request).await;
@@ -481,6 +732,8 @@ mod tests {
             }],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let _response = ingest_data_inner(State(server), request).await;
@@ -505,12 +758,146 @@ mod tests {
             }],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let _response = ingest_data_inner(State(server), request).await;
         // Test passes if no panic occurs
     }
 
+    #[test]
+    fn test_properties_source_quotes_unusual_keys() {
+        assert_eq!(
+            properties_source(&json!({"first name": "Ann", "age": 30})).unwrap(),
+            r#" {age: 30, `first name`: "Ann"}"#
+        );
+        assert_eq!(properties_source(&json!({})).unwrap(), "");
+        assert!(properties_source(&json!({"": 1})).is_err());
+    }
+
+    async fn count_people(server: &NexusServer) -> u64 {
+        let mut engine = server.engine.write().await;
+        let result = engine
+            .execute_cypher("MATCH (n:Person) RETURN count(n)")
+            .unwrap();
+        first_id(&result).unwrap_or(0)
+    }
+
+    #[tokio::test]
+    async fn test_ingest_job_reports_failed_rows_and_rolls_back() {
+        let (_ctx, server) = create_test_server().await;
+        let person = |name: &str| NodeIngest {
+            id: None,
+            labels: vec!["Person".to_string()],
+            properties: json!({ "name": name }),
+        };
+        let request = IngestRequest {
+            nodes: vec![
+                person("Alice"),
+                NodeIngest {
+                    id: None,
+                    labels: vec![String::new()],
+                    properties: json!({}),
+                },
+                person("Bob"),
+            ],
+            relationships: vec![],
+            batch_size: 1000,
+            use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
+        };
+
+        let Json(response) = ingest_data_inner(State(server.clone()), request).await;
+        assert_eq!(response.job_status, IngestJobStatus::Failed);
+        assert_eq!(response.nodes_ingested, 2);
+        assert!(response.error.unwrap().starts_with("Node row 1:"));
+
+        let Json(lookup) =
+            get_ingest_job(State(server.clone()), Path(response.job_id.clone())).await;
+        let job = lookup.job.unwrap();
+        assert_eq!(job.chunks.len(), 1);
+        assert_eq!(job.chunks[0].status, IngestChunkStatus::Failed);
+        assert_eq!(job.chunks[0].errors[0].row, 1);
+
+        // Resuming retries only the failed row, so nothing is duplicated.
+        let Json(resumed) =
+            resume_ingest_job(State(server.clone()), Path(response.job_id.clone())).await;
+        let job = resumed.job.unwrap();
+        assert_eq!(job.status, IngestJobStatus::Failed);
+        assert_eq!(job.applied(IngestChunkKind::Nodes), 2);
+        assert_eq!(count_people(&server).await, 2);
+
+        let Json(rolled_back) =
+            rollback_ingest_job(State(server.clone()), Path(response.job_id.clone())).await;
+        assert!(rolled_back.error.is_none());
+        assert_eq!(rolled_back.job.unwrap().status, IngestJobStatus::RolledBack);
+        assert_eq!(count_people(&server).await, 0);
+
+        let Json(again) = resume_ingest_job(State(server), Path(response.job_id)).await;
+        assert!(again.error.unwrap().contains("rolled back"));
+    }
+
+    #[tokio::test]
+    async fn test_ingest_relationship_merge_mode_skips_duplicates() {
+        let (_ctx, server) = create_test_server().await;
+        let (a, b) = {
+            let mut engine = server.engine.write().await;
+            let a = engine
+                .create_node(vec!["Person".to_string()], json!({}))
+                .unwrap();
+            let b = engine
+                .create_node(vec!["Person".to_string()], json!({}))
+                .unwrap();
+            engine
+                .create_relationship(a, b, "KNOWS".to_string(), json!({"since": 2020}))
+                .unwrap();
+            (a, b)
+        };
+        let knows = |since: i64| RelIngest {
+            id: None,
+            src: a,
+            dst: b,
+            r#type: "KNOWS".to_string(),
+            properties: json!({ "since": since }),
+        };
+        let request = IngestRequest {
+            nodes: vec![],
+            relationships: vec![knows(2020), knows(2021), knows(2021)],
+            batch_size: 1000,
+            use_batching: false,
+            relationship_mode: RelationshipMode::Merge,
+            relationship_keys: vec!["since".to_string()],
+        };
+
+        let Json(response) = ingest_data_inner(State(server.clone()), request).await;
+        assert_eq!(response.job_status, IngestJobStatus::Completed);
+        assert_eq!(response.relationships_ingested, 3);
+        assert_eq!(response.relationships_merged, Some(2));
+
+        let count_knows = || async {
+            let mut engine = server.engine.write().await;
+            let result = engine
+                .execute_cypher("MATCH ()-[r:KNOWS]->() RETURN count(r)")
+                .unwrap();
+            first_id(&result).unwrap_or(0)
+        };
+        assert_eq!(count_knows().await, 2);
+
+        // Rolling back removes only the edge the job created.
+        rollback_ingest_job(State(server.clone()), Path(response.job_id)).await;
+        assert_eq!(count_knows().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_unknown_ingest_job() {
+        let (_ctx, server) = create_test_server().await;
+        let Json(response) = get_ingest_job(State(server), Path("missing".to_string())).await;
+        assert!(response.job.is_none());
+        assert!(response.error.is_some());
+    }
+
     #[tokio::test]
     #[ignore] // TODO: Fix temp dir race condition in parallel tests
     async fn test_ingest_empty_request() {
@@ -520,6 +907,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let _response = ingest_data_inner(State(server), request).await;
@@ -537,6 +926,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -555,6 +946,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -582,6 +975,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -604,6 +999,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -622,6 +1019,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -640,6 +1039,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -658,6 +1059,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -705,6 +1108,8 @@ mod tests {
             }],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -736,6 +1141,8 @@ mod tests {
             }],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -767,6 +1174,8 @@ mod tests {
             }],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
@@ -790,6 +1199,8 @@ mod tests {
             relationships: vec![],
             batch_size: 1000,
             use_batching: false,
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: vec![],
         };
 
         let (_temp_dir, server) = create_test_server().await;
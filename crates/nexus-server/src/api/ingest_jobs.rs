@@ -0,0 +1,539 @@
+//! Bookkeeping for `/ingest` jobs.
+//!
+//! Every ingest request becomes a job split into chunks (one per batch of
+//! nodes or relationships). A chunk records which of its rows were
+//! applied, which failed and why, and the ids of everything it created,
+//! so a caller whose ingest stopped half-way can look the job up, retry
+//! just the rows that did not land, or undo the whole job.
+//!
+//! This module only holds state; running chunks against the engine lives
+//! in [`super::ingest`].
+
+use super::ingest::{NodeIngest, RelIngest, RelationshipMode};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Jobs kept for lookup before the oldest finished ones are forgotten.
+pub const DEFAULT_MAX_RETAINED_JOBS: usize = 256;
+
+/// Row errors kept per chunk; further failures are only counted.
+pub const MAX_REPORTED_ROW_ERRORS: usize = 100;
+
+/// Lifecycle of an ingest job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestJobStatus {
+    /// Chunks are being applied or rolled back right now
+    Running,
+    /// Every row was applied
+    Completed,
+    /// Some rows or chunks failed; the job can be resumed or rolled back
+    Failed,
+    /// Everything the job created has been removed again
+    RolledBack,
+}
+
+/// Which half of the request a chunk covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestChunkKind {
+    /// Rows of `nodes`
+    Nodes,
+    /// Rows of `relationships`
+    Relationships,
+}
+
+/// Progress of a single chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestChunkStatus {
+    /// Not attempted yet
+    Pending,
+    /// Every row in the chunk was applied
+    Applied,
+    /// At least one row (or the chunk's transaction) failed
+    Failed,
+    /// The rows this chunk created were removed
+    RolledBack,
+}
+
+/// A row that could not be applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IngestRowError {
+    /// Zero-based index into the request's `nodes` or `relationships`
+    pub row: usize,
+    /// Why the row failed
+    pub message: String,
+}
+
+/// One batch of rows of an ingest job
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestChunk {
+    /// Position of the chunk within the job
+    pub index: usize,
+    /// Whether the chunk holds nodes or relationships
+    pub kind: IngestChunkKind,
+    /// First row covered by the chunk
+    pub start_row: usize,
+    /// One past the last row covered by the chunk
+    pub end_row: usize,
+    /// Chunk status
+    pub status: IngestChunkStatus,
+    /// Rows applied so far
+    pub applied: usize,
+    /// Applied rows that matched an existing relationship instead of
+    /// creating one (`relationshipMode: merge`)
+    pub merged: usize,
+    /// Failures from the latest attempt, by row
+    pub errors: Vec<IngestRowError>,
+    /// Failures beyond [`MAX_REPORTED_ROW_ERRORS`]
+    pub errors_truncated: usize,
+    /// Chunk-level failure (transaction begin / commit, rollback)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Rows still to apply
+    #[serde(skip)]
+    pub(crate) remaining: Vec<usize>,
+    /// Ids of the nodes or relationships this chunk created
+    #[serde(skip)]
+    pub(crate) created_ids: Vec<u64>,
+    /// Progress when the current attempt began, restored if the attempt
+    /// is discarded
+    #[serde(skip)]
+    attempt_start: AttemptStart,
+}
+
+/// Chunk bookkeeping captured by [`IngestChunk::begin_attempt`]
+#[derive(Debug, Clone, Default)]
+struct AttemptStart {
+    remaining: Vec<usize>,
+    applied: usize,
+    merged: usize,
+    created: usize,
+}
+
+impl IngestChunk {
+    fn new(index: usize, kind: IngestChunkKind, start_row: usize, end_row: usize) -> Self {
+        Self {
+            index,
+            kind,
+            start_row,
+            end_row,
+            status: IngestChunkStatus::Pending,
+            applied: 0,
+            merged: 0,
+            errors: Vec::new(),
+            errors_truncated: 0,
+            error: None,
+            remaining: (start_row..end_row).collect(),
+            created_ids: Vec::new(),
+            attempt_start: AttemptStart::default(),
+        }
+    }
+
+    /// Clear the failures of the previous attempt before retrying, and
+    /// note the progress so far for [`Self::discard_attempt`].
+    pub(crate) fn begin_attempt(&mut self) {
+        self.errors.clear();
+        self.errors_truncated = 0;
+        self.error = None;
+        self.attempt_start = AttemptStart {
+            remaining: self.remaining.clone(),
+            applied: self.applied,
+            merged: self.merged,
+            created: self.created_ids.len(),
+        };
+    }
+
+    /// Undo the bookkeeping of the current attempt, whose writes did not
+    /// persist (its transaction failed to commit). Its rows are remaining
+    /// again, and no longer count as applied, merged or created.
+    pub(crate) fn discard_attempt(&mut self) {
+        self.remaining = self.attempt_start.remaining.clone();
+        self.applied = self.attempt_start.applied;
+        self.merged = self.attempt_start.merged;
+        self.created_ids.truncate(self.attempt_start.created);
+    }
+
+    /// Record that `row` was applied and created `id` (if one was returned).
+    pub(crate) fn record_applied(&mut self, row: usize, id: Option<u64>) {
+        self.remaining.retain(|&r| r != row);
+        self.applied += 1;
+        self.created_ids.extend(id);
+    }
+
+    /// Record that `row` matched an existing relationship. Nothing was
+    /// created, so a rollback leaves that relationship alone.
+    pub(crate) fn record_merged(&mut self, row: usize) {
+        self.remaining.retain(|&r| r != row);
+        self.applied += 1;
+        self.merged += 1;
+    }
+
+    /// Record that `row` failed; it stays in the remaining set.
+    pub(crate) fn record_failed(&mut self, row: usize, message: String) {
+        if self.errors.len() < MAX_REPORTED_ROW_ERRORS {
+            self.errors.push(IngestRowError { row, message });
+        } else {
+            self.errors_truncated += 1;
+        }
+    }
+
+    /// Settle the chunk status after an attempt.
+    pub(crate) fn finish_attempt(&mut self) {
+        self.status = if self.remaining.is_empty() && self.error.is_none() {
+            IngestChunkStatus::Applied
+        } else {
+            IngestChunkStatus::Failed
+        };
+    }
+
+    /// Whether a resume has anything left to do in this chunk.
+    pub fn is_resumable(&self) -> bool {
+        matches!(
+            self.status,
+            IngestChunkStatus::Pending | IngestChunkStatus::Failed
+        ) && !self.remaining.is_empty()
+    }
+}
+
+/// Rows of the original request, kept so failed chunks can be retried.
+#[derive(Debug)]
+pub struct IngestPayload {
+    /// Nodes to ingest
+    pub nodes: Vec<NodeIngest>,
+    /// Relationships to ingest
+    pub relationships: Vec<RelIngest>,
+    /// How relationship rows are written
+    pub relationship_mode: RelationshipMode,
+    /// Key properties for `merge` mode
+    pub relationship_keys: Vec<String>,
+}
+
+/// An ingest job and the state of each of its chunks
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestJob {
+    /// Job id
+    pub id: String,
+    /// Job status
+    pub status: IngestJobStatus,
+    /// Creation time (RFC 3339)
+    pub created_at: String,
+    /// Time of the last status change (RFC 3339)
+    pub updated_at: String,
+    /// Nodes in the request
+    pub nodes_total: usize,
+    /// Relationships in the request
+    pub relationships_total: usize,
+    /// Whether chunks run inside `BEGIN` / `COMMIT`
+    pub transactional: bool,
+    /// How relationship rows are written
+    pub relationship_mode: RelationshipMode,
+    /// Chunks, nodes first
+    pub chunks: Vec<IngestChunk>,
+    #[serde(skip)]
+    pub(crate) payload: Option<Arc<IngestPayload>>,
+}
+
+impl IngestJob {
+    /// Split `payload` into chunks of at most `chunk_size` rows.
+    pub fn new(payload: IngestPayload, chunk_size: usize, transactional: bool) -> Self {
+        let chunk_size = chunk_size.max(1);
+        let mut chunks = Vec::new();
+        for (kind, len) in [
+            (IngestChunkKind::Nodes, payload.nodes.len()),
+            (IngestChunkKind::Relationships, payload.relationships.len()),
+        ] {
+            let mut start = 0;
+            while start < len {
+                let end = len.min(start.saturating_add(chunk_size));
+                chunks.push(IngestChunk::new(chunks.len(), kind, start, end));
+                start = end;
+            }
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            status: IngestJobStatus::Running,
+            created_at: now.clone(),
+            updated_at: now,
+            nodes_total: payload.nodes.len(),
+            relationships_total: payload.relationships.len(),
+            transactional,
+            relationship_mode: payload.relationship_mode,
+            chunks,
+            payload: Some(Arc::new(payload)),
+        }
+    }
+
+    /// Rows of `kind` applied so far.
+    pub fn applied(&self, kind: IngestChunkKind) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| c.kind == kind)
+            .map(|c| c.applied)
+            .sum()
+    }
+
+    /// Relationship rows that matched an existing relationship.
+    pub fn merged(&self) -> usize {
+        self.chunks.iter().map(|c| c.merged).sum()
+    }
+
+    /// Chunks that have been attempted at least once.
+    pub fn chunks_attempted(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| c.status != IngestChunkStatus::Pending)
+            .count()
+    }
+
+    /// Every reported failure, formatted for the `/ingest` response.
+    pub fn error_messages(&self) -> Vec<String> {
+        let mut messages = Vec::new();
+        for chunk in &self.chunks {
+            let what = match chunk.kind {
+                IngestChunkKind::Nodes => "Node",
+                IngestChunkKind::Relationships => "Relationship",
+            };
+            if let Some(error) = &chunk.error {
+                messages.push(format!("Chunk {}: {}", chunk.index, error));
+            }
+            messages.extend(
+                chunk
+                    .errors
+                    .iter()
+                    .map(|e| format!("{} row {}: {}", what, e.row, e.message)),
+            );
+            if chunk.errors_truncated > 0 {
+                messages.push(format!(
+                    "Chunk {}: {} more failed row(s)",
+                    chunk.index, chunk.errors_truncated
+                ));
+            }
+        }
+        messages
+    }
+
+    /// Settle the job status once a run or resume has finished.
+    pub(crate) fn finish_run(&mut self) {
+        self.status = if self
+            .chunks
+            .iter()
+            .all(|c| c.status == IngestChunkStatus::Applied)
+        {
+            // Nothing left to retry; the rows themselves are no longer needed.
+            self.payload = None;
+            IngestJobStatus::Completed
+        } else {
+            IngestJobStatus::Failed
+        };
+        self.touch();
+    }
+
+    /// Settle the job status once a rollback has finished.
+    pub(crate) fn finish_rollback(&mut self) {
+        self.status = if self
+            .chunks
+            .iter()
+            .all(|c| c.status == IngestChunkStatus::RolledBack)
+        {
+            self.payload = None;
+            IngestJobStatus::RolledBack
+        } else {
+            IngestJobStatus::Failed
+        };
+        self.touch();
+    }
+
+    pub(crate) fn touch(&mut self) {
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+}
+
+/// Shared handle on one job. Chunk runners lock it briefly per row; it is
+/// never held across an `.await`.
+pub type IngestJobHandle = Arc<Mutex<IngestJob>>;
+
+/// Recently created ingest jobs, by id.
+pub struct IngestJobRegistry {
+    inner: Mutex<RegistryInner>,
+    max_jobs: usize,
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    jobs: HashMap<String, IngestJobHandle>,
+    /// Job ids, oldest first
+    order: VecDeque<String>,
+}
+
+impl Default for IngestJobRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RETAINED_JOBS)
+    }
+}
+
+impl IngestJobRegistry {
+    /// Create a registry that remembers at most `max_jobs` jobs.
+    pub fn new(max_jobs: usize) -> Self {
+        Self {
+            inner: Mutex::new(RegistryInner::default()),
+            max_jobs: max_jobs.max(1),
+        }
+    }
+
+    /// Register `job`, forgetting the oldest jobs that are not running
+    /// once the registry is full.
+    pub fn insert(&self, job: IngestJob) -> IngestJobHandle {
+        let id = job.id.clone();
+        let handle = Arc::new(Mutex::new(job));
+        let mut inner = self.inner.lock();
+        while inner.order.len() >= self.max_jobs {
+            let RegistryInner { jobs, order } = &mut *inner;
+            let Some(pos) = order.iter().position(|id| {
+                jobs.get(id)
+                    .is_none_or(|j| j.lock().status != IngestJobStatus::Running)
+            }) else {
+                break;
+            };
+            if let Some(evicted) = order.remove(pos) {
+                jobs.remove(&evicted);
+            }
+        }
+        inner.jobs.insert(id.clone(), Arc::clone(&handle));
+        inner.order.push_back(id);
+        handle
+    }
+
+    /// Look up a job by id.
+    pub fn get(&self, id: &str) -> Option<IngestJobHandle> {
+        self.inner.lock().jobs.get(id).cloned()
+    }
+
+    /// Number of jobs currently remembered.
+    pub fn len(&self) -> usize {
+        self.inner.lock().order.len()
+    }
+
+    /// Whether no job is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(nodes: usize, relationships: usize) -> IngestPayload {
+        IngestPayload {
+            nodes: (0..nodes)
+                .map(|_| NodeIngest {
+                    id: None,
+                    labels: vec!["Person".to_string()],
+                    properties: serde_json::Value::Null,
+                })
+                .collect(),
+            relationships: (0..relationships)
+                .map(|i| RelIngest {
+                    id: None,
+                    src: i as u64,
+                    dst: i as u64 + 1,
+                    r#type: "KNOWS".to_string(),
+                    properties: serde_json::Value::Null,
+                })
+                .collect(),
+            relationship_mode: RelationshipMode::Create,
+            relationship_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn jobs_are_split_into_node_then_relationship_chunks() {
+        let job = IngestJob::new(payload(5, 2), 2, true);
+        let shape: Vec<_> = job
+            .chunks
+            .iter()
+            .map(|c| (c.kind, c.start_row, c.end_row))
+            .collect();
+        assert_eq!(
+            shape,
+            vec![
+                (IngestChunkKind::Nodes, 0, 2),
+                (IngestChunkKind::Nodes, 2, 4),
+                (IngestChunkKind::Nodes, 4, 5),
+                (IngestChunkKind::Relationships, 0, 2),
+            ]
+        );
+        assert!(job.chunks.iter().all(IngestChunk::is_resumable));
+    }
+
+    #[test]
+    fn failed_rows_stay_resumable_and_are_reported_by_row() {
+        let mut job = IngestJob::new(payload(3, 0), 10, false);
+        let chunk = &mut job.chunks[0];
+        chunk.begin_attempt();
+        chunk.record_applied(0, Some(10));
+        chunk.record_failed(1, "bad label".to_string());
+        chunk.record_applied(2, Some(12));
+        chunk.finish_attempt();
+        job.finish_run();
+
+        assert_eq!(job.status, IngestJobStatus::Failed);
+        assert_eq!(job.applied(IngestChunkKind::Nodes), 2);
+        assert_eq!(job.chunks[0].remaining, vec![1]);
+        assert_eq!(job.chunks[0].created_ids, vec![10, 12]);
+        assert_eq!(job.error_messages(), vec!["Node row 1: bad label"]);
+        assert!(job.payload.is_some());
+
+        let chunk = &mut job.chunks[0];
+        chunk.begin_attempt();
+        chunk.record_applied(1, Some(11));
+        chunk.finish_attempt();
+        job.finish_run();
+        assert_eq!(job.status, IngestJobStatus::Completed);
+        assert!(job.error_messages().is_empty());
+        assert!(job.payload.is_none());
+    }
+
+    #[test]
+    fn discarded_attempt_restores_its_rows() {
+        let mut job = IngestJob::new(payload(3, 0), 10, true);
+        let chunk = &mut job.chunks[0];
+        chunk.begin_attempt();
+        chunk.record_applied(0, Some(10));
+        chunk.finish_attempt();
+
+        chunk.begin_attempt();
+        chunk.record_applied(1, Some(11));
+        chunk.record_merged(2);
+        chunk.discard_attempt();
+        chunk.error = Some("Transaction commit failed".to_string());
+        chunk.finish_attempt();
+
+        assert_eq!(chunk.status, IngestChunkStatus::Failed);
+        assert_eq!(chunk.remaining, vec![1, 2]);
+        assert_eq!(chunk.applied, 1);
+        assert_eq!(chunk.merged, 0);
+        assert_eq!(chunk.created_ids, vec![10]);
+        assert!(chunk.is_resumable());
+    }
+
+    #[test]
+    fn registry_evicts_oldest_finished_jobs() {
+        let registry = IngestJobRegistry::new(2);
+        let running = registry.insert(IngestJob::new(payload(1, 0), 1, false));
+        let finished = registry.insert(IngestJob::new(payload(1, 0), 1, false));
+        finished.lock().status = IngestJobStatus::Completed;
+        let finished_id = finished.lock().id.clone();
+
+        let newest = registry.insert(IngestJob::new(payload(1, 0), 1, false));
+        assert_eq!(registry.len(), 2);
+        assert!(registry.get(&running.lock().id).is_some());
+        assert!(registry.get(&finished_id).is_none());
+        assert!(registry.get(&newest.lock().id).is_some());
+    }
+}
@@ -28,6 +28,10 @@ pub struct KnnTraverseRequest {
     /// Result limit
     #[serde(default = "default_limit")]
     pub limit: usize,
+    /// Re-rank the `k` vector-index neighbours server-side with graph
+    /// signals (see `nexus_core::engine::knn_rerank`)
+    #[serde(default)]
+    pub rerank: Option<nexus_core::engine::KnnRerankOptions>,
 }
 
 fn default_limit() -> usize {
@@ -53,8 +57,20 @@ pub struct KnnNode {
     pub id: u64,
     /// Node properties
     pub properties: serde_json::Value,
-    /// Similarity score
+    /// Similarity score (the re-ranking score for re-ranked requests)
     pub score: f32,
+    /// Vector similarity before re-ranking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<f32>,
+    /// Relationship count, when the scoring expression used it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degree: Option<u64>,
+    /// PageRank, when the scoring expression used it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagerank: Option<f64>,
+    /// Hops to the context node, when the scoring expression used proximity
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<u64>,
 }
 
 /// Execute KNN-seeded traversal
@@ -86,6 +102,49 @@ pub async fn knn_traverse(
         }
     };
 
+    if let Some(options) = &request.rerank {
+        let engine = server.engine.read().await;
+        let result = engine
+            .knn_rerank(&safe_label, &request.vector, request.k, options)
+            .and_then(|ranked| {
+                ranked
+                    .into_iter()
+                    .take(request.limit)
+                    .map(|c| {
+                        let properties = engine
+                            .storage
+                            .load_node_properties(c.node_id)?
+                            .unwrap_or_else(|| serde_json::json!({}));
+                        Ok(KnnNode {
+                            id: c.node_id,
+                            properties,
+                            score: c.score as f32,
+                            similarity: Some(c.similarity),
+                            degree: c.degree,
+                            pagerank: c.pagerank,
+                            distance: c.distance,
+                        })
+                    })
+                    .collect::<nexus_core::Result<Vec<_>>>()
+            });
+        let execution_time = start_time.elapsed().as_millis() as u64;
+        return match result {
+            Ok(nodes) => Json(KnnTraverseResponse {
+                nodes,
+                execution_time_ms: execution_time,
+                error: None,
+            }),
+            Err(e) => {
+                tracing::warn!("KNN re-rank failed: {}", e);
+                Json(KnnTraverseResponse {
+                    nodes: vec![],
+                    execution_time_ms: execution_time,
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+    }
+
     let executor = server.executor.clone();
 
     // For MVP, we'll use a simple approach:
@@ -118,6 +177,10 @@ pub async fn knn_traverse(
                                     id,
                                     properties: node_value.clone(),
                                     score,
+                                    similarity: None,
+                                    degree: None,
+                                    pagerank: None,
+                                    distance: None,
                                 });
                             }
                         }
@@ -211,6 +274,7 @@ mod tests {
             expand: vec![],
             r#where: None,
             limit: 10,
+            rerank: None,
         }
     }
 
@@ -258,4 +322,18 @@ mod tests {
         assert!(resp_a.nodes.is_empty());
         assert!(resp_b.nodes.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_knn_traverse_rerank_rejects_unknown_signal() {
+        let server = build_test_server();
+        let mut request = probe_request("Doc", 5, vec![0.1; 4]);
+        request.rerank = Some(nexus_core::engine::KnnRerankOptions {
+            expression: "similarity + popularity".to_string(),
+            ..Default::default()
+        });
+        let response = knn_traverse(State(server), Json(request)).await.0;
+        assert!(response.nodes.is_empty());
+        let error = response.error.expect("unknown signal must be reported");
+        assert!(error.contains("popularity"), "{error}");
+    }
 }
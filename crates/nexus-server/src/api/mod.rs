@@ -1,6 +1,9 @@
 //! API handlers
 
+pub mod admin_engine;
+pub mod admin_plans;
 pub mod admin_queries;
+pub mod artifacts;
 pub mod auth;
 pub mod auto_generate;
 pub mod cluster;
@@ -27,10 +30,13 @@ pub mod health;
 pub mod identifier;
 pub mod indexes;
 pub mod ingest;
+pub mod ingest_jobs;
 pub mod knn;
 pub mod logs;
 pub mod mcp_performance;
 pub mod openapi;
+pub mod operation_registry;
+pub mod operations;
 pub mod performance;
 pub mod prometheus;
 pub mod property_keys;
@@ -39,3 +45,4 @@ pub mod replication;
 pub mod schema;
 pub mod stats;
 pub mod streaming;
+pub mod vector_indexes;
@@ -0,0 +1,307 @@
+//! Bookkeeping for `/operations`.
+//!
+//! An operation is a piece of server-side work too long for one HTTP
+//! request — populating an index, copying a backup, applying a bulk
+//! import. It carries its progress (items done out of items known), an
+//! ETA extrapolated from the rate so far, a cancellation flag the runner
+//! polls at its checkpoints, and the outcome once it finishes.
+//!
+//! This module only holds state; running operations lives in
+//! [`super::operations`].
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Operations kept for lookup before the oldest finished ones are
+/// forgotten.
+pub const DEFAULT_MAX_RETAINED_OPERATIONS: usize = 256;
+
+/// What an operation does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    /// Build a property index over the existing nodes
+    IndexPopulation,
+    /// Copy the data directory to a backup directory
+    Backup,
+    /// Apply a bulk import (an `/ingest` request)
+    Import,
+}
+
+/// Lifecycle of an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    /// Work is in progress
+    Running,
+    /// The work finished
+    Completed,
+    /// The work stopped on an error
+    Failed,
+    /// The work stopped at a checkpoint after a cancel request
+    Cancelled,
+}
+
+impl OperationStatus {
+    /// Whether the operation has stopped for good.
+    pub fn is_finished(self) -> bool {
+        self != OperationStatus::Running
+    }
+}
+
+/// A long-running operation and its progress
+#[derive(Debug, Clone, Serialize)]
+pub struct Operation {
+    /// Operation id
+    pub id: String,
+    /// What the operation does
+    pub kind: OperationKind,
+    /// Operation status
+    pub status: OperationStatus,
+    /// Human-readable summary, e.g. `index :Person(email)`
+    pub description: String,
+    /// Items done so far
+    pub processed: u64,
+    /// Items to do, once known
+    pub total: u64,
+    /// What `processed` and `total` count (`nodes`, `bytes`, `rows`)
+    pub unit: &'static str,
+    /// `processed` out of `total`, 0–100
+    pub progress_percent: f64,
+    /// Estimated seconds left, extrapolated from the rate so far
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
+    /// Whether a cancel has been requested and not yet honoured
+    pub cancel_requested: bool,
+    /// Creation time (RFC 3339)
+    pub created_at: String,
+    /// Time of the last progress or status change (RFC 3339)
+    pub updated_at: String,
+    /// Completion time (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    /// Kind-specific outcome (backup location, ingest job id, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    /// Why the operation failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// URL that receives the finished operation as a JSON `POST`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+    /// Why the webhook could not be delivered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_error: Option<String>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+impl Operation {
+    /// A running operation with no progress yet.
+    pub fn new(
+        kind: OperationKind,
+        description: String,
+        unit: &'static str,
+        webhook: Option<String>,
+    ) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            status: OperationStatus::Running,
+            description,
+            processed: 0,
+            total: 0,
+            unit,
+            progress_percent: 0.0,
+            eta_seconds: None,
+            cancel_requested: false,
+            created_at: now.clone(),
+            updated_at: now,
+            finished_at: None,
+            result: None,
+            error: None,
+            webhook,
+            webhook_error: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Record that `processed` of `total` items are done and refresh the
+    /// ETA.
+    pub fn report(&mut self, processed: u64, total: u64) {
+        self.processed = processed;
+        self.total = total;
+        self.progress_percent = if total == 0 {
+            0.0
+        } else {
+            (processed.min(total) as f64 / total as f64 * 100.0).min(100.0)
+        };
+        self.eta_seconds = (processed > 0 && total >= processed).then(|| {
+            let per_item = self.started.elapsed().as_secs_f64() / processed as f64;
+            (per_item * (total - processed) as f64).ceil() as u64
+        });
+        self.touch();
+    }
+
+    /// Settle the operation. A completed operation reports 100%.
+    pub fn finish(
+        &mut self,
+        status: OperationStatus,
+        result: Option<serde_json::Value>,
+        error: Option<String>,
+    ) {
+        self.status = status;
+        self.result = result;
+        self.error = error;
+        self.cancel_requested = false;
+        if status == OperationStatus::Completed {
+            self.progress_percent = 100.0;
+            self.processed = self.processed.max(self.total);
+        }
+        self.eta_seconds = None;
+        self.touch();
+        self.finished_at = Some(self.updated_at.clone());
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
+}
+
+/// Shared handle on one operation. Runners lock it briefly at each
+/// checkpoint; it is never held across an `.await`.
+pub type OperationHandle = Arc<Mutex<Operation>>;
+
+/// Recently started operations, by id.
+pub struct OperationRegistry {
+    inner: Mutex<RegistryInner>,
+    max_operations: usize,
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    operations: HashMap<String, OperationHandle>,
+    /// Operation ids, oldest first
+    order: VecDeque<String>,
+}
+
+impl Default for OperationRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_RETAINED_OPERATIONS)
+    }
+}
+
+impl OperationRegistry {
+    /// Create a registry that remembers at most `max_operations`
+    /// operations.
+    pub fn new(max_operations: usize) -> Self {
+        Self {
+            inner: Mutex::new(RegistryInner::default()),
+            max_operations: max_operations.max(1),
+        }
+    }
+
+    /// Register `operation`, forgetting the oldest finished operations
+    /// once the registry is full.
+    pub fn insert(&self, operation: Operation) -> OperationHandle {
+        let id = operation.id.clone();
+        let handle = Arc::new(Mutex::new(operation));
+        let mut inner = self.inner.lock();
+        while inner.order.len() >= self.max_operations {
+            let RegistryInner { operations, order } = &mut *inner;
+            let Some(pos) = order.iter().position(|id| {
+                operations
+                    .get(id)
+                    .is_none_or(|op| op.lock().status.is_finished())
+            }) else {
+                break;
+            };
+            if let Some(evicted) = order.remove(pos) {
+                operations.remove(&evicted);
+            }
+        }
+        inner.operations.insert(id.clone(), Arc::clone(&handle));
+        inner.order.push_back(id);
+        handle
+    }
+
+    /// Look up an operation by id.
+    pub fn get(&self, id: &str) -> Option<OperationHandle> {
+        self.inner.lock().operations.get(id).cloned()
+    }
+
+    /// Every remembered operation, newest first.
+    pub fn list(&self) -> Vec<Operation> {
+        let inner = self.inner.lock();
+        inner
+            .order
+            .iter()
+            .rev()
+            .filter_map(|id| inner.operations.get(id))
+            .map(|op| op.lock().clone())
+            .collect()
+    }
+
+    /// Number of operations currently remembered.
+    pub fn len(&self) -> usize {
+        self.inner.lock().order.len()
+    }
+
+    /// Whether no operation is remembered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation() -> Operation {
+        Operation::new(
+            OperationKind::IndexPopulation,
+            "index :Person(email)".to_string(),
+            "nodes",
+            None,
+        )
+    }
+
+    #[test]
+    fn progress_and_eta_follow_reports() {
+        let mut op = operation();
+        op.report(0, 200);
+        assert_eq!(op.progress_percent, 0.0);
+        assert_eq!(op.eta_seconds, None);
+
+        op.report(50, 200);
+        assert_eq!(op.progress_percent, 25.0);
+        assert!(op.eta_seconds.is_some());
+
+        op.finish(OperationStatus::Completed, None, None);
+        assert_eq!(op.progress_percent, 100.0);
+        assert_eq!(op.processed, 200);
+        assert_eq!(op.eta_seconds, None);
+        assert!(op.finished_at.is_some());
+    }
+
+    #[test]
+    fn registry_evicts_oldest_finished_operations() {
+        let registry = OperationRegistry::new(2);
+        let running = registry.insert(operation());
+        let finished = registry.insert(operation());
+        finished
+            .lock()
+            .finish(OperationStatus::Cancelled, None, None);
+        let finished_id = finished.lock().id.clone();
+
+        let newest = registry.insert(operation());
+        assert_eq!(registry.len(), 2);
+        assert!(registry.get(&running.lock().id).is_some());
+        assert!(registry.get(&finished_id).is_none());
+        assert_eq!(registry.list()[0].id, newest.lock().id);
+    }
+}
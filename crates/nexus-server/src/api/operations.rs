@@ -0,0 +1,675 @@
+//! Long-running operation endpoints
+//!
+//! - POST /operations - Start an index population, backup or import in
+//!   the background and return its id at once
+//! - GET /operations - List recent operations, newest first
+//! - GET /operations/{id} - Progress, ETA and outcome of one operation
+//! - POST /operations/{id}/cancel - Stop an operation at its next checkpoint
+//!
+//! A cancelled index population drops the half-built index and a
+//! cancelled backup removes the partial copy. A cancelled import stops
+//! between chunks: the rows already applied stay, and its ingest job
+//! (`result.ingest_job_id`) can be resumed or rolled back through
+//! `/ingest/jobs/{id}`. When the request names a `webhook`, the finished
+//! operation is `POST`ed to it as JSON; delivery failures are recorded on
+//! the operation as `webhook_error`.
+//!
+//! Backups are only written under the server's backup directory, and a
+//! webhook must pass the same host allow-list and scheme rules as the
+//! `nexus.load.*` remote data sources (`server.external_data`), so a
+//! caller cannot make the server write anywhere or call any host.
+
+use super::ingest::{IngestRequest, run_job_until};
+use super::ingest_jobs::{IngestChunkKind, IngestJob, IngestJobStatus, IngestPayload};
+use super::operation_registry::{Operation, OperationHandle, OperationKind, OperationStatus};
+use crate::NexusServer;
+use axum::extract::{Json, Path, State};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a webhook delivery may take.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The work an operation does
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OperationSpec {
+    /// Build the property index on `:label(property)`
+    IndexPopulation {
+        /// Label of the indexed nodes
+        label: String,
+        /// Indexed property
+        property: String,
+    },
+    /// Copy the server's data directory into `destination`, a directory
+    /// under the server's backup directory that must not exist yet;
+    /// relative paths are resolved against the backup directory
+    Backup {
+        /// Backup directory
+        destination: String,
+    },
+    /// Apply an `/ingest` request, one chunk of `batch_size` rows at a time
+    Import(IngestRequest),
+}
+
+/// Start operation request
+#[derive(Debug, Deserialize)]
+pub struct StartOperationRequest {
+    /// What to run, tagged by `kind`
+    #[serde(flatten)]
+    pub spec: OperationSpec,
+    /// URL that receives the finished operation; its host must be on
+    /// the `server.external_data` allow-list
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+/// Response carrying one operation
+#[derive(Debug, Serialize)]
+pub struct OperationResponse {
+    /// The operation, if started or found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<Operation>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// List operations response
+#[derive(Debug, Serialize)]
+pub struct ListOperationsResponse {
+    /// Remembered operations, newest first
+    pub operations: Vec<Operation>,
+}
+
+fn operation_error(message: String, operation: Option<Operation>) -> Json<OperationResponse> {
+    Json(OperationResponse {
+        operation,
+        error: Some(message),
+    })
+}
+
+/// Start an operation in the background
+pub async fn start_operation(
+    State(server): State<Arc<NexusServer>>,
+    Json(mut request): Json<StartOperationRequest>,
+) -> Json<OperationResponse> {
+    if let Some(url) = &request.webhook {
+        let external_data = server.engine.read().await.external_data_config().clone();
+        if let Err(e) = check_webhook(&external_data, url) {
+            return operation_error(e, None);
+        }
+    }
+    let (kind, description, unit) = match &mut request.spec {
+        OperationSpec::IndexPopulation { label, property } => (
+            OperationKind::IndexPopulation,
+            format!("index :{label}({property})"),
+            "nodes",
+        ),
+        OperationSpec::Backup { destination } => {
+            match check_backup_destination(&server.data_dir, &server.backup_dir, destination) {
+                Ok(resolved) => *destination = resolved.display().to_string(),
+                Err(e) => return operation_error(e, None),
+            }
+            (
+                OperationKind::Backup,
+                format!("backup to {destination}"),
+                "bytes",
+            )
+        }
+        OperationSpec::Import(import) => (
+            OperationKind::Import,
+            format!(
+                "import of {} nodes and {} relationships",
+                import.nodes.len(),
+                import.relationships.len()
+            ),
+            "rows",
+        ),
+    };
+
+    let handle = server
+        .operations
+        .insert(Operation::new(kind, description, unit, request.webhook));
+    let operation = handle.lock().clone();
+    tracing::info!(
+        "Started operation {} ({})",
+        operation.id,
+        operation.description
+    );
+    tokio::spawn(run_operation(server, handle, request.spec));
+    Json(OperationResponse {
+        operation: Some(operation),
+        error: None,
+    })
+}
+
+/// List recent operations
+pub async fn list_operations(
+    State(server): State<Arc<NexusServer>>,
+) -> Json<ListOperationsResponse> {
+    Json(ListOperationsResponse {
+        operations: server.operations.list(),
+    })
+}
+
+/// Show one operation
+pub async fn get_operation(
+    State(server): State<Arc<NexusServer>>,
+    Path(id): Path<String>,
+) -> Json<OperationResponse> {
+    match server.operations.get(&id) {
+        Some(handle) => Json(OperationResponse {
+            operation: Some(handle.lock().clone()),
+            error: None,
+        }),
+        None => operation_error(format!("Operation '{id}' not found"), None),
+    }
+}
+
+/// Ask an operation to stop at its next checkpoint
+pub async fn cancel_operation(
+    State(server): State<Arc<NexusServer>>,
+    Path(id): Path<String>,
+) -> Json<OperationResponse> {
+    let Some(handle) = server.operations.get(&id) else {
+        return operation_error(format!("Operation '{id}' not found"), None);
+    };
+    let mut operation = handle.lock();
+    if operation.status.is_finished() {
+        return operation_error(
+            format!("Operation '{id}' has already finished"),
+            Some(operation.clone()),
+        );
+    }
+    operation.cancel_requested = true;
+    Json(OperationResponse {
+        operation: Some(operation.clone()),
+        error: None,
+    })
+}
+
+/// Final status, kind-specific result and error of an operation
+type Outcome = (OperationStatus, Option<serde_json::Value>, Option<String>);
+
+async fn run_operation(server: Arc<NexusServer>, handle: OperationHandle, spec: OperationSpec) {
+    let (status, result, error) = match spec {
+        OperationSpec::IndexPopulation { label, property } => {
+            populate_index(&server, &handle, label, property).await
+        }
+        OperationSpec::Backup { destination } => {
+            backup(&server, &handle, PathBuf::from(destination)).await
+        }
+        OperationSpec::Import(request) => import(&server, &handle, request).await,
+    };
+
+    let operation = {
+        let mut operation = handle.lock();
+        operation.finish(status, result, error);
+        operation.clone()
+    };
+    tracing::info!(
+        "Operation {} ({}) finished: {:?}",
+        operation.id,
+        operation.description,
+        operation.status
+    );
+    if let Some(url) = &operation.webhook
+        && let Err(e) = deliver_webhook(url, &operation).await
+    {
+        tracing::warn!(
+            "Operation {} webhook to {} failed: {}",
+            operation.id,
+            url,
+            e
+        );
+        handle.lock().webhook_error = Some(e);
+    }
+}
+
+async fn populate_index(
+    server: &Arc<NexusServer>,
+    handle: &OperationHandle,
+    label: String,
+    property: String,
+) -> Outcome {
+    let server = Arc::clone(server);
+    let operation = Arc::clone(handle);
+    let built = tokio::task::spawn_blocking(move || {
+        let mut engine = server.engine.blocking_write();
+        engine.create_property_index(&label, &property, &mut |scanned, total| {
+            let mut operation = operation.lock();
+            operation.report(scanned, total);
+            !operation.cancel_requested
+        })
+    })
+    .await;
+    match built {
+        Ok(Ok(true)) => (OperationStatus::Completed, None, None),
+        Ok(Ok(false)) => (OperationStatus::Cancelled, None, None),
+        Ok(Err(e)) => (OperationStatus::Failed, None, Some(e.to_string())),
+        Err(e) => (
+            OperationStatus::Failed,
+            None,
+            Some(format!("index population task failed: {e}")),
+        ),
+    }
+}
+
+/// Reject a webhook URL the remote data rules would not let a query
+/// fetch: a scheme other than https (or http when allowed), or a host
+/// missing from the allow-list.
+fn check_webhook(
+    external_data: &nexus_core::external_data::ExternalDataConfig,
+    url: &str,
+) -> Result<(), String> {
+    let parsed =
+        reqwest::Url::parse(url).map_err(|e| format!("webhook '{url}' is not a URL: {e}"))?;
+    external_data
+        .check_url(&parsed)
+        .map_err(|e| format!("webhook '{url}' is not allowed: {e}"))
+}
+
+/// Resolve a backup destination against the backup directory. Reject
+/// one that exists already, lies outside the backup directory, or lies
+/// inside the data directory (the copy would include itself).
+fn check_backup_destination(
+    data_dir: &std::path::Path,
+    backup_dir: &std::path::Path,
+    destination: &str,
+) -> Result<PathBuf, String> {
+    if destination.is_empty() {
+        return Err("backup destination is empty".to_string());
+    }
+    let root = backup_dir.canonicalize().map_err(|e| {
+        format!(
+            "backup directory '{}' is not usable: {e}",
+            backup_dir.display()
+        )
+    })?;
+    let joined = root.join(destination);
+    if joined.exists() {
+        return Err(format!("backup destination '{destination}' already exists"));
+    }
+    let (Some(parent), Some(name)) = (joined.parent(), joined.file_name()) else {
+        return Err(format!(
+            "backup destination '{destination}' does not name a directory"
+        ));
+    };
+    let parent = parent.canonicalize().map_err(|e| {
+        format!(
+            "backup destination parent '{}' is not usable: {e}",
+            parent.display()
+        )
+    })?;
+    if !parent.starts_with(&root) {
+        return Err(format!(
+            "backup destination '{destination}' is outside the backup directory '{}'",
+            root.display()
+        ));
+    }
+    if let Ok(data_dir) = data_dir.canonicalize()
+        && parent.starts_with(&data_dir)
+    {
+        return Err(format!(
+            "backup destination '{destination}' is inside the data directory"
+        ));
+    }
+    Ok(parent.join(name))
+}
+
+async fn backup(
+    server: &Arc<NexusServer>,
+    handle: &OperationHandle,
+    destination: PathBuf,
+) -> Outcome {
+    let server = Arc::clone(server);
+    let operation = Arc::clone(handle);
+    let copied = tokio::task::spawn_blocking(move || {
+        // The read lock keeps writers out of the default database for
+        // the whole copy, so its files are consistent with each other.
+        let _engine = server.engine.blocking_read();
+        let copied =
+            nexus_core::database::copy_dir(&server.data_dir, &destination, |copied, total| {
+                let mut operation = operation.lock();
+                operation.report(copied, total);
+                !operation.cancel_requested
+            });
+        if !matches!(copied, Ok(Some(_))) {
+            let _ = std::fs::remove_dir_all(&destination);
+        }
+        copied.map(|done| {
+            done.map(|(files, bytes)| {
+                serde_json::json!({
+                    "destination": destination,
+                    "files": files,
+                    "bytes": bytes,
+                })
+            })
+        })
+    })
+    .await;
+    match copied {
+        Ok(Ok(Some(result))) => (OperationStatus::Completed, Some(result), None),
+        Ok(Ok(None)) => (OperationStatus::Cancelled, None, None),
+        Ok(Err(e)) => (
+            OperationStatus::Failed,
+            None,
+            Some(format!("backup failed: {e}")),
+        ),
+        Err(e) => (
+            OperationStatus::Failed,
+            None,
+            Some(format!("backup task failed: {e}")),
+        ),
+    }
+}
+
+async fn import(
+    server: &Arc<NexusServer>,
+    handle: &OperationHandle,
+    request: IngestRequest,
+) -> Outcome {
+    let total = (request.nodes.len() + request.relationships.len()) as u64;
+    let job = server.ingest_jobs.insert(IngestJob::new(
+        IngestPayload {
+            nodes: request.nodes,
+            relationships: request.relationships,
+            relationship_mode: request.relationship_mode,
+            relationship_keys: request.relationship_keys,
+        },
+        request.batch_size,
+        request.use_batching,
+    ));
+    let applied = |job: &IngestJob| {
+        (job.applied(IngestChunkKind::Nodes) + job.applied(IngestChunkKind::Relationships)) as u64
+    };
+
+    let mut cancelled = false;
+    run_job_until(server, &job, |job| {
+        let mut operation = handle.lock();
+        operation.report(applied(job), total);
+        cancelled = operation.cancel_requested;
+        !cancelled
+    })
+    .await;
+
+    let job = job.lock().clone();
+    handle.lock().report(applied(&job), total);
+    let result = serde_json::json!({
+        "ingest_job_id": job.id,
+        "job_status": job.status,
+        "nodes_ingested": job.applied(IngestChunkKind::Nodes),
+        "relationships_ingested": job.applied(IngestChunkKind::Relationships),
+    });
+    if cancelled {
+        (OperationStatus::Cancelled, Some(result), None)
+    } else if job.status == IngestJobStatus::Completed {
+        (OperationStatus::Completed, Some(result), None)
+    } else {
+        (
+            OperationStatus::Failed,
+            Some(result),
+            Some(job.error_messages().join("; ")),
+        )
+    }
+}
+
+async fn deliver_webhook(url: &str, operation: &Operation) -> Result<(), String> {
+    let body = serde_json::to_vec(operation).map_err(|e| e.to_string())?;
+    // A redirect could lead to a host the allow-list was never asked
+    // about, so it is reported as a failed delivery instead.
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("webhook returned HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RootUserConfig;
+    use nexus_core::testing::TestContext;
+    use nexus_core::{
+        Engine,
+        auth::{
+            AuditConfig, AuditLogger, AuthConfig, AuthManager, JwtConfig, JwtManager,
+            RoleBasedAccessControl,
+        },
+        database::DatabaseManager,
+        executor::Executor,
+    };
+    use parking_lot::RwLock as ParkingLotRwLock;
+    use tokio::sync::RwLock;
+
+    /// Test server whose data directory is `ctx` and whose backups go
+    /// under `backup_dir`; keep `ctx` alive for the duration of the test.
+    fn create_test_server(ctx: &TestContext, backup_dir: &std::path::Path) -> Arc<NexusServer> {
+        let engine = Engine::with_isolated_catalog(ctx.path()).unwrap();
+        let database_manager = DatabaseManager::new(ctx.path().join("databases")).unwrap();
+        let audit_logger = Arc::new(
+            AuditLogger::new(AuditConfig {
+                enabled: false,
+                log_dir: ctx.path().join("audit"),
+                retention_days: 1,
+                compress_logs: false,
+            })
+            .unwrap(),
+        );
+        let mut server = NexusServer::new(
+            Arc::new(Executor::default()),
+            Arc::new(RwLock::new(engine)),
+            Arc::new(ParkingLotRwLock::new(database_manager)),
+            Arc::new(RwLock::new(RoleBasedAccessControl::new())),
+            Arc::new(AuthManager::new(AuthConfig::default())),
+            Arc::new(JwtManager::new(JwtConfig::default())),
+            audit_logger,
+            RootUserConfig::default(),
+        );
+        server.set_data_dir(ctx.path().to_path_buf());
+        server.set_backup_dir(backup_dir.to_path_buf());
+        Arc::new(server)
+    }
+
+    async fn start(server: &Arc<NexusServer>, body: serde_json::Value) -> Operation {
+        let request: StartOperationRequest = serde_json::from_value(body).unwrap();
+        let response = start_operation(State(Arc::clone(server)), Json(request))
+            .await
+            .0;
+        assert!(
+            response.error.is_none(),
+            "start failed: {:?}",
+            response.error
+        );
+        response.operation.unwrap()
+    }
+
+    async fn wait_until_finished(server: &Arc<NexusServer>, id: &str) -> Operation {
+        for _ in 0..500 {
+            let operation = get_operation(State(Arc::clone(server)), Path(id.to_string()))
+                .await
+                .0
+                .operation
+                .unwrap();
+            if operation.status.is_finished() {
+                return operation;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("operation {id} did not finish");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn index_population_runs_in_the_background() {
+        let ctx = TestContext::new();
+        let server = create_test_server(&ctx, &ctx.path().join("backups"));
+        server
+            .engine
+            .write()
+            .await
+            .execute_cypher("UNWIND range(1, 3000) AS i CREATE (:Person {email: i})")
+            .unwrap();
+
+        let started = start(
+            &server,
+            serde_json::json!({"kind": "index_population", "label": "Person", "property": "email"}),
+        )
+        .await;
+        assert_eq!(started.kind, OperationKind::IndexPopulation);
+
+        let finished = wait_until_finished(&server, &started.id).await;
+        assert_eq!(finished.status, OperationStatus::Completed);
+        assert_eq!(finished.progress_percent, 100.0);
+        assert_eq!(finished.total, 3000);
+        let log = server.engine.read().await.schema_log(None, None).unwrap();
+        assert_eq!(
+            log.last().unwrap().statement,
+            "CREATE INDEX ON :Person(email)"
+        );
+
+        let listed = list_operations(State(Arc::clone(&server))).await.0;
+        assert_eq!(listed.operations[0].id, started.id);
+        let cancel = cancel_operation(State(server), Path(started.id)).await.0;
+        assert!(
+            cancel.error.is_some(),
+            "finished operations cannot be cancelled"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backup_copies_the_data_directory_and_imports_report_their_job() {
+        let ctx = TestContext::new();
+        let target = TestContext::new();
+        let server = create_test_server(&ctx, target.path());
+        let destination = target.path().join("backup");
+
+        let started = start(
+            &server,
+            serde_json::json!({"kind": "backup", "destination": destination}),
+        )
+        .await;
+        let finished = wait_until_finished(&server, &started.id).await;
+        assert_eq!(
+            finished.status,
+            OperationStatus::Completed,
+            "{:?}",
+            finished.error
+        );
+        let result = finished.result.unwrap();
+        assert!(result["files"].as_u64().unwrap() > 0);
+        assert_eq!(result["bytes"].as_u64().unwrap(), finished.processed);
+        assert!(destination.is_dir());
+
+        // The destination now exists, so a second backup is refused.
+        let request: StartOperationRequest = serde_json::from_value(
+            serde_json::json!({"kind": "backup", "destination": destination}),
+        )
+        .unwrap();
+        let refused = start_operation(State(Arc::clone(&server)), Json(request))
+            .await
+            .0;
+        assert!(refused.error.is_some());
+
+        // Relative destinations land in the backup directory; nothing may
+        // be written outside it.
+        let started = start(
+            &server,
+            serde_json::json!({"kind": "backup", "destination": "relative"}),
+        )
+        .await;
+        wait_until_finished(&server, &started.id).await;
+        assert!(target.path().join("relative").is_dir());
+        for outside in [
+            serde_json::json!("../escape"),
+            serde_json::json!(ctx.path().join("inside")),
+        ] {
+            let request: StartOperationRequest = serde_json::from_value(
+                serde_json::json!({"kind": "backup", "destination": outside}),
+            )
+            .unwrap();
+            let refused = start_operation(State(Arc::clone(&server)), Json(request))
+                .await
+                .0;
+            let error = refused
+                .error
+                .expect("destination outside the backup directory");
+            assert!(error.contains("outside the backup directory"), "{error}");
+        }
+
+        let started = start(
+            &server,
+            serde_json::json!({
+                "kind": "import",
+                "nodes": [
+                    {"labels": ["Person"], "properties": {"name": "Alice"}},
+                    {"labels": ["Person"], "properties": {"name": "Bob"}},
+                ],
+                "batch_size": 1,
+            }),
+        )
+        .await;
+        let finished = wait_until_finished(&server, &started.id).await;
+        assert_eq!(
+            finished.status,
+            OperationStatus::Completed,
+            "{:?}",
+            finished.error
+        );
+        assert_eq!((finished.processed, finished.total), (2, 2));
+        let job_id = finished.result.unwrap()["ingest_job_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(server.ingest_jobs.get(&job_id).is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn webhooks_must_pass_the_external_data_allow_list() {
+        let ctx = TestContext::new();
+        let server = create_test_server(&ctx, &ctx.path().join("backups"));
+        let start_with_webhook = |url: &str| {
+            let request: StartOperationRequest = serde_json::from_value(serde_json::json!({
+                "kind": "index_population",
+                "label": "Person",
+                "property": "email",
+                "webhook": url,
+            }))
+            .unwrap();
+            start_operation(State(Arc::clone(&server)), Json(request))
+        };
+
+        // No allowed hosts: every webhook is refused, internal ones included.
+        let refused = start_with_webhook("https://169.254.169.254/latest").await.0;
+        assert!(refused.error.unwrap().contains("not allowed"));
+
+        server
+            .engine
+            .write()
+            .await
+            .set_external_data_config(nexus_core::external_data::ExternalDataConfig {
+                allowed_hosts: vec!["hooks.example.com".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+        let refused = start_with_webhook("https://internal.local/hook").await.0;
+        assert!(refused.error.unwrap().contains("not allowed"));
+        let refused = start_with_webhook("http://hooks.example.com/hook").await.0;
+        assert!(refused.error.unwrap().contains("not allowed"));
+        let accepted = start_with_webhook("https://hooks.example.com/hook").await.0;
+        assert!(accepted.error.is_none(), "{:?}", accepted.error);
+    }
+}
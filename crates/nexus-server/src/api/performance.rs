@@ -4,23 +4,31 @@
 //! - GET /performance/statistics - Query execution statistics
 //! - GET /performance/slow-queries - Slow query log
 //! - GET /performance/plan-cache - Plan cache statistics
+//! - GET /performance/page-cache - Page cache hits/misses per query template
 //! - POST /performance/plan-cache/clear - Clear plan cache
 
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::NexusServer;
 
+/// Number of templates listed in `top_templates` unless `?top=` says
+/// otherwise
+pub const DEFAULT_TOP_TEMPLATES: usize = 10;
+
 /// Query statistics response
 #[derive(Debug, Serialize)]
 pub struct QueryStatisticsResponse {
     /// Overall statistics
     pub statistics: QueryStatisticsSummary,
-    /// Pattern statistics
+    /// Statistics for every query template, by total time (descending)
     pub patterns: Vec<QueryPatternStatsResponse>,
+    /// The most expensive templates by total time
+    pub top_templates: Vec<QueryPatternStatsResponse>,
 }
 
 /// Query statistics summary response
@@ -45,12 +53,14 @@ pub struct QueryStatisticsSummary {
 }
 
 /// Query pattern statistics response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct QueryPatternStatsResponse {
-    /// Query pattern (normalized)
+    /// Query template (literals replaced by `?`)
     pub pattern: String,
     /// Execution count
     pub count: u64,
+    /// Total execution time in milliseconds
+    pub total_time_ms: u64,
     /// Average execution time in milliseconds
     pub avg_time_ms: f64,
     /// Minimum execution time in milliseconds
@@ -61,6 +71,14 @@ pub struct QueryPatternStatsResponse {
     pub success_count: u64,
     /// Failure count
     pub failure_count: u64,
+    /// Total rows returned
+    pub total_rows: u64,
+    /// Average rows returned per execution
+    pub avg_rows: f64,
+    /// Page cache hits charged to this template
+    pub page_cache_hits: u64,
+    /// Page cache misses charged to this template
+    pub page_cache_misses: u64,
 }
 
 /// Slow queries response
@@ -105,27 +123,51 @@ pub struct PlanCacheStatisticsResponse {
     pub hit_rate: f64,
 }
 
-/// Get query statistics
-/// GET /performance/statistics
+/// Read the `?top=N` parameter, defaulting to [`DEFAULT_TOP_TEMPLATES`]
+fn parse_top(
+    params: &HashMap<String, String>,
+) -> Result<usize, (StatusCode, Json<serde_json::Value>)> {
+    match params.get("top").map(|raw| raw.parse::<usize>()) {
+        None => Ok(DEFAULT_TOP_TEMPLATES),
+        Some(Ok(top)) => Ok(top),
+        Some(Err(_)) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "Query parameter `top` must be a non-negative integer"
+            })),
+        )),
+    }
+}
+
+/// Get query statistics, aggregated per query template
+/// GET /performance/statistics?top=N
 pub async fn get_query_statistics(
     State(server): State<Arc<NexusServer>>,
+    Query(params): Query<HashMap<String, String>>,
 ) -> Result<Json<QueryStatisticsResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let top = parse_top(&params)?;
+
     let stats = server.query_stats.clone();
     let summary = stats.get_statistics();
-    let pattern_stats = stats.get_pattern_stats();
-
-    let patterns: Vec<QueryPatternStatsResponse> = pattern_stats
-        .into_values()
+    let patterns: Vec<QueryPatternStatsResponse> = stats
+        .top_patterns(usize::MAX)
+        .into_iter()
         .map(|stats| QueryPatternStatsResponse {
             pattern: stats.pattern,
             count: stats.count,
+            total_time_ms: stats.total_time_ms,
             avg_time_ms: stats.avg_time_ms,
             min_time_ms: stats.min_time_ms,
             max_time_ms: stats.max_time_ms,
             success_count: stats.success_count,
             failure_count: stats.failure_count,
+            total_rows: stats.total_rows,
+            avg_rows: stats.avg_rows,
+            page_cache_hits: stats.page_cache_hits,
+            page_cache_misses: stats.page_cache_misses,
         })
         .collect();
+    let top_templates = patterns.iter().take(top).cloned().collect();
 
     Ok(Json(QueryStatisticsResponse {
         statistics: QueryStatisticsSummary {
@@ -139,9 +181,62 @@ pub async fn get_query_statistics(
             slow_query_count: summary.slow_query_count,
         },
         patterns,
+        top_templates,
     }))
 }
 
+/// Page cache effectiveness per query template
+#[derive(Debug, Serialize)]
+pub struct PageCacheReportResponse {
+    /// Templates with the most page cache misses per execution first
+    pub templates: Vec<PageCacheTemplateStats>,
+}
+
+/// Page cache accesses charged to one query template
+#[derive(Debug, Serialize)]
+pub struct PageCacheTemplateStats {
+    /// Query template (literals replaced by `?`)
+    pub pattern: String,
+    /// Execution count
+    pub count: u64,
+    /// Page cache hits
+    pub hits: u64,
+    /// Page cache misses
+    pub misses: u64,
+    /// Fraction of lookups that hit (0.0 to 1.0)
+    pub hit_rate: f64,
+    /// Average misses per execution
+    pub misses_per_execution: f64,
+    /// Average execution time in milliseconds
+    pub avg_time_ms: f64,
+}
+
+/// Report which query templates are I/O-bound: page cache hits and
+/// misses charged to each template, most misses per execution first
+/// GET /performance/page-cache?top=N
+pub async fn get_page_cache_report(
+    State(server): State<Arc<NexusServer>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<PageCacheReportResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let top = parse_top(&params)?;
+    let templates = server
+        .query_stats
+        .io_bound_patterns(top)
+        .into_iter()
+        .map(|stats| PageCacheTemplateStats {
+            hit_rate: stats.page_cache_hit_rate(),
+            misses_per_execution: stats.page_cache_misses_per_execution(),
+            pattern: stats.pattern,
+            count: stats.count,
+            hits: stats.page_cache_hits,
+            misses: stats.page_cache_misses,
+            avg_time_ms: stats.avg_time_ms,
+        })
+        .collect();
+
+    Ok(Json(PageCacheReportResponse { templates }))
+}
+
 /// Get slow queries
 /// GET /performance/slow-queries
 pub async fn get_slow_queries(
@@ -296,11 +391,96 @@ mod tests {
     #[tokio::test]
     async fn test_get_query_statistics_empty_server_returns_zero_counters() {
         let server = build_test_server();
-        let response = get_query_statistics(State(server)).await.expect("ok");
+        let response = get_query_statistics(State(server), Query(HashMap::new()))
+            .await
+            .expect("ok");
         assert_eq!(response.statistics.total_queries, 0);
         assert_eq!(response.statistics.successful_queries, 0);
         assert_eq!(response.statistics.failed_queries, 0);
         assert!(response.patterns.is_empty());
+        assert!(response.top_templates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_query_statistics_groups_by_template() {
+        let server = build_test_server();
+        for (name, ms) in [("Ann", 5), ("Bob", 7)] {
+            server.query_stats.record_query(
+                &format!("MATCH (n:Person {{name: '{name}'}}) RETURN n"),
+                std::time::Duration::from_millis(ms),
+                true,
+                None,
+                2,
+            );
+        }
+        server.query_stats.record_query(
+            "RETURN 1",
+            std::time::Duration::from_millis(1),
+            true,
+            None,
+            1,
+        );
+
+        let params = HashMap::from([("top".to_string(), "1".to_string())]);
+        let response = get_query_statistics(State(server), Query(params))
+            .await
+            .expect("ok");
+        assert_eq!(response.patterns.len(), 2);
+        assert_eq!(response.top_templates.len(), 1);
+        let top = &response.top_templates[0];
+        assert_eq!(top.pattern, "MATCH (n:Person {name: ?}) RETURN n");
+        assert_eq!(top.count, 2);
+        assert_eq!(top.total_time_ms, 12);
+        assert_eq!(top.total_rows, 4);
+        assert_eq!(response.patterns[1].pattern, "RETURN ?");
+    }
+
+    #[tokio::test]
+    async fn test_get_query_statistics_rejects_bad_top() {
+        let server = build_test_server();
+        let params = HashMap::from([("top".to_string(), "many".to_string())]);
+        let (status, _) = get_query_statistics(State(server), Query(params))
+            .await
+            .expect_err("invalid top");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_page_cache_report_orders_by_misses_per_execution() {
+        use nexus_core::page_cache::attribution::PageCacheAccesses;
+
+        let server = build_test_server();
+        for (query, hits, misses) in [
+            ("MATCH (n:Hot) RETURN n", 9, 1),
+            ("MATCH (n:Cold) RETURN n", 1, 9),
+            ("RETURN 1", 0, 0),
+        ] {
+            server.query_stats.record_query(
+                query,
+                std::time::Duration::from_millis(1),
+                true,
+                None,
+                1,
+            );
+            server
+                .query_stats
+                .record_page_cache_accesses(query, PageCacheAccesses { hits, misses });
+        }
+
+        let response = get_page_cache_report(State(server), Query(HashMap::new()))
+            .await
+            .expect("ok");
+        let patterns: Vec<&str> = response
+            .templates
+            .iter()
+            .map(|t| t.pattern.as_str())
+            .collect();
+        assert_eq!(
+            patterns,
+            vec!["MATCH (n:Cold) RETURN n", "MATCH (n:Hot) RETURN n"]
+        );
+        assert_eq!(response.templates[0].misses, 9);
+        assert_eq!(response.templates[1].hit_rate, 0.9);
     }
 
     #[tokio::test]
@@ -349,10 +529,10 @@ mod tests {
             0,
         );
 
-        let resp_a = get_query_statistics(State(Arc::clone(&server_a)))
+        let resp_a = get_query_statistics(State(Arc::clone(&server_a)), Query(HashMap::new()))
             .await
             .expect("ok");
-        let resp_b = get_query_statistics(State(Arc::clone(&server_b)))
+        let resp_b = get_query_statistics(State(Arc::clone(&server_b)), Query(HashMap::new()))
             .await
             .expect("ok");
 
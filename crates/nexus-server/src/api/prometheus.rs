@@ -119,6 +119,12 @@ impl PrometheusMetrics {
         // to `{:?}` and log warn!). See
         // `nexus_core::executor::serde_metrics`.
         let serde_fallback = nexus_core::executor::serde_metrics::snapshot();
+        // Idle session reaper counters, process-wide inside
+        // `nexus_core::session`.
+        let reaper = nexus_core::session::reaper_metrics();
+        // Node TTL sweeper counters, process-wide inside
+        // `nexus_core::engine::ttl`.
+        let ttl = nexus_core::engine::ttl_sweeper_metrics();
 
         let avg_time = if total > 0 {
             total_time as f64 / total as f64
@@ -233,6 +239,30 @@ nexus_executor_serde_fallback_total{{site="distinct_key"}} {serde_distinct}
 nexus_executor_serde_fallback_total{{site="union_dedup_key"}} {serde_union}
 nexus_executor_serde_fallback_total{{site="helper_row_dedup_key"}} {serde_helper}
 nexus_executor_serde_fallback_total{{site="warm_cache_lazy"}} {serde_warm_cache}
+
+# HELP nexus_idle_transactions_rolled_back_total Explicit transactions rolled back by the idle session reaper, including those of closed sessions.
+# TYPE nexus_idle_transactions_rolled_back_total counter
+nexus_idle_transactions_rolled_back_total {reaper_tx_rolled_back}
+
+# HELP nexus_abandoned_sessions_closed_total Sessions closed by the idle session reaper after exceeding the session timeout.
+# TYPE nexus_abandoned_sessions_closed_total counter
+nexus_abandoned_sessions_closed_total {reaper_sessions_closed}
+
+# HELP nexus_ttl_sweeps_total Node TTL sweeps run.
+# TYPE nexus_ttl_sweeps_total counter
+nexus_ttl_sweeps_total {ttl_sweeps}
+
+# HELP nexus_ttl_nodes_deleted_total Nodes deleted by the TTL sweeper after their expiry passed.
+# TYPE nexus_ttl_nodes_deleted_total counter
+nexus_ttl_nodes_deleted_total {ttl_deleted}
+
+# HELP nexus_ttl_sweeper_lag_ms How long the oldest expired node had been overdue when the latest sweep started.
+# TYPE nexus_ttl_sweeper_lag_ms gauge
+nexus_ttl_sweeper_lag_ms {ttl_lag_ms}
+
+# HELP nexus_ttl_expired_pending Expired nodes the latest sweep left for the next one.
+# TYPE nexus_ttl_expired_pending gauge
+nexus_ttl_expired_pending {ttl_pending}
 "#,
             total = total,
             successful = successful,
@@ -262,14 +292,65 @@ nexus_executor_serde_fallback_total{{site="warm_cache_lazy"}} {serde_warm_cache}
             serde_union = serde_fallback.union_dedup_key,
             serde_helper = serde_fallback.helper_row_dedup_key,
             serde_warm_cache = serde_fallback.warm_cache_lazy,
+            reaper_tx_rolled_back = reaper.idle_transactions_rolled_back,
+            reaper_sessions_closed = reaper.abandoned_sessions_closed,
+            ttl_sweeps = ttl.sweeps,
+            ttl_deleted = ttl.nodes_deleted,
+            ttl_lag_ms = ttl.lag_ms,
+            ttl_pending = ttl.pending,
         )
     }
 }
 
+/// Format the query pool's queue depth and throughput. Kept apart from
+/// [`PrometheusMetrics::format_prometheus`] because the pool belongs to
+/// the server, not to the counter pack.
+pub fn format_query_pool_metrics(pool: &crate::query_pool::QueryPoolMetrics) -> String {
+    format!(
+        r#"
+# HELP nexus_query_pool_threads Worker threads the query pool may run.
+# TYPE nexus_query_pool_threads gauge
+nexus_query_pool_threads {threads}
+
+# HELP nexus_query_pool_active Queries executing on the query pool right now.
+# TYPE nexus_query_pool_active gauge
+nexus_query_pool_active {active}
+
+# HELP nexus_query_pool_queue_depth Queries waiting for a query pool thread.
+# TYPE nexus_query_pool_queue_depth gauge
+nexus_query_pool_queue_depth {queued}
+
+# HELP nexus_query_pool_queue_depth_peak Deepest the query pool queue has been since server start.
+# TYPE nexus_query_pool_queue_depth_peak gauge
+nexus_query_pool_queue_depth_peak {peak_queued}
+
+# HELP nexus_query_pool_tasks_total Queries finished on the query pool since server start.
+# TYPE nexus_query_pool_tasks_total counter
+nexus_query_pool_tasks_total {completed}
+
+# HELP nexus_query_pool_panics_total Queries that panicked on a query pool thread.
+# TYPE nexus_query_pool_panics_total counter
+nexus_query_pool_panics_total {panicked}
+
+# HELP nexus_query_pool_wait_microseconds_total Sum of the time queries waited for a query pool thread, in microseconds. Divide by nexus_query_pool_tasks_total for an average.
+# TYPE nexus_query_pool_wait_microseconds_total counter
+nexus_query_pool_wait_microseconds_total {wait_micros}
+"#,
+        threads = pool.threads,
+        active = pool.active,
+        queued = pool.queued,
+        peak_queued = pool.peak_queued,
+        completed = pool.completed_total,
+        panicked = pool.panicked_total,
+        wait_micros = pool.wait_micros_total,
+    )
+}
+
 /// Prometheus metrics endpoint handler. Reads the counter pack the
-/// server owns via `NexusServer::metrics`.
+/// server owns via `NexusServer::metrics`, followed by the query pool.
 pub async fn prometheus_metrics(State(server): State<Arc<NexusServer>>) -> impl IntoResponse {
-    let formatted = server.metrics.format_prometheus();
+    let mut formatted = server.metrics.format_prometheus();
+    formatted.push_str(&format_query_pool_metrics(&server.query_pool.metrics()));
 
     (
         axum::http::StatusCode::OK,
@@ -419,4 +500,34 @@ mod tests {
         // Arc identities differ too.
         assert!(!Arc::ptr_eq(&server_a.metrics, &server_b.metrics));
     }
+
+    #[test]
+    fn session_reaper_metrics_are_exported() {
+        let formatted = PrometheusMetrics::new().format_prometheus();
+
+        assert!(formatted.contains("# TYPE nexus_idle_transactions_rolled_back_total counter"));
+        assert!(formatted.contains("# TYPE nexus_abandoned_sessions_closed_total counter"));
+    }
+
+    #[tokio::test]
+    async fn query_pool_metrics_are_exported() {
+        let server = build_test_server();
+        server.query_pool.run(|| ()).await.unwrap();
+        let response = prometheus_metrics(State(server)).await.into_response();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let formatted = String::from_utf8(bytes.to_vec()).unwrap();
+
+        assert!(formatted.contains("# TYPE nexus_query_pool_queue_depth gauge"));
+        assert!(formatted.contains("nexus_query_pool_tasks_total 1\n"));
+    }
+
+    #[test]
+    fn ttl_sweeper_metrics_are_exported() {
+        let formatted = PrometheusMetrics::new().format_prometheus();
+
+        assert!(formatted.contains("# TYPE nexus_ttl_nodes_deleted_total counter"));
+        assert!(formatted.contains("# TYPE nexus_ttl_sweeper_lag_ms gauge"));
+    }
 }
@@ -1,6 +1,8 @@
 //! Schema management endpoints
 
-use axum::extract::{Json, State};
+use axum::extract::{Json, Path, Query, State};
+use nexus_core::catalog::SchemaChange;
+use nexus_core::engine::EmbeddingModelInfo;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -93,6 +95,105 @@ pub struct ListRelTypesResponse {
     pub error: Option<String>,
 }
 
+/// Bind a label/property to an embedding model
+#[derive(Debug, Deserialize)]
+pub struct SetEmbeddingModelRequest {
+    /// Label whose nodes carry the embeddings
+    pub label: String,
+    /// Property holding the embeddings
+    pub property: String,
+    /// Embedding model identifier
+    pub model: String,
+    /// Embedding length
+    pub dimensions: usize,
+}
+
+/// Response carrying one embedding model binding
+#[derive(Debug, Serialize)]
+pub struct EmbeddingModelResponse {
+    /// The binding, if set or found
+    pub binding: Option<EmbeddingModelInfo>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// List embedding model bindings response
+#[derive(Debug, Serialize)]
+pub struct ListEmbeddingModelsResponse {
+    /// Every binding, by label and property
+    pub models: Vec<EmbeddingModelInfo>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Drop embedding model binding response
+#[derive(Debug, Serialize)]
+pub struct DropEmbeddingModelResponse {
+    /// Whether a binding existed
+    pub dropped: bool,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Time range of a schema changelog request. Each bound is epoch
+/// milliseconds or an RFC 3339 timestamp; both are inclusive.
+#[derive(Debug, Default, Deserialize)]
+pub struct SchemaLogQuery {
+    /// Earliest change to include
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Latest change to include
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+impl SchemaLogQuery {
+    fn range(&self) -> Result<(Option<i64>, Option<i64>), String> {
+        let parse = |bound: &Option<String>| -> Result<Option<i64>, String> {
+            bound
+                .as_deref()
+                .map(|value| {
+                    value.parse::<i64>().or_else(|_| {
+                        chrono::DateTime::parse_from_rfc3339(value)
+                            .map(|t| t.timestamp_millis())
+                            .map_err(|_| {
+                                format!(
+                                    "invalid time '{value}': expected epoch milliseconds or RFC 3339"
+                                )
+                            })
+                    })
+                })
+                .transpose()
+        };
+        Ok((parse(&self.since)?, parse(&self.until)?))
+    }
+}
+
+/// Schema changelog response
+#[derive(Debug, Serialize)]
+pub struct SchemaLogResponse {
+    /// Changes in the range, oldest first
+    pub changes: Vec<SchemaChange>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Schema rollback script response
+#[derive(Debug, Serialize)]
+pub struct SchemaRollbackResponse {
+    /// Cypher script undoing the changes in the range, newest first
+    pub script: String,
+    /// Number of changes the script covers
+    pub changes: usize,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Create a new label. Registers the name in the shared engine's
 /// catalog and returns the allocated `LabelId`.
 pub async fn create_label(
@@ -193,6 +294,119 @@ pub async fn list_rel_types(State(server): State<Arc<NexusServer>>) -> Json<List
     Json(ListRelTypesResponse { types, error: None })
 }
 
+/// Bind a label/property to the embedding model its vectors come from.
+pub async fn set_embedding_model(
+    State(server): State<Arc<NexusServer>>,
+    Json(request): Json<SetEmbeddingModelRequest>,
+) -> Json<EmbeddingModelResponse> {
+    let mut engine = server.engine.write().await;
+    match engine.set_embedding_model(
+        &request.label,
+        &request.property,
+        &request.model,
+        request.dimensions,
+    ) {
+        Ok(binding) => Json(EmbeddingModelResponse {
+            binding: Some(binding),
+            error: None,
+        }),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to bind :{}({}) to embedding model {}: {}",
+                request.label,
+                request.property,
+                request.model,
+                e
+            );
+            Json(EmbeddingModelResponse {
+                binding: None,
+                error: Some(e.to_string()),
+            })
+        }
+    }
+}
+
+/// List every embedding model binding.
+pub async fn list_embedding_models(
+    State(server): State<Arc<NexusServer>>,
+) -> Json<ListEmbeddingModelsResponse> {
+    let engine = server.engine.read().await;
+    match engine.embedding_models() {
+        Ok(models) => Json(ListEmbeddingModelsResponse {
+            models,
+            error: None,
+        }),
+        Err(e) => Json(ListEmbeddingModelsResponse {
+            models: Vec::new(),
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// Remove the embedding model binding of a label/property.
+pub async fn drop_embedding_model(
+    State(server): State<Arc<NexusServer>>,
+    Path((label, property)): Path<(String, String)>,
+) -> Json<DropEmbeddingModelResponse> {
+    let mut engine = server.engine.write().await;
+    match engine.drop_embedding_model(&label, &property) {
+        Ok(dropped) => Json(DropEmbeddingModelResponse {
+            dropped,
+            error: None,
+        }),
+        Err(e) => Json(DropEmbeddingModelResponse {
+            dropped: false,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// List the schema changes applied in a time range.
+pub async fn schema_log(
+    State(server): State<Arc<NexusServer>>,
+    Query(query): Query<SchemaLogQuery>,
+) -> Json<SchemaLogResponse> {
+    match changes_in_range(&server, &query).await {
+        Ok(changes) => Json(SchemaLogResponse {
+            changes,
+            error: None,
+        }),
+        Err(e) => Json(SchemaLogResponse {
+            changes: Vec::new(),
+            error: Some(e),
+        }),
+    }
+}
+
+async fn changes_in_range(
+    server: &NexusServer,
+    query: &SchemaLogQuery,
+) -> Result<Vec<SchemaChange>, String> {
+    let (since, until) = query.range()?;
+    let engine = server.engine.read().await;
+    engine.schema_log(since, until).map_err(|e| e.to_string())
+}
+
+/// Generate a Cypher script undoing the schema changes applied in a
+/// time range.
+pub async fn schema_rollback(
+    State(server): State<Arc<NexusServer>>,
+    Query(query): Query<SchemaLogQuery>,
+) -> Json<SchemaRollbackResponse> {
+    match changes_in_range(&server, &query).await {
+        Ok(changes) => Json(SchemaRollbackResponse {
+            script: nexus_core::engine::schema_log::rollback_script(&changes),
+            changes: changes.len(),
+            error: None,
+        }),
+        Err(e) => Json(SchemaRollbackResponse {
+            script: String::new(),
+            changes: 0,
+            error: Some(e),
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +520,90 @@ mod tests {
             listed_b.labels
         );
     }
+
+    #[tokio::test]
+    async fn test_embedding_model_bindings_round_trip() {
+        let server = build_test_server();
+
+        let out = set_embedding_model(
+            State(Arc::clone(&server)),
+            Json(SetEmbeddingModelRequest {
+                label: "Doc".to_string(),
+                property: "embedding".to_string(),
+                model: "bge-small".to_string(),
+                dimensions: 384,
+            }),
+        )
+        .await
+        .0;
+        assert!(out.error.is_none(), "bind failed: {:?}", out.error);
+
+        let listed = list_embedding_models(State(Arc::clone(&server))).await.0;
+        assert_eq!(listed.models.len(), 1);
+        assert_eq!(listed.models[0].model, "bge-small");
+        assert_eq!(listed.models[0].dimensions, 384);
+
+        let dropped = drop_embedding_model(
+            State(Arc::clone(&server)),
+            Path(("Doc".to_string(), "embedding".to_string())),
+        )
+        .await
+        .0;
+        assert!(dropped.dropped);
+        assert!(
+            list_embedding_models(State(server))
+                .await
+                .0
+                .models
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_log_and_rollback_script() {
+        let server = build_test_server();
+        server
+            .engine
+            .write()
+            .await
+            .execute_cypher("CREATE INDEX ON :Person(email)")
+            .unwrap();
+
+        let log = schema_log(State(Arc::clone(&server)), Query(SchemaLogQuery::default()))
+            .await
+            .0;
+        assert!(log.error.is_none(), "log failed: {:?}", log.error);
+        assert!(
+            log.changes
+                .iter()
+                .any(|c| c.statement == "CREATE INDEX ON :Person(email)")
+        );
+
+        let rollback = schema_rollback(
+            State(Arc::clone(&server)),
+            Query(SchemaLogQuery {
+                since: Some("2000-01-01T00:00:00Z".to_string()),
+                until: None,
+            }),
+        )
+        .await
+        .0;
+        assert!(
+            rollback.error.is_none(),
+            "rollback failed: {:?}",
+            rollback.error
+        );
+        assert!(rollback.script.contains("DROP INDEX ON :Person(email);"));
+
+        let bad = schema_log(
+            State(server),
+            Query(SchemaLogQuery {
+                since: Some("yesterday".to_string()),
+                until: None,
+            }),
+        )
+        .await
+        .0;
+        assert!(bad.error.is_some());
+    }
 }
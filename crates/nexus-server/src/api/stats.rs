@@ -154,6 +154,38 @@ pub async fn get_stats(State(server): State<Arc<NexusServer>>) -> Json<DatabaseS
     }
 }
 
+/// Storage metrics response
+#[derive(Debug, Serialize)]
+pub struct StorageStatsResponse {
+    /// Per-store file metrics, if they could be collected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<nexus_core::StorageMetrics>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Get per-store file sizes, record counts, tombstone ratios and
+/// fragmentation estimates, to decide when a store is worth compacting.
+pub async fn get_storage_stats(
+    State(server): State<Arc<NexusServer>>,
+) -> Json<StorageStatsResponse> {
+    let engine = server.engine.read().await;
+    match engine.storage_metrics() {
+        Ok(storage) => Json(StorageStatsResponse {
+            storage: Some(storage),
+            error: None,
+        }),
+        Err(e) => {
+            tracing::error!("Failed to collect storage metrics: {}", e);
+            Json(StorageStatsResponse {
+                storage: None,
+                error: Some(format!("Failed to collect storage metrics: {e}")),
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +272,21 @@ mod tests {
             "server B should not see nodes created on server A"
         );
     }
+
+    #[tokio::test]
+    async fn test_get_storage_stats_lists_every_store() {
+        let server = build_test_server();
+        {
+            let mut engine = server.engine.write().await;
+            engine
+                .create_node(vec!["A".to_string()], serde_json::json!({"k": 1}))
+                .unwrap();
+        }
+        let response = get_storage_stats(State(server)).await.0;
+        assert!(response.error.is_none(), "{:?}", response.error);
+        let storage = response.storage.expect("metrics");
+        assert_eq!(storage.stores.len(), 6);
+        assert_eq!(storage.stores[0].store, "nodes");
+        assert_eq!(storage.stores[0].records, Some(1));
+    }
 }
@@ -0,0 +1,1128 @@
+//! Named vector index endpoints
+//!
+//! - GET /schema/vector_indexes - List vector indexes with their stats
+//! - POST /schema/vector_indexes - Create a vector index
+//! - GET /schema/vector_indexes/{name} - Show one vector index
+//! - DELETE /schema/vector_indexes/{name} - Drop a vector index
+//! - POST /schema/vector_indexes/{name}/vectors - Insert or replace node vectors
+//! - DELETE /schema/vector_indexes/{name}/vectors/{node_id} - Remove a node's vector
+//! - POST /schema/vector_indexes/{name}/search - k nearest neighbours of a vector
+//! - GET /schema/vector_indexes/{name}/migration - Show an in-progress migration
+//! - POST /schema/vector_indexes/{name}/migration - Start migrating dimensions, metric or property
+//! - DELETE /schema/vector_indexes/{name}/migration - Abort a migration
+//! - POST /schema/vector_indexes/{name}/migration/vectors - Write re-embedded vectors
+//! - POST /schema/vector_indexes/{name}/migration/complete - Swap the migrated index in
+//! - POST /vectors/bulk?index={name} - Streamed NDJSON embedding backfill
+
+use axum::body::Body;
+use axum::extract::{Json, Path, Query, State};
+use futures::StreamExt;
+use nexus_core::index::{KnnSearchMode, KnnSearchOptions, VectorMetric};
+use nexus_core::{VectorIndexInfo, VectorIndexMigrationInfo, VectorUpsertReport};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::NexusServer;
+
+/// Neighbours returned by a search that does not set `k`.
+pub const DEFAULT_SEARCH_K: usize = 10;
+
+/// Records a bulk upload applies per engine write (`batch_size`).
+pub const DEFAULT_BULK_BATCH_SIZE: usize = 1_000;
+
+/// Largest `batch_size` a bulk upload may ask for.
+pub const MAX_BULK_BATCH_SIZE: usize = 50_000;
+
+/// Failures listed in a bulk response; the rest are only counted.
+pub const MAX_REPORTED_BULK_FAILURES: usize = 100;
+
+/// Request to create a vector index
+#[derive(Debug, Deserialize)]
+pub struct CreateVectorIndexRequest {
+    /// Index name
+    pub name: String,
+    /// Label whose nodes are indexed
+    pub label: String,
+    /// Property holding the embeddings
+    pub property: String,
+    /// Embedding length; omit to use the property's embedding model
+    /// binding
+    #[serde(default)]
+    pub dimensions: usize,
+    /// `cosine` (default) or `euclidean`
+    #[serde(default)]
+    pub metric: Option<String>,
+}
+
+/// Response carrying one vector index
+#[derive(Debug, Serialize)]
+pub struct VectorIndexResponse {
+    /// Success message
+    pub message: String,
+    /// The index, if found or created
+    pub index: Option<VectorIndexInfo>,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Response for listing vector indexes
+#[derive(Debug, Serialize)]
+pub struct ListVectorIndexesResponse {
+    /// Vector indexes, by name
+    pub indexes: Vec<VectorIndexInfo>,
+}
+
+/// Response for dropping a vector index
+#[derive(Debug, Serialize)]
+pub struct DropVectorIndexResponse {
+    /// Success message
+    pub message: String,
+    /// Whether the index existed
+    pub dropped: bool,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// One node's embedding
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NodeVector {
+    /// Node ID
+    pub node_id: u64,
+    /// Embedding, of the index's dimensions
+    pub vector: Vec<f32>,
+}
+
+/// Request to insert or replace node vectors
+#[derive(Debug, Deserialize)]
+pub struct UpsertVectorsRequest {
+    /// Vectors to write
+    pub vectors: Vec<NodeVector>,
+}
+
+/// A vector that could not be written
+#[derive(Debug, Serialize)]
+pub struct VectorFailure {
+    /// Node ID
+    pub node_id: u64,
+    /// Why the write failed
+    pub error: String,
+}
+
+/// Response for inserting node vectors
+#[derive(Debug, Serialize)]
+pub struct UpsertVectorsResponse {
+    /// Success message
+    pub message: String,
+    /// Vectors written
+    pub upserted: usize,
+    /// Vectors rejected, with the reason
+    pub failed: Vec<VectorFailure>,
+    /// Error message if the request failed as a whole
+    pub error: Option<String>,
+}
+
+/// Response for removing a node's vector
+#[derive(Debug, Serialize)]
+pub struct DeleteVectorResponse {
+    /// Success message
+    pub message: String,
+    /// Whether the node was indexed
+    pub removed: bool,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Request to start a vector index migration; unset fields keep the
+/// live index's value
+#[derive(Debug, Deserialize)]
+pub struct StartVectorMigrationRequest {
+    /// Property the migrated index reads
+    #[serde(default)]
+    pub property: Option<String>,
+    /// Embedding length of the migrated index
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    /// `cosine` or `euclidean`
+    #[serde(default)]
+    pub metric: Option<String>,
+}
+
+/// Response carrying an in-progress migration
+#[derive(Debug, Serialize)]
+pub struct VectorMigrationResponse {
+    /// Success message
+    pub message: String,
+    /// The migration, if found or started
+    pub migration: Option<VectorIndexMigrationInfo>,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Query parameters for completing a migration
+#[derive(Debug, Default, Deserialize)]
+pub struct CompleteVectorMigrationParams {
+    /// Swap even though some nodes have not been re-embedded
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Response for aborting a migration
+#[derive(Debug, Serialize)]
+pub struct AbortVectorMigrationResponse {
+    /// Success message
+    pub message: String,
+    /// Whether a migration was in progress
+    pub aborted: bool,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// Request for a nearest-neighbour search
+#[derive(Debug, Deserialize)]
+pub struct VectorSearchRequest {
+    /// Query vector
+    pub vector: Vec<f32>,
+    /// Neighbours to return (default [`DEFAULT_SEARCH_K`])
+    #[serde(default)]
+    pub k: Option<usize>,
+    /// `auto` (default), `approximate` or `exact`
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// HNSW candidate list size for approximate searches
+    #[serde(default)]
+    pub ef_search: Option<usize>,
+    /// Run both searches and report the approximate search's recall
+    #[serde(default)]
+    pub measure_recall: bool,
+}
+
+/// One search hit
+#[derive(Debug, Serialize)]
+pub struct VectorSearchHit {
+    /// Node ID
+    pub node_id: u64,
+    /// Similarity score, higher is closer
+    pub score: f32,
+}
+
+/// Response for a nearest-neighbour search
+#[derive(Debug, Serialize)]
+pub struct VectorSearchResponse {
+    /// Success message
+    pub message: String,
+    /// Hits, best first
+    pub results: Vec<VectorSearchHit>,
+    /// Search that served the hits: `approximate` or `exact`
+    pub mode: Option<KnnSearchMode>,
+    /// Recall@k of the approximate search against the exact one, when
+    /// `measure_recall` was set
+    pub recall: Option<f64>,
+    /// Error message if any
+    pub error: Option<String>,
+}
+
+/// List every vector index with its current vector count
+pub async fn list_vector_indexes(
+    State(server): State<Arc<NexusServer>>,
+) -> Json<ListVectorIndexesResponse> {
+    let engine = server.engine.read().await;
+    Json(ListVectorIndexesResponse {
+        indexes: engine.vector_indexes(),
+    })
+}
+
+/// Create a vector index and fill it from the nodes already carrying
+/// the label
+pub async fn create_vector_index(
+    State(server): State<Arc<NexusServer>>,
+    Json(request): Json<CreateVectorIndexRequest>,
+) -> Json<VectorIndexResponse> {
+    let metric = match request.metric.as_deref().map(str::parse::<VectorMetric>) {
+        None => VectorMetric::default(),
+        Some(Ok(metric)) => metric,
+        Some(Err(e)) => return index_error(e.to_string()),
+    };
+    let mut engine = server.engine.write().await;
+    match engine.create_vector_index(
+        &request.name,
+        &request.label,
+        &request.property,
+        request.dimensions,
+        metric,
+    ) {
+        Ok(index) => Json(VectorIndexResponse {
+            message: format!(
+                "Vector index '{}' created with {} vectors",
+                index.name, index.vectors
+            ),
+            index: Some(index),
+            error: None,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to create vector index {}: {}", request.name, e);
+            index_error(format!("Failed to create vector index: {}", e))
+        }
+    }
+}
+
+/// Show one vector index
+pub async fn get_vector_index(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+) -> Json<VectorIndexResponse> {
+    let engine = server.engine.read().await;
+    match engine.vector_index(&name) {
+        Ok(index) => Json(VectorIndexResponse {
+            message: format!("Vector index '{}'", index.name),
+            index: Some(index),
+            error: None,
+        }),
+        Err(e) => index_error(e.to_string()),
+    }
+}
+
+/// Drop a vector index; the embeddings stay on the nodes
+pub async fn drop_vector_index(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+) -> Json<DropVectorIndexResponse> {
+    let mut engine = server.engine.write().await;
+    match engine.drop_vector_index(&name) {
+        Ok(dropped) => Json(DropVectorIndexResponse {
+            message: if dropped {
+                format!("Vector index '{}' dropped", name)
+            } else {
+                format!("Vector index '{}' does not exist", name)
+            },
+            dropped,
+            error: None,
+        }),
+        Err(e) => {
+            tracing::warn!("Failed to drop vector index {}: {}", name, e);
+            Json(DropVectorIndexResponse {
+                message: "".to_string(),
+                dropped: false,
+                error: Some(format!("Failed to drop vector index: {}", e)),
+            })
+        }
+    }
+}
+
+/// Store and index node embeddings, replacing earlier ones. Each vector
+/// is written independently; rejected ones are listed in `failed`.
+pub async fn upsert_vectors(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Json(request): Json<UpsertVectorsRequest>,
+) -> Json<UpsertVectorsResponse> {
+    let vectors = request
+        .vectors
+        .into_iter()
+        .map(|v| (v.node_id, v.vector))
+        .collect();
+    let mut engine = server.engine.write().await;
+    upsert_response(engine.upsert_node_vectors(&name, vectors))
+}
+
+fn upsert_response(result: nexus_core::Result<VectorUpsertReport>) -> Json<UpsertVectorsResponse> {
+    match result {
+        Ok(report) => {
+            let failed: Vec<VectorFailure> = report
+                .failed
+                .into_iter()
+                .map(|f| VectorFailure {
+                    node_id: f.node_id,
+                    error: f.error,
+                })
+                .collect();
+            Json(UpsertVectorsResponse {
+                message: format!(
+                    "{} vectors written, {} rejected",
+                    report.upserted,
+                    failed.len()
+                ),
+                upserted: report.upserted,
+                failed,
+                error: None,
+            })
+        }
+        Err(e) => Json(UpsertVectorsResponse {
+            message: "".to_string(),
+            upserted: 0,
+            failed: Vec::new(),
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// Show the in-progress migration of a vector index
+pub async fn get_vector_migration(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+) -> Json<VectorMigrationResponse> {
+    let engine = server.engine.read().await;
+    migration_response(engine.vector_index_migration(&name), |m| {
+        format!("{} node(s) left to re-embed", m.pending)
+    })
+}
+
+/// Start migrating a vector index to new dimensions, metric or
+/// property. Node writes feed both indexes until the migration is
+/// completed or aborted; searches keep using the live one.
+pub async fn start_vector_migration(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Json(request): Json<StartVectorMigrationRequest>,
+) -> Json<VectorMigrationResponse> {
+    let metric = match request.metric.as_deref().map(str::parse::<VectorMetric>) {
+        None => None,
+        Some(Ok(metric)) => Some(metric),
+        Some(Err(e)) => return migration_response(Err(e), |_| String::new()),
+    };
+    let mut engine = server.engine.write().await;
+    let result = engine.start_vector_index_migration(
+        &name,
+        request.property.as_deref(),
+        request.dimensions,
+        metric,
+    );
+    migration_response(result, |m| {
+        format!(
+            "Migration of '{}' started, {} node(s) left to re-embed",
+            name, m.pending
+        )
+    })
+}
+
+/// Write re-embedded vectors to the migration target
+pub async fn upsert_migration_vectors(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Json(request): Json<UpsertVectorsRequest>,
+) -> Json<UpsertVectorsResponse> {
+    let vectors = request
+        .vectors
+        .into_iter()
+        .map(|v| (v.node_id, v.vector))
+        .collect();
+    let mut engine = server.engine.write().await;
+    upsert_response(engine.upsert_migration_vectors(&name, vectors))
+}
+
+/// Swap the migrated index in as the live one. Refused while nodes are
+/// left to re-embed, unless `?force=true`.
+pub async fn complete_vector_migration(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Query(params): Query<CompleteVectorMigrationParams>,
+) -> Json<VectorIndexResponse> {
+    let mut engine = server.engine.write().await;
+    match engine.complete_vector_index_migration(&name, params.force) {
+        Ok(index) => Json(VectorIndexResponse {
+            message: format!(
+                "Vector index '{}' migrated to {} dimensions ({})",
+                name,
+                index.dimensions,
+                index.metric.as_str()
+            ),
+            index: Some(index),
+            error: None,
+        }),
+        Err(e) => index_error(e.to_string()),
+    }
+}
+
+/// Abort a migration, leaving the live index unchanged
+pub async fn abort_vector_migration(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+) -> Json<AbortVectorMigrationResponse> {
+    let mut engine = server.engine.write().await;
+    match engine.abort_vector_index_migration(&name) {
+        Ok(aborted) => Json(AbortVectorMigrationResponse {
+            message: if aborted {
+                format!("Migration of '{}' aborted", name)
+            } else {
+                format!("Vector index '{}' has no migration in progress", name)
+            },
+            aborted,
+            error: None,
+        }),
+        Err(e) => Json(AbortVectorMigrationResponse {
+            message: "".to_string(),
+            aborted: false,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+fn migration_response(
+    result: nexus_core::Result<VectorIndexMigrationInfo>,
+    message: impl FnOnce(&VectorIndexMigrationInfo) -> String,
+) -> Json<VectorMigrationResponse> {
+    match result {
+        Ok(migration) => Json(VectorMigrationResponse {
+            message: message(&migration),
+            migration: Some(migration),
+            error: None,
+        }),
+        Err(e) => Json(VectorMigrationResponse {
+            message: "".to_string(),
+            migration: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// Remove a node's embedding from the index and the node
+pub async fn delete_vector(
+    State(server): State<Arc<NexusServer>>,
+    Path((name, node_id)): Path<(String, u64)>,
+) -> Json<DeleteVectorResponse> {
+    let mut engine = server.engine.write().await;
+    match engine.remove_node_vector(&name, node_id) {
+        Ok(removed) => Json(DeleteVectorResponse {
+            message: if removed {
+                format!("Vector of node {} removed", node_id)
+            } else {
+                format!("Node {} had no vector in '{}'", node_id, name)
+            },
+            removed,
+            error: None,
+        }),
+        Err(e) => Json(DeleteVectorResponse {
+            message: "".to_string(),
+            removed: false,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// Return the `k` indexed nodes closest to a query vector
+pub async fn search_vector_index(
+    State(server): State<Arc<NexusServer>>,
+    Path(name): Path<String>,
+    Json(request): Json<VectorSearchRequest>,
+) -> Json<VectorSearchResponse> {
+    let search_error = |error: String| {
+        Json(VectorSearchResponse {
+            message: "".to_string(),
+            results: Vec::new(),
+            mode: None,
+            recall: None,
+            error: Some(error),
+        })
+    };
+    let mode = match request.mode.as_deref().map(str::parse::<KnnSearchMode>) {
+        None => KnnSearchMode::default(),
+        Some(Ok(mode)) => mode,
+        Some(Err(e)) => return search_error(e.to_string()),
+    };
+    let options = KnnSearchOptions {
+        mode,
+        ef_search: request.ef_search,
+        measure_recall: request.measure_recall,
+    };
+    let k = request.k.unwrap_or(DEFAULT_SEARCH_K);
+    let engine = server.engine.read().await;
+    match engine.vector_search_with(&name, &request.vector, k, options) {
+        Ok(outcome) => Json(VectorSearchResponse {
+            message: format!(
+                "{} neighbours found ({} search)",
+                outcome.results.len(),
+                outcome.mode.as_str()
+            ),
+            results: outcome
+                .results
+                .into_iter()
+                .map(|(node_id, score)| VectorSearchHit { node_id, score })
+                .collect(),
+            mode: Some(outcome.mode),
+            recall: outcome.recall,
+            error: None,
+        }),
+        Err(e) => search_error(e.to_string()),
+    }
+}
+
+/// One line of a bulk upload: the node, by id or external id, and its
+/// embedding
+#[derive(Debug, Deserialize)]
+pub struct BulkVectorRecord {
+    /// Node ID
+    #[serde(default)]
+    pub node_id: Option<u64>,
+    /// Prefixed external id (`str:doc-42`, `uuid:…`), instead of `node_id`
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// Embedding, of the index's dimensions
+    pub vector: Vec<f32>,
+}
+
+/// A bulk upload line that was not applied
+#[derive(Debug, Serialize)]
+pub struct BulkVectorFailure {
+    /// 1-based line number in the request body
+    pub line: usize,
+    /// Node ID, when the line named or resolved to one
+    pub node_id: Option<u64>,
+    /// Why the line was rejected
+    pub error: String,
+}
+
+/// Response for a bulk upload
+#[derive(Debug, Default, Serialize)]
+pub struct BulkVectorResponse {
+    /// Success message
+    pub message: String,
+    /// Non-blank lines read
+    pub lines: usize,
+    /// Vectors written and indexed
+    pub upserted: usize,
+    /// Engine writes the upload was applied in
+    pub batches: usize,
+    /// Lines rejected
+    pub failed_count: usize,
+    /// The first [`MAX_REPORTED_BULK_FAILURES`] rejected lines
+    pub failed: Vec<BulkVectorFailure>,
+    /// Error that stopped the upload early; lines before it stay applied
+    pub error: Option<String>,
+}
+
+/// Accumulates a streamed upload into engine-sized batches.
+struct BulkVectorLoader {
+    index: String,
+    batch_size: usize,
+    line: usize,
+    pending: Vec<(usize, BulkVectorRecord)>,
+    response: BulkVectorResponse,
+}
+
+impl BulkVectorLoader {
+    fn reject(&mut self, line: usize, node_id: Option<u64>, error: String) {
+        self.response.failed_count += 1;
+        if self.response.failed.len() < MAX_REPORTED_BULK_FAILURES {
+            self.response.failed.push(BulkVectorFailure {
+                line,
+                node_id,
+                error,
+            });
+        }
+    }
+
+    /// Parse one NDJSON line into the pending batch.
+    fn push_line(&mut self, raw: &[u8]) {
+        self.line += 1;
+        let text = String::from_utf8_lossy(raw);
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        self.response.lines += 1;
+        match serde_json::from_str::<BulkVectorRecord>(text) {
+            Ok(record) => self.pending.push((self.line, record)),
+            Err(e) => self.reject(self.line, None, format!("Invalid record: {}", e)),
+        }
+    }
+
+    /// Resolve and write the pending batch under one engine write lock.
+    async fn flush(&mut self, server: &NexusServer) -> Result<(), String> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let pending = std::mem::take(&mut self.pending);
+        let mut engine = server.engine.write().await;
+
+        let mut lines = Vec::with_capacity(pending.len());
+        let mut vectors = Vec::with_capacity(pending.len());
+        {
+            let txn = engine
+                .catalog
+                .read_txn()
+                .map_err(|e| format!("Catalog read txn failed: {}", e))?;
+            for (line, record) in pending {
+                let node_id = match (record.node_id, record.external_id.as_deref()) {
+                    (Some(id), None) => id,
+                    (None, Some(raw)) => {
+                        let resolved = nexus_core::catalog::external_id::ExternalId::from_str(raw)
+                            .map_err(|e| format!("Invalid external_id `{}`: {}", raw, e))
+                            .and_then(|ext| {
+                                engine
+                                    .catalog
+                                    .external_id_index()
+                                    .get_internal(&txn, &ext)
+                                    .map_err(|e| format!("Catalog lookup failed: {}", e))?
+                                    .ok_or_else(|| format!("No node with external_id `{}`", raw))
+                            });
+                        match resolved {
+                            Ok(id) => id,
+                            Err(e) => {
+                                self.reject(line, None, e);
+                                continue;
+                            }
+                        }
+                    }
+                    _ => {
+                        self.reject(
+                            line,
+                            record.node_id,
+                            "Exactly one of node_id and external_id is required".to_string(),
+                        );
+                        continue;
+                    }
+                };
+                lines.push(line);
+                vectors.push((node_id, record.vector));
+            }
+        }
+
+        let report = engine
+            .upsert_node_vectors(&self.index, vectors)
+            .map_err(|e| e.to_string())?;
+        drop(engine);
+        self.response.batches += 1;
+        self.response.upserted += report.upserted;
+        for failure in report.failed {
+            self.reject(
+                lines[failure.position],
+                Some(failure.node_id),
+                failure.error,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Backfill embeddings into vector index `index` from a streamed
+/// NDJSON body: `POST /vectors/bulk?index=docs&batch_size=1000`.
+///
+/// Each line is `{"node_id": 42, "vector": [...]}` or
+/// `{"external_id": "str:doc-42", "vector": [...]}`. The body is read
+/// incrementally and applied `batch_size` records at a time, each batch
+/// in one engine write with a single parallel HNSW insert, so uploads
+/// are not bounded by the request size limit and memory stays
+/// proportional to `batch_size`. Bad lines are reported by line number
+/// and skipped; a transport or engine error stops the upload, keeping
+/// the batches already applied.
+pub async fn bulk_upsert_vectors(
+    State(server): State<Arc<NexusServer>>,
+    Query(params): Query<HashMap<String, String>>,
+    body: Body,
+) -> Json<BulkVectorResponse> {
+    let error = |message: String| {
+        Json(BulkVectorResponse {
+            error: Some(message),
+            ..Default::default()
+        })
+    };
+    let Some(index) = params.get("index").filter(|i| !i.is_empty()) else {
+        return error("Missing required query parameter `index`".to_string());
+    };
+    let batch_size = match params.get("batch_size").map(|raw| raw.parse::<usize>()) {
+        None => DEFAULT_BULK_BATCH_SIZE,
+        Some(Ok(n)) if (1..=MAX_BULK_BATCH_SIZE).contains(&n) => n,
+        Some(_) => {
+            return error(format!(
+                "Invalid query parameter `batch_size` — expected 1..={}",
+                MAX_BULK_BATCH_SIZE
+            ));
+        }
+    };
+    if let Err(e) = server.engine.read().await.vector_index(index) {
+        return error(e.to_string());
+    }
+
+    let mut loader = BulkVectorLoader {
+        index: index.clone(),
+        batch_size,
+        line: 0,
+        pending: Vec::with_capacity(batch_size),
+        response: BulkVectorResponse::default(),
+    };
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut stream = body.into_data_stream();
+    let mut failure = None;
+    'read: while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                failure = Some(format!("Failed to read request body: {}", e));
+                break;
+            }
+        };
+        buffer.extend_from_slice(&chunk);
+        let mut start = 0;
+        while let Some(offset) = buffer[start..].iter().position(|&b| b == b'\n') {
+            loader.push_line(&buffer[start..start + offset]);
+            start += offset + 1;
+            if loader.pending.len() >= loader.batch_size
+                && let Err(e) = loader.flush(&server).await
+            {
+                failure = Some(e);
+                break 'read;
+            }
+        }
+        buffer.drain(..start);
+    }
+    if failure.is_none() {
+        loader.push_line(&buffer);
+        failure = loader.flush(&server).await.err();
+    }
+
+    let mut response = loader.response;
+    response.message = format!(
+        "{} vectors written in {} batches, {} lines rejected",
+        response.upserted, response.batches, response.failed_count
+    );
+    if let Some(e) = failure {
+        tracing::warn!("Bulk vector upload into {} stopped: {}", index, e);
+        response.error = Some(e);
+    }
+    Json(response)
+}
+
+fn index_error(message: String) -> Json<VectorIndexResponse> {
+    Json(VectorIndexResponse {
+        message: "".to_string(),
+        index: None,
+        error: Some(message),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Build an isolated `Arc<NexusServer>` per test so handler calls
+    /// exercise real engine state without touching process-wide globals.
+    fn build_test_server() -> Arc<NexusServer> {
+        use parking_lot::RwLock as PlRwLock;
+        use tokio::sync::RwLock as TokioRwLock;
+
+        let ctx = nexus_core::testing::TestContext::new();
+        let engine = nexus_core::Engine::with_data_dir(ctx.path()).expect("engine init");
+        let engine_arc = Arc::new(TokioRwLock::new(engine));
+        let executor = Arc::new(nexus_core::executor::Executor::default());
+        let dbm = Arc::new(PlRwLock::new(
+            nexus_core::database::DatabaseManager::new(ctx.path().to_path_buf()).expect("dbm init"),
+        ));
+        let rbac = Arc::new(TokioRwLock::new(
+            nexus_core::auth::RoleBasedAccessControl::new(),
+        ));
+        let auth_mgr = Arc::new(nexus_core::auth::AuthManager::new(
+            nexus_core::auth::AuthConfig::default(),
+        ));
+        let jwt = Arc::new(nexus_core::auth::JwtManager::new(
+            nexus_core::auth::JwtConfig::default(),
+        ));
+        let audit = Arc::new(
+            nexus_core::auth::AuditLogger::new(nexus_core::auth::AuditConfig {
+                enabled: false,
+                log_dir: ctx.path().join("audit"),
+                retention_days: 1,
+                compress_logs: false,
+            })
+            .expect("audit init"),
+        );
+
+        // Leak the TestContext so its tempdir outlives the request.
+        let _leaked = Box::leak(Box::new(ctx));
+
+        Arc::new(NexusServer::new(
+            executor,
+            engine_arc,
+            dbm,
+            rbac,
+            auth_mgr,
+            jwt,
+            audit,
+            crate::config::RootUserConfig::default(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_vector_index_lifecycle() {
+        let server = build_test_server();
+        let (a, b) = {
+            let mut engine = server.engine.write().await;
+            let a = engine
+                .create_node(vec!["Doc".to_string()], json!({}))
+                .unwrap();
+            let b = engine
+                .create_node(vec!["Doc".to_string()], json!({}))
+                .unwrap();
+            (a, b)
+        };
+
+        let created = create_vector_index(
+            State(Arc::clone(&server)),
+            Json(CreateVectorIndexRequest {
+                name: "docs".to_string(),
+                label: "Doc".to_string(),
+                property: "embedding".to_string(),
+                dimensions: 2,
+                metric: Some("euclidean".to_string()),
+            }),
+        )
+        .await
+        .0;
+        assert!(created.error.is_none(), "{:?}", created.error);
+
+        let written = upsert_vectors(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Json(UpsertVectorsRequest {
+                vectors: vec![
+                    NodeVector {
+                        node_id: a,
+                        vector: vec![0.0, 0.0],
+                    },
+                    NodeVector {
+                        node_id: b,
+                        vector: vec![3.0, 4.0],
+                    },
+                    NodeVector {
+                        node_id: b,
+                        vector: vec![1.0],
+                    },
+                ],
+            }),
+        )
+        .await
+        .0;
+        assert_eq!(written.upserted, 2);
+        assert_eq!(written.failed.len(), 1);
+
+        let found = search_vector_index(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Json(VectorSearchRequest {
+                vector: vec![2.9, 4.1],
+                k: Some(1),
+                mode: None,
+                ef_search: None,
+                measure_recall: false,
+            }),
+        )
+        .await
+        .0;
+        assert!(found.error.is_none(), "{:?}", found.error);
+        assert_eq!(found.results.len(), 1);
+        assert_eq!(found.results[0].node_id, b);
+        assert_eq!(found.mode, Some(KnnSearchMode::Exact));
+
+        let sampled = search_vector_index(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Json(VectorSearchRequest {
+                vector: vec![2.9, 4.1],
+                k: Some(2),
+                mode: Some("approximate".to_string()),
+                ef_search: Some(32),
+                measure_recall: true,
+            }),
+        )
+        .await
+        .0;
+        assert!(sampled.error.is_none(), "{:?}", sampled.error);
+        assert_eq!(sampled.mode, Some(KnnSearchMode::Approximate));
+        assert!(sampled.recall.is_some());
+
+        let rejected = search_vector_index(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Json(VectorSearchRequest {
+                vector: vec![2.9, 4.1],
+                k: None,
+                mode: Some("fastest".to_string()),
+                ef_search: None,
+                measure_recall: false,
+            }),
+        )
+        .await
+        .0;
+        assert!(rejected.error.is_some());
+
+        let removed = delete_vector(State(Arc::clone(&server)), Path(("docs".to_string(), b)))
+            .await
+            .0;
+        assert!(removed.removed);
+        let listed = list_vector_indexes(State(Arc::clone(&server))).await.0;
+        assert_eq!(listed.indexes.len(), 1);
+        assert_eq!(listed.indexes[0].vectors, 1);
+
+        let dropped = drop_vector_index(State(Arc::clone(&server)), Path("docs".to_string()))
+            .await
+            .0;
+        assert!(dropped.dropped);
+        let missing = get_vector_index(State(server), Path("docs".to_string()))
+            .await
+            .0;
+        assert!(missing.index.is_none());
+        assert!(missing.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_create_vector_index_rejects_unknown_metric() {
+        let server = build_test_server();
+        let response = create_vector_index(
+            State(server),
+            Json(CreateVectorIndexRequest {
+                name: "docs".to_string(),
+                label: "Doc".to_string(),
+                property: "embedding".to_string(),
+                dimensions: 2,
+                metric: Some("manhattan".to_string()),
+            }),
+        )
+        .await
+        .0;
+        assert!(response.index.is_none());
+        assert!(response.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_upsert_streams_batches_and_reports_bad_lines() {
+        let server = build_test_server();
+        let (a, b) = {
+            let mut engine = server.engine.write().await;
+            let a = engine
+                .create_node(vec!["Doc".to_string()], json!({}))
+                .unwrap();
+            let b = engine
+                .create_node_with_external_id(
+                    vec!["Doc".to_string()],
+                    json!({}),
+                    Some("str:doc-b".parse().unwrap()),
+                    nexus_core::storage::external_id::ConflictPolicy::Error,
+                )
+                .unwrap();
+            engine
+                .create_vector_index("docs", "Doc", "embedding", 2, VectorMetric::Cosine)
+                .unwrap();
+            (a, b)
+        };
+
+        let body = format!(
+            "{{\"node_id\": {a}, \"vector\": [1.0, 0.0]}}\n\
+             \n\
+             {{\"external_id\": \"str:doc-b\", \"vector\": [0.0, 1.0]}}\n\
+             not json\n\
+             {{\"external_id\": \"str:nope\", \"vector\": [0.0, 1.0]}}\n\
+             {{\"node_id\": {a}, \"vector\": [1.0]}}"
+        );
+        let mut params = HashMap::new();
+        params.insert("index".to_string(), "docs".to_string());
+        params.insert("batch_size".to_string(), "2".to_string());
+        let response =
+            bulk_upsert_vectors(State(Arc::clone(&server)), Query(params), Body::from(body))
+                .await
+                .0;
+
+        assert!(response.error.is_none(), "{:?}", response.error);
+        assert_eq!(response.lines, 5);
+        assert_eq!(response.upserted, 2);
+        assert_eq!(response.batches, 2);
+        assert_eq!(
+            response.failed.iter().map(|f| f.line).collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        assert_eq!(response.failed[2].node_id, Some(a));
+        let engine = server.engine.read().await;
+        assert_eq!(engine.vector_index("docs").unwrap().vectors, 2);
+        assert_eq!(
+            engine.vector_search("docs", &[0.0, 1.0], 1).unwrap()[0].0,
+            b
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_upsert_requires_known_index() {
+        let server = build_test_server();
+        let mut params = HashMap::new();
+        params.insert("index".to_string(), "missing".to_string());
+        let response = bulk_upsert_vectors(State(server), Query(params), Body::empty())
+            .await
+            .0;
+        assert!(response.error.is_some());
+        assert_eq!(response.upserted, 0);
+    }
+
+    #[tokio::test]
+    async fn test_vector_index_migration() {
+        let server = build_test_server();
+        let node = {
+            let mut engine = server.engine.write().await;
+            let node = engine
+                .create_node(vec!["Doc".to_string()], json!({"embedding": [1.0, 0.0]}))
+                .unwrap();
+            engine
+                .create_vector_index("docs", "Doc", "embedding", 2, VectorMetric::Cosine)
+                .unwrap();
+            node
+        };
+
+        let started = start_vector_migration(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Json(StartVectorMigrationRequest {
+                property: Some("embedding_v2".to_string()),
+                dimensions: Some(3),
+                metric: None,
+            }),
+        )
+        .await
+        .0;
+        assert!(started.error.is_none(), "{:?}", started.error);
+        assert_eq!(started.migration.unwrap().pending, 1);
+
+        let refused = complete_vector_migration(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Query(CompleteVectorMigrationParams::default()),
+        )
+        .await
+        .0;
+        assert!(refused.error.is_some());
+
+        let written = upsert_migration_vectors(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Json(UpsertVectorsRequest {
+                vectors: vec![NodeVector {
+                    node_id: node,
+                    vector: vec![0.0, 0.0, 1.0],
+                }],
+            }),
+        )
+        .await
+        .0;
+        assert_eq!(written.upserted, 1, "{:?}", written.failed);
+        let status = get_vector_migration(State(Arc::clone(&server)), Path("docs".to_string()))
+            .await
+            .0;
+        assert_eq!(status.migration.unwrap().pending, 0);
+
+        let completed = complete_vector_migration(
+            State(Arc::clone(&server)),
+            Path("docs".to_string()),
+            Query(CompleteVectorMigrationParams::default()),
+        )
+        .await
+        .0;
+        let index = completed.index.expect("migrated index");
+        assert_eq!(
+            (index.property.as_str(), index.dimensions),
+            ("embedding_v2", 3)
+        );
+
+        let aborted = abort_vector_migration(State(server), Path("docs".to_string()))
+            .await
+            .0;
+        assert!(!aborted.aborted);
+        assert!(aborted.error.is_none());
+    }
+}
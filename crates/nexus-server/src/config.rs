@@ -12,6 +12,8 @@ pub struct Config {
     pub addr: SocketAddr,
     /// Data directory
     pub data_dir: String,
+    /// Directory backups are written to and attached from
+    pub backup_dir: String,
     /// Maximum request body size in bytes. Enforced via Axum's
     /// `DefaultBodyLimit` layer to keep bulk ingest payloads from
     /// monopolising memory.
@@ -42,6 +44,65 @@ pub struct Config {
     ///
     /// [`KeyProvider`]: nexus_core::storage::crypto::KeyProvider
     pub encryption: EncryptionConfig,
+    /// Result-size guard for `/cypher` — default row limit, global
+    /// hard cap, and per-role caps.
+    pub result_limits: ResultLimitConfig,
+    /// Memory budget and the thresholds at which caches shrink and
+    /// query-bearing requests queue.
+    pub memory_pressure: nexus_core::memory_management::MemoryPressureConfig,
+    /// Threads that execute Cypher queries, apart from the tokio
+    /// runtime.
+    pub query_pool: crate::query_pool::QueryPoolConfig,
+}
+
+/// Result-size guard for `/cypher` responses. Keeps an accidental
+/// `MATCH (n) RETURN n` from serializing the whole database into one
+/// HTTP body.
+///
+/// A request gets `default_max_rows` unless it sends its own
+/// `max_rows`, which is clamped to the caller's cap: the largest
+/// `role_caps` entry among the caller's RBAC roles, or `hard_cap` for
+/// callers with no capped role (including unauthenticated requests).
+/// A response that lost rows carries a `truncated` marker.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ResultLimitConfig {
+    /// Rows returned when the request does not ask for a limit.
+    pub default_max_rows: usize,
+    /// Ceiling for per-request overrides from callers without a
+    /// role-specific cap.
+    pub hard_cap: usize,
+    /// Per-role ceilings keyed by RBAC role id. Replaces `hard_cap`
+    /// (up or down) for users holding the role.
+    pub role_caps: std::collections::HashMap<String, usize>,
+}
+
+impl Default for ResultLimitConfig {
+    fn default() -> Self {
+        Self {
+            default_max_rows: 10_000,
+            hard_cap: 100_000,
+            role_caps: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl ResultLimitConfig {
+    /// Cap for a caller holding `roles`. The most generous matching
+    /// role wins; `hard_cap` applies when none of the roles is capped.
+    pub fn cap_for_roles<'a>(&self, roles: impl IntoIterator<Item = &'a String>) -> usize {
+        roles
+            .into_iter()
+            .filter_map(|role| self.role_caps.get(role).copied())
+            .max()
+            .unwrap_or(self.hard_cap)
+    }
+
+    /// Effective row limit for a request that asked for `requested`
+    /// rows (or nothing) from a caller whose cap is `cap`.
+    pub fn effective_limit(&self, requested: Option<usize>, cap: usize) -> usize {
+        requested.unwrap_or(self.default_max_rows).min(cap)
+    }
 }
 
 /// Encryption-at-rest configuration. Resolved from
@@ -264,6 +325,7 @@ impl Default for Config {
         Self {
             addr: "127.0.0.1:15474".parse().unwrap(),
             data_dir: "./data".to_string(),
+            backup_dir: "./backups".to_string(),
             // 16 MiB — generous for single Cypher statements and small bulk
             // ingest payloads, but bounded so a single oversized POST cannot
             // exhaust the server's allocator.
@@ -276,6 +338,9 @@ impl Default for Config {
             rpc: RpcConfig::default(),
             cluster: nexus_core::cluster::ClusterConfig::default(),
             encryption: EncryptionConfig::default(),
+            result_limits: ResultLimitConfig::default(),
+            memory_pressure: Default::default(),
+            query_pool: Default::default(),
         }
     }
 }
@@ -770,8 +835,36 @@ pub struct YamlOverrides {
     pub max_body_size_mb: Option<usize>,
     /// `storage.data_dir`
     pub data_dir: Option<String>,
+    /// `storage.backup_dir`
+    pub backup_dir: Option<String>,
     /// `storage.page_cache.capacity`
     pub page_cache_capacity: Option<usize>,
+    /// `storage.integrity_check`
+    pub integrity_check: Option<nexus_core::IntegrityCheckConfig>,
+    /// `storage.property_bloom_filters`
+    pub property_bloom_filters: Option<Vec<nexus_core::engine::PropertyBloomFilterSpec>>,
+    /// `storage.ttl_policies`
+    pub ttl_policies: Option<Vec<nexus_core::engine::TtlPolicySpec>>,
+    /// `storage.count_aggregates`
+    pub count_aggregates: Option<Vec<nexus_core::engine::CountAggregateSpec>>,
+    /// `storage.property_history`
+    pub property_history: Option<Vec<nexus_core::engine::PropertyHistorySpec>>,
+    /// `storage.string_normalization`
+    pub string_normalization: Option<nexus_core::text::StringNormalization>,
+    /// `storage.write_pipeline`
+    pub write_pipeline: Option<nexus_core::engine::WritePipelineConfig>,
+    /// `server.result_limits`
+    pub result_limits: Option<ResultLimitConfig>,
+    /// `server.memory_pressure`
+    pub memory_pressure: Option<nexus_core::memory_management::MemoryPressureConfig>,
+    /// `server.query_pool`
+    pub query_pool: Option<crate::query_pool::QueryPoolConfig>,
+    /// `server.sessions`
+    pub session_timeouts: Option<nexus_core::session::SessionTimeoutConfig>,
+    /// `server.external_data`
+    pub external_data: Option<nexus_core::external_data::ExternalDataConfig>,
+    /// `server.plan_history`
+    pub plan_history: Option<nexus_core::performance::plan_history::PlanHistoryConfig>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -786,13 +879,27 @@ struct YamlRootConfig {
 struct YamlServerSection {
     addr: Option<String>,
     max_body_size_mb: Option<usize>,
+    result_limits: Option<ResultLimitConfig>,
+    memory_pressure: Option<nexus_core::memory_management::MemoryPressureConfig>,
+    query_pool: Option<crate::query_pool::QueryPoolConfig>,
+    sessions: Option<nexus_core::session::SessionTimeoutConfig>,
+    external_data: Option<nexus_core::external_data::ExternalDataConfig>,
+    plan_history: Option<nexus_core::performance::plan_history::PlanHistoryConfig>,
 }
 
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 struct YamlStorageSection {
     data_dir: Option<String>,
+    backup_dir: Option<String>,
     page_cache: YamlPageCacheSection,
+    integrity_check: Option<nexus_core::IntegrityCheckConfig>,
+    property_bloom_filters: Option<Vec<nexus_core::engine::PropertyBloomFilterSpec>>,
+    ttl_policies: Option<Vec<nexus_core::engine::TtlPolicySpec>>,
+    count_aggregates: Option<Vec<nexus_core::engine::CountAggregateSpec>>,
+    property_history: Option<Vec<nexus_core::engine::PropertyHistorySpec>>,
+    string_normalization: Option<nexus_core::text::StringNormalization>,
+    write_pipeline: Option<nexus_core::engine::WritePipelineConfig>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -856,7 +963,21 @@ impl Config {
                         addr: parsed.server.addr,
                         max_body_size_mb: parsed.server.max_body_size_mb,
                         data_dir: parsed.storage.data_dir,
+                        backup_dir: parsed.storage.backup_dir,
                         page_cache_capacity: parsed.storage.page_cache.capacity,
+                        integrity_check: parsed.storage.integrity_check,
+                        property_bloom_filters: parsed.storage.property_bloom_filters,
+                        ttl_policies: parsed.storage.ttl_policies,
+                        count_aggregates: parsed.storage.count_aggregates,
+                        property_history: parsed.storage.property_history,
+                        string_normalization: parsed.storage.string_normalization,
+                        write_pipeline: parsed.storage.write_pipeline,
+                        result_limits: parsed.server.result_limits,
+                        memory_pressure: parsed.server.memory_pressure,
+                        query_pool: parsed.server.query_pool,
+                        session_timeouts: parsed.server.sessions,
+                        external_data: parsed.server.external_data,
+                        plan_history: parsed.server.plan_history,
                     })
                 }
                 Err(e) => {
@@ -892,6 +1013,13 @@ impl Config {
             .or(yaml.data_dir)
             .unwrap_or_else(|| "./data".to_string());
 
+        // Backups are written to and attached from this directory only:
+        // NEXUS_BACKUP_DIR > yaml.storage.backup_dir > ./backups.
+        let backup_dir = std::env::var("NEXUS_BACKUP_DIR")
+            .ok()
+            .or(yaml.backup_dir)
+            .unwrap_or_else(|| "./backups".to_string());
+
         // Max body size: NEXUS_MAX_BODY_SIZE_MB > yaml.server.max_body_size_mb > 16 MiB.
         let max_body_size_bytes = std::env::var("NEXUS_MAX_BODY_SIZE_MB")
             .ok()
@@ -900,11 +1028,155 @@ impl Config {
             .map(|mb| mb * 1024 * 1024)
             .unwrap_or(16 * 1024 * 1024);
 
+        // Result limits: NEXUS_MAX_RESULT_ROWS / NEXUS_RESULT_ROWS_HARD_CAP
+        // > yaml.server.result_limits > defaults. Per-role caps are
+        // YAML-only.
+        let mut result_limits = yaml.result_limits.unwrap_or_default();
+        if let Some(rows) = std::env::var("NEXUS_MAX_RESULT_ROWS")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+        {
+            result_limits.default_max_rows = rows;
+        }
+        if let Some(cap) = std::env::var("NEXUS_RESULT_ROWS_HARD_CAP")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+        {
+            result_limits.hard_cap = cap;
+        }
+
+        // Memory pressure: NEXUS_MEMORY_LIMIT_MB / NEXUS_MEMORY_PRESSURE_ENABLED
+        // > yaml.server.memory_pressure > defaults (cgroup limit, enabled).
+        let mut memory_pressure = yaml.memory_pressure.unwrap_or_default();
+        if let Some(mb) = std::env::var("NEXUS_MEMORY_LIMIT_MB")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            memory_pressure.limit_bytes = Some(mb * 1024 * 1024);
+        }
+        if let Ok(v) = std::env::var("NEXUS_MEMORY_PRESSURE_ENABLED") {
+            memory_pressure.enabled =
+                matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1" | "yes");
+        }
+
+        // Query pool: NEXUS_QUERY_POOL_THREADS > yaml.server.query_pool
+        // > one thread per CPU.
+        let mut query_pool = yaml.query_pool.unwrap_or_default();
+        if let Some(threads) = std::env::var("NEXUS_QUERY_POOL_THREADS")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+        {
+            query_pool.threads = threads;
+        }
+
         // Engine config. Start from defaults and let YAML override.
         let mut engine = nexus_core::EngineConfig::default();
         if let Some(cap) = yaml.page_cache_capacity {
             engine.page_cache_capacity = cap;
         }
+        // Startup integrity scan: NEXUS_INTEGRITY_CHECK (off|quick|full)
+        // and NEXUS_INTEGRITY_ON_CORRUPTION (fail_fast|read_only) >
+        // yaml.storage.integrity_check > quick + fail_fast.
+        if let Some(check) = yaml.integrity_check {
+            engine.integrity_check = check;
+        }
+        if let Some(level) = std::env::var("NEXUS_INTEGRITY_CHECK")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            engine.integrity_check.level = level;
+        }
+        if let Some(policy) = std::env::var("NEXUS_INTEGRITY_ON_CORRUPTION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            engine.integrity_check.on_corruption = policy;
+        }
+        // Property bloom filters are YAML-only.
+        if let Some(filters) = yaml.property_bloom_filters {
+            engine.property_bloom_filters = filters;
+        }
+        // So are node TTL policies.
+        if let Some(policies) = yaml.ttl_policies {
+            engine.ttl_policies = policies;
+        }
+        // And count aggregates.
+        if let Some(aggregates) = yaml.count_aggregates {
+            engine.count_aggregates = aggregates;
+        }
+        // And property history policies.
+        if let Some(policies) = yaml.property_history {
+            engine.property_history = policies;
+        }
+        // Unicode normalization of incoming strings:
+        // NEXUS_STRING_NORMALIZATION (off|nfc|nfd|nfkc|nfkd) >
+        // yaml.storage.string_normalization > off.
+        if let Some(form) = yaml.string_normalization {
+            engine.string_normalization = form;
+        }
+        if let Some(form) = std::env::var("NEXUS_STRING_NORMALIZATION")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            engine.string_normalization = form;
+        }
+        // Commit path: NEXUS_COMMIT_MODE (inline|pipelined) >
+        // yaml.storage.write_pipeline > inline.
+        if let Some(pipeline) = yaml.write_pipeline {
+            engine.write_pipeline = pipeline;
+        }
+        if let Some(mode) = std::env::var("NEXUS_COMMIT_MODE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            engine.write_pipeline.commit_mode = mode;
+        }
+        // Idle limits: NEXUS_IDLE_TRANSACTION_TIMEOUT_SECS (0 disables) and
+        // NEXUS_SESSION_TIMEOUT_SECS > yaml.server.sessions > 300s / 1800s.
+        if let Some(timeouts) = yaml.session_timeouts {
+            engine.session_timeouts = timeouts;
+        }
+        if let Some(secs) = std::env::var("NEXUS_IDLE_TRANSACTION_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            engine.session_timeouts.idle_transaction_timeout_secs = secs;
+        }
+        if let Some(secs) = std::env::var("NEXUS_SESSION_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            engine.session_timeouts.session_timeout_secs = secs;
+        }
+        // Remote data for nexus.load.*: NEXUS_EXTERNAL_DATA_ALLOWED_HOSTS
+        // (comma-separated) and NEXUS_EXTERNAL_DATA_TIMEOUT_MS >
+        // yaml.server.external_data > no hosts allowed.
+        if let Some(external_data) = yaml.external_data {
+            engine.external_data = external_data;
+        }
+        if let Ok(hosts) = std::env::var("NEXUS_EXTERNAL_DATA_ALLOWED_HOSTS") {
+            engine.external_data.allowed_hosts = hosts
+                .split(',')
+                .map(str::trim)
+                .filter(|host| !host.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(ms) = std::env::var("NEXUS_EXTERNAL_DATA_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            engine.external_data.timeout_ms = ms;
+        }
+        // Plan recording for regression detection:
+        // NEXUS_PLAN_HISTORY_ENABLED > yaml.server.plan_history > off.
+        if let Some(plan_history) = yaml.plan_history {
+            engine.plan_history = plan_history;
+        }
+        if let Ok(v) = std::env::var("NEXUS_PLAN_HISTORY_ENABLED") {
+            engine.plan_history.enabled =
+                matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1" | "yes");
+        }
 
         // Try to load from config file first (will be overridden by env vars)
         let (mut root_user, mut auth) = Self::from_auth_file("config")
@@ -993,6 +1265,7 @@ impl Config {
         Self {
             addr,
             data_dir,
+            backup_dir,
             max_body_size_bytes,
             engine,
             root_user,
@@ -1034,6 +1307,9 @@ impl Config {
                  set NEXUS_ENCRYPT_AT_REST=false to start in plaintext, \
                  or fix NEXUS_DATA_KEY / NEXUS_KEY_FILE",
             ),
+            result_limits,
+            memory_pressure,
+            query_pool,
         }
     }
 
@@ -1338,11 +1614,38 @@ server:
   addr: "0.0.0.0:9999"
   max_body_size_mb: 7
   workers: 4           # unrelated field — must be ignored without error
+  sessions:
+    idle_transaction_timeout_secs: 60
+  external_data:
+    allowed_hosts: ["data.example.com", "*.s3.amazonaws.com"]
+    timeout_ms: 5000
+  plan_history:
+    enabled: true
 storage:
   data_dir: "/custom/data"
   page_cache:
     capacity: 2048
     eviction_policy: "clock"
+  integrity_check:
+    level: full
+    on_corruption: read_only
+  property_bloom_filters:
+    - label: Person
+      property: email
+  ttl_policies:
+    - label: Session
+      property: expiresAt
+  count_aggregates:
+    - name: orders_by_status
+      label: Order
+      group_by: status
+  property_history:
+    - label: Order
+      property: status
+      max_entries: 5
+  string_normalization: nfc
+  write_pipeline:
+    commit_mode: pipelined
 "#,
         )
         .unwrap();
@@ -1352,6 +1655,82 @@ storage:
         assert_eq!(overrides.max_body_size_mb, Some(7));
         assert_eq!(overrides.data_dir.as_deref(), Some("/custom/data"));
         assert_eq!(overrides.page_cache_capacity, Some(2048));
+        assert_eq!(
+            overrides.integrity_check,
+            Some(nexus_core::IntegrityCheckConfig {
+                level: nexus_core::IntegrityScanLevel::Full,
+                on_corruption: nexus_core::CorruptionPolicy::ReadOnly,
+            })
+        );
+        assert_eq!(
+            overrides.property_bloom_filters,
+            Some(vec![nexus_core::engine::PropertyBloomFilterSpec {
+                label: "Person".to_string(),
+                property: "email".to_string(),
+                expected_items: nexus_core::index::property_bloom::DEFAULT_BLOOM_EXPECTED_ITEMS,
+            }])
+        );
+        assert_eq!(
+            overrides.ttl_policies,
+            Some(vec![nexus_core::engine::TtlPolicySpec {
+                label: "Session".to_string(),
+                property: "expiresAt".to_string(),
+            }])
+        );
+        assert_eq!(
+            overrides.count_aggregates,
+            Some(vec![nexus_core::engine::CountAggregateSpec {
+                name: "orders_by_status".to_string(),
+                label: "Order".to_string(),
+                group_by: Some("status".to_string()),
+            }])
+        );
+        assert_eq!(
+            overrides.property_history,
+            Some(vec![nexus_core::engine::PropertyHistorySpec {
+                label: "Order".to_string(),
+                property: "status".to_string(),
+                max_entries: 5,
+            }])
+        );
+        assert_eq!(
+            overrides.string_normalization,
+            Some(nexus_core::text::StringNormalization::Nfc)
+        );
+        // Omitted fields keep their defaults.
+        assert_eq!(
+            overrides.write_pipeline,
+            Some(nexus_core::engine::WritePipelineConfig {
+                commit_mode: nexus_core::engine::CommitMode::Pipelined,
+                store_flush_interval_ms: nexus_core::engine::DEFAULT_STORE_FLUSH_INTERVAL_MS,
+            })
+        );
+        assert_eq!(
+            overrides.session_timeouts,
+            Some(nexus_core::session::SessionTimeoutConfig {
+                idle_transaction_timeout_secs: 60,
+                session_timeout_secs: 1800,
+            })
+        );
+        assert_eq!(
+            overrides.external_data,
+            Some(nexus_core::external_data::ExternalDataConfig {
+                allowed_hosts: vec![
+                    "data.example.com".to_string(),
+                    "*.s3.amazonaws.com".to_string()
+                ],
+                timeout_ms: 5000,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            overrides.plan_history,
+            Some(nexus_core::performance::plan_history::PlanHistoryConfig {
+                enabled: true,
+                max_templates:
+                    nexus_core::performance::plan_history::DEFAULT_PLAN_HISTORY_TEMPLATES,
+            })
+        );
     }
 
     #[test]
@@ -1374,4 +1753,95 @@ storage:
         assert_eq!(overrides.data_dir, None);
         assert_eq!(overrides.page_cache_capacity, Some(500));
     }
+
+    #[test]
+    fn test_from_yaml_file_result_limits() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("limits.yml");
+        std::fs::write(
+            &path,
+            r#"
+server:
+  result_limits:
+    default_max_rows: 500
+    role_caps:
+      analyst: 50000
+"#,
+        )
+        .unwrap();
+
+        let overrides = Config::from_yaml_file(&path).expect("yaml should parse");
+        let limits = overrides.result_limits.expect("result_limits parsed");
+        assert_eq!(limits.default_max_rows, 500);
+        // Unset fields keep their defaults.
+        assert_eq!(limits.hard_cap, ResultLimitConfig::default().hard_cap);
+        assert_eq!(limits.role_caps.get("analyst"), Some(&50_000));
+    }
+
+    #[test]
+    fn test_from_yaml_file_memory_pressure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("memory.yml");
+        std::fs::write(
+            &path,
+            r#"
+server:
+  memory_pressure:
+    limit_bytes: 4294967296
+    soft_ratio: 0.7
+"#,
+        )
+        .unwrap();
+
+        let overrides = Config::from_yaml_file(&path).expect("yaml should parse");
+        let pressure = overrides.memory_pressure.expect("memory_pressure parsed");
+        assert_eq!(pressure.limit_bytes, Some(4 * 1024 * 1024 * 1024));
+        assert_eq!(pressure.soft_ratio, 0.7);
+        assert!(pressure.enabled);
+        assert_eq!(pressure.hard_ratio, 0.92);
+    }
+
+    #[test]
+    fn test_from_yaml_file_query_pool() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("query_pool.yml");
+        std::fs::write(
+            &path,
+            r#"
+server:
+  query_pool:
+    threads: 3
+"#,
+        )
+        .unwrap();
+
+        let overrides = Config::from_yaml_file(&path).expect("yaml should parse");
+        assert_eq!(
+            overrides.query_pool,
+            Some(crate::query_pool::QueryPoolConfig { threads: 3 })
+        );
+    }
+
+    #[test]
+    fn test_result_limit_resolution() {
+        let mut limits = ResultLimitConfig {
+            default_max_rows: 100,
+            hard_cap: 1_000,
+            ..ResultLimitConfig::default()
+        };
+        limits.role_caps.insert("analyst".to_string(), 5_000);
+        limits.role_caps.insert("guest".to_string(), 10);
+
+        let none: Vec<String> = Vec::new();
+        assert_eq!(limits.cap_for_roles(&none), 1_000);
+        let roles = vec!["guest".to_string(), "analyst".to_string()];
+        assert_eq!(limits.cap_for_roles(&roles), 5_000);
+
+        // No override: server default, still bounded by a smaller cap.
+        assert_eq!(limits.effective_limit(None, 1_000), 100);
+        assert_eq!(limits.effective_limit(None, 10), 10);
+        // Overrides are clamped to the caller's cap.
+        assert_eq!(limits.effective_limit(Some(2_000), 1_000), 1_000);
+        assert_eq!(limits.effective_limit(Some(2_000), 5_000), 2_000);
+    }
 }
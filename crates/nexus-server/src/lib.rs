@@ -4,6 +4,7 @@
 //! - POST /cypher - Execute Cypher queries
 //! - POST /knn_traverse - KNN-seeded graph traversal
 //! - POST /ingest - Bulk data ingestion
+//! - GET /ingest/jobs/{id} - Ingest job status (also /resume, /rollback)
 //! - POST /schema/labels - Create labels
 //! - GET /schema/labels - List labels
 //! - POST /schema/rel_types - Create relationship types
@@ -26,6 +27,7 @@ pub mod config;
 pub mod hub;
 pub mod middleware;
 pub mod protocol;
+pub mod query_pool;
 
 use config::RootUserConfig;
 
@@ -127,6 +129,20 @@ pub struct NexusServer {
     /// wedge the engine.
     pub admission: Arc<crate::middleware::AdmissionQueue>,
 
+    /// Threads that run Cypher execution for `/cypher` and the RPC /
+    /// RESP3 `CYPHER` commands, sized apart from the tokio runtime so
+    /// heavy queries queue here instead of starving request handling.
+    /// `main.rs` installs the configured size via
+    /// [`NexusServer::set_query_pool_config`].
+    pub query_pool: Arc<crate::query_pool::QueryPool>,
+
+    /// Memory usage against the configured or cgroup limit. Sampled by
+    /// the job from [`NexusServer::start_memory_pressure_job`], which
+    /// shrinks the engine caches and feeds the level to `admission`.
+    /// Inert (no limit) until `main.rs` installs the boot config via
+    /// [`NexusServer::set_memory_pressure_config`].
+    pub memory_pressure: Arc<nexus_core::memory_management::MemoryPressureMonitor>,
+
     /// Encryption-at-rest configuration as resolved at boot —
     /// provider source + key fingerprint, never the key bytes
     /// themselves. Surfaced via the `/admin/encryption/status`
@@ -134,6 +150,29 @@ pub struct NexusServer {
     /// the same master key without leaking it. Standalone
     /// deployments leave this at the default (`enabled = false`).
     pub encryption_config: crate::config::EncryptionConfig,
+
+    /// Row limits applied to `/cypher` responses — see
+    /// [`crate::config::ResultLimitConfig`]. `main.rs` installs the
+    /// boot-resolved values via [`NexusServer::set_result_limits`].
+    pub result_limits: crate::config::ResultLimitConfig,
+
+    /// Jobs created by `POST /ingest`, kept so callers can inspect,
+    /// resume or roll back a partially-applied import through
+    /// `/ingest/jobs/{id}`.
+    pub ingest_jobs: Arc<crate::api::ingest_jobs::IngestJobRegistry>,
+
+    /// Background operations started through `POST /operations`
+    /// (index population, backups, imports) and their progress.
+    pub operations: Arc<crate::api::operation_registry::OperationRegistry>,
+
+    /// Directory the default engine stores its files in; the source of
+    /// `/operations` backups. `main.rs` installs the configured path via
+    /// [`NexusServer::set_data_dir`].
+    pub data_dir: std::path::PathBuf,
+
+    /// Directory `/operations` backups are written under. `main.rs`
+    /// installs the configured path via [`NexusServer::set_backup_dir`].
+    pub backup_dir: std::path::PathBuf,
 }
 
 impl NexusServer {
@@ -332,10 +371,22 @@ impl NexusServer {
             admission: Arc::new(crate::middleware::AdmissionQueue::new(
                 crate::middleware::AdmissionConfig::from_env(),
             )),
+            query_pool: Arc::new(crate::query_pool::QueryPool::with_defaults()),
             // Default-disabled — main.rs overrides via
             // `set_encryption_config` after parsing the runtime
             // Config. Tests can leave this at the default.
             encryption_config: crate::config::EncryptionConfig::default(),
+            memory_pressure: Arc::new(
+                nexus_core::memory_management::MemoryPressureMonitor::with_limit(
+                    Default::default(),
+                    None,
+                ),
+            ),
+            result_limits: crate::config::ResultLimitConfig::default(),
+            ingest_jobs: Arc::new(crate::api::ingest_jobs::IngestJobRegistry::default()),
+            operations: Arc::new(crate::api::operation_registry::OperationRegistry::default()),
+            data_dir: std::path::PathBuf::from("./data"),
+            backup_dir: std::path::PathBuf::from("./backups"),
         }
     }
 
@@ -348,6 +399,44 @@ impl NexusServer {
         self.encryption_config = cfg;
     }
 
+    /// Install the `/cypher` result limits resolved at boot. Called
+    /// from `main.rs` after `Config::from_env`.
+    pub fn set_result_limits(&mut self, limits: crate::config::ResultLimitConfig) {
+        self.result_limits = limits;
+    }
+
+    /// Install the memory-pressure configuration resolved at boot,
+    /// resolving the cgroup limit when none is configured. Called from
+    /// `main.rs` after `Config::from_env`.
+    pub fn set_memory_pressure_config(
+        &mut self,
+        config: nexus_core::memory_management::MemoryPressureConfig,
+    ) {
+        self.admission
+            .set_large_request_bytes(config.large_request_bytes);
+        self.memory_pressure = Arc::new(nexus_core::memory_management::MemoryPressureMonitor::new(
+            config,
+        ));
+    }
+
+    /// Replace the query pool with one sized by `config`. Called from
+    /// `main.rs` after `Config::from_env`, before any query runs.
+    pub fn set_query_pool_config(&mut self, config: crate::query_pool::QueryPoolConfig) {
+        self.query_pool = Arc::new(crate::query_pool::QueryPool::new(config));
+    }
+
+    /// Install the data directory resolved at boot. Called from
+    /// `main.rs` after `Config::from_env`.
+    pub fn set_data_dir(&mut self, data_dir: std::path::PathBuf) {
+        self.data_dir = data_dir;
+    }
+
+    /// Install the backup directory resolved at boot. Called from
+    /// `main.rs` after `Config::from_env`.
+    pub fn set_backup_dir(&mut self, backup_dir: std::path::PathBuf) {
+        self.backup_dir = backup_dir;
+    }
+
     /// Install (or clear) the V2 cluster controller. Called from the
     /// server bootstrap once sharding has started. Idempotent —
     /// passing `None` clears the controller.
@@ -449,6 +538,170 @@ impl NexusServer {
             }
         });
     }
+
+    /// Start the idle session reaper. Every `interval` it rolls back
+    /// explicit transactions and closes sessions past their idle limits
+    /// (see `nexus_core::session::SessionTimeoutConfig`) and writes one
+    /// audit entry per reaped session.
+    pub fn start_session_reaper_job(
+        engine: Arc<TokioRwLock<nexus_core::Engine>>,
+        audit_logger: Arc<nexus_core::auth::AuditLogger>,
+        interval: std::time::Duration,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // Skip the immediate first tick.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+
+                // Cheap read-side check first so an idle server never
+                // queues behind writers for the exclusive engine lock.
+                let due = !engine
+                    .read()
+                    .await
+                    .session_manager
+                    .sessions_due_for_reaping()
+                    .is_empty();
+                if !due {
+                    continue;
+                }
+                let reaped = match engine.write().await.reap_idle_sessions() {
+                    Ok(reaped) => reaped,
+                    Err(e) => {
+                        tracing::warn!("Failed to reap idle sessions: {}", e);
+                        continue;
+                    }
+                };
+                for entry in &reaped {
+                    if let Err(e) = audit_logger.log_session_reaped(entry).await {
+                        tracing::warn!("Failed to audit reaped session: {}", e);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Start the node TTL sweeper. Every `interval` it detach-deletes up
+    /// to `batch` nodes whose TTL expiry has passed (see
+    /// `nexus_core::engine::ttl`); the lag and backlog it leaves are
+    /// exported as Prometheus metrics.
+    pub fn start_ttl_sweeper_job(
+        engine: Arc<TokioRwLock<nexus_core::Engine>>,
+        interval: std::time::Duration,
+        batch: usize,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+
+                let now_ms = nexus_core::engine::ttl::now_millis();
+                // Same read-side check as the session reaper: only take
+                // the write lock when something is actually overdue.
+                let due = {
+                    let engine = engine.read().await;
+                    engine.indexes.ttl.has_any_policy()
+                        && !engine.indexes.ttl.expired(now_ms, 1).is_empty()
+                };
+                if !due {
+                    continue;
+                }
+                match engine.write().await.sweep_expired_nodes(now_ms, batch) {
+                    Ok(report) if report.remaining > 0 => tracing::debug!(
+                        "TTL sweep deleted {} nodes, {} still expired",
+                        report.deleted,
+                        report.remaining
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("TTL sweep failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Start the materialized-view refresher. Every `interval` it
+    /// refreshes the views that are due under their `REFRESH` policy
+    /// (see `nexus_core::engine::views`).
+    pub fn start_view_refresh_job(
+        engine: Arc<TokioRwLock<nexus_core::Engine>>,
+        interval: std::time::Duration,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+
+                if !engine.read().await.has_due_views() {
+                    continue;
+                }
+                let refreshed = engine.write().await.refresh_due_views();
+                if !refreshed.is_empty() {
+                    tracing::debug!("Refreshed materialized views: {}", refreshed.join(", "));
+                }
+            }
+        });
+    }
+
+    /// Start the memory-pressure job. Every `check_interval_ms` it
+    /// samples the process RSS, hands the level to the admission queue
+    /// and, while there is pressure (or the page cache has not grown
+    /// back yet), shrinks or regrows the engine caches (see
+    /// `nexus_core::engine::memory_pressure`). Does nothing when the
+    /// monitor has no limit to measure against.
+    pub fn start_memory_pressure_job(
+        engine: Arc<TokioRwLock<nexus_core::Engine>>,
+        admission: Arc<crate::middleware::AdmissionQueue>,
+        monitor: Arc<nexus_core::memory_management::MemoryPressureMonitor>,
+    ) {
+        use nexus_core::memory_management::MemoryPressureLevel;
+
+        if monitor.limit_bytes().is_none() {
+            return;
+        }
+        let interval = std::time::Duration::from_millis(monitor.config().check_interval_ms.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut previous = MemoryPressureLevel::Normal;
+            loop {
+                ticker.tick().await;
+
+                let level = monitor.sample();
+                admission.set_memory_pressure(level);
+                if level != previous {
+                    let status = monitor.status();
+                    tracing::warn!(
+                        usage_bytes = status.usage_bytes,
+                        limit_bytes = status.limit_bytes,
+                        "memory pressure {:?} -> {:?}",
+                        previous,
+                        level
+                    );
+                    previous = level;
+                }
+                if level == MemoryPressureLevel::Normal && !engine.read().await.page_cache_shrunk()
+                {
+                    continue;
+                }
+                match engine.write().await.relieve_memory_pressure(level) {
+                    Ok(relief) if relief.pages_evicted > 0 => tracing::debug!(
+                        "Memory pressure {:?}: page cache down to {} pages ({} evicted)",
+                        relief.level,
+                        relief.page_cache_capacity,
+                        relief.pages_evicted
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Failed to relieve memory pressure: {}", e),
+                }
+            }
+        });
+    }
 }
 
 /// Build the two default comparison graphs the `/comparison/*` handlers
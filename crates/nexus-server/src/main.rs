@@ -4,10 +4,16 @@
 //! - POST /cypher - Execute Cypher queries
 //! - POST /knn_traverse - KNN-seeded graph traversal
 //! - POST /ingest - Bulk data ingestion
+//! - GET /ingest/jobs/{id} - Ingest job status (also /resume, /rollback)
+//! - GET/POST /operations - List / start background operations
+//! - GET /operations/{id} - Operation progress (also POST /cancel)
 //! - POST /schema/labels - Create labels
 //! - GET /schema/labels - List labels
 //! - POST /schema/rel_types - Create relationship types
 //! - GET /schema/rel_types - List relationship types
+//! - GET/POST /schema/vector_indexes - List / create named vector indexes
+//! - GET/POST/DELETE /schema/vector_indexes/{name}/migration - Vector index migrations
+//! - POST /vectors/bulk - Streamed bulk embedding upsert
 //! - POST /data/nodes - Create nodes
 //! - POST /data/relationships - Create relationships
 //! - PUT /data/nodes - Update nodes
@@ -249,7 +255,9 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
     // init_* pair used.
 
     // Initialize DatabaseManager for multi-database support
-    let database_manager = nexus_core::database::DatabaseManager::new(data_dir.clone().into())?;
+    std::fs::create_dir_all(&config.backup_dir)?;
+    let mut database_manager = nexus_core::database::DatabaseManager::new(data_dir.clone().into())?;
+    database_manager.set_backups_root(std::path::PathBuf::from(&config.backup_dir));
     let database_manager_arc = Arc::new(RwLock::new(database_manager));
 
     // Wire the DatabaseManager into the executor so multi-database
@@ -362,6 +370,11 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
         std::path::Path::new(&data_dir),
     )?;
     nexus_server_owned.set_encryption_config(encryption_cfg.clone());
+    nexus_server_owned.set_result_limits(config.result_limits.clone());
+    nexus_server_owned.set_data_dir(std::path::PathBuf::from(&data_dir));
+    nexus_server_owned.set_backup_dir(std::path::PathBuf::from(&config.backup_dir));
+    nexus_server_owned.set_memory_pressure_config(config.memory_pressure.clone());
+    nexus_server_owned.set_query_pool_config(config.query_pool);
     if encryption_cfg.enabled {
         if let Some(fp) = encryption_cfg.fingerprint.as_deref() {
             info!(
@@ -379,6 +392,62 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
     }
     let nexus_server = Arc::new(nexus_server_owned);
 
+    // Roll back idle explicit transactions and close abandoned sessions.
+    let reap_interval = config.engine.session_timeouts.reap_interval();
+    NexusServer::start_session_reaper_job(
+        nexus_server.engine.clone(),
+        audit_logger.clone(),
+        reap_interval,
+    );
+    info!(
+        "Started idle session reaper (every {:?}, idle_transaction_timeout={}s, session_timeout={}s)",
+        reap_interval,
+        config.engine.session_timeouts.idle_transaction_timeout_secs,
+        config.engine.session_timeouts.session_timeout_secs
+    );
+
+    // Delete nodes whose TTL expiry has passed.
+    let ttl_sweep_interval = std::time::Duration::from_millis(
+        std::env::var("NEXUS_TTL_SWEEP_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1_000),
+    );
+    let ttl_sweep_batch = std::env::var("NEXUS_TTL_SWEEP_BATCH")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1_000);
+    NexusServer::start_ttl_sweeper_job(
+        nexus_server.engine.clone(),
+        ttl_sweep_interval,
+        ttl_sweep_batch,
+    );
+
+    // Refresh materialized views that are due under their REFRESH policy.
+    let view_refresh_interval = std::time::Duration::from_millis(
+        std::env::var("NEXUS_VIEW_REFRESH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1_000),
+    );
+    NexusServer::start_view_refresh_job(nexus_server.engine.clone(), view_refresh_interval);
+
+    // Shrink caches and queue large requests as memory nears its limit.
+    NexusServer::start_memory_pressure_job(
+        nexus_server.engine.clone(),
+        nexus_server.admission.clone(),
+        nexus_server.memory_pressure.clone(),
+    );
+    match nexus_server.memory_pressure.limit_bytes() {
+        Some(limit) => info!(
+            "Memory pressure handling enabled (limit {} MiB, soft {:.0}%, hard {:.0}%)",
+            limit / (1024 * 1024),
+            config.memory_pressure.soft_ratio * 100.0,
+            config.memory_pressure.hard_ratio * 100.0
+        ),
+        None => info!("Memory pressure handling inactive: no memory limit configured or detected"),
+    }
+
     // Start expired API keys cleanup job (runs every hour)
     // Only start if authentication is enabled
     if auth_manager.config().enabled {
@@ -517,6 +586,7 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
             Json(serde_json::json!({"message": "Debug endpoint received", "body": body}))
         }))
         .route("/cypher", post(api::cypher::execute_cypher))
+        .route("/cypher/diff", post(api::cypher::diff_cypher))
         // Encryption-at-rest status: read-only, reports the
         // boot-time KeyProvider source + master-key fingerprint.
         // Storage-layer wiring lands in follow-up tasks
@@ -533,6 +603,33 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
             "/admin/queries",
             get(api::admin_queries::list_queries),
         )
+        // Cache, WAL and transaction controls behind
+        // `nexus admin cache|wal|tx`.
+        .route("/admin/cache", get(api::admin_engine::cache_stats))
+        .route("/admin/cache/flush", post(api::admin_engine::flush_cache))
+        .route("/admin/wal", get(api::admin_engine::wal_info))
+        .route(
+            "/admin/wal/checkpoint",
+            post(api::admin_engine::wal_checkpoint),
+        )
+        .route(
+            "/admin/transactions",
+            get(api::admin_engine::list_transactions),
+        )
+        .route(
+            "/admin/transactions/{id}",
+            delete(api::admin_engine::kill_transaction),
+        )
+        // Plan regression detection behind `nexus admin plans`.
+        .route("/admin/plans", get(api::admin_plans::list_plans))
+        .route(
+            "/admin/plans/baseline",
+            post(api::admin_plans::capture_baseline),
+        )
+        .route(
+            "/admin/plans/regressions",
+            get(api::admin_plans::regressions),
+        )
         .route("/test-handler", get(|| async {
             tracing::debug!("Handler called!");
             "Handler called successfully"
@@ -618,6 +715,24 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
                 },
             ),
         )
+        .route("/ingest/jobs/{id}", get(api::ingest::get_ingest_job))
+        .route(
+            "/ingest/jobs/{id}/resume",
+            post(api::ingest::resume_ingest_job),
+        )
+        .route(
+            "/ingest/jobs/{id}/rollback",
+            post(api::ingest::rollback_ingest_job),
+        )
+        .route(
+            "/operations",
+            get(api::operations::list_operations).post(api::operations::start_operation),
+        )
+        .route("/operations/{id}", get(api::operations::get_operation))
+        .route(
+            "/operations/{id}/cancel",
+            post(api::operations::cancel_operation),
+        )
         .route(
             "/export",
             get(
@@ -626,11 +741,22 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
                 },
             ),
         )
+        .route("/export/csr", get(api::export::export_csr))
         // Schema management endpoints
         .route("/schema/labels", post(api::schema::create_label))
         .route("/schema/labels", get(api::schema::list_labels))
         .route("/schema/rel_types", post(api::schema::create_rel_type))
         .route("/schema/rel_types", get(api::schema::list_rel_types))
+        .route("/schema/changelog", get(api::schema::schema_log))
+        .route("/schema/changelog/rollback", get(api::schema::schema_rollback))
+        .route(
+            "/schema/embedding_models",
+            get(api::schema::list_embedding_models).post(api::schema::set_embedding_model),
+        )
+        .route(
+            "/schema/embedding_models/{label}/{property}",
+            delete(api::schema::drop_embedding_model),
+        )
         .route("/schema/indexes", get({
             let server = nexus_server.clone();
             move || {
@@ -658,6 +784,47 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
                 api::indexes::delete_index(axum::extract::State(state), path)
             }
         }))
+        // Named vector (KNN) index endpoints
+        .route(
+            "/schema/vector_indexes",
+            get(api::vector_indexes::list_vector_indexes)
+                .post(api::vector_indexes::create_vector_index),
+        )
+        .route(
+            "/schema/vector_indexes/{name}",
+            get(api::vector_indexes::get_vector_index)
+                .delete(api::vector_indexes::drop_vector_index),
+        )
+        .route(
+            "/schema/vector_indexes/{name}/vectors",
+            post(api::vector_indexes::upsert_vectors),
+        )
+        .route(
+            "/schema/vector_indexes/{name}/vectors/{node_id}",
+            delete(api::vector_indexes::delete_vector),
+        )
+        .route(
+            "/schema/vector_indexes/{name}/search",
+            post(api::vector_indexes::search_vector_index),
+        )
+        .route(
+            "/schema/vector_indexes/{name}/migration",
+            get(api::vector_indexes::get_vector_migration)
+                .post(api::vector_indexes::start_vector_migration)
+                .delete(api::vector_indexes::abort_vector_migration),
+        )
+        .route(
+            "/schema/vector_indexes/{name}/migration/vectors",
+            post(api::vector_indexes::upsert_migration_vectors),
+        )
+        .route(
+            "/schema/vector_indexes/{name}/migration/complete",
+            post(api::vector_indexes::complete_vector_migration),
+        )
+        .route(
+            "/vectors/bulk",
+            post(api::vector_indexes::bulk_upsert_vectors),
+        )
         // Property keys endpoint
         .route("/property_keys", get({
             let server = nexus_server.clone();
@@ -730,6 +897,11 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
                             "signature": "db.relationshipTypes() :: (relationshipType :: STRING)",
                             "description": "Stream every registered relationship type."
                         },
+                        {
+                            "name": "db.propertyKeys",
+                            "signature": "db.propertyKeys() :: (propertyKey :: STRING)",
+                            "description": "Stream every registered property key."
+                        },
                         {
                             "name": "db.indexes",
                             "signature": "db.indexes() :: (name :: STRING, type :: STRING, label :: STRING, properties :: LIST<STRING>, state :: STRING)",
@@ -748,11 +920,18 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
             "/data/nodes/by-external-id",
             get(api::data::get_node_by_external_id),
         )
+        .route("/data/nodes/diff", get(api::data::diff_nodes))
+        .route("/data/nodes/search", get(api::data::search_nodes))
+        .route(
+            "/data/nodes/{id}/relationships",
+            get(api::data::get_node_relationships),
+        )
         .route("/data/nodes", put(api::data::update_node))
         .route("/data/nodes", delete(api::data::delete_node))
         .route("/data/relationships", post(api::data::create_rel))
         // Statistics endpoint
         .route("/stats", get(api::stats::get_stats))
+        .route("/stats/storage", get(api::stats::get_storage_stats))
         // Cluster-mode per-tenant stats. Returns 404
         // CLUSTER_MODE_DISABLED on standalone deployments, 404
         // TENANT_UNKNOWN for tenants that haven't been seen yet,
@@ -785,6 +964,18 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
                 }
             }),
         )
+        .route(
+            "/databases/attach",
+            post({
+                let server = nexus_server.clone();
+                move |request| {
+                    let manager = server.database_manager.clone();
+                    async move {
+                        api::database::attach_database(axum::extract::State(api::database::DatabaseState { manager }), request).await
+                    }
+                }
+            }),
+        )
         .route(
             "/databases/{name}",
             get({
@@ -858,6 +1049,10 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
             "/performance/plan-cache/clear",
             post(api::performance::clear_plan_cache),
         )
+        .route(
+            "/performance/page-cache",
+            get(api::performance::get_page_cache_report),
+        )
         // MCP tool performance monitoring endpoints
         .route(
             "/mcp/performance/statistics",
@@ -921,6 +1116,18 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
                 }
             }),
         )
+        // Versioned analysis artifacts
+        .route("/artifacts", get(api::artifacts::list_artifacts))
+        .route(
+            "/artifacts/{name}",
+            get(api::artifacts::list_artifact_versions)
+                .post(api::artifacts::save_artifact)
+                .delete(api::artifacts::delete_artifact),
+        )
+        .route(
+            "/artifacts/{name}/{version}",
+            get(api::artifacts::get_artifact).delete(api::artifacts::delete_artifact_version),
+        )
         // Graph correlation endpoints
         .route(
             "/graph-correlation/generate",
@@ -969,7 +1176,7 @@ async fn async_main(_worker_threads: usize) -> anyhow::Result<()> {
     // single client's burst can't wedge the process. Light-weight
     // endpoints (/health, /prometheus, /auth, …) bypass the queue via
     // `is_heavy_path`; only /cypher, /ingest, /knn_traverse, /graphql,
-    // /umicp actually acquire a permit. Configurable via
+    // /umicp, /vectors actually acquire a permit. Configurable via
     // NEXUS_ADMISSION_* env vars.
     app = app.layer(axum_middleware::from_fn_with_state(
         nexus_server.admission.clone(),
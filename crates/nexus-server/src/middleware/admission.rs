@@ -8,6 +8,17 @@
 //! rejected with [`AdmissionError::Overloaded`], which the HTTP
 //! layer maps to `503 Service Unavailable + Retry-After`.
 //!
+//! # Memory pressure
+//!
+//! The server's memory-pressure job reports the current
+//! [`MemoryPressureLevel`] through
+//! [`AdmissionQueue::set_memory_pressure`]. Under soft pressure,
+//! requests whose body is at least `large_request_bytes` long go
+//! through a single-slot gate before taking a regular permit, so at
+//! most one large request runs at a time; under hard pressure every
+//! gated request does. Waiting at the gate counts against the same
+//! `queue_timeout`.
+//!
 //! # Why a new layer on top of rate limiting?
 //!
 //! [`crate::middleware::RateLimiter`] gates requests on per-API-key
@@ -29,6 +40,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
+use nexus_core::memory_management::MemoryPressureLevel;
 use thiserror::Error;
 use tokio::sync::Semaphore;
 use tokio::time::{Instant, timeout};
@@ -140,6 +152,12 @@ pub struct AdmissionQueue {
     /// can expose `count` / `sum` without allocating a bucket
     /// array per-request here.
     wait_micros_total: AtomicU64,
+    /// Single-slot gate that large requests (all requests under hard
+    /// pressure) pass through while memory is under pressure.
+    pressure_gate: Arc<Semaphore>,
+    memory_pressure: parking_lot::RwLock<MemoryPressureLevel>,
+    large_request_bytes: AtomicU64,
+    pressure_queued: AtomicU64,
 }
 
 impl std::fmt::Debug for AdmissionQueue {
@@ -151,6 +169,7 @@ impl std::fmt::Debug for AdmissionQueue {
             .field("in_flight", &self.in_flight.load(Ordering::Relaxed))
             .field("granted", &self.granted.load(Ordering::Relaxed))
             .field("rejected", &self.rejected.load(Ordering::Relaxed))
+            .field("memory_pressure", &*self.memory_pressure.read())
             .finish()
     }
 }
@@ -174,6 +193,12 @@ impl AdmissionQueue {
             rejected: AtomicU64::new(0),
             in_flight: AtomicU64::new(0),
             wait_micros_total: AtomicU64::new(0),
+            pressure_gate: Arc::new(Semaphore::new(1)),
+            memory_pressure: parking_lot::RwLock::new(MemoryPressureLevel::Normal),
+            large_request_bytes: AtomicU64::new(
+                nexus_core::memory_management::MemoryPressureConfig::default().large_request_bytes,
+            ),
+            pressure_queued: AtomicU64::new(0),
         }
     }
 
@@ -190,19 +215,76 @@ impl AdmissionQueue {
         self.cfg
     }
 
+    /// Record the memory pressure level reported by the server's
+    /// memory-pressure job.
+    pub fn set_memory_pressure(&self, level: MemoryPressureLevel) {
+        *self.memory_pressure.write() = level;
+    }
+
+    /// Body size from which a request counts as large under soft
+    /// memory pressure.
+    pub fn set_large_request_bytes(&self, bytes: u64) {
+        self.large_request_bytes.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Memory pressure level last reported.
+    #[must_use]
+    pub fn memory_pressure(&self) -> MemoryPressureLevel {
+        *self.memory_pressure.read()
+    }
+
+    /// Whether a request with a body of `body_bytes` must pass the
+    /// memory-pressure gate.
+    fn gated_by_memory_pressure(&self, body_bytes: Option<u64>) -> bool {
+        match self.memory_pressure() {
+            MemoryPressureLevel::Normal => false,
+            MemoryPressureLevel::Soft => body_bytes
+                .is_some_and(|bytes| bytes >= self.large_request_bytes.load(Ordering::Relaxed)),
+            MemoryPressureLevel::Hard => true,
+        }
+    }
+
     /// Ask for a permit. Blocks up to [`AdmissionConfig::queue_timeout`];
     /// rejects with [`AdmissionError::Overloaded`] afterwards.
     pub async fn acquire(self: &Arc<Self>) -> Result<AdmissionPermit, AdmissionError> {
+        self.acquire_sized(None).await
+    }
+
+    /// [`Self::acquire`] for a request whose body is `body_bytes` long
+    /// (when known), queueing it behind the memory-pressure gate first
+    /// if memory is under pressure and the request counts as large.
+    pub async fn acquire_sized(
+        self: &Arc<Self>,
+        body_bytes: Option<u64>,
+    ) -> Result<AdmissionPermit, AdmissionError> {
+        let start = Instant::now();
+        let pressure = if self.gated_by_memory_pressure(body_bytes) {
+            self.pressure_queued.fetch_add(1, Ordering::Relaxed);
+            let gate_fut = self.pressure_gate.clone().acquire_owned();
+            match timeout(self.cfg.queue_timeout, gate_fut).await {
+                Ok(Ok(p)) => Some(p),
+                _ => {
+                    self.rejected.fetch_add(1, Ordering::Relaxed);
+                    return Err(AdmissionError::Overloaded {
+                        waited_ms: start.elapsed().as_millis() as u64,
+                        timeout_ms: self.cfg.queue_timeout.as_millis() as u64,
+                    });
+                }
+            }
+        } else {
+            None
+        };
         if !self.cfg.enabled {
             // Disabled — no-op permit.
             return Ok(AdmissionPermit {
                 _inner: None,
+                _pressure: pressure,
                 queue: self.clone(),
             });
         }
-        let start = Instant::now();
+        let remaining = self.cfg.queue_timeout.saturating_sub(start.elapsed());
         let acquire_fut = self.sem.clone().acquire_owned();
-        let permit = match timeout(self.cfg.queue_timeout, acquire_fut).await {
+        let permit = match timeout(remaining, acquire_fut).await {
             Ok(Ok(p)) => p,
             Ok(Err(_)) => {
                 // Semaphore closed — treat as overload to avoid
@@ -228,6 +310,7 @@ impl AdmissionQueue {
             .fetch_add(start.elapsed().as_micros() as u64, Ordering::Relaxed);
         Ok(AdmissionPermit {
             _inner: Some(permit),
+            _pressure: pressure,
             queue: self.clone(),
         })
     }
@@ -243,6 +326,8 @@ impl AdmissionQueue {
             configured_max_concurrent: self.cfg.max_concurrent,
             configured_queue_timeout_ms: self.cfg.queue_timeout.as_millis() as u64,
             enabled: self.cfg.enabled,
+            pressure_queued_total: self.pressure_queued.load(Ordering::Relaxed),
+            memory_pressure: self.memory_pressure(),
         }
     }
 }
@@ -257,12 +342,17 @@ pub struct AdmissionMetrics {
     pub configured_max_concurrent: u32,
     pub configured_queue_timeout_ms: u64,
     pub enabled: bool,
+    /// Requests sent through the memory-pressure gate
+    pub pressure_queued_total: u64,
+    /// Memory pressure level last reported
+    pub memory_pressure: MemoryPressureLevel,
 }
 
 /// RAII permit. Dropping it releases the semaphore slot and
 /// decrements the in-flight gauge.
 pub struct AdmissionPermit {
     _inner: Option<tokio::sync::OwnedSemaphorePermit>,
+    _pressure: Option<tokio::sync::OwnedSemaphorePermit>,
     queue: Arc<AdmissionQueue>,
 }
 
@@ -287,8 +377,14 @@ impl Drop for AdmissionPermit {
 /// reads) bypass the queue so a saturated engine doesn't starve
 /// diagnostics. The list is a prefix match — every route that
 /// drives the Cypher executor or a bulk-ingest loop belongs here.
-pub const HEAVY_PATH_PREFIXES: &[&str] =
-    &["/cypher", "/ingest", "/knn_traverse", "/graphql", "/umicp"];
+pub const HEAVY_PATH_PREFIXES: &[&str] = &[
+    "/cypher",
+    "/ingest",
+    "/knn_traverse",
+    "/graphql",
+    "/umicp",
+    "/vectors",
+];
 
 /// True iff `path` is one of the gated prefixes.
 #[must_use]
@@ -312,7 +408,12 @@ pub async fn admission_middleware_handler(
     if !is_heavy_path(request.uri().path()) {
         return next.run(request).await;
     }
-    match queue.acquire().await {
+    let body_bytes = request
+        .headers()
+        .get(axum::http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    match queue.acquire_sized(body_bytes).await {
         Ok(_permit) => next.run(request).await,
         Err(e) => admission_overloaded_response(&e),
     }
@@ -363,6 +464,35 @@ mod tests {
         assert_eq!(q.metrics().in_flight, 0);
     }
 
+    #[tokio::test]
+    async fn memory_pressure_queues_large_requests_one_at_a_time() {
+        let q = queue(4, Duration::from_millis(30));
+        q.set_large_request_bytes(1_000);
+
+        // No pressure: large requests run side by side.
+        let a = q.acquire_sized(Some(5_000)).await.unwrap();
+        let b = q.acquire_sized(Some(5_000)).await.unwrap();
+        drop((a, b));
+
+        q.set_memory_pressure(MemoryPressureLevel::Soft);
+        let large = q.acquire_sized(Some(5_000)).await.unwrap();
+        assert!(q.acquire_sized(Some(5_000)).await.is_err());
+        // Small and unsized requests are not gated under soft pressure.
+        let small = q.acquire_sized(Some(10)).await.unwrap();
+        let unsized_request = q.acquire().await.unwrap();
+        drop((small, unsized_request));
+
+        q.set_memory_pressure(MemoryPressureLevel::Hard);
+        assert!(q.acquire().await.is_err());
+        drop(large);
+        let _only = q.acquire().await.unwrap();
+
+        let m = q.metrics();
+        assert_eq!(m.memory_pressure, MemoryPressureLevel::Hard);
+        assert_eq!(m.pressure_queued_total, 4);
+        assert_eq!(m.rejected_total, 2);
+    }
+
     #[tokio::test]
     async fn rejects_over_capacity_after_timeout() {
         let q = queue(1, Duration::from_millis(30));
@@ -495,6 +625,7 @@ mod tests {
         assert!(is_heavy_path("/ingest"));
         assert!(is_heavy_path("/knn_traverse"));
         assert!(is_heavy_path("/graphql"));
+        assert!(is_heavy_path("/vectors/bulk"));
         assert!(!is_heavy_path("/health"));
         assert!(!is_heavy_path("/prometheus"));
         assert!(!is_heavy_path("/auth/users"));
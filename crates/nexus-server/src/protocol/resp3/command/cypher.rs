@@ -4,18 +4,23 @@
 //! — the sync core of the Cypher stack. The wrapper does three things:
 //!
 //! 1. Pulls the engine out of `Arc<TokioRwLock<Engine>>` and runs the
-//!    actual query inside `spawn_blocking` so the tokio reactor thread
+//!    actual query on the server's query pool
+//!    ([`crate::query_pool::QueryPool`]) so the tokio reactor thread
 //!    that's driving this socket is never pinned on a parking_lot guard
 //!    (the same policy the HTTP handlers follow — see
 //!    `docs/performance/CONCURRENCY.md`).
 //! 2. Converts the `ResultSet` to a RESP3 `Map` envelope with `columns`,
-//!    `rows`, `stats`, and `execution_time_ms`.
+//!    `rows`, `stats`, and `execution_time_ms`, keeping at most the
+//!    server's default row limit (see [`crate::api::cypher::row_limit`];
+//!    the query runs under it). A cut result adds
+//!    `truncated: {row_limit}`.
 //! 3. Maps runtime errors to `Verbatim(txt, …)` so `redis-cli` renders
 //!    multi-line Cypher diagnostics with the right line-feeds.
 
 use std::collections::HashMap;
 use std::time::Instant;
 
+use crate::api::cypher::row_limit::{cut_rows, execution_limit, resolve_row_limit};
 use crate::protocol::resp3::parser::Resp3Value;
 
 use super::{
@@ -97,23 +102,33 @@ async fn run_cypher(
         }
         None => HashMap::new(),
     };
+    let row_limit = resolve_row_limit(&state.server, None, None).await;
     let engine = state.server.engine.clone();
     let started = Instant::now();
-    let out = tokio::task::spawn_blocking(move || {
-        let mut guard = engine.blocking_write();
-        guard.execute_cypher_with_params(&query, params_map)
-    })
-    .await;
+    let out = state
+        .server
+        .query_pool
+        .run(move || {
+            let mut guard = engine.blocking_write();
+            let _rows = execution_limit(row_limit);
+            guard.execute_cypher_with_params(&query, params_map)
+        })
+        .await;
     let elapsed_ms = started.elapsed().as_millis() as i64;
 
     match out {
-        Ok(Ok(rs)) => result_set_to_resp3(&rs, elapsed_ms),
+        Ok(Ok(rs)) => result_set_to_resp3(rs, elapsed_ms, row_limit),
         Ok(Err(e)) => Resp3Value::Verbatim("txt".into(), format!("Cypher error: {e}").into_bytes()),
-        Err(_join_err) => err("ERR internal join error running Cypher"),
+        Err(_pool_err) => err("ERR internal pool error running Cypher"),
     }
 }
 
-fn result_set_to_resp3(rs: &nexus_core::executor::ResultSet, execution_time_ms: i64) -> Resp3Value {
+fn result_set_to_resp3(
+    mut rs: nexus_core::executor::ResultSet,
+    execution_time_ms: i64,
+    row_limit: usize,
+) -> Resp3Value {
+    let truncated = cut_rows(&mut rs.rows, row_limit);
     let columns = Resp3Value::Array(
         rs.columns
             .iter()
@@ -130,7 +145,7 @@ fn result_set_to_resp3(rs: &nexus_core::executor::ResultSet, execution_time_ms:
         Resp3Value::bulk("rows"),
         Resp3Value::Integer(rs.rows.len() as i64),
     )]);
-    Resp3Value::Map(vec![
+    let mut entries = vec![
         (Resp3Value::bulk("columns"), columns),
         (Resp3Value::bulk("rows"), rows),
         (Resp3Value::bulk("stats"), stats),
@@ -138,7 +153,17 @@ fn result_set_to_resp3(rs: &nexus_core::executor::ResultSet, execution_time_ms:
             Resp3Value::bulk("execution_time_ms"),
             Resp3Value::Integer(execution_time_ms),
         ),
-    ])
+    ];
+    if truncated {
+        entries.push((
+            Resp3Value::bulk("truncated"),
+            Resp3Value::Map(vec![(
+                Resp3Value::bulk("row_limit"),
+                Resp3Value::Integer(row_limit as i64),
+            )]),
+        ));
+    }
+    Resp3Value::Map(entries)
 }
 
 fn json_to_resp3(v: &serde_json::Value) -> Resp3Value {
@@ -156,11 +181,20 @@ fn json_to_resp3(v: &serde_json::Value) -> Resp3Value {
         }
         serde_json::Value::String(s) => Resp3Value::bulk(s.clone()),
         serde_json::Value::Array(arr) => Resp3Value::Array(arr.iter().map(json_to_resp3).collect()),
-        serde_json::Value::Object(obj) => Resp3Value::Map(
-            obj.iter()
-                .map(|(k, v)| (Resp3Value::bulk(k.clone()), json_to_resp3(v)))
-                .collect(),
-        ),
+        serde_json::Value::Object(obj) => {
+            // BYTES go out raw in a binary-safe BulkString.
+            if obj.len() == 1
+                && obj.get("_bytes").is_some_and(serde_json::Value::is_string)
+                && let Some(raw) = nexus_core::value::NexusValue::from_json(v).as_bytes()
+            {
+                return Resp3Value::BulkString(raw.to_vec());
+            }
+            Resp3Value::Map(
+                obj.iter()
+                    .map(|(k, v)| (Resp3Value::bulk(k.clone()), json_to_resp3(v)))
+                    .collect(),
+            )
+        }
     }
 }
 
@@ -288,6 +322,32 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn cypher_cuts_rows_at_the_default_row_limit() {
+        let mut s = session();
+        Arc::get_mut(&mut s.server)
+            .unwrap()
+            .set_result_limits(crate::config::ResultLimitConfig {
+                default_max_rows: 2,
+                ..Default::default()
+            });
+        let args = vec![
+            Resp3Value::bulk("CYPHER"),
+            Resp3Value::bulk("UNWIND range(1, 5) AS i RETURN i"),
+        ];
+        let pairs = expect_map(cypher(&s, &args).await);
+        match lookup(&pairs, "rows") {
+            Resp3Value::Array(rows) => assert_eq!(rows.len(), 2),
+            other => panic!("expected rows Array, got {other:?}"),
+        }
+        match lookup(&pairs, "truncated") {
+            Resp3Value::Map(p) => {
+                assert_eq!(lookup(p, "row_limit"), &Resp3Value::Integer(2));
+            }
+            other => panic!("expected truncated Map, got {other:?}"),
+        }
+    }
+
     #[test]
     fn json_primitives_lower_to_expected_variants() {
         assert_eq!(json_to_resp3(&serde_json::json!(null)), Resp3Value::Null);
@@ -318,4 +378,13 @@ mod tests {
             other => panic!("expected Map, got {other:?}"),
         }
     }
+
+    #[test]
+    fn json_bytes_become_raw_bulk_string() {
+        let v = serde_json::json!({"_bytes": "/wD+"});
+        assert_eq!(
+            json_to_resp3(&v),
+            Resp3Value::BulkString(vec![0xFF, 0x00, 0xFE])
+        );
+    }
 }
@@ -4,14 +4,19 @@
 //! wire type and the engine's internal `serde_json` representation goes
 //! through these helpers so error shapes and UTF-8 / finite-number checks
 //! stay consistent.
+//!
+//! BYTES travel raw: [`NexusValue::Bytes`] maps to the engine's
+//! `{"_bytes": "<base64>"}` shape and back, so RPC clients never see or
+//! produce base64.
 
 use crate::protocol::rpc::NexusValue;
+use nexus_core::value::{MAX_ELEMENT_BYTES, NexusValue as CoreValue};
+use std::sync::Arc;
 
 /// Convert a [`NexusValue`] into the corresponding `serde_json::Value`.
 ///
-/// Bytes are interpreted as UTF-8 text; non-UTF-8 bytes are rejected so a
-/// value can never silently turn into an encoding-dependent string.
-/// Non-finite [`f64`] values (NaN, +/-inf) are rejected because JSON has
+/// Bytes become a BYTES value; payloads above the 64 MiB per-value cap
+/// are rejected. Non-finite [`f64`] values (NaN, +/-inf) are rejected because JSON has
 /// no spelling for them.
 pub fn nexus_to_json(value: NexusValue) -> Result<serde_json::Value, String> {
     match value {
@@ -24,9 +29,13 @@ pub fn nexus_to_json(value: NexusValue) -> Result<serde_json::Value, String> {
             Ok(serde_json::Value::Number(n))
         }
         NexusValue::Bytes(b) => {
-            let s = String::from_utf8(b)
-                .map_err(|_| "ERR Bytes value must be valid UTF-8".to_string())?;
-            Ok(serde_json::Value::String(s))
+            if b.len() > MAX_ELEMENT_BYTES {
+                return Err(format!(
+                    "ERR_BYTES_TOO_LARGE: {} bytes exceeds {MAX_ELEMENT_BYTES}-byte per-property cap",
+                    b.len()
+                ));
+            }
+            Ok(CoreValue::Bytes(Arc::from(b)).into_json())
         }
         NexusValue::Str(s) => Ok(serde_json::Value::String(s)),
         NexusValue::Array(items) => items
@@ -50,8 +59,9 @@ pub fn nexus_to_json(value: NexusValue) -> Result<serde_json::Value, String> {
 
 /// Convert a `serde_json::Value` into the matching [`NexusValue`].
 /// Integer-fitting numbers become [`NexusValue::Int`]; the rest of the
-/// variants map 1:1. Numbers larger than `i64::MAX` survive as strings
-/// so no precision is silently lost.
+/// variants map 1:1, except that a BYTES value becomes raw
+/// [`NexusValue::Bytes`]. Numbers larger than `i64::MAX` survive as
+/// strings so no precision is silently lost.
 pub fn json_to_nexus(value: serde_json::Value) -> NexusValue {
     match value {
         serde_json::Value::Null => NexusValue::Null,
@@ -73,11 +83,20 @@ pub fn json_to_nexus(value: serde_json::Value) -> NexusValue {
         serde_json::Value::Array(items) => {
             NexusValue::Array(items.into_iter().map(json_to_nexus).collect())
         }
-        serde_json::Value::Object(obj) => NexusValue::Map(
-            obj.into_iter()
-                .map(|(k, v)| (NexusValue::Str(k), json_to_nexus(v)))
-                .collect(),
-        ),
+        serde_json::Value::Object(obj) => {
+            if obj.len() == 1
+                && obj.get("_bytes").is_some_and(serde_json::Value::is_string)
+                && let Some(raw) =
+                    CoreValue::from_json(&serde_json::Value::Object(obj.clone())).as_bytes()
+            {
+                return NexusValue::Bytes(raw.to_vec());
+            }
+            NexusValue::Map(
+                obj.into_iter()
+                    .map(|(k, v)| (NexusValue::Str(k), json_to_nexus(v)))
+                    .collect(),
+            )
+        }
     }
 }
 
@@ -126,7 +145,7 @@ mod tests {
         );
         assert_eq!(
             nexus_to_json(NexusValue::Bytes(b"xyz".to_vec())).unwrap(),
-            serde_json::json!("xyz")
+            serde_json::json!({"_bytes": "eHl6"})
         );
     }
 
@@ -139,9 +158,15 @@ mod tests {
     }
 
     #[test]
-    fn nexus_to_json_bytes_not_utf8_rejected() {
-        let err = nexus_to_json(NexusValue::Bytes(vec![0xFF, 0xFE])).unwrap_err();
-        assert!(err.contains("UTF-8"));
+    fn bytes_round_trip_raw() {
+        let raw = vec![0xFF, 0x00, 0xFE];
+        let as_json = nexus_to_json(NexusValue::Bytes(raw.clone())).unwrap();
+        assert_eq!(as_json, serde_json::json!({"_bytes": "/wD+"}));
+        assert_eq!(json_to_nexus(as_json), NexusValue::Bytes(raw));
+
+        // Not valid base64, so it stays a plain map.
+        let map = serde_json::json!({"_bytes": "not base64!"});
+        assert!(matches!(json_to_nexus(map), NexusValue::Map(_)));
     }
 
     #[test]
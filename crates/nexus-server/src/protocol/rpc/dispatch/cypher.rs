@@ -21,8 +21,13 @@
 //!   rows:              Array<Array<NexusValue>>,
 //!   stats:             Map { rows: Int },
 //!   execution_time_ms: Int,
+//!   truncated:         Map { row_limit: Int },  // only when rows were cut
 //! }
 //! ```
+//!
+//! Rows are limited like the REST endpoint's: RPC sessions carry no
+//! user, so every query gets the server's default row limit (see
+//! [`crate::api::cypher::row_limit`]).
 
 use std::collections::HashMap;
 use std::time::Instant;
@@ -31,6 +36,9 @@ use nexus_core::executor::Query;
 use nexus_core::executor::parser::{Clause, CypherParser, CypherQuery};
 
 use crate::api::cypher::routing::needs_engine_interception;
+use crate::api::cypher::row_limit::{
+    apply_row_limit, cut_rows, execution_limit, resolve_row_limit,
+};
 use crate::api::cypher::{
     CypherResponse, execute_api_key_commands, execute_database_commands,
     execute_query_management_commands, execute_user_commands,
@@ -81,6 +89,7 @@ async fn dispatch_admin_if_any(
     state: &RpcSession,
     ast: &CypherQuery,
     started: Instant,
+    row_limit: usize,
 ) -> Option<Result<NexusValue, String>> {
     let has_db = ast.clauses.iter().any(|c| {
         matches!(
@@ -146,7 +155,10 @@ async fn dispatch_admin_if_any(
         None
     };
 
-    resp.map(|r| Ok(cypher_response_to_nexus(r)))
+    resp.map(|mut r| {
+        apply_row_limit(&mut r, row_limit);
+        Ok(cypher_response_to_nexus(r))
+    })
 }
 
 /// Convert a REST `CypherResponse` (`{columns, rows: Vec<serde_json::Value>,
@@ -166,6 +178,9 @@ fn cypher_response_to_nexus(resp: CypherResponse) -> NexusValue {
         execution_time_ms,
         error,
         notifications,
+        truncated,
+        constraint_violation,
+        ..
     } = resp;
 
     let columns_val = NexusValue::Array(columns.into_iter().map(NexusValue::Str).collect());
@@ -196,9 +211,18 @@ fn cypher_response_to_nexus(resp: CypherResponse) -> NexusValue {
             NexusValue::Int(execution_time_ms as i64),
         ),
     ];
+    if let Some(truncated) = truncated {
+        entries.push(truncation_entry(truncated.row_limit));
+    }
     if let Some(e) = error {
         entries.push((NexusValue::Str("error".into()), NexusValue::Str(e)));
     }
+    if let Some(details) = constraint_violation.and_then(|v| serde_json::to_value(v).ok()) {
+        entries.push((
+            NexusValue::Str("constraint_violation".into()),
+            json_to_nexus(details),
+        ));
+    }
     // Mirror the REST envelope's `notifications` field on the RPC
     // wire so SDKs see an identical shape on both transports. Empty
     // vec is omitted to keep the hot-path payload compact.
@@ -249,7 +273,8 @@ async fn execute_query(
         Err(e) => return Err(format!("Parse error: {e}")),
     };
 
-    if let Some(admin_result) = dispatch_admin_if_any(state, &ast, started).await {
+    let row_limit = resolve_row_limit(&state.server, None, None).await;
+    if let Some(admin_result) = dispatch_admin_if_any(state, &ast, started, row_limit).await {
         return admin_result;
     }
 
@@ -274,7 +299,7 @@ async fn execute_query(
         // carve-out (`api::cypher::execute::handler`) — a pure autocommit
         // read (`routing::is_read_only`) with no open explicit transaction
         // on the "default" session runs through a cloned `Engine::executor`
-        // snapshot in `spawn_blocking` instead of the exclusive
+        // snapshot on the query pool instead of the exclusive
         // `engine.write().await` every other clause here still needs. See
         // that handler's inline comments for the freshness argument
         // (`Engine::refresh_executor` keeps `Engine::executor` current
@@ -305,28 +330,33 @@ async fn execute_query(
                     params: params.clone(),
                 };
                 // phase9_store-lock-read-concurrency §1 — measure the
-                // `spawn_blocking` queue wait (time from scheduling to
-                // the closure's first instruction) and the executor's
-                // own wall time separately, so a busy blocking-thread
-                // pool shows up distinctly from slow query execution.
+                // query pool queue wait (time from scheduling to the
+                // closure's first instruction) and the executor's own
+                // wall time separately, so a busy pool shows up
+                // distinctly from slow query execution.
                 let scheduled_at = std::time::Instant::now();
-                let out = tokio::task::spawn_blocking(move || {
-                    if nexus_core::perf_probe::enabled() {
-                        nexus_core::perf_probe::SPAWN_BLOCKING_QUEUE.record(scheduled_at.elapsed());
-                    }
-                    let exec_start = std::time::Instant::now();
-                    let result = lock_free_executor.execute(&q);
-                    if nexus_core::perf_probe::enabled() {
-                        nexus_core::perf_probe::EXECUTOR_EXECUTE.record(exec_start.elapsed());
-                    }
-                    result
-                })
-                .await;
+                let out = state
+                    .server
+                    .query_pool
+                    .run(move || {
+                        if nexus_core::perf_probe::enabled() {
+                            nexus_core::perf_probe::SPAWN_BLOCKING_QUEUE
+                                .record(scheduled_at.elapsed());
+                        }
+                        let exec_start = std::time::Instant::now();
+                        let _rows = execution_limit(row_limit);
+                        let result = lock_free_executor.execute(&q);
+                        if nexus_core::perf_probe::enabled() {
+                            nexus_core::perf_probe::EXECUTOR_EXECUTE.record(exec_start.elapsed());
+                        }
+                        result
+                    })
+                    .await;
                 let elapsed_ms = started.elapsed().as_millis() as i64;
                 return match out {
-                    Ok(Ok(rs)) => Ok(result_set_to_nexus(rs, elapsed_ms)),
+                    Ok(Ok(rs)) => Ok(result_set_to_nexus(rs, elapsed_ms, row_limit)),
                     Ok(Err(e)) => Err(format!("Cypher error: {e}")),
-                    Err(join_err) => Err(format!("ERR internal join error: {join_err}")),
+                    Err(pool_err) => Err(format!("ERR internal pool error: {pool_err}")),
                 };
             }
             // Else: an explicit transaction is open on the "default"
@@ -342,11 +372,12 @@ async fn execute_query(
         let engine_arc = state.server.engine.clone();
         let result = {
             let mut engine = engine_arc.write().await;
+            let _rows = execution_limit(row_limit);
             engine.execute_cypher_ast_with_params(&ast, &query, params)
         };
         let elapsed_ms = started.elapsed().as_millis() as i64;
         return match result {
-            Ok(rs) => Ok(result_set_to_nexus(rs, elapsed_ms)),
+            Ok(rs) => Ok(result_set_to_nexus(rs, elapsed_ms, row_limit)),
             Err(e) => Err(format!("Cypher error: {e}")),
         };
     }
@@ -357,19 +388,31 @@ async fn execute_query(
         params,
     };
 
-    let out = tokio::task::spawn_blocking(move || executor.execute(&q)).await;
+    let out = state
+        .server
+        .query_pool
+        .run(move || {
+            let _rows = execution_limit(row_limit);
+            executor.execute(&q)
+        })
+        .await;
     let elapsed_ms = started.elapsed().as_millis() as i64;
 
     match out {
-        Ok(Ok(rs)) => Ok(result_set_to_nexus(rs, elapsed_ms)),
+        Ok(Ok(rs)) => Ok(result_set_to_nexus(rs, elapsed_ms, row_limit)),
         Ok(Err(e)) => Err(format!("Cypher error: {e}")),
-        Err(join_err) => Err(format!("ERR internal join error: {join_err}")),
+        Err(pool_err) => Err(format!("ERR internal pool error: {pool_err}")),
     }
 }
 
 /// Convert a `ResultSet` into the canonical NexusValue envelope described
-/// in the module docs.
-fn result_set_to_nexus(rs: nexus_core::executor::ResultSet, elapsed_ms: i64) -> NexusValue {
+/// in the module docs, keeping at most `row_limit` rows.
+fn result_set_to_nexus(
+    mut rs: nexus_core::executor::ResultSet,
+    elapsed_ms: i64,
+    row_limit: usize,
+) -> NexusValue {
+    let truncated = cut_rows(&mut rs.rows, row_limit);
     let columns = NexusValue::Array(rs.columns.into_iter().map(NexusValue::Str).collect());
     let row_count = rs.rows.len() as i64;
     let rows = NexusValue::Array(
@@ -383,7 +426,7 @@ fn result_set_to_nexus(rs: nexus_core::executor::ResultSet, elapsed_ms: i64) ->
         NexusValue::Int(row_count),
     )]);
 
-    NexusValue::Map(vec![
+    let mut entries = vec![
         (NexusValue::Str("columns".into()), columns),
         (NexusValue::Str("rows".into()), rows),
         (NexusValue::Str("stats".into()), stats),
@@ -391,7 +434,22 @@ fn result_set_to_nexus(rs: nexus_core::executor::ResultSet, elapsed_ms: i64) ->
             NexusValue::Str("execution_time_ms".into()),
             NexusValue::Int(elapsed_ms),
         ),
-    ])
+    ];
+    if truncated {
+        entries.push(truncation_entry(row_limit));
+    }
+    NexusValue::Map(entries)
+}
+
+/// The envelope's `truncated` entry for rows cut at `row_limit`.
+fn truncation_entry(row_limit: usize) -> (NexusValue, NexusValue) {
+    (
+        NexusValue::Str("truncated".into()),
+        NexusValue::Map(vec![(
+            NexusValue::Str("row_limit".into()),
+            NexusValue::Int(row_limit as i64),
+        )]),
+    )
 }
 
 /// Convert a client-supplied parameter map (`NexusValue::Map`) into the
@@ -536,6 +594,44 @@ mod tests {
         assert_eq!(lookup(&stats, "rows").as_int(), Some(1));
     }
 
+    #[tokio::test]
+    async fn cypher_cuts_rows_at_the_default_row_limit() {
+        let mut s = session();
+        Arc::get_mut(&mut s.server)
+            .unwrap()
+            .set_result_limits(crate::config::ResultLimitConfig {
+                default_max_rows: 2,
+                ..Default::default()
+            });
+        let out = run(
+            &s,
+            "CYPHER",
+            &[NexusValue::Str("UNWIND range(1, 5) AS i RETURN i".into())],
+        )
+        .await
+        .unwrap();
+        let pairs = expect_map(out);
+        match lookup(&pairs, "rows") {
+            NexusValue::Array(rows) => assert_eq!(rows.len(), 2),
+            other => panic!("expected rows Array, got {other:?}"),
+        }
+        let truncated = match lookup(&pairs, "truncated") {
+            NexusValue::Map(p) => p.clone(),
+            other => panic!("expected truncated Map, got {other:?}"),
+        };
+        assert_eq!(lookup(&truncated, "row_limit").as_int(), Some(2));
+
+        let out = run(
+            &s,
+            "CYPHER",
+            &[NexusValue::Str("UNWIND range(1, 2) AS i RETURN i".into())],
+        )
+        .await
+        .unwrap();
+        let pairs = expect_map(out);
+        assert!(!pairs.iter().any(|(k, _)| k.as_str() == Some("truncated")));
+    }
+
     #[tokio::test]
     async fn cypher_accepts_parameter_map_as_second_arg() {
         let s = session();
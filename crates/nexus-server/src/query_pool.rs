@@ -0,0 +1,359 @@
+//! Dedicated thread pool for Cypher execution.
+//!
+//! Query execution is CPU work that blocks for as long as the query
+//! runs. Sending it to tokio's blocking pool shares those threads with
+//! every other `spawn_blocking` caller (database admin, schema reads,
+//! file I/O) and lets the blocking pool grow to hundreds of threads
+//! under an analytics burst, all competing with the runtime's workers
+//! for the same cores. [`QueryPool`] runs queries on a fixed number of
+//! its own threads instead, so a backlog of heavy queries waits in the
+//! pool's queue rather than starving request handling.
+//!
+//! The `/cypher` handler and the RPC / RESP3 `CYPHER` commands submit
+//! through [`QueryPool::run`]. Workers are started on demand, up to
+//! [`QueryPoolConfig::threads`], and live as long as the pool.
+//!
+//! Concurrency is already bounded upstream by the admission queue
+//! ([`crate::middleware::admission`]); the pool does not reject work,
+//! it only decides where it runs. [`QueryPool::metrics`] reports queue
+//! depth and wait time, exported by `GET /prometheus` as
+//! `nexus_query_pool_*`.
+
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use parking_lot::{Condvar, Mutex};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Sizing of the query pool. Parsed from `server.query_pool` in
+/// `config.yml`; `NEXUS_QUERY_POOL_THREADS` overrides `threads`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct QueryPoolConfig {
+    /// Worker threads. Defaults to the number of CPUs; `0` is treated
+    /// as `1`.
+    pub threads: usize,
+}
+
+impl Default for QueryPoolConfig {
+    fn default() -> Self {
+        Self {
+            threads: std::thread::available_parallelism()
+                .map(std::num::NonZero::get)
+                .unwrap_or(4),
+        }
+    }
+}
+
+/// Errors surfaced by [`QueryPool::run`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QueryPoolError {
+    /// The task panicked on its worker thread.
+    #[error("query task panicked")]
+    Panicked,
+    /// No worker thread could be started to run the task.
+    #[error("query pool has no worker thread: {0}")]
+    Unavailable(String),
+}
+
+type Task = Box<dyn FnOnce() + Send + 'static>;
+
+struct Queued {
+    task: Task,
+    queued_at: Instant,
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<Queued>,
+    workers: usize,
+    idle: usize,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    work_ready: Condvar,
+    threads: usize,
+    queued: AtomicU64,
+    peak_queued: AtomicU64,
+    active: AtomicU64,
+    submitted: AtomicU64,
+    completed: AtomicU64,
+    panicked: AtomicU64,
+    wait_micros_total: AtomicU64,
+}
+
+/// Fixed-size pool of threads that run query execution off the tokio
+/// runtime. Dropping the pool lets its workers finish the queue and
+/// exit.
+pub struct QueryPool {
+    shared: Arc<Shared>,
+}
+
+impl std::fmt::Debug for QueryPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryPool")
+            .field("threads", &self.shared.threads)
+            .field("queued", &self.shared.queued.load(Ordering::Relaxed))
+            .field("active", &self.shared.active.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl QueryPool {
+    /// Build a pool sized by `cfg`. No thread starts until the first
+    /// task arrives.
+    #[must_use]
+    pub fn new(cfg: QueryPoolConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State::default()),
+                work_ready: Condvar::new(),
+                threads: cfg.threads.max(1),
+                queued: AtomicU64::new(0),
+                peak_queued: AtomicU64::new(0),
+                active: AtomicU64::new(0),
+                submitted: AtomicU64::new(0),
+                completed: AtomicU64::new(0),
+                panicked: AtomicU64::new(0),
+                wait_micros_total: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Pool with one thread per CPU.
+    #[must_use]
+    pub fn with_defaults() -> Self {
+        Self::new(QueryPoolConfig::default())
+    }
+
+    /// Run `f` on a pool thread and wait for its result. A panic in
+    /// `f` is caught and reported as [`QueryPoolError::Panicked`],
+    /// like a `JoinError` from `spawn_blocking`.
+    pub async fn run<F, R>(&self, f: F) -> Result<R, QueryPoolError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let shared = Arc::clone(&self.shared);
+        self.submit(Box::new(move || {
+            let out = std::panic::catch_unwind(AssertUnwindSafe(f));
+            if out.is_err() {
+                shared.panicked.fetch_add(1, Ordering::Relaxed);
+            }
+            // The caller may have gone away (client disconnect); the
+            // result is simply dropped then.
+            let _ = tx.send(out);
+        }))?;
+        match rx.await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) | Err(_) => Err(QueryPoolError::Panicked),
+        }
+    }
+
+    /// Queue `task`, starting another worker if none is idle and the
+    /// pool is not full yet.
+    fn submit(&self, task: Task) -> Result<(), QueryPoolError> {
+        let mut state = self.shared.state.lock();
+        state.queue.push_back(Queued {
+            task,
+            queued_at: Instant::now(),
+        });
+        let depth = self.shared.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        self.shared.peak_queued.fetch_max(depth, Ordering::Relaxed);
+        self.shared.submitted.fetch_add(1, Ordering::Relaxed);
+
+        if state.idle > 0 || state.workers >= self.shared.threads {
+            self.shared.work_ready.notify_one();
+            return Ok(());
+        }
+        let index = state.workers;
+        let shared = Arc::clone(&self.shared);
+        let spawned = std::thread::Builder::new()
+            .name(format!("nexus-query-{index}"))
+            .spawn(move || worker_loop(&shared));
+        match spawned {
+            Ok(_) => {
+                state.workers += 1;
+                Ok(())
+            }
+            Err(e) if state.workers > 0 => {
+                // The running workers will get to the task.
+                tracing::warn!("query pool could not start worker {index}: {e}");
+                Ok(())
+            }
+            Err(e) => {
+                state.queue.pop_back();
+                self.shared.queued.fetch_sub(1, Ordering::Relaxed);
+                Err(QueryPoolError::Unavailable(e.to_string()))
+            }
+        }
+    }
+
+    /// Observability snapshot.
+    #[must_use]
+    pub fn metrics(&self) -> QueryPoolMetrics {
+        let shared = &self.shared;
+        QueryPoolMetrics {
+            threads: shared.threads,
+            workers: shared.state.lock().workers,
+            active: shared.active.load(Ordering::Relaxed),
+            queued: shared.queued.load(Ordering::Relaxed),
+            peak_queued: shared.peak_queued.load(Ordering::Relaxed),
+            submitted_total: shared.submitted.load(Ordering::Relaxed),
+            completed_total: shared.completed.load(Ordering::Relaxed),
+            panicked_total: shared.panicked.load(Ordering::Relaxed),
+            wait_micros_total: shared.wait_micros_total.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for QueryPool {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+impl Drop for QueryPool {
+    fn drop(&mut self) {
+        self.shared.state.lock().shutdown = true;
+        self.shared.work_ready.notify_all();
+    }
+}
+
+fn worker_loop(shared: &Shared) {
+    loop {
+        let next = {
+            let mut state = shared.state.lock();
+            loop {
+                if let Some(next) = state.queue.pop_front() {
+                    break next;
+                }
+                if state.shutdown {
+                    state.workers -= 1;
+                    return;
+                }
+                state.idle += 1;
+                shared.work_ready.wait(&mut state);
+                state.idle -= 1;
+            }
+        };
+        shared.queued.fetch_sub(1, Ordering::Relaxed);
+        shared.wait_micros_total.fetch_add(
+            next.queued_at.elapsed().as_micros() as u64,
+            Ordering::Relaxed,
+        );
+        shared.active.fetch_add(1, Ordering::Relaxed);
+        (next.task)();
+        shared.active.fetch_sub(1, Ordering::Relaxed);
+        shared.completed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Read-only metrics snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryPoolMetrics {
+    /// Configured worker threads
+    pub threads: usize,
+    /// Worker threads started so far
+    pub workers: usize,
+    /// Tasks running right now
+    pub active: u64,
+    /// Tasks waiting for a worker
+    pub queued: u64,
+    /// Deepest the queue has been since startup
+    pub peak_queued: u64,
+    /// Tasks submitted since startup
+    pub submitted_total: u64,
+    /// Tasks finished since startup, panicked ones included
+    pub completed_total: u64,
+    /// Tasks that panicked
+    pub panicked_total: u64,
+    /// Sum of the time tasks spent queued, in microseconds
+    pub wait_micros_total: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn pool(threads: usize) -> QueryPool {
+        QueryPool::new(QueryPoolConfig { threads })
+    }
+
+    #[tokio::test]
+    async fn runs_tasks_off_the_runtime_threads() {
+        let pool = pool(2);
+        let name = pool
+            .run(|| std::thread::current().name().map(str::to_string))
+            .await
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("nexus-query-0"));
+
+        let m = pool.metrics();
+        assert_eq!(m.workers, 1);
+        assert_eq!(m.submitted_total, 1);
+        assert_eq!(m.queued, 0);
+    }
+
+    #[tokio::test]
+    async fn queues_beyond_the_thread_count() {
+        let pool = Arc::new(pool(1));
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let blocker = {
+            let pool = Arc::clone(&pool);
+            tokio::spawn(async move { pool.run(move || release_rx.recv().unwrap()).await })
+        };
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while pool.metrics().active < 1 {
+            assert!(Instant::now() < deadline, "blocker never started");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let waiters: Vec<_> = (0..3)
+            .map(|i| {
+                let pool = Arc::clone(&pool);
+                tokio::spawn(async move { pool.run(move || i).await })
+            })
+            .collect();
+
+        while pool.metrics().queued < 3 {
+            assert!(Instant::now() < deadline, "tasks never queued");
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(pool.metrics().workers, 1);
+        assert_eq!(pool.metrics().active, 1);
+
+        release_tx.send(()).unwrap();
+        blocker.await.unwrap().unwrap();
+        let mut results = Vec::new();
+        for waiter in waiters {
+            results.push(waiter.await.unwrap().unwrap());
+        }
+        results.sort_unstable();
+        assert_eq!(results, vec![0, 1, 2]);
+
+        let m = pool.metrics();
+        assert_eq!(m.queued, 0);
+        assert!(m.peak_queued >= 3);
+        assert_eq!(m.submitted_total, 4);
+    }
+
+    #[tokio::test]
+    async fn a_panicking_task_does_not_take_the_worker_down() {
+        let pool = pool(1);
+        let err = pool.run(|| -> u32 { panic!("boom") }).await.unwrap_err();
+        assert_eq!(err, QueryPoolError::Panicked);
+        assert_eq!(pool.run(|| 7).await.unwrap(), 7);
+
+        let m = pool.metrics();
+        assert_eq!(m.panicked_total, 1);
+        assert_eq!(m.workers, 1);
+    }
+}
@@ -102,6 +102,7 @@ async fn knn_traverse_rejects_injection_in_label() {
         expand: vec![],
         r#where: None,
         limit: 10,
+        rerank: None,
     };
 
     let response = knn_traverse(State(server), Json(request)).await.0;
@@ -133,6 +134,7 @@ async fn knn_traverse_accepts_well_formed_label() {
         expand: vec![],
         r#where: None,
         limit: 10,
+        rerank: None,
     };
 
     let response = knn_traverse(State(server), Json(request)).await.0;
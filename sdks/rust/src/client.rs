@@ -447,7 +447,8 @@ fn value_to_nexus(v: Value) -> NexusValue {
 }
 
 /// Decode the CYPHER reply envelope (`{columns, rows, execution_time_ms, error}`)
-/// into `QueryResult`.
+/// into `QueryResult`. Errors carrying `constraint_violation` details
+/// become [`NexusError::ConstraintViolation`].
 fn cypher_envelope_to_query_result(value: NexusValue) -> Result<QueryResult> {
     let json = nexus_to_json(&value);
     let obj = json.as_object().ok_or_else(|| {
@@ -472,6 +473,15 @@ fn cypher_envelope_to_query_result(value: NexusValue) -> Result<QueryResult> {
     let error = obj.get("error").and_then(|v| v.as_str()).map(String::from);
 
     if let Some(msg) = error.clone() {
+        if let Some(details) = obj
+            .get("constraint_violation")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+        {
+            return Err(NexusError::ConstraintViolation {
+                message: msg,
+                details: Box::new(details),
+            });
+        }
         return Err(NexusError::Api {
             message: msg,
             status: 0,
@@ -133,6 +133,266 @@ pub struct DeleteRelResponse {
     pub error: Option<String>,
 }
 
+/// A named vector (KNN) index
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorIndexInfo {
+    /// Index name
+    pub name: String,
+    /// Label whose nodes are indexed
+    pub label: String,
+    /// Property holding the embeddings
+    pub property: String,
+    /// Embedding length
+    pub dimensions: usize,
+    /// Distance function: `cosine` or `euclidean`
+    pub metric: String,
+    /// Vectors currently indexed
+    pub vectors: u64,
+    /// Running average search latency in microseconds
+    pub avg_search_time_us: f64,
+    /// Searches served by the HNSW graph
+    #[serde(default)]
+    pub approximate_searches: u64,
+    /// Searches served by an exact scan
+    #[serde(default)]
+    pub exact_searches: u64,
+    /// Searches that sampled both to measure recall
+    #[serde(default)]
+    pub recall_samples: u64,
+    /// Mean recall of the HNSW graph over those samples
+    #[serde(default)]
+    pub avg_recall: Option<f64>,
+}
+
+/// Create vector index request
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateVectorIndexRequest {
+    /// Index name
+    pub name: String,
+    /// Label whose nodes are indexed
+    pub label: String,
+    /// Property holding the embeddings
+    pub property: String,
+    /// Embedding length
+    pub dimensions: usize,
+    /// `cosine` (server default) or `euclidean`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metric: Option<String>,
+}
+
+/// Response carrying one vector index
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorIndexResponse {
+    /// Success message
+    pub message: String,
+    /// The index, if found or created
+    pub index: Option<VectorIndexInfo>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// List vector indexes response
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListVectorIndexesResponse {
+    /// Vector indexes, by name
+    pub indexes: Vec<VectorIndexInfo>,
+}
+
+/// Drop vector index response
+#[derive(Debug, Clone, Deserialize)]
+pub struct DropVectorIndexResponse {
+    /// Success message
+    pub message: String,
+    /// Whether the index existed
+    pub dropped: bool,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One node's embedding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeVector {
+    /// Node ID
+    pub node_id: u64,
+    /// Embedding, of the index's dimensions
+    pub vector: Vec<f32>,
+}
+
+/// A vector the server rejected
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorFailure {
+    /// Node ID
+    pub node_id: u64,
+    /// Why the write failed
+    pub error: String,
+}
+
+/// Upsert vectors response
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpsertVectorsResponse {
+    /// Success message
+    pub message: String,
+    /// Vectors written
+    pub upserted: usize,
+    /// Vectors rejected, with the reason
+    #[serde(default)]
+    pub failed: Vec<VectorFailure>,
+    /// Error message if the request failed as a whole
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Delete vector response
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeleteVectorResponse {
+    /// Success message
+    pub message: String,
+    /// Whether the node was indexed
+    pub removed: bool,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One nearest-neighbour hit
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorSearchHit {
+    /// Node ID
+    pub node_id: u64,
+    /// Similarity score, higher is closer
+    pub score: f32,
+}
+
+/// How a vector search is served
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VectorSearchOptions {
+    /// `auto` (server default), `approximate` or `exact`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// HNSW candidate list size for approximate searches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ef_search: Option<usize>,
+    /// Run both searches and report the approximate search's recall
+    pub measure_recall: bool,
+}
+
+/// Vector search response
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorSearchResponse {
+    /// Success message
+    pub message: String,
+    /// Hits, best first
+    pub results: Vec<VectorSearchHit>,
+    /// Search that served the hits: `approximate` or `exact`
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// Recall@k of the approximate search against the exact one, when
+    /// requested
+    #[serde(default)]
+    pub recall: Option<f64>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Start vector index migration request; unset fields keep the live
+/// index's value
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StartVectorMigrationRequest {
+    /// Property the migrated index reads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
+    /// Embedding length of the migrated index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<usize>,
+    /// `cosine` or `euclidean`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metric: Option<String>,
+}
+
+/// An in-progress vector index migration
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorIndexMigrationInfo {
+    /// The live index, still serving searches
+    pub source: VectorIndexInfo,
+    /// The index that replaces it when the migration completes
+    pub target: VectorIndexInfo,
+    /// Nodes in the live index the target does not hold yet
+    pub pending: u64,
+}
+
+/// Vector index migration response
+#[derive(Debug, Clone, Deserialize)]
+pub struct VectorMigrationResponse {
+    /// Success message
+    pub message: String,
+    /// The migration, if found or started
+    pub migration: Option<VectorIndexMigrationInfo>,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Abort vector index migration response
+#[derive(Debug, Clone, Deserialize)]
+pub struct AbortVectorMigrationResponse {
+    /// Success message
+    pub message: String,
+    /// Whether a migration was in progress
+    pub aborted: bool,
+    /// Error message if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One record of a bulk vector upload: the node, by id or external id,
+/// and its embedding
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkVectorRecord {
+    /// Node ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<u64>,
+    /// Prefixed external id (`str:doc-42`, `uuid:…`), instead of `node_id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Embedding, of the index's dimensions
+    pub vector: Vec<f32>,
+}
+
+/// A bulk upload line the server rejected
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkVectorFailure {
+    /// 1-based line number in the upload
+    pub line: usize,
+    /// Node ID, when the line named or resolved to one
+    pub node_id: Option<u64>,
+    /// Why the line was rejected
+    pub error: String,
+}
+
+/// Bulk vector upload response
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkVectorResponse {
+    /// Success message
+    pub message: String,
+    /// Non-blank lines read
+    pub lines: usize,
+    /// Vectors written and indexed
+    pub upserted: usize,
+    /// Engine writes the upload was applied in
+    pub batches: usize,
+    /// Lines rejected
+    pub failed_count: usize,
+    /// The first rejected lines
+    #[serde(default)]
+    pub failed: Vec<BulkVectorFailure>,
+    /// Error that stopped the upload early
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 impl NexusClient {
     /// Create a new node
     ///
@@ -547,4 +807,228 @@ impl NexusClient {
             error: None,
         })
     }
+
+    /// Create a named vector index over `label`'s `property` and fill it
+    /// from the nodes that already carry an embedding there.
+    ///
+    /// `metric` is `"cosine"` (default) or `"euclidean"`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use nexus_sdk::NexusClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), nexus_sdk::NexusError> {
+    /// # let client = NexusClient::new("http://localhost:15474")?;
+    /// let response = client
+    ///     .create_vector_index("docs", "Document", "embedding", 384, Some("cosine"))
+    ///     .await?;
+    /// tracing::info!("{}", response.message);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_vector_index(
+        &self,
+        name: impl Into<String>,
+        label: impl Into<String>,
+        property: impl Into<String>,
+        dimensions: usize,
+        metric: Option<&str>,
+    ) -> Result<VectorIndexResponse> {
+        let request = CreateVectorIndexRequest {
+            name: name.into(),
+            label: label.into(),
+            property: property.into(),
+            dimensions,
+            metric: metric.map(str::to_owned),
+        };
+        let url = self.vector_index_url(&[])?;
+        self.send_vector_index_request(self.get_client().post(url).json(&request))
+            .await
+    }
+
+    /// List every vector index with its vector count and search latency.
+    pub async fn list_vector_indexes(&self) -> Result<ListVectorIndexesResponse> {
+        let url = self.vector_index_url(&[])?;
+        self.send_vector_index_request(self.get_client().get(url))
+            .await
+    }
+
+    /// Show one vector index (`index: None` plus `error` when absent).
+    pub async fn get_vector_index(&self, name: &str) -> Result<VectorIndexResponse> {
+        let url = self.vector_index_url(&[name])?;
+        self.send_vector_index_request(self.get_client().get(url))
+            .await
+    }
+
+    /// Drop a vector index. The embeddings stay on the nodes.
+    pub async fn drop_vector_index(&self, name: &str) -> Result<DropVectorIndexResponse> {
+        let url = self.vector_index_url(&[name])?;
+        self.send_vector_index_request(self.get_client().delete(url))
+            .await
+    }
+
+    /// Store and index node embeddings, replacing earlier ones. Vectors
+    /// are applied one by one; rejected ones come back in `failed`.
+    pub async fn upsert_vectors(
+        &self,
+        name: &str,
+        vectors: Vec<NodeVector>,
+    ) -> Result<UpsertVectorsResponse> {
+        let url = self.vector_index_url(&[name, "vectors"])?;
+        let body = serde_json::json!({ "vectors": vectors });
+        self.send_vector_index_request(self.get_client().post(url).json(&body))
+            .await
+    }
+
+    /// Remove a node's embedding from the index and the node.
+    pub async fn delete_vector(&self, name: &str, node_id: u64) -> Result<DeleteVectorResponse> {
+        let node_id = node_id.to_string();
+        let url = self.vector_index_url(&[name, "vectors", &node_id])?;
+        self.send_vector_index_request(self.get_client().delete(url))
+            .await
+    }
+
+    /// The `k` indexed nodes closest to `vector`, best first.
+    pub async fn search_vectors(
+        &self,
+        name: &str,
+        vector: Vec<f32>,
+        k: usize,
+    ) -> Result<VectorSearchResponse> {
+        self.search_vectors_with(name, vector, k, VectorSearchOptions::default())
+            .await
+    }
+
+    /// [`NexusClient::search_vectors`] with an explicit search mode,
+    /// `ef_search` and optional recall sampling.
+    pub async fn search_vectors_with(
+        &self,
+        name: &str,
+        vector: Vec<f32>,
+        k: usize,
+        options: VectorSearchOptions,
+    ) -> Result<VectorSearchResponse> {
+        let url = self.vector_index_url(&[name, "search"])?;
+        let mut body = serde_json::to_value(&options)?;
+        body["vector"] = serde_json::json!(vector);
+        body["k"] = serde_json::json!(k);
+        self.send_vector_index_request(self.get_client().post(url).json(&body))
+            .await
+    }
+
+    /// Backfill embeddings through `POST /vectors/bulk`, sent as NDJSON
+    /// and applied by the server `batch_size` records at a time (server
+    /// default when `None`). Rejected lines come back in `failed`.
+    pub async fn bulk_upsert_vectors(
+        &self,
+        index: &str,
+        records: &[BulkVectorRecord],
+        batch_size: Option<usize>,
+    ) -> Result<BulkVectorResponse> {
+        let mut body = String::new();
+        for record in records {
+            body.push_str(&serde_json::to_string(record)?);
+            body.push('\n');
+        }
+        let mut url = self.get_base_url().join("/vectors/bulk")?;
+        url.query_pairs_mut().append_pair("index", index);
+        if let Some(batch_size) = batch_size {
+            url.query_pairs_mut()
+                .append_pair("batch_size", &batch_size.to_string());
+        }
+        let request_builder = self
+            .get_client()
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body);
+        self.send_vector_index_request(request_builder).await
+    }
+
+    /// `/schema/vector_indexes` followed by `segments`, each
+    /// percent-encoded so index names may contain any character.
+    /// Start migrating a vector index to new dimensions, metric or
+    /// property. Node writes feed both indexes until the migration is
+    /// completed or aborted; searches keep using the live one.
+    pub async fn start_vector_migration(
+        &self,
+        name: &str,
+        request: StartVectorMigrationRequest,
+    ) -> Result<VectorMigrationResponse> {
+        let url = self.vector_index_url(&[name, "migration"])?;
+        self.send_vector_index_request(self.get_client().post(url).json(&request))
+            .await
+    }
+
+    /// Show a vector index's in-progress migration and how many nodes
+    /// are left to re-embed.
+    pub async fn get_vector_migration(&self, name: &str) -> Result<VectorMigrationResponse> {
+        let url = self.vector_index_url(&[name, "migration"])?;
+        self.send_vector_index_request(self.get_client().get(url))
+            .await
+    }
+
+    /// Write re-embedded vectors, of the target's dimensions, to a
+    /// migration.
+    pub async fn upsert_migration_vectors(
+        &self,
+        name: &str,
+        vectors: Vec<NodeVector>,
+    ) -> Result<UpsertVectorsResponse> {
+        let url = self.vector_index_url(&[name, "migration", "vectors"])?;
+        let body = serde_json::json!({ "vectors": vectors });
+        self.send_vector_index_request(self.get_client().post(url).json(&body))
+            .await
+    }
+
+    /// Swap the migrated index in as the live one. The server refuses
+    /// while nodes are left to re-embed unless `force` is set.
+    pub async fn complete_vector_migration(
+        &self,
+        name: &str,
+        force: bool,
+    ) -> Result<VectorIndexResponse> {
+        let mut url = self.vector_index_url(&[name, "migration", "complete"])?;
+        if force {
+            url.query_pairs_mut().append_pair("force", "true");
+        }
+        self.send_vector_index_request(self.get_client().post(url))
+            .await
+    }
+
+    /// Abort a migration, leaving the live index unchanged.
+    pub async fn abort_vector_migration(&self, name: &str) -> Result<AbortVectorMigrationResponse> {
+        let url = self.vector_index_url(&[name, "migration"])?;
+        self.send_vector_index_request(self.get_client().delete(url))
+            .await
+    }
+
+    fn vector_index_url(&self, segments: &[&str]) -> Result<url::Url> {
+        let mut url = self.get_base_url().join("/schema/vector_indexes")?;
+        url.path_segments_mut()
+            .map_err(|_| NexusError::Configuration("base URL cannot have a path".to_string()))?
+            .extend(segments);
+        Ok(url)
+    }
+
+    async fn send_vector_index_request<T: serde::de::DeserializeOwned>(
+        &self,
+        request_builder: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let request_builder = self.add_auth_headers(request_builder)?;
+        let response = self.execute_with_retry(request_builder).await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(NexusError::Api {
+                message: error_text,
+                status: status.as_u16(),
+            })
+        }
+    }
 }
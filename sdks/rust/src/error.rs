@@ -29,6 +29,16 @@ pub enum NexusError {
         status: u16,
     },
 
+    /// Constraint violation, with the details needed to report the
+    /// offending value or retry as an update of `conflicting_id`
+    #[error("Constraint violation: {message}")]
+    ConstraintViolation {
+        /// Error message from API
+        message: String,
+        /// Structured details
+        details: Box<crate::models::ConstraintViolation>,
+    },
+
     /// Authentication error
     #[error("Authentication failed: {0}")]
     Authentication(String),
@@ -18,6 +18,35 @@ pub struct QueryResult {
     pub error: Option<String>,
 }
 
+/// Structured details of a constraint violation reported by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintViolation {
+    /// Server error message
+    pub message: String,
+    /// Constraint name, when the constraint was created with one
+    #[serde(default)]
+    pub constraint_name: Option<String>,
+    /// Constraint kind (`UNIQUENESS`, `NODE_KEY`, `PROPERTY_TYPE`, ...)
+    pub kind: String,
+    /// `NODE` or `RELATIONSHIP`
+    pub entity_type: String,
+    /// Labels (node constraints) or relationship type involved
+    #[serde(default)]
+    pub labels_or_types: Vec<String>,
+    /// Constrained properties
+    #[serde(default)]
+    pub properties: Vec<String>,
+    /// Id of the entity being written, when it already had one
+    #[serde(default)]
+    pub offending_id: Option<u64>,
+    /// Id of the existing entity the write collides with
+    #[serde(default)]
+    pub conflicting_id: Option<u64>,
+    /// Values that broke the constraint, keyed by property
+    #[serde(default)]
+    pub offending_values: HashMap<String, serde_json::Value>,
+}
+
 /// A single row in a query result (helper for accessing row values)
 #[derive(Debug, Clone)]
 pub struct Row {